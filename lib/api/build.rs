@@ -119,12 +119,15 @@ fn configure_validation(builder: Builder) -> Builder {
         .validates(&[
             ("GetCollectionInfoRequest.collection_name", "length(min = 1, max = 255)"),
             ("CollectionExistsRequest.collection_name", "length(min = 1, max = 255)"),
+            ("FlushCollectionRequest.collection_name", "length(min = 1, max = 255)"),
             ("CreateCollection.collection_name", "length(min = 1, max = 255), custom(function = \"common::validation::validate_collection_name\")"),
             ("CreateCollection.hnsw_config", ""),
             ("CreateCollection.wal_config", ""),
             ("CreateCollection.optimizers_config", ""),
             ("CreateCollection.vectors_config", ""),
             ("CreateCollection.quantization_config", ""),
+            ("CreateCollection.ttl_config", ""),
+            ("CreateCollection.payload_compression", ""),
             ("UpdateCollection.collection_name", "length(min = 1, max = 255)"),
             ("UpdateCollection.optimizers_config", ""),
             ("UpdateCollection.params", ""),
@@ -132,6 +135,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollection.hnsw_config", ""),
             ("UpdateCollection.vectors_config", ""),
             ("UpdateCollection.quantization_config", ""),
+            ("UpdateCollection.new_vectors_config", ""),
             ("DeleteCollection.collection_name", "length(min = 1, max = 255)"),
             ("DeleteCollection.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("CollectionConfig.params", ""),
@@ -142,9 +146,12 @@ fn configure_validation(builder: Builder) -> Builder {
             ("ChangeAliases.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("ListCollectionAliasesRequest.collection_name", "length(min = 1, max = 255)"),
             ("HnswConfigDiff.ef_construct", "custom(function = \"crate::grpc::validate::validate_u64_range_min_4\")"),
+            ("HnswConfigDiff.level_multiplier", "custom(function = \"crate::grpc::validate::validate_f64_range_10\")"),
             ("WalConfigDiff.wal_capacity_mb", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("OptimizersConfigDiff.deleted_threshold", "custom(function = \"crate::grpc::validate::validate_f64_range_1\")"),
             ("OptimizersConfigDiff.vacuum_min_vector_number", "custom(function = \"crate::grpc::validate::validate_u64_range_min_100\")"),
+            ("OptimizersConfigDiff.deduplication_epsilon", "custom(function = \"crate::grpc::validate::validate_f64_range_min_0\")"),
+            ("OptimizersConfigDiff.payload_index_deleted_threshold", "custom(function = \"crate::grpc::validate::validate_f64_range_1\")"),
             ("VectorsConfig.config", ""),
             ("VectorsConfigDiff.config", ""),
             ("VectorParams.size", "range(min = 1, max = 65536)"),
@@ -161,6 +168,11 @@ fn configure_validation(builder: Builder) -> Builder {
             ("UpdateCollectionClusterSetupRequest.operation", ""),
             ("StrictModeConfig.max_query_limit", "range(min = 1)"),
             ("StrictModeConfig.max_timeout", "range(min = 1)"),
+            ("StrictModeConfig.scroll_max_limit", "range(min = 1)"),
+            ("TtlConfig.ttl_sec", "range(min = 1)"),
+            ("TtlConfig.check_interval_sec", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("TtlConfig.delete_batch_size", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("PayloadCompressionConfig.level", "range(min = 1, max = 22)"),
         ], &[
             "ListCollectionsRequest",
             "CollectionParamsDiff",
@@ -182,6 +194,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("WaitForShardStateRequest.timeout", "range(min = 1)"),
             ("GetShardRecoveryPointRequest.collection_name", "length(min = 1, max = 255)"),
             ("UpdateShardCutoffPointRequest.collection_name", "length(min = 1, max = 255)"),
+            ("HasAppliedSessionRequest.collection_name", "length(min = 1, max = 255)"),
         ], &[])
         // Service: points.proto
         .validates(&[
@@ -207,6 +220,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPoints.limit", "range(min = 1)"),
             ("SearchPoints.params", ""),
             ("SearchPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("SearchPoints.score_modifier", ""),
+            ("ScoreModifier.oversampling", "custom(function = \"crate::grpc::validate::validate_f64_range_min_1\")"),
             ("SearchBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("SearchBatchPoints.search_points", ""),
             ("SearchBatchPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
@@ -218,6 +233,9 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchPointGroups.limit", "range(min = 1)"),
             ("SearchPointGroups.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("SearchParams.quantization", ""),
+            ("SearchParams.hnsw_beam_width", "range(min = 1)"),
+            ("SearchParams.hnsw_max_candidates", "range(min = 1)"),
+            ("SearchParams.sparse_top_k", "range(min = 1)"),
             ("QuantizationSearchParams.oversampling", "custom(function = \"crate::grpc::validate::validate_f64_range_min_1\")"),
             ("ScrollPoints.collection_name", "length(min = 1, max = 255)"),
             ("ScrollPoints.filter", ""),
@@ -228,6 +246,7 @@ fn configure_validation(builder: Builder) -> Builder {
             ("RecommendPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
             ("RecommendPoints.positive_vectors", ""),
             ("RecommendPoints.negative_vectors", ""),
+            ("RecommendPoints.mmr_lambda", "custom(function = \"crate::grpc::validate::validate_f32_range_1\")"),
             ("RecommendBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("RecommendBatchPoints.recommend_points", ""),
             ("RecommendBatchPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
@@ -245,6 +264,8 @@ fn configure_validation(builder: Builder) -> Builder {
             ("DiscoverPoints.params", ""),
             ("DiscoverPoints.limit", "range(min = 1)"),
             ("DiscoverPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("DiscoverPoints.weighted_context", ""),
+            ("WeightedContext.weight", "range(min = 1, max = 100)"),
             ("DiscoverBatchPoints.collection_name", "length(min = 1, max = 255)"),
             ("DiscoverBatchPoints.discover_points", ""),
             ("DiscoverBatchPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
@@ -280,7 +301,11 @@ fn configure_validation(builder: Builder) -> Builder {
             ("SearchMatrixPoints.filter", ""),
             ("SearchMatrixPoints.sample", "custom(function = \"crate::grpc::validate::validate_u64_range_min_2\")"),
             ("SearchMatrixPoints.limit", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
-            ("SearchMatrixPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")")
+            ("SearchMatrixPoints.timeout", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("ExportPointsRequest.collection_name", "length(min = 1, max = 255)"),
+            ("ExportPointsRequest.batch_size", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")"),
+            ("ImportPointsRequest.collection_name", "length(min = 1, max = 255)"),
+            ("ImportPointsRequest.batch_size", "custom(function = \"crate::grpc::validate::validate_u64_range_min_1\")")
         ], &[])
         .type_attribute(".", "#[derive(serde::Serialize)]")
         // Service: points_internal_service.proto
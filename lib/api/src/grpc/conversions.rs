@@ -22,29 +22,33 @@ use super::qdrant::{
     DatetimeIndexParams, DatetimeRange, Direction, FacetHit, FacetHitInternal, FacetValue,
     FacetValueInternal, FieldType, FloatIndexParams, GeoIndexParams, GeoLineString, GroupId,
     KeywordIndexParams, LookupLocation, MultiVectorComparator, MultiVectorConfig, OrderBy,
-    OrderValue, Range, RawVector, RecommendStrategy, SearchMatrixPair, SearchPointGroups,
-    SearchPoints, ShardKeySelector, SparseIndices, StartFrom, UuidIndexParams, WithLookup,
+    OrderValue, Range, RawVector, RecommendStrategy, RerankParams, ScoreModifier, SearchMatrixPair,
+    SearchPointGroups, SearchPoints, ShardKeySelector, SparseIndices, StartFrom, UuidIndexParams,
+    WithLookup,
 };
 use crate::grpc::models::{CollectionsResponse, VersionInfo};
 use crate::grpc::qdrant::condition::ConditionOneOf;
 use crate::grpc::qdrant::payload_index_params::IndexParams;
 use crate::grpc::qdrant::point_id::PointIdOptions;
 use crate::grpc::qdrant::r#match::MatchValue;
+use crate::grpc::qdrant::stemming_algorithm::StemmingParams;
 use crate::grpc::qdrant::value::Kind;
 use crate::grpc::qdrant::vectors::VectorsOptions;
 use crate::grpc::qdrant::with_payload_selector::SelectorOptions;
 use crate::grpc::qdrant::{
     shard_key, with_vectors_selector, CollectionDescription, CollectionOperationResponse,
     Condition, DenseVector, Distance, FieldCondition, Filter, GeoBoundingBox, GeoPoint, GeoPolygon,
-    GeoRadius, HasIdCondition, HealthCheckReply, HnswConfigDiff, IntegerIndexParams,
-    IsEmptyCondition, IsNullCondition, ListCollectionsResponse, ListValue, Match, MinShould,
-    MultiDenseVector, NamedVectors, NestedCondition, PayloadExcludeSelector,
-    PayloadIncludeSelector, PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId,
+    GeoRadius, HasFieldCondition, HasIdCondition, HasVectorCondition, HealthCheckReply,
+    HnswConfigDiff, IdRangeCondition, IntegerIndexParams, IsEmptyCondition, IsNullCondition,
+    ListCollectionsResponse, ListValue, Match, MinShould, MultiDenseVector, NamedVectors,
+    NestedCondition, PayloadCompressionConfig, PayloadExcludeSelector, PayloadIncludeSelector,
+    PayloadIndexParams, PayloadSchemaInfo, PayloadSchemaType, PointId, PointProvenance,
     PointsOperationResponse, PointsOperationResponseInternal, ProductQuantization,
     QuantizationConfig, QuantizationSearchParams, QuantizationType, RepeatedIntegers,
-    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SparseVector, Struct,
-    TextIndexParams, TokenizerType, UpdateResult, UpdateResultInternal, Value, ValuesCount, Vector,
-    Vectors, VectorsSelector, WithPayloadSelector, WithVectorsSelector,
+    RepeatedStrings, ScalarQuantization, ScoredPoint, SearchParams, ShardKey, SnowballParams,
+    SparseVector, StemmingAlgorithm, Struct, TextIndexParams, TokenizerType, UpdateResult,
+    UpdateResultInternal, Value, ValuesCount, Vector, Vectors, VectorsSelector,
+    WithPayloadSelector, WithVectorsSelector,
 };
 use crate::rest::schema as rest;
 
@@ -101,6 +105,27 @@ pub fn proto_to_payloads(proto: HashMap<String, Value>) -> Result<segment::types
     Ok(map.into())
 }
 
+pub fn vector_metadata_to_proto(
+    vector_metadata: segment::types::VectorMetadata,
+) -> HashMap<String, Struct> {
+    vector_metadata
+        .into_iter()
+        .map(|(vector_name, metadata)| {
+            let fields = payload_to_proto(metadata);
+            (vector_name, Struct { fields })
+        })
+        .collect()
+}
+
+pub fn proto_to_vector_metadata(
+    proto: HashMap<String, Struct>,
+) -> Result<segment::types::VectorMetadata, Status> {
+    proto
+        .into_iter()
+        .map(|(vector_name, metadata)| Ok((vector_name, proto_to_payloads(metadata.fields)?)))
+        .collect()
+}
+
 fn proto_to_json(proto: Value) -> Result<serde_json::Value, Status> {
     match proto.kind {
         None => Ok(serde_json::Value::default()),
@@ -218,6 +243,7 @@ impl From<segment::data_types::index::TokenizerType> for TokenizerType {
             segment::data_types::index::TokenizerType::Whitespace => TokenizerType::Whitespace,
             segment::data_types::index::TokenizerType::Multilingual => TokenizerType::Multilingual,
             segment::data_types::index::TokenizerType::Word => TokenizerType::Word,
+            segment::data_types::index::TokenizerType::Ngram => TokenizerType::Ngram,
         }
     }
 }
@@ -228,6 +254,7 @@ impl From<segment::data_types::index::KeywordIndexParams> for PayloadIndexParams
             index_params: Some(IndexParams::KeywordIndexParams(KeywordIndexParams {
                 is_tenant: params.is_tenant,
                 on_disk: params.on_disk,
+                hnsw_partitions: params.hnsw_partitions.unwrap_or_default(),
             })),
         }
     }
@@ -275,15 +302,18 @@ impl From<segment::data_types::index::TextIndexParams> for PayloadIndexParams {
                 min_token_len: params.min_token_len.map(|x| x as u64),
                 max_token_len: params.max_token_len.map(|x| x as u64),
                 on_disk: params.on_disk,
+                stemmer: params.stemmer.map(StemmingAlgorithm::from),
             })),
         }
     }
 }
 
 impl From<segment::data_types::index::BoolIndexParams> for PayloadIndexParams {
-    fn from(_params: segment::data_types::index::BoolIndexParams) -> Self {
+    fn from(params: segment::data_types::index::BoolIndexParams) -> Self {
         PayloadIndexParams {
-            index_params: Some(IndexParams::BoolIndexParams(BoolIndexParams {})),
+            index_params: Some(IndexParams::BoolIndexParams(BoolIndexParams {
+                on_disk: params.on_disk,
+            })),
         }
     }
 }
@@ -310,6 +340,23 @@ impl From<segment::data_types::index::DatetimeIndexParams> for PayloadIndexParam
     }
 }
 
+impl From<segment::types::PayloadCompressionConfig> for PayloadCompressionConfig {
+    fn from(value: segment::types::PayloadCompressionConfig) -> Self {
+        let segment::types::PayloadCompressionConfig { level } = value;
+        PayloadCompressionConfig { level: Some(level) }
+    }
+}
+
+impl From<PayloadCompressionConfig> for segment::types::PayloadCompressionConfig {
+    fn from(value: PayloadCompressionConfig) -> Self {
+        let PayloadCompressionConfig { level } = value;
+        let default = Self::default();
+        Self {
+            level: level.unwrap_or(default.level),
+        }
+    }
+}
+
 impl From<segment::types::PayloadIndexInfo> for PayloadSchemaInfo {
     fn from(schema: segment::types::PayloadIndexInfo) -> Self {
         PayloadSchemaInfo {
@@ -361,6 +408,42 @@ impl TryFrom<TokenizerType> for segment::data_types::index::TokenizerType {
             }
             TokenizerType::Whitespace => Ok(segment::data_types::index::TokenizerType::Whitespace),
             TokenizerType::Word => Ok(segment::data_types::index::TokenizerType::Word),
+            TokenizerType::Ngram => Ok(segment::data_types::index::TokenizerType::Ngram),
+        }
+    }
+}
+
+impl From<segment::data_types::index::StemmingAlgorithm> for StemmingAlgorithm {
+    fn from(algorithm: segment::data_types::index::StemmingAlgorithm) -> Self {
+        let stemming_params = match algorithm {
+            segment::data_types::index::StemmingAlgorithm::Snowball(params) => {
+                StemmingParams::Snowball(SnowballParams {
+                    language: params.language.to_string(),
+                })
+            }
+        };
+        Self {
+            stemming_params: Some(stemming_params),
+        }
+    }
+}
+
+impl TryFrom<StemmingAlgorithm> for segment::data_types::index::StemmingAlgorithm {
+    type Error = Status;
+    fn try_from(algorithm: StemmingAlgorithm) -> Result<Self, Self::Error> {
+        match algorithm.stemming_params {
+            Some(StemmingParams::Snowball(SnowballParams { language })) => {
+                let language = language.parse().map_err(|_| {
+                    Status::invalid_argument(format!("unsupported snowball language: {language}"))
+                })?;
+                Ok(segment::data_types::index::StemmingAlgorithm::Snowball(
+                    segment::data_types::index::SnowballParams {
+                        r#type: segment::data_types::index::Snowball::Snowball,
+                        language,
+                    },
+                ))
+            }
+            None => Err(Status::invalid_argument("unknown stemming algorithm")),
         }
     }
 }
@@ -387,6 +470,7 @@ impl TryFrom<KeywordIndexParams> for segment::data_types::index::KeywordIndexPar
             r#type: KeywordIndexType::Keyword,
             is_tenant: params.is_tenant,
             on_disk: params.on_disk,
+            hnsw_partitions: Some(params.hnsw_partitions).filter(|values| !values.is_empty()),
         })
     }
 }
@@ -427,7 +511,7 @@ impl TryFrom<GeoIndexParams> for segment::data_types::index::GeoIndexParams {
 impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
     type Error = Status;
     fn try_from(params: TextIndexParams) -> Result<Self, Self::Error> {
-        Ok(segment::data_types::index::TextIndexParams {
+        let text_index_params = segment::data_types::index::TextIndexParams {
             r#type: TextIndexType::Text,
             tokenizer: TokenizerType::try_from(params.tokenizer)
                 .map(|x| x.try_into())
@@ -435,16 +519,22 @@ impl TryFrom<TextIndexParams> for segment::data_types::index::TextIndexParams {
             lowercase: params.lowercase,
             min_token_len: params.min_token_len.map(|x| x as usize),
             max_token_len: params.max_token_len.map(|x| x as usize),
+            stemmer: params.stemmer.map(TryInto::try_into).transpose()?,
             on_disk: params.on_disk,
-        })
+        };
+        validator::Validate::validate(&text_index_params).map_err(|err| {
+            Status::invalid_argument(format!("Malformed TextIndexParams type: {err}"))
+        })?;
+        Ok(text_index_params)
     }
 }
 
 impl TryFrom<BoolIndexParams> for segment::data_types::index::BoolIndexParams {
     type Error = Status;
-    fn try_from(_params: BoolIndexParams) -> Result<Self, Self::Error> {
+    fn try_from(params: BoolIndexParams) -> Result<Self, Self::Error> {
         Ok(segment::data_types::index::BoolIndexParams {
             r#type: BoolIndexType::Bool,
+            on_disk: params.on_disk,
         })
     }
 }
@@ -647,6 +737,13 @@ impl From<SearchParams> for segment::types::SearchParams {
             exact: params.exact.unwrap_or(false),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: params.indexed_only.unwrap_or(false),
+            hnsw_beam_width: params.hnsw_beam_width.map(|x| x as usize),
+            hnsw_max_candidates: params.hnsw_max_candidates.map(|x| x as usize),
+            sparse_top_k: params.sparse_top_k.map(|x| x as usize),
+            provenance: params.provenance.unwrap_or(false),
+            distance: params
+                .distance
+                .and_then(|distance| from_grpc_dist(distance).ok()),
         }
     }
 }
@@ -658,6 +755,13 @@ impl From<segment::types::SearchParams> for SearchParams {
             exact: Some(params.exact),
             quantization: params.quantization.map(|q| q.into()),
             indexed_only: Some(params.indexed_only),
+            hnsw_beam_width: params.hnsw_beam_width.map(|x| x as u64),
+            hnsw_max_candidates: params.hnsw_max_candidates.map(|x| x as u64),
+            sparse_top_k: params.sparse_top_k.map(|x| x as u64),
+            provenance: Some(params.provenance),
+            distance: params
+                .distance
+                .map(|distance| Distance::from(distance) as i32),
         }
     }
 }
@@ -819,6 +923,26 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vectors: point.vector.map(|v| v.into()),
             shard_key: point.shard_key.map(convert_shard_key_to_grpc),
             order_value: point.order_value.map(From::from),
+            distance: point.distance,
+            provenance: point.provenance.map(From::from),
+        }
+    }
+}
+
+impl From<segment::types::PointProvenance> for PointProvenance {
+    fn from(provenance: segment::types::PointProvenance) -> Self {
+        Self {
+            shard_id: provenance.shard_id,
+            segment_id: provenance.segment_id as u64,
+        }
+    }
+}
+
+impl From<PointProvenance> for segment::types::PointProvenance {
+    fn from(provenance: PointProvenance) -> Self {
+        Self {
+            shard_id: provenance.shard_id,
+            segment_id: provenance.segment_id as usize,
         }
     }
 }
@@ -1210,6 +1334,12 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::HasId(has_id) => {
                     Ok(segment::types::Condition::HasId(has_id.try_into()?))
                 }
+                ConditionOneOf::HasVector(has_vector) => {
+                    Ok(segment::types::Condition::HasVector(has_vector.into()))
+                }
+                ConditionOneOf::IdRange(id_range) => {
+                    Ok(segment::types::Condition::IdRange(id_range.into()))
+                }
                 ConditionOneOf::Filter(filter) => {
                     Ok(segment::types::Condition::Filter(filter.try_into()?))
                 }
@@ -1219,6 +1349,9 @@ impl TryFrom<Condition> for segment::types::Condition {
                 ConditionOneOf::IsNull(is_null) => {
                     Ok(segment::types::Condition::IsNull(is_null.try_into()?))
                 }
+                ConditionOneOf::HasField(has_field) => {
+                    Ok(segment::types::Condition::HasField(has_field.try_into()?))
+                }
                 ConditionOneOf::Nested(nested) => Ok(segment::types::Condition::Nested(
                     segment::types::NestedCondition::new(nested.try_into()?),
                 )),
@@ -1240,9 +1373,18 @@ impl From<segment::types::Condition> for Condition {
             segment::types::Condition::IsNull(is_null) => {
                 Some(ConditionOneOf::IsNull(IsNullCondition::from(is_null)))
             }
+            segment::types::Condition::HasField(has_field) => {
+                Some(ConditionOneOf::HasField(HasFieldCondition::from(has_field)))
+            }
             segment::types::Condition::HasId(has_id) => {
                 Some(ConditionOneOf::HasId(HasIdCondition::from(has_id)))
             }
+            segment::types::Condition::HasVector(has_vector) => Some(ConditionOneOf::HasVector(
+                HasVectorCondition::from(has_vector),
+            )),
+            segment::types::Condition::IdRange(id_range) => {
+                Some(ConditionOneOf::IdRange(IdRangeCondition::from(id_range)))
+            }
             segment::types::Condition::Filter(filter) => {
                 Some(ConditionOneOf::Filter(Filter::from(filter)))
             }
@@ -1323,6 +1465,26 @@ impl From<segment::types::IsNullCondition> for IsNullCondition {
     }
 }
 
+impl TryFrom<HasFieldCondition> for segment::types::HasFieldCondition {
+    type Error = Status;
+
+    fn try_from(value: HasFieldCondition) -> Result<Self, Status> {
+        Ok(segment::types::HasFieldCondition {
+            has_field: segment::types::PayloadField {
+                key: json_path_from_proto(&value.key)?,
+            },
+        })
+    }
+}
+
+impl From<segment::types::HasFieldCondition> for HasFieldCondition {
+    fn from(value: segment::types::HasFieldCondition) -> Self {
+        Self {
+            key: value.has_field.key.to_string(),
+        }
+    }
+}
+
 impl TryFrom<HasIdCondition> for segment::types::HasIdCondition {
     type Error = Status;
 
@@ -1343,6 +1505,38 @@ impl From<segment::types::HasIdCondition> for HasIdCondition {
     }
 }
 
+impl From<HasVectorCondition> for segment::types::HasVectorCondition {
+    fn from(value: HasVectorCondition) -> Self {
+        Self {
+            has_vector: value.has_vector,
+        }
+    }
+}
+
+impl From<segment::types::HasVectorCondition> for HasVectorCondition {
+    fn from(value: segment::types::HasVectorCondition) -> Self {
+        Self {
+            has_vector: value.has_vector,
+        }
+    }
+}
+
+impl From<IdRangeCondition> for segment::types::IdRangeCondition {
+    fn from(value: IdRangeCondition) -> Self {
+        let IdRangeCondition { lt, gt, gte, lte } = value;
+        Self {
+            id_range: segment::types::IdRange { lt, gt, gte, lte },
+        }
+    }
+}
+
+impl From<segment::types::IdRangeCondition> for IdRangeCondition {
+    fn from(value: segment::types::IdRangeCondition) -> Self {
+        let segment::types::IdRange { lt, gt, gte, lte } = value.id_range;
+        Self { lt, gt, gte, lte }
+    }
+}
+
 impl TryFrom<FieldCondition> for segment::types::FieldCondition {
     type Error = Status;
 
@@ -1445,10 +1639,18 @@ impl TryFrom<GeoRadius> for segment::types::GeoRadius {
             GeoRadius {
                 center: Some(c),
                 radius,
-            } => Ok(Self {
-                center: c.into(),
-                radius: radius.into(),
-            }),
+                inner_radius,
+            } => {
+                let geo_radius = Self {
+                    center: c.into(),
+                    radius: radius.into(),
+                    inner_radius: inner_radius.map(Into::into),
+                };
+                validator::Validate::validate(&geo_radius).map_err(|err| {
+                    Status::invalid_argument(format!("Malformed GeoRadius type: {err}"))
+                })?;
+                Ok(geo_radius)
+            }
             _ => Err(Status::invalid_argument("Malformed GeoRadius type")),
         }
     }
@@ -1459,6 +1661,7 @@ impl From<segment::types::GeoRadius> for GeoRadius {
         Self {
             center: Some(value.center.into()),
             radius: value.radius as f32, // TODO lossy ok?
+            inner_radius: value.inner_radius.map(|inner_radius| inner_radius as f32),
         }
     }
 }
@@ -1471,9 +1674,15 @@ impl TryFrom<GeoPolygon> for segment::types::GeoPolygon {
             GeoPolygon {
                 exterior: Some(e),
                 interiors,
-            } => Ok(Self {
+                crosses_antimeridian,
+            } => segment::types::GeoPolygonShadow {
                 exterior: e.into(),
                 interiors: Some(interiors.into_iter().map(Into::into).collect()),
+                crosses_antimeridian,
+            }
+            .try_into()
+            .map_err(|err: segment::common::operation_error::OperationError| {
+                Status::invalid_argument(format!("Malformed GeoPolygon type: {err}"))
             }),
             _ => Err(Status::invalid_argument(
                 "Malformed GeoPolygon type - field `exterior` is required",
@@ -1492,6 +1701,7 @@ impl From<segment::types::GeoPolygon> for GeoPolygon {
                 .into_iter()
                 .map(Into::into)
                 .collect(),
+            crosses_antimeridian: value.crosses_antimeridian,
         }
     }
 }
@@ -1613,6 +1823,8 @@ impl TryFrom<Match> for segment::types::Match {
                 MatchValue::ExceptKeywords(ints) => {
                     segment::types::Match::Except(ints.strings.into())
                 }
+                MatchValue::AllKeywords(kwds) => segment::types::Match::All(kwds.strings.into()),
+                MatchValue::AllIntegers(ints) => segment::types::Match::All(ints.integers.into()),
             }),
             _ => Err(Status::invalid_argument("Malformed Match condition")),
         }
@@ -1650,6 +1862,16 @@ impl From<segment::types::Match> for Match {
                     MatchValue::ExceptIntegers(RepeatedIntegers { integers })
                 }
             },
+            segment::types::Match::All(all) => match all.all {
+                segment::types::AnyVariants::Strings(strings) => {
+                    let strings = strings.into_iter().collect();
+                    MatchValue::AllKeywords(RepeatedStrings { strings })
+                }
+                segment::types::AnyVariants::Integers(integers) => {
+                    let integers = integers.into_iter().collect();
+                    MatchValue::AllIntegers(RepeatedIntegers { integers })
+                }
+            },
         };
         Self {
             match_value: Some(match_value),
@@ -1748,11 +1970,14 @@ impl From<HnswConfigDiff> for segment::types::HnswConfig {
     fn from(hnsw_config: HnswConfigDiff) -> Self {
         Self {
             m: hnsw_config.m.unwrap_or_default() as usize,
+            m0: hnsw_config.m0.map(|x| x as usize),
             ef_construct: hnsw_config.ef_construct.unwrap_or_default() as usize,
             full_scan_threshold: hnsw_config.full_scan_threshold.unwrap_or_default() as usize,
             max_indexing_threads: hnsw_config.max_indexing_threads.unwrap_or_default() as usize,
             on_disk: hnsw_config.on_disk,
             payload_m: hnsw_config.payload_m.map(|x| x as usize),
+            level_multiplier: hnsw_config.level_multiplier,
+            deterministic_build: hnsw_config.deterministic_build,
         }
     }
 }
@@ -1794,6 +2019,17 @@ impl TryFrom<Distance> for segment::types::Distance {
     }
 }
 
+impl From<segment::types::Distance> for Distance {
+    fn from(value: segment::types::Distance) -> Self {
+        match value {
+            segment::types::Distance::Cosine => Distance::Cosine,
+            segment::types::Distance::Euclid => Distance::Euclid,
+            segment::types::Distance::Dot => Distance::Dot,
+            segment::types::Distance::Manhattan => Distance::Manhattan,
+        }
+    }
+}
+
 pub fn from_grpc_dist(dist: i32) -> Result<segment::types::Distance, Status> {
     match Distance::try_from(dist) {
         Err(_) => Err(Status::invalid_argument(format!(
@@ -1855,6 +2091,8 @@ impl From<UpdateResultInternal> for UpdateResult {
         Self {
             operation_id: res.operation_id,
             status: res.status,
+            session_token: None,
+            invalid_points: Vec::new(),
         }
     }
 }
@@ -2030,6 +2268,7 @@ impl From<segment_query::ContextPair<segment_vectors::Vector>> for raw_query::Ra
         Self {
             positive: Some(RawVector::from(value.positive)),
             negative: Some(RawVector::from(value.negative)),
+            weight: Some(value.weight),
         }
     }
 }
@@ -2052,6 +2291,9 @@ impl TryFrom<raw_query::RawContextPair> for segment_query::ContextPair<segment_v
                 .ok_or_else(|| {
                     Status::invalid_argument("No negative part of context pair provided")
                 })?,
+            weight: value
+                .weight
+                .unwrap_or(segment_query::DEFAULT_CONTEXT_WEIGHT),
         })
     }
 }
@@ -2104,6 +2346,36 @@ impl TryFrom<raw_query::Discovery> for segment_query::DiscoveryQuery<segment_vec
     }
 }
 
+impl TryFrom<ScoreModifier> for segment::data_types::score_modifier::ScoreModifier {
+    type Error = Status;
+
+    fn try_from(value: ScoreModifier) -> Result<Self, Self::Error> {
+        let ScoreModifier {
+            expression,
+            default_multiplier,
+            oversampling,
+        } = value;
+
+        Ok(Self {
+            expression: expression.parse().map_err(|err| {
+                Status::invalid_argument(format!("Invalid score modifier expression: {err}"))
+            })?,
+            default_multiplier,
+            oversampling,
+        })
+    }
+}
+
+impl From<RerankParams> for rest::RerankParams {
+    fn from(value: RerankParams) -> Self {
+        let RerankParams { name, candidates } = value;
+        Self {
+            name,
+            candidates: candidates.map(|candidates| candidates as usize),
+        }
+    }
+}
+
 impl TryFrom<SearchPoints> for rest::SearchRequestInternal {
     type Error = Status;
 
@@ -2137,6 +2409,10 @@ impl TryFrom<SearchPoints> for rest::SearchRequestInternal {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            score_modifier: value.score_modifier.map(TryInto::try_into).transpose()?,
+            rerank: value.rerank.map(Into::into),
+            max_score_threshold: value.max_score_threshold,
+            with_distance: value.with_distance,
         })
     }
 }
@@ -2160,6 +2436,10 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             timeout: None,
             shard_key_selector: None,
             sparse_indices: value.sparse_indices,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
         };
 
         if let Some(sparse_indices) = &search_points.sparse_indices {
@@ -2181,6 +2461,10 @@ impl TryFrom<SearchPointGroups> for rest::SearchGroupsRequestInternal {
             with_payload,
             with_vector,
             score_threshold,
+            score_modifier: _,
+            rerank: _,
+            max_score_threshold: _,
+            with_distance: _,
         } = search_points.try_into()?;
 
         Ok(Self {
@@ -11,6 +11,21 @@ pub mod grpc_health_v1;
 pub mod transport_channel_pool;
 pub mod validate;
 
+use segment::common::operation_error::ErrorCode;
+use tonic::metadata::MetadataValue;
+
+/// Name of the metadata entry of a failed gRPC response, which holds its [`ErrorCode`]
+pub const ERROR_CODE_METADATA_KEY: &str = "qdrant-error-code";
+
+/// Attach the category of an error to a gRPC status, like `error_code` of REST responses
+pub fn with_error_code(mut status: tonic::Status, code: ErrorCode) -> tonic::Status {
+    status.metadata_mut().insert(
+        ERROR_CODE_METADATA_KEY,
+        MetadataValue::from_static(code.as_str()),
+    );
+    status
+}
+
 pub const fn api_crate_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
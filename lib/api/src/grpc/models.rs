@@ -1,6 +1,7 @@
 use std::fmt::Debug;
 
 use schemars::JsonSchema;
+use segment::common::operation_error::ErrorCode;
 use serde;
 use serde::Serialize;
 
@@ -42,6 +43,9 @@ pub struct ApiResponse<D> {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub result: Option<D>,
     pub status: ApiStatus,
+    /// Category of the error, only present if the request failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<ErrorCode>,
     pub time: f64,
 }
 
@@ -28,6 +28,9 @@ pub struct VectorParams {
     /// Configuration for multi-vector search
     #[prost(message, optional, tag = "7")]
     pub multivector_config: ::core::option::Option<MultiVectorConfig>,
+    /// How zero vectors are treated with Cosine distance
+    #[prost(enumeration = "ZeroVectorPolicy", optional, tag = "8")]
+    pub zero_vector_policy: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -119,6 +122,9 @@ pub struct SparseVectorParams {
     /// If set - apply modifier to the vector values
     #[prost(enumeration = "Modifier", optional, tag = "2")]
     pub modifier: ::core::option::Option<i32>,
+    /// Similarity function of the vectors, can't be changed after creation. Default: Dot
+    #[prost(enumeration = "SparseDistance", optional, tag = "3")]
+    pub distance: ::core::option::Option<i32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -178,6 +184,16 @@ pub struct CollectionExistsResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlushCollectionRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ListCollectionsRequest {}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -246,6 +262,19 @@ pub struct HnswConfigDiff {
     /// Number of additional payload-aware links per node in the index graph. If not set - regular M parameter will be used.
     #[prost(uint64, optional, tag = "6")]
     pub payload_m: ::core::option::Option<u64>,
+    /// Level generation multiplier (mL) of the HNSW graph. Larger values produce more layers.
+    /// If not set - `1 / ln(M)` will be used.
+    #[prost(double, optional, tag = "7")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_10"))]
+    pub level_multiplier: ::core::option::Option<f64>,
+    /// Build the graph in a single thread, with a fixed seed, so that the same data always produces the same index.
+    /// Building is slower, `max_indexing_threads` is not used. Default: false
+    #[prost(bool, optional, tag = "8")]
+    pub deterministic_build: ::core::option::Option<bool>,
+    /// Number of edges per node on the base layer (level 0) of the index graph, which contains all points.
+    /// Must not be smaller than `m`. If not set - `2 * m` will be used.
+    #[prost(uint64, optional, tag = "9")]
+    pub m0: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -336,6 +365,41 @@ pub struct OptimizersConfigDiff {
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[prost(uint64, optional, tag = "8")]
     pub max_optimization_threads: ::core::option::Option<u64>,
+    /// Store exact duplicate vectors only once in optimized segments with on-disk vector storage.
+    /// Does not affect search results.
+    #[prost(bool, optional, tag = "9")]
+    pub deduplicate_vectors: ::core::option::Option<bool>,
+    /// If set along with `deduplicate_vectors`, also store vectors whose components all differ by at most this value only once.
+    /// Changes scores of merged vectors, so only use it if near-duplicates are interchangeable.
+    #[prost(double, optional, tag = "10")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_min_0"))]
+    pub deduplication_epsilon: ::core::option::Option<f64>,
+    /// Pause vector indexing, e.g. for the duration of a bulk load.
+    /// New and optimized segments are kept plain, and searched by full scan, until indexing is resumed.
+    /// Segments which are large enough are indexed in the background once this is unset or set to false.
+    #[prost(bool, optional, tag = "11")]
+    pub defer_indexing: ::core::option::Option<bool>,
+    /// The minimal fraction of points removed from the payload index of a field in a segment, required to perform segment optimization.
+    /// Removed points are kept in payload indexes of optimized segments until they are optimized again, which slows down filtering.
+    /// If not set, `deleted_threshold` is used.
+    #[prost(double, optional, tag = "12")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_1"))]
+    pub payload_index_deleted_threshold: ::core::option::Option<f64>,
+    /// Minimal size (in kilobytes) of writes to buffer in appendable segments before they are flushed.
+    /// Small frequent flushes fragment the data into many small segments on ingest.
+    /// Writes are buffered in memory, and covered by the WAL, until this size is reached or `max_flush_delay_sec` has passed.
+    /// Buffered writes are always flushed on shutdown.
+    /// If not set, segments are flushed every `flush_interval_sec`.
+    ///
+    /// Note: 1Kb = 1 vector of size 256
+    #[prost(uint64, optional, tag = "13")]
+    pub min_segment_size: ::core::option::Option<u64>,
+    /// Maximum time (in seconds) writes are buffered for before they are flushed, when `min_segment_size` is set.
+    #[prost(uint64, optional, tag = "14")]
+    pub max_flush_delay_sec: ::core::option::Option<u64>,
+    /// Split segments which grew beyond twice `max_segment_size` into several segments no larger than `max_segment_size`.
+    #[prost(bool, optional, tag = "15")]
+    pub split_oversized_segments: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -452,6 +516,48 @@ pub struct StrictModeConfig {
     pub search_allow_exact: ::core::option::Option<bool>,
     #[prost(float, optional, tag = "8")]
     pub search_max_oversampling: ::core::option::Option<f32>,
+    #[prost(uint32, optional, tag = "9")]
+    #[validate(range(min = 1))]
+    pub scroll_max_limit: ::core::option::Option<u32>,
+    #[prost(bool, optional, tag = "10")]
+    pub clamp_query_limit: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TtlConfig {
+    /// Time-to-live of points, in seconds
+    #[prost(uint64, tag = "1")]
+    #[validate(range(min = 1))]
+    pub ttl_sec: u64,
+    /// Top-level payload field with a unix timestamp in seconds, from which the TTL of a point is counted. Points without this field never expire
+    #[prost(string, tag = "2")]
+    pub timestamp_field: ::prost::alloc::string::String,
+    /// Set the timestamp field to the current time when inserting points which don't have it, default = true
+    #[prost(bool, optional, tag = "3")]
+    pub set_on_insert: ::core::option::Option<bool>,
+    /// Set the timestamp field to the current time on upserts and payload updates of existing points which don't set it explicitly, default = false
+    #[prost(bool, optional, tag = "4")]
+    pub refresh_on_update: ::core::option::Option<bool>,
+    /// How often to look for expired points, in seconds, default = 60
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub check_interval_sec: ::core::option::Option<u64>,
+    /// Max number of expired points deleted by a single operation, default = 1000
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub delete_batch_size: ::core::option::Option<u64>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PayloadCompressionConfig {
+    /// Zstd compression level, from 1 (fastest) to 22 (smallest), default = 3
+    #[prost(int32, optional, tag = "1")]
+    #[validate(range(min = 1, max = 22))]
+    pub level: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -512,6 +618,14 @@ pub struct CreateCollection {
     /// Configuration for strict mode
     #[prost(message, optional, tag = "17")]
     pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// Expiry of points after a time-to-live
+    #[prost(message, optional, tag = "18")]
+    #[validate(nested)]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
+    /// Compression of stored payloads, disabled if not set
+    #[prost(message, optional, tag = "19")]
+    #[validate(nested)]
+    pub payload_compression: ::core::option::Option<PayloadCompressionConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -549,6 +663,16 @@ pub struct UpdateCollection {
     /// New sparse vector parameters
     #[prost(message, optional, tag = "8")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Overrides of optimizer parameters for individual shards, replacing previous overrides of these shards. Empty parameters remove the override of a shard
+    #[prost(map = "uint32, message", tag = "9")]
+    pub shard_optimizers_config: ::std::collections::HashMap<u32, OptimizersConfigDiff>,
+    /// Named vectors to add to the collection. Existing points have no values for them
+    #[prost(map = "string, message", tag = "10")]
+    #[validate(nested)]
+    pub new_vectors_config: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        VectorParams,
+    >,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -605,6 +729,9 @@ pub struct CollectionParams {
     /// Configuration for sparse vectors
     #[prost(message, optional, tag = "10")]
     pub sparse_vectors_config: ::core::option::Option<SparseVectorConfig>,
+    /// Compression of stored payloads, disabled if not set
+    #[prost(message, optional, tag = "11")]
+    pub payload_compression: ::core::option::Option<PayloadCompressionConfig>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -650,6 +777,12 @@ pub struct CollectionConfig {
     /// Configuration of strict mode.
     #[prost(message, optional, tag = "6")]
     pub strict_mode_config: ::core::option::Option<StrictModeConfig>,
+    /// Expiry of points after a time-to-live
+    #[prost(message, optional, tag = "7")]
+    pub ttl_config: ::core::option::Option<TtlConfig>,
+    /// Overrides of the optimizer configuration for individual shards
+    #[prost(map = "uint32, message", tag = "8")]
+    pub shard_optimizer_config: ::std::collections::HashMap<u32, OptimizersConfigDiff>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -661,6 +794,9 @@ pub struct KeywordIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "2")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Values of the field to build a separate HNSW graph for
+    #[prost(string, repeated, tag = "3")]
+    pub hnsw_partitions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -713,11 +849,44 @@ pub struct TextIndexParams {
     /// If true - store index on disk.
     #[prost(bool, optional, tag = "5")]
     pub on_disk: ::core::option::Option<bool>,
+    /// Stemming algorithm to reduce words to their root form
+    #[prost(message, optional, tag = "6")]
+    pub stemmer: ::core::option::Option<StemmingAlgorithm>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct BoolIndexParams {}
+pub struct StemmingAlgorithm {
+    #[prost(oneof = "stemming_algorithm::StemmingParams", tags = "1")]
+    pub stemming_params: ::core::option::Option<stemming_algorithm::StemmingParams>,
+}
+/// Nested message and enum types in `StemmingAlgorithm`.
+pub mod stemming_algorithm {
+    #[derive(serde::Serialize)]
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum StemmingParams {
+        /// Parameters for snowball stemming
+        #[prost(message, tag = "1")]
+        Snowball(super::SnowballParams),
+    }
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SnowballParams {
+    /// Which language the algorithm should stem
+    #[prost(string, tag = "1")]
+    pub language: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct BoolIndexParams {
+    /// If true - store index on disk.
+    #[prost(bool, optional, tag = "1")]
+    pub on_disk: ::core::option::Option<bool>,
+}
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -1115,6 +1284,17 @@ pub struct Replica {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChangeReplicationFactor {
+    /// Number of replicas for each shard
+    #[prost(uint32, tag = "1")]
+    pub replication_factor: u32,
+    /// Method for transferring shards to new replicas
+    #[prost(enumeration = "ShardTransferMethod", optional, tag = "2")]
+    pub method: ::core::option::Option<i32>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CreateShardKey {
     /// User-defined shard key
     #[prost(message, optional, tag = "1")]
@@ -1151,7 +1331,7 @@ pub struct UpdateCollectionClusterSetupRequest {
     pub timeout: ::core::option::Option<u64>,
     #[prost(
         oneof = "update_collection_cluster_setup_request::Operation",
-        tags = "2, 3, 4, 5, 7, 8, 9"
+        tags = "2, 3, 4, 5, 7, 8, 9, 10, 11, 12"
     )]
     #[validate(nested)]
     pub operation: ::core::option::Option<
@@ -1178,6 +1358,12 @@ pub mod update_collection_cluster_setup_request {
         DeleteShardKey(super::DeleteShardKey),
         #[prost(message, tag = "9")]
         RestartTransfer(super::RestartTransfer),
+        #[prost(message, tag = "10")]
+        ChangeReplicationFactor(super::ChangeReplicationFactor),
+        #[prost(message, tag = "11")]
+        MakeReplicaReadOnly(super::Replica),
+        #[prost(message, tag = "12")]
+        PromoteReplica(super::Replica),
     }
 }
 #[derive(serde::Serialize)]
@@ -1237,6 +1423,7 @@ pub enum Datatype {
     Float32 = 1,
     Uint8 = 2,
     Float16 = 3,
+    Int8 = 4,
 }
 impl Datatype {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1249,6 +1436,7 @@ impl Datatype {
             Datatype::Float32 => "Float32",
             Datatype::Uint8 => "Uint8",
             Datatype::Float16 => "Float16",
+            Datatype::Int8 => "Int8",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1258,6 +1446,40 @@ impl Datatype {
             "Float32" => Some(Self::Float32),
             "Uint8" => Some(Self::Uint8),
             "Float16" => Some(Self::Float16),
+            "Int8" => Some(Self::Int8),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum ZeroVectorPolicy {
+    /// Zero vectors are stored, and score 0 against any query
+    ScoreZero = 0,
+    /// Zero vectors are not stored, zero queries return no results
+    Exclude = 1,
+    /// Zero vectors are rejected on upsert, zero queries return no results
+    Reject = 2,
+}
+impl ZeroVectorPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            ZeroVectorPolicy::ScoreZero => "ScoreZero",
+            ZeroVectorPolicy::Exclude => "Exclude",
+            ZeroVectorPolicy::Reject => "Reject",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "ScoreZero" => Some(Self::ScoreZero),
+            "Exclude" => Some(Self::Exclude),
+            "Reject" => Some(Self::Reject),
             _ => None,
         }
     }
@@ -1293,6 +1515,37 @@ impl Modifier {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum SparseDistance {
+    SparseDot = 0,
+    SparseCosine = 1,
+    /// Weighted Jaccard, same as Jaccard for binary vectors
+    SparseJaccard = 2,
+}
+impl SparseDistance {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SparseDistance::SparseDot => "SparseDot",
+            SparseDistance::SparseCosine => "SparseCosine",
+            SparseDistance::SparseJaccard => "SparseJaccard",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "SparseDot" => Some(Self::SparseDot),
+            "SparseCosine" => Some(Self::SparseCosine),
+            "SparseJaccard" => Some(Self::SparseJaccard),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum MultiVectorComparator {
     MaxSim = 0,
 }
@@ -1539,6 +1792,7 @@ pub enum TokenizerType {
     Whitespace = 2,
     Word = 3,
     Multilingual = 4,
+    Ngram = 5,
 }
 impl TokenizerType {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1552,6 +1806,7 @@ impl TokenizerType {
             TokenizerType::Whitespace => "Whitespace",
             TokenizerType::Word => "Word",
             TokenizerType::Multilingual => "Multilingual",
+            TokenizerType::Ngram => "Ngram",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1562,6 +1817,7 @@ impl TokenizerType {
             "Whitespace" => Some(Self::Whitespace),
             "Word" => Some(Self::Word),
             "Multilingual" => Some(Self::Multilingual),
+            "Ngram" => Some(Self::Ngram),
             _ => None,
         }
     }
@@ -1586,6 +1842,8 @@ pub enum ReplicaState {
     Recovery = 6,
     /// Points are being migrated to this shard as part of resharding
     Resharding = 7,
+    /// A shard which receives data and is used for search, but is not counted for write consistency; Can only become active by an explicit promotion
+    ReadOnly = 8,
 }
 impl ReplicaState {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -1602,6 +1860,7 @@ impl ReplicaState {
             ReplicaState::PartialSnapshot => "PartialSnapshot",
             ReplicaState::Recovery => "Recovery",
             ReplicaState::Resharding => "Resharding",
+            ReplicaState::ReadOnly => "ReadOnly",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -1615,6 +1874,7 @@ impl ReplicaState {
             "PartialSnapshot" => Some(Self::PartialSnapshot),
             "Recovery" => Some(Self::Recovery),
             "Resharding" => Some(Self::Resharding),
+            "ReadOnly" => Some(Self::ReadOnly),
             _ => None,
         }
     }
@@ -2072,6 +2332,32 @@ pub mod collections_client {
                 .insert(GrpcMethod::new("qdrant.Collections", "DeleteShardKey"));
             self.inner.unary(req, path, codec).await
         }
+        /// Persist WAL and segments of all local shards of the collection on this peer to disk
+        pub async fn flush(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FlushCollectionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Collections/Flush",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Collections", "Flush"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -2185,6 +2471,14 @@ pub mod collections_server {
             tonic::Response<super::DeleteShardKeyResponse>,
             tonic::Status,
         >;
+        /// Persist WAL and segments of all local shards of the collection on this peer to disk
+        async fn flush(
+            &self,
+            request: tonic::Request<super::FlushCollectionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CollectionOperationResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsServer<T: Collections> {
@@ -2872,6 +3166,52 @@ pub mod collections_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Collections/Flush" => {
+                    #[allow(non_camel_case_types)]
+                    struct FlushSvc<T: Collections>(pub Arc<T>);
+                    impl<
+                        T: Collections,
+                    > tonic::server::UnaryService<super::FlushCollectionRequest>
+                    for FlushSvc<T> {
+                        type Response = super::CollectionOperationResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FlushCollectionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Collections>::flush(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = FlushSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -3016,6 +3356,33 @@ pub struct UpdateShardCutoffPointRequest {
     #[prost(message, optional, tag = "3")]
     pub cutoff: ::core::option::Option<RecoveryPoint>,
 }
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasAppliedSessionRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Id of the shard
+    #[prost(uint32, tag = "2")]
+    pub shard_id: u32,
+    /// Session token, only clocks of this shard are checked
+    #[prost(string, tag = "3")]
+    pub session_token: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasAppliedSessionResponse {
+    /// Whether all updates of the session are applied to the segments
+    #[prost(bool, tag = "1")]
+    pub applied: bool,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
 /// Generated client implementations.
 pub mod collections_internal_client {
     #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
@@ -3245,6 +3612,34 @@ pub mod collections_internal_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        /// Check whether a shard has applied the updates of a session token
+        pub async fn has_applied_session(
+            &mut self,
+            request: impl tonic::IntoRequest<super::HasAppliedSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::HasAppliedSessionResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.CollectionsInternal/HasAppliedSession",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("qdrant.CollectionsInternal", "HasAppliedSession"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -3296,6 +3691,14 @@ pub mod collections_internal_server {
             tonic::Response<super::CollectionOperationResponse>,
             tonic::Status,
         >;
+        /// Check whether a shard has applied the updates of a session token
+        async fn has_applied_session(
+            &self,
+            request: tonic::Request<super::HasAppliedSessionRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::HasAppliedSessionResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct CollectionsInternalServer<T: CollectionsInternal> {
@@ -3621,6 +4024,56 @@ pub mod collections_internal_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.CollectionsInternal/HasAppliedSession" => {
+                    #[allow(non_camel_case_types)]
+                    struct HasAppliedSessionSvc<T: CollectionsInternal>(pub Arc<T>);
+                    impl<
+                        T: CollectionsInternal,
+                    > tonic::server::UnaryService<super::HasAppliedSessionRequest>
+                    for HasAppliedSessionSvc<T> {
+                        type Response = super::HasAppliedSessionResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::HasAppliedSessionRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as CollectionsInternal>::has_applied_session(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = HasAppliedSessionSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -3774,8 +4227,16 @@ pub struct WriteOrdering {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WriteConsistency {
+    /// Number of replicas which have to confirm the update
+    #[prost(enumeration = "WriteConsistencyType", tag = "1")]
+    pub r#type: i32,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ReadConsistency {
-    #[prost(oneof = "read_consistency::Value", tags = "1, 2")]
+    #[prost(oneof = "read_consistency::Value", tags = "1, 2, 3")]
     pub value: ::core::option::Option<read_consistency::Value>,
 }
 /// Nested message and enum types in `ReadConsistency`.
@@ -3790,6 +4251,9 @@ pub mod read_consistency {
         /// Send request to a specified number of nodes, and return points which are present on all of them
         #[prost(uint64, tag = "2")]
         Factor(u64),
+        /// Read from a replica, which has applied the update that returned this session token
+        #[prost(string, tag = "3")]
+        SessionToken(::prost::alloc::string::String),
     }
 }
 #[derive(serde::Serialize)]
@@ -3911,6 +4375,21 @@ pub struct UpsertPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// If not empty, only apply if all listed points still have the given versions
+    #[prost(message, repeated, tag = "6")]
+    pub expected_versions: ::prost::alloc::vec::Vec<PointVersion>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// How points with the same id are handled, `LastWins` if not specified
+    #[prost(enumeration = "DuplicateIdsPolicy", optional, tag = "8")]
+    pub duplicate_ids: ::core::option::Option<i32>,
+    /// If set, retries of the operation with the same key are applied only once
+    #[prost(string, optional, tag = "9")]
+    pub idempotency_key: ::core::option::Option<::prost::alloc::string::String>,
+    /// If true, points with invalid vectors or oversized payloads are left out and reported in the result, instead of failing the whole update
+    #[prost(bool, optional, tag = "10")]
+    pub skip_invalid_points: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3933,6 +4412,12 @@ pub struct DeletePoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "6")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// If set, retries of the operation with the same key are applied only once
+    #[prost(string, optional, tag = "7")]
+    pub idempotency_key: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3961,6 +4446,9 @@ pub struct GetPoints {
     /// If set, overrides global timeout setting for this request. Unit is seconds.
     #[prost(uint64, optional, tag = "8")]
     pub timeout: ::core::option::Option<u64>,
+    /// If true, return the version of each point, which can be used as expected version of a conditional upsert
+    #[prost(bool, optional, tag = "9")]
+    pub with_version: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -3983,6 +4471,12 @@ pub struct UpdatePointVectors {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "6")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// If true, points with invalid vectors are left out and reported in the result, instead of failing the whole update
+    #[prost(bool, optional, tag = "7")]
+    pub skip_invalid_points: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4019,6 +4513,9 @@ pub struct DeletePointVectors {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "6")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "7")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4047,6 +4544,12 @@ pub struct SetPayloadPoints {
     /// Option for indicate property of payload
     #[prost(string, optional, tag = "8")]
     pub key: ::core::option::Option<::prost::alloc::string::String>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "9")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
+    /// Apply payload as JSON merge patch (RFC 7386), nested objects are merged and null values remove keys. Not compatible with `key`
+    #[prost(bool, optional, tag = "10")]
+    pub merge_patch: ::core::option::Option<bool>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4072,6 +4575,9 @@ pub struct DeletePayloadPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "7")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "8")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4094,6 +4600,9 @@ pub struct ClearPayloadPoints {
     /// Option for custom sharding to specify used shard keys
     #[prost(message, optional, tag = "5")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "6")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4281,6 +4790,30 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[prost(bool, optional, tag = "4")]
     pub indexed_only: ::core::option::Option<bool>,
+    /// Number of candidates kept while descending the upper layers of the HNSW graph.
+    /// By default the descent is greedy and keeps only the single closest candidate.
+    /// Capped by `hnsw_ef`, which is still the number of candidates considered on the bottom layer.
+    #[prost(uint64, optional, tag = "5")]
+    #[validate(range(min = 1))]
+    pub hnsw_beam_width: ::core::option::Option<u64>,
+    /// Maximum number of candidates to visit on the bottom layer of the HNSW graph.
+    /// Once reached, the search stops and returns the best points found so far.
+    /// The search still continues until `limit` points are found, if there are enough.
+    #[prost(uint64, optional, tag = "6")]
+    #[validate(range(min = 1))]
+    pub hnsw_max_candidates: ::core::option::Option<u64>,
+    /// Only use this many dimensions of a sparse query vector, those with the highest absolute weights.
+    /// The other dimensions are ignored, trading recall for latency. Stored vectors are not affected.
+    #[prost(uint64, optional, tag = "7")]
+    #[validate(range(min = 1))]
+    pub sparse_top_k: ::core::option::Option<u64>,
+    /// For debugging: annotate each found point with the shard and segment it was found in.
+    #[prost(bool, optional, tag = "8")]
+    pub provenance: ::core::option::Option<bool>,
+    /// Score points with this distance instead of the distance configured for the vector.
+    /// The search is then exact, over original vectors. Only supported for nearest search by dense vectors.
+    #[prost(enumeration = "Distance", optional, tag = "9")]
+    pub distance: ::core::option::Option<i32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4333,6 +4866,46 @@ pub struct SearchPoints {
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
     #[prost(message, optional, tag = "15")]
     pub sparse_indices: ::core::option::Option<SparseIndices>,
+    /// Adjust the final score of found points with payload values
+    #[prost(message, optional, tag = "16")]
+    #[validate(nested)]
+    pub score_modifier: ::core::option::Option<ScoreModifier>,
+    /// Reorder found points with a reranker, registered on the server
+    #[prost(message, optional, tag = "17")]
+    pub rerank: ::core::option::Option<RerankParams>,
+    /// If provided - cut off results with better scores, e.g. near-duplicates of the query
+    #[prost(float, optional, tag = "18")]
+    pub max_score_threshold: ::core::option::Option<f32>,
+    /// If `true` - return the raw distance to the query vector of found points, in addition to the score
+    #[prost(bool, optional, tag = "19")]
+    pub with_distance: ::core::option::Option<bool>,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ScoreModifier {
+    /// Expression to compute the final score, e.g. `score * ln(1 + popularity)`
+    #[prost(string, tag = "1")]
+    pub expression: ::prost::alloc::string::String,
+    /// Multiplier for the vector score of points for which the expression can't be evaluated. Default is 1.0
+    #[prost(float, optional, tag = "2")]
+    pub default_multiplier: ::core::option::Option<f32>,
+    /// How many times more candidates than `offset + limit` to select by vector similarity and rescore. Default is 4.0
+    #[prost(double, optional, tag = "3")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f64_range_min_1"))]
+    pub oversampling: ::core::option::Option<f64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RerankParams {
+    /// Name of the reranker, registered on the server
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    /// Number of points to retrieve by vector similarity and pass to the reranker. Must be larger than offset + limit. Default is 4 times offset + limit
+    #[prost(uint64, optional, tag = "2")]
+    pub candidates: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4581,6 +5154,10 @@ pub struct RecommendPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "20")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Balance between relevance (1, default) and diversity (0) of the results, using Maximal Marginal Relevance
+    #[prost(float, optional, tag = "21")]
+    #[validate(custom(function = "crate::grpc::validate::validate_f32_range_1"))]
+    pub mmr_lambda: ::core::option::Option<f32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4726,6 +5303,19 @@ pub struct ContextExamplePair {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WeightedContext {
+    /// Pairs of examples of this context
+    #[prost(message, repeated, tag = "1")]
+    pub pairs: ::prost::alloc::vec::Vec<ContextExamplePair>,
+    /// How many times each pair of this context counts in the score, from 1 to 100
+    #[prost(uint32, tag = "2")]
+    #[validate(range(min = 1, max = 100))]
+    pub weight: u32,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct DiscoverPoints {
     /// name of the collection
     #[prost(string, tag = "1")]
@@ -4774,6 +5364,10 @@ pub struct DiscoverPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "14")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Additional contexts with a weight, pairs of `context` have a weight of 1
+    #[prost(message, repeated, tag = "15")]
+    #[validate(nested)]
+    pub weighted_context: ::prost::alloc::vec::Vec<WeightedContext>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4820,6 +5414,12 @@ pub struct CountPoints {
     /// If set, overrides global timeout setting for this request. Unit is seconds.
     #[prost(uint64, optional, tag = "6")]
     pub timeout: ::core::option::Option<u64>,
+    /// If `true` - count each shard at a single version of its data, so that the total is coherent under concurrent updates
+    #[prost(bool, optional, tag = "7")]
+    pub consistent: ::core::option::Option<bool>,
+    /// If `true` - leave unavailable shards out of the count and mark the result as partial, instead of failing
+    #[prost(bool, optional, tag = "8")]
+    pub allow_partial: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -4929,6 +5529,9 @@ pub struct PrefetchQuery {
     /// The location to use for IDs lookup, if not specified - use the current collection and the 'using' vector
     #[prost(message, optional, tag = "8")]
     pub lookup_from: ::core::option::Option<LookupLocation>,
+    /// Weight of the results of this prefetch, if the query fuses the results of its prefetches. Default is 1.0
+    #[prost(float, optional, tag = "9")]
+    pub weight: ::core::option::Option<f32>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -4985,6 +5588,9 @@ pub struct QueryPoints {
     #[prost(uint64, optional, tag = "15")]
     #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
     pub timeout: ::core::option::Option<u64>,
+    /// Seed for random sampling. The same seed gives the same sample of unchanged points.
+    #[prost(uint64, optional, tag = "16")]
+    pub seed: ::core::option::Option<u64>,
 }
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
@@ -5140,7 +5746,7 @@ pub struct SearchMatrixPoints {
     #[prost(message, optional, tag = "2")]
     #[validate(nested)]
     pub filter: ::core::option::Option<Filter>,
-    /// How many points to select and search within. Default is 10.
+    /// How many points to select and search within. Default is 10. Larger samples are reduced to 10000.
     #[prost(uint64, optional, tag = "3")]
     #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_2"))]
     pub sample: ::core::option::Option<u64>,
@@ -5161,6 +5767,9 @@ pub struct SearchMatrixPoints {
     /// Specify in which shards to look for the points, if not specified - look in all shards
     #[prost(message, optional, tag = "8")]
     pub shard_key_selector: ::core::option::Option<ShardKeySelector>,
+    /// Seed for sampling the points. The same seed gives the same sample of unchanged points.
+    #[prost(uint64, optional, tag = "9")]
+    pub seed: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5169,6 +5778,9 @@ pub struct SearchMatrixPairs {
     /// List of pairs of points with scores
     #[prost(message, repeated, tag = "1")]
     pub pairs: ::prost::alloc::vec::Vec<SearchMatrixPair>,
+    /// Number of sampled points, can be lower than requested
+    #[prost(uint64, tag = "2")]
+    pub sample_size: u64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5222,6 +5834,12 @@ pub mod points_update_operation {
         /// Option for custom sharding to specify used shard keys
         #[prost(message, optional, tag = "2")]
         pub shard_key_selector: ::core::option::Option<super::ShardKeySelector>,
+        /// If not empty, only apply if all listed points still have the given versions
+        #[prost(message, repeated, tag = "3")]
+        pub expected_versions: ::prost::alloc::vec::Vec<super::PointVersion>,
+        /// How points with the same id are handled, `LastWins` if not specified
+        #[prost(enumeration = "super::DuplicateIdsPolicy", optional, tag = "4")]
+        pub duplicate_ids: ::core::option::Option<i32>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5241,6 +5859,9 @@ pub mod points_update_operation {
         /// Option for indicate property of payload
         #[prost(string, optional, tag = "4")]
         pub key: ::core::option::Option<::prost::alloc::string::String>,
+        /// Apply payload as JSON merge patch (RFC 7386), nested objects are merged and null values remove keys. Not compatible with `key`
+        #[prost(bool, optional, tag = "5")]
+        pub merge_patch: ::core::option::Option<bool>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5366,6 +5987,9 @@ pub struct UpdateBatchPoints {
     /// Write ordering guarantees
     #[prost(message, optional, tag = "4")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Write consistency guarantees, `write_consistency_factor` of the collection if not specified
+    #[prost(message, optional, tag = "5")]
+    pub write_consistency: ::core::option::Option<WriteConsistency>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5387,6 +6011,23 @@ pub struct UpdateResult {
     /// Operation status
     #[prost(enumeration = "UpdateStatus", tag = "2")]
     pub status: i32,
+    /// Token to read own writes with session read consistency
+    #[prost(string, optional, tag = "4")]
+    pub session_token: ::core::option::Option<::prost::alloc::string::String>,
+    /// Points left out of the update, because their vectors don't match the collection
+    #[prost(message, repeated, tag = "5")]
+    pub invalid_points: ::prost::alloc::vec::Vec<InvalidPoint>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct InvalidPoint {
+    /// Point id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Reason why the point was rejected
+    #[prost(string, tag = "2")]
+    pub error: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5432,6 +6073,23 @@ pub struct ScoredPoint {
     /// Order by value
     #[prost(message, optional, tag = "8")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Raw distance to the query vector, if requested
+    #[prost(float, optional, tag = "9")]
+    pub distance: ::core::option::Option<f32>,
+    /// Shard and segment the point was found in, if requested
+    #[prost(message, optional, tag = "10")]
+    pub provenance: ::core::option::Option<PointProvenance>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointProvenance {
+    /// Shard of the point
+    #[prost(uint32, optional, tag = "1")]
+    pub shard_id: ::core::option::Option<u32>,
+    /// Segment of the point within the shard, the one with the latest version of the point
+    #[prost(uint64, tag = "2")]
+    pub segment_id: u64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5575,6 +6233,12 @@ pub struct ScrollResponse {
 pub struct CountResult {
     #[prost(uint64, tag = "1")]
     pub count: u64,
+    /// Whether the count is exact, or an estimation based on index statistics
+    #[prost(bool, optional, tag = "2")]
+    pub exact: ::core::option::Option<bool>,
+    /// Whether some shards were unavailable and are left out of the count
+    #[prost(bool, optional, tag = "3")]
+    pub partial: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5592,6 +6256,15 @@ pub struct RetrievedPoint {
     /// Order-by value
     #[prost(message, optional, tag = "6")]
     pub order_value: ::core::option::Option<OrderValue>,
+    /// Version of the point, if requested
+    #[prost(uint64, optional, tag = "7")]
+    pub version: ::core::option::Option<u64>,
+    /// Metadata of the returned named vectors
+    #[prost(map = "string, message", tag = "8")]
+    pub vector_metadata: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        Struct,
+    >,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5666,29 +6339,91 @@ pub struct UpdateBatchResponse {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct FacetResponse {
-    #[prost(message, repeated, tag = "1")]
-    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+pub struct FacetResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub hits: ::prost::alloc::vec::Vec<FacetHit>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+    /// Sum of the counts of values which are not in `hits`, because of the limit
+    #[prost(uint64, tag = "3")]
+    pub other_count: u64,
+    /// Whether the counts are exact
+    #[prost(bool, tag = "4")]
+    pub exact: bool,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixPairsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixPairs>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SearchMatrixOffsetsResponse {
+    #[prost(message, optional, tag = "1")]
+    pub result: ::core::option::Option<SearchMatrixOffsets>,
+    /// Time spent to process
+    #[prost(double, tag = "2")]
+    pub time: f64,
+}
+#[derive(validator::Validate)]
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportPointsRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Number of points read per batch
+    #[prost(uint64, optional, tag = "2")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub batch_size: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportPointsResponse {
+    /// Binary dump of the points
+    #[prost(bytes = "vec", tag = "1")]
+    pub dump: ::prost::alloc::vec::Vec<u8>,
+    /// Number of exported points
+    #[prost(uint64, tag = "2")]
+    pub exported: u64,
     /// Time spent to process
-    #[prost(double, tag = "2")]
+    #[prost(double, tag = "3")]
     pub time: f64,
 }
+#[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchMatrixPairsResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<SearchMatrixPairs>,
-    /// Time spent to process
-    #[prost(double, tag = "2")]
-    pub time: f64,
+pub struct ImportPointsRequest {
+    /// Name of the collection
+    #[prost(string, tag = "1")]
+    #[validate(length(min = 1, max = 255))]
+    pub collection_name: ::prost::alloc::string::String,
+    /// Binary dump created by ExportPoints
+    #[prost(bytes = "vec", tag = "2")]
+    pub dump: ::prost::alloc::vec::Vec<u8>,
+    /// Number of points upserted per batch
+    #[prost(uint64, optional, tag = "3")]
+    #[validate(custom(function = "crate::grpc::validate::validate_u64_range_min_1"))]
+    pub batch_size: ::core::option::Option<u64>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
-pub struct SearchMatrixOffsetsResponse {
-    #[prost(message, optional, tag = "1")]
-    pub result: ::core::option::Option<SearchMatrixOffsets>,
+pub struct ImportPointsResponse {
+    /// Number of imported points
+    #[prost(uint64, tag = "1")]
+    pub imported: u64,
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
@@ -5728,7 +6463,7 @@ pub struct MinShould {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Condition {
-    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6")]
+    #[prost(oneof = "condition::ConditionOneOf", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9")]
     #[validate(nested)]
     pub condition_one_of: ::core::option::Option<condition::ConditionOneOf>,
 }
@@ -5750,6 +6485,12 @@ pub mod condition {
         IsNull(super::IsNullCondition),
         #[prost(message, tag = "6")]
         Nested(super::NestedCondition),
+        #[prost(message, tag = "7")]
+        HasVector(super::HasVectorCondition),
+        #[prost(message, tag = "8")]
+        HasField(super::HasFieldCondition),
+        #[prost(message, tag = "9")]
+        IdRange(super::IdRangeCondition),
     }
 }
 #[derive(serde::Serialize)]
@@ -5769,10 +6510,41 @@ pub struct IsNullCondition {
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasFieldCondition {
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct HasIdCondition {
     #[prost(message, repeated, tag = "1")]
     pub has_id: ::prost::alloc::vec::Vec<PointId>,
 }
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct IdRangeCondition {
+    /// point.id < lt
+    #[prost(uint64, optional, tag = "1")]
+    pub lt: ::core::option::Option<u64>,
+    /// point.id > gt
+    #[prost(uint64, optional, tag = "2")]
+    pub gt: ::core::option::Option<u64>,
+    /// point.id >= gte
+    #[prost(uint64, optional, tag = "3")]
+    pub gte: ::core::option::Option<u64>,
+    /// point.id <= lte
+    #[prost(uint64, optional, tag = "4")]
+    pub lte: ::core::option::Option<u64>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HasVectorCondition {
+    #[prost(string, tag = "1")]
+    pub has_vector: ::prost::alloc::string::String,
+}
 #[derive(validator::Validate)]
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5818,7 +6590,7 @@ pub struct FieldCondition {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct Match {
-    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8")]
+    #[prost(oneof = "r#match::MatchValue", tags = "1, 2, 3, 4, 5, 6, 7, 8, 9, 10")]
     pub match_value: ::core::option::Option<r#match::MatchValue>,
 }
 /// Nested message and enum types in `Match`.
@@ -5851,6 +6623,12 @@ pub mod r#match {
         /// Match any other value except those keywords
         #[prost(message, tag = "8")]
         ExceptKeywords(super::RepeatedStrings),
+        /// Match all of these keywords
+        #[prost(message, tag = "9")]
+        AllKeywords(super::RepeatedStrings),
+        /// Match all of these integers
+        #[prost(message, tag = "10")]
+        AllIntegers(super::RepeatedIntegers),
     }
 }
 #[derive(serde::Serialize)]
@@ -5919,6 +6697,9 @@ pub struct GeoRadius {
     /// In meters
     #[prost(float, tag = "2")]
     pub radius: f32,
+    /// Points closer to the center than this are excluded, in meters
+    #[prost(float, optional, tag = "3")]
+    pub inner_radius: ::core::option::Option<f32>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -5947,6 +6728,9 @@ pub struct GeoPolygon {
         custom(function = "crate::grpc::validate::validate_geo_polygon_interiors")
     )]
     pub interiors: ::prost::alloc::vec::Vec<GeoLineString>,
+    /// If true, edges spanning more than 180 degrees of longitude cross the antimeridian, instead of going around the globe the other way. Default: false
+    #[prost(bool, optional, tag = "3")]
+    pub crosses_antimeridian: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6001,6 +6785,23 @@ pub struct PointStruct {
     #[prost(message, optional, tag = "4")]
     #[validate(nested)]
     pub vectors: ::core::option::Option<Vectors>,
+    /// Metadata of individual named vectors, keyed by vector name
+    #[prost(map = "string, message", tag = "5")]
+    pub vector_metadata: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        Struct,
+    >,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVersion {
+    /// Point id
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    /// Version the point is expected to have
+    #[prost(uint64, tag = "2")]
+    pub version: u64,
 }
 #[derive(serde::Serialize)]
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -6047,6 +6848,72 @@ impl WriteOrderingType {
 #[derive(serde::Serialize)]
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum WriteConsistencyType {
+    /// Only the replica on the peer coordinating the update has to confirm it
+    LocalReplica = 0,
+    /// More than half of all replicas of the shard have to confirm the update
+    MajorityReplicas = 1,
+    /// All replicas of the shard have to confirm the update
+    AllReplicas = 2,
+}
+impl WriteConsistencyType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            WriteConsistencyType::LocalReplica => "LocalReplica",
+            WriteConsistencyType::MajorityReplicas => "MajorityReplicas",
+            WriteConsistencyType::AllReplicas => "AllReplicas",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LocalReplica" => Some(Self::LocalReplica),
+            "MajorityReplicas" => Some(Self::MajorityReplicas),
+            "AllReplicas" => Some(Self::AllReplicas),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
+pub enum DuplicateIdsPolicy {
+    /// Only the last point with the id is upserted, default
+    LastWins = 0,
+    /// Only the first point with the id is upserted
+    FirstWins = 1,
+    /// The whole upsert is rejected
+    Error = 2,
+}
+impl DuplicateIdsPolicy {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            DuplicateIdsPolicy::LastWins => "LastWins",
+            DuplicateIdsPolicy::FirstWins => "FirstWins",
+            DuplicateIdsPolicy::Error => "Error",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "LastWins" => Some(Self::LastWins),
+            "FirstWins" => Some(Self::FirstWins),
+            "Error" => Some(Self::Error),
+            _ => None,
+        }
+    }
+}
+#[derive(serde::Serialize)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum ReadConsistencyType {
     /// Send request to all nodes and return points which are present on all of them
     All = 0,
@@ -7046,6 +7913,58 @@ pub mod points_client {
                 .insert(GrpcMethod::new("qdrant.Points", "SearchMatrixOffsets"));
             self.inner.unary(req, path, codec).await
         }
+        /// Export all points of the collection, including vectors and payloads, as a binary dump
+        pub async fn export_points(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExportPointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportPointsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/ExportPoints",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "ExportPoints"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Upsert all points of a binary dump created by ExportPoints
+        pub async fn import_points(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ImportPointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportPointsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/qdrant.Points/ImportPoints",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("qdrant.Points", "ImportPoints"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -7275,6 +8194,22 @@ pub mod points_server {
             tonic::Response<super::SearchMatrixOffsetsResponse>,
             tonic::Status,
         >;
+        /// Export all points of the collection, including vectors and payloads, as a binary dump
+        async fn export_points(
+            &self,
+            request: tonic::Request<super::ExportPointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportPointsResponse>,
+            tonic::Status,
+        >;
+        /// Upsert all points of a binary dump created by ExportPoints
+        async fn import_points(
+            &self,
+            request: tonic::Request<super::ImportPointsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportPointsResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct PointsServer<T: Points> {
@@ -8609,6 +9544,98 @@ pub mod points_server {
                     };
                     Box::pin(fut)
                 }
+                "/qdrant.Points/ExportPoints" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportPointsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ExportPointsRequest>
+                    for ExportPointsSvc<T> {
+                        type Response = super::ExportPointsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExportPointsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::export_points(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExportPointsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/qdrant.Points/ImportPoints" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportPointsSvc<T: Points>(pub Arc<T>);
+                    impl<
+                        T: Points,
+                    > tonic::server::UnaryService<super::ImportPointsRequest>
+                    for ImportPointsSvc<T> {
+                        type Response = super::ImportPointsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ImportPointsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as Points>::import_points(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ImportPointsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
@@ -8672,6 +9699,9 @@ pub struct SyncPoints {
     pub to_id: ::core::option::Option<PointId>,
     #[prost(message, optional, tag = "6")]
     pub ordering: ::core::option::Option<WriteOrdering>,
+    /// Versions the points have in the source shard
+    #[prost(message, repeated, tag = "7")]
+    pub versions: ::prost::alloc::vec::Vec<PointVersion>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8698,6 +9728,32 @@ pub struct UpsertPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// TTL timestamp to set on points which don't set it in their payload
+    #[prost(message, optional, tag = "4")]
+    pub ttl_stamp: ::core::option::Option<TtlStamp>,
+    /// Unix timestamp in microseconds, which versions of upserted points are derived from
+    #[prost(uint64, optional, tag = "5")]
+    pub version_timestamp: ::core::option::Option<u64>,
+    /// Versions assigned to points as they are, when copying points between shards
+    #[prost(message, repeated, tag = "6")]
+    pub versions: ::prost::alloc::vec::Vec<PointVersion>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TtlStamp {
+    /// Top-level payload key of the timestamp
+    #[prost(string, tag = "1")]
+    pub key: ::prost::alloc::string::String,
+    /// Unix timestamp in seconds
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    /// Set the timestamp on points which don't exist yet
+    #[prost(bool, tag = "3")]
+    pub set_on_insert: bool,
+    /// Set the timestamp on existing points, instead of keeping their current one
+    #[prost(bool, tag = "4")]
+    pub refresh_on_update: bool,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8724,6 +9780,18 @@ pub struct UpdateVectorsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Vectors to delete from points as part of the update
+    #[prost(message, repeated, tag = "4")]
+    pub delete_vectors: ::prost::alloc::vec::Vec<PointVectorName>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PointVectorName {
+    #[prost(message, optional, tag = "1")]
+    pub id: ::core::option::Option<PointId>,
+    #[prost(string, tag = "2")]
+    pub vector_name: ::prost::alloc::string::String,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8750,6 +9818,26 @@ pub struct SetPayloadPointsInternal {
     pub shard_id: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "3")]
     pub clock_tag: ::core::option::Option<ClockTag>,
+    /// Apply payload as JSON merge patch (RFC 7386)
+    #[prost(bool, optional, tag = "4")]
+    pub merge_patch: ::core::option::Option<bool>,
+    /// Copy a payload field, instead of setting the payload
+    #[prost(message, optional, tag = "5")]
+    pub copy_field: ::core::option::Option<CopyPayloadFieldInternal>,
+}
+#[derive(serde::Serialize)]
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CopyPayloadFieldInternal {
+    /// Field to copy the value from
+    #[prost(string, tag = "1")]
+    pub from: ::prost::alloc::string::String,
+    /// Field to copy the value to
+    #[prost(string, tag = "2")]
+    pub to: ::prost::alloc::string::String,
+    /// Remove the field to copy the value from
+    #[prost(bool, tag = "3")]
+    pub move_field: bool,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8897,6 +9985,9 @@ pub struct ContextPair {
     #[prost(message, optional, tag = "2")]
     #[validate(nested)]
     pub negative: ::core::option::Option<Vector>,
+    /// How many times this pair counts in the score, default is 1
+    #[prost(uint32, optional, tag = "3")]
+    pub weight: ::core::option::Option<u32>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -8978,6 +10069,10 @@ pub struct CoreSearchPoints {
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
     #[prost(message, optional, tag = "11")]
     pub read_consistency: ::core::option::Option<ReadConsistency>,
+    #[prost(float, optional, tag = "12")]
+    pub max_score_threshold: ::core::option::Option<f32>,
+    #[prost(bool, optional, tag = "13")]
+    pub with_distance: ::core::option::Option<bool>,
 }
 #[derive(serde::Serialize)]
 #[derive(validator::Validate)]
@@ -9088,6 +10183,9 @@ pub mod raw_query {
         pub positive: ::core::option::Option<super::RawVector>,
         #[prost(message, optional, tag = "2")]
         pub negative: ::core::option::Option<super::RawVector>,
+        /// How many times this pair counts in the score, default is 1
+        #[prost(uint32, optional, tag = "3")]
+        pub weight: ::core::option::Option<u32>,
     }
     #[derive(serde::Serialize)]
     #[allow(clippy::derive_partial_eq_without_eq)]
@@ -9147,6 +10245,9 @@ pub struct QueryShardPoints {
     pub with_payload: ::core::option::Option<WithPayloadSelector>,
     #[prost(message, optional, tag = "10")]
     pub with_vectors: ::core::option::Option<WithVectorsSelector>,
+    /// Seed for random sampling
+    #[prost(uint64, optional, tag = "11")]
+    pub seed: ::core::option::Option<u64>,
 }
 /// Nested message and enum types in `QueryShardPoints`.
 pub mod query_shard_points {
@@ -9195,6 +10296,8 @@ pub mod query_shard_points {
         pub params: ::core::option::Option<super::SearchParams>,
         #[prost(float, optional, tag = "7")]
         pub score_threshold: ::core::option::Option<f32>,
+        #[prost(float, optional, tag = "8")]
+        pub weight: ::core::option::Option<f32>,
     }
 }
 #[derive(serde::Serialize)]
@@ -9298,6 +10401,9 @@ pub struct FacetResponseInternal {
     /// Time spent to process
     #[prost(double, tag = "2")]
     pub time: f64,
+    /// Sum of the counts of values which are not in `hits`, because of the limit
+    #[prost(uint64, tag = "3")]
+    pub other_count: u64,
 }
 /// Generated client implementations.
 pub mod points_internal_client {
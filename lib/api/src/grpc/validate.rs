@@ -107,6 +107,9 @@ impl Validate for grpc::update_collection_cluster_setup_request::Operation {
             Operation::CreateShardKey(op) => op.validate(),
             Operation::DeleteShardKey(op) => op.validate(),
             Operation::RestartTransfer(op) => op.validate(),
+            Operation::ChangeReplicationFactor(op) => op.validate(),
+            Operation::MakeReplicaReadOnly(op) => op.validate(),
+            Operation::PromoteReplica(op) => op.validate(),
         }
     }
 }
@@ -180,6 +183,21 @@ impl Validate for grpc::RestartTransfer {
     }
 }
 
+impl Validate for grpc::ChangeReplicationFactor {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        if self.replication_factor == 0 {
+            let mut errors = ValidationErrors::new();
+            errors.add(
+                "replication_factor",
+                ValidationError::new("Replication factor must be greater than 0"),
+            );
+            return Err(errors);
+        }
+
+        Ok(())
+    }
+}
+
 impl Validate for grpc::condition::ConditionOneOf {
     fn validate(&self) -> Result<(), ValidationErrors> {
         use grpc::condition::ConditionOneOf;
@@ -189,7 +207,10 @@ impl Validate for grpc::condition::ConditionOneOf {
             ConditionOneOf::Filter(filter) => filter.validate(),
             ConditionOneOf::IsEmpty(_) => Ok(()),
             ConditionOneOf::HasId(_) => Ok(()),
+            ConditionOneOf::HasVector(_) => Ok(()),
+            ConditionOneOf::IdRange(_) => Ok(()),
             ConditionOneOf::IsNull(_) => Ok(()),
+            ConditionOneOf::HasField(_) => Ok(()),
         }
     }
 }
@@ -311,11 +332,26 @@ pub fn validate_f32_range_min_0_5_max_1(value: &f32) -> Result<(), ValidationErr
     validate_range_generic(value, Some(&0.5), Some(&1.0))
 }
 
+/// Validate the value is in `[0.0, 1.0]`.
+pub fn validate_f32_range_1(value: &f32) -> Result<(), ValidationError> {
+    validate_range_generic(value, Some(&0.0), Some(&1.0))
+}
+
 /// Validate the value is in `[0.0, 1.0]`.
 pub fn validate_f64_range_1(value: &f64) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&0.0), Some(&1.0))
 }
 
+/// Validate the value is in `[0.0, 10.0]`.
+pub fn validate_f64_range_10(value: &f64) -> Result<(), ValidationError> {
+    validate_range_generic(value, Some(&0.0), Some(&10.0))
+}
+
+/// Validate the value is in `[0.0, ]`.
+pub fn validate_f64_range_min_0(value: &f64) -> Result<(), ValidationError> {
+    validate_range_generic(value, Some(&0.0), None)
+}
+
 /// Validate the value is in `[1.0, ]`.
 pub fn validate_f64_range_min_1(value: &f64) -> Result<(), ValidationError> {
     validate_range_generic(value, Some(&1.0), None)
@@ -510,6 +546,7 @@ mod tests {
         let bad_polygon = GeoPolygon {
             exterior: Some(GeoLineString { points: vec![] }),
             interiors: vec![],
+            crosses_antimeridian: None,
         };
         assert!(
             bad_polygon.validate().is_err(),
@@ -525,6 +562,7 @@ mod tests {
                 ],
             }),
             interiors: vec![],
+            crosses_antimeridian: None,
         };
         assert!(
             bad_polygon.validate().is_err(),
@@ -541,6 +579,7 @@ mod tests {
                 ],
             }),
             interiors: vec![],
+            crosses_antimeridian: None,
         };
 
         assert!(
@@ -565,6 +604,7 @@ mod tests {
                     GeoPoint { lat: 2., lon: 2. },
                 ],
             }],
+            crosses_antimeridian: None,
         };
 
         assert!(
@@ -582,6 +622,7 @@ mod tests {
                 ],
             }),
             interiors: vec![],
+            crosses_antimeridian: None,
         };
         assert!(
             good_polygon.validate().is_ok(),
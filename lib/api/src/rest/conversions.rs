@@ -184,6 +184,8 @@ impl From<segment::types::ScoredPoint> for ScoredPoint {
             vector: value.vector.map(From::from),
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
+            distance: value.distance,
+            provenance: value.provenance,
         }
     }
 }
@@ -198,6 +200,8 @@ impl From<ScoredPoint> for segment::types::ScoredPoint {
             vector: value.vector.map(From::from),
             shard_key: value.shard_key,
             order_value: value.order_value.map(From::from),
+            distance: value.distance,
+            provenance: value.provenance,
         }
     }
 }
@@ -277,6 +281,8 @@ impl From<segment::data_types::facets::FacetResponse> for FacetResponse {
     fn from(value: segment::data_types::facets::FacetResponse) -> Self {
         Self {
             hits: value.hits.into_iter().map(From::from).collect(),
+            other_count: value.other_count,
+            exact: value.exact,
         }
     }
 }
@@ -4,6 +4,7 @@ use common::types::ScoreType;
 use schemars::JsonSchema;
 use segment::common::utils::MaybeOneOrMany;
 use segment::data_types::order_by::OrderBy;
+use segment::data_types::score_modifier::ScoreModifier;
 use segment::json_path::JsonPath;
 use segment::types::{
     Filter, IntPayloadType, PointIdType, SearchParams, ShardKey, WithPayloadInterface, WithVector,
@@ -132,12 +133,21 @@ pub struct ScoredPoint {
     /// Vector of the point
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vector: Option<VectorStruct>,
+    /// Metadata of the returned vectors of the point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_metadata: Option<segment::types::VectorMetadata>,
     /// Shard Key
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKey>,
     /// Order-by value
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
+    /// Raw distance to the query vector, only returned if requested with `with_distance`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub distance: Option<ScoreType>,
+    /// Shard and segment the point was found in, only returned if requested with `provenance`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<segment::types::PointProvenance>,
 }
 
 /// Point data
@@ -152,11 +162,17 @@ pub struct Record {
     /// Vector of the point
     #[serde(skip_serializing_if = "Option::is_none")]
     pub vector: Option<VectorStruct>,
+    /// Metadata of the returned vectors of the point
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vector_metadata: Option<segment::types::VectorMetadata>,
     /// Shard Key
     #[serde(skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<segment::types::ShardKey>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub order_value: Option<segment::data_types::order_by::OrderValue>,
+    /// Point version, if requested, can be used as expected version of a conditional upsert
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<segment::types::SeqNumberType>,
 }
 
 /// Vector data separator for named and unnamed modes
@@ -257,6 +273,10 @@ pub struct QueryRequestInternal {
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Seed for random sampling. The same seed gives the same sample of unchanged points.
+    #[serde(default)]
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -386,6 +406,10 @@ pub struct Prefetch {
     /// Note: the other collection vectors should have the same vector size as the 'using' vector in the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Weight of the results of this prefetch, if the query fuses the results of its prefetches.
+    /// Results of prefetches with a higher weight contribute more to the fused score. Default is 1.0.
+    pub weight: Option<f32>,
 }
 
 /// How to use positive and negative examples to find the results, default is `average_vector`:
@@ -600,6 +624,39 @@ pub struct SearchRequestInternal {
     /// Score of the returned result might be higher or smaller than the threshold depending on the
     /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
     pub score_threshold: Option<ScoreType>,
+    /// Define a maximal score threshold for the result, together with `score_threshold` it forms a
+    /// score range. If defined, more similar results will not be returned, e.g. to exclude
+    /// near-duplicates of the query. Results with a score equal to the threshold are returned.
+    /// Score of the returned result might be higher or smaller than the threshold depending on the
+    /// Distance function used. E.g. for cosine similarity only lower scores will be returned.
+    /// With quantization, both thresholds apply to the final scores: rescored with original
+    /// vectors if rescoring is enabled, otherwise approximate scores of quantized vectors.
+    pub max_score_threshold: Option<ScoreType>,
+    /// Return the raw distance to the query vector of found points, in addition to the score.
+    /// E.g. Euclidean distance, or `1 - score` for cosine similarity. Default is false.
+    /// With quantization, the distance is exact if rescoring is enabled, otherwise it is
+    /// approximated from the quantized vectors, just like the score.
+    pub with_distance: Option<bool>,
+    /// Adjust the final score of found points with an expression over numeric payload fields.
+    /// Only the top candidates by vector similarity are rescored, see `oversampling`.
+    #[validate(nested)]
+    pub score_modifier: Option<ScoreModifier>,
+    /// Reorder the points found by vector similarity with a reranker, registered on the server.
+    /// Can't be combined with `score_modifier`.
+    #[validate(nested)]
+    pub rerank: Option<RerankParams>,
+}
+
+/// Second stage of a search, which reorders the points found by vector similarity
+#[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+pub struct RerankParams {
+    /// Name of the reranker, registered on the server
+    #[validate(length(min = 1))]
+    pub name: String,
+    /// Number of points to retrieve by vector similarity and pass to the reranker.
+    /// Must be larger than `offset + limit`. Default is 4 times `offset + limit`.
+    #[validate(range(min = 1))]
+    pub candidates: Option<usize>,
 }
 
 #[derive(Validate, Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
@@ -679,7 +736,7 @@ pub struct SearchMatrixRequestInternal {
     /// Look only for points which satisfies this conditions
     #[validate(nested)]
     pub filter: Option<Filter>,
-    /// How many points to select and search within. Default is 10.
+    /// How many points to select and search within. Default is 10. Larger samples are reduced to 10000.
     #[validate(range(min = 2))]
     pub sample: Option<usize>,
     /// How many neighbours per sample to find. Default is 3.
@@ -687,6 +744,8 @@ pub struct SearchMatrixRequestInternal {
     pub limit: Option<usize>,
     /// Define which vector name to use for querying. If missing, the default vector is used.
     pub using: Option<String>,
+    /// Seed for sampling the points. The same seed gives the same sample of unchanged points.
+    pub seed: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, JsonSchema, Validate)]
@@ -737,6 +796,8 @@ impl SearchMatrixPair {
 pub struct SearchMatrixPairsResponse {
     /// List of pairs of points with scores
     pub pairs: Vec<SearchMatrixPair>,
+    /// Number of sampled points, can be lower than requested
+    pub sample_size: u64,
 }
 
 #[derive(Debug, JsonSchema, Serialize, Deserialize, Validate)]
@@ -780,4 +841,8 @@ pub struct FacetValueHit {
 #[derive(Debug, Serialize, JsonSchema)]
 pub struct FacetResponse {
     pub hits: Vec<FacetValueHit>,
+    /// Sum of the counts of values which are not in `hits`, because of the limit
+    pub other_count: usize,
+    /// Whether the counts are exact. Approximate counts may be off when points are being updated
+    pub exact: bool,
 }
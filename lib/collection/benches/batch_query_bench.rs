@@ -1,6 +1,7 @@
 use std::sync::Arc;
 
 use api::rest::SearchRequestInternal;
+use cancel::CancellationToken;
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
 use collection::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct,
@@ -51,18 +52,27 @@ fn setup() -> (TempDir, LocalShard) {
         params: collection_params,
         optimizer_config: OptimizersConfig {
             deleted_threshold: 0.9,
+            payload_index_deleted_threshold: None,
             vacuum_min_vector_number: 1000,
             default_segment_number: 2,
             max_segment_size: Some(100_000),
             memmap_threshold: Some(100_000),
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
+            min_segment_size: None,
+            max_flush_delay_sec: None,
             max_optimization_threads: Some(2),
+            deduplicate_vectors: None,
+            deduplication_epsilon: None,
+            defer_indexing: None,
+            split_oversized_segments: None,
         },
         wal_config,
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let optimizers_config = collection_config.optimizer_config.clone();
@@ -112,6 +122,7 @@ fn create_rnd_batch() -> CollectionUpdateOperations {
             id: (i as u64).into(),
             vector: VectorStructInternal::from(vectors).into(),
             payload: Some(Payload(payload_map)),
+            vector_metadata: None,
         };
         points.push(point);
     }
@@ -167,6 +178,7 @@ fn batch_search_bench(c: &mut Criterion) {
                             limit: 10,
                             offset: 0,
                             with_payload: WithPayloadInterface::Bool(true),
+                            seed: None,
                             with_vector: WithVector::Bool(false),
                             score_threshold: None,
                         };
@@ -198,13 +210,22 @@ fn batch_search_bench(c: &mut Criterion) {
                             with_payload: Some(WithPayloadInterface::Bool(true)),
                             with_vector: None,
                             score_threshold: None,
+                            score_modifier: None,
+                            rerank: None,
+                            max_score_threshold: None,
+                            with_distance: None,
                         };
                         searches.push(search_query.into());
                     }
 
                     let search_query = CoreSearchRequestBatch { searches };
                     let result = shard
-                        .core_search(Arc::new(search_query), search_runtime_handle, None)
+                        .core_search(
+                            Arc::new(search_query),
+                            search_runtime_handle,
+                            None,
+                            CancellationToken::new(),
+                        )
                         .await
                         .unwrap();
                     assert!(!result.is_empty());
@@ -244,6 +265,7 @@ fn batch_rrf_query_bench(c: &mut Criterion) {
                                     params: None,
                                     filter: None,
                                     score_threshold: None,
+                                    weight: None,
                                 },
                                 ShardPrefetch {
                                     prefetches: vec![],
@@ -252,6 +274,7 @@ fn batch_rrf_query_bench(c: &mut Criterion) {
                                     params: None,
                                     filter: None,
                                     score_threshold: None,
+                                    weight: None,
                                 },
                             ],
                             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
@@ -260,6 +283,7 @@ fn batch_rrf_query_bench(c: &mut Criterion) {
                             limit: 10,
                             offset: 0,
                             with_payload: WithPayloadInterface::Bool(true),
+                            seed: None,
                             with_vector: WithVector::Bool(false),
                             score_threshold: None,
                         };
@@ -306,6 +330,7 @@ fn batch_rescore_bench(c: &mut Criterion) {
                                 params: None,
                                 filter: None,
                                 score_threshold: None,
+                                weight: None,
                             }],
                             query: Some(ScoringQuery::Vector(query2.into())),
                             filter: filter.clone(),
@@ -313,6 +338,7 @@ fn batch_rescore_bench(c: &mut Criterion) {
                             limit: 10,
                             offset: 0,
                             with_payload: WithPayloadInterface::Bool(true),
+                            seed: None,
                             with_vector: WithVector::Bool(false),
                             score_threshold: None,
                         };
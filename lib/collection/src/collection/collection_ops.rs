@@ -1,10 +1,13 @@
 use std::cmp;
+use std::collections::BTreeMap;
 use std::sync::Arc;
 
 use futures::{future, TryStreamExt as _};
 use lazy_static::lazy_static;
 use segment::types::QuantizationConfig;
+use segment::utils::mem::Mem;
 use semver::Version;
+use validator::Validate as _;
 
 use super::Collection;
 use crate::operations::config_diff::*;
@@ -12,7 +15,8 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::replica_set::{Change, ReplicaState};
-use crate::shards::shard::PeerId;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::shard_holder::shard_not_found_error;
 
 lazy_static! {
     /// When dropping a shard, only cancel all related shard transfers to and from it when all nodes
@@ -50,7 +54,9 @@ impl Collection {
     ) -> CollectionResult<()> {
         {
             let mut config = self.collection_config.write().await;
-            config.hnsw_config = hnsw_config_diff.update(&config.hnsw_config)?;
+            let hnsw_config = hnsw_config_diff.update(&config.hnsw_config)?;
+            hnsw_config.validate()?;
+            config.hnsw_config = hnsw_config;
         }
         self.collection_config.read().await.save(&self.path)?;
         Ok(())
@@ -74,6 +80,23 @@ impl Collection {
         Ok(())
     }
 
+    /// Adds new named vectors:
+    /// Saves new params on disk
+    ///
+    /// Existing points have no values for the new vectors.
+    ///
+    /// After this, `recreate_optimizers_blocking` must be called, which also adds the new vectors
+    /// to the segments of local shards.
+    pub async fn add_vectors(
+        &self,
+        new_vectors: &BTreeMap<String, VectorParams>,
+    ) -> CollectionResult<()> {
+        let mut config = self.collection_config.write().await;
+        config.params.add_vectors(new_vectors)?;
+        config.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates sparse vectors config:
     /// Saves new params on disk
     ///
@@ -110,6 +133,50 @@ impl Collection {
         Ok(())
     }
 
+    /// Updates optimization params overrides of individual shards:
+    /// Saves new params on disk
+    ///
+    /// Each given diff replaces the previous override of its shard, an empty diff removes it.
+    /// Overrides are applied on top of the collection optimization params, and must result in a
+    /// valid config.
+    ///
+    /// After this, `recreate_optimizers_blocking` must be called to create new optimizers using
+    /// the updated configuration.
+    pub async fn update_shard_optimizer_params_from_diff(
+        &self,
+        shard_optimizer_config_diffs: BTreeMap<ShardId, OptimizersConfigDiff>,
+    ) -> CollectionResult<()> {
+        {
+            let shard_holder = self.shards_holder.read().await;
+            if let Some(&shard_id) = shard_optimizer_config_diffs
+                .keys()
+                .find(|shard_id| !shard_holder.contains_shard(shard_id))
+            {
+                return Err(shard_not_found_error(shard_id));
+            }
+        }
+
+        {
+            let mut config = self.collection_config.write().await;
+
+            // Validate all overrides before applying any of them
+            for diff in shard_optimizer_config_diffs.values() {
+                let optimizer_config = diff.clone().update(&config.optimizer_config)?;
+                optimizer_config.validate()?;
+            }
+
+            for (shard_id, diff) in shard_optimizer_config_diffs {
+                if diff.is_empty() {
+                    config.shard_optimizer_config.remove(&shard_id);
+                } else {
+                    config.shard_optimizer_config.insert(shard_id, diff);
+                }
+            }
+        }
+        self.collection_config.read().await.save(&self.path)?;
+        Ok(())
+    }
+
     /// Updates shard optimization params: Saves new params on disk
     ///
     /// After this, `recreate_optimizers_blocking` must be called to create new optimizers using
@@ -245,6 +312,72 @@ impl Collection {
         Ok(())
     }
 
+    /// Persist WAL and segments of all local shards to disk.
+    ///
+    /// Returns once all data is synced to disk. Meant to be called right before taking an
+    /// external, filesystem-level snapshot of the storage.
+    pub async fn flush(&self) -> CollectionResult<()> {
+        let shard_holder = self.shards_holder.read().await;
+        for replica_set in shard_holder.all_shards() {
+            replica_set.flush().await?;
+        }
+        Ok(())
+    }
+
+    /// Describe the segments of all shards stored on this peer, for debugging.
+    ///
+    /// Segments of each shard are a consistent snapshot: a segment which is being optimized is
+    /// listed once, either before or after the optimized segment replaces it.
+    pub async fn list_segments(&self) -> Vec<SegmentDescription> {
+        let shard_holder = self.shards_holder.read().await;
+        let mut segments = Vec::new();
+        for replica_set in shard_holder.all_shards() {
+            segments.extend(replica_set.local_segments().await);
+        }
+        segments
+    }
+
+    /// Read memory-mapped vector and index data of local shards into the page cache,
+    /// so that first searches after a restart don't have to wait for disk.
+    ///
+    /// Shards are warmed up one after another, sharing a single `max_bytes` budget.
+    pub async fn warmup(&self, request: WarmupRequest) -> CollectionResult<WarmupResult> {
+        let WarmupRequest {
+            shard_ids,
+            with_quantization,
+            max_bytes,
+        } = request;
+
+        // Reading more than available memory would evict the data we just read
+        let max_bytes =
+            max_bytes.unwrap_or_else(|| Mem::new().available_memory_bytes() as usize / 2);
+
+        let shard_holder = self.shards_holder.read().await;
+
+        let shards: Vec<_> = match &shard_ids {
+            Some(shard_ids) => shard_ids
+                .iter()
+                .map(|shard_id| {
+                    shard_holder
+                        .get_shard(shard_id)
+                        .ok_or_else(|| shard_not_found_error(*shard_id))
+                })
+                .collect::<CollectionResult<_>>()?,
+            None => shard_holder.all_shards().collect(),
+        };
+
+        let mut result = WarmupResult::default();
+        for replica_set in shards {
+            let remaining_bytes = max_bytes.saturating_sub(result.warmed_bytes);
+            let shard_result = replica_set
+                .warmup(with_quantization, remaining_bytes)
+                .await?;
+            result.merge(shard_result);
+        }
+
+        Ok(result)
+    }
+
     pub async fn strict_mode_config(&self) -> Option<StrictModeConfig> {
         self.collection_config
             .read()
@@ -310,6 +443,8 @@ impl Collection {
         let count_request = Arc::new(CountRequestInternal {
             filter: None,
             exact: false, // Don't need exact count of unique ids here, only size estimation
+            consistent: false,
+            allow_partial: false,
         });
         let shard_to_key = shards_holder.get_shard_id_to_key_mapping();
 
@@ -5,6 +5,9 @@ use api::rest::{
     SearchMatrixOffsetsResponse, SearchMatrixPair, SearchMatrixPairsResponse,
     SearchMatrixRequestInternal,
 };
+use cancel::CancellationToken;
+use common::random::seeded_rng;
+use rand::Rng;
 use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
 use segment::types::{Condition, Filter, HasIdCondition, PointIdType, ScoredPoint, WithVector};
 
@@ -27,11 +30,15 @@ pub struct CollectionSearchMatrixRequest {
     pub limit_per_sample: usize,
     pub filter: Option<Filter>,
     pub using: String,
+    /// Seed for sampling the points
+    pub seed: Option<u64>,
 }
 
 impl CollectionSearchMatrixRequest {
     pub const DEFAULT_LIMIT_PER_SAMPLE: usize = 3;
     pub const DEFAULT_SAMPLE: usize = 10;
+    /// Every sampled point is searched within all other samples, so the cost grows quadratically.
+    pub const MAX_SAMPLE: usize = 10_000;
 }
 
 impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
@@ -41,6 +48,7 @@ impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
             limit,
             filter,
             using,
+            seed,
         } = request;
         Self {
             sample_size: sample.unwrap_or(CollectionSearchMatrixRequest::DEFAULT_SAMPLE),
@@ -48,6 +56,7 @@ impl From<SearchMatrixRequestInternal> for CollectionSearchMatrixRequest {
                 .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_LIMIT_PER_SAMPLE),
             filter,
             using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
+            seed,
         }
     }
 }
@@ -91,6 +100,7 @@ impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
             nearests,
         } = response;
 
+        let sample_size = sample_ids.len() as u64;
         let mut pairs = vec![];
 
         for (a, scored_points) in sample_ids.into_iter().zip(nearests.into_iter()) {
@@ -103,7 +113,7 @@ impl From<CollectionSearchMatrixResponse> for SearchMatrixPairsResponse {
             }
         }
 
-        Self { pairs }
+        Self { pairs, sample_size }
     }
 }
 
@@ -111,7 +121,10 @@ impl From<CollectionSearchMatrixResponse> for api::grpc::qdrant::SearchMatrixPai
     fn from(response: CollectionSearchMatrixResponse) -> Self {
         let rest_result = SearchMatrixPairsResponse::from(response);
         let pairs = rest_result.pairs.into_iter().map(From::from).collect();
-        Self { pairs }
+        Self {
+            pairs,
+            sample_size: rest_result.sample_size,
+        }
     }
 }
 
@@ -143,12 +156,17 @@ impl Collection {
             limit_per_sample,
             filter,
             using,
+            seed,
         } = request;
         if limit_per_sample == 0 || sample_size == 0 {
             return Ok(Default::default());
         }
+        // larger samples are reduced, the effective size is reported by the response
+        let sample_size = sample_size.min(CollectionSearchMatrixRequest::MAX_SAMPLE);
 
         let mut sampled_points: Vec<(_, _)> = Vec::with_capacity(sample_size);
+        // Every try samples with its own seed, derived from the seed of the request
+        let mut seeds = seeded_rng(seed);
 
         // Sampling multiple times because we might not have enough points with the named vector
         for _ in 0..SAMPLING_TRIES {
@@ -182,12 +200,13 @@ impl Collection {
                 params: None,
                 with_vector: WithVector::Selector(vec![using.clone()]), // retrieve the vector
                 with_payload: Default::default(),
+                seed: seed.map(|_| seeds.gen()),
             };
 
             let sampling_response = self
                 .query(
                     sampling_query.clone(),
-                    read_consistency,
+                    read_consistency.clone(),
                     shard_selection.clone(),
                     timeout,
                 )
@@ -245,13 +264,23 @@ impl Collection {
                 params: None,
                 with_vector: None,
                 with_payload: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
             });
         }
 
         // run batch search request
         let batch_request = CoreSearchRequestBatch { searches };
         let nearest = self
-            .core_search_batch(batch_request, read_consistency, shard_selection, timeout)
+            .core_search_batch(
+                batch_request,
+                read_consistency,
+                shard_selection,
+                timeout,
+                CancellationToken::new(),
+            )
             .await?;
 
         Ok(CollectionSearchMatrixResponse {
@@ -276,6 +305,8 @@ mod tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
@@ -303,6 +334,7 @@ mod tests {
                 SearchMatrixPair::new(3, 1, 0.6),
                 SearchMatrixPair::new(3, 3, 0.5),
             ],
+            sample_size: 3,
         };
 
         let actual = SearchMatrixPairsResponse::from(response);
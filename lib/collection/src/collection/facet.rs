@@ -21,7 +21,11 @@ impl Collection {
         timeout: Option<Duration>,
     ) -> CollectionResult<FacetResponse> {
         if request.limit == 0 {
-            return Ok(FacetResponse { hits: vec![] });
+            return Ok(FacetResponse {
+                hits: vec![],
+                other_count: 0,
+                exact: request.exact,
+            });
         }
 
         let request = Arc::new(request);
@@ -32,7 +36,7 @@ impl Collection {
         let shards_reads_f = target_shards.iter().map(|(shard, _shard_key)| {
             shard.facet(
                 request.clone(),
-                read_consistency,
+                read_consistency.clone(),
                 shard_selection.is_shard_id(),
                 timeout,
             )
@@ -40,16 +44,31 @@ impl Collection {
 
         let shards_results = future::try_join_all(shards_reads_f).await?;
 
-        let hits = aggregate_facet_hits(
+        let exact = shards_results.iter().all(|response| response.exact);
+        let shards_other_count: usize = shards_results
+            .iter()
+            .map(|response| response.other_count)
+            .sum();
+
+        let mut hits = aggregate_facet_hits(
             shards_results
                 .into_iter()
-                .flat_map(|FacetResponse { hits }| hits),
+                .flat_map(|FacetResponse { hits, .. }| hits),
         )
         .into_iter()
         .map(|(value, count)| FacetValueHit { value, count })
-        .k_largest(request.limit)
-        .collect();
+        .collect_vec();
+
+        // Values which don't fit into the limit are summed up into a single "other" count
+        hits.sort_unstable_by(|a, b| b.cmp(a));
+        let other_hits = hits.split_off(request.limit.min(hits.len()));
+        let other_count =
+            shards_other_count + other_hits.iter().map(|hit| hit.count).sum::<usize>();
 
-        Ok(FacetResponse { hits })
+        Ok(FacetResponse {
+            hits,
+            other_count,
+            exact,
+        })
     }
 }
@@ -0,0 +1,292 @@
+//! Health report of a collection, for operators to assess a collection with a single call.
+//!
+//! Shard replica states and transfers are known for the whole cluster, while WAL lag, disk usage,
+//! optimizations and load failures are only known for the replicas stored on this peer.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+
+use super::Collection;
+use crate::operations::types::{
+    CollectionHealth, HealthIssue, HealthStatus, LocalShardHealth, ShardHealth,
+};
+use crate::shards::replica_set::ReplicaState;
+use crate::shards::shard::{PeerId, ShardId};
+use crate::shards::transfer::ShardTransfer;
+
+/// An optimization running longer than this is reported as possibly stuck.
+const STUCK_OPTIMIZATION_DURATION: Duration = Duration::from_secs(3 * 60 * 60);
+
+/// Number of operations in the WAL not applied to the segments, above which the update handler
+/// is considered to fall behind.
+const MAX_WAL_LAG: u64 = 10_000;
+
+impl Collection {
+    /// Aggregate the health of all shards into a single report.
+    ///
+    /// The collection is red if any shard is unavailable or failed, i.e. a replica is dead, a
+    /// replica failed to load, optimizations failed or the disk is full. It is yellow if it is in
+    /// transition or needs attention, i.e. a shard transfer is in progress, an optimization is
+    /// running for too long, or the WAL is not applied in time.
+    pub async fn health(&self) -> CollectionHealth {
+        let shard_holder = self.shards_holder.read().await;
+        let transfers = shard_holder.get_transfers(|_| true);
+        let now = Utc::now();
+
+        let mut issues = Vec::new();
+        let mut shards = Vec::new();
+
+        for (&shard_id, replica_set) in shard_holder.get_shards() {
+            let replicas = replica_set.peers();
+            let local = replica_set.local_health().await;
+
+            let shard_issues = check_shard(shard_id, &replicas, &transfers, local.as_ref(), now);
+            let status = worst_status(&shard_issues);
+            issues.extend(shard_issues);
+
+            shards.push(ShardHealth {
+                shard_id,
+                status,
+                replicas,
+                local,
+            });
+        }
+
+        shards.sort_unstable_by_key(|shard| shard.shard_id);
+
+        CollectionHealth {
+            status: worst_status(&issues),
+            issues,
+            shards,
+        }
+    }
+}
+
+fn worst_status(issues: &[HealthIssue]) -> HealthStatus {
+    issues
+        .iter()
+        .map(|issue| issue.status)
+        .max()
+        .unwrap_or_default()
+}
+
+fn check_shard(
+    shard_id: ShardId,
+    replicas: &HashMap<PeerId, ReplicaState>,
+    transfers: &[ShardTransfer],
+    local: Option<&LocalShardHealth>,
+    now: DateTime<Utc>,
+) -> Vec<HealthIssue> {
+    let mut issues = Vec::new();
+    let mut issue = |status, reason| {
+        issues.push(HealthIssue {
+            status,
+            shard_id,
+            reason,
+        })
+    };
+
+    let shard_transfers: Vec<_> = transfers
+        .iter()
+        .filter(|transfer| transfer.shard_id == shard_id || transfer.to_shard_id == Some(shard_id))
+        .collect();
+
+    for transfer in &shard_transfers {
+        issue(
+            HealthStatus::Yellow,
+            format!(
+                "Shard transfer from peer {} to peer {} is in progress",
+                transfer.from, transfer.to,
+            ),
+        );
+    }
+
+    let mut has_active_replica = false;
+    let mut in_transition = !shard_transfers.is_empty();
+
+    for (&peer_id, &state) in replicas {
+        // Target replica of a transfer is covered by the transfer itself
+        let is_transfer_target = shard_transfers
+            .iter()
+            .any(|transfer| transfer.to == peer_id);
+
+        match state {
+            ReplicaState::Active => has_active_replica = true,
+            ReplicaState::Listener | ReplicaState::ReadOnly => {}
+            _ if is_transfer_target => {}
+            ReplicaState::Dead => issue(
+                HealthStatus::Red,
+                format!("Replica on peer {peer_id} is dead"),
+            ),
+            ReplicaState::Initializing | ReplicaState::Resharding => {
+                in_transition = true;
+                issue(
+                    HealthStatus::Yellow,
+                    format!("Replica on peer {peer_id} is in {state:?} state"),
+                );
+            }
+            ReplicaState::Partial | ReplicaState::PartialSnapshot | ReplicaState::Recovery => {
+                issue(
+                    HealthStatus::Yellow,
+                    format!("Replica on peer {peer_id} is in {state:?} state"),
+                )
+            }
+        }
+    }
+
+    if !has_active_replica && !in_transition {
+        issue(HealthStatus::Red, "Shard has no active replica".to_string());
+    }
+
+    let Some(local) = local else {
+        return issues;
+    };
+
+    if let Some(error) = &local.load_error {
+        issue(
+            HealthStatus::Red,
+            format!("Local replica is not loaded: {error}"),
+        );
+    }
+
+    if let Some(error) = &local.optimizer_error {
+        issue(HealthStatus::Red, format!("Optimization failed: {error}"));
+    }
+
+    if local.disk_full {
+        issue(
+            HealthStatus::Red,
+            "Free disk space is below the configured minimum, updates are rejected".to_string(),
+        );
+    }
+
+    for optimization in &local.running_optimizations {
+        let Ok(running_for) = (now - optimization.start_at).to_std() else {
+            continue;
+        };
+        if running_for > STUCK_OPTIMIZATION_DURATION {
+            issue(
+                HealthStatus::Yellow,
+                format!(
+                    "Optimization {} of segments {:?} is running for {} minutes",
+                    optimization.name,
+                    optimization.segment_ids,
+                    running_for.as_secs() / 60,
+                ),
+            );
+        }
+    }
+
+    if local.wal_lag > MAX_WAL_LAG {
+        issue(
+            HealthStatus::Yellow,
+            format!(
+                "{} operations in the WAL are not applied to the segments yet",
+                local.wal_lag,
+            ),
+        );
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collection_manager::optimizers::{TrackerStatus, TrackerTelemetry};
+
+    fn transfer(from: PeerId, to: PeerId) -> ShardTransfer {
+        ShardTransfer {
+            shard_id: 0,
+            to_shard_id: None,
+            from,
+            to,
+            sync: true,
+            method: None,
+            target_created: false,
+        }
+    }
+
+    fn statuses(issues: &[HealthIssue]) -> Vec<HealthStatus> {
+        issues.iter().map(|issue| issue.status).collect()
+    }
+
+    #[test]
+    fn test_replica_states() {
+        let now = Utc::now();
+
+        let replicas = HashMap::from([(1, ReplicaState::Active), (2, ReplicaState::Listener)]);
+        assert!(check_shard(0, &replicas, &[], None, now).is_empty());
+
+        let replicas = HashMap::from([(1, ReplicaState::Active), (2, ReplicaState::Dead)]);
+        let issues = check_shard(0, &replicas, &[], None, now);
+        assert_eq!(statuses(&issues), vec![HealthStatus::Red]);
+
+        // Mid-transfer is yellow, even if the target was dead before
+        let transfers = [transfer(1, 2)];
+        for state in [ReplicaState::Partial, ReplicaState::Dead] {
+            let replicas = HashMap::from([(1, ReplicaState::Active), (2, state)]);
+            let issues = check_shard(0, &replicas, &transfers, None, now);
+            assert_eq!(statuses(&issues), vec![HealthStatus::Yellow]);
+            assert!(issues[0].reason.contains("transfer"));
+        }
+
+        // Transfers of other shards don't matter
+        let replicas = HashMap::from([(1, ReplicaState::Dead)]);
+        let issues = check_shard(1, &replicas, &transfers, None, now);
+        assert_eq!(worst_status(&issues), HealthStatus::Red);
+
+        let replicas = HashMap::from([(1, ReplicaState::Initializing)]);
+        let issues = check_shard(0, &replicas, &[], None, now);
+        assert_eq!(statuses(&issues), vec![HealthStatus::Yellow]);
+    }
+
+    #[test]
+    fn test_local_health() {
+        let now = Utc::now();
+        let replicas = HashMap::from([(1, ReplicaState::Active)]);
+
+        let optimization = |running_for: chrono::Duration| TrackerTelemetry {
+            name: "indexing".to_string(),
+            segment_ids: vec![1, 2],
+            status: TrackerStatus::Optimizing,
+            start_at: now - running_for,
+            end_at: None,
+        };
+
+        let local = LocalShardHealth {
+            wal_lag: 10,
+            disk_usage_bytes: 1024,
+            running_optimizations: vec![optimization(chrono::Duration::minutes(5))],
+            ..Default::default()
+        };
+        assert!(check_shard(0, &replicas, &[], Some(&local), now).is_empty());
+
+        let local = LocalShardHealth {
+            wal_lag: MAX_WAL_LAG + 1,
+            running_optimizations: vec![optimization(chrono::Duration::hours(4))],
+            ..Default::default()
+        };
+        let issues = check_shard(0, &replicas, &[], Some(&local), now);
+        assert_eq!(
+            statuses(&issues),
+            vec![HealthStatus::Yellow, HealthStatus::Yellow],
+        );
+
+        let local = LocalShardHealth {
+            load_error: Some("Failed to load segment".to_string()),
+            ..Default::default()
+        };
+        let issues = check_shard(0, &replicas, &[], Some(&local), now);
+        assert_eq!(statuses(&issues), vec![HealthStatus::Red]);
+
+        let local = LocalShardHealth {
+            disk_full: true,
+            ..Default::default()
+        };
+        let issues = check_shard(0, &replicas, &[], Some(&local), now);
+        assert_eq!(worst_status(&issues), HealthStatus::Red);
+    }
+}
@@ -0,0 +1,413 @@
+//! Tracks which payload fields are filtered by read requests and suggests payload indexes for
+//! the frequently used ones.
+//!
+//! Fields are only suggested if they are used by a significant share of requests, and if the
+//! values they are matched against don't look unique, because indexing a field with a distinct
+//! value per point costs a lot of memory and rarely pays off.
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+
+use itertools::Itertools;
+use parking_lot::Mutex;
+use segment::json_path::JsonPath;
+use segment::problems::unindexed_field::infer_schema_from_field_condition;
+use segment::types::{
+    AnyVariants, Condition, FieldCondition, Filter, Match, PayloadFieldSchema, PayloadKeyType,
+    ValueVariants,
+};
+
+use super::Collection;
+
+/// Suggestions are re-evaluated once per this number of tracked requests.
+const CHECK_INTERVAL: u64 = 1_000;
+
+/// Min number of requests which filter by a field, before the field can be suggested.
+const MIN_FIELD_USES: u64 = 100;
+
+/// Min share of tracked requests which filter by a field, before the field can be suggested.
+const MIN_FIELD_USAGE_RATIO: f64 = 0.1;
+
+/// Max number of distinct fields to track, protects from unbounded growth on arbitrary keys.
+const MAX_TRACKED_FIELDS: usize = 256;
+
+/// Max number of distinct values to track per field.
+/// A field which reaches this limit is considered to have a high cardinality.
+const MAX_TRACKED_VALUES: usize = 1_000;
+
+/// Min number of matched values before the cardinality of a field can be judged.
+const MIN_OBSERVED_VALUES: u64 = 50;
+
+/// Max share of distinct values among the matched values of a field which may be indexed.
+const MAX_DISTINCT_VALUES_RATIO: f64 = 0.5;
+
+/// Payload index suggested for a frequently filtered field.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PayloadIndexSuggestion {
+    pub field_name: JsonPath,
+    pub field_schema: PayloadFieldSchema,
+    /// Number of tracked requests which filter by the field.
+    pub uses: u64,
+}
+
+#[derive(Debug, Default)]
+struct FieldUsage {
+    /// Number of requests which filter by the field.
+    uses: u64,
+    /// How many times each schema was inferred from conditions on the field.
+    schemas: HashMap<PayloadFieldSchema, u64>,
+    /// Number of values the field was matched against.
+    observed_values: u64,
+    /// Hashes of the distinct values the field was matched against.
+    distinct_values: HashSet<u64>,
+}
+
+impl FieldUsage {
+    /// Most frequently inferred schema, `None` if there is no single best one.
+    fn best_schema(&self) -> Option<&PayloadFieldSchema> {
+        let mut schemas = self.schemas.iter().sorted_by(|a, b| b.1.cmp(a.1));
+        let (best, best_count) = schemas.next()?;
+        match schemas.next() {
+            Some((_, count)) if count == best_count => None,
+            _ => Some(best),
+        }
+    }
+
+    fn has_high_cardinality(&self) -> bool {
+        if self.distinct_values.len() >= MAX_TRACKED_VALUES {
+            return true;
+        }
+        if self.observed_values < MIN_OBSERVED_VALUES {
+            return false;
+        }
+        self.distinct_values.len() as f64 / self.observed_values as f64 > MAX_DISTINCT_VALUES_RATIO
+    }
+
+    fn observe_value(&mut self, value_hash: u64) {
+        self.observed_values += 1;
+        if self.distinct_values.len() < MAX_TRACKED_VALUES {
+            self.distinct_values.insert(value_hash);
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct IndexAdvisorState {
+    requests: u64,
+    fields: HashMap<PayloadKeyType, FieldUsage>,
+    /// Fields which were already suggested, so that they are logged only once.
+    suggested: HashSet<PayloadKeyType>,
+}
+
+/// Collects statistics of filtered payload fields from read requests.
+#[derive(Debug, Default)]
+pub struct IndexAdvisor {
+    state: Mutex<IndexAdvisorState>,
+}
+
+impl IndexAdvisor {
+    /// Records the filters of a single request.
+    ///
+    /// Returns `true` if it is time to re-evaluate the suggestions.
+    pub fn record_request<'a>(
+        &self,
+        filters: impl IntoIterator<Item = Option<&'a Filter>>,
+    ) -> bool {
+        let mut conditions = RequestConditions::default();
+        for filter in filters.into_iter().flatten() {
+            conditions.add_filter(None, filter);
+        }
+
+        let mut state = self.state.lock();
+        state.requests += 1;
+
+        for (key, condition) in conditions.fields {
+            if !state.fields.contains_key(&key) && state.fields.len() >= MAX_TRACKED_FIELDS {
+                continue;
+            }
+
+            let usage = state.fields.entry(key).or_default();
+            usage.uses += 1;
+            for schema in condition.schemas {
+                *usage.schemas.entry(schema).or_default() += 1;
+            }
+            for value_hash in condition.value_hashes {
+                usage.observe_value(value_hash);
+            }
+        }
+
+        state.requests % CHECK_INTERVAL == 0
+    }
+
+    /// Fields which are worth indexing, most used first.
+    ///
+    /// Fields which are already indexed, rarely used or have a high cardinality are left out.
+    pub fn suggestions(
+        &self,
+        indexed: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> Vec<PayloadIndexSuggestion> {
+        let state = self.state.lock();
+
+        state
+            .fields
+            .iter()
+            .filter(|(key, _)| !indexed.contains_key(*key))
+            .filter(|(_, usage)| {
+                usage.uses >= MIN_FIELD_USES
+                    && usage.uses as f64 / state.requests as f64 >= MIN_FIELD_USAGE_RATIO
+            })
+            .filter(|(_, usage)| !usage.has_high_cardinality())
+            .filter_map(|(key, usage)| {
+                Some(PayloadIndexSuggestion {
+                    field_name: key.clone(),
+                    field_schema: usage.best_schema()?.clone(),
+                    uses: usage.uses,
+                })
+            })
+            .sorted_by(|a, b| b.uses.cmp(&a.uses))
+            .collect()
+    }
+
+    /// Like [`IndexAdvisor::suggestions`], but only returns each field once over the lifetime of
+    /// the advisor.
+    fn take_new_suggestions(
+        &self,
+        indexed: &HashMap<PayloadKeyType, PayloadFieldSchema>,
+    ) -> Vec<PayloadIndexSuggestion> {
+        let suggestions = self.suggestions(indexed);
+        let mut state = self.state.lock();
+        suggestions
+            .into_iter()
+            .filter(|suggestion| state.suggested.insert(suggestion.field_name.clone()))
+            .collect()
+    }
+}
+
+/// Conditions of a single field within one request.
+#[derive(Default)]
+struct FieldConditions {
+    schemas: Vec<PayloadFieldSchema>,
+    value_hashes: Vec<u64>,
+}
+
+/// Fields used by a single request, so that each field is counted once per request.
+#[derive(Default)]
+struct RequestConditions {
+    fields: HashMap<PayloadKeyType, FieldConditions>,
+}
+
+impl RequestConditions {
+    fn add_filter(&mut self, nested_prefix: Option<&JsonPath>, filter: &Filter) {
+        for condition in filter.iter_conditions() {
+            self.add_condition(nested_prefix, condition);
+        }
+    }
+
+    fn add_condition(&mut self, nested_prefix: Option<&JsonPath>, condition: &Condition) {
+        match condition {
+            Condition::Field(field_condition) => {
+                let key = JsonPath::extend_or_new(nested_prefix, &field_condition.key);
+                self.add_field_condition(key, field_condition);
+            }
+            Condition::Filter(filter) => self.add_filter(nested_prefix, filter),
+            Condition::Nested(nested) => self.add_filter(
+                Some(&JsonPath::extend_or_new(nested_prefix, nested.raw_key())),
+                nested.filter(),
+            ),
+            // Any index would do, so these conditions don't tell which one to build
+            Condition::IsEmpty(is_empty) => {
+                let key = JsonPath::extend_or_new(nested_prefix, &is_empty.is_empty.key);
+                self.fields.entry(key).or_default();
+            }
+            Condition::IsNull(is_null) => {
+                let key = JsonPath::extend_or_new(nested_prefix, &is_null.is_null.key);
+                self.fields.entry(key).or_default();
+            }
+            Condition::HasField(has_field) => {
+                let key = JsonPath::extend_or_new(nested_prefix, &has_field.has_field.key);
+                self.fields.entry(key).or_default();
+            }
+            Condition::HasId(_)
+            | Condition::IdRange(_)
+            | Condition::HasVector(_)
+            | Condition::CustomIdChecker(_) => {}
+        }
+    }
+
+    fn add_field_condition(&mut self, key: JsonPath, field_condition: &FieldCondition) {
+        let conditions = self.fields.entry(key).or_default();
+
+        // A values count condition alone would accept any index
+        if field_condition.values_count.is_none() {
+            conditions.schemas.extend(
+                infer_schema_from_field_condition(field_condition)
+                    .into_iter()
+                    .take(1),
+            );
+        }
+
+        match &field_condition.r#match {
+            Some(Match::Value(value)) => conditions.value_hashes.push(match &value.value {
+                ValueVariants::String(string) => hash_value(string),
+                ValueVariants::Integer(integer) => hash_value(integer),
+                ValueVariants::Bool(boolean) => hash_value(boolean),
+            }),
+            Some(Match::Any(any)) => conditions.value_hashes.extend(hash_any_variants(&any.any)),
+            Some(Match::Except(except)) => conditions
+                .value_hashes
+                .extend(hash_any_variants(&except.except)),
+            Some(Match::All(all)) => conditions.value_hashes.extend(hash_any_variants(&all.all)),
+            // Full-text, range and geo indexes don't depend on the number of distinct values
+            Some(Match::Text(_)) | None => {}
+        }
+    }
+}
+
+fn hash_value(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+fn hash_any_variants(variants: &AnyVariants) -> Vec<u64> {
+    match variants {
+        AnyVariants::Strings(strings) => strings.iter().map(hash_value).collect(),
+        AnyVariants::Integers(integers) => integers.iter().map(hash_value).collect(),
+    }
+}
+
+impl Collection {
+    /// Payload indexes which would likely speed up the filters of recent read requests.
+    pub fn suggest_payload_indexes(&self) -> Vec<PayloadIndexSuggestion> {
+        let indexed = self.payload_index_schema.read().schema.clone();
+        self.index_advisor.suggestions(&indexed)
+    }
+
+    /// Records the filters of a read request, and periodically logs new index suggestions.
+    ///
+    /// Indexes are never created from here: the read path has no write access to the collection,
+    /// and creating an index is left to users, through the checked update path.
+    pub(crate) fn track_filter_usage<'a>(
+        &self,
+        filters: impl IntoIterator<Item = Option<&'a Filter>>,
+    ) {
+        if !self.index_advisor.record_request(filters) {
+            return;
+        }
+
+        let indexed = self.payload_index_schema.read().schema.clone();
+        let suggestions = self.index_advisor.take_new_suggestions(&indexed);
+
+        for PayloadIndexSuggestion {
+            field_name,
+            field_schema,
+            uses,
+        } in suggestions
+        {
+            log::info!(
+                "Field {field_name} of collection {} is used in filters of {uses} requests, \
+                 consider creating a payload index of type {:?} for it",
+                self.id,
+                field_schema.kind(),
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use segment::types::PayloadSchemaType;
+
+    use super::*;
+
+    fn keyword_filter(key: &str, value: &str) -> Filter {
+        Filter::new_must(Condition::Field(FieldCondition::new_match(
+            JsonPath::from_str(key).unwrap(),
+            value.to_string().into(),
+        )))
+    }
+
+    #[test]
+    fn test_suggest_frequent_low_cardinality_field() {
+        let advisor = IndexAdvisor::default();
+
+        for i in 0..200 {
+            let filter = keyword_filter("color", ["red", "green", "blue"][i % 3]);
+            advisor.record_request([Some(&filter)]);
+        }
+
+        let suggestions = advisor.suggestions(&HashMap::new());
+        assert_eq!(suggestions.len(), 1);
+        assert_eq!(
+            suggestions[0].field_name,
+            JsonPath::from_str("color").unwrap()
+        );
+        assert_eq!(
+            suggestions[0].field_schema,
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        );
+        assert_eq!(suggestions[0].uses, 200);
+
+        // Already indexed fields are not suggested
+        let indexed = HashMap::from([(
+            JsonPath::from_str("color").unwrap(),
+            PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+        )]);
+        assert!(advisor.suggestions(&indexed).is_empty());
+    }
+
+    #[test]
+    fn test_skip_high_cardinality_field() {
+        let advisor = IndexAdvisor::default();
+
+        for i in 0..200 {
+            let filter = keyword_filter("user_id", &format!("user-{i}"));
+            advisor.record_request([Some(&filter)]);
+        }
+
+        assert!(advisor.suggestions(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_skip_rarely_used_field() {
+        let advisor = IndexAdvisor::default();
+
+        let filter = keyword_filter("color", "red");
+        for i in 0..2_000 {
+            let used = i % 20 == 0;
+            advisor.record_request([used.then_some(&filter)]);
+        }
+
+        // Used by 5% of requests only
+        assert!(advisor.suggestions(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_field_counted_once_per_request() {
+        let advisor = IndexAdvisor::default();
+
+        let filter = keyword_filter("color", "red");
+        for _ in 0..60 {
+            advisor.record_request([Some(&filter), Some(&filter)]);
+        }
+
+        // 60 requests are below the min number of uses, even with 120 filters
+        assert!(advisor.suggestions(&HashMap::new()).is_empty());
+    }
+
+    #[test]
+    fn test_new_suggestions_taken_once() {
+        let advisor = IndexAdvisor::default();
+
+        let filter = keyword_filter("color", "red");
+        for _ in 0..200 {
+            advisor.record_request([Some(&filter)]);
+        }
+
+        assert_eq!(advisor.take_new_suggestions(&HashMap::new()).len(), 1);
+        assert!(advisor.take_new_suggestions(&HashMap::new()).is_empty());
+    }
+}
@@ -0,0 +1,71 @@
+//! Estimation of the memory a collection takes once loaded, to size nodes before loading it.
+
+use std::path::Path;
+
+use segment::segment_constructor::memory_estimation::{
+    estimate_segment_memory, SegmentMemoryEstimation,
+};
+
+use super::Collection;
+use crate::operations::types::{
+    CollectionMemoryEstimation, CollectionResult, ShardMemoryEstimation,
+};
+use crate::shards::local_shard::LocalShard;
+use crate::shards::shard::ShardId;
+
+impl Collection {
+    /// Estimate the memory of the collection stored at `collection_path` once loaded.
+    ///
+    /// The collection is not loaded, only the state and the file sizes of its segments are read.
+    /// Every component is placed in RAM or on disk by the config its segments are stored with,
+    /// which is the config they are loaded with. Only shards with a local replica are included.
+    pub fn estimate_memory(collection_path: &Path) -> CollectionResult<CollectionMemoryEstimation> {
+        let mut shards = Vec::new();
+        for entry in std::fs::read_dir(collection_path)? {
+            let shard_path = entry?.path();
+            let Some(shard_id) = shard_path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<ShardId>().ok())
+            else {
+                continue;
+            };
+
+            let segments_path = LocalShard::segments_path(&shard_path);
+            if !segments_path.is_dir() {
+                continue;
+            }
+
+            let mut segments_count = 0;
+            let mut components = SegmentMemoryEstimation::default();
+            for entry in std::fs::read_dir(&segments_path)? {
+                let segment_path = entry?.path();
+                if !segment_path.is_dir() {
+                    continue;
+                }
+                if let Some(estimation) = estimate_segment_memory(&segment_path)? {
+                    segments_count += 1;
+                    components += estimation;
+                }
+            }
+
+            shards.push(ShardMemoryEstimation {
+                shard_id,
+                segments_count,
+                components,
+            });
+        }
+        shards.sort_by_key(|shard| shard.shard_id);
+
+        let mut components = SegmentMemoryEstimation::default();
+        for shard in &shards {
+            components += shard.components;
+        }
+
+        Ok(CollectionMemoryEstimation {
+            shards,
+            components,
+            total: components.total(),
+        })
+    }
+}
@@ -1,15 +1,23 @@
 mod collection_ops;
 pub mod distance_matrix;
 mod facet;
+mod health;
+pub mod index_advisor;
+mod memory_estimation;
 pub mod payload_index_schema;
-mod point_ops;
+pub mod point_dump;
+pub mod point_ops;
 pub mod query;
+pub mod query_cache;
 mod resharding;
 mod search;
 mod shard_transfer;
 mod sharding_keys;
+mod snapshot_schedule;
 mod snapshots;
 mod state_management;
+mod ttl;
+mod zero_vectors;
 
 use std::collections::HashMap;
 use std::ops::Deref;
@@ -25,12 +33,16 @@ use semver::Version;
 use tokio::runtime::Handle;
 use tokio::sync::{Mutex, RwLock, RwLockWriteGuard};
 
+use crate::collection::index_advisor::IndexAdvisor;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::collection::query_cache::QueryCache;
+use crate::collection::snapshot_schedule::ScheduledSnapshots;
 use crate::collection_state::{ShardInfo, State};
 use crate::common::is_ready::IsReady;
 use crate::config::CollectionConfig;
 use crate::operations::cluster_ops::ReshardingDirection;
 use crate::operations::config_diff::{DiffConfig, OptimizersConfigDiff};
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult, NodeType};
 use crate::optimizers_builder::OptimizersConfig;
@@ -57,10 +69,15 @@ pub struct Collection {
     pub(crate) collection_config: Arc<RwLock<CollectionConfig>>,
     pub(crate) shared_storage_config: Arc<SharedStorageConfig>,
     payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
+    // Statistics of filtered payload fields, to suggest payload indexes.
+    index_advisor: IndexAdvisor,
+    // Cached results of search and query requests, `None` if caching is disabled.
+    query_cache: Option<Arc<QueryCache>>,
     optimizers_overwrite: Option<OptimizersConfigDiff>,
     this_peer_id: PeerId,
     path: PathBuf,
     snapshots_path: PathBuf,
+    scheduled_snapshots: ScheduledSnapshots,
     channel_service: ChannelService,
     transfer_tasks: Mutex<TransferTasksPool>,
     reshard_tasks: Mutex<ReshardTasksPool>,
@@ -152,16 +169,19 @@ impl Collection {
         CollectionVersion::save(path)?;
         collection_config.save(path)?;
 
-        Ok(Self {
+        let collection = Self {
             id: name.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
             optimizers_overwrite,
             payload_index_schema,
+            index_advisor: Default::default(),
+            query_cache: QueryCache::from_storage_config(&shared_storage_config).map(Arc::new),
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
             snapshots_path: snapshots_path.to_owned(),
+            scheduled_snapshots: Default::default(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(name.clone())),
             reshard_tasks: Mutex::new(ReshardTasksPool::new(name)),
@@ -174,7 +194,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
-        })
+        };
+        collection.start_ttl_task(collection_config.ttl_config.clone());
+
+        Ok(collection)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -261,16 +284,19 @@ impl Collection {
 
         let locked_shard_holder = Arc::new(LockedShardHolder::new(shard_holder));
 
-        Self {
+        let collection = Self {
             id: collection_id.clone(),
             shards_holder: locked_shard_holder,
             collection_config: shared_collection_config,
             optimizers_overwrite,
             payload_index_schema,
+            index_advisor: Default::default(),
+            query_cache: QueryCache::from_storage_config(&shared_storage_config).map(Arc::new),
             shared_storage_config,
             this_peer_id,
             path: path.to_owned(),
             snapshots_path: snapshots_path.to_owned(),
+            scheduled_snapshots: Default::default(),
             channel_service,
             transfer_tasks: Mutex::new(TransferTasksPool::new(collection_id.clone())),
             reshard_tasks: Mutex::new(ReshardTasksPool::new(collection_id)),
@@ -283,7 +309,10 @@ impl Collection {
             update_runtime: update_runtime.unwrap_or_else(Handle::current),
             search_runtime: search_runtime.unwrap_or_else(Handle::current),
             optimizer_cpu_budget,
-        }
+        };
+        collection.start_ttl_task(collection_config.ttl_config);
+
+        collection
     }
 
     /// Check if stored version have consequent version.
@@ -495,6 +524,46 @@ impl Collection {
         replica_set.shard_recovery_point().await
     }
 
+    /// Check whether the local replica of the shard has applied all given session clocks
+    pub async fn shard_has_applied_clocks(
+        &self,
+        shard_id: ShardId,
+        clocks: &[SessionClock],
+    ) -> CollectionResult<bool> {
+        let shard_holder_read = self.shards_holder.read().await;
+
+        let shard = shard_holder_read.get_shard(&shard_id);
+        let Some(replica_set) = shard else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set.local_has_applied_clocks(clocks).await
+    }
+
+    /// Wait until a read-only replica has seen all updates applied by active replicas of its
+    /// shard. Barrier before the replica is promoted to `Active`, so that it doesn't miss any.
+    pub async fn wait_for_read_only_catch_up(
+        &self,
+        shard_id: ShardId,
+        peer_id: PeerId,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        let shard_holder_read = self.shards_holder.read().await;
+
+        let shard = shard_holder_read.get_shard(&shard_id);
+        let Some(replica_set) = shard else {
+            return Err(CollectionError::NotFound {
+                what: format!("Shard {shard_id}"),
+            });
+        };
+
+        replica_set
+            .wait_for_read_only_catch_up(peer_id, timeout)
+            .await
+    }
+
     pub async fn update_shard_cutoff_point(
         &self,
         shard_id: ShardId,
@@ -686,6 +755,7 @@ impl Collection {
                     sync: true,
                     // For automatic shard transfers, always select some default method from this point on
                     method: Some(shard_transfer_method),
+                    target_created: false,
                 };
 
                 if check_transfer_conflicts_strict(&transfer, transfers.iter()).is_some() {
@@ -752,6 +822,7 @@ impl Collection {
             shards: shards_telemetry,
             transfers,
             resharding,
+            scheduled_snapshots: self.scheduled_snapshots_telemetry(),
         }
     }
 
@@ -0,0 +1,344 @@
+//! Compact binary dump of all points in a collection.
+//!
+//! Layout of a dump:
+//!
+//! ```text
+//! +-------+------------------+----------------------------------------------+
+//! | magic | version (u32 LE) | records: [length (u32 LE) | CBOR point] ...  |
+//! +-------+------------------+----------------------------------------------+
+//! ```
+//!
+//! The records end at EOF. Importers must check the version header and decode records with the
+//! matching format, so that older dumps stay readable when the record format evolves.
+
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+
+use api::rest::VectorStruct;
+use futures::TryStreamExt as _;
+use segment::types::{
+    Payload, PointIdType, ShardKey, VectorMetadata, WithPayloadInterface, WithVector,
+};
+use serde::{Deserialize, Serialize};
+
+use super::Collection;
+use crate::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{CollectionError, CollectionResult, ScrollRequestInternal};
+use crate::operations::CollectionUpdateOperations;
+
+/// Magic bytes at the start of every dump.
+pub const POINT_DUMP_MAGIC: &[u8; 4] = b"QPDM";
+
+/// Version of the record format written by [`Collection::export_points`].
+pub const POINT_DUMP_VERSION: u32 = 1;
+
+/// Number of points read or upserted per batch if the caller does not specify one.
+pub const DEFAULT_POINT_DUMP_BATCH_SIZE: usize = 256;
+
+/// Max size of a single encoded record, protects the importer from allocating garbage lengths.
+const MAX_RECORD_SIZE: usize = 1024 * 1024 * 1024;
+
+/// A single point as it is stored in a dump of version 1.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct DumpedPoint {
+    pub id: PointIdType,
+    pub vector: VectorStruct,
+    #[serde(default)]
+    pub payload: Option<Payload>,
+    #[serde(default)]
+    pub shard_key: Option<ShardKey>,
+    #[serde(default)]
+    pub vector_metadata: Option<VectorMetadata>,
+}
+
+impl From<api::rest::Record> for DumpedPoint {
+    fn from(record: api::rest::Record) -> Self {
+        let api::rest::Record {
+            id,
+            payload,
+            vector,
+            vector_metadata,
+            shard_key,
+            order_value: _,
+            version: _,
+        } = record;
+
+        Self {
+            id,
+            // Points may have no vectors at all if all vectors of a collection are named
+            vector: vector.unwrap_or_else(|| VectorStruct::Named(HashMap::new())),
+            payload,
+            shard_key,
+            vector_metadata,
+        }
+    }
+}
+
+impl From<DumpedPoint> for PointStruct {
+    fn from(point: DumpedPoint) -> Self {
+        let DumpedPoint {
+            id,
+            vector,
+            payload,
+            shard_key: _,
+            vector_metadata,
+        } = point;
+
+        Self {
+            id,
+            vector,
+            payload,
+            vector_metadata,
+        }
+    }
+}
+
+/// Writes the dump header.
+pub fn write_header(writer: &mut impl Write) -> io::Result<()> {
+    writer.write_all(POINT_DUMP_MAGIC)?;
+    writer.write_all(&POINT_DUMP_VERSION.to_le_bytes())
+}
+
+/// Reads and validates the dump header, returns the format version of the dump.
+pub fn read_header(reader: &mut impl Read) -> CollectionResult<u32> {
+    let mut magic = [0; POINT_DUMP_MAGIC.len()];
+    reader.read_exact(&mut magic).map_err(|err| {
+        CollectionError::bad_input(format!("Failed to read point dump header: {err}"))
+    })?;
+    if &magic != POINT_DUMP_MAGIC {
+        return Err(CollectionError::bad_input("Input is not a point dump"));
+    }
+
+    let mut version = [0; 4];
+    reader.read_exact(&mut version).map_err(|err| {
+        CollectionError::bad_input(format!("Failed to read point dump version: {err}"))
+    })?;
+    let version = u32::from_le_bytes(version);
+
+    if version == 0 || version > POINT_DUMP_VERSION {
+        return Err(CollectionError::bad_input(format!(
+            "Unsupported point dump version {version}, max supported version is {POINT_DUMP_VERSION}",
+        )));
+    }
+
+    Ok(version)
+}
+
+/// Writes a single length-prefixed record.
+pub fn write_point(writer: &mut impl Write, point: &DumpedPoint) -> CollectionResult<()> {
+    let bytes = serde_cbor::to_vec(point).map_err(|err| {
+        CollectionError::service_error(format!("Failed to encode point {}: {err}", point.id))
+    })?;
+
+    let len = u32::try_from(bytes.len())
+        .ok()
+        .filter(|&len| len as usize <= MAX_RECORD_SIZE)
+        .ok_or_else(|| {
+            CollectionError::service_error(format!("Point {} is too large to dump", point.id))
+        })?;
+
+    writer.write_all(&len.to_le_bytes())?;
+    writer.write_all(&bytes)?;
+    Ok(())
+}
+
+/// Reads the next record of a dump with the given format `version`.
+///
+/// Returns `None` on a clean EOF at a record boundary.
+pub fn read_point(reader: &mut impl Read, version: u32) -> CollectionResult<Option<DumpedPoint>> {
+    let mut len = [0; 4];
+    if !read_exact_or_eof(reader, &mut len)? {
+        return Ok(None);
+    }
+    let len = u32::from_le_bytes(len) as usize;
+    if len > MAX_RECORD_SIZE {
+        return Err(CollectionError::bad_input(format!(
+            "Point dump record of {len} bytes exceeds the max size, dump is probably corrupted",
+        )));
+    }
+
+    let mut bytes = vec![0; len];
+    reader
+        .read_exact(&mut bytes)
+        .map_err(|err| CollectionError::bad_input(format!("Point dump is truncated: {err}")))?;
+
+    let point = match version {
+        1 => serde_cbor::from_slice(&bytes).map_err(|err| {
+            CollectionError::bad_input(format!("Failed to decode point dump record: {err}"))
+        })?,
+        _ => {
+            return Err(CollectionError::bad_input(format!(
+                "Unsupported point dump version {version}",
+            )))
+        }
+    };
+
+    Ok(Some(point))
+}
+
+/// Like [`Read::read_exact`], but returns `false` if the reader is at EOF before reading anything.
+fn read_exact_or_eof(reader: &mut impl Read, buf: &mut [u8]) -> CollectionResult<bool> {
+    let mut read = 0;
+    while read < buf.len() {
+        match reader.read(&mut buf[read..]) {
+            Ok(0) if read == 0 => return Ok(false),
+            Ok(0) => {
+                return Err(CollectionError::bad_input(
+                    "Point dump is truncated in the middle of a record length",
+                ))
+            }
+            Ok(n) => read += n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(true)
+}
+
+impl Collection {
+    /// Export all points of the collection, including vectors and payloads, as a binary dump
+    ///
+    /// Points are read page by page with `batch_size` points per scroll request.
+    /// Returns the number of exported points.
+    pub async fn export_points(
+        &self,
+        writer: &mut impl Write,
+        batch_size: usize,
+    ) -> CollectionResult<usize> {
+        write_header(writer)?;
+
+        let request = ScrollRequestInternal {
+            offset: None,
+            limit: Some(batch_size.max(1)),
+            filter: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: WithVector::Bool(true),
+            order_by: None,
+        };
+        let records = self.scroll_stream(request, None, ShardSelectorInternal::All, None);
+        tokio::pin!(records);
+
+        let mut exported = 0;
+        while let Some(record) = records.try_next().await? {
+            write_point(writer, &DumpedPoint::from(record))?;
+            exported += 1;
+        }
+
+        writer.flush()?;
+
+        Ok(exported)
+    }
+
+    /// Import points from a binary dump created by [`Collection::export_points`]
+    ///
+    /// Points are upserted in batches of `batch_size`, consecutive points with the same shard key
+    /// are grouped together. Returns the number of imported points.
+    pub async fn import_points(
+        &self,
+        reader: &mut impl Read,
+        batch_size: usize,
+    ) -> CollectionResult<usize> {
+        let version = read_header(reader)?;
+        let batch_size = batch_size.max(1);
+
+        let mut batch = Vec::with_capacity(batch_size);
+        let mut batch_shard_key = None;
+        let mut imported = 0;
+
+        while let Some(point) = read_point(reader, version)? {
+            if point.shard_key != batch_shard_key || batch.len() >= batch_size {
+                imported += batch.len();
+                self.upsert_dumped_batch(std::mem::take(&mut batch), batch_shard_key.take())
+                    .await?;
+            }
+
+            batch_shard_key.clone_from(&point.shard_key);
+            batch.push(PointStruct::from(point));
+        }
+
+        imported += batch.len();
+        self.upsert_dumped_batch(batch, batch_shard_key).await?;
+
+        Ok(imported)
+    }
+
+    async fn upsert_dumped_batch(
+        &self,
+        points: Vec<PointStruct>,
+        shard_key: Option<ShardKey>,
+    ) -> CollectionResult<()> {
+        if points.is_empty() {
+            return Ok(());
+        }
+
+        let operation = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(points),
+        ));
+
+        self.update_from_client(operation, true, WriteOrdering::default(), None, shard_key)
+            .await?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(id: u64) -> DumpedPoint {
+        DumpedPoint {
+            id: id.into(),
+            vector: VectorStruct::Single(vec![id as f32, 1.0, 2.0]),
+            payload: Some(serde_json::json!({ "id": id }).into()),
+            shard_key: None,
+            vector_metadata: None,
+        }
+    }
+
+    #[test]
+    fn test_point_dump_roundtrip() {
+        let points = (0..10).map(point).collect::<Vec<_>>();
+
+        let mut dump = Vec::new();
+        write_header(&mut dump).unwrap();
+        for point in &points {
+            write_point(&mut dump, point).unwrap();
+        }
+
+        let mut reader = dump.as_slice();
+        let version = read_header(&mut reader).unwrap();
+        assert_eq!(version, POINT_DUMP_VERSION);
+
+        let mut read = Vec::new();
+        while let Some(point) = read_point(&mut reader, version).unwrap() {
+            read.push(point);
+        }
+        assert_eq!(read, points);
+    }
+
+    #[test]
+    fn test_point_dump_rejects_bad_header() {
+        let mut dump = Vec::new();
+        dump.extend_from_slice(POINT_DUMP_MAGIC);
+        dump.extend_from_slice(&(POINT_DUMP_VERSION + 1).to_le_bytes());
+        assert!(read_header(&mut dump.as_slice()).is_err());
+
+        assert!(read_header(&mut b"JSON{}{}".as_slice()).is_err());
+    }
+
+    #[test]
+    fn test_point_dump_truncated() {
+        let mut dump = Vec::new();
+        write_header(&mut dump).unwrap();
+        write_point(&mut dump, &point(1)).unwrap();
+        dump.pop();
+
+        let mut reader = dump.as_slice();
+        let version = read_header(&mut reader).unwrap();
+        assert!(read_point(&mut reader, version).is_err());
+    }
+}
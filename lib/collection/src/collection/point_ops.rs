@@ -1,17 +1,19 @@
 use std::collections::HashSet;
+use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
-use futures::stream::FuturesUnordered;
-use futures::{future, StreamExt as _, TryFutureExt, TryStreamExt as _};
+use futures::stream::{self, FuturesUnordered};
+use futures::{future, Stream, StreamExt as _, TryFutureExt, TryStreamExt as _};
 use itertools::Itertools;
 use segment::data_types::order_by::{Direction, OrderBy};
 use segment::types::{ShardKey, WithPayload, WithPayloadInterface};
 use validator::Validate as _;
 
 use super::Collection;
-use crate::operations::consistency_params::ReadConsistency;
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::consistency_params::{ReadConsistency, SessionToken};
+use crate::operations::idempotency::{IdempotencyKey, OperationFingerprint};
+use crate::operations::point_ops::{WriteConsistency, WriteOrdering};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
 use crate::operations::{CollectionUpdateOperations, OperationWithClockTag};
@@ -31,6 +33,8 @@ impl Collection {
     ) -> CollectionResult<Option<UpdateResult>> {
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
+        let query_cache = self.query_cache.clone();
+        let shards_holder = self.shards_holder.clone();
 
         let results = tokio::task::spawn(async move {
             let _update_lock = update_lock;
@@ -55,6 +59,10 @@ impl Collection {
 
             let results: Vec<_> = local_updates.collect().await;
 
+            if let Some(query_cache) = query_cache {
+                query_cache.invalidate_after_update(&shards_holder, wait);
+            }
+
             results
         })
         .await?;
@@ -85,9 +93,12 @@ impl Collection {
         shard_selection: ShardId,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
     ) -> CollectionResult<UpdateResult> {
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
+        let query_cache = self.query_cache.clone();
+        let shards_holder = self.shards_holder.clone();
 
         let result = tokio::task::spawn(async move {
             let _update_lock = update_lock;
@@ -96,7 +107,7 @@ impl Collection {
                 return Ok(None);
             };
 
-            match ordering {
+            let result = match ordering {
                 WriteOrdering::Weak => shard.update_local(operation, wait).await,
                 WriteOrdering::Medium | WriteOrdering::Strong => {
                     if let Some(clock_tag) = operation.clock_tag {
@@ -108,11 +119,23 @@ impl Collection {
                     }
 
                     shard
-                        .update_with_consistency(operation.operation, wait, ordering, false)
+                        .update_with_consistency(
+                            operation.operation,
+                            wait,
+                            ordering,
+                            consistency,
+                            false,
+                        )
                         .await
                         .map(Some)
                 }
+            };
+
+            if let Some(query_cache) = query_cache {
+                query_cache.invalidate_after_update(&shards_holder, wait);
             }
+
+            result
         })
         .await??;
 
@@ -135,12 +158,64 @@ impl Collection {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
+        shard_keys_selection: Option<ShardKey>,
+    ) -> CollectionResult<UpdateResult> {
+        self.update_from_client_idempotent(
+            operation,
+            None,
+            false,
+            wait,
+            ordering,
+            consistency,
+            shard_keys_selection,
+        )
+        .await
+    }
+
+    /// Same as [`Collection::update_from_client`], but deduplicates retries by idempotency key.
+    ///
+    /// If an operation with the same key was recently applied to a shard, it is not applied to
+    /// that shard again, and the prior result is returned instead. Reusing a key for a different
+    /// operation is rejected.
+    ///
+    /// If `skip_invalid_points` is set, points which fail the checks of
+    /// [`Collection::check_operation_points`] are left out of the operation and reported in the
+    /// result, instead of failing the whole operation.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_from_client_idempotent(
+        &self,
+        mut operation: CollectionUpdateOperations,
+        idempotency_key: Option<IdempotencyKey>,
+        skip_invalid_points: bool,
+        wait: bool,
+        ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
         shard_keys_selection: Option<ShardKey>,
     ) -> CollectionResult<UpdateResult> {
         operation.validate()?;
+        if let Some(idempotency_key) = &idempotency_key {
+            idempotency_key.validate()?;
+        }
+
+        let invalid_points = self
+            .check_operation_points(&mut operation, skip_invalid_points)
+            .await?;
+
+        operation.exclude_zero_vectors(&self.collection_config.read().await.params);
+
+        let ttl_timestamps = self.ttl_timestamps().await;
+
+        let expected_versions_count = operation.expected_versions_count();
 
         let update_lock = self.updates_lock.clone().read_owned().await;
         let shard_holder = self.shards_holder.clone().read_owned().await;
+        let query_cache = self.query_cache.clone();
+        let shards_holder = self.shards_holder.clone();
 
         let mut results = tokio::task::spawn(async move {
             let _update_lock = update_lock;
@@ -148,25 +223,64 @@ impl Collection {
             let updates = FuturesUnordered::new();
             let operations = shard_holder.split_by_shard(operation, &shard_keys_selection)?;
 
+            if let Some(expected_versions_count) = expected_versions_count {
+                check_single_shard_versioned_upsert(&operations, expected_versions_count)?;
+            }
+
             for (shard, operation) in operations {
+                let idempotency = match &idempotency_key {
+                    Some(key) => Some((key.clone(), OperationFingerprint::of(&operation)?)),
+                    None => None,
+                };
+
+                // Timestamps are set after fingerprinting, so that retries of the operation
+                // match the fingerprint of the original one
+                let operation = match &ttl_timestamps {
+                    Some(ttl_timestamps) => ttl_timestamps.apply(operation),
+                    None => operation,
+                };
+
                 let operation = shard_holder.split_by_mode(shard.shard_id, operation);
 
                 updates.push(async move {
+                    // Held until the key is recorded, so concurrent retries are applied once
+                    let _key_guard = match &idempotency {
+                        Some((key, _)) => Some(shard.idempotency_cache().lock_key(key).await),
+                        None => None,
+                    };
+
+                    if let Some((key, fingerprint)) = &idempotency {
+                        if let Some(prior_result) =
+                            shard.idempotency_cache().check(key, fingerprint)?
+                        {
+                            return Ok(prior_result);
+                        }
+                    }
+
                     let mut result = UpdateResult {
                         operation_id: None,
                         status: UpdateStatus::Acknowledged,
                         clock_tag: None,
+                        session_token: None,
+                        invalid_points: Vec::new(),
                     };
 
+                    // Clock tags of all operations applied to this shard
+                    let mut session_token = SessionToken::default();
+
                     for operation in operation.update_all {
                         result = shard
-                            .update_with_consistency(operation, wait, ordering, false)
+                            .update_with_consistency(operation, wait, ordering, consistency, false)
                             .await?;
+
+                        if let Some(clock_tag) = result.clock_tag {
+                            session_token.add(shard.shard_id, clock_tag);
+                        }
                     }
 
                     for operation in operation.update_only_existing {
                         let res = shard
-                            .update_with_consistency(operation, wait, ordering, true)
+                            .update_with_consistency(operation, wait, ordering, consistency, true)
                             .await;
 
                         if let Err(err) = &res {
@@ -176,6 +290,25 @@ impl Collection {
                         }
 
                         result = res?;
+
+                        if let Some(clock_tag) = result.clock_tag {
+                            session_token.add(shard.shard_id, clock_tag);
+                        }
+                    }
+
+                    result.session_token = Some(session_token);
+
+                    if let Some((key, fingerprint)) = idempotency {
+                        // The update is applied at this point, failing to remember the key must
+                        // not turn it into an error
+                        if let Err(err) =
+                            shard.idempotency_cache().record(key, fingerprint, &result)
+                        {
+                            log::warn!(
+                                "Failed to record idempotency key for shard {}: {err}",
+                                shard.shard_id,
+                            );
+                        }
                     }
 
                     CollectionResult::Ok(result)
@@ -184,6 +317,10 @@ impl Collection {
 
             let results: Vec<_> = updates.collect().await;
 
+            if let Some(query_cache) = query_cache {
+                query_cache.invalidate_after_update(&shards_holder, wait);
+            }
+
             CollectionResult::Ok(results)
         })
         .await??;
@@ -217,9 +354,63 @@ impl Collection {
                 first_err
             }
         } else {
+            // Combine clock tags of all shards into a single session token
+            let mut session_token = SessionToken::default();
+            for result in results.iter_mut().flatten() {
+                if let Some(shard_token) = result.session_token.take() {
+                    session_token.merge(shard_token);
+                }
+            }
+
             // At least one result is always present.
-            results.pop().unwrap()
+            let mut result = results.pop().unwrap()?;
+            result.session_token = (!session_token.is_empty()).then_some(session_token);
+            result.invalid_points = invalid_points;
+            Ok(result)
+        }
+    }
+
+    /// Check vectors of the operation against the collection config, and payloads against the max
+    /// payload size, before it is sent to shards
+    ///
+    /// A point with invalid vectors or an oversized payload fails the whole operation, with an
+    /// error that names the point. If `skip_invalid_points` is set, they are removed from the
+    /// operation and returned instead, unless no valid points are left.
+    async fn check_operation_points(
+        &self,
+        operation: &mut CollectionUpdateOperations,
+        skip_invalid_points: bool,
+    ) -> CollectionResult<Vec<InvalidPoint>> {
+        let mut invalid_points = {
+            let collection_config = self.collection_config.read().await;
+            operation.invalid_vectors(&collection_config.params)
+        };
+        if let Some(max_size) = self.shared_storage_config.max_payload_size {
+            invalid_points.extend(operation.oversized_payloads(max_size));
+        }
+
+        let Some((_, first_err)) = invalid_points.first() else {
+            return Ok(Vec::new());
+        };
+
+        if !skip_invalid_points {
+            return Err(first_err.clone());
+        }
+
+        let invalid_ids: HashSet<_> = invalid_points.iter().map(|(id, _)| *id).collect();
+        operation.retain_point_ids(|id| !invalid_ids.contains(id));
+
+        if operation.point_ids().is_empty() {
+            return Err(first_err.clone());
         }
+
+        Ok(invalid_points
+            .into_iter()
+            .map(|(id, err)| InvalidPoint {
+                id,
+                error: err.to_string(),
+            })
+            .collect())
     }
 
     /// # Cancel safety
@@ -231,7 +422,7 @@ impl Collection {
         wait: bool,
         ordering: WriteOrdering,
     ) -> CollectionResult<UpdateResult> {
-        self.update_from_client(operation, wait, ordering, None)
+        self.update_from_client(operation, wait, ordering, None, None)
             .await
     }
 
@@ -288,7 +479,7 @@ impl Collection {
                         &with_payload_interface,
                         &with_vector,
                         request.filter.as_ref(),
-                        read_consistency,
+                        read_consistency.clone(),
                         local_only,
                         order_by.as_ref(),
                         timeout,
@@ -373,6 +564,37 @@ impl Collection {
         })
     }
 
+    /// Scroll all points matching the request as a stream, instead of a single page
+    ///
+    /// Points are requested from the shards in pages of `request.limit` points, starting at
+    /// `request.offset`. The next page is only requested once the consumer has taken all points of
+    /// the previous one, so at most one page is kept in memory. Points are streamed in the order of
+    /// their IDs, and the ID following the last page is the cursor of the next one, so every point
+    /// is returned at most once. Ordering by payload is not supported, as it has no stable cursor.
+    ///
+    /// # Consistency
+    ///
+    /// The stream is not a snapshot of the collection, each page reads its current state. Points
+    /// changed before the cursor reaches them are returned in their latest version, points deleted
+    /// before that are not returned, and points inserted behind the cursor are not returned.
+    pub fn scroll_stream(
+        &self,
+        request: ScrollRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> impl Stream<Item = CollectionResult<api::rest::Record>> + '_ {
+        scroll_pages(request, move |request| {
+            let read_consistency = read_consistency.clone();
+            let shard_selection = shard_selection.clone();
+
+            async move {
+                self.scroll_by(request, read_consistency, &shard_selection, timeout)
+                    .await
+            }
+        })
+    }
+
     pub async fn count(
         &self,
         request: CountRequestInternal,
@@ -383,27 +605,56 @@ impl Collection {
         let shards_holder = self.shards_holder.read().await;
         let shards = shards_holder.select_shards(shard_selection)?;
 
+        let allow_partial = request.allow_partial;
         let request = Arc::new(request);
 
         let mut requests: FuturesUnordered<_> = shards
             .into_iter()
             // `count` requests received through internal gRPC *always* have `shard_selection`
             .map(|(shard, _shard_key)| {
-                shard.count(
-                    Arc::clone(&request),
-                    read_consistency,
-                    timeout,
-                    shard_selection.is_shard_id(),
-                )
+                let request = Arc::clone(&request);
+                let read_consistency = read_consistency.clone();
+                async move {
+                    let result = shard
+                        .count(
+                            request,
+                            read_consistency,
+                            timeout,
+                            shard_selection.is_shard_id(),
+                        )
+                        .await;
+                    (shard.shard_id, result)
+                }
             })
             .collect();
 
         let mut count = 0;
-        while let Some(response) = requests.try_next().await? {
-            count += response.count;
+        let mut exact = true;
+        let mut partial = false;
+        while let Some((shard_id, result)) = requests.next().await {
+            match result {
+                Ok(response) => {
+                    count += response.count;
+                    exact &= response.exact;
+                    partial |= response.partial;
+                }
+                // Leave out shards that are unavailable, but not invalid requests
+                Err(err) if allow_partial && err.is_transient() => {
+                    log::debug!(
+                        "Shard {}:{shard_id} left out of partial count: {err}",
+                        self.id,
+                    );
+                    partial = true;
+                }
+                Err(err) => return Err(err),
+            }
         }
 
-        Ok(CountResult { count })
+        Ok(CountResult {
+            count,
+            exact,
+            partial,
+        })
     }
 
     pub async fn retrieve(
@@ -429,6 +680,7 @@ impl Collection {
                 // block below without unnecessarily cloning anything
                 let request = &request;
                 let with_payload = &with_payload;
+                let read_consistency = read_consistency.clone();
 
                 async move {
                     let mut records = shard
@@ -476,3 +728,68 @@ impl Collection {
             .await
     }
 }
+
+/// Versions are checked by each shard on its own, so a versioned upsert can't be applied
+/// atomically across shards. Reject it, instead of applying it to some shards only, or dropping
+/// the versions of points routed to shards which receive no points.
+fn check_single_shard_versioned_upsert<S>(
+    operations: &[(S, CollectionUpdateOperations)],
+    expected_versions_count: usize,
+) -> CollectionResult<()> {
+    let routed_versions_count: usize = operations
+        .iter()
+        .filter_map(|(_, operation)| operation.expected_versions_count())
+        .sum();
+
+    if operations.len() > 1 || routed_versions_count != expected_versions_count {
+        return Err(CollectionError::bad_input(
+            "Versioned upsert must only reference points of a single shard".to_string(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Turn a paginated scroll into a stream of points, see [`Collection::scroll_stream`]
+///
+/// `scroll_page` is called with the request of every page, with the offset set to the cursor of
+/// that page. It is only called again once all points of the previous page have been consumed.
+pub fn scroll_pages<F, Fut, E>(
+    request: ScrollRequestInternal,
+    mut scroll_page: F,
+) -> impl Stream<Item = Result<api::rest::Record, E>>
+where
+    F: FnMut(ScrollRequestInternal) -> Fut,
+    Fut: Future<Output = Result<ScrollResult, E>>,
+    E: From<CollectionError>,
+{
+    // `Some(offset)` is the cursor of the next page, `None` if there are no more pages
+    let cursor = Some(request.offset);
+
+    stream::try_unfold(cursor, move |cursor| {
+        let page = cursor.map(|offset| {
+            if request.order_by.is_some() {
+                return future::Either::Left(future::err(E::from(CollectionError::bad_input(
+                    "Cannot stream a scroll with `order_by`, use paginated scroll instead",
+                ))));
+            }
+
+            future::Either::Right(scroll_page(ScrollRequestInternal {
+                offset,
+                ..request.clone()
+            }))
+        });
+
+        async move {
+            let Some(page) = page else {
+                return Ok(None);
+            };
+            let page = page.await?;
+
+            let points = stream::iter(page.points.into_iter().map(Ok::<_, E>));
+            let next_cursor = page.next_page_offset.map(Some);
+            Ok(Some((points, next_cursor)))
+        }
+    })
+    .try_flatten()
+}
@@ -2,17 +2,20 @@ use std::future::Future;
 use std::sync::Arc;
 use std::time::Duration;
 
+use common::random::seeded_rng;
 use common::types::ScoreType;
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
 use rand::Rng;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::common::reciprocal_rank_fusion::weighted_rrf_scoring;
 use segment::common::score_fusion::{score_fusion, ScoreFusion};
-use segment::types::{Order, ScoredPoint};
+use segment::types::{Order, ScoredPoint, SearchParams};
 use segment::utils::scored_point_ties::ScoredPointTies;
 use tokio::sync::RwLockReadGuard;
 use tokio::time::Instant;
 
+use super::query_cache::{CachedRequest, QueryCacheKey};
+use super::zero_vectors::query_has_results;
 use super::Collection;
 use crate::common::batching::batch_requests;
 use crate::common::fetch_vectors::{
@@ -25,11 +28,14 @@ use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::universal_query::collection_query::CollectionQueryRequest;
 use crate::operations::universal_query::shard_query::{
-    Fusion, ScoringQuery, ShardQueryRequest, ShardQueryResponse,
+    Fusion, ScoringQuery, ShardPrefetch, ShardQueryRequest, ShardQueryResponse,
 };
+use crate::shards::fan_out::{prioritize_shards, try_join_shards};
 
 struct IntermediateQueryInfo<'a> {
     scoring_query: Option<&'a ScoringQuery>,
+    /// Search params the query is scored with, only used without prefetches
+    params: Option<&'a SearchParams>,
     /// Limit + offset
     take: usize,
 }
@@ -62,18 +68,30 @@ impl Collection {
     ) -> CollectionResult<Vec<Vec<ShardQueryResponse>>> {
         // query all shards concurrently
         let shard_holder = self.shards_holder.read().await;
-        let target_shards = shard_holder.select_shards(shard_selection)?;
+        let mut target_shards = shard_holder.select_shards(shard_selection)?;
+        let concurrency = self.shared_storage_config.search_shard_concurrency;
+        if concurrency.is_some() {
+            prioritize_shards(&mut target_shards);
+        }
 
         let all_searches = target_shards.iter().map(|(shard, shard_key)| {
+            let shard_id = shard.shard_id;
             let shard_key = shard_key.cloned();
             shard
                 .query_batch(
                     Arc::clone(&batch_request),
-                    read_consistency,
+                    read_consistency.clone(),
                     shard_selection.is_shard_id(),
                     timeout,
                 )
                 .and_then(move |mut shard_responses| async move {
+                    shard_responses
+                        .iter_mut()
+                        .flatten()
+                        .flatten()
+                        .filter_map(|point| point.provenance.as_mut())
+                        .for_each(|provenance| provenance.shard_id = Some(shard_id));
+
                     if shard_key.is_none() {
                         return Ok(shard_responses);
                     }
@@ -86,7 +104,7 @@ impl Collection {
                     Ok(shard_responses)
                 })
         });
-        future::try_join_all(all_searches).await
+        try_join_shards(all_searches, concurrency).await
     }
 
     /// This function is used to query the collection. It will return a list of scored points.
@@ -96,6 +114,40 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Explicit read consistency asks for fresh results, and random samples must not repeat
+        let is_cacheable = read_consistency.is_none();
+
+        self.with_query_cache(
+            requests_batch,
+            |request| {
+                (is_cacheable && !request.has_random_sample()).then(|| {
+                    QueryCacheKey::new(
+                        CachedRequest::Query(request.clone()),
+                        shard_selection.clone(),
+                    )
+                })
+            },
+            |requests_batch| {
+                self.without_zero_queries(requests_batch, query_has_results, |requests_batch| {
+                    self.do_query_batch_uncached(
+                        requests_batch,
+                        read_consistency,
+                        shard_selection.clone(),
+                        timeout,
+                    )
+                })
+            },
+        )
+        .await
+    }
+
+    async fn do_query_batch_uncached(
+        &self,
+        requests_batch: Vec<ShardQueryRequest>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let instant = Instant::now();
 
@@ -121,13 +173,15 @@ impl Collection {
                 let result = Self::intermediates_to_final_list(
                     merged_intermediates,
                     request.query.as_ref(),
+                    &request.prefetches,
                     request.limit,
                     request.offset,
                     request.score_threshold,
                 )?;
 
                 let filter_refs = request.filter_refs();
-                self.post_process_if_slow_request(instant.elapsed(), filter_refs);
+                self.post_process_if_slow_request(instant.elapsed(), filter_refs.iter().copied());
+                self.track_filter_usage(filter_refs);
 
                 Ok::<_, CollectionError>(result)
             });
@@ -139,6 +193,7 @@ impl Collection {
     fn intermediates_to_final_list(
         mut intermediates: Vec<Vec<ScoredPoint>>,
         query: Option<&ScoringQuery>,
+        prefetches: &[ShardPrefetch],
         limit: usize,
         offset: usize,
         score_threshold: Option<ScoreType>,
@@ -146,9 +201,19 @@ impl Collection {
         let result = match query {
             Some(ScoringQuery::Fusion(fusion)) => {
                 // If the root query is a Fusion, the returned results correspond to each the prefetches.
+                let weights: Vec<_> = prefetches
+                    .iter()
+                    .map(ShardPrefetch::fusion_weight)
+                    .collect();
                 let mut fused = match fusion {
-                    Fusion::Rrf => rrf_scoring(intermediates),
-                    Fusion::Dbsf => score_fusion(intermediates, ScoreFusion::dbsf()),
+                    Fusion::Rrf => weighted_rrf_scoring(intermediates, &weights),
+                    Fusion::Dbsf => score_fusion(
+                        intermediates,
+                        ScoreFusion {
+                            weights,
+                            ..ScoreFusion::dbsf()
+                        },
+                    ),
                 };
                 if let Some(score_threshold) = score_threshold {
                     fused = fused
@@ -198,7 +263,7 @@ impl Collection {
             &resolver_requests,
             self,
             collection_by_name,
-            read_consistency,
+            read_consistency.clone(),
             timeout,
         )
         .await?;
@@ -239,7 +304,7 @@ impl Collection {
 
                 futures.push(self.do_query_batch(
                     shard_requests,
-                    read_consistency,
+                    read_consistency.clone(),
                     shard_selection,
                     timeout,
                 ));
@@ -320,7 +385,11 @@ impl Collection {
             query_infos.into_iter().zip(all_shards_result_by_transposed)
         {
             // `shards_results` shape: [num_shards, num_scored_points]
-            let order = ScoringQuery::order(query_info.scoring_query, &collection_params)?;
+            let order = ScoringQuery::order(
+                query_info.scoring_query,
+                query_info.params,
+                &collection_params,
+            )?;
 
             // Equivalent to:
             //
@@ -352,7 +421,7 @@ impl Collection {
                 .collect()
             } else {
                 // If the order is not defined, it is a random query. Take from all shards randomly.
-                let mut rng = rand::thread_rng();
+                let mut rng = seeded_rng(request.seed);
                 shards_results
                     .into_iter()
                     .kmerge_by(|_, _| rng.gen_bool(0.5))
@@ -385,6 +454,11 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
             .iter()
             .map(|prefetch| IntermediateQueryInfo {
                 scoring_query: prefetch.query.as_ref(),
+                params: prefetch
+                    .prefetches
+                    .is_empty()
+                    .then_some(prefetch.params.as_ref())
+                    .flatten(),
                 take: prefetch.limit,
             })
             .collect_vec()
@@ -392,6 +466,11 @@ fn intermediate_query_infos(request: &ShardQueryRequest) -> Vec<IntermediateQuer
         // Otherwise, we expect the root result
         vec![IntermediateQueryInfo {
             scoring_query: request.query.as_ref(),
+            params: request
+                .prefetches
+                .is_empty()
+                .then_some(request.params.as_ref())
+                .flatten(),
             take: request.offset + request.limit,
         }]
     }
@@ -0,0 +1,419 @@
+//! Cache of search and query results, for read heavy collections which receive the same requests
+//! over and over again, such as from dashboards.
+//!
+//! Results are cached per request and the whole cache is invalidated on any update of the
+//! collection. Updates which are acknowledged before being applied invalidate the cache once more
+//! after they are applied. Entries also expire after a configured time, which bounds staleness of
+//! results in cases where an update isn't observed by the cache in time.
+//!
+//! Updates of shards hosted by other peers don't pass through this collection instance, so the
+//! cache is only used in single node deployments.
+
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use segment::data_types::vectors::VectorRef;
+use segment::types::ScoredPoint;
+use serde::Deserialize;
+use validator::Validate;
+
+use super::Collection;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::shared_storage_config::SharedStorageConfig;
+use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequest};
+use crate::operations::universal_query::shard_query::{ScoringQuery, ShardQueryRequest};
+use crate::shards::shard_holder::LockedShardHolder;
+
+/// Configuration of the query result cache, applies to each collection separately.
+#[derive(Debug, Deserialize, Validate, Clone, Copy, PartialEq, Eq)]
+pub struct QueryCacheConfig {
+    /// Max number of cached results per collection.
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+    /// How long a cached result may be returned, in seconds.
+    #[validate(range(min = 1))]
+    pub ttl_sec: u64,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum CachedRequest {
+    Search(CoreSearchRequest),
+    Query(ShardQueryRequest),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct QueryCacheKey {
+    request: CachedRequest,
+    shard_selection: ShardSelectorInternal,
+}
+
+impl QueryCacheKey {
+    pub fn new(request: CachedRequest, shard_selection: ShardSelectorInternal) -> Self {
+        Self {
+            request,
+            shard_selection,
+        }
+    }
+
+    /// Requests contain floats and don't implement `Hash`, so only the fields which usually tell
+    /// requests apart are hashed. Keys with the same fingerprint are compared on lookup anyway.
+    fn fingerprint(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+
+        mem::discriminant(&self.request).hash(&mut hasher);
+        match &self.request {
+            CachedRequest::Search(request) => {
+                hash_query(&request.query, &mut hasher);
+                request.limit.hash(&mut hasher);
+                request.offset.hash(&mut hasher);
+            }
+            CachedRequest::Query(request) => {
+                match &request.query {
+                    Some(ScoringQuery::Vector(query)) => hash_query(query, &mut hasher),
+                    query => query.as_ref().map(mem::discriminant).hash(&mut hasher),
+                }
+                request.prefetches.len().hash(&mut hasher);
+                request.limit.hash(&mut hasher);
+                request.offset.hash(&mut hasher);
+                request.seed.hash(&mut hasher);
+            }
+        }
+
+        mem::discriminant(&self.shard_selection).hash(&mut hasher);
+        match &self.shard_selection {
+            ShardSelectorInternal::Empty | ShardSelectorInternal::All => {}
+            ShardSelectorInternal::ShardKey(shard_key) => shard_key.hash(&mut hasher),
+            ShardSelectorInternal::ShardKeys(shard_keys) => shard_keys.hash(&mut hasher),
+            ShardSelectorInternal::ShardId(shard_id) => shard_id.hash(&mut hasher),
+        }
+
+        hasher.finish()
+    }
+}
+
+/// Hash the kind of a query, the name of its vector, and the vector itself for nearest queries.
+fn hash_query(query: &QueryEnum, hasher: &mut impl Hasher) {
+    mem::discriminant(query).hash(hasher);
+    query.get_vector_name().hash(hasher);
+
+    let QueryEnum::Nearest(vector) = query else {
+        return;
+    };
+    match vector.get_vector() {
+        VectorRef::Dense(vector) => hash_floats(vector, hasher),
+        VectorRef::Sparse(vector) => {
+            vector.indices.hash(hasher);
+            hash_floats(&vector.values, hasher);
+        }
+        VectorRef::MultiDense(vector) => hash_floats(vector.flattened_vectors, hasher),
+    }
+}
+
+fn hash_floats(floats: &[f32], hasher: &mut impl Hasher) {
+    for float in floats {
+        float.to_bits().hash(hasher);
+    }
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    key: QueryCacheKey,
+    result: Vec<ScoredPoint>,
+    inserted_at: Instant,
+}
+
+#[derive(Debug, Default)]
+struct QueryCacheState {
+    /// Incremented on every invalidation, so that results of requests which were running while
+    /// the collection got updated are not cached.
+    generation: u64,
+    /// Ordered from least to most recently used.
+    entries: IndexMap<u64, CacheEntry>,
+}
+
+/// Bounded LRU of search and query results of a single collection.
+#[derive(Debug)]
+pub struct QueryCache {
+    state: Mutex<QueryCacheState>,
+    capacity: usize,
+    ttl: Duration,
+}
+
+impl QueryCache {
+    pub fn new(config: QueryCacheConfig) -> Self {
+        Self {
+            state: Default::default(),
+            capacity: config.capacity,
+            ttl: Duration::from_secs(config.ttl_sec),
+        }
+    }
+
+    /// Cache for a collection, `None` if caching is disabled or not supported by the deployment.
+    pub fn from_storage_config(shared_storage_config: &SharedStorageConfig) -> Option<Self> {
+        if shared_storage_config.is_distributed {
+            return None;
+        }
+        shared_storage_config.query_cache.map(Self::new)
+    }
+
+    pub fn generation(&self) -> u64 {
+        self.state.lock().generation
+    }
+
+    pub fn get(&self, key: &QueryCacheKey) -> Option<Vec<ScoredPoint>> {
+        let fingerprint = key.fingerprint();
+        let mut state = self.state.lock();
+
+        let index = state.entries.get_index_of(&fingerprint)?;
+        let entry = &state.entries[index];
+        if entry.key != *key {
+            return None;
+        }
+        if entry.inserted_at.elapsed() >= self.ttl {
+            state.entries.shift_remove_index(index);
+            return None;
+        }
+
+        let result = entry.result.clone();
+        let last = state.entries.len() - 1;
+        state.entries.move_index(index, last);
+        Some(result)
+    }
+
+    /// Cache the result of a request, which was started at the given cache generation.
+    ///
+    /// The result is dropped if the cache was invalidated since then.
+    pub fn insert(&self, generation: u64, key: QueryCacheKey, result: Vec<ScoredPoint>) {
+        let mut state = self.state.lock();
+        if state.generation != generation {
+            return;
+        }
+
+        let entry = CacheEntry {
+            key,
+            result,
+            inserted_at: Instant::now(),
+        };
+        state.entries.shift_remove(&entry.key.fingerprint());
+        state.entries.insert(entry.key.fingerprint(), entry);
+
+        while state.entries.len() > self.capacity {
+            state.entries.shift_remove_index(0);
+        }
+    }
+
+    pub fn invalidate(&self) {
+        let mut state = self.state.lock();
+        state.generation += 1;
+        state.entries.clear();
+    }
+
+    /// Invalidate the cache after an update of the collection.
+    ///
+    /// Updates which are not waited for are acknowledged once written to the WAL, before they are
+    /// applied to the segments. In that case the cache is invalidated once more after the local
+    /// shards applied all updates received so far, which drops results cached in between.
+    pub fn invalidate_after_update(
+        self: &Arc<Self>,
+        shards_holder: &Arc<LockedShardHolder>,
+        wait: bool,
+    ) {
+        self.invalidate();
+
+        if wait {
+            return;
+        }
+
+        let cache = self.clone();
+        let shards_holder = shards_holder.clone();
+        tokio::spawn(async move {
+            let shard_holder = shards_holder.read().await;
+            for shard in shard_holder.all_shards() {
+                if let Err(err) = shard.plunge_local().await {
+                    log::debug!(
+                        "Failed to wait for updates of shard {} to be applied: {err}",
+                        shard.shard_id,
+                    );
+                }
+            }
+            cache.invalidate();
+        });
+    }
+
+    pub fn len(&self) -> usize {
+        self.state.lock().entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Collection {
+    /// Return cached results for the requests which have them, and execute the rest.
+    ///
+    /// `cache_key` returns `None` for requests which must not be cached.
+    pub(super) async fn with_query_cache<R, F, Fut>(
+        &self,
+        requests: Vec<R>,
+        cache_key: impl Fn(&R) -> Option<QueryCacheKey>,
+        execute: F,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: FnOnce(Vec<R>) -> Fut,
+        Fut: Future<Output = CollectionResult<Vec<Vec<ScoredPoint>>>>,
+    {
+        let Some(cache) = &self.query_cache else {
+            return execute(requests).await;
+        };
+
+        let generation = cache.generation();
+
+        let mut cached_results = Vec::with_capacity(requests.len());
+        let mut uncached_requests = Vec::new();
+        let mut uncached_keys = Vec::new();
+
+        for request in requests {
+            let key = cache_key(&request);
+            let cached = key.as_ref().and_then(|key| cache.get(key));
+            if cached.is_none() {
+                uncached_requests.push(request);
+                uncached_keys.push(key);
+            }
+            cached_results.push(cached);
+        }
+
+        let mut uncached_results = if uncached_requests.is_empty() {
+            Vec::new()
+        } else {
+            execute(uncached_requests).await?
+        }
+        .into_iter()
+        .zip(uncached_keys);
+
+        cached_results
+            .into_iter()
+            .map(|cached| {
+                if let Some(result) = cached {
+                    return Ok(result);
+                }
+
+                let (result, key) = uncached_results.next().ok_or_else(|| {
+                    CollectionError::service_error("Missing result of an uncached request")
+                })?;
+                if let Some(key) = key {
+                    cache.insert(generation, key, result.clone());
+                }
+                Ok(result)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::PointIdType;
+
+    use super::*;
+
+    fn key(limit: usize) -> QueryCacheKey {
+        let request = CoreSearchRequest {
+            query: QueryEnum::Nearest(vec![1.0, 2.0].into()),
+            filter: None,
+            params: None,
+            limit,
+            offset: 0,
+            with_payload: None,
+            with_vector: None,
+            score_threshold: None,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: false,
+        };
+        QueryCacheKey::new(CachedRequest::Search(request), ShardSelectorInternal::All)
+    }
+
+    fn result(id: u64) -> Vec<ScoredPoint> {
+        vec![ScoredPoint {
+            id: PointIdType::NumId(id),
+            version: 0,
+            score: 1.0,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            distance: None,
+            provenance: None,
+        }]
+    }
+
+    #[test]
+    fn test_query_cache() {
+        let cache = QueryCache::new(QueryCacheConfig {
+            capacity: 2,
+            ttl_sec: 60,
+        });
+
+        let generation = cache.generation();
+        cache.insert(generation, key(1), result(1));
+        cache.insert(generation, key(2), result(2));
+        assert_eq!(cache.get(&key(1)), Some(result(1)));
+        assert_eq!(cache.get(&key(3)), None);
+
+        // Least recently used entry is evicted
+        cache.insert(generation, key(3), result(3));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key(2)), None);
+        assert_eq!(cache.get(&key(1)), Some(result(1)));
+        assert_eq!(cache.get(&key(3)), Some(result(3)));
+
+        // Results of requests started before an invalidation are not cached
+        cache.invalidate();
+        assert!(cache.is_empty());
+        cache.insert(generation, key(1), result(1));
+        assert_eq!(cache.get(&key(1)), None);
+
+        cache.insert(cache.generation(), key(1), result(1));
+        assert_eq!(cache.get(&key(1)), Some(result(1)));
+    }
+
+    #[test]
+    fn test_query_cache_key_fingerprint() {
+        assert_eq!(key(1).fingerprint(), key(1).fingerprint());
+        assert_ne!(key(1).fingerprint(), key(2).fingerprint());
+
+        let mut other_vector = key(1);
+        let CachedRequest::Search(request) = &mut other_vector.request else {
+            unreachable!();
+        };
+        request.query = QueryEnum::Nearest(vec![2.0, 1.0].into());
+        assert_ne!(key(1).fingerprint(), other_vector.fingerprint());
+
+        let other_shard = QueryCacheKey {
+            shard_selection: ShardSelectorInternal::ShardId(1),
+            ..key(1)
+        };
+        assert_ne!(key(1).fingerprint(), other_shard.fingerprint());
+    }
+
+    #[test]
+    fn test_query_cache_ttl() {
+        let cache = QueryCache {
+            state: Default::default(),
+            capacity: 10,
+            ttl: Duration::ZERO,
+        };
+
+        cache.insert(cache.generation(), key(1), result(1));
+        assert_eq!(cache.get(&key(1)), None);
+        assert!(cache.is_empty());
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 
 use futures::Future;
 use parking_lot::Mutex;
+use segment::types::ShardKey;
 
 use super::Collection;
 use crate::config::ShardingMethod;
@@ -11,6 +12,7 @@ use crate::operations::types::CollectionResult;
 use crate::shards::replica_set::ReplicaState;
 use crate::shards::resharding::tasks_pool::{ReshardTaskItem, ReshardTaskProgress};
 use crate::shards::resharding::{self, ReshardKey, ReshardState};
+use crate::shards::shard::ShardId;
 use crate::shards::transfer::ShardTransferConsensus;
 
 impl Collection {
@@ -23,13 +25,51 @@ impl Collection {
             .clone()
     }
 
+    /// Local shard with more than `max_points` points, to be split by resharding up.
+    ///
+    /// A shard is only reported by the peer with the lowest ID among its active replicas, so that
+    /// a single split is proposed for it. Returns `None` while resharding is in progress.
+    pub async fn oversized_local_shard(
+        &self,
+        max_points: usize,
+    ) -> Option<(ShardId, Option<ShardKey>)> {
+        let shard_holder = self.shards_holder.read().await;
+        if shard_holder.resharding_state.read().is_some() {
+            return None;
+        }
+
+        for (&shard_id, replica_set) in shard_holder.get_shards() {
+            let lowest_active_peer = replica_set.active_shards().await.into_iter().min();
+            if lowest_active_peer != Some(self.this_peer_id) {
+                continue;
+            }
+
+            let Some(point_count) = replica_set.local_available_point_count().await else {
+                continue;
+            };
+            if point_count > max_points {
+                let shard_key = shard_holder
+                    .get_shard_id_to_key_mapping()
+                    .get(&shard_id)
+                    .cloned();
+                return Some((shard_id, shard_key));
+            }
+        }
+
+        None
+    }
+
     /// Start a new resharding operation
+    ///
+    /// Automatic resharding is driven by this collection until it finishes, if this peer is the
+    /// target of the resharding. Otherwise it's driven externally, stage by stage.
     pub async fn start_resharding<T, F>(
         &self,
         resharding_key: ReshardKey,
-        _consensus: Box<dyn ShardTransferConsensus>,
-        _on_finish: T,
-        _on_error: F,
+        automatic: bool,
+        consensus: Box<dyn ShardTransferConsensus>,
+        on_finish: T,
+        on_error: F,
     ) -> CollectionResult<()>
     where
         T: Future<Output = ()> + Send + 'static,
@@ -55,7 +95,11 @@ impl Collection {
                 None
             };
 
-            shard_holder.start_resharding_unchecked(resharding_key.clone(), replica_set)?;
+            shard_holder.start_resharding_unchecked(
+                resharding_key.clone(),
+                replica_set,
+                automatic,
+            )?;
 
             if resharding_key.direction == ReshardingDirection::Up {
                 let mut config = self.collection_config.write().await;
@@ -81,21 +125,22 @@ impl Collection {
             }
         }
 
-        // Drive resharding
-        // self.drive_resharding(resharding_key, consensus, false, on_finish, on_error)
-        //     .await?;
+        if automatic {
+            self.drive_resharding(resharding_key, consensus, false, on_finish, on_error)
+                .await?;
+        }
 
         Ok(())
     }
 
     /// Resume an existing resharding operation
     ///
-    /// This method will check if a resharding operation is in progress according to our state, and
-    /// it will start and resume the driving task accordingly.
+    /// This method will check if an automatic resharding operation is in progress according to our
+    /// state, and it will start and resume the driving task accordingly.
     ///
     /// This does not check whether the task is already active.
     ///
-    /// If no resharding is active, this returns early without error.
+    /// If no automatic resharding is active, this returns early without error.
     pub async fn resume_resharding_unchecked<T, F>(
         &self,
         consensus: Box<dyn ShardTransferConsensus>,
@@ -106,7 +151,11 @@ impl Collection {
         T: Future<Output = ()> + Send + 'static,
         F: Future<Output = ()> + Send + 'static,
     {
-        let Some(state) = self.resharding_state().await else {
+        let Some(state) = self
+            .resharding_state()
+            .await
+            .filter(|state| state.automatic)
+        else {
             return Ok(());
         };
 
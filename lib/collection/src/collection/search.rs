@@ -3,24 +3,49 @@ use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
+use cancel::CancellationToken;
 use futures::{future, TryFutureExt};
 use itertools::{Either, Itertools};
+use segment::data_types::score_modifier::ScoreModifier;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{
     ExtendedPointId, Filter, Order, ScoredPoint, WithPayloadInterface, WithVector,
 };
 use tokio::time::Instant;
 
+use super::query_cache::{CachedRequest, QueryCacheKey};
+use super::zero_vectors::search_has_results;
 use super::Collection;
 use crate::events::SlowQueryEvent;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::rerank::{self, Reranker};
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::*;
+use crate::shards::fan_out::{prioritize_shards, try_join_shards};
+
+/// Score modifier taken out of a search request, with the page to return after rescoring.
+struct PendingScoreModifier {
+    modifier: ScoreModifier,
+    offset: usize,
+    limit: usize,
+    order: Order,
+}
+
+/// Reranker taken out of a search request, with the page and the fields to return after reranking.
+struct PendingRerank {
+    reranker: Arc<dyn Reranker>,
+    query: QueryEnum,
+    offset: usize,
+    limit: usize,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: Option<WithVector>,
+}
 
 impl Collection {
     pub async fn search(
         &self,
-        request: CoreSearchRequest,
+        mut request: CoreSearchRequest,
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
@@ -28,22 +53,317 @@ impl Collection {
         if request.limit == 0 {
             return Ok(vec![]);
         }
+        if !search_has_results(&self.collection_config.read().await.params, &mut request) {
+            return Ok(vec![]);
+        }
+        if request.score_modifier.is_some() || request.rerank.is_some() {
+            let request_batch = CoreSearchRequestBatch {
+                searches: vec![request],
+            };
+            let results = self
+                .core_search_batch(
+                    request_batch,
+                    read_consistency,
+                    shard_selection.clone(),
+                    timeout,
+                    CancellationToken::new(),
+                )
+                .await?;
+            return Ok(results.into_iter().next().unwrap_or_default());
+        }
         // search is a special case of search_batch with a single batch
         let request_batch = CoreSearchRequestBatch {
             searches: vec![request],
         };
         let results = self
-            .do_core_search_batch(request_batch, read_consistency, shard_selection, timeout)
+            .do_core_search_batch(
+                request_batch,
+                read_consistency,
+                shard_selection,
+                timeout,
+                CancellationToken::new(),
+            )
             .await?;
         Ok(results.into_iter().next().unwrap())
     }
 
+    /// Search in all selected shards, the search stops early once `cancel` is triggered
     pub async fn core_search_batch(
         &self,
         request: CoreSearchRequestBatch,
         read_consistency: Option<ReadConsistency>,
         shard_selection: ShardSelectorInternal,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        self.without_zero_queries(request.searches, search_has_results, |searches| {
+            self.core_search_batch_with_modifiers(
+                CoreSearchRequestBatch { searches },
+                read_consistency,
+                shard_selection,
+                timeout,
+                cancel,
+            )
+        })
+        .await
+    }
+
+    async fn core_search_batch_with_modifiers(
+        &self,
+        mut request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        cancel: CancellationToken,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let start = Instant::now();
+
+        let rerankers = Self::take_rerankers(&mut request)?;
+        let score_modifiers = self.take_score_modifiers(&mut request).await?;
+
+        let results = self
+            .core_search_batch_by_vector(
+                request,
+                read_consistency.clone(),
+                shard_selection.clone(),
+                timeout,
+                cancel,
+            )
+            .await?;
+
+        if score_modifiers.iter().all(Option::is_none) && rerankers.iter().all(Option::is_none) {
+            return Ok(results);
+        }
+
+        let timeout = timeout.map(|t| t.saturating_sub(start.elapsed()));
+        let results = self
+            .apply_rerankers(
+                results,
+                rerankers,
+                read_consistency.clone(),
+                &shard_selection,
+                timeout,
+            )
+            .await?;
+
+        let timeout = timeout.map(|t| t.saturating_sub(start.elapsed()));
+        self.apply_score_modifiers(
+            results,
+            score_modifiers,
+            read_consistency,
+            &shard_selection,
+            timeout,
+        )
+        .await
+    }
+
+    /// Takes score modifiers out of the requests, so that shards only score by vector similarity.
+    ///
+    /// Requests with a modifier search from the first result instead and oversample, so that
+    /// points ranked below `offset + limit` by vector similarity can still move into the page.
+    async fn take_score_modifiers(
+        &self,
+        request: &mut CoreSearchRequestBatch,
+    ) -> CollectionResult<Vec<Option<PendingScoreModifier>>> {
+        if request.searches.iter().all(|s| s.score_modifier.is_none()) {
+            return Ok(request.searches.iter().map(|_| None).collect());
+        }
+
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        request
+            .searches
+            .iter_mut()
+            .map(|search| -> CollectionResult<_> {
+                let Some(modifier) = search.score_modifier.take() else {
+                    return Ok(None);
+                };
+
+                let order = if search.query.is_distance_scored() {
+                    collection_params
+                        .get_search_distance(
+                            search.query.get_vector_name(),
+                            search.params.as_ref(),
+                        )?
+                        .distance_order()
+                } else {
+                    Order::LargeBetter
+                };
+
+                let pending = PendingScoreModifier {
+                    modifier,
+                    offset: search.offset,
+                    limit: search.limit,
+                    order,
+                };
+
+                search.limit = pending.modifier.candidates(search.offset, search.limit);
+                search.offset = 0;
+
+                Ok(Some(pending))
+            })
+            .collect()
+    }
+
+    /// Takes rerankers out of the requests, so that shards only search by vector similarity.
+    ///
+    /// Requests with a reranker search for all rerank candidates from the first result, with
+    /// payload and vectors, which are passed to the reranker.
+    fn take_rerankers(
+        request: &mut CoreSearchRequestBatch,
+    ) -> CollectionResult<Vec<Option<PendingRerank>>> {
+        request
+            .searches
+            .iter_mut()
+            .map(|search| -> CollectionResult<_> {
+                let Some(params) = search.rerank.take() else {
+                    return Ok(None);
+                };
+
+                if search.score_modifier.is_some() {
+                    return Err(CollectionError::bad_input(
+                        "Rerank can't be combined with a score modifier",
+                    ));
+                }
+
+                let reranker = rerank::get_reranker(&params.name)?;
+                let candidates = rerank::rerank_candidates(&params, search.offset + search.limit)?;
+
+                let pending = PendingRerank {
+                    reranker,
+                    query: search.query.clone(),
+                    offset: search.offset,
+                    limit: search.limit,
+                    with_payload: search
+                        .with_payload
+                        .replace(WithPayloadInterface::Bool(true)),
+                    with_vector: search.with_vector.replace(WithVector::Bool(true)),
+                };
+
+                search.limit = candidates;
+                search.offset = 0;
+
+                Ok(Some(pending))
+            })
+            .collect()
+    }
+
+    async fn apply_rerankers(
+        &self,
+        results: Vec<Vec<ScoredPoint>>,
+        rerankers: Vec<Option<PendingRerank>>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let mut reranked_results = Vec::with_capacity(results.len());
+
+        for (candidates, pending) in results.into_iter().zip(rerankers) {
+            let Some(PendingRerank {
+                reranker,
+                query,
+                offset,
+                limit,
+                with_payload,
+                with_vector,
+            }) = pending
+            else {
+                reranked_results.push(candidates);
+                continue;
+            };
+
+            // Rerankers may be expensive, so they don't block the async runtime
+            let reranked = self
+                .search_runtime
+                .spawn_blocking(move || rerank::rerank(reranker.as_ref(), &query, candidates))
+                .await??;
+            let page = reranked.into_iter().skip(offset).take(limit).collect();
+
+            // Candidates have all payload and vectors, return only the requested ones
+            let page = self
+                .fill_search_result_with_payload(
+                    page,
+                    Some(with_payload.unwrap_or(WithPayloadInterface::Bool(false))),
+                    with_vector.unwrap_or_default(),
+                    read_consistency.clone(),
+                    shard_selection,
+                    timeout,
+                )
+                .await?;
+            reranked_results.push(page);
+        }
+
+        Ok(reranked_results)
+    }
+
+    async fn apply_score_modifiers(
+        &self,
+        results: Vec<Vec<ScoredPoint>>,
+        score_modifiers: Vec<Option<PendingScoreModifier>>,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let mut modified_results = Vec::with_capacity(results.len());
+
+        for (mut points, pending) in results.into_iter().zip(score_modifiers) {
+            let Some(PendingScoreModifier {
+                modifier,
+                offset,
+                limit,
+                order,
+            }) = pending
+            else {
+                modified_results.push(points);
+                continue;
+            };
+
+            // Retrieve only the fields used by the expression, independent of requested payload
+            let fields = modifier.expression.fields();
+            let mut payloads = HashMap::new();
+            if !fields.is_empty() && !points.is_empty() {
+                let retrieve_request = PointRequestInternal {
+                    ids: points.iter().map(|point| point.id).collect(),
+                    with_payload: Some(WithPayloadInterface::Fields(fields.to_vec())),
+                    with_vector: WithVector::Bool(false),
+                    with_version: false,
+                };
+                payloads = self
+                    .retrieve(
+                        retrieve_request,
+                        read_consistency.clone(),
+                        shard_selection,
+                        timeout,
+                    )
+                    .await?
+                    .into_iter()
+                    .filter_map(|record| Some((record.id, record.payload?)))
+                    .collect();
+            }
+
+            for point in &mut points {
+                point.score = modifier.apply(point.score, payloads.get(&point.id));
+            }
+
+            // Stable sort, points with equal scores keep their vector similarity order
+            match order {
+                Order::LargeBetter => points.sort_by(|a, b| b.score.total_cmp(&a.score)),
+                Order::SmallBetter => points.sort_by(|a, b| a.score.total_cmp(&b.score)),
+            }
+
+            modified_results.push(points.into_iter().skip(offset).take(limit).collect());
+        }
+
+        Ok(modified_results)
+    }
+
+    async fn core_search_batch_by_vector(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: ShardSelectorInternal,
+        timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let start = Instant::now();
         // shortcuts batch if all requests with limit=0
@@ -96,9 +416,10 @@ impl Collection {
             let without_payload_results = self
                 .do_core_search_batch(
                     without_payload_batch,
-                    read_consistency,
+                    read_consistency.clone(),
                     &shard_selection,
                     timeout,
+                    cancel,
                 )
                 .await?;
             // update timeout
@@ -111,7 +432,7 @@ impl Collection {
                         without_payload_result,
                         req.with_payload.clone(),
                         req.with_vector.unwrap_or_default(),
-                        read_consistency,
+                        read_consistency.clone(),
                         &shard_selection,
                         timeout,
                     )
@@ -119,7 +440,7 @@ impl Collection {
             future::try_join_all(filled_results).await
         } else {
             let result = self
-                .do_core_search_batch(request, read_consistency, &shard_selection, timeout)
+                .do_core_search_batch(request, read_consistency, &shard_selection, timeout, cancel)
                 .await?;
             Ok(result)
         }
@@ -131,6 +452,41 @@ impl Collection {
         read_consistency: Option<ReadConsistency>,
         shard_selection: &ShardSelectorInternal,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        // Explicit read consistency asks for fresh results
+        let is_cacheable = read_consistency.is_none();
+
+        self.with_query_cache(
+            request.searches,
+            |search| {
+                is_cacheable.then(|| {
+                    QueryCacheKey::new(
+                        CachedRequest::Search(search.clone()),
+                        shard_selection.clone(),
+                    )
+                })
+            },
+            |searches| {
+                self.do_core_search_batch_uncached(
+                    CoreSearchRequestBatch { searches },
+                    read_consistency,
+                    shard_selection,
+                    timeout,
+                    cancel,
+                )
+            },
+        )
+        .await
+    }
+
+    async fn do_core_search_batch_uncached(
+        &self,
+        request: CoreSearchRequestBatch,
+        read_consistency: Option<ReadConsistency>,
+        shard_selection: &ShardSelectorInternal,
+        timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let request = Arc::new(request);
 
@@ -139,17 +495,29 @@ impl Collection {
         // query all shards concurrently
         let all_searches_res = {
             let shard_holder = self.shards_holder.read().await;
-            let target_shards = shard_holder.select_shards(shard_selection)?;
+            let mut target_shards = shard_holder.select_shards(shard_selection)?;
+            let concurrency = self.shared_storage_config.search_shard_concurrency;
+            if concurrency.is_some() {
+                prioritize_shards(&mut target_shards);
+            }
             let all_searches = target_shards.iter().map(|(shard, shard_key)| {
+                let shard_id = shard.shard_id;
                 let shard_key = shard_key.cloned();
                 shard
                     .core_search(
                         Arc::clone(&request),
-                        read_consistency,
+                        read_consistency.clone(),
                         shard_selection.is_shard_id(),
                         timeout,
+                        cancel.clone(),
                     )
                     .and_then(move |mut records| async move {
+                        records
+                            .iter_mut()
+                            .flatten()
+                            .filter_map(|point| point.provenance.as_mut())
+                            .for_each(|provenance| provenance.shard_id = Some(shard_id));
+
                         if shard_key.is_none() {
                             return Ok(records);
                         }
@@ -161,7 +529,9 @@ impl Collection {
                         Ok(records)
                     })
             });
-            future::try_join_all(all_searches).await?
+            // Stop waiting for shards once cancelled, even for those which are slow to react
+            cancel::future::cancel_on_token(cancel, try_join_shards(all_searches, concurrency))
+                .await??
         };
 
         let result = self
@@ -176,6 +546,10 @@ impl Collection {
 
         self.post_process_if_slow_request(instant.elapsed(), filters_refs);
 
+        for search in &request.searches {
+            self.track_filter_usage([search.filter.as_ref()]);
+        }
+
         result
     }
 
@@ -206,6 +580,7 @@ impl Collection {
             ids: search_result.iter().map(|x| x.id).collect(),
             with_payload,
             with_vector,
+            with_version: false,
         };
         let retrieved_records = self
             .retrieve(retrieve_request, read_consistency, shard_selection, timeout)
@@ -247,7 +622,7 @@ impl Collection {
         for (batch_index, request) in request.searches.iter().enumerate() {
             let order = if request.query.is_distance_scored() {
                 collection_params
-                    .get_distance(request.query.get_vector_name())?
+                    .get_search_distance(request.query.get_vector_name(), request.params.as_ref())?
                     .distance_order()
             } else {
                 // Score comes from special handling of the distances in a way that it doesn't
@@ -73,6 +73,11 @@ impl Collection {
             let to_replica_set = shards_holder.get_shard(&to_shard_id).ok_or_else(|| {
                 CollectionError::service_error(format!("Shard {to_shard_id} doesn't exist"))
             })?;
+
+            // Remember if target replica is created by this transfer, so that abort can remove it.
+            // Consensus state is the same on all peers, so all of them come to the same conclusion.
+            shard_transfer.target_created = to_replica_set.peer_state(&shard_transfer.to).is_none();
+
             let _was_not_transferred =
                 shards_holder.register_start_shard_transfer(shard_transfer.clone())?;
 
@@ -198,6 +203,15 @@ impl Collection {
             None => shard_holder_guard.insert(self.shards_holder.read().await),
         };
 
+        // Transfer might have been aborted concurrently, don't promote replicas in that case
+        if !shard_holder.check_transfer_exists(&transfer.key()) {
+            log::warn!(
+                "Finishing shard transfer {:?}, but it is not registered anymore, ignoring",
+                transfer.key(),
+            );
+            return Ok(());
+        }
+
         let is_resharding_transfer = transfer
             .method
             .map_or(false, |method| method.is_resharding());
@@ -272,6 +286,9 @@ impl Collection {
     /// 2. Stop transfer task
     /// 3. Unwrap the proxy
     /// 4. Remove temp shard, or mark it as dead
+    ///
+    /// Target replica created by the transfer is removed together with partially received data,
+    /// so both source and target are back in their pre-transfer roles.
     pub async fn abort_shard_transfer(
         &self,
         transfer_key: ShardTransferKey,
@@ -318,7 +335,8 @@ impl Collection {
                     //   to all other shards; all other shards are expected to be `Active`,
                     //   and so failed transfer does not introduce any inconsistencies to points
                     //   that are not affected by resharding in all other shards
-                } else if transfer.sync {
+                } else if transfer.sync && !transfer.target_created {
+                    // Replica existed before the transfer, but its data might be inconsistent now
                     replica_set.set_replica_state(&transfer.to, ReplicaState::Dead)?;
                 } else {
                     replica_set.remove_peer(transfer.to).await?;
@@ -0,0 +1,189 @@
+//! Snapshots created periodically, according to the snapshot schedule of the storage config.
+//!
+//! The schedule is driven by the table of content, which owns the collections, while each
+//! collection creates its scheduled snapshots and deletes the old ones. Only one scheduled snapshot
+//! of a collection is created at a time: if the previous one is still running when the next one is
+//! due, the next one is skipped instead of being queued.
+
+use std::num::NonZeroUsize;
+use std::path::Path;
+
+use chrono::Utc;
+
+use super::Collection;
+use crate::events::ScheduledSnapshotEvent;
+use crate::operations::snapshot_ops::SnapshotDescription;
+use crate::operations::types::CollectionResult;
+use crate::shards::shard::PeerId;
+use crate::telemetry::ScheduledSnapshotsTelemetry;
+
+/// State of the scheduled snapshots of a collection
+#[derive(Default)]
+pub(super) struct ScheduledSnapshots {
+    /// Held while a scheduled snapshot is being created
+    running: tokio::sync::Mutex<()>,
+    telemetry: parking_lot::Mutex<ScheduledSnapshotsTelemetry>,
+}
+
+impl Collection {
+    /// Create a scheduled snapshot, and delete the oldest scheduled snapshots to keep `retention`
+    /// of them.
+    ///
+    /// Returns `None` if the snapshot is skipped, because the previous scheduled snapshot is still
+    /// being created.
+    pub async fn create_scheduled_snapshot(
+        &self,
+        global_temp_dir: &Path,
+        this_peer_id: PeerId,
+        retention: NonZeroUsize,
+    ) -> CollectionResult<Option<SnapshotDescription>> {
+        let Ok(_running) = self.scheduled_snapshots.running.try_lock() else {
+            log::warn!(
+                "Skipping scheduled snapshot of collection {}, the previous one is still running",
+                self.id,
+            );
+            self.scheduled_snapshots.telemetry.lock().skipped += 1;
+            return Ok(None);
+        };
+
+        let prefix = scheduled_snapshot_prefix(&self.id, this_peer_id);
+        let snapshot_name = format!(
+            "{prefix}{}.snapshot",
+            Utc::now().format("%Y-%m-%d-%H-%M-%S"),
+        );
+
+        let result = self
+            .create_snapshot_with_name(global_temp_dir, &snapshot_name)
+            .await;
+
+        {
+            let mut telemetry = self.scheduled_snapshots.telemetry.lock();
+            match &result {
+                Ok(_) => {
+                    telemetry.succeeded += 1;
+                    telemetry.last_success = Some(Utc::now());
+                }
+                Err(err) => {
+                    telemetry.failed += 1;
+                    telemetry.last_error = Some(err.to_string());
+                }
+            }
+        }
+
+        match &result {
+            Ok(snapshot) => log::info!(
+                "Created scheduled snapshot {} of collection {}",
+                snapshot.name,
+                self.id,
+            ),
+            Err(err) => log::error!(
+                "Failed to create scheduled snapshot of collection {}: {err}",
+                self.id,
+            ),
+        }
+
+        issues::publish(ScheduledSnapshotEvent {
+            collection_id: self.id.clone(),
+            result: result.as_ref().cloned().map_err(|err| err.to_string()),
+        });
+
+        let snapshot = result?;
+
+        // The snapshot is created, failing to delete old ones doesn't fail it
+        if let Err(err) = self.prune_scheduled_snapshots(&prefix, retention).await {
+            log::warn!(
+                "Failed to delete old scheduled snapshots of collection {}: {err}",
+                self.id,
+            );
+        }
+
+        Ok(Some(snapshot))
+    }
+
+    /// Delete scheduled snapshots of this peer, except for the latest `retention` ones
+    async fn prune_scheduled_snapshots(
+        &self,
+        prefix: &str,
+        retention: NonZeroUsize,
+    ) -> CollectionResult<()> {
+        let snapshot_manager = self.get_snapshots_storage_manager()?;
+        let snapshots = snapshot_manager
+            .list_snapshots(&self.snapshots_path)
+            .await?;
+
+        let names = snapshots.into_iter().map(|snapshot| snapshot.name);
+        for name in outdated_snapshots(names, prefix, retention) {
+            let path = snapshot_manager
+                .get_snapshot_path(&self.snapshots_path, &name)
+                .await?;
+            log::debug!("Deleting outdated scheduled snapshot {name}");
+            snapshot_manager.delete_snapshot(&path).await?;
+        }
+
+        Ok(())
+    }
+
+    /// Telemetry of the scheduled snapshots, `None` if snapshots are not scheduled
+    pub(super) fn scheduled_snapshots_telemetry(&self) -> Option<ScheduledSnapshotsTelemetry> {
+        self.shared_storage_config
+            .snapshots_config
+            .schedule
+            .as_ref()
+            .map(|_| self.scheduled_snapshots.telemetry.lock().clone())
+    }
+}
+
+/// Names of scheduled snapshots only differ by their time, manually created snapshots of the
+/// collection don't have the `scheduled` part.
+fn scheduled_snapshot_prefix(collection_name: &str, this_peer_id: PeerId) -> String {
+    format!("{collection_name}-{this_peer_id}-scheduled-")
+}
+
+/// Scheduled snapshots to delete, all except the latest `retention` ones.
+///
+/// The time in the names is formatted to sort chronologically.
+fn outdated_snapshots(
+    names: impl IntoIterator<Item = String>,
+    prefix: &str,
+    retention: NonZeroUsize,
+) -> Vec<String> {
+    let mut scheduled: Vec<_> = names
+        .into_iter()
+        .filter(|name| name.starts_with(prefix) && name.ends_with(".snapshot"))
+        .collect();
+    scheduled.sort_unstable();
+
+    let outdated = scheduled.len().saturating_sub(retention.get());
+    scheduled.truncate(outdated);
+    scheduled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_outdated_snapshots() {
+        let prefix = scheduled_snapshot_prefix("test", 1);
+        let names = [
+            "test-1-scheduled-2024-01-03-00-00-00.snapshot",
+            "test-1-scheduled-2024-01-01-00-00-00.snapshot",
+            // Manual snapshot
+            "test-1-2024-01-01-00-00-00.snapshot",
+            // Other peer
+            "test-2-scheduled-2024-01-01-00-00-00.snapshot",
+            "test-1-scheduled-2024-01-02-00-00-00.snapshot",
+            "test-1-scheduled-2024-01-02-00-00-00.snapshot.checksum",
+        ]
+        .map(String::from);
+
+        let retention = NonZeroUsize::new(2).unwrap();
+        assert_eq!(
+            outdated_snapshots(names.clone(), &prefix, retention),
+            vec!["test-1-scheduled-2024-01-01-00-00-00.snapshot".to_string()],
+        );
+
+        let retention = NonZeroUsize::new(3).unwrap();
+        assert!(outdated_snapshots(names, &prefix, retention).is_empty());
+    }
+}
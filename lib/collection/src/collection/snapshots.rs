@@ -56,8 +56,18 @@ impl Collection {
             chrono::Utc::now().format("%Y-%m-%d-%H-%M-%S"),
         );
 
+        self.create_snapshot_with_name(global_temp_dir, &snapshot_name)
+            .await
+    }
+
+    /// Creates a snapshot of the collection with the given file name, see `create_snapshot`.
+    pub(super) async fn create_snapshot_with_name(
+        &self,
+        global_temp_dir: &Path,
+        snapshot_name: &str,
+    ) -> CollectionResult<SnapshotDescription> {
         // Final location of snapshot
-        let snapshot_path = self.snapshots_path.join(&snapshot_name);
+        let snapshot_path = self.snapshots_path.join(snapshot_name);
         log::info!(
             "Creating collection snapshot {} into {:?}",
             snapshot_name,
@@ -225,6 +235,27 @@ impl Collection {
         Ok(())
     }
 
+    /// Check that local shard data at `snapshot_shard_path` can be restored into this collection
+    ///
+    /// Allows to validate all shards of a snapshot before restoring any of them.
+    /// Shards without local data are skipped, as they are not restored either.
+    pub async fn check_local_shard_snapshot(
+        &self,
+        snapshot_shard_path: &Path,
+    ) -> CollectionResult<()> {
+        if !LocalShard::check_data(snapshot_shard_path) {
+            return Ok(());
+        }
+
+        let snapshot_shard_path = snapshot_shard_path.to_path_buf();
+        let collection_config = self.collection_config.read().await.clone();
+
+        tokio::task::spawn_blocking(move || {
+            LocalShard::check_data_compatibility(&snapshot_shard_path, &collection_config)
+        })
+        .await?
+    }
+
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
@@ -234,10 +265,6 @@ impl Collection {
         shard_id: ShardId,
         cancel: cancel::CancellationToken,
     ) -> CollectionResult<bool> {
-        // TODO:
-        //   Check that shard snapshot is compatible with the collection
-        //   (see `VectorsConfig::check_compatible_with_segment_config`)
-
         // `ShardHolder::recover_local_shard_from` is *not* cancel safe
         // (see `ShardReplicaSet::restore_local_replica_from`)
         self.shards_holder
@@ -282,10 +309,6 @@ impl Collection {
         temp_dir: &Path,
         cancel: cancel::CancellationToken,
     ) -> CollectionResult<()> {
-        // TODO:
-        //   Check that shard snapshot is compatible with the collection
-        //   (see `VectorsConfig::check_compatible_with_segment_config`)
-
         // `ShardHolder::restore_shard_snapshot` is *not* cancel safe
         // (see `ShardReplicaSet::restore_local_replica_from`)
         self.shards_holder
@@ -0,0 +1,329 @@
+//! Expiry of points after a time-to-live, for cache-like collections.
+//!
+//! The TTL of a point is counted from a unix timestamp in its payload, which is either managed by
+//! the user, or set on insertion and updates of points. Upserts carry the timestamp to the shards,
+//! which set it depending on whether points already exist. A background task
+//! periodically looks for expired points and deletes them in batches, to bound the size of each
+//! delete operation.
+//!
+//! In a cluster, expired points of a shard are only deleted by the peer which would lead updates
+//! of the shard with medium write ordering, and the deletes are replicated as regular updates.
+
+use std::sync::{Arc, Weak};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use segment::types::{
+    Condition, FieldCondition, Filter, PointIdType, Range, WithPayloadInterface, WithVector,
+};
+use serde_json::Value;
+use tokio::sync::RwLock;
+
+use super::query_cache::QueryCache;
+use super::Collection;
+use crate::config::TtlConfig;
+use crate::operations::payload_ops::PayloadOps;
+use crate::operations::point_ops::{PointOperations, TtlStamp, WriteOrdering};
+use crate::operations::types::CollectionResult;
+use crate::operations::CollectionUpdateOperations;
+use crate::shards::replica_set::ShardReplicaSet;
+use crate::shards::shard_holder::LockedShardHolder;
+use crate::shards::CollectionId;
+
+impl Collection {
+    /// Start the background task which deletes expired points, if the collection has a TTL.
+    ///
+    /// The task stops on its own once the collection is dropped.
+    pub(super) fn start_ttl_task(&self, ttl_config: Option<TtlConfig>) {
+        let Some(ttl_config) = ttl_config else {
+            return;
+        };
+
+        self.update_runtime.spawn(run_ttl_task(
+            self.id.clone(),
+            Arc::downgrade(&self.shards_holder),
+            self.updates_lock.clone(),
+            self.query_cache.clone(),
+            ttl_config,
+        ));
+    }
+
+    /// Prepare TTL timestamps of points inserted or updated by an operation, `None` if the
+    /// collection has no TTL.
+    pub(super) async fn ttl_timestamps(&self) -> Option<TtlTimestamps> {
+        let config = self.collection_config.read().await.ttl_config.clone()?;
+        Some(TtlTimestamps {
+            config,
+            now: unix_timestamp().as_secs(),
+        })
+    }
+}
+
+/// TTL timestamps to set on points of an update operation.
+///
+/// Prepared once for the whole operation, and applied to the parts of the operation sent to each
+/// shard.
+pub(super) struct TtlTimestamps {
+    config: TtlConfig,
+    /// Unix timestamp in seconds
+    now: u64,
+}
+
+impl TtlTimestamps {
+    /// Set timestamps of points inserted or updated by the operation, which don't set them
+    /// explicitly.
+    ///
+    /// Timestamps of upserted points depend on whether they exist, so they are set by the shard
+    /// applying the upsert.
+    pub fn apply(&self, mut operation: CollectionUpdateOperations) -> CollectionUpdateOperations {
+        let field = &self.config.timestamp_field.first_key;
+
+        if let CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::SetPayload(op)
+            | PayloadOps::OverwritePayload(op)
+            | PayloadOps::MergePatchPayload(op),
+        ) = &mut operation
+        {
+            if self.config.refresh_on_update && op.key.is_none() && !op.payload.contains_key(field)
+            {
+                op.payload.0.insert(field.clone(), Value::from(self.now));
+            }
+        }
+
+        match operation {
+            CollectionUpdateOperations::PointOperation(point_operation) => {
+                let stamp = TtlStamp {
+                    key: field.clone(),
+                    timestamp: self.now,
+                    set_on_insert: self.config.set_on_insert,
+                    refresh_on_update: self.config.refresh_on_update,
+                };
+                CollectionUpdateOperations::PointOperation(point_operation.with_ttl_stamp(stamp))
+            }
+            operation => operation,
+        }
+    }
+}
+
+async fn run_ttl_task(
+    collection_id: CollectionId,
+    shards_holder: Weak<LockedShardHolder>,
+    updates_lock: Arc<RwLock<()>>,
+    query_cache: Option<Arc<QueryCache>>,
+    ttl_config: TtlConfig,
+) {
+    let check_interval = Duration::from_secs(ttl_config.check_interval_sec);
+
+    loop {
+        tokio::time::sleep(check_interval).await;
+
+        let Some(shards_holder) = shards_holder.upgrade() else {
+            return;
+        };
+
+        let result = delete_expired_points(&shards_holder, &updates_lock, &ttl_config).await;
+
+        // Some points may have been deleted before a failure
+        if !matches!(result, Ok(0)) {
+            if let Some(query_cache) = &query_cache {
+                query_cache.invalidate();
+            }
+        }
+
+        match result {
+            Ok(0) => {}
+            Ok(deleted) => {
+                log::debug!("Deleted {deleted} expired points of collection {collection_id}");
+            }
+            Err(err) => {
+                log::warn!("Failed to delete expired points of collection {collection_id}: {err}");
+            }
+        }
+    }
+}
+
+/// Delete points which expired before the start of the call, returns the number of deleted
+/// points.
+///
+/// Each shard is cleaned up in batches of `delete_batch_size` points, and locks are released
+/// between the batches, so that other updates are not blocked for long.
+async fn delete_expired_points(
+    shards_holder: &LockedShardHolder,
+    updates_lock: &RwLock<()>,
+    ttl_config: &TtlConfig,
+) -> CollectionResult<usize> {
+    let cutoff = unix_timestamp().saturating_sub(Duration::from_secs(ttl_config.ttl_sec));
+    let filter = expired_filter(ttl_config, cutoff);
+    let batch_size = ttl_config.delete_batch_size;
+
+    let mut deleted = 0;
+    loop {
+        let _update_lock = updates_lock.read().await;
+        let shard_holder = shards_holder.read().await;
+
+        let mut has_more = false;
+        for replica_set in shard_holder.all_shards() {
+            if !is_expiry_leader(replica_set).await {
+                continue;
+            }
+
+            let expired = replica_set
+                .scroll_by(
+                    None,
+                    batch_size,
+                    &WithPayloadInterface::Bool(false),
+                    &WithVector::Bool(false),
+                    Some(&filter),
+                    None,
+                    true,
+                    None,
+                    None,
+                )
+                .await?;
+            if expired.is_empty() {
+                continue;
+            }
+
+            let ids: Vec<PointIdType> = expired.into_iter().map(|record| record.id).collect();
+            let count = ids.len();
+            let operation =
+                CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints { ids });
+            replica_set
+                .update_with_consistency(operation, true, WriteOrdering::Medium, None, false)
+                .await?;
+
+            deleted += count;
+            has_more |= count == batch_size;
+        }
+
+        if !has_more {
+            return Ok(deleted);
+        }
+    }
+}
+
+/// Whether this peer deletes expired points of the shard, there is one such peer per shard
+async fn is_expiry_leader(replica_set: &ShardReplicaSet) -> bool {
+    replica_set.has_local_shard().await
+        && replica_set.leader_peer_for_update(WriteOrdering::Medium)
+            == Some(replica_set.this_peer_id())
+}
+
+fn expired_filter(ttl_config: &TtlConfig, cutoff: Duration) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_range(
+        ttl_config.timestamp_field.clone(),
+        Range {
+            lt: Some(cutoff.as_secs_f64()),
+            gt: None,
+            gte: None,
+            lte: None,
+        },
+    )))
+}
+
+fn unix_timestamp() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::VectorStructInternal;
+    use segment::json_path::JsonPath;
+    use segment::types::Payload;
+
+    use super::*;
+    use crate::operations::payload_ops::SetPayloadOp;
+    use crate::operations::point_ops::{
+        PointInsertOperationsInternal, PointStruct, TtlInsertOperationInternal,
+    };
+
+    fn ttl_config() -> TtlConfig {
+        TtlConfig {
+            ttl_sec: 60,
+            timestamp_field: JsonPath::new("created_at"),
+            set_on_insert: true,
+            refresh_on_update: false,
+            check_interval_sec: 60,
+            delete_batch_size: 100,
+        }
+    }
+
+    #[test]
+    fn test_apply_ttl_timestamps() {
+        let ttl_timestamps = TtlTimestamps {
+            config: ttl_config(),
+            now: 1_000,
+        };
+
+        // Timestamps of upserted points are left to the shard
+        let points_op = PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: 1.into(),
+            vector: VectorStructInternal::from(vec![1.0]).into(),
+            payload: None,
+            vector_metadata: None,
+        }]);
+        let operation = ttl_timestamps.apply(CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(points_op.clone()),
+        ));
+        let CollectionUpdateOperations::PointOperation(PointOperations::UpsertPointsWithTtl(
+            TtlInsertOperationInternal { upsert, stamp },
+        )) = operation
+        else {
+            panic!("expected an upsert with TTL timestamps");
+        };
+        assert_eq!(upsert.points_op, points_op);
+        assert!(upsert.expected_versions.is_empty());
+        assert_eq!(
+            stamp,
+            TtlStamp {
+                key: "created_at".to_string(),
+                timestamp: 1_000,
+                set_on_insert: true,
+                refresh_on_update: false,
+            },
+        );
+
+        // Payload updates only refresh timestamps if configured
+        let set_payload =
+            CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(SetPayloadOp {
+                payload: Payload::from(serde_json::json!({"color": "red"})),
+                points: Some(vec![1.into()]),
+                filter: None,
+                key: None,
+            }));
+        assert_eq!(ttl_timestamps.apply(set_payload.clone()), set_payload);
+
+        let refreshed = TtlTimestamps {
+            config: TtlConfig {
+                refresh_on_update: true,
+                ..ttl_config()
+            },
+            now: 1_000,
+        }
+        .apply(set_payload);
+        let CollectionUpdateOperations::PayloadOperation(PayloadOps::SetPayload(op)) = refreshed
+        else {
+            panic!("expected a set payload operation");
+        };
+        assert_eq!(op.payload.0.get("created_at"), Some(&Value::from(1_000)));
+    }
+
+    #[test]
+    fn test_expired_filter() {
+        let filter = expired_filter(&ttl_config(), Duration::from_secs(1_000));
+        let Some(Condition::Field(condition)) = filter.must.as_ref().and_then(|c| c.first()) else {
+            panic!("expected a field condition");
+        };
+        assert_eq!(condition.key, JsonPath::new("created_at"));
+        assert_eq!(
+            condition.range,
+            Some(segment::types::RangeInterface::Float(Range {
+                lt: Some(1_000.0),
+                gt: None,
+                gte: None,
+                lte: None,
+            })),
+        );
+    }
+}
@@ -0,0 +1,190 @@
+//! Queries by zero vectors, for vectors with a zero vector policy.
+//!
+//! Cosine similarity is undefined for zero vectors. Unless the policy scores them as 0, a query
+//! by a zero vector has no results, and is not sent to the shards at all.
+
+use std::future::Future;
+
+use segment::data_types::vectors::NamedVectorStruct;
+use segment::types::ScoredPoint;
+
+use super::Collection;
+use crate::config::CollectionParams;
+use crate::operations::point_ops::is_zero_vector;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::types::{CollectionResult, CoreSearchRequest, ZeroVectorPolicy};
+use crate::operations::universal_query::shard_query::{
+    ScoringQuery, ShardPrefetch, ShardQueryRequest,
+};
+
+impl Collection {
+    /// Execute only requests which may have results, other requests get an empty result.
+    ///
+    /// `has_results` may also remove parts of a request which have no results.
+    pub(super) async fn without_zero_queries<R, F, Fut>(
+        &self,
+        mut requests: Vec<R>,
+        has_results: impl Fn(&CollectionParams, &mut R) -> bool,
+        execute: F,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>>
+    where
+        F: FnOnce(Vec<R>) -> Fut,
+        Fut: Future<Output = CollectionResult<Vec<Vec<ScoredPoint>>>>,
+    {
+        let executed: Vec<bool> = {
+            let collection_config = self.collection_config.read().await;
+            requests
+                .iter_mut()
+                .map(|request| has_results(&collection_config.params, request))
+                .collect()
+        };
+
+        if executed.iter().all(|&executed| executed) {
+            return execute(requests).await;
+        }
+
+        let requests: Vec<_> = requests
+            .into_iter()
+            .zip(&executed)
+            .filter_map(|(request, &executed)| executed.then_some(request))
+            .collect();
+
+        let mut results = if requests.is_empty() {
+            Vec::new()
+        } else {
+            execute(requests).await?
+        }
+        .into_iter();
+
+        Ok(executed
+            .into_iter()
+            .map(|executed| {
+                if executed {
+                    results.next().unwrap_or_default()
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect())
+    }
+}
+
+/// Whether the search may have results
+pub(super) fn search_has_results(
+    params: &CollectionParams,
+    request: &mut CoreSearchRequest,
+) -> bool {
+    !is_excluded_zero_query(params, &request.query)
+}
+
+/// Whether the query may have results, prefetches without results are removed from it
+pub(super) fn query_has_results(
+    params: &CollectionParams,
+    request: &mut ShardQueryRequest,
+) -> bool {
+    has_results(params, request.query.as_ref(), &mut request.prefetches)
+}
+
+fn has_results(
+    params: &CollectionParams,
+    query: Option<&ScoringQuery>,
+    prefetches: &mut Vec<ShardPrefetch>,
+) -> bool {
+    if let Some(ScoringQuery::Vector(query)) = query {
+        if is_excluded_zero_query(params, query) {
+            return false;
+        }
+    }
+
+    // A query without prefetches scores the whole collection, so it must keep at least one
+    if prefetches.is_empty() {
+        return true;
+    }
+    prefetches.retain_mut(|prefetch| {
+        has_results(params, prefetch.query.as_ref(), &mut prefetch.prefetches)
+    });
+    !prefetches.is_empty()
+}
+
+/// Whether the query is a nearest search by a zero vector, which has no results by the zero
+/// vector policy of the searched vector
+fn is_excluded_zero_query(params: &CollectionParams, query: &QueryEnum) -> bool {
+    let is_zero = match query {
+        QueryEnum::Nearest(vector) => match vector {
+            NamedVectorStruct::Default(vector) => is_zero_vector(vector),
+            NamedVectorStruct::Dense(vector) => is_zero_vector(&vector.vector),
+            NamedVectorStruct::MultiDense(vector) => {
+                is_zero_vector(&vector.vector.flattened_vectors)
+            }
+            NamedVectorStruct::Sparse(_) => false,
+        },
+        QueryEnum::RecommendBestScore(_) | QueryEnum::Discover(_) | QueryEnum::Context(_) => false,
+    };
+
+    is_zero
+        && params
+            .vectors
+            .get_params(query.get_vector_name())
+            .is_some_and(|vector_params| {
+                vector_params.zero_vector_policy() != ZeroVectorPolicy::ScoreZero
+            })
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::Distance;
+
+    use super::*;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
+
+    fn prefetch(query: Vec<f32>) -> ShardPrefetch {
+        ShardPrefetch {
+            prefetches: Vec::new(),
+            query: Some(ScoringQuery::Vector(QueryEnum::from(query))),
+            limit: 10,
+            params: None,
+            filter: None,
+            score_threshold: None,
+            weight: None,
+        }
+    }
+
+    #[test]
+    fn test_query_has_results() {
+        let params = CollectionParams {
+            vectors: VectorParamsBuilder::new(2, Distance::Cosine)
+                .with_zero_vector_policy(ZeroVectorPolicy::Exclude)
+                .build()
+                .into(),
+            ..CollectionParams::empty()
+        };
+
+        let mut request = ShardQueryRequest {
+            prefetches: vec![prefetch(vec![0.0, 0.0]), prefetch(vec![0.5, 0.5])],
+            query: None,
+            filter: None,
+            score_threshold: None,
+            limit: 10,
+            offset: 0,
+            params: None,
+            with_vector: Default::default(),
+            with_payload: Default::default(),
+            seed: None,
+        };
+        assert!(query_has_results(&params, &mut request));
+        assert_eq!(request.prefetches.len(), 1);
+
+        request.prefetches = vec![prefetch(vec![0.0, 0.0])];
+        assert!(!query_has_results(&params, &mut request));
+
+        request.prefetches = Vec::new();
+        request.query = Some(ScoringQuery::Vector(QueryEnum::from(vec![0.0, 0.0])));
+        assert!(!query_has_results(&params, &mut request));
+
+        let params = CollectionParams {
+            vectors: VectorParamsBuilder::new(2, Distance::Cosine).build().into(),
+            ..CollectionParams::empty()
+        };
+        assert!(query_has_results(&params, &mut request));
+    }
+}
@@ -101,31 +101,36 @@ mod tests {
                 id: 11.into(),
                 vector: VectorStructInternal::from(vec11).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: 12.into(),
                 vector: VectorStructInternal::from(vec12).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: 13.into(),
                 vector: VectorStructInternal::from(vec13).into(),
                 payload: Some(json!({ "color": "red" }).into()),
+                vector_metadata: None,
             },
             PointStruct {
                 id: 14.into(),
                 vector: VectorStructInternal::from(vec![0., 0., 0., 0.]).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: VectorStructInternal::from(vec![2., 0., 2., 0.]).into(),
                 payload: None,
+                vector_metadata: None,
             },
         ];
 
         let (num_deleted, num_new, num_updated) =
-            sync_points(&segments.read(), 100, Some(10.into()), None, &points).unwrap();
+            sync_points(&segments.read(), 100, Some(10.into()), None, &points, &[]).unwrap();
 
         assert_eq!(num_deleted, 1); // delete point 15
         assert_eq!(num_new, 1); // insert point 500
@@ -144,11 +149,13 @@ mod tests {
                 id: 1.into(),
                 vector: VectorStructInternal::from(vec![2., 2., 2., 2.]).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: 500.into(),
                 vector: VectorStructInternal::from(vec![2., 0., 2., 0.]).into(),
                 payload: None,
+                vector_metadata: None,
             },
         ];
 
@@ -161,6 +168,7 @@ mod tests {
             &[1.into(), 2.into(), 500.into()],
             &WithPayload::from(true),
             &true.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -196,6 +204,7 @@ mod tests {
             &[1.into(), 2.into(), 500.into()],
             &WithPayload::from(true),
             &true.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -235,6 +244,7 @@ mod tests {
             &points,
             &WithPayload::from(true),
             &false.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -270,6 +280,7 @@ mod tests {
             &[3.into()],
             &WithPayload::from(true),
             &false.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -286,6 +297,7 @@ mod tests {
             &[2.into()],
             &WithPayload::from(true),
             &false.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -308,6 +320,7 @@ mod tests {
             &[2.into()],
             &WithPayload::from(true),
             &false.into(),
+            false,
             &is_stopped,
         )
         .unwrap()
@@ -18,6 +18,7 @@ use crate::collection_manager::holders::segment_holder::SegmentHolder;
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
+use crate::collection_manager::optimizers::split_optimizer::SplitOptimizer;
 use crate::config::CollectionParams;
 use crate::operations::types::VectorsConfig;
 use crate::operations::vector_params_builder::VectorParamsBuilder;
@@ -218,6 +219,7 @@ pub(crate) fn get_merge_optimizer(
             max_segment_size_kb: 100_000,
             memmap_threshold_kb: 1_000_000,
             indexing_threshold_kb: 1_000_000,
+            vector_deduplication: None,
         }),
         segment_path.to_owned(),
         collection_temp_dir.to_owned(),
@@ -232,6 +234,32 @@ pub(crate) fn get_merge_optimizer(
     )
 }
 
+pub(crate) fn get_split_optimizer(
+    segment_path: &Path,
+    collection_temp_dir: &Path,
+    dim: usize,
+    max_segment_size_kb: usize,
+) -> SplitOptimizer {
+    SplitOptimizer::new(
+        OptimizerThresholds {
+            max_segment_size_kb,
+            memmap_threshold_kb: 1_000_000,
+            indexing_threshold_kb: 1_000_000,
+            vector_deduplication: None,
+        },
+        segment_path.to_owned(),
+        collection_temp_dir.to_owned(),
+        CollectionParams {
+            vectors: VectorsConfig::Single(
+                VectorParamsBuilder::new(dim as u64, Distance::Dot).build(),
+            ),
+            ..CollectionParams::empty()
+        },
+        Default::default(),
+        Default::default(),
+    )
+}
+
 pub(crate) fn get_indexing_optimizer(
     segment_path: &Path,
     collection_temp_dir: &Path,
@@ -243,6 +271,7 @@ pub(crate) fn get_indexing_optimizer(
             max_segment_size_kb: 100_000,
             memmap_threshold_kb: 100,
             indexing_threshold_kb: 100,
+            vector_deduplication: None,
         },
         segment_path.to_owned(),
         collection_temp_dir.to_owned(),
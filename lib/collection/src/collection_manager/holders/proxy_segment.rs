@@ -5,6 +5,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::BitVec;
+use common::random::split_seed;
 use common::tar_ext;
 use common::types::{PointOffsetType, TelemetryDetail};
 use parking_lot::{RwLock, RwLockUpgradableReadGuard};
@@ -20,8 +21,8 @@ use segment::json_path::JsonPath;
 use segment::telemetry::SegmentTelemetry;
 use segment::types::{
     Condition, Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
+    VectorMetadata, WithPayload, WithVector,
 };
 
 use crate::collection_manager::holders::segment_holder::LockedSegment;
@@ -159,9 +160,11 @@ impl ProxySegment {
                 return Ok(false);
             }
 
-            let (all_vectors, payload) = (
+            let (all_vectors, payload, vector_metadata, revision) = (
                 wrapped_segment_guard.all_vectors(point_id)?,
                 wrapped_segment_guard.payload(point_id)?,
+                wrapped_segment_guard.vector_metadata(point_id)?,
+                wrapped_segment_guard.point_revision(point_id)?,
             );
 
             {
@@ -172,6 +175,12 @@ impl ProxySegment {
                 if !payload.is_empty() {
                     write_segment.set_full_payload(op_num, point_id, &payload)?;
                 }
+                if !vector_metadata.is_empty() {
+                    write_segment.set_vector_metadata(op_num, point_id, &vector_metadata)?;
+                }
+                if revision != 0 {
+                    write_segment.set_point_revision(op_num, point_id, revision)?;
+                }
             };
 
             point_offset
@@ -532,6 +541,32 @@ impl SegmentEntry for ProxySegment {
             .clear_payload(op_num, point_id)
     }
 
+    fn set_vector_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &VectorMetadata,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_vector_metadata(op_num, point_id, metadata)
+    }
+
+    fn set_point_revision(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        revision: SeqNumberType,
+    ) -> OperationResult<bool> {
+        self.move_if_exists(op_num, point_id)?;
+        self.write_segment
+            .get()
+            .write()
+            .set_point_revision(op_num, point_id, revision)
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         return if self.deleted_points.read().contains(&point_id) {
             self.write_segment
@@ -597,6 +632,61 @@ impl SegmentEntry for ProxySegment {
         };
     }
 
+    fn payload_projected(
+        &self,
+        point_id: PointIdType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment
+                .get()
+                .read()
+                .payload_projected(point_id, include)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.payload_projected(point_id, include);
+                }
+            }
+            self.wrapped_segment
+                .get()
+                .read()
+                .payload_projected(point_id, include)
+        };
+    }
+
+    fn vector_metadata(&self, point_id: PointIdType) -> OperationResult<VectorMetadata> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().vector_metadata(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.vector_metadata(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().vector_metadata(point_id)
+        };
+    }
+
+    fn point_revision(&self, point_id: PointIdType) -> OperationResult<SeqNumberType> {
+        return if self.deleted_points.read().contains(&point_id) {
+            self.write_segment.get().read().point_revision(point_id)
+        } else {
+            {
+                let write_segment = self.write_segment.get();
+                let segment_guard = write_segment.read();
+                if segment_guard.has_point(point_id) {
+                    return segment_guard.point_revision(point_id);
+                }
+            }
+            self.wrapped_segment.get().read().point_revision(point_id)
+        };
+    }
+
     /// Not implemented for proxy
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // iter_points is not available for Proxy implementation
@@ -674,20 +764,25 @@ impl SegmentEntry for ProxySegment {
         &'a self,
         limit: usize,
         filter: Option<&'a Filter>,
+        seed: Option<u64>,
         is_stopped: &AtomicBool,
     ) -> Vec<PointIdType> {
+        let [wrapped_seed, write_seed] = split_seed(seed);
         let deleted_points = self.deleted_points.read();
         let mut read_points = if deleted_points.is_empty() {
-            self.wrapped_segment
-                .get()
-                .read()
-                .read_random_filtered(limit, filter, is_stopped)
+            self.wrapped_segment.get().read().read_random_filtered(
+                limit,
+                filter,
+                wrapped_seed,
+                is_stopped,
+            )
         } else {
             let wrapped_filter =
                 Self::add_deleted_points_condition_to_filter(filter, &deleted_points);
             self.wrapped_segment.get().read().read_random_filtered(
                 limit,
                 Some(&wrapped_filter),
+                wrapped_seed,
                 is_stopped,
             )
         };
@@ -695,7 +790,7 @@ impl SegmentEntry for ProxySegment {
             .write_segment
             .get()
             .read()
-            .read_random_filtered(limit, filter, is_stopped);
+            .read_random_filtered(limit, filter, write_seed, is_stopped);
         read_points.append(&mut write_segment_points);
         read_points
     }
@@ -1671,6 +1766,7 @@ mod tests {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         let mut original_segment = build_segment(dir.path(), &config, true).unwrap();
         let write_segment = build_segment(dir.path(), &config, true).unwrap();
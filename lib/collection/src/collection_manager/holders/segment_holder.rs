@@ -18,7 +18,7 @@ use segment::data_types::named_vectors::NamedVectors;
 use segment::entry::entry_point::SegmentEntry;
 use segment::segment::{Segment, SegmentVersion};
 use segment::segment_constructor::build_segment;
-use segment::types::{Payload, PointIdType, SegmentConfig, SeqNumberType};
+use segment::types::{Payload, PointIdType, SegmentConfig, SeqNumberType, VectorMetadata};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
@@ -310,10 +310,18 @@ impl<'s> SegmentHolder {
     pub fn non_appendable_then_appendable_segments(
         &'s self,
     ) -> impl Iterator<Item = LockedSegment> + 's {
+        self.non_appendable_then_appendable_segments_with_ids()
+            .map(|(_, segment)| segment)
+    }
+
+    /// Get all locked segments with their IDs, non-appendable first, then appendable.
+    pub fn non_appendable_then_appendable_segments_with_ids(
+        &'s self,
+    ) -> impl Iterator<Item = (SegmentId, LockedSegment)> + 's {
         self.non_appendable_segments
-            .values()
-            .chain(self.appendable_segments.values())
-            .cloned()
+            .iter()
+            .chain(self.appendable_segments.iter())
+            .map(|(id, segment)| (*id, segment.clone()))
     }
 
     /// Get two separate lists for non-appendable and appendable locked segments
@@ -566,19 +574,25 @@ impl<'s> SegmentHolder {
     ///
     /// It's always safe to pass a closure that always returns false (i.e. `|_| false`).
     ///
+    /// Each point changed by the operation gets the revision returned by `next_revision` for its
+    /// current one. Points the operation was already applied to keep their revision.
+    ///
     /// Returns set of point ids which were successfully (already) applied to segments.
-    pub fn apply_points_with_conditional_move<F, G, H>(
+    pub fn apply_points_with_conditional_move<F, G, H, N>(
         &self,
         op_num: SeqNumberType,
         ids: &[PointIdType],
         mut point_operation: F,
         mut point_cow_operation: H,
         update_nonappendable: G,
+        mut next_revision: N,
     ) -> OperationResult<HashSet<PointIdType>>
     where
         F: FnMut(PointIdType, &mut RwLockWriteGuard<dyn SegmentEntry>) -> OperationResult<bool>,
-        for<'n, 'o, 'p> H: FnMut(PointIdType, &'n mut NamedVectors<'o>, &'p mut Payload),
+        for<'n, 'o, 'p, 'm> H:
+            FnMut(PointIdType, &'n mut NamedVectors<'o>, &'p mut Payload, &'m mut VectorMetadata),
         G: FnMut(&dyn SegmentEntry) -> bool,
+        N: FnMut(PointIdType, SeqNumberType) -> SeqNumberType,
     {
         let _update_guard = self.update_tracker.update();
 
@@ -598,20 +612,48 @@ impl<'s> SegmentHolder {
                     }
                 }
 
+                let revision = write_segment.point_revision(point_id)?;
+
                 let is_applied = if update_nonappendable || write_segment.is_appendable() {
-                    point_operation(point_id, write_segment)?
+                    let is_applied = point_operation(point_id, write_segment)?;
+                    if is_applied {
+                        write_segment.set_point_revision(
+                            op_num,
+                            point_id,
+                            next_revision(point_id, revision),
+                        )?;
+                    }
+                    is_applied
                 } else {
                     self.aloha_random_write(
                         &appendable_segments,
                         |_appendable_idx, appendable_write_segment| {
                             let mut all_vectors = write_segment.all_vectors(point_id)?;
                             let mut payload = write_segment.payload(point_id)?;
+                            let mut vector_metadata = write_segment.vector_metadata(point_id)?;
 
-                            point_cow_operation(point_id, &mut all_vectors, &mut payload);
+                            point_cow_operation(
+                                point_id,
+                                &mut all_vectors,
+                                &mut payload,
+                                &mut vector_metadata,
+                            );
 
                             appendable_write_segment.upsert_point(op_num, point_id, all_vectors)?;
                             appendable_write_segment
                                 .set_full_payload(op_num, point_id, &payload)?;
+                            if !vector_metadata.is_empty() {
+                                appendable_write_segment.set_vector_metadata(
+                                    op_num,
+                                    point_id,
+                                    &vector_metadata,
+                                )?;
+                            }
+                            appendable_write_segment.set_point_revision(
+                                op_num,
+                                point_id,
+                                next_revision(point_id, revision),
+                            )?;
 
                             write_segment.delete_point(op_num, point_id)?;
 
@@ -920,6 +962,7 @@ impl<'s> SegmentHolder {
                     .to_sparse_vector_data()
                     .map_err(|err| OperationError::service_error(format!("Failed to source sparse vector configuration from collection parameters: {err:?}")))?,
                 payload_storage_type: collection_params.payload_storage_type(),
+                payload_compression: collection_params.payload_compression,
             },
             // Fall back: base config on existing appendable segment
             None => {
@@ -1370,8 +1413,9 @@ mod tests {
                     assert!(segment.has_point(point_id));
                     Ok(true)
                 },
-                |point_id, _, _| processed_points2.push(point_id),
+                |point_id, _, _, _| processed_points2.push(point_id),
                 |_| update_nonappendable,
+                |_, revision| revision + 1,
             )
             .unwrap();
 
@@ -1485,8 +1529,9 @@ mod tests {
                     assert!(segment.has_point(point_id));
                     Ok(true)
                 },
-                |point_id, _, _| processed_points2.push(point_id),
+                |point_id, _, _, _| processed_points2.push(point_id),
                 |_| false,
+                |_, revision| revision + 1,
             )
             .unwrap();
         assert_eq!(3, processed_points.len() + processed_points2.len());
@@ -1530,6 +1575,7 @@ mod tests {
         segment2
             .set_full_payload(100, 123.into(), &payload)
             .unwrap();
+        segment2.set_point_revision(100, 123.into(), 7).unwrap();
         segment2.appendable_flag = false;
 
         let mut holder = SegmentHolder::default();
@@ -1560,11 +1606,12 @@ mod tests {
                 1010,
                 &[123.into()],
                 |_, _| unreachable!(),
-                |_point_id, vectors, payload| {
+                |_point_id, vectors, payload, _| {
                     vectors.insert("".to_string(), Vector::Dense(vec![9.0; 4]));
                     payload.0.insert(PAYLOAD_KEY.to_string(), 2.into());
                 },
                 |_| false,
+                |_, revision| revision + 1,
             )
             .unwrap();
 
@@ -1580,6 +1627,8 @@ mod tests {
             new_payload_value.get_value(&JsonPath::from_str(PAYLOAD_KEY).unwrap())[0],
             &Value::from(2)
         );
+        // Moved point keeps its revision, which is incremented by the update
+        assert_eq!(read_segment_1.point_revision(123.into()).unwrap(), 8);
     }
 
     #[tokio::test]
@@ -298,6 +298,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            vector_deduplication: None,
         };
         let collection_params = CollectionParams {
             vectors: VectorsConfig::Single(
@@ -318,11 +319,14 @@ mod tests {
 
         let hnsw_config = HnswConfig {
             m: 16,
+            m0: None,
             ef_construct: 100,
             full_scan_threshold: 10,
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            level_multiplier: None,
+            deterministic_build: None,
         };
 
         // Optimizers used in test
@@ -432,6 +436,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            vector_deduplication: None,
         };
         let hnsw_config_vector1 = HnswConfigDiff {
             m: Some(10),
@@ -473,11 +478,14 @@ mod tests {
 
         let hnsw_config_collection = HnswConfig {
             m: 16,
+            m0: None,
             ef_construct: 100,
             full_scan_threshold: 10,
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            level_multiplier: None,
+            deterministic_build: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config_collection.max_indexing_threads);
@@ -600,6 +608,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            vector_deduplication: None,
         };
         let quantization_config_vector1 =
             QuantizationConfig::Scalar(segment::types::ScalarQuantization {
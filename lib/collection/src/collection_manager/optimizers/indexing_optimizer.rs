@@ -352,6 +352,7 @@ mod tests {
                 max_segment_size_kb: 300,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 1000,
+                vector_deduplication: None,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -453,6 +454,7 @@ mod tests {
                 max_segment_size_kb: 300,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 1000,
+                vector_deduplication: None,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -746,6 +748,7 @@ mod tests {
                 max_segment_size_kb: 1000,
                 memmap_threshold_kb: 1000,
                 indexing_threshold_kb: 10, // Always optimize
+                vector_deduplication: None,
             },
             segments_dir.path().to_owned(),
             segments_temp_dir.path().to_owned(),
@@ -823,6 +826,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: 10,
             indexing_threshold_kb: usize::MAX,
+            vector_deduplication: None,
         };
         let mut collection_params = CollectionParams {
             vectors: VectorsConfig::Single(
@@ -845,11 +849,14 @@ mod tests {
 
         let hnsw_config = HnswConfig {
             m: 16,
+            m0: None,
             ef_construct: 100,
             full_scan_threshold: 10,
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            level_multiplier: None,
+            deterministic_build: None,
         };
 
         {
@@ -12,6 +12,7 @@ pub mod config_mismatch_optimizer;
 pub mod indexing_optimizer;
 pub mod merge_optimizer;
 pub mod segment_optimizer;
+pub mod split_optimizer;
 pub mod vacuum_optimizer;
 
 /// Number of last trackers to keep in tracker log
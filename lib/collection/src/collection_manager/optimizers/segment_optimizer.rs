@@ -22,6 +22,7 @@ use segment::types::{
     HnswConfig, Indexes, PayloadFieldSchema, PayloadKeyType, PayloadStorageType, PointIdType,
     QuantizationConfig, SegmentConfig, VectorStorageType,
 };
+use segment::vector_storage::dense::vector_deduplication::VectorDeduplication;
 
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
@@ -38,6 +39,8 @@ pub struct OptimizerThresholds {
     pub max_segment_size_kb: usize,
     pub memmap_threshold_kb: usize,
     pub indexing_threshold_kb: usize,
+    /// Store duplicate vectors only once in optimized segments
+    pub vector_deduplication: Option<VectorDeduplication>,
 }
 
 /// SegmentOptimizer - trait implementing common functionality of the optimizers
@@ -79,6 +82,13 @@ pub trait SegmentOptimizer {
 
     fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator>;
 
+    /// Number of segments to split the optimized segments into
+    ///
+    /// By default all optimized segments are merged into a single one.
+    fn split_parts(&self, _optimizing_segments: &[LockedSegment]) -> usize {
+        1
+    }
+
     /// Build temp segment
     fn temp_segment(&self, save_version: bool) -> CollectionResult<LockedSegment> {
         let collection_params = self.collection_params();
@@ -90,6 +100,7 @@ pub trait SegmentOptimizer {
             } else {
                 PayloadStorageType::InMemory
             },
+            payload_compression: collection_params.payload_compression,
         };
         Ok(LockedSegment::new(build_segment(
             self.segments_path(),
@@ -301,13 +312,16 @@ pub trait SegmentOptimizer {
             } else {
                 PayloadStorageType::InMemory
             },
+            payload_compression: collection_params.payload_compression,
         };
 
-        Ok(SegmentBuilder::new(
-            self.segments_path(),
-            self.temp_path(),
-            &optimized_config,
-        )?)
+        let mut segment_builder =
+            SegmentBuilder::new(self.segments_path(), self.temp_path(), &optimized_config)?;
+        if let Some(vector_deduplication) = thresholds.vector_deduplication {
+            segment_builder.set_vector_deduplication(vector_deduplication);
+        }
+
+        Ok(segment_builder)
     }
 
     /// Restores original segments from proxies
@@ -395,21 +409,26 @@ pub trait SegmentOptimizer {
     /// * `proxy_deleted_points` - Holds a set of points, deleted while optimization was running
     /// * `proxy_deleted_indexes` - Holds a set of Indexes, deleted while optimization was running
     /// * `proxy_created_indexes` - Holds a set of Indexes, created while optimization was running
+    /// * `num_parts` - number of segments to split the optimized segments into
+    /// * `permit` - CPU permit, shared between all parts of a split segment
     /// * `stopped` - flag to check if optimization was cancelled by external thread
     ///
     /// # Result
     ///
-    /// Constructs optimized segment
-    fn build_new_segment(
+    /// Constructs optimized segments, one for each part
+    fn build_new_segments(
         &self,
         optimizing_segments: &[LockedSegment],
         proxy_deleted_points: Arc<RwLock<HashSet<PointIdType>>>,
         proxy_deleted_indexes: Arc<RwLock<HashSet<PayloadKeyType>>>,
         proxy_created_indexes: Arc<RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>>,
+        num_parts: usize,
         permit: CpuPermit,
         stopped: &AtomicBool,
-    ) -> CollectionResult<Segment> {
-        let mut segment_builder = self.optimized_segment_builder(optimizing_segments)?;
+    ) -> CollectionResult<Vec<Segment>> {
+        let mut segment_builders = (0..num_parts)
+            .map(|_| self.optimized_segment_builder(optimizing_segments))
+            .collect::<CollectionResult<Vec<_>>>()?;
 
         self.check_cancellation(stopped)?;
 
@@ -438,17 +457,50 @@ pub trait SegmentOptimizer {
         }
 
         if !defragmentation_keys.is_empty() {
-            segment_builder.set_defragment_keys(defragmentation_keys.into_iter().collect());
+            let defragmentation_keys = defragmentation_keys.into_iter().collect_vec();
+            for segment_builder in &mut segment_builders {
+                segment_builder.set_defragment_keys(defragmentation_keys.clone());
+            }
         }
 
         {
             let segment_guards = segments.iter().map(|segment| segment.read()).collect_vec();
-            segment_builder.update(
+            SegmentBuilder::update_partitioned(
+                &mut segment_builders,
                 &segment_guards.iter().map(Deref::deref).collect_vec(),
                 stopped,
             )?;
         }
 
+        // Share CPU budget between all parts, release it as soon as all of them are built
+        let permit = Arc::new(permit);
+
+        segment_builders
+            .into_iter()
+            .map(|segment_builder| {
+                self.build_new_segment(
+                    segment_builder,
+                    &proxy_deleted_points,
+                    &proxy_deleted_indexes,
+                    &proxy_created_indexes,
+                    permit.clone(),
+                    stopped,
+                )
+            })
+            .collect()
+    }
+
+    /// Build a single optimized segment out of a filled segment builder,
+    /// and apply changes which happened while optimization was running
+    fn build_new_segment(
+        &self,
+        mut segment_builder: SegmentBuilder,
+        proxy_deleted_points: &RwLock<HashSet<PointIdType>>,
+        proxy_deleted_indexes: &RwLock<HashSet<PayloadKeyType>>,
+        proxy_created_indexes: &RwLock<HashMap<PayloadKeyType, PayloadFieldSchema>>,
+        permit: Arc<CpuPermit>,
+        stopped: &AtomicBool,
+    ) -> CollectionResult<Segment> {
         for field in proxy_deleted_indexes.read().iter() {
             segment_builder.remove_indexed_field(field);
         }
@@ -456,7 +508,8 @@ pub trait SegmentOptimizer {
             segment_builder.add_indexed_field(field.to_owned(), schema_type.to_owned());
         }
 
-        let mut optimized_segment: Segment = segment_builder.build(permit, stopped)?;
+        let mut optimized_segment: Segment =
+            segment_builder.build_with_shared_permit(permit, stopped)?;
 
         // Delete points in 2 steps
         // First step - delete all points with read lock
@@ -506,6 +559,7 @@ pub trait SegmentOptimizer {
     /// # Result
     ///
     /// New optimized segment should be added into `segments`.
+    /// If the optimizer splits segments, several new segments are added instead.
     /// If there were any record changes during the optimization - an additional plain segment will be created.
     ///
     /// Returns id of the created optimized segment. If no optimization was done - returns None
@@ -606,15 +660,18 @@ pub trait SegmentOptimizer {
 
         // ---- SLOW PART -----
 
-        let mut optimized_segment = match self.build_new_segment(
+        let num_parts = self.split_parts(&optimizing_segments).max(1);
+
+        let mut optimized_segments = match self.build_new_segments(
             &optimizing_segments,
             proxy_deleted_points.clone(),
             proxy_deleted_indexes.clone(),
             proxy_created_indexes.clone(),
+            num_parts,
             permit,
             stopped,
         ) {
-            Ok(segment) => segment,
+            Ok(segments) => segments,
             Err(error) => {
                 if matches!(error, CollectionError::Cancelled { .. }) {
                     self.handle_cancellation(&segments, &proxy_ids, &tmp_segment);
@@ -629,8 +686,10 @@ pub trait SegmentOptimizer {
         let already_remove_points = {
             let mut all_removed_points: HashSet<_> =
                 proxy_deleted_points.read().iter().cloned().collect();
-            for existing_point in optimized_segment.iter_points() {
-                all_removed_points.remove(&existing_point);
+            for optimized_segment in &optimized_segments {
+                for existing_point in optimized_segment.iter_points() {
+                    all_removed_points.remove(&existing_point);
+                }
             }
             all_removed_points
         };
@@ -644,37 +703,52 @@ pub trait SegmentOptimizer {
             // This block locks all operations with collection. It should be fast
             let mut write_segments_guard = segments.write();
             let deleted_points = proxy_deleted_points.read();
-            let points_diff = deleted_points.difference(&already_remove_points);
-            for &point_id in points_diff {
-                optimized_segment
-                    .delete_point(optimized_segment.version(), point_id)
-                    .unwrap();
-            }
+            for optimized_segment in optimized_segments.iter_mut() {
+                let points_diff = deleted_points.difference(&already_remove_points);
+                for &point_id in points_diff {
+                    optimized_segment
+                        .delete_point(optimized_segment.version(), point_id)
+                        .unwrap();
+                }
 
-            for deleted_field_name in proxy_deleted_indexes.read().iter() {
-                optimized_segment
-                    .delete_field_index(optimized_segment.version(), deleted_field_name)?;
-            }
+                for deleted_field_name in proxy_deleted_indexes.read().iter() {
+                    optimized_segment
+                        .delete_field_index(optimized_segment.version(), deleted_field_name)?;
+                }
+
+                for (created_field_name, schema_type) in proxy_created_indexes.read().iter() {
+                    optimized_segment.create_field_index(
+                        optimized_segment.version(),
+                        created_field_name,
+                        Some(schema_type),
+                    )?;
+                }
 
-            for (created_field_name, schema_type) in proxy_created_indexes.read().iter() {
-                optimized_segment.create_field_index(
-                    optimized_segment.version(),
-                    created_field_name,
-                    Some(schema_type),
-                )?;
+                optimized_segment.prefault_mmap_pages();
             }
 
-            optimized_segment.prefault_mmap_pages();
+            let point_count = optimized_segments
+                .iter()
+                .map(|segment| segment.available_point_count())
+                .sum();
 
-            let point_count = optimized_segment.available_point_count();
+            let mut optimized_segments = optimized_segments.into_iter();
+            let first_segment = optimized_segments
+                .next()
+                .expect("at least one optimized segment is built");
 
-            let (_, proxies) = write_segments_guard.swap_new(optimized_segment, &proxy_ids);
+            let (_, proxies) = write_segments_guard.swap_new(first_segment, &proxy_ids);
             debug_assert_eq!(
                 proxies.len(),
                 proxy_ids.len(),
                 "swapped different number of proxies on unwrap, missing or incorrect segment IDs?"
             );
 
+            // Other parts of a split segment are added as new segments
+            for segment in optimized_segments {
+                write_segments_guard.add_new(segment);
+            }
+
             let has_appendable_segments = write_segments_guard.has_appendable_segment();
 
             // Release reference counter of the optimized segments
@@ -0,0 +1,239 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use parking_lot::Mutex;
+use segment::common::operation_time_statistics::OperationDurationsAggregator;
+use segment::types::{HnswConfig, QuantizationConfig, SegmentType};
+
+use crate::collection_manager::holders::segment_holder::{
+    LockedSegment, LockedSegmentHolder, SegmentId,
+};
+use crate::collection_manager::optimizers::segment_optimizer::{
+    OptimizerThresholds, SegmentOptimizer,
+};
+use crate::config::CollectionParams;
+
+const BYTES_IN_KB: usize = 1024;
+
+/// Segment is only split if it is this many times larger than the max segment size.
+/// Prevents splitting segments which are just slightly over the limit into tiny parts.
+const SPLIT_SIZE_FACTOR: usize = 2;
+
+/// Optimizer that splits oversized segments into several smaller ones.
+///
+/// Counterpart of the [`MergeOptimizer`](super::merge_optimizer::MergeOptimizer): merging
+/// brings the number of segments down to the configured target, splitting keeps segments
+/// within the max segment size. Segments are only split because of their size, never to reach
+/// the target number of segments.
+///
+/// Each part is at most the max segment size, so the merge optimizer never joins them back.
+pub struct SplitOptimizer {
+    thresholds_config: OptimizerThresholds,
+    segments_path: PathBuf,
+    collection_temp_dir: PathBuf,
+    collection_params: CollectionParams,
+    hnsw_config: HnswConfig,
+    quantization_config: Option<QuantizationConfig>,
+    telemetry_durations_aggregator: Arc<Mutex<OperationDurationsAggregator>>,
+}
+
+impl SplitOptimizer {
+    pub fn new(
+        thresholds_config: OptimizerThresholds,
+        segments_path: PathBuf,
+        collection_temp_dir: PathBuf,
+        collection_params: CollectionParams,
+        hnsw_config: HnswConfig,
+        quantization_config: Option<QuantizationConfig>,
+    ) -> Self {
+        SplitOptimizer {
+            thresholds_config,
+            segments_path,
+            collection_temp_dir,
+            collection_params,
+            hnsw_config,
+            quantization_config,
+            telemetry_durations_aggregator: OperationDurationsAggregator::new(),
+        }
+    }
+
+    fn max_segment_size_bytes(&self) -> usize {
+        self.thresholds_config
+            .max_segment_size_kb
+            .saturating_mul(BYTES_IN_KB)
+    }
+
+    fn segment_size_bytes(segment: &LockedSegment) -> usize {
+        segment
+            .get()
+            .read()
+            .max_available_vectors_size_in_bytes()
+            .unwrap_or_default()
+    }
+}
+
+impl SegmentOptimizer for SplitOptimizer {
+    fn name(&self) -> &str {
+        "split"
+    }
+
+    fn segments_path(&self) -> &Path {
+        self.segments_path.as_path()
+    }
+
+    fn temp_path(&self) -> &Path {
+        self.collection_temp_dir.as_path()
+    }
+
+    fn collection_params(&self) -> CollectionParams {
+        self.collection_params.clone()
+    }
+
+    fn hnsw_config(&self) -> &HnswConfig {
+        &self.hnsw_config
+    }
+
+    fn quantization_config(&self) -> Option<QuantizationConfig> {
+        self.quantization_config.clone()
+    }
+
+    fn threshold_config(&self) -> &OptimizerThresholds {
+        &self.thresholds_config
+    }
+
+    fn check_condition(
+        &self,
+        segments: LockedSegmentHolder,
+        excluded_ids: &HashSet<SegmentId>,
+    ) -> Vec<SegmentId> {
+        let max_segment_size = self.max_segment_size_bytes();
+        if max_segment_size == 0 {
+            return vec![];
+        }
+        let split_threshold = max_segment_size.saturating_mul(SPLIT_SIZE_FACTOR);
+
+        let read_segments = segments.read();
+
+        // Split the largest oversized segment first
+        read_segments
+            .iter()
+            .filter(|(sid, segment)| {
+                matches!(segment, LockedSegment::Original(_)) && !excluded_ids.contains(sid)
+            })
+            .filter(|(_, segment)| segment.get().read().segment_type() != SegmentType::Special)
+            .map(|(sid, segment)| (*sid, Self::segment_size_bytes(segment)))
+            .filter(|(_, size)| *size > split_threshold)
+            .max_by_key(|(_, size)| *size)
+            .map(|(sid, _)| vec![sid])
+            .unwrap_or_default()
+    }
+
+    fn split_parts(&self, optimizing_segments: &[LockedSegment]) -> usize {
+        let max_segment_size = self.max_segment_size_bytes();
+        if max_segment_size == 0 {
+            return 1;
+        }
+        let total_size: usize = optimizing_segments
+            .iter()
+            .map(Self::segment_size_bytes)
+            .sum();
+        total_size.div_ceil(max_segment_size).max(1)
+    }
+
+    fn get_telemetry_counter(&self) -> &Mutex<OperationDurationsAggregator> {
+        &self.telemetry_durations_aggregator
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::AtomicBool;
+    use std::sync::Arc;
+
+    use common::cpu::CpuPermit;
+    use itertools::Itertools;
+    use parking_lot::RwLock;
+    use segment::entry::entry_point::SegmentEntry;
+    use segment::index::hnsw_index::num_rayon_threads;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::collection_manager::fixtures::{get_split_optimizer, random_segment};
+    use crate::collection_manager::holders::segment_holder::SegmentHolder;
+
+    #[test]
+    fn test_split_optimizer() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let dim = 256;
+
+        // 200 vectors of 1 KB each
+        let large_segment_id = holder.add_new(random_segment(dir.path(), 100, 200, dim));
+        let small_segment_id = holder.add_new(random_segment(dir.path(), 100, 40, dim));
+
+        let large_segment_points = match holder.get(large_segment_id).unwrap() {
+            LockedSegment::Original(segment) => segment.read().iter_points().collect_vec(),
+            LockedSegment::Proxy(_) => panic!("Not expected"),
+        };
+
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        // Segment is not large enough to be split
+        let mut split_optimizer = get_split_optimizer(dir.path(), temp_dir.path(), dim, 150);
+        assert!(split_optimizer
+            .check_condition(locked_holder.clone(), &Default::default())
+            .is_empty());
+
+        split_optimizer.thresholds_config.max_segment_size_kb = 60;
+        let suggested_for_split =
+            split_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_for_split, vec![large_segment_id]);
+
+        let permit_cpu_count = num_rayon_threads(0);
+        let permit = CpuPermit::dummy(permit_cpu_count as u32);
+
+        let point_count = split_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_for_split,
+                permit,
+                &AtomicBool::new(false),
+            )
+            .unwrap();
+        assert_eq!(point_count, 200);
+
+        let holder_guard = locked_holder.read();
+        assert!(holder_guard.get(large_segment_id).is_none());
+        assert!(holder_guard.get(small_segment_id).is_some());
+
+        let parts = holder_guard
+            .iter()
+            .filter(|(sid, _)| **sid != small_segment_id)
+            .map(|(_, segment)| segment.get())
+            .collect_vec();
+
+        // 200 KB split into 60 KB parts
+        assert_eq!(parts.len(), 4);
+
+        let mut split_points = HashSet::new();
+        for part in &parts {
+            let part = part.read();
+            assert!(part.available_point_count() > 0);
+            assert!(part.available_point_count() < 200);
+            split_points.extend(part.iter_points());
+        }
+        assert_eq!(split_points.len(), large_segment_points.len());
+        for point_id in large_segment_points {
+            assert!(split_points.contains(&point_id));
+        }
+
+        // Parts are small enough to never be split again
+        drop(holder_guard);
+        assert!(split_optimizer
+            .check_condition(locked_holder, &Default::default())
+            .is_empty());
+    }
+}
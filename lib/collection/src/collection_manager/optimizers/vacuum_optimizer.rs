@@ -6,7 +6,7 @@ use ordered_float::OrderedFloat;
 use parking_lot::Mutex;
 use segment::common::operation_time_statistics::OperationDurationsAggregator;
 use segment::entry::entry_point::SegmentEntry;
-use segment::index::VectorIndex;
+use segment::index::{PayloadIndex, VectorIndex};
 use segment::types::{HnswConfig, QuantizationConfig, SegmentType};
 use segment::vector_storage::VectorStorage;
 
@@ -23,9 +23,14 @@ use crate::config::CollectionParams;
 /// Since the creation of a segment, a lot of points or vectors may have been soft-deleted. This
 /// results in the index slowly breaking apart, and unnecessary storage usage.
 ///
+/// Payload indexes of non-appendable segments also keep removed points, marked as deleted, which
+/// slows down filtering. Segments with many of them are rebuilt as well, which compacts their
+/// payload indexes.
+///
 /// This optimizer will look for the worst segment to rebuilt the index and minimize storage usage.
 pub struct VacuumOptimizer {
     deleted_threshold: f64,
+    payload_index_deleted_threshold: f64,
     min_vectors_number: usize,
     thresholds_config: OptimizerThresholds,
     segments_path: PathBuf,
@@ -40,6 +45,7 @@ impl VacuumOptimizer {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         deleted_threshold: f64,
+        payload_index_deleted_threshold: f64,
         min_vectors_number: usize,
         thresholds_config: OptimizerThresholds,
         segments_path: PathBuf,
@@ -50,6 +56,7 @@ impl VacuumOptimizer {
     ) -> Self {
         VacuumOptimizer {
             deleted_threshold,
+            payload_index_deleted_threshold,
             min_vectors_number,
             thresholds_config,
             segments_path,
@@ -75,10 +82,15 @@ impl VacuumOptimizer {
                 // Calculate littered ratio for segment and named vectors
                 let littered_ratio_segment = self.littered_ratio_segment(segment);
                 let littered_ratio_vectors = self.littered_vectors_index_ratio(segment);
-                [littered_ratio_segment, littered_ratio_vectors]
-                    .into_iter()
-                    .flatten()
-                    .map(|ratio| (*idx, ratio))
+                let littered_ratio_payload_index = self.littered_payload_index_ratio(segment);
+                [
+                    littered_ratio_segment,
+                    littered_ratio_vectors,
+                    littered_ratio_payload_index,
+                ]
+                .into_iter()
+                .flatten()
+                .map(|ratio| (*idx, ratio))
             })
             .max_by_key(|(_, ratio)| OrderedFloat(*ratio))
             .map(|(idx, _)| (idx, segments_read_guard.get(idx).unwrap().clone()))
@@ -160,6 +172,40 @@ impl VacuumOptimizer {
             })
             .max_by_key(|ratio| OrderedFloat(*ratio))
     }
+
+    /// Calculate littered ratio for segment on payload index level
+    ///
+    /// Field indexes of non-appendable segments keep points removed from them in their posting
+    /// lists, until the segment is rebuilt. The ratio is based on the number of removed points
+    /// versus the number of points in the index of a field, including removed ones.
+    ///
+    /// Returns `None` if littered ratio did not reach vacuum thresholds for any field.
+    fn littered_payload_index_ratio(&self, segment: &LockedSegment) -> Option<f64> {
+        // We can only work with original segments
+        let real_segment = match segment {
+            LockedSegment::Original(segment) => segment.read(),
+            LockedSegment::Proxy(_) => return None,
+        };
+
+        // Never optimize special segments
+        if real_segment.segment_type() == SegmentType::Special {
+            return None;
+        }
+
+        let payload_index = real_segment.payload_index.borrow();
+        payload_index
+            .removed_points()
+            .iter()
+            .filter_map(|(field, &removed_points)| {
+                let stored_points = payload_index.indexed_points(field) + removed_points;
+                let removed_ratio = removed_points as f64 / stored_points as f64;
+
+                let reached_minimum = removed_points >= self.min_vectors_number;
+                let reached_ratio = removed_ratio > self.payload_index_deleted_threshold;
+                (reached_minimum && reached_ratio).then_some(removed_ratio)
+            })
+            .max_by_key(|ratio| OrderedFloat(*ratio))
+    }
 }
 
 impl SegmentOptimizer for VacuumOptimizer {
@@ -218,6 +264,7 @@ mod tests {
     use parking_lot::RwLock;
     use segment::entry::entry_point::SegmentEntry;
     use segment::index::hnsw_index::num_rayon_threads;
+    use segment::json_path::JsonPath;
     use segment::types::{Distance, PayloadContainer, PayloadSchemaType};
     use serde_json::{json, Value};
     use tempfile::Builder;
@@ -290,12 +337,14 @@ mod tests {
         let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
 
         let vacuum_optimizer = VacuumOptimizer::new(
+            0.2,
             0.2,
             50,
             OptimizerThresholds {
                 max_segment_size_kb: 1000000,
                 memmap_threshold_kb: 1000000,
                 indexing_threshold_kb: 1000000,
+                vector_deduplication: None,
             },
             dir.path().to_owned(),
             temp_dir.path().to_owned(),
@@ -387,6 +436,7 @@ mod tests {
             max_segment_size_kb: usize::MAX,
             memmap_threshold_kb: usize::MAX,
             indexing_threshold_kb: 10,
+            vector_deduplication: None,
         };
         let collection_params = CollectionParams {
             vectors: VectorsConfig::Multi(BTreeMap::from([
@@ -428,11 +478,14 @@ mod tests {
 
         let hnsw_config = HnswConfig {
             m: 16,
+            m0: None,
             ef_construct: 100,
             full_scan_threshold: 10, // Force to build HNSW links for payload
             max_indexing_threads: 0,
             on_disk: None,
             payload_m: None,
+            level_multiplier: None,
+            deterministic_build: None,
         };
 
         let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -449,6 +502,7 @@ mod tests {
             Default::default(),
         );
         let vacuum_optimizer = VacuumOptimizer::new(
+            0.2,
             0.2,
             5,
             thresholds_config,
@@ -615,4 +669,143 @@ mod tests {
                 });
             });
     }
+
+    /// This tests the vacuum optimizer when many points are removed from a payload index.
+    ///
+    /// Points are only removed from the index, so neither points nor vectors are littered. The
+    /// optimized segment must not keep removed points in its payload index.
+    #[test]
+    fn test_vacuum_payload_index() {
+        let (point_count, vector1_dim, vector2_dim) = (1000, 10, 20);
+        let thresholds_config = OptimizerThresholds {
+            max_segment_size_kb: usize::MAX,
+            memmap_threshold_kb: usize::MAX,
+            indexing_threshold_kb: 10,
+            vector_deduplication: None,
+        };
+        let collection_params = CollectionParams {
+            vectors: VectorsConfig::Multi(BTreeMap::from([
+                (
+                    "vector1".into(),
+                    VectorParamsBuilder::new(vector1_dim, Distance::Dot).build(),
+                ),
+                (
+                    "vector2".into(),
+                    VectorParamsBuilder::new(vector2_dim, Distance::Dot).build(),
+                ),
+            ])),
+            ..CollectionParams::empty()
+        };
+        let keyword_key: JsonPath = "keyword".parse().unwrap();
+
+        let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+
+        let mut segment = random_multi_vec_segment(
+            dir.path(),
+            100,
+            point_count,
+            vector1_dim as usize,
+            vector2_dim as usize,
+        );
+        segment
+            .create_field_index(101, &keyword_key, Some(&PayloadSchemaType::Keyword.into()))
+            .unwrap();
+
+        let segment_id = holder.add_new(segment);
+        let locked_holder: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+        let permit_cpu_count = num_rayon_threads(0);
+
+        // Build a non-appendable segment, its payload index keeps removed points
+        let index_optimizer = IndexingOptimizer::new(
+            2,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params.clone(),
+            Default::default(),
+            Default::default(),
+        );
+        let vacuum_optimizer = VacuumOptimizer::new(
+            0.2,
+            0.2,
+            5,
+            thresholds_config,
+            dir.path().to_owned(),
+            temp_dir.path().to_owned(),
+            collection_params,
+            Default::default(),
+            Default::default(),
+        );
+        index_optimizer
+            .optimize(
+                locked_holder.clone(),
+                vec![segment_id],
+                CpuPermit::dummy(permit_cpu_count as u32),
+                &false.into(),
+            )
+            .unwrap();
+
+        let optimized_segment = || {
+            locked_holder
+                .read()
+                .iter()
+                .map(|(_, segment)| segment.clone())
+                .find(|segment| segment.get().read().total_point_count() > 0)
+                .unwrap()
+        };
+
+        // Remove 30% of points from the keyword index
+        {
+            let segment = optimized_segment().get();
+            let mut segment = segment.write();
+            let points_to_clear = segment
+                .iter_points()
+                .enumerate()
+                .filter_map(|(i, point_id)| (i % 10 < 3).then_some(point_id))
+                .collect_vec();
+            for &point_id in &points_to_clear {
+                segment.delete_payload(201, point_id, &keyword_key).unwrap();
+            }
+        }
+
+        // Nothing else is littered, only the payload index
+        let segment = optimized_segment();
+        assert_eq!(vacuum_optimizer.littered_ratio_segment(&segment), None);
+        assert_eq!(
+            vacuum_optimizer.littered_vectors_index_ratio(&segment),
+            None
+        );
+        assert_eq!(
+            vacuum_optimizer.littered_payload_index_ratio(&segment),
+            Some(0.3),
+        );
+
+        let suggested_to_optimize =
+            vacuum_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert_eq!(suggested_to_optimize.len(), 1);
+
+        vacuum_optimizer
+            .optimize(
+                locked_holder.clone(),
+                suggested_to_optimize,
+                CpuPermit::dummy(permit_cpu_count as u32),
+                &false.into(),
+            )
+            .unwrap();
+
+        // The rebuilt payload index only has the remaining points
+        let segment = optimized_segment().get();
+        let segment = segment.read();
+        let payload_index = segment.payload_index.borrow();
+        assert!(payload_index.removed_points().is_empty());
+        assert_eq!(payload_index.indexed_points(&keyword_key), 700);
+        assert_eq!(segment.available_point_count(), point_count as usize);
+
+        let suggested_to_optimize =
+            vacuum_optimizer.check_condition(locked_holder.clone(), &Default::default());
+        assert!(suggested_to_optimize.is_empty());
+    }
 }
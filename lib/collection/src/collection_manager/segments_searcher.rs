@@ -1,31 +1,37 @@
 use std::collections::{BTreeSet, HashMap};
+use std::future::Future;
+use std::num::NonZeroUsize;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use common::types::ScoreType;
 use futures::future::try_join_all;
+use futures::{stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use ordered_float::Float;
 use segment::common::operation_error::OperationError;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::query_context::QueryContext;
 use segment::data_types::vectors::{QueryVector, VectorStructInternal};
+use segment::entry::entry_point::SegmentEntry;
 use segment::types::{
-    Filter, Indexes, PointIdType, ScoredPoint, SearchParams, SegmentConfig, SeqNumberType,
-    WithPayload, WithPayloadInterface, WithVector,
+    Filter, Indexes, PayloadSelector, PointIdType, PointProvenance, ScoredPoint, SearchParams,
+    SegmentConfig, SeqNumberType, WithPayload, WithPayloadInterface, WithVector,
 };
 use tinyvec::TinyVec;
 use tokio::runtime::Handle;
-use tokio::task::JoinHandle;
+use tokio::task::JoinError;
 
 use super::holders::segment_holder::LockedSegmentHolder;
-use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::collection_manager::holders::segment_holder::{LockedSegment, SegmentId};
 use crate::collection_manager::probabilistic_segment_search_sampling::find_search_sampling_over_point_distribution;
 use crate::collection_manager::search_result_aggregator::BatchResultAggregator;
 use crate::common::stopping_guard::StoppingGuard;
 use crate::config::CollectionConfig;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::{CollectionResult, CoreSearchRequestBatch, Modifier, Record};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequestBatch, Modifier, Record,
+};
 use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
 
 type BatchOffset = usize;
@@ -45,11 +51,25 @@ type SegmentSearchExecutedResult = CollectionResult<(SegmentBatchSearchResult, V
 pub struct SegmentsSearcher {}
 
 impl SegmentsSearcher {
-    async fn execute_searches(
-        searches: Vec<JoinHandle<SegmentSearchExecutedResult>>,
-    ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>)> {
-        let searches = try_join_all(searches);
-        let search_results_per_segment_res = searches.await?;
+    /// Execute searches in segments, at most `concurrency` at once if specified.
+    ///
+    /// Searches start when polled, the resulting Vec is in the same order as the searches.
+    async fn execute_searches<F>(
+        searches: Vec<F>,
+        concurrency: Option<NonZeroUsize>,
+    ) -> CollectionResult<(BatchSearchResult, Vec<Vec<bool>>)>
+    where
+        F: Future<Output = Result<SegmentSearchExecutedResult, JoinError>>,
+    {
+        let search_results_per_segment_res: Vec<_> = match concurrency {
+            Some(concurrency) => {
+                stream::iter(searches)
+                    .buffered(concurrency.get())
+                    .try_collect()
+                    .await?
+            }
+            None => try_join_all(searches).await?,
+        };
 
         let mut search_results_per_segment = vec![];
         let mut further_searches_per_segment = vec![];
@@ -170,7 +190,17 @@ impl SegmentsSearcher {
         // check vector names existing
         for req in &batch_request.searches {
             let vector_name = req.query.get_vector_name();
-            collection_config.params.get_distance(vector_name)?;
+            let params = req.params.as_ref();
+            collection_config
+                .params
+                .get_search_distance(vector_name, params)?;
+            if params.is_some_and(|params| params.distance.is_some())
+                && !matches!(req.query, QueryEnum::Nearest(_))
+            {
+                return Err(CollectionError::bad_input(
+                    "Distance can only be overridden for nearest search",
+                ));
+            }
             if let Some(sparse_vector_params) = collection_config
                 .params
                 .get_sparse_vector_params_opt(vector_name)
@@ -221,12 +251,16 @@ impl SegmentsSearcher {
         Ok(task.await?)
     }
 
+    /// Search in all segments, with at most `segment_concurrency` segments searched at once if
+    /// specified. This bounds the number of threads used by a single search, which leaves room
+    /// for other concurrent searches.
     pub async fn search(
         segments: LockedSegmentHolder,
         batch_request: Arc<CoreSearchRequestBatch>,
         runtime_handle: &Handle,
         sampling_enabled: bool,
         query_context: QueryContext,
+        segment_concurrency: Option<NonZeroUsize>,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let query_context_arc = Arc::new(query_context);
 
@@ -235,7 +269,7 @@ impl SegmentsSearcher {
             // Unfortunately, we have to do `segments.read()` twice, once in blocking task
             // and once here, due to `Send` bounds :/
             let segments_lock = segments.read();
-            let segments = segments_lock.non_appendable_then_appendable_segments();
+            let segments = segments_lock.non_appendable_then_appendable_segments_with_ids();
 
             // Probabilistic sampling for the `limit` parameter avoids over-fetching points from segments.
             // e.g. 10 segments with limit 1000 would fetch 10000 points in total and discard 9000 points.
@@ -249,28 +283,32 @@ impl SegmentsSearcher {
                 && query_context_arc.available_point_count() > 0;
 
             segments
-                .map(|segment| {
+                .map(|(segment_id, segment)| {
                     let query_context_arc_segment = query_context_arc.clone();
-                    let search = runtime_handle.spawn_blocking({
-                        let (segment, batch_request) = (segment.clone(), batch_request.clone());
-                        move || {
-                            search_in_segment(
-                                segment,
-                                batch_request,
-                                use_sampling,
-                                query_context_arc_segment,
-                            )
-                        }
-                    });
-                    (segment.clone(), search)
+                    let runtime_handle = runtime_handle.clone();
+                    let (segment_clone, batch_request) = (segment.clone(), batch_request.clone());
+                    let search = async move {
+                        runtime_handle
+                            .spawn_blocking(move || {
+                                search_in_segment(
+                                    segment_id,
+                                    segment_clone,
+                                    batch_request,
+                                    use_sampling,
+                                    query_context_arc_segment,
+                                )
+                            })
+                            .await
+                    };
+                    ((segment_id, segment), search)
                 })
                 .unzip()
         };
 
-        // perform search on all segments concurrently
+        // perform search on segments concurrently
         // the resulting Vec is in the same order as the segment searches were provided.
         let (all_search_results_per_segment, further_results) =
-            Self::execute_searches(searches).await?;
+            Self::execute_searches(searches, segment_concurrency).await?;
         debug_assert!(all_search_results_per_segment.len() == locked_segments.len());
 
         let (mut result_aggregator, searches_to_rerun) = Self::process_search_result_step1(
@@ -292,33 +330,39 @@ impl SegmentsSearcher {
 
             let secondary_searches: Vec<_> = {
                 let mut res = vec![];
-                for (segment_id, batch_ids) in searches_to_rerun.iter() {
+                for (segment_offset, batch_ids) in searches_to_rerun.iter() {
                     let query_context_arc_segment = query_context_arc.clone();
-                    let segment = locked_segments[*segment_id].clone();
+                    let (segment_id, segment) = locked_segments[*segment_offset].clone();
                     let partial_batch_request = Arc::new(CoreSearchRequestBatch {
                         searches: batch_ids
                             .iter()
                             .map(|batch_id| batch_request.searches[*batch_id].clone())
                             .collect(),
                     });
-                    res.push(runtime_handle.spawn_blocking(move || {
-                        search_in_segment(
-                            segment,
-                            partial_batch_request,
-                            false,
-                            query_context_arc_segment,
-                        )
-                    }))
+                    let runtime_handle = runtime_handle.clone();
+                    res.push(async move {
+                        runtime_handle
+                            .spawn_blocking(move || {
+                                search_in_segment(
+                                    segment_id,
+                                    segment,
+                                    partial_batch_request,
+                                    false,
+                                    query_context_arc_segment,
+                                )
+                            })
+                            .await
+                    })
                 }
                 res
             };
 
             let (secondary_search_results_per_segment, _) =
-                Self::execute_searches(secondary_searches).await?;
+                Self::execute_searches(secondary_searches, segment_concurrency).await?;
 
             result_aggregator.update_point_versions(&secondary_search_results_per_segment);
 
-            for ((_segment_id, batch_ids), segments_result) in searches_to_rerun
+            for ((_segment_offset, batch_ids), segments_result) in searches_to_rerun
                 .into_iter()
                 .zip(secondary_search_results_per_segment.into_iter())
             {
@@ -338,6 +382,7 @@ impl SegmentsSearcher {
     /// Retrieve records for the given points ids from the segments
     /// - if payload is enabled, payload will be fetched
     /// - if vector is enabled, vector will be fetched
+    /// - if version is enabled, version will be fetched
     ///
     /// The points ids can contain duplicates, the records will be fetched only once
     ///
@@ -347,6 +392,7 @@ impl SegmentsSearcher {
         points: &[PointIdType],
         with_payload: &WithPayload,
         with_vector: &WithVector,
+        with_version: bool,
         runtime_handle: &Handle,
     ) -> CollectionResult<HashMap<PointIdType, Record>> {
         let stopping_guard = StoppingGuard::new();
@@ -364,6 +410,7 @@ impl SegmentsSearcher {
                         &points,
                         &with_payload,
                         &with_vector,
+                        with_version,
                         &is_stopped,
                     )
                 }
@@ -376,6 +423,7 @@ impl SegmentsSearcher {
         points: &[PointIdType],
         with_payload: &WithPayload,
         with_vector: &WithVector,
+        with_version: bool,
         is_stopped: &AtomicBool,
     ) -> CollectionResult<HashMap<PointIdType, Record>> {
         let mut point_version: HashMap<PointIdType, SeqNumberType> = Default::default();
@@ -395,7 +443,14 @@ impl SegmentsSearcher {
                             id,
                             payload: if with_payload.enable {
                                 if let Some(selector) = &with_payload.payload_selector {
-                                    Some(selector.process(segment.payload(id)?))
+                                    Some(match selector {
+                                        PayloadSelector::Include(include) => {
+                                            segment.payload_projected(id, &include.include)?
+                                        }
+                                        PayloadSelector::Exclude(_) => {
+                                            selector.process(segment.payload(id)?)
+                                        }
+                                    })
                                 } else {
                                     Some(segment.payload(id)?)
                                 }
@@ -418,8 +473,25 @@ impl SegmentsSearcher {
                                 };
                                 vector.map(Into::into)
                             },
+                            vector_metadata: {
+                                let mut vector_metadata = match with_vector {
+                                    WithVector::Bool(false) => Default::default(),
+                                    _ => segment.vector_metadata(id)?,
+                                };
+                                if let WithVector::Selector(vector_names) = with_vector {
+                                    vector_metadata.retain(|vector_name, _| {
+                                        vector_names.contains(vector_name)
+                                    });
+                                }
+                                (!vector_metadata.is_empty()).then_some(vector_metadata)
+                            },
                             shard_key: None,
                             order_value: None,
+                            version: if with_version {
+                                Some(segment.point_revision(id)?)
+                            } else {
+                                None
+                            },
                         },
                     );
                     point_version.insert(id, version);
@@ -520,6 +592,7 @@ fn effective_limit(limit: usize, ef_limit: usize, poisson_sampling: usize) -> us
 ///
 /// # Arguments
 ///
+/// * `segment_id` - ID of the segment, found points are annotated with it if provenance is requested
 /// * `segment` - Locked segment to search in
 /// * `request` - Batch of search requests
 /// * `use_sampling` - If true, try to use probabilistic sampling
@@ -531,6 +604,7 @@ fn effective_limit(limit: usize, ef_limit: usize, poisson_sampling: usize) -> us
 /// * Vector of ScoredPoints for each request in the batch
 /// * Vector of boolean indicating if the segment have further points to search
 fn search_in_segment(
+    segment_id: SegmentId,
     segment: LockedSegment,
     request: Arc<CoreSearchRequestBatch>,
     use_sampling: bool,
@@ -538,12 +612,11 @@ fn search_in_segment(
 ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
     let batch_size = request.searches.len();
 
-    let mut result: Vec<Vec<ScoredPoint>> = Vec::with_capacity(batch_size);
-    let mut further_results: Vec<bool> = Vec::with_capacity(batch_size); // if segment have more points to return
-    let mut vectors_batch: Vec<QueryVector> = vec![];
-    let mut prev_params = BatchSearchParams::default();
+    // Group searches with same params into batches, even if they are not adjacent in the request.
+    // Searches with different params (e.g. different filters) never share a batch.
+    let mut batches: Vec<(BatchSearchParams, Vec<BatchOffset>, Vec<QueryVector>)> = Vec::new();
 
-    for search_query in &request.searches {
+    for (batch_offset, search_query) in request.searches.iter().enumerate() {
         let with_payload_interface = search_query
             .with_payload
             .as_ref()
@@ -562,55 +635,65 @@ fn search_in_segment(
         let query = search_query.query.clone().into();
 
         // same params enables batching
-        if params == prev_params {
-            vectors_batch.push(query);
-        } else {
-            // different params means different batches
-            // execute what has been batched so far
-            if !vectors_batch.is_empty() {
-                let (mut res, mut further) = execute_batch_search(
-                    &segment,
-                    &vectors_batch,
-                    &prev_params,
-                    use_sampling,
-                    &query_context,
-                )?;
-                further_results.append(&mut further);
-                result.append(&mut res);
-                vectors_batch.clear()
+        match batches
+            .iter_mut()
+            .find(|(batch_params, _, _)| *batch_params == params)
+        {
+            Some((_, offsets, vectors_batch)) => {
+                offsets.push(batch_offset);
+                vectors_batch.push(query);
             }
-            // start new batch for current search query
-            vectors_batch.push(query);
-            prev_params = params;
+            None => batches.push((params, vec![batch_offset], vec![query])),
         }
     }
 
-    // run last batch if any
-    if !vectors_batch.is_empty() {
-        let (mut res, mut further) = execute_batch_search(
-            &segment,
-            &vectors_batch,
-            &prev_params,
+    let locked_segment = segment.get();
+
+    let mut result: Vec<Vec<ScoredPoint>> = vec![Vec::new(); batch_size];
+    let mut further_results: Vec<bool> = vec![false; batch_size]; // if segment have more points to return
+
+    for (params, offsets, vectors_batch) in &batches {
+        // Take the read lock per batch, so updates are not blocked for the whole request
+        let read_segment = locked_segment.read();
+        let (res, further) = execute_batch_search(
+            &*read_segment,
+            vectors_batch,
+            params,
             use_sampling,
             &query_context,
         )?;
-        further_results.append(&mut further);
-        result.append(&mut res);
+        drop(read_segment);
+
+        // Put results back in request order
+        for ((&batch_offset, points), further) in offsets.iter().zip(res).zip(further) {
+            result[batch_offset] = points;
+            further_results[batch_offset] = further;
+        }
+    }
+
+    // Points found in multiple segments are deduplicated by version later, so each point keeps
+    // the segment of its latest version
+    for (points, search_query) in result.iter_mut().zip(&request.searches) {
+        if search_query.params.is_some_and(|params| params.provenance) {
+            for point in points {
+                point.provenance = Some(PointProvenance {
+                    shard_id: None,
+                    segment_id,
+                });
+            }
+        }
     }
 
     Ok((result, further_results))
 }
 
 fn execute_batch_search(
-    segment: &LockedSegment,
+    read_segment: &dyn SegmentEntry,
     vectors_batch: &[QueryVector],
     search_params: &BatchSearchParams,
     use_sampling: bool,
     query_context: &QueryContext,
 ) -> CollectionResult<(Vec<Vec<ScoredPoint>>, Vec<bool>)> {
-    let locked_segment = segment.get();
-    let read_segment = locked_segment.read();
-
     let segment_points = read_segment.available_point_count();
     let segment_config = read_segment.config();
 
@@ -676,7 +759,9 @@ mod tests {
     use tempfile::Builder;
 
     use super::*;
-    use crate::collection_manager::fixtures::{build_test_holder, random_segment};
+    use crate::collection_manager::fixtures::{
+        build_segment_1, build_segment_2, build_test_holder, random_segment,
+    };
     use crate::collection_manager::holders::segment_holder::SegmentHolder;
     use crate::operations::types::CoreSearchRequest;
     use crate::optimizers_builder::DEFAULT_INDEXING_THRESHOLD_KB;
@@ -727,6 +812,10 @@ mod tests {
             limit: 5,
             score_threshold: None,
             offset: 0,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: false,
         };
 
         let batch_request = CoreSearchRequestBatch {
@@ -739,6 +828,7 @@ mod tests {
             &Handle::current(),
             true,
             QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+            None,
         )
         .await
         .unwrap()
@@ -754,6 +844,188 @@ mod tests {
         assert!(result[1].id == 3.into() || result[1].id == 11.into());
     }
 
+    #[tokio::test]
+    async fn test_segments_search_segment_concurrency() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment_holder = Arc::new(build_test_holder(dir.path()));
+
+        let batch_request = Arc::new(CoreSearchRequestBatch {
+            searches: vec![CoreSearchRequest {
+                query: vec![1.0, 1.0, 1.0, 1.0].into(),
+                with_payload: None,
+                with_vector: None,
+                filter: None,
+                params: None,
+                limit: 5,
+                score_threshold: None,
+                offset: 0,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
+            }],
+        });
+
+        let handle = Handle::current();
+        let search = |segment_concurrency| {
+            SegmentsSearcher::search(
+                segment_holder.clone(),
+                batch_request.clone(),
+                &handle,
+                false,
+                QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                segment_concurrency,
+            )
+        };
+
+        let unbounded = search(None).await.unwrap();
+        assert_eq!(unbounded[0].len(), 5);
+
+        // Searching segments one by one, or with a budget above the number of segments, gives the
+        // same results
+        for concurrency in [1, 100] {
+            let bounded = search(NonZeroUsize::new(concurrency)).await.unwrap();
+            assert_eq!(bounded, unbounded);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_segments_search_batch_mixed_filters() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let segment_holder = Arc::new(build_test_holder(dir.path()));
+
+        let ids: HashSet<_> = vec![1, 2, 11].into_iter().map(PointIdType::from).collect();
+        let ids_filter = Filter::new_must(Condition::HasId(HasIdCondition::from(ids)));
+
+        let request = |filter: Option<Filter>| CoreSearchRequest {
+            query: vec![1.0, 1.0, 1.0, 1.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter,
+            params: None,
+            limit: 5,
+            score_threshold: None,
+            offset: 0,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: false,
+        };
+
+        // Interleave filtered and unfiltered searches, so that batches are not adjacent
+        let searches = vec![
+            request(None),
+            request(Some(ids_filter.clone())),
+            request(None),
+            request(Some(ids_filter)),
+        ];
+
+        let batch_result = SegmentsSearcher::search(
+            segment_holder.clone(),
+            Arc::new(CoreSearchRequestBatch {
+                searches: searches.clone(),
+            }),
+            &Handle::current(),
+            false,
+            QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+            None,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(batch_result.len(), searches.len());
+
+        for (search, batch_points) in searches.into_iter().zip(batch_result) {
+            let is_filtered = search.filter.is_some();
+
+            let single_result = SegmentsSearcher::search(
+                segment_holder.clone(),
+                Arc::new(CoreSearchRequestBatch {
+                    searches: vec![search],
+                }),
+                &Handle::current(),
+                false,
+                QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                None,
+            )
+            .await
+            .unwrap()
+            .into_iter()
+            .next()
+            .unwrap();
+
+            let batch_ids: Vec<_> = batch_points.iter().map(|point| point.id).collect();
+            let single_ids: Vec<_> = single_result.iter().map(|point| point.id).collect();
+            assert_eq!(batch_ids, single_ids);
+
+            if is_filtered {
+                assert_eq!(batch_ids.len(), 3);
+            } else {
+                assert_eq!(batch_ids.len(), 5);
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_segments_search_provenance() {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+        let mut holder = SegmentHolder::default();
+        let sid1 = holder.add_new(build_segment_1(dir.path()));
+        let sid2 = holder.add_new(build_segment_2(dir.path()));
+
+        let search = |provenance| CoreSearchRequest {
+            query: vec![1.0, 1.0, 0.0, 1.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: Some(SearchParams {
+                provenance,
+                ..Default::default()
+            }),
+            limit: 20,
+            score_threshold: None,
+            offset: 0,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: false,
+        };
+        let batch_request = CoreSearchRequestBatch {
+            searches: vec![search(true), search(false)],
+        };
+
+        let result = SegmentsSearcher::search(
+            Arc::new(RwLock::new(holder)),
+            Arc::new(batch_request),
+            &Handle::current(),
+            false,
+            QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+            None,
+        )
+        .await
+        .unwrap();
+
+        let provenance = |id: u64| {
+            result[0]
+                .iter()
+                .find(|point| point.id == id.into())
+                .and_then(|point| point.provenance)
+                .unwrap()
+        };
+        assert_eq!(provenance(1).segment_id, sid1);
+        // Points 4 and 5 are in both segments, the latest versions are in the second one
+        assert_eq!(provenance(4).segment_id, sid2);
+        assert_eq!(provenance(5).segment_id, sid2);
+        assert_eq!(provenance(11).segment_id, sid2);
+        // Shard is only known to the collection
+        assert_eq!(provenance(1).shard_id, None);
+
+        assert!(result[1].iter().all(|point| point.provenance.is_none()));
+    }
+
     #[tokio::test]
     async fn test_segments_search_sampling() {
         let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -780,6 +1052,10 @@ mod tests {
                 filter: None,
                 params: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             };
             let req2 = SearchRequestInternal {
                 vector: random_vector(&mut rnd, 4).into(),
@@ -790,6 +1066,10 @@ mod tests {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             };
 
             let batch_request = CoreSearchRequestBatch {
@@ -804,6 +1084,7 @@ mod tests {
                 &Handle::current(),
                 false,
                 QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                None,
             )
             .await
             .unwrap();
@@ -816,6 +1097,7 @@ mod tests {
                 &Handle::current(),
                 true,
                 QueryContext::new(DEFAULT_INDEXING_THRESHOLD_KB),
+                None,
             )
             .await
             .unwrap();
@@ -842,10 +1124,13 @@ mod tests {
             &[1.into(), 2.into(), 3.into()],
             &WithPayload::from(true),
             &true.into(),
+            false,
             &AtomicBool::new(false),
         )
         .unwrap();
         assert_eq!(records.len(), 3);
+        // Versions are only read on request
+        assert!(records.values().all(|record| record.version.is_none()));
     }
 
     #[test]
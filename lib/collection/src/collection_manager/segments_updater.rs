@@ -7,17 +7,19 @@ use itertools::iproduct;
 use parking_lot::{RwLock, RwLockWriteGuard};
 use segment::common::operation_error::{OperationError, OperationResult};
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
 use segment::entry::entry_point::SegmentEntry;
 use segment::json_path::JsonPath;
 use segment::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    SeqNumberType,
+    SeqNumberType, VectorMetadata,
 };
+use serde_json::Value;
 
 use crate::collection_manager::holders::segment_holder::SegmentHolder;
-use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointInsertOperationsInternal, PointOperations, PointStruct};
+use crate::operations::payload_ops::{PayloadCopyMode, PayloadOps};
+use crate::operations::point_ops::{
+    PointOperations, PointStruct, PointVersion, TtlInsertOperationInternal, TtlStamp,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::vector_ops::{PointVectors, VectorOperations};
 use crate::operations::FieldIndexOperations;
@@ -34,6 +36,14 @@ pub(crate) fn check_unprocessed_points(
     }
 }
 
+/// Next revision of a point changed by an update
+///
+/// Revisions only depend on the sequence of updates of the point, so all replicas which applied
+/// the same updates have the same revisions.
+fn increment_revision(_point_id: PointIdType, revision: SeqNumberType) -> SeqNumberType {
+    revision + 1
+}
+
 /// Tries to delete points from all segments, returns number of actually deleted points
 pub(crate) fn delete_points(
     segments: &SegmentHolder,
@@ -82,12 +92,14 @@ pub(crate) fn update_vectors(
                 let vectors = points_map[&id].clone();
                 write_segment.update_vectors(op_num, id, vectors)
             },
-            |id, owned_vectors, _| {
+            |id, owned_vectors, _, vector_metadata| {
                 for (vector_name, vector_ref) in points_map[&id].iter() {
                     owned_vectors.insert(vector_name.to_string(), vector_ref.to_owned());
+                    vector_metadata.remove(vector_name);
                 }
             },
             |_| false,
+            increment_revision,
         )?;
         check_unprocessed_points(batch, &updated_points)?;
         total_updated_points += updated_points.len();
@@ -112,10 +124,22 @@ pub(crate) fn delete_vectors(
             batch,
             |_| (),
             |id, _idx, write_segment, ()| {
+                // Applying the operation again must not change the revision again
+                let is_applied = write_segment
+                    .point_version(id)
+                    .is_some_and(|version| version >= op_num);
+                let revision = write_segment.point_revision(id)?;
                 let mut res = true;
                 for name in vector_names {
                     res &= write_segment.delete_vector(op_num, id, name)?;
                 }
+                if !is_applied {
+                    write_segment.set_point_revision(
+                        op_num,
+                        id,
+                        increment_revision(id, revision),
+                    )?;
+                }
                 Ok(res)
             },
         )?;
@@ -153,10 +177,11 @@ pub(crate) fn overwrite_payload(
             op_num,
             batch,
             |id, write_segment| write_segment.set_full_payload(op_num, id, payload),
-            |_, _, old_payload| {
+            |_, _, old_payload, _| {
                 *old_payload = payload.clone();
             },
             |segment| segment.get_indexed_fields().is_empty(),
+            increment_revision,
         )?;
 
         total_updated_points += updated_points.len();
@@ -190,7 +215,7 @@ pub(crate) fn set_payload(
             op_num,
             chunk,
             |id, write_segment| write_segment.set_payload(op_num, id, payload, key),
-            |_, _, old_payload| match key {
+            |_, _, old_payload, _| match key {
                 Some(key) => old_payload.merge_by_key(payload, key),
                 None => old_payload.merge(payload),
             },
@@ -199,6 +224,7 @@ pub(crate) fn set_payload(
                     !indexed_path.is_affected_by_value_set(&payload.0, key.as_ref())
                 })
             },
+            increment_revision,
         )?;
 
         check_unprocessed_points(chunk, &updated_points)?;
@@ -208,6 +234,107 @@ pub(crate) fn set_payload(
     Ok(total_updated_points)
 }
 
+pub(crate) fn merge_patch_payload(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &Payload,
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    let mut total_updated_points = 0;
+
+    for chunk in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
+        let updated_points = segments.apply_points_with_conditional_move(
+            op_num,
+            chunk,
+            |id, write_segment| {
+                // Read and write happen under the same segment write lock,
+                // so the patch is applied atomically for each point
+                let mut payload = write_segment.payload(id)?;
+                payload.merge_patch(patch);
+                write_segment.set_full_payload(op_num, id, &payload)
+            },
+            |_, _, old_payload, _| old_payload.merge_patch(patch),
+            |segment| {
+                segment
+                    .get_indexed_fields()
+                    .keys()
+                    .all(|indexed_path| !indexed_path.is_affected_by_value_set(&patch.0, None))
+            },
+            increment_revision,
+        )?;
+
+        check_unprocessed_points(chunk, &updated_points)?;
+        total_updated_points += updated_points.len();
+    }
+
+    Ok(total_updated_points)
+}
+
+pub(crate) fn merge_patch_payload_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    patch: &Payload,
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    merge_patch_payload(segments, op_num, patch, &affected_points)
+}
+
+/// Copy or move the value of the `from` field to the `to` field.
+///
+/// Unlike other payload operations, points without the `from` field, or missing points, are
+/// skipped instead of failing the operation.
+pub(crate) fn copy_payload_field(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    from: &JsonPath,
+    to: &JsonPath,
+    move_field: bool,
+    points: &[PointIdType],
+) -> CollectionResult<usize> {
+    let mut total_updated_points = 0;
+
+    for chunk in points.chunks(PAYLOAD_OP_BATCH_SIZE) {
+        let updated_points = segments.apply_points_with_conditional_move(
+            op_num,
+            chunk,
+            |id, write_segment| {
+                let mut payload = write_segment.payload(id)?;
+                if !payload.copy_field(from, to, move_field) {
+                    return Ok(false);
+                }
+                write_segment.set_full_payload(op_num, id, &payload)
+            },
+            |_, _, old_payload, _| {
+                old_payload.copy_field(from, to, move_field);
+            },
+            |segment| {
+                segment.get_indexed_fields().keys().all(|indexed_path| {
+                    !indexed_path.is_affected_by_value_remove(to)
+                        && !(move_field && indexed_path.is_affected_by_value_remove(from))
+                })
+            },
+            increment_revision,
+        )?;
+
+        total_updated_points += updated_points.len();
+    }
+
+    Ok(total_updated_points)
+}
+
+pub(crate) fn copy_payload_field_by_filter(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    from: &JsonPath,
+    to: &JsonPath,
+    move_field: bool,
+    filter: &Filter,
+) -> CollectionResult<usize> {
+    let affected_points = points_by_filter(segments, filter)?;
+    copy_payload_field(segments, op_num, from, to, move_field, &affected_points)
+}
+
 fn points_by_filter(
     segments: &SegmentHolder,
     filter: &Filter,
@@ -253,7 +380,7 @@ pub(crate) fn delete_payload(
                 }
                 Ok(res)
             },
-            |_, _, payload| {
+            |_, _, payload, _| {
                 for key in keys {
                     payload.remove(key);
                 }
@@ -265,6 +392,7 @@ pub(crate) fn delete_payload(
                     },
                 )
             },
+            increment_revision,
         )?;
 
         check_unprocessed_points(batch, &updated_points)?;
@@ -296,8 +424,9 @@ pub(crate) fn clear_payload(
             op_num,
             batch,
             |id, write_segment| write_segment.clear_payload(op_num, id),
-            |_, _, payload| payload.0.clear(),
+            |_, _, payload, _| payload.0.clear(),
             |segment| segment.get_indexed_fields().is_empty(),
+            increment_revision,
         )?;
         check_unprocessed_points(batch, &updated_points)?;
         total_updated_points += updated_points.len();
@@ -321,8 +450,9 @@ pub(crate) fn clear_payload_by_filter(
             op_num,
             batch,
             |id, write_segment| write_segment.clear_payload(op_num, id),
-            |_, _, payload| payload.0.clear(),
+            |_, _, payload, _| payload.0.clear(),
             |segment| segment.get_indexed_fields().is_empty(),
+            increment_revision,
         )?;
         total_updated_points += updated_points.len();
     }
@@ -374,14 +504,53 @@ fn upsert_with_payload(
     point_id: PointIdType,
     vectors: NamedVectors,
     payload: Option<&Payload>,
+    vector_metadata: Option<&VectorMetadata>,
 ) -> OperationResult<bool> {
     let mut res = segment.upsert_point(op_num, point_id, vectors)?;
     if let Some(full_payload) = payload {
         res &= segment.set_full_payload(op_num, point_id, full_payload)?;
     }
+    // Replacing vectors drops their metadata, so only non-empty metadata has to be set
+    if let Some(vector_metadata) = vector_metadata.filter(|metadata| !metadata.is_empty()) {
+        res &= segment.set_vector_metadata(op_num, point_id, vector_metadata)?;
+    }
     Ok(res)
 }
 
+/// Revisions assigned to points by an upsert
+#[derive(Debug, Default)]
+pub(crate) struct UpsertRevisions {
+    /// Unix timestamp in microseconds of the operation, the lowest revision upserted points get
+    timestamp: Option<u64>,
+    /// Revisions assigned to points as they are, when points are copied between shards
+    explicit: HashMap<PointIdType, SeqNumberType>,
+}
+
+impl UpsertRevisions {
+    pub fn new(timestamp: Option<u64>, versions: &[PointVersion]) -> Self {
+        Self {
+            timestamp,
+            explicit: versions
+                .iter()
+                .map(|version| (version.id, version.version))
+                .collect(),
+        }
+    }
+
+    /// Next revision of an upserted point, 0 is the current revision of points which don't exist
+    ///
+    /// The timestamp is taken once for the whole operation before it is sent to replicas, so all
+    /// of them assign the same revisions. A reinserted point gets a revision which is higher than
+    /// the ones it had before being deleted, unless it was updated more often than once per
+    /// microsecond.
+    fn next(&self, point_id: PointIdType, revision: SeqNumberType) -> SeqNumberType {
+        match self.explicit.get(&point_id) {
+            Some(&explicit) => explicit,
+            None => increment_revision(point_id, revision).max(self.timestamp.unwrap_or(0)),
+        }
+    }
+}
+
 /// Sync points within a given [from_id; to_id) range
 ///
 /// 1. Retrieve existing points for a range
@@ -390,6 +559,8 @@ fn upsert_with_payload(
 /// 4. Select new points
 /// 5. Upsert points which differ from the stored ones
 ///
+/// Points which have a version given in `versions` get it as their revision.
+///
 /// Returns:
 ///     (number of deleted points, number of new points, number of updated points)
 pub(crate) fn sync_points(
@@ -398,7 +569,9 @@ pub(crate) fn sync_points(
     from_id: Option<PointIdType>,
     to_id: Option<PointIdType>,
     points: &[PointStruct],
+    versions: &[PointVersion],
 ) -> CollectionResult<(usize, usize, usize)> {
+    let revisions = UpsertRevisions::new(None, versions);
     let id_to_point = points
         .iter()
         .map(|p| (p.id, p))
@@ -438,7 +611,16 @@ pub(crate) fn sync_points(
                     Some(ref p) => p == &payload,
                     None => Payload::default() == payload,
                 };
-                if !payload_match {
+                let vector_metadata = segment.vector_metadata(id)?;
+                let vector_metadata_match = match point.vector_metadata {
+                    Some(ref m) => m == &vector_metadata,
+                    None => vector_metadata.is_empty(),
+                };
+                let revision_match = match revisions.explicit.get(&id) {
+                    Some(&revision) => revision == segment.point_revision(id)?,
+                    None => true,
+                };
+                if !payload_match || !vector_metadata_match || !revision_match {
                     points_to_update.push(*point);
                     Ok(true)
                 } else {
@@ -456,7 +638,8 @@ pub(crate) fn sync_points(
     });
 
     // 5. Upsert points which differ from the stored ones
-    let num_replaced = upsert_points(segments, op_num, points_to_update)?;
+    let num_replaced =
+        upsert_points_with_revisions(segments, op_num, points_to_update, &revisions)?;
     debug_assert!(num_replaced <= num_updated, "number of replaced points cannot be greater than points to update ({num_replaced} <= {num_updated})");
 
     Ok((deleted, num_new, num_updated))
@@ -470,6 +653,19 @@ pub(crate) fn upsert_points<'a, T>(
     op_num: SeqNumberType,
     points: T,
 ) -> CollectionResult<usize>
+where
+    T: IntoIterator<Item = &'a PointStruct>,
+{
+    upsert_points_with_revisions(segments, op_num, points, &UpsertRevisions::default())
+}
+
+/// Same as [`upsert_points`], but assigns the given revisions to upserted points
+pub(crate) fn upsert_points_with_revisions<'a, T>(
+    segments: &SegmentHolder,
+    op_num: SeqNumberType,
+    points: T,
+    revisions: &UpsertRevisions,
+) -> CollectionResult<usize>
 where
     T: IntoIterator<Item = &'a PointStruct>,
 {
@@ -489,9 +685,10 @@ where
                 id,
                 point.get_vectors(),
                 point.payload.as_ref(),
+                point.vector_metadata.as_ref(),
             )
         },
-        |id, vectors, old_payload| {
+        |id, vectors, old_payload, vector_metadata| {
             let point = points_map[&id];
             for (name, vec) in point.get_vectors() {
                 vectors.insert(name.to_string(), vec.to_owned());
@@ -499,8 +696,10 @@ where
             if let Some(payload) = &point.payload {
                 *old_payload = payload.clone();
             }
+            *vector_metadata = point.vector_metadata.clone().unwrap_or_default();
         },
         |_| false,
+        |id, revision| revisions.next(id, revision),
     )?;
 
     let mut res = updated_points.len();
@@ -528,7 +727,9 @@ where
                 point_id,
                 point.get_vectors(),
                 point.payload.as_ref(),
+                point.vector_metadata.as_ref(),
             )?);
+            write_segment.set_point_revision(op_num, point_id, revisions.next(point_id, 0))?;
         }
         RwLockWriteGuard::unlock_fair(write_segment);
     };
@@ -536,6 +737,124 @@ where
     Ok(res)
 }
 
+/// Checks that all points have the expected versions, i.e. were not changed since they were read.
+///
+/// Versions are compared with the stored revisions of points, which are the same on all replicas.
+/// A point which does not exist has no version, so it never matches.
+pub(crate) fn check_point_versions(
+    segments: &SegmentHolder,
+    expected_versions: &[PointVersion],
+) -> CollectionResult<()> {
+    let ids: Vec<PointIdType> = expected_versions
+        .iter()
+        .map(|expected| expected.id)
+        .collect();
+
+    // Operation number and revision of the latest copy of each point
+    let mut current_versions: HashMap<PointIdType, (SeqNumberType, SeqNumberType)> = HashMap::new();
+    segments.read_points(&ids, &AtomicBool::new(false), |id, segment| {
+        // Point might be present in multiple segments, the latest version is the actual one
+        let Some(op_num) = segment.point_version(id) else {
+            return Ok(true);
+        };
+        let is_latest = match current_versions.get(&id) {
+            Some(&(latest_op_num, _)) => latest_op_num < op_num,
+            None => true,
+        };
+        if is_latest {
+            current_versions.insert(id, (op_num, segment.point_revision(id)?));
+        }
+        Ok(true)
+    })?;
+
+    for expected in expected_versions {
+        match current_versions.get(&expected.id) {
+            Some(&(_, version)) if version == expected.version => {}
+            Some((_, version)) => {
+                return Err(CollectionError::pre_condition_failed(format!(
+                    "Point {} has version {version}, expected {}",
+                    expected.id, expected.version,
+                )));
+            }
+            None => {
+                return Err(CollectionError::pre_condition_failed(format!(
+                    "Point {} does not exist, expected version {}",
+                    expected.id, expected.version,
+                )));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Set the TTL timestamp on points which don't set it in their payload.
+///
+/// Points which don't exist yet get the timestamp of the operation if `set_on_insert` is set.
+/// Existing points get it if `refresh_on_update` is set, and keep their current one otherwise.
+pub(crate) fn set_ttl_timestamps(
+    segments: &SegmentHolder,
+    points: &mut [PointStruct],
+    stamp: &TtlStamp,
+) -> CollectionResult<()> {
+    let has_timestamp = |point: &PointStruct| {
+        point
+            .payload
+            .as_ref()
+            .is_some_and(|payload| payload.contains_key(&stamp.key))
+    };
+
+    // Current timestamps only matter if new and existing points are stamped differently
+    let mut stored: HashMap<PointIdType, (SeqNumberType, Option<Value>)> = HashMap::new();
+    if !(stamp.set_on_insert && stamp.refresh_on_update) {
+        let ids: Vec<PointIdType> = points
+            .iter()
+            .filter(|point| !has_timestamp(point))
+            .map(|point| point.id)
+            .collect();
+        segments.read_points(&ids, &AtomicBool::new(false), |id, segment| {
+            // Point might be present in multiple segments, the latest version is the actual one
+            let Some(op_num) = segment.point_version(id) else {
+                return Ok(true);
+            };
+            let is_latest = match stored.get(&id) {
+                Some(&(latest_op_num, _)) => latest_op_num < op_num,
+                None => true,
+            };
+            if is_latest {
+                let timestamp = segment.payload(id)?.0.remove(&stamp.key);
+                stored.insert(id, (op_num, timestamp));
+            }
+            Ok(true)
+        })?;
+    }
+
+    let now = Value::from(stamp.timestamp);
+    for point in points.iter_mut() {
+        if has_timestamp(point) {
+            continue;
+        }
+
+        // Existing points aren't looked up if all points are stamped alike
+        let timestamp = match stored.get(&point.id) {
+            Some(_) if stamp.refresh_on_update => Some(now.clone()),
+            Some((_, stored_timestamp)) => stored_timestamp.clone(),
+            None if stamp.set_on_insert => Some(now.clone()),
+            None => None,
+        };
+
+        if let Some(timestamp) = timestamp {
+            point
+                .payload
+                .get_or_insert_with(Payload::default)
+                .0
+                .insert(stamp.key.clone(), timestamp);
+        }
+    }
+
+    Ok(())
+}
+
 pub(crate) fn process_point_operation(
     segments: &RwLock<SegmentHolder>,
     op_num: SeqNumberType,
@@ -544,34 +863,28 @@ pub(crate) fn process_point_operation(
     match point_operation {
         PointOperations::DeletePoints { ids, .. } => delete_points(&segments.read(), op_num, &ids),
         PointOperations::UpsertPoints(operation) => {
-            let points: Vec<_> = match operation {
-                PointInsertOperationsInternal::PointsBatch(batch) => {
-                    let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
-                    let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
-                    let vectors_iter = batch.ids.into_iter().zip(all_vectors);
-                    match batch.payloads {
-                        None => vectors_iter
-                            .map(|(id, vectors)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload: None,
-                            })
-                            .collect(),
-                        Some(payloads) => vectors_iter
-                            .zip(payloads)
-                            .map(|((id, vectors), payload)| PointStruct {
-                                id,
-                                vector: VectorStructInternal::from(vectors).into(),
-                                payload,
-                            })
-                            .collect(),
-                    }
-                }
-                PointInsertOperationsInternal::PointsList(points) => points,
-            };
+            let points = operation.into_point_list();
             let res = upsert_points(&segments.read(), op_num, points.iter())?;
             Ok(res)
         }
+        PointOperations::UpsertPointsVersioned(operation) => {
+            // Updates are applied sequentially, nothing can change the points in between
+            let segments = segments.read();
+            check_point_versions(&segments, &operation.expected_versions)?;
+            let revisions = UpsertRevisions::new(operation.timestamp, &operation.versions);
+            let points = operation.points_op.into_point_list();
+            let res = upsert_points_with_revisions(&segments, op_num, points.iter(), &revisions)?;
+            Ok(res)
+        }
+        PointOperations::UpsertPointsWithTtl(TtlInsertOperationInternal { upsert, stamp }) => {
+            let segments = segments.read();
+            check_point_versions(&segments, &upsert.expected_versions)?;
+            let revisions = UpsertRevisions::new(upsert.timestamp, &upsert.versions);
+            let mut points = upsert.points_op.into_point_list();
+            set_ttl_timestamps(&segments, &mut points, &stamp)?;
+            let res = upsert_points_with_revisions(&segments, op_num, points.iter(), &revisions)?;
+            Ok(res)
+        }
         PointOperations::DeletePointsByFilter(filter) => {
             delete_points_by_filter(&segments.read(), op_num, &filter)
         }
@@ -582,6 +895,7 @@ pub(crate) fn process_point_operation(
                 operation.from_id,
                 operation.to_id,
                 &operation.points,
+                &operation.versions,
             )?;
             Ok(deleted + new + updated)
         }
@@ -595,7 +909,17 @@ pub(crate) fn process_vector_operation(
 ) -> CollectionResult<usize> {
     match vector_operation {
         VectorOperations::UpdateVectors(operation) => {
-            update_vectors(&segments.read(), op_num, operation.points)
+            let segments = segments.read();
+            let mut updated = update_vectors(&segments, op_num, operation.points)?;
+            let delete_by_name = operation
+                .delete_vectors
+                .into_iter()
+                .into_group_map_by(|(_, name)| name.clone());
+            for (name, points) in delete_by_name {
+                let ids = points.into_iter().map(|(id, _)| id).collect_vec();
+                updated += delete_vectors(&segments, op_num, &ids, &[name])?;
+            }
+            Ok(updated)
         }
         VectorOperations::DeleteVectors(ids, vector_names) => {
             delete_vectors(&segments.read(), op_num, &ids.points, &vector_names)
@@ -653,6 +977,49 @@ pub(crate) fn process_payload_operation(
                 })
             }
         }
+        PayloadOps::MergePatchPayload(sp) => {
+            if sp.key.is_some() {
+                return Err(CollectionError::bad_request(
+                    "Merge patch is applied to the whole payload, `key` is not supported",
+                ));
+            }
+            let patch: Payload = sp.payload;
+            if let Some(points) = sp.points {
+                merge_patch_payload(&segments.read(), op_num, &patch, &points)
+            } else if let Some(filter) = sp.filter {
+                merge_patch_payload_by_filter(&segments.read(), op_num, &patch, &filter)
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
+        PayloadOps::CopyPayloadField(op) => {
+            let move_field = op.mode == PayloadCopyMode::Move;
+            if let Some(points) = op.points {
+                copy_payload_field(
+                    &segments.read(),
+                    op_num,
+                    &op.from,
+                    &op.to,
+                    move_field,
+                    &points,
+                )
+            } else if let Some(filter) = op.filter {
+                copy_payload_field_by_filter(
+                    &segments.read(),
+                    op_num,
+                    &op.from,
+                    &op.to,
+                    move_field,
+                    &filter,
+                )
+            } else {
+                Err(CollectionError::BadRequest {
+                    description: "No points or filter specified".to_string(),
+                })
+            }
+        }
     }
 }
 
@@ -5,20 +5,39 @@ use std::path::Path;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
+use api::rest::VectorStruct;
+use common::cpu::CpuPermit;
 use itertools::Itertools;
 use parking_lot::RwLock;
-use segment::data_types::vectors::{only_default_vector, VectorStructInternal};
+use segment::data_types::vectors::{
+    only_default_vector, QueryVector, Vector, VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
 use segment::entry::entry_point::SegmentEntry;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, PointIdType};
+use segment::segment::Segment;
+use segment::segment_constructor::segment_builder::SegmentBuilder;
+use segment::types::{
+    HnswConfig, Indexes, Payload, PayloadFieldSchema, PayloadKeyType, PointIdType, SearchParams,
+    VectorStorageType, WithPayload,
+};
+use serde_json::json;
 use tempfile::Builder;
 
-use crate::collection_manager::fixtures::{build_segment_1, build_segment_2, empty_segment};
+use crate::collection_manager::fixtures::{
+    build_segment_1, build_segment_2, empty_segment, random_segment,
+};
 use crate::collection_manager::holders::proxy_segment::ProxySegment;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder, SegmentId,
 };
-use crate::collection_manager::segments_updater::upsert_points;
-use crate::operations::point_ops::PointStruct;
+use crate::collection_manager::segments_updater::{
+    copy_payload_field, delete_points, merge_patch_payload, overwrite_payload,
+    process_point_operation, update_vectors, upsert_points,
+};
+use crate::operations::point_ops::{
+    PointOperations, PointStruct, PointVersion, TtlInsertOperationInternal, TtlStamp,
+    VersionedInsertOperationInternal,
+};
+use crate::operations::vector_ops::PointVectors;
 
 fn wrap_proxy(segments: LockedSegmentHolder, sid: SegmentId, path: &Path) -> SegmentId {
     let mut write_segments = segments.write();
@@ -73,11 +92,13 @@ fn test_update_proxy_segments() {
                 id: (100 * i + 1).into(),
                 vector: VectorStructInternal::from(vectors[0].clone()).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: (100 * i + 2).into(),
                 vector: VectorStructInternal::from(vectors[1].clone()).into(),
                 payload: None,
+                vector_metadata: None,
             },
         ];
         upsert_points(&segments.read(), 1000 + i, &points).unwrap();
@@ -122,11 +143,13 @@ fn test_move_points_to_copy_on_write() {
             id: 1.into(),
             vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            vector_metadata: None,
         },
         PointStruct {
             id: 2.into(),
             vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            vector_metadata: None,
         },
     ];
 
@@ -137,11 +160,13 @@ fn test_move_points_to_copy_on_write() {
             id: 2.into(),
             vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            vector_metadata: None,
         },
         PointStruct {
             id: 3.into(),
             vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            vector_metadata: None,
         },
     ];
 
@@ -224,6 +249,7 @@ fn test_upsert_points_in_smallest_segment() {
             id: id.into(),
             vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
             payload: None,
+            vector_metadata: None,
         })
         .collect();
     upsert_points(&segments.read(), 1000, &points).unwrap();
@@ -240,3 +266,391 @@ fn test_upsert_points_in_smallest_segment() {
         }
     }
 }
+
+#[test]
+fn test_merge_patch_payload() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add_new(build_segment_1(dir.path()));
+
+    let point_id: PointIdType = 1.into();
+    let payload: Payload = json!({
+        "meta": {"author": "John", "tags": ["a", "b"], "stats": {"views": 1}},
+        "title": "Old",
+    })
+    .into();
+    overwrite_payload(&holder, 100, &payload, &[point_id]).unwrap();
+
+    let patch: Payload = json!({
+        "meta": {"author": null, "tags": ["c"], "stats": {"likes": 2}},
+        "title": "New",
+        "draft": null,
+    })
+    .into();
+    merge_patch_payload(&holder, 101, &patch, &[point_id]).unwrap();
+
+    let stored = holder
+        .get(sid)
+        .unwrap()
+        .get()
+        .read()
+        .payload(point_id)
+        .unwrap();
+    let expected: Payload = json!({
+        "meta": {"tags": ["c"], "stats": {"views": 1, "likes": 2}},
+        "title": "New",
+    })
+    .into();
+    assert_eq!(stored, expected);
+}
+
+#[test]
+fn test_copy_payload_field() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add_new(build_segment_1(dir.path()));
+
+    let with_field: PointIdType = 1.into();
+    let without_field: PointIdType = 2.into();
+    overwrite_payload(
+        &holder,
+        100,
+        &json!({"name": "John", "age": 30}).into(),
+        &[with_field],
+    )
+    .unwrap();
+    overwrite_payload(&holder, 100, &json!({"age": 40}).into(), &[without_field]).unwrap();
+
+    // Points without the field and missing points are skipped
+    let missing_point: PointIdType = 999.into();
+    copy_payload_field(
+        &holder,
+        101,
+        &"name".parse().unwrap(),
+        &"author.name".parse().unwrap(),
+        true,
+        &[with_field, without_field, missing_point],
+    )
+    .unwrap();
+
+    let stored = |point_id| {
+        holder
+            .get(sid)
+            .unwrap()
+            .get()
+            .read()
+            .payload(point_id)
+            .unwrap()
+    };
+    let expected: Payload = json!({"age": 30, "author": {"name": "John"}}).into();
+    assert_eq!(stored(with_field), expected);
+    let expected: Payload = json!({"age": 40}).into();
+    assert_eq!(stored(without_field), expected);
+}
+
+#[test]
+fn test_upsert_points_versioned() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let replica_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+
+    let mut holder = SegmentHolder::default();
+    let sid = holder.add_new(build_segment_1(dir.path()));
+    let segments = RwLock::new(holder);
+
+    let point_id: PointIdType = 1.into();
+    let version = |segments: &RwLock<SegmentHolder>, sid: SegmentId| {
+        let segment = segments.read().get(sid).unwrap().get();
+        let segment = segment.read();
+        segment.point_revision(point_id).unwrap()
+    };
+
+    let vector_a = vec![0.0, 0.0, 0.0, 0.0];
+    let vector_b = vec![1.0, 0.0, 1.0, 1.0];
+    let versioned_upsert = |vector: &Vec<f32>, expected_version: Option<u64>, timestamp| {
+        PointOperations::UpsertPointsVersioned(VersionedInsertOperationInternal {
+            points_op: vec![PointStruct {
+                id: point_id,
+                vector: VectorStructInternal::from(vector.clone()).into(),
+                payload: None,
+                vector_metadata: None,
+            }]
+            .into(),
+            expected_versions: expected_version
+                .map(|version| PointVersion {
+                    id: point_id,
+                    version,
+                })
+                .into_iter()
+                .collect(),
+            timestamp: Some(timestamp),
+            versions: Vec::new(),
+        })
+    };
+
+    // Upsert with the current version is applied, the version is derived from the timestamp
+    let read_version = version(&segments, sid);
+    process_point_operation(
+        &segments,
+        100,
+        versioned_upsert(&vector_a, Some(read_version), 10),
+    )
+    .unwrap();
+    let a_version = version(&segments, sid);
+    assert_eq!(a_version, 10);
+
+    // Point was changed since it was read, upsert with the stale version is rejected
+    let err = process_point_operation(
+        &segments,
+        101,
+        versioned_upsert(&vector_a, Some(read_version), 11),
+    )
+    .unwrap_err();
+    assert!(err.is_pre_condition_failed());
+    assert_eq!(version(&segments, sid), a_version);
+
+    // Changing the point back and forth gives a new version each time, even within the same
+    // timestamp and with the same content as before
+    process_point_operation(
+        &segments,
+        102,
+        versioned_upsert(&vector_b, Some(a_version), 10),
+    )
+    .unwrap();
+    let b_version = version(&segments, sid);
+    process_point_operation(
+        &segments,
+        103,
+        versioned_upsert(&vector_a, Some(b_version), 10),
+    )
+    .unwrap();
+    let a_again_version = version(&segments, sid);
+    assert!(a_version < b_version && b_version < a_again_version);
+    let err = process_point_operation(
+        &segments,
+        104,
+        versioned_upsert(&vector_a, Some(a_version), 12),
+    )
+    .unwrap_err();
+    assert!(err.is_pre_condition_failed());
+
+    // Another replica applies the same updates under different operation numbers,
+    // the versions don't depend on them
+    let mut replica_holder = SegmentHolder::default();
+    let replica_sid = replica_holder.add_new(build_segment_1(replica_dir.path()));
+    let replica_segments = RwLock::new(replica_holder);
+    for (op_num, (vector, expected_version)) in [
+        (&vector_a, read_version),
+        (&vector_b, a_version),
+        (&vector_a, b_version),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        process_point_operation(
+            &replica_segments,
+            7 + op_num as u64,
+            versioned_upsert(vector, Some(expected_version), 10),
+        )
+        .unwrap();
+    }
+    assert_eq!(version(&replica_segments, replica_sid), a_again_version);
+
+    // Deleted point has no version
+    delete_points(&segments.read(), 105, &[point_id]).unwrap();
+    let err = process_point_operation(
+        &segments,
+        106,
+        versioned_upsert(&vector_a, Some(a_again_version), 12),
+    )
+    .unwrap_err();
+    assert!(err.is_pre_condition_failed());
+
+    // Reinserted point with the same content gets a new version
+    process_point_operation(&segments, 107, versioned_upsert(&vector_a, None, 20)).unwrap();
+    let reinserted_version = version(&segments, sid);
+    assert!(reinserted_version > a_again_version);
+    let err = process_point_operation(
+        &segments,
+        108,
+        versioned_upsert(&vector_a, Some(a_again_version), 21),
+    )
+    .unwrap_err();
+    assert!(err.is_pre_condition_failed());
+    process_point_operation(
+        &segments,
+        109,
+        versioned_upsert(&vector_b, Some(reinserted_version), 21),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_upsert_points_with_ttl() {
+    let point = |id: u64, payload: Option<serde_json::Value>| PointStruct {
+        id: id.into(),
+        vector: VectorStructInternal::from(vec![0.0, 0.0, 0.0, 0.0]).into(),
+        payload: payload.map(Payload::from),
+        vector_metadata: None,
+    };
+    let upsert_with_ttl = |set_on_insert, refresh_on_update| {
+        PointOperations::UpsertPointsWithTtl(TtlInsertOperationInternal {
+            upsert: VersionedInsertOperationInternal {
+                points_op: vec![
+                    // New point
+                    point(10, None),
+                    // Existing point with a timestamp
+                    point(2, Some(json!({"color": "red"}))),
+                    // Existing point without a timestamp
+                    point(3, None),
+                    // Explicit timestamp
+                    point(4, Some(json!({"created_at": 10}))),
+                ]
+                .into(),
+                expected_versions: Vec::new(),
+                timestamp: None,
+                versions: Vec::new(),
+            },
+            stamp: TtlStamp {
+                key: "created_at".to_string(),
+                timestamp: 1_000,
+                set_on_insert,
+                refresh_on_update,
+            },
+        })
+    };
+    let timestamps = |set_on_insert, refresh_on_update| {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let mut holder = SegmentHolder::default();
+        let sid = holder.add_new(build_segment_1(dir.path()));
+        let segments = RwLock::new(holder);
+
+        upsert_points(
+            &segments.read(),
+            100,
+            &[point(2, Some(json!({"created_at": 500})))],
+        )
+        .unwrap();
+        process_point_operation(
+            &segments,
+            101,
+            upsert_with_ttl(set_on_insert, refresh_on_update),
+        )
+        .unwrap();
+
+        let segment = segments.read().get(sid).unwrap().get();
+        let segment = segment.read();
+        [10, 2, 3, 4].map(|id: u64| segment.payload(id.into()).unwrap().0.remove("created_at"))
+    };
+
+    assert_eq!(
+        timestamps(true, false),
+        [Some(json!(1_000)), Some(json!(500)), None, Some(json!(10))],
+    );
+    assert_eq!(
+        timestamps(false, true),
+        [
+            None,
+            Some(json!(1_000)),
+            Some(json!(1_000)),
+            Some(json!(10))
+        ],
+    );
+    assert_eq!(
+        timestamps(true, true),
+        [
+            Some(json!(1_000)),
+            Some(json!(1_000)),
+            Some(json!(1_000)),
+            Some(json!(10))
+        ],
+    );
+}
+
+#[test]
+fn test_update_vectors_in_indexed_segment() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let temp_dir = Builder::new().prefix("segment_temp_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+
+    let plain_segment = random_segment(dir.path(), 100, 200, 4);
+    let point_id = plain_segment.iter_points().next().unwrap();
+    let payload = plain_segment.payload(point_id).unwrap();
+    let old_vector = plain_segment
+        .vector(DEFAULT_VECTOR_NAME, point_id)
+        .unwrap()
+        .unwrap();
+
+    let mut config = plain_segment.segment_config.clone();
+    let vector_config = config.vector_data.get_mut(DEFAULT_VECTOR_NAME).unwrap();
+    vector_config.index = Indexes::Hnsw(HnswConfig {
+        m: 8,
+        ef_construct: 16,
+        full_scan_threshold: 1,
+        ..Default::default()
+    });
+    vector_config.storage_type = VectorStorageType::Mmap;
+
+    let mut builder = SegmentBuilder::new(dir.path(), temp_dir.path(), &config).unwrap();
+    builder.update(&[&plain_segment], &stopped).unwrap();
+    let indexed_segment = builder.build(CpuPermit::dummy(1), &stopped).unwrap();
+    assert!(!indexed_segment.is_appendable());
+
+    let mut holder = SegmentHolder::default();
+    let indexed_id = holder.add_new(indexed_segment);
+    let appendable_id = holder.add_new(empty_segment(dir.path()));
+
+    let new_vector = vec![-1.0, -1.0, -1.0, -1.0];
+    let points = vec![PointVectors {
+        id: point_id,
+        vector: VectorStruct::Single(new_vector.clone()),
+    }];
+    assert_eq!(update_vectors(&holder, 101, points).unwrap(), 1);
+
+    let search = |segment: &Segment, vector: Vector| {
+        segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &QueryVector::Nearest(vector),
+                &WithPayload::default(),
+                &false.into(),
+                None,
+                10,
+                Some(&SearchParams {
+                    exact: false,
+                    ..Default::default()
+                }),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|point| point.id)
+            .collect_vec()
+    };
+
+    // The HNSW graph can't be changed in place, so the point is deleted from the indexed segment,
+    // and its old vector doesn't lead to it anymore
+    let LockedSegment::Original(indexed_segment) = holder.get(indexed_id).unwrap() else {
+        panic!("wrong type");
+    };
+    let indexed_segment = indexed_segment.read();
+    assert!(!indexed_segment.has_point(point_id));
+    assert!(!search(&indexed_segment, old_vector).contains(&point_id));
+
+    // The point is moved with its payload, and is found by the new vector
+    let LockedSegment::Original(appendable_segment) = holder.get(appendable_id).unwrap() else {
+        panic!("wrong type");
+    };
+    let appendable_segment = appendable_segment.read();
+    assert_eq!(appendable_segment.payload(point_id).unwrap(), payload);
+    assert_eq!(
+        appendable_segment
+            .vector(DEFAULT_VECTOR_NAME, point_id)
+            .unwrap(),
+        Some(new_vector.clone().into()),
+    );
+    assert_eq!(
+        search(&appendable_segment, new_vector.into()).first(),
+        Some(&point_id),
+    );
+}
@@ -12,6 +12,8 @@ fn score_point(id: usize, score: ScoreType, version: SeqNumberType) -> ScoredPoi
         vector: None,
         shard_key: None,
         order_value: None,
+        distance: None,
+        provenance: None,
     }
 }
 
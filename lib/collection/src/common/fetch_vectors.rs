@@ -36,6 +36,7 @@ pub async fn retrieve_points(
                 ids,
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vector_names),
+                with_version: false,
             },
             read_consistency,
             shard_selector,
@@ -230,7 +231,7 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
                     CollectionRefHolder::Ref(collection),
                     points,
                     vector_names,
-                    read_consistency,
+                    read_consistency.clone(),
                     &shard_selector,
                     timeout,
                 )),
@@ -242,7 +243,7 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
                                 CollectionRefHolder::Guard(other_collection),
                                 points,
                                 vector_names,
-                                read_consistency,
+                                read_consistency.clone(),
                                 &shard_selector,
                                 timeout,
                             ))
@@ -274,39 +275,54 @@ impl<'coll_name> ReferencedPoints<'coll_name> {
     }
 }
 
+fn missing_vector_error(point_id: PointIdType, vector_name: &str) -> CollectionError {
+    CollectionError::bad_input(format!(
+        "Point {point_id} has no vector named '{vector_name}' to be used as example"
+    ))
+}
+
+/// Convert examples into vectors, resolving point ids to their stored vectors.
+///
+/// Fails if a referenced point has no vector with the given name.
 pub fn convert_to_vectors_owned(
     examples: Vec<RecommendExample>,
     all_vectors_records_map: &ReferencedVectors,
     vector_name: &str,
     collection_name: Option<&String>,
-) -> Vec<Vector> {
+) -> CollectionResult<Vec<Vector>> {
     examples
         .into_iter()
-        .filter_map(|example| match example {
-            RecommendExample::Dense(vector) => Some(vector.into()),
-            RecommendExample::Sparse(vector) => Some(vector.into()),
+        .map(|example| match example {
+            RecommendExample::Dense(vector) => Ok(vector.into()),
+            RecommendExample::Sparse(vector) => Ok(vector.into()),
             RecommendExample::PointId(vid) => {
                 let rec = all_vectors_records_map.get(collection_name, vid).unwrap();
-                rec.get_vector_by_name(vector_name).map(|v| v.to_owned())
+                rec.get_vector_by_name(vector_name)
+                    .map(|v| v.to_owned())
+                    .ok_or_else(|| missing_vector_error(vid, vector_name))
             }
         })
         .collect()
 }
 
+/// Same as [`convert_to_vectors_owned`], but borrows the vectors.
 pub fn convert_to_vectors<'a>(
     examples: impl Iterator<Item = &'a RecommendExample> + 'a,
     all_vectors_records_map: &'a ReferencedVectors,
     vector_name: &'a str,
     collection_name: Option<&'a String>,
-) -> impl Iterator<Item = VectorRef<'a>> + 'a {
-    examples.filter_map(move |example| match example {
-        RecommendExample::Dense(vector) => Some(vector.into()),
-        RecommendExample::Sparse(vector) => Some(vector.into()),
-        RecommendExample::PointId(vid) => {
-            let rec = all_vectors_records_map.get(collection_name, *vid).unwrap();
-            rec.get_vector_by_name(vector_name)
-        }
-    })
+) -> CollectionResult<Vec<VectorRef<'a>>> {
+    examples
+        .map(move |example| match example {
+            RecommendExample::Dense(vector) => Ok(vector.into()),
+            RecommendExample::Sparse(vector) => Ok(vector.into()),
+            RecommendExample::PointId(vid) => {
+                let rec = all_vectors_records_map.get(collection_name, *vid).unwrap();
+                rec.get_vector_by_name(vector_name)
+                    .ok_or_else(|| missing_vector_error(*vid, vector_name))
+            }
+        })
+        .collect()
 }
 
 pub async fn resolve_referenced_vectors_batch<'a, 'b, F, Fut, Req: RetrieveRequest>(
@@ -350,7 +366,7 @@ where
             }
             let fetch = referenced_points.fetch_vectors(
                 collection,
-                read_consistency,
+                read_consistency.clone(),
                 &collection_by_name,
                 shard_selector,
                 timeout,
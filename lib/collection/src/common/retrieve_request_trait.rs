@@ -70,14 +70,12 @@ impl RetrieveRequest for DiscoverRequestInternal {
             }
         }
 
-        if let Some(context) = &self.context {
-            for pair in context {
-                if let Some(pos_id) = pair.positive.as_point_id() {
-                    res.push(pos_id);
-                }
-                if let Some(neg_id) = pair.negative.as_point_id() {
-                    res.push(neg_id);
-                }
+        for (pair, _weight) in self.weighted_pairs() {
+            if let Some(pos_id) = pair.positive.as_point_id() {
+                res.push(pos_id);
+            }
+            if let Some(neg_id) = pair.negative.as_point_id() {
+                res.push(neg_id);
             }
         }
 
@@ -2,6 +2,8 @@ use std::io;
 use std::path::Path;
 
 use bytes::BytesMut;
+use itertools::Itertools as _;
+use serde_json::Value;
 use sha2::{Digest, Sha256};
 use tokio::io::AsyncReadExt;
 
@@ -38,6 +40,44 @@ pub fn hashes_equal(a: &str, b: &str) -> bool {
     )
 }
 
+/// Feed a JSON value into the hasher, with object keys sorted
+///
+/// Unlike hashing the serialized string, the hash doesn't depend on the order of object keys.
+pub fn hash_json_value(value: &Value, hasher: &mut Sha256) {
+    match value {
+        Value::Null => hasher.update([0]),
+        Value::Bool(value) => hasher.update([1, u8::from(*value)]),
+        Value::Number(number) => {
+            hasher.update([2]);
+            hash_str(&number.to_string(), hasher);
+        }
+        Value::String(string) => {
+            hasher.update([3]);
+            hash_str(string, hasher);
+        }
+        Value::Array(array) => {
+            hasher.update([4]);
+            hasher.update((array.len() as u64).to_le_bytes());
+            for item in array {
+                hash_json_value(item, hasher);
+            }
+        }
+        Value::Object(object) => {
+            hasher.update([5]);
+            hasher.update((object.len() as u64).to_le_bytes());
+            for (key, item) in object.iter().sorted_unstable_by_key(|(key, _)| *key) {
+                hash_str(key, hasher);
+                hash_json_value(item, hasher);
+            }
+        }
+    }
+}
+
+fn hash_str(string: &str, hasher: &mut Sha256) {
+    hasher.update((string.len() as u64).to_le_bytes());
+    hasher.update(string.as_bytes());
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1,3 +1,4 @@
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -22,6 +23,18 @@ use crate::shards::shard_holder::LockedShardHolder;
 pub struct SnapShotsConfig {
     pub snapshots_storage: SnapshotsStorageConfig,
     pub s3_config: Option<S3Config>,
+    /// Create snapshots of all collections periodically, if set
+    #[serde(default)]
+    pub schedule: Option<SnapshotScheduleConfig>,
+}
+
+#[derive(Clone, Deserialize, Debug)]
+pub struct SnapshotScheduleConfig {
+    /// Interval between snapshots of a collection, in seconds
+    pub interval_sec: NonZeroU64,
+    /// Number of scheduled snapshots to keep per collection, older ones are deleted.
+    /// Snapshots created through the API are never deleted by the schedule.
+    pub retention: NonZeroUsize,
 }
 
 #[derive(Clone, Debug, Default, Deserialize)]
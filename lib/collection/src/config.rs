@@ -1,3 +1,4 @@
+use std::borrow::Cow;
 use std::collections::{BTreeMap, HashMap};
 use std::fs::File;
 use std::io::{Read, Write};
@@ -10,23 +11,27 @@ use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
 use segment::index::sparse_index::sparse_index_config::{SparseIndexConfig, SparseIndexType};
+use segment::json_path::JsonPath;
 use segment::types::{
     default_replication_factor_const, default_shard_number_const,
-    default_write_consistency_factor_const, Distance, HnswConfig, Indexes, PayloadStorageType,
-    QuantizationConfig, SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype,
-    VectorStorageType,
+    default_write_consistency_factor_const, Distance, HnswConfig, Indexes,
+    PayloadCompressionConfig, PayloadStorageType, QuantizationConfig, SearchParams,
+    SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype, VectorStorageType,
 };
 use serde::{Deserialize, Serialize};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 use wal::WalOptions;
 
-use crate::operations::config_diff::{DiffConfig, QuantizationConfigDiff, StrictModeConfig};
+use crate::operations::config_diff::{
+    DiffConfig, OptimizersConfigDiff, QuantizationConfigDiff, StrictModeConfig,
+};
 use crate::operations::types::{
     CollectionError, CollectionResult, SparseVectorParams, SparseVectorsConfig, VectorParams,
     VectorParamsDiff, VectorsConfig, VectorsConfigDiff,
 };
 use crate::operations::validation;
 use crate::optimizers_builder::OptimizersConfig;
+use crate::shards::shard::ShardId;
 
 pub const COLLECTION_CONFIG_FILE: &str = "config.json";
 
@@ -102,6 +107,11 @@ pub struct CollectionParams {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default = "default_on_disk_payload")]
     pub on_disk_payload: bool,
+    /// If set - payloads are compressed with zstd before being stored.
+    /// Payloads too small to benefit from compression are stored as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub payload_compression: Option<PayloadCompressionConfig>,
     /// Configuration of the sparse vector storage
     #[serde(default, skip_serializing_if = "Option::is_none")]
     #[validate(nested)]
@@ -128,6 +138,7 @@ impl Anonymize for CollectionParams {
             write_consistency_factor: self.write_consistency_factor,
             read_fan_out_factor: self.read_fan_out_factor,
             on_disk_payload: self.on_disk_payload,
+            payload_compression: self.payload_compression,
             sparse_vectors: self.sparse_vectors.anonymize(),
         }
     }
@@ -149,6 +160,63 @@ const fn default_on_disk_payload() -> bool {
     false
 }
 
+pub const fn default_ttl_set_on_insert() -> bool {
+    true
+}
+
+pub const fn default_ttl_check_interval_sec() -> u64 {
+    60
+}
+
+pub const fn default_ttl_delete_batch_size() -> usize {
+    1_000
+}
+
+/// Expiry of points after a time-to-live, for cache-like collections.
+///
+/// Expired points are deleted by a background task, so they may still be returned for up to
+/// `check_interval_sec` after their expiry.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq, Hash)]
+pub struct TtlConfig {
+    /// Time-to-live of points, in seconds
+    #[validate(range(min = 1))]
+    pub ttl_sec: u64,
+    /// Top-level payload field with a unix timestamp in seconds, from which the TTL of a point
+    /// is counted. Points without this field never expire.
+    #[validate(custom(function = "validate_ttl_timestamp_field"))]
+    pub timestamp_field: JsonPath,
+    /// Set the timestamp field to the current time when inserting points which don't have it.
+    /// Default: true
+    #[serde(default = "default_ttl_set_on_insert")]
+    pub set_on_insert: bool,
+    /// Set the timestamp field to the current time on upserts and payload updates of existing
+    /// points which don't set it explicitly, which resets their TTL. Otherwise existing points
+    /// keep their timestamp. Default: false
+    #[serde(default)]
+    pub refresh_on_update: bool,
+    /// How often to look for expired points, in seconds. Default: 60
+    #[serde(default = "default_ttl_check_interval_sec")]
+    #[validate(range(min = 1))]
+    pub check_interval_sec: u64,
+    /// Max number of expired points deleted by a single operation. Default: 1000
+    #[serde(default = "default_ttl_delete_batch_size")]
+    #[validate(range(min = 1))]
+    pub delete_batch_size: usize,
+}
+
+/// Timestamps are set by replacing the value of a top-level key, which can't be done for an
+/// arbitrary path.
+fn validate_ttl_timestamp_field(field: &JsonPath) -> Result<(), ValidationError> {
+    if field.rest.is_empty() {
+        Ok(())
+    } else {
+        let mut err = ValidationError::new("top_level_key");
+        err.add_param(Cow::from("field"), &field.to_string());
+        err.message = Some(Cow::from("must be a top-level payload key"));
+        Err(err)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 pub struct CollectionConfig {
     #[validate(nested)]
@@ -162,9 +230,15 @@ pub struct CollectionConfig {
     #[serde(default)]
     pub quantization_config: Option<QuantizationConfig>,
     #[serde(default)]
-    #[schemars(skip)]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub strict_mode_config: Option<StrictModeConfig>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub ttl_config: Option<TtlConfig>,
+    /// Overrides of the optimizer config for individual shards, applied on top of
+    /// `optimizer_config`. Useful if shards are stored on disks with different performance.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub shard_optimizer_config: BTreeMap<ShardId, OptimizersConfigDiff>,
 }
 
 impl CollectionConfig {
@@ -196,6 +270,19 @@ impl CollectionConfig {
         config_path.exists()
     }
 
+    /// Optimizer config of the given shard, which is the given config with the overrides of this
+    /// shard applied
+    pub fn optimizer_config_for_shard(
+        &self,
+        shard_id: ShardId,
+        optimizer_config: &OptimizersConfig,
+    ) -> CollectionResult<OptimizersConfig> {
+        match self.shard_optimizer_config.get(&shard_id) {
+            Some(diff) => diff.clone().update(optimizer_config),
+            None => Ok(optimizer_config.clone()),
+        }
+    }
+
     pub fn validate_and_warn(&self) {
         if let Err(ref errs) = self.validate() {
             validation::warn_validation_errors("Collection configuration file", errs);
@@ -214,6 +301,7 @@ impl CollectionParams {
             read_fan_out_factor: None,
             on_disk_payload: default_on_disk_payload(),
             sparse_vectors: None,
+            payload_compression: None,
         }
     }
 
@@ -241,6 +329,31 @@ impl CollectionParams {
         }
     }
 
+    /// Distance a search on `vector_name` is scored with, which `params` may override.
+    ///
+    /// The distance can only be overridden for dense vectors, not for sparse or multivectors.
+    pub fn get_search_distance(
+        &self,
+        vector_name: &str,
+        params: Option<&SearchParams>,
+    ) -> CollectionResult<Distance> {
+        let Some(distance) = params.and_then(|params| params.distance) else {
+            return self.get_distance(vector_name);
+        };
+        match self.vectors.get_params(vector_name) {
+            Some(params) if params.multivector_config.is_none() => Ok(distance),
+            Some(_) => Err(CollectionError::bad_input(format!(
+                "Distance can't be overridden for multivector {vector_name}"
+            ))),
+            None => {
+                self.get_distance(vector_name)?;
+                Err(CollectionError::bad_input(format!(
+                    "Distance can't be overridden for sparse vector {vector_name}"
+                )))
+            }
+        }
+    }
+
     fn get_vector_params_mut(&mut self, vector_name: &str) -> CollectionResult<&mut VectorParams> {
         self.vectors
             .get_params_mut(vector_name)
@@ -317,6 +430,35 @@ impl CollectionParams {
         Ok(())
     }
 
+    /// Add new named vectors to the collection
+    pub fn add_vectors(
+        &mut self,
+        new_vectors: &BTreeMap<String, VectorParams>,
+    ) -> CollectionResult<()> {
+        for (vector_name, params) in new_vectors {
+            params.validate()?;
+            if self.vectors.get_params(vector_name).is_some()
+                || self.get_sparse_vector_params_opt(vector_name).is_some()
+            {
+                return Err(CollectionError::bad_input(format!(
+                    "Vector {vector_name} already exists"
+                )));
+            }
+        }
+
+        let VectorsConfig::Multi(vectors) = &mut self.vectors else {
+            return Err(CollectionError::bad_input(
+                "Named vectors can't be added to a collection with a single unnamed vector",
+            ));
+        };
+        vectors.extend(
+            new_vectors
+                .iter()
+                .map(|(vector_name, params)| (vector_name.clone(), params.clone())),
+        );
+        Ok(())
+    }
+
     /// Update collection vectors from the given update vectors config
     pub fn update_sparse_vectors_from_other(
         &mut self,
@@ -324,7 +466,19 @@ impl CollectionParams {
     ) -> CollectionResult<()> {
         for (vector_name, update_params) in update_vectors.0.iter() {
             let sparse_vector_params = self.get_sparse_vector_params_mut(vector_name)?;
-            let SparseVectorParams { index, modifier } = update_params.clone();
+            let SparseVectorParams {
+                index,
+                modifier,
+                distance,
+            } = update_params.clone();
+
+            if let Some(distance) = distance {
+                if distance != sparse_vector_params.distance.unwrap_or_default() {
+                    return Err(CollectionError::bad_input(format!(
+                        "Distance of sparse vector {vector_name} can't be changed",
+                    )));
+                }
+            }
 
             if let Some(modifier) = modifier {
                 sparse_vector_params.modifier = Some(modifier);
@@ -397,6 +551,7 @@ impl CollectionParams {
                                     .and_then(|index| index.datatype)
                                     .map(VectorStorageDatatype::from),
                             },
+                            distance: params.distance,
                         },
                     ))
                 })
@@ -1,7 +1,7 @@
 use std::time::Duration;
 
+use cancel::CancellationToken;
 use futures::Future;
-use itertools::Itertools;
 use segment::data_types::vectors::NamedQuery;
 use segment::types::{Condition, Filter, HasIdCondition, ScoredPoint};
 use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery};
@@ -51,30 +51,31 @@ fn discovery_into_core_search(
         all_vectors_records_map,
         &lookup_vector_name,
         lookup_collection_name,
-    )
+    )?
+    .into_iter()
     .next()
     .map(|v| v.to_owned());
 
     let context_pairs = request
-        .context
-        .iter()
-        .flatten()
-        .map(|pair| {
+        .weighted_pairs()
+        .map(|(pair, weight)| {
             let mut vector_pair = convert_to_vectors(
                 pair.iter(),
                 all_vectors_records_map,
                 &lookup_vector_name,
                 lookup_collection_name,
-            )
+            )?
+            .into_iter()
             .map(|v| v.to_owned());
 
-            ContextPair {
+            Ok(ContextPair {
                 // SAFETY: we know there are two elements in the iterator
                 positive: vector_pair.next().unwrap(),
                 negative: vector_pair.next().unwrap(),
-            }
+                weight,
+            })
         })
-        .collect_vec();
+        .collect::<CollectionResult<Vec<_>>>()?;
 
     let query: QueryEnum = match (target, context_pairs) {
         // Target with/without pairs => Discovery
@@ -118,6 +119,10 @@ fn discovery_into_core_search(
         with_payload: request.with_payload,
         with_vector: request.with_vector,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: false,
     };
 
     Ok(core_search)
@@ -171,11 +176,7 @@ where
 
     // Validate context_pairs and/or target have value(s)
     request_batch.iter().try_for_each(|(request, _)| {
-        let no_pairs = request.context.is_none()
-            || request
-                .context
-                .as_ref()
-                .is_some_and(|pairs| pairs.is_empty());
+        let no_pairs = request.weighted_pairs().next().is_none();
 
         let no_target = request.target.is_none();
 
@@ -192,7 +193,7 @@ where
         &request_batch,
         collection,
         collection_by_name,
-        read_consistency,
+        read_consistency.clone(),
         timeout,
     )
     .await?;
@@ -226,9 +227,10 @@ where
 
             requests.push(collection.core_search_batch(
                 core_search_batch_request,
-                read_consistency,
+                read_consistency.clone(),
                 shard_selector,
                 timeout,
+                CancellationToken::new(),
             ));
 
             Ok(())
@@ -3,6 +3,7 @@ use std::collections::HashMap;
 use segment::json_path::JsonPath;
 use segment::types::{Filter, PayloadFieldSchema};
 
+use crate::operations::snapshot_ops::SnapshotDescription;
 use crate::shards::CollectionId;
 
 pub struct CollectionDeletedEvent {
@@ -15,6 +16,12 @@ pub struct SlowQueryEvent {
     pub schema: HashMap<JsonPath, PayloadFieldSchema>,
 }
 
+/// A snapshot created on schedule, or the error if it failed
+pub struct ScheduledSnapshotEvent {
+    pub collection_id: CollectionId,
+    pub result: Result<SnapshotDescription, String>,
+}
+
 pub struct IndexCreatedEvent {
     pub collection_id: CollectionId,
     pub field_name: JsonPath,
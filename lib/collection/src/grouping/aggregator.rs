@@ -215,6 +215,8 @@ mod unit_tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
@@ -227,6 +229,8 @@ mod unit_tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
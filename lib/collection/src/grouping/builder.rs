@@ -83,7 +83,7 @@ where
             .into_query_group_request(
                 self.collection,
                 self.collection_by_name.clone(),
-                self.read_consistency,
+                self.read_consistency.clone(),
                 self.shard_selection.clone(),
                 self.timeout,
             )
@@ -92,7 +92,7 @@ where
         let mut groups = group_by(
             core_group_by,
             self.collection,
-            self.read_consistency,
+            self.read_consistency.clone(),
             self.shard_selection.clone(),
             self.timeout,
         )
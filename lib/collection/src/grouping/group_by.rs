@@ -197,6 +197,10 @@ impl From<SearchGroupsRequestInternal> for GroupRequest {
             with_payload,
             with_vector,
             score_threshold,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
         };
 
         GroupRequest {
@@ -244,6 +248,7 @@ impl From<RecommendGroupsRequestInternal> for GroupRequest {
             score_threshold,
             using,
             lookup_from,
+            mmr_lambda: None,
         };
 
         GroupRequest {
@@ -286,6 +291,7 @@ impl From<CollectionQueryGroupsRequest> for GroupRequest {
             with_vector,
             with_payload,
             lookup_from,
+            seed: None,
         };
 
         GroupRequest {
@@ -308,7 +314,17 @@ pub async fn group_by(
 ) -> CollectionResult<Vec<PointGroup>> {
     let start = std::time::Instant::now();
     let collection_params = collection.collection_config.read().await.params.clone();
-    let score_ordering = ScoringQuery::order(request.source.query.as_ref(), &collection_params)?;
+    let score_params = request
+        .source
+        .prefetches
+        .is_empty()
+        .then_some(request.source.params.as_ref())
+        .flatten();
+    let score_ordering = ScoringQuery::order(
+        request.source.query.as_ref(),
+        score_params,
+        &collection_params,
+    )?;
 
     let mut aggregator = GroupsAggregator::new(
         request.groups,
@@ -362,7 +378,7 @@ pub async fn group_by(
         let points = request
             .r#do(
                 collection,
-                read_consistency,
+                read_consistency.clone(),
                 shard_selection.clone(),
                 timeout,
             )
@@ -424,7 +440,7 @@ pub async fn group_by(
             let points = request
                 .r#do(
                     collection,
-                    read_consistency,
+                    read_consistency.clone(),
                     shard_selection.clone(),
                     timeout,
                 )
@@ -555,6 +571,8 @@ mod tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
@@ -7,6 +7,7 @@ pub mod discovery;
 pub mod grouping;
 pub mod hash_ring;
 pub mod lookup;
+pub mod neighbors;
 pub mod operations;
 pub mod optimizers_builder;
 pub mod recommendations;
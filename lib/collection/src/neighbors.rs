@@ -0,0 +1,98 @@
+use std::time::Duration;
+
+use segment::data_types::vectors::{NamedVectorStruct, DEFAULT_VECTOR_NAME};
+use segment::types::{Condition, Filter, HasIdCondition, ScoredPoint};
+
+use crate::collection::Collection;
+use crate::common::fetch_vectors::retrieve_points;
+use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::shard_selector_internal::ShardSelectorInternal;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, NeighborsRequestInternal, UsingVector,
+};
+
+/// Search the nearest neighbors of a stored point, by its stored vector.
+///
+/// The vector of the point is retrieved first, then used as a regular nearest search. Unless
+/// requested otherwise, the point itself is excluded from the results.
+pub async fn neighbors_by_id(
+    request: NeighborsRequestInternal,
+    collection: &Collection,
+    read_consistency: Option<ReadConsistency>,
+    shard_selector: ShardSelectorInternal,
+    timeout: Option<Duration>,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    let NeighborsRequestInternal {
+        id,
+        filter,
+        params,
+        limit,
+        offset,
+        with_payload,
+        with_vector,
+        score_threshold,
+        using,
+        include_self,
+    } = request;
+
+    let vector_name = match using {
+        None => DEFAULT_VECTOR_NAME.to_string(),
+        Some(UsingVector::Name(name)) => name,
+    };
+
+    let records = retrieve_points(
+        collection,
+        vec![id],
+        vec![vector_name.clone()],
+        read_consistency.clone(),
+        &shard_selector,
+        timeout,
+    )
+    .await?;
+
+    let record = records.first().ok_or(CollectionError::PointNotFound {
+        missed_point_id: id,
+    })?;
+
+    let vector = record
+        .get_vector_by_name(&vector_name)
+        .ok_or_else(|| {
+            CollectionError::bad_input(format!(
+                "Point {id} has no vector named '{vector_name}' to search neighbors by"
+            ))
+        })?
+        .to_owned();
+
+    let filter = if include_self.unwrap_or(false) {
+        filter
+    } else {
+        Some(Filter {
+            should: None,
+            min_should: None,
+            must: filter.map(|filter| vec![Condition::Filter(filter)]),
+            must_not: Some(vec![Condition::HasId(HasIdCondition::from(
+                [id].into_iter().collect(),
+            ))]),
+        })
+    };
+
+    let search_request = CoreSearchRequest {
+        query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(vector, vector_name)),
+        filter,
+        params,
+        limit,
+        offset: offset.unwrap_or_default(),
+        with_payload,
+        with_vector,
+        score_threshold,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: false,
+    };
+
+    collection
+        .search(search_request, read_consistency, &shard_selector, timeout)
+        .await
+}
@@ -26,6 +26,12 @@ pub enum ClusterOperations {
     DropShardingKey(DropShardingKeyOperation),
     /// Restart transfer
     RestartTransfer(RestartTransferOperation),
+    /// Change replication factor, create or drop replicas of all shards to match it
+    ChangeReplicationFactor(ChangeReplicationFactorOperation),
+    /// Make an active replica read-only, it keeps serving reads, but no longer confirms writes
+    MakeReplicaReadOnly(MakeReplicaReadOnlyOperation),
+    /// Promote a read-only replica to active, once it caught up with active replicas
+    PromoteReplica(PromoteReplicaOperation),
 
     /// Start resharding
     #[schemars(skip)]
@@ -65,6 +71,13 @@ pub struct RestartTransferOperation {
     pub restart_transfer: RestartTransfer,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangeReplicationFactorOperation {
+    #[validate(nested)]
+    pub change_replication_factor: ChangeReplicationFactor,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct CreateShardingKey {
@@ -99,6 +112,16 @@ pub struct RestartTransfer {
     pub method: ShardTransferMethod,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ChangeReplicationFactor {
+    /// Number of replicas for each shard
+    /// Must not be lower than the write consistency factor of the collection
+    pub replication_factor: NonZeroU32,
+    /// Method for transferring shards to new replicas
+    pub method: Option<ShardTransferMethod>,
+}
+
 impl Validate for ClusterOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
@@ -109,6 +132,9 @@ impl Validate for ClusterOperations {
             ClusterOperations::CreateShardingKey(op) => op.validate(),
             ClusterOperations::DropShardingKey(op) => op.validate(),
             ClusterOperations::RestartTransfer(op) => op.validate(),
+            ClusterOperations::ChangeReplicationFactor(op) => op.validate(),
+            ClusterOperations::MakeReplicaReadOnly(op) => op.validate(),
+            ClusterOperations::PromoteReplica(op) => op.validate(),
             ClusterOperations::StartResharding(op) => op.validate(),
             ClusterOperations::FinishMigratingPoints(op) => op.validate(),
             ClusterOperations::CommitReadHashRing(op) => op.validate(),
@@ -140,6 +166,20 @@ pub struct DropReplicaOperation {
     pub drop_replica: Replica,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct MakeReplicaReadOnlyOperation {
+    #[validate(nested)]
+    pub make_replica_read_only: Replica,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct PromoteReplicaOperation {
+    #[validate(nested)]
+    pub promote_replica: Replica,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct AbortTransferOperation {
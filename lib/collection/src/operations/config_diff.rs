@@ -3,11 +3,13 @@ use std::num::NonZeroU32;
 
 use merge::Merge;
 use schemars::JsonSchema;
-use segment::types::{BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization};
+use segment::types::{
+    validate_hnsw_m0, BinaryQuantization, HnswConfig, ProductQuantization, ScalarQuantization,
+};
 use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
-use validator::{Validate, ValidationErrors};
+use validator::{Validate, ValidationError, ValidationErrors};
 
 use crate::config::{CollectionParams, WalConfig};
 use crate::operations::types::CollectionResult;
@@ -38,25 +40,17 @@ pub trait DiffConfig<T: DeserializeOwned + Serialize> {
     }
 }
 
-#[derive(
-    Debug,
-    Default,
-    Deserialize,
-    Serialize,
-    JsonSchema,
-    Validate,
-    Copy,
-    Clone,
-    PartialEq,
-    Eq,
-    Merge,
-    Hash,
-)]
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, Merge)]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_hnsw_config_diff_m0"))]
 pub struct HnswConfigDiff {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub m: Option<usize>,
+    /// Number of edges per node on the base layer (level 0) of the index graph, which contains all
+    /// points. Must not be smaller than `m`. If not set, `2 * m` will be used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub m0: Option<usize>,
     /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build the index.
     #[validate(range(min = 4))]
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -84,9 +78,77 @@ pub struct HnswConfigDiff {
     /// Custom M param for additional payload-aware HNSW links. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub payload_m: Option<usize>,
+    /// Level generation multiplier (mL) of the HNSW graph. Larger values produce more layers.
+    /// If not set, the standard `1 / ln(M)` will be used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 10.0))]
+    pub level_multiplier: Option<f64>,
+    /// Build the graph in a single thread, with a fixed seed, so that the same data always produces
+    /// the same index. Building is slower, `max_indexing_threads` is not used. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deterministic_build: Option<bool>,
 }
 
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
+impl Hash for HnswConfigDiff {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        let Self {
+            m,
+            m0,
+            ef_construct,
+            full_scan_threshold,
+            max_indexing_threads,
+            on_disk,
+            payload_m,
+            level_multiplier,
+            deterministic_build,
+        } = self;
+
+        m.hash(state);
+        m0.hash(state);
+        ef_construct.hash(state);
+        full_scan_threshold.hash(state);
+        max_indexing_threads.hash(state);
+        on_disk.hash(state);
+        payload_m.hash(state);
+        level_multiplier.map(|i| i.to_le_bytes()).hash(state);
+        deterministic_build.hash(state);
+    }
+}
+
+impl PartialEq for HnswConfigDiff {
+    fn eq(&self, other: &Self) -> bool {
+        let Self {
+            m,
+            m0,
+            ef_construct,
+            full_scan_threshold,
+            max_indexing_threads,
+            on_disk,
+            payload_m,
+            level_multiplier,
+            deterministic_build,
+        } = self;
+
+        *m == other.m
+            && *m0 == other.m0
+            && *ef_construct == other.ef_construct
+            && *full_scan_threshold == other.full_scan_threshold
+            && *max_indexing_threads == other.max_indexing_threads
+            && *on_disk == other.on_disk
+            && *payload_m == other.payload_m
+            && level_multiplier.map(|i| i.to_le_bytes())
+                == other.level_multiplier.map(|i| i.to_le_bytes())
+            && *deterministic_build == other.deterministic_build
+    }
+}
+
+impl Eq for HnswConfigDiff {}
+
+fn validate_hnsw_config_diff_m0(diff: &HnswConfigDiff) -> Result<(), ValidationError> {
+    validate_hnsw_m0(diff.m, diff.m0)
+}
+
+#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, Merge)]
 pub struct StrictModeConfig {
     // Global
     /// Whether strict mode is enabled for a collection or not.
@@ -98,6 +160,11 @@ pub struct StrictModeConfig {
     #[validate(range(min = 1))]
     pub max_query_limit: Option<usize>,
 
+    /// Reduce `limit` parameters above `max_query_limit` to the maximum, instead of rejecting the
+    /// request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub clamp_query_limit: Option<bool>,
+
     /// Max allowed `timeout` parameter.
     #[serde(skip_serializing_if = "Option::is_none")]
     #[validate(range(min = 1))]
@@ -123,6 +190,13 @@ pub struct StrictModeConfig {
     /// Max oversampling value allowed in search.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub search_max_oversampling: Option<f64>,
+
+    // Scroll
+    /// Max `limit` of scroll requests without a filter. Larger limits, including the default
+    /// one, are reduced to it instead of rejecting the request.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
+    pub scroll_max_limit: Option<usize>,
 }
 
 impl Hash for StrictModeConfig {
@@ -130,22 +204,26 @@ impl Hash for StrictModeConfig {
         let Self {
             enabled,
             max_query_limit,
+            clamp_query_limit,
             max_timeout,
             unindexed_filtering_retrieve,
             unindexed_filtering_update,
             search_max_hnsw_ef,
             search_allow_exact,
             search_max_oversampling,
+            scroll_max_limit,
         } = self;
 
         enabled.hash(state);
         max_query_limit.hash(state);
+        clamp_query_limit.hash(state);
         max_timeout.hash(state);
         unindexed_filtering_retrieve.hash(state);
         unindexed_filtering_update.hash(state);
         search_max_hnsw_ef.hash(state);
         search_allow_exact.hash(state);
         search_max_oversampling.map(|i| i.to_le_bytes()).hash(state);
+        scroll_max_limit.hash(state);
     }
 }
 
@@ -156,16 +234,19 @@ impl PartialEq for StrictModeConfig {
         let Self {
             enabled,
             max_query_limit,
+            clamp_query_limit,
             max_timeout,
             unindexed_filtering_retrieve,
             unindexed_filtering_update,
             search_max_hnsw_ef,
             search_allow_exact,
             search_max_oversampling,
+            scroll_max_limit,
         } = self;
 
         *enabled == other.enabled
             && *max_query_limit == other.max_query_limit
+            && *clamp_query_limit == other.clamp_query_limit
             && *max_timeout == other.max_timeout
             && *unindexed_filtering_retrieve == other.unindexed_filtering_retrieve
             && *unindexed_filtering_update == other.unindexed_filtering_update
@@ -173,6 +254,7 @@ impl PartialEq for StrictModeConfig {
             && *search_allow_exact == other.search_allow_exact
             && search_max_oversampling.map(|i| i.to_le_bytes())
                 == other.search_max_oversampling.map(|i| i.to_le_bytes())
+            && *scroll_max_limit == other.scroll_max_limit
     }
 }
 
@@ -207,6 +289,13 @@ pub struct CollectionParamsDiff {
 pub struct OptimizersConfigDiff {
     /// The minimal fraction of deleted vectors in a segment, required to perform segment optimization
     pub deleted_threshold: Option<f64>,
+    /// The minimal fraction of points removed from the payload index of a field in a segment,
+    /// required to perform segment optimization. Removed points are kept in payload indexes of
+    /// optimized segments until they are optimized again, which slows down filtering.
+    /// If not set, `deleted_threshold` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub payload_index_deleted_threshold: Option<f64>,
     /// The minimal number of vectors in a segment, required to perform segment optimization
     pub vacuum_min_vector_number: Option<usize>,
     /// Target amount of segments optimizer will try to keep.
@@ -248,23 +337,65 @@ pub struct OptimizersConfigDiff {
     pub indexing_threshold: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: Option<u64>,
+    /// Minimal size (in kilobytes) of writes to buffer in appendable segments before they are flushed.
+    /// Small frequent flushes fragment the data into many small segments on ingest.
+    /// Writes are buffered in memory, and covered by the WAL, until this size is reached or
+    /// `max_flush_delay_sec` has passed. Buffered writes are always flushed on shutdown.
+    /// If not set, segments are flushed every `flush_interval_sec`.
+    ///
+    /// Note: 1Kb = 1 vector of size 256
+    #[serde(alias = "min_segment_size_kb")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_segment_size: Option<usize>,
+    /// Maximum time (in seconds) writes are buffered for before they are flushed, when
+    /// `min_segment_size` is set.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_flush_delay_sec: Option<u64>,
     /// Max number of threads (jobs) for running optimizations per shard.
     /// Note: each optimization job will also use `max_indexing_threads` threads by itself for index building.
     /// If null - have no limit and choose dynamically to saturate CPU.
     /// If 0 - no optimization threads, optimizations will be disabled.
     pub max_optimization_threads: Option<usize>,
+    /// Store exact duplicate vectors only once in optimized segments with on-disk vector storage.
+    /// Does not affect search results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplicate_vectors: Option<bool>,
+    /// If set along with `deduplicate_vectors`, also store vectors whose components all differ by
+    /// at most this value only once.
+    /// Changes scores of merged vectors, so only use it if near-duplicates are interchangeable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub deduplication_epsilon: Option<f64>,
+    /// Pause vector indexing, e.g. for the duration of a bulk load.
+    /// New and optimized segments are kept plain, and searched by full scan, until indexing is resumed.
+    /// Segments which are large enough are indexed in the background once this is unset or set to false.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defer_indexing: Option<bool>,
+    /// Split segments which grew beyond twice `max_segment_size` into several segments no larger
+    /// than `max_segment_size`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_oversized_segments: Option<bool>,
 }
 
 impl std::hash::Hash for OptimizersConfigDiff {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         self.deleted_threshold.map(f64::to_le_bytes).hash(state);
+        self.payload_index_deleted_threshold
+            .map(f64::to_le_bytes)
+            .hash(state);
         self.vacuum_min_vector_number.hash(state);
         self.default_segment_number.hash(state);
         self.max_segment_size.hash(state);
         self.memmap_threshold.hash(state);
         self.indexing_threshold.hash(state);
         self.flush_interval_sec.hash(state);
+        self.min_segment_size.hash(state);
+        self.max_flush_delay_sec.hash(state);
         self.max_optimization_threads.hash(state);
+        self.deduplicate_vectors.hash(state);
+        self.deduplication_epsilon.map(f64::to_le_bytes).hash(state);
+        self.defer_indexing.hash(state);
+        self.split_oversized_segments.hash(state);
     }
 }
 
@@ -272,18 +403,66 @@ impl PartialEq for OptimizersConfigDiff {
     fn eq(&self, other: &Self) -> bool {
         self.deleted_threshold.map(f64::to_le_bytes)
             == other.deleted_threshold.map(f64::to_le_bytes)
+            && self.payload_index_deleted_threshold.map(f64::to_le_bytes)
+                == other.payload_index_deleted_threshold.map(f64::to_le_bytes)
             && self.vacuum_min_vector_number == other.vacuum_min_vector_number
             && self.default_segment_number == other.default_segment_number
             && self.max_segment_size == other.max_segment_size
             && self.memmap_threshold == other.memmap_threshold
             && self.indexing_threshold == other.indexing_threshold
             && self.flush_interval_sec == other.flush_interval_sec
+            && self.min_segment_size == other.min_segment_size
+            && self.max_flush_delay_sec == other.max_flush_delay_sec
             && self.max_optimization_threads == other.max_optimization_threads
+            && self.deduplicate_vectors == other.deduplicate_vectors
+            && self.deduplication_epsilon.map(f64::to_le_bytes)
+                == other.deduplication_epsilon.map(f64::to_le_bytes)
+            && self.defer_indexing == other.defer_indexing
+            && self.split_oversized_segments == other.split_oversized_segments
     }
 }
 
 impl Eq for OptimizersConfigDiff {}
 
+impl OptimizersConfigDiff {
+    /// Whether this diff doesn't change any field
+    pub fn is_empty(&self) -> bool {
+        let Self {
+            deleted_threshold,
+            payload_index_deleted_threshold,
+            vacuum_min_vector_number,
+            default_segment_number,
+            max_segment_size,
+            memmap_threshold,
+            indexing_threshold,
+            flush_interval_sec,
+            min_segment_size,
+            max_flush_delay_sec,
+            max_optimization_threads,
+            deduplicate_vectors,
+            deduplication_epsilon,
+            defer_indexing,
+            split_oversized_segments,
+        } = self;
+
+        deleted_threshold.is_none()
+            && payload_index_deleted_threshold.is_none()
+            && vacuum_min_vector_number.is_none()
+            && default_segment_number.is_none()
+            && max_segment_size.is_none()
+            && memmap_threshold.is_none()
+            && indexing_threshold.is_none()
+            && flush_interval_sec.is_none()
+            && min_segment_size.is_none()
+            && max_flush_delay_sec.is_none()
+            && max_optimization_threads.is_none()
+            && deduplicate_vectors.is_none()
+            && deduplication_epsilon.is_none()
+            && defer_indexing.is_none()
+            && split_oversized_segments.is_none()
+    }
+}
+
 impl DiffConfig<HnswConfig> for HnswConfigDiff {}
 
 impl DiffConfig<HnswConfigDiff> for HnswConfigDiff {}
@@ -456,17 +635,43 @@ mod tests {
         assert_eq!(new_config.m, 32)
     }
 
+    #[test]
+    fn test_hnsw_m0_validation() {
+        let base_config = HnswConfig::default();
+
+        let update: HnswConfigDiff = serde_json::from_str(r#"{ "m": 16, "m0": 8 }"#).unwrap();
+        assert!(update.validate().is_err());
+
+        let update: HnswConfigDiff = serde_json::from_str(r#"{ "m0": 48 }"#).unwrap();
+        assert!(update.validate().is_ok());
+        let new_config = update.update(&base_config).unwrap();
+        assert_eq!(new_config.m0, Some(48));
+        assert!(new_config.validate().is_ok());
+
+        // Only valid in combination with the base config
+        let update: HnswConfigDiff = serde_json::from_str(r#"{ "m": 64 }"#).unwrap();
+        assert!(update.validate().is_ok());
+        assert!(update.update(&new_config).unwrap().validate().is_err());
+    }
+
     #[test]
     fn test_optimizer_update() {
         let base_config = OptimizersConfig {
             deleted_threshold: 0.9,
+            payload_index_deleted_threshold: None,
             vacuum_min_vector_number: 1000,
             default_segment_number: 10,
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(50_000),
             flush_interval_sec: 30,
+            min_segment_size: None,
+            max_flush_delay_sec: None,
             max_optimization_threads: Some(1),
+            deduplicate_vectors: None,
+            deduplication_epsilon: None,
+            defer_indexing: None,
+            split_oversized_segments: None,
         };
         let update: OptimizersConfigDiff =
             serde_json::from_str(r#"{ "indexing_threshold": 10000 }"#).unwrap();
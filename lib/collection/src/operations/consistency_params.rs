@@ -1,13 +1,20 @@
 use std::borrow::Cow;
+use std::fmt;
+use std::str::FromStr;
 
 use api::grpc::qdrant::{
     read_consistency, ReadConsistency as ReadConsistencyGrpc,
     ReadConsistencyType as ReadConsistencyTypeGrpc,
 };
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 
+use crate::operations::ClockTag;
+use crate::shards::shard::{PeerId, ShardId};
+
 /// Read consistency parameter
 ///
 /// Defines how many replicas should be queried to get the result
@@ -20,13 +27,17 @@ use validator::{Validate, ValidationError as ValidatorError, ValidationErrors};
 ///
 /// * `all` - send requests to all nodes and return points which present on all of them
 ///
+/// * session token - read from a replica, which has applied the update that returned this token
+///
 /// Default value is `Factor(1)`
-#[derive(Copy, Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
+#[derive(Clone, Debug, Eq, PartialEq, Deserialize, Serialize, JsonSchema)]
 #[serde(untagged)]
 pub enum ReadConsistency {
     // send N random request and return points, which present on all of them
     Factor(#[serde(deserialize_with = "deserialize_factor")] usize),
     Type(ReadConsistencyType),
+    // read from a replica, which has applied the update that returned this token
+    Session(SessionToken),
 }
 
 impl Validate for ReadConsistency {
@@ -42,7 +53,9 @@ impl Validate for ReadConsistency {
                 });
                 Err(errors)
             }
-            ReadConsistency::Factor(_) | ReadConsistency::Type(_) => Ok(()),
+            ReadConsistency::Factor(_) | ReadConsistency::Type(_) | ReadConsistency::Session(_) => {
+                Ok(())
+            }
         }
     }
 }
@@ -88,6 +101,11 @@ impl TryFrom<ReadConsistencyGrpc> for ReadConsistency {
                     .map_err(|err| tonic::Status::invalid_argument(err.to_string()))?,
             ),
             read_consistency::Value::Type(consistency) => Self::Type(consistency.try_into()?),
+            read_consistency::Value::SessionToken(token) => {
+                Self::Session(token.parse().map_err(|err: SessionTokenError| {
+                    tonic::Status::invalid_argument(err.to_string())
+                })?)
+            }
         };
 
         Ok(consistency)
@@ -101,6 +119,9 @@ impl From<ReadConsistency> for ReadConsistencyGrpc {
                 read_consistency::Value::Factor(factor.try_into().unwrap())
             }
             ReadConsistency::Type(consistency) => read_consistency::Value::Type(consistency.into()),
+            ReadConsistency::Session(token) => {
+                read_consistency::Value::SessionToken(token.to_string())
+            }
         };
 
         ReadConsistencyGrpc { value: Some(value) }
@@ -200,6 +221,153 @@ impl From<ReadConsistencyType> for ReadConsistencyTypeGrpc {
 #[error("Read consistency factor cannot be less than 1")]
 pub struct ValidationError;
 
+const SESSION_TOKEN_VERSION_PREFIX: &str = "v1:";
+
+/// Token returned by an update, which can be used to read own writes
+///
+/// Holds the clock tag the update got in each shard it was applied to. A replica has applied the
+/// update once it has seen all clock tags of its shard.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct SessionToken {
+    clocks: Vec<SessionClock>,
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub struct SessionClock {
+    pub shard_id: ShardId,
+    pub peer_id: PeerId,
+    pub clock_id: u32,
+    pub clock_tick: u64,
+}
+
+impl SessionToken {
+    pub fn is_empty(&self) -> bool {
+        self.clocks.is_empty()
+    }
+
+    /// Add the clock tag an update got in the given shard
+    pub fn add(&mut self, shard_id: ShardId, clock_tag: ClockTag) {
+        self.clocks.push(SessionClock {
+            shard_id,
+            peer_id: clock_tag.peer_id,
+            clock_id: clock_tag.clock_id,
+            clock_tick: clock_tag.clock_tick,
+        });
+    }
+
+    /// Add all clocks of the other token
+    pub fn merge(&mut self, other: SessionToken) {
+        self.clocks.extend(other.clocks);
+    }
+
+    /// Clocks that a replica of the given shard must have seen
+    pub fn shard_clocks(&self, shard_id: ShardId) -> Vec<SessionClock> {
+        self.clocks
+            .iter()
+            .filter(|clock| clock.shard_id == shard_id)
+            .copied()
+            .collect()
+    }
+}
+
+impl FromIterator<SessionClock> for SessionToken {
+    fn from_iter<I: IntoIterator<Item = SessionClock>>(clocks: I) -> Self {
+        Self {
+            clocks: clocks.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for SessionToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(SESSION_TOKEN_VERSION_PREFIX)?;
+
+        for (i, clock) in self.clocks.iter().enumerate() {
+            if i > 0 {
+                f.write_str(",")?;
+            }
+
+            write!(
+                f,
+                "{}-{}-{}-{}",
+                clock.shard_id, clock.peer_id, clock.clock_id, clock.clock_tick,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Clone, Debug, thiserror::Error)]
+#[error("Invalid session token {0:?}")]
+pub struct SessionTokenError(String);
+
+impl FromStr for SessionToken {
+    type Err = SessionTokenError;
+
+    fn from_str(token: &str) -> Result<Self, Self::Err> {
+        let invalid = || SessionTokenError(token.to_string());
+
+        let clocks = token
+            .strip_prefix(SESSION_TOKEN_VERSION_PREFIX)
+            .ok_or_else(invalid)?;
+
+        let clocks = clocks
+            .split(',')
+            .filter(|clock| !clock.is_empty())
+            .map(|clock| {
+                let mut parts = clock.split('-');
+                let mut next = || parts.next().ok_or_else(invalid);
+
+                let clock = SessionClock {
+                    shard_id: next()?.parse().map_err(|_| invalid())?,
+                    peer_id: next()?.parse().map_err(|_| invalid())?,
+                    clock_id: next()?.parse().map_err(|_| invalid())?,
+                    clock_tick: next()?.parse().map_err(|_| invalid())?,
+                };
+
+                if parts.next().is_some() {
+                    return Err(invalid());
+                }
+
+                Ok(clock)
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self { clocks })
+    }
+}
+
+impl Serialize for SessionToken {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for SessionToken {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let token = String::deserialize(deserializer)?;
+        token.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for SessionToken {
+    fn schema_name() -> String {
+        "SessionToken".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.metadata().description = Some(
+            "Token returned by an update, which can be used to read own writes\n\n\
+             Holds the clock tag the update got in each shard it was applied to. A replica has \
+             applied the update once it has seen all clock tags of its shard."
+                .to_string(),
+        );
+        schema.into()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use schemars::schema_for;
@@ -239,6 +407,20 @@ mod tests {
         let consistency: ReadConsistency = serde_json::from_str(json).unwrap();
         assert_eq!(consistency, ReadConsistency::Type(ReadConsistencyType::All));
 
+        let json = "\"v1:0-1-0-42,2-1-3-7\"";
+        let consistency: ReadConsistency = serde_json::from_str(json).unwrap();
+        let ReadConsistency::Session(token) = &consistency else {
+            panic!("expected session token, got {consistency:?}");
+        };
+        assert_eq!(token.shard_clocks(0).len(), 1);
+        assert_eq!(token.shard_clocks(2)[0].clock_tick, 7);
+        assert!(token.shard_clocks(1).is_empty());
+        assert_eq!(serde_json::to_string(&consistency).unwrap(), json);
+
+        let json = "\"v1:0-1-0\"";
+        let consistency: Result<ReadConsistency, _> = serde_json::from_str(json);
+        assert!(consistency.is_err());
+
         let schema = schema_for!(ReadConsistency);
         let schema_str = serde_json::to_string_pretty(&schema).unwrap();
         println!("{schema_str}")
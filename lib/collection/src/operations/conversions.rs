@@ -5,6 +5,7 @@ use std::time::Duration;
 use api::grpc::conversions::{
     convert_shard_key_from_grpc, convert_shard_key_from_grpc_opt, convert_shard_key_to_grpc,
     from_grpc_dist, json_path_from_proto, payload_to_proto, proto_to_payloads,
+    proto_to_vector_metadata, vector_metadata_to_proto,
 };
 use api::grpc::qdrant::quantization_config_diff::Quantization;
 use api::grpc::qdrant::update_collection_cluster_setup_request::{
@@ -18,30 +19,36 @@ use itertools::Itertools;
 use segment::data_types::vectors::{
     BatchVectorStructInternal, NamedQuery, Vector, VectorStructInternal,
 };
-use segment::types::{Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint};
-use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::types::{
+    Distance, MultiVectorConfig, QuantizationConfig, ScoredPoint, SparseDistance,
+};
+use segment::vector_storage::query::{
+    ContextPair, ContextQuery, DiscoveryQuery, RecoQuery, DEFAULT_CONTEXT_WEIGHT,
+};
 use sparse::common::sparse_vector::{validate_sparse_vector_impl, SparseVector};
 use tonic::Status;
 
 use super::config_diff::StrictModeConfig;
-use super::consistency_params::ReadConsistency;
+use super::consistency_params::{ReadConsistency, SessionTokenError};
 use super::types::{
     ContextExamplePair, CoreSearchRequest, Datatype, DiscoverRequestInternal, GroupsResult,
     Modifier, PointGroup, RecommendExample, RecommendGroupsRequestInternal, ReshardingInfo,
     SparseIndexParams, SparseVectorParams, SparseVectorsConfig, VectorParamsDiff,
-    VectorsConfigDiff,
+    VectorsConfigDiff, WeightedContext,
 };
 use crate::config::{
-    default_replication_factor, default_write_consistency_factor, CollectionConfig,
-    CollectionParams, ShardingMethod, WalConfig,
+    default_replication_factor, default_ttl_check_interval_sec, default_ttl_delete_batch_size,
+    default_ttl_set_on_insert, default_write_consistency_factor, CollectionConfig,
+    CollectionParams, ShardingMethod, TtlConfig, WalConfig,
 };
 use crate::lookup::types::WithLookupInterface;
 use crate::lookup::WithLookup;
 use crate::operations::cluster_ops::{
-    AbortShardTransfer, AbortTransferOperation, ClusterOperations, CreateShardingKey,
+    AbortShardTransfer, AbortTransferOperation, ChangeReplicationFactor,
+    ChangeReplicationFactorOperation, ClusterOperations, CreateShardingKey,
     CreateShardingKeyOperation, DropReplicaOperation, DropShardingKey, DropShardingKeyOperation,
-    MoveShard, MoveShardOperation, Replica, ReplicateShard, ReplicateShardOperation,
-    RestartTransfer, RestartTransferOperation,
+    MakeReplicaReadOnlyOperation, MoveShard, MoveShardOperation, PromoteReplicaOperation, Replica,
+    ReplicateShard, ReplicateShardOperation, RestartTransfer, RestartTransferOperation,
 };
 use crate::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
@@ -49,14 +56,16 @@ use crate::operations::config_diff::{
 };
 use crate::operations::point_ops::PointsSelector::PointIdsSelector;
 use crate::operations::point_ops::{
-    Batch, FilterSelector, PointIdsList, PointStruct, PointsSelector, WriteOrdering,
+    Batch, DuplicateIdsPolicy, FilterSelector, PointIdsList, PointStruct, PointVersion,
+    PointsSelector, TtlStamp, WriteConsistency, WriteOrdering,
 };
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionStatus, CountResult,
-    LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record, RemoteShardInfo,
-    ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    InvalidPoint, LocalShardInfo, OptimizersStatus, RecommendRequestInternal, Record,
+    RemoteShardInfo, ShardTransferInfo, UpdateResult, UpdateStatus, VectorParams, VectorsConfig,
+    ZeroVectorPolicy,
 };
 use crate::optimizers_builder::OptimizersConfig;
 use crate::shards::remote_shard::CollectionCoreSearchRequest;
@@ -117,6 +126,60 @@ pub fn write_ordering_from_proto(
     })
 }
 
+pub fn write_consistency_to_proto(
+    consistency: WriteConsistency,
+) -> api::grpc::qdrant::WriteConsistency {
+    api::grpc::qdrant::WriteConsistency {
+        r#type: match consistency {
+            WriteConsistency::Local => api::grpc::qdrant::WriteConsistencyType::LocalReplica as i32,
+            WriteConsistency::Majority => {
+                api::grpc::qdrant::WriteConsistencyType::MajorityReplicas as i32
+            }
+            WriteConsistency::All => api::grpc::qdrant::WriteConsistencyType::AllReplicas as i32,
+        },
+    }
+}
+
+pub fn write_consistency_from_proto(
+    consistency: Option<api::grpc::qdrant::WriteConsistency>,
+) -> Result<Option<WriteConsistency>, Status> {
+    let Some(consistency) = consistency else {
+        return Ok(None);
+    };
+
+    let consistency_parsed = api::grpc::qdrant::WriteConsistencyType::try_from(consistency.r#type)
+        .map_err(|_| {
+            Status::invalid_argument(format!(
+                "cannot convert write consistency: {}",
+                consistency.r#type
+            ))
+        })?;
+
+    Ok(Some(match consistency_parsed {
+        api::grpc::qdrant::WriteConsistencyType::LocalReplica => WriteConsistency::Local,
+        api::grpc::qdrant::WriteConsistencyType::MajorityReplicas => WriteConsistency::Majority,
+        api::grpc::qdrant::WriteConsistencyType::AllReplicas => WriteConsistency::All,
+    }))
+}
+
+pub fn duplicate_ids_policy_from_proto(
+    policy: Option<i32>,
+) -> Result<Option<DuplicateIdsPolicy>, Status> {
+    let Some(policy) = policy else {
+        return Ok(None);
+    };
+
+    let policy_parsed = api::grpc::qdrant::DuplicateIdsPolicy::try_from(policy).map_err(|_| {
+        Status::invalid_argument(format!("cannot convert duplicate ids policy: {policy}"))
+    })?;
+
+    Ok(Some(match policy_parsed {
+        api::grpc::qdrant::DuplicateIdsPolicy::LastWins => DuplicateIdsPolicy::LastWins,
+        api::grpc::qdrant::DuplicateIdsPolicy::FirstWins => DuplicateIdsPolicy::FirstWins,
+        api::grpc::qdrant::DuplicateIdsPolicy::Error => DuplicateIdsPolicy::Error,
+    }))
+}
+
 pub fn try_record_from_grpc(
     point: api::grpc::qdrant::RetrievedPoint,
     with_payload: bool,
@@ -140,15 +203,39 @@ pub fn try_record_from_grpc(
 
     let order_value = point.order_value.map(TryFrom::try_from).transpose()?;
 
+    // empty vector metadata means None in Record
+    let vector_metadata = if point.vector_metadata.is_empty() {
+        None
+    } else {
+        Some(proto_to_vector_metadata(point.vector_metadata)?)
+    };
+
     Ok(Record {
         id,
         payload,
         vector,
+        vector_metadata,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
         order_value,
+        version: point.version,
     })
 }
 
+fn try_context_example_pair_from_grpc(
+    pair: api::grpc::qdrant::ContextExamplePair,
+) -> Result<ContextExamplePair, Status> {
+    match (
+        pair.positive.map(|p| p.try_into()),
+        pair.negative.map(|n| n.try_into()),
+    ) {
+        (Some(Ok(positive)), Some(Ok(negative))) => Ok(ContextExamplePair { positive, negative }),
+        (Some(Err(e)), _) | (_, Some(Err(e))) => Err(e),
+        (None, _) | (_, None) => Err(Status::invalid_argument(
+            "Both positive and negative are required in a context pair",
+        )),
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub fn try_discover_request_from_grpc(
     value: api::grpc::qdrant::DiscoverPoints,
@@ -177,31 +264,34 @@ pub fn try_discover_request_from_grpc(
         read_consistency,
         timeout,
         shard_key_selector,
+        weighted_context,
     } = value;
 
     let target = target.map(TryInto::try_into).transpose()?;
 
     let context = context
         .into_iter()
-        .map(|pair| {
-            match (
-                pair.positive.map(|p| p.try_into()),
-                pair.negative.map(|n| n.try_into()),
-            ) {
-                (Some(Ok(positive)), Some(Ok(negative))) => {
-                    Ok(ContextExamplePair { positive, negative })
-                }
-                (Some(Err(e)), _) | (_, Some(Err(e))) => Err(e),
-                (None, _) | (_, None) => Err(Status::invalid_argument(
-                    "Both positive and negative are required in a context pair",
-                )),
-            }
-        })
+        .map(try_context_example_pair_from_grpc)
         .try_collect()?;
 
+    let weighted_context = weighted_context
+        .into_iter()
+        .map(|context| {
+            Ok(WeightedContext {
+                pairs: context
+                    .pairs
+                    .into_iter()
+                    .map(try_context_example_pair_from_grpc)
+                    .try_collect()?,
+                weight: context.weight,
+            })
+        })
+        .collect::<Result<Vec<_>, Status>>()?;
+
     let request = DiscoverRequestInternal {
         target,
         context: Some(context),
+        weighted_context: (!weighted_context.is_empty()).then_some(weighted_context),
         filter: filter.map(|f| f.try_into()).transpose()?,
         params: params.map(|p| p.into()),
         limit: limit as usize,
@@ -233,11 +323,14 @@ impl From<api::grpc::qdrant::HnswConfigDiff> for HnswConfigDiff {
     fn from(value: api::grpc::qdrant::HnswConfigDiff) -> Self {
         Self {
             m: value.m.map(|v| v as usize),
+            m0: value.m0.map(|v| v as usize),
             ef_construct: value.ef_construct.map(|v| v as usize),
             full_scan_threshold: value.full_scan_threshold.map(|v| v as usize),
             max_indexing_threads: value.max_indexing_threads.map(|v| v as usize),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as usize),
+            level_multiplier: value.level_multiplier,
+            deterministic_build: value.deterministic_build,
         }
     }
 }
@@ -246,11 +339,14 @@ impl From<HnswConfigDiff> for api::grpc::qdrant::HnswConfigDiff {
     fn from(value: HnswConfigDiff) -> Self {
         Self {
             m: value.m.map(|v| v as u64),
+            m0: value.m0.map(|v| v as u64),
             ef_construct: value.ef_construct.map(|v| v as u64),
             full_scan_threshold: value.full_scan_threshold.map(|v| v as u64),
             max_indexing_threads: value.max_indexing_threads.map(|v| v as u64),
             on_disk: value.on_disk,
             payload_m: value.payload_m.map(|v| v as u64),
+            level_multiplier: value.level_multiplier,
+            deterministic_build: value.deterministic_build,
         }
     }
 }
@@ -301,6 +397,35 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfigDiff {
             indexing_threshold: value.indexing_threshold.map(|v| v as usize),
             flush_interval_sec: value.flush_interval_sec,
             max_optimization_threads: value.max_optimization_threads.map(|v| v as usize),
+            deduplicate_vectors: value.deduplicate_vectors,
+            deduplication_epsilon: value.deduplication_epsilon,
+            defer_indexing: value.defer_indexing,
+            payload_index_deleted_threshold: value.payload_index_deleted_threshold,
+            min_segment_size: value.min_segment_size.map(|v| v as usize),
+            max_flush_delay_sec: value.max_flush_delay_sec,
+            split_oversized_segments: value.split_oversized_segments,
+        }
+    }
+}
+
+impl From<OptimizersConfigDiff> for api::grpc::qdrant::OptimizersConfigDiff {
+    fn from(value: OptimizersConfigDiff) -> Self {
+        Self {
+            deleted_threshold: value.deleted_threshold,
+            vacuum_min_vector_number: value.vacuum_min_vector_number.map(|v| v as u64),
+            default_segment_number: value.default_segment_number.map(|v| v as u64),
+            max_segment_size: value.max_segment_size.map(|v| v as u64),
+            memmap_threshold: value.memmap_threshold.map(|v| v as u64),
+            indexing_threshold: value.indexing_threshold.map(|v| v as u64),
+            flush_interval_sec: value.flush_interval_sec,
+            max_optimization_threads: value.max_optimization_threads.map(|v| v as u64),
+            deduplicate_vectors: value.deduplicate_vectors,
+            deduplication_epsilon: value.deduplication_epsilon,
+            defer_indexing: value.defer_indexing,
+            payload_index_deleted_threshold: value.payload_index_deleted_threshold,
+            min_segment_size: value.min_segment_size.map(|v| v as u64),
+            max_flush_delay_sec: value.max_flush_delay_sec,
+            split_oversized_segments: value.split_oversized_segments,
         }
     }
 }
@@ -397,14 +522,18 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                                 .collect(),
                         }
                     }),
+                    payload_compression: config.params.payload_compression.map(From::from),
                 }),
                 hnsw_config: Some(api::grpc::qdrant::HnswConfigDiff {
                     m: Some(config.hnsw_config.m as u64),
+                    m0: config.hnsw_config.m0.map(|v| v as u64),
                     ef_construct: Some(config.hnsw_config.ef_construct as u64),
                     full_scan_threshold: Some(config.hnsw_config.full_scan_threshold as u64),
                     max_indexing_threads: Some(config.hnsw_config.max_indexing_threads as u64),
                     on_disk: config.hnsw_config.on_disk,
                     payload_m: config.hnsw_config.payload_m.map(|v| v as u64),
+                    level_multiplier: config.hnsw_config.level_multiplier,
+                    deterministic_build: config.hnsw_config.deterministic_build,
                 }),
                 optimizer_config: Some(api::grpc::qdrant::OptimizersConfigDiff {
                     deleted_threshold: Some(config.optimizer_config.deleted_threshold),
@@ -425,6 +554,15 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                         .optimizer_config
                         .max_optimization_threads
                         .map(|n| n as u64),
+                    deduplicate_vectors: config.optimizer_config.deduplicate_vectors,
+                    deduplication_epsilon: config.optimizer_config.deduplication_epsilon,
+                    defer_indexing: config.optimizer_config.defer_indexing,
+                    payload_index_deleted_threshold: config
+                        .optimizer_config
+                        .payload_index_deleted_threshold,
+                    min_segment_size: config.optimizer_config.min_segment_size.map(|x| x as u64),
+                    max_flush_delay_sec: config.optimizer_config.max_flush_delay_sec,
+                    split_oversized_segments: config.optimizer_config.split_oversized_segments,
                 }),
                 wal_config: Some(api::grpc::qdrant::WalConfigDiff {
                     wal_capacity_mb: Some(config.wal_config.wal_capacity_mb as u64),
@@ -432,6 +570,12 @@ impl From<CollectionInfo> for api::grpc::qdrant::CollectionInfo {
                 }),
                 quantization_config: config.quantization_config.map(|x| x.into()),
                 strict_mode_config: config.strict_mode_config.map(From::from),
+                ttl_config: config.ttl_config.map(From::from),
+                shard_optimizer_config: config
+                    .shard_optimizer_config
+                    .into_iter()
+                    .map(|(shard_id, diff)| (shard_id, diff.into()))
+                    .collect(),
             }),
             payload_schema: payload_schema
                 .into_iter()
@@ -446,12 +590,27 @@ impl From<StrictModeConfig> for api::grpc::qdrant::StrictModeConfig {
         api::grpc::qdrant::StrictModeConfig {
             enabled: value.enabled,
             max_query_limit: value.max_query_limit.map(|i| i as u32),
+            clamp_query_limit: value.clamp_query_limit,
             max_timeout: value.max_timeout.map(|i| i as u32),
             unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
             unindexed_filtering_update: value.unindexed_filtering_update,
             search_max_hnsw_ef: value.search_max_hnsw_ef.map(|i| i as u32),
             search_allow_exact: value.search_allow_exact,
             search_max_oversampling: value.search_max_oversampling.map(|i| i as f32),
+            scroll_max_limit: value.scroll_max_limit.map(|i| i as u32),
+        }
+    }
+}
+
+impl From<TtlConfig> for api::grpc::qdrant::TtlConfig {
+    fn from(value: TtlConfig) -> Self {
+        api::grpc::qdrant::TtlConfig {
+            ttl_sec: value.ttl_sec,
+            timestamp_field: value.timestamp_field.to_string(),
+            set_on_insert: Some(value.set_on_insert),
+            refresh_on_update: Some(value.refresh_on_update),
+            check_interval_sec: Some(value.check_interval_sec),
+            delete_batch_size: Some(value.delete_batch_size as u64),
         }
     }
 }
@@ -466,6 +625,11 @@ impl From<Record> for api::grpc::qdrant::RetrievedPoint {
             vectors: vectors.map(api::grpc::qdrant::Vectors::from),
             shard_key: record.shard_key.map(convert_shard_key_to_grpc),
             order_value: record.order_value.map(From::from),
+            version: record.version,
+            vector_metadata: record
+                .vector_metadata
+                .map(vector_metadata_to_proto)
+                .unwrap_or_default(),
         }
     }
 }
@@ -506,6 +670,13 @@ impl From<api::grpc::qdrant::OptimizersConfigDiff> for OptimizersConfig {
             max_optimization_threads: optimizer_config
                 .max_optimization_threads
                 .map(|n| n as usize),
+            deduplicate_vectors: optimizer_config.deduplicate_vectors,
+            deduplication_epsilon: optimizer_config.deduplication_epsilon,
+            defer_indexing: optimizer_config.defer_indexing,
+            payload_index_deleted_threshold: optimizer_config.payload_index_deleted_threshold,
+            min_segment_size: optimizer_config.min_segment_size.map(|x| x as usize),
+            max_flush_delay_sec: optimizer_config.max_flush_delay_sec,
+            split_oversized_segments: optimizer_config.split_oversized_segments,
         }
     }
 }
@@ -580,10 +751,25 @@ impl TryFrom<api::grpc::qdrant::VectorParams> for VectorParams {
                 .multivector_config
                 .map(MultiVectorConfig::try_from)
                 .transpose()?,
+            zero_vector_policy: vector_params
+                .zero_vector_policy
+                .map(convert_zero_vector_policy_from_proto)
+                .transpose()?,
         })
     }
 }
 
+fn convert_zero_vector_policy_from_proto(policy: i32) -> Result<ZeroVectorPolicy, Status> {
+    match api::grpc::qdrant::ZeroVectorPolicy::try_from(policy) {
+        Ok(api::grpc::qdrant::ZeroVectorPolicy::ScoreZero) => Ok(ZeroVectorPolicy::ScoreZero),
+        Ok(api::grpc::qdrant::ZeroVectorPolicy::Exclude) => Ok(ZeroVectorPolicy::Exclude),
+        Ok(api::grpc::qdrant::ZeroVectorPolicy::Reject) => Ok(ZeroVectorPolicy::Reject),
+        Err(_) => Err(Status::invalid_argument(format!(
+            "Cannot convert zero vector policy: {policy}"
+        ))),
+    }
+}
+
 fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>, Status> {
     if let Some(datatype_int) = datatype {
         let grpc_datatype = api::grpc::qdrant::Datatype::try_from(datatype_int);
@@ -592,6 +778,7 @@ fn convert_datatype_from_proto(datatype: Option<i32>) -> Result<Option<Datatype>
                 api::grpc::qdrant::Datatype::Uint8 => Ok(Some(Datatype::Uint8)),
                 api::grpc::qdrant::Datatype::Float32 => Ok(Some(Datatype::Float32)),
                 api::grpc::qdrant::Datatype::Float16 => Ok(Some(Datatype::Float16)),
+                api::grpc::qdrant::Datatype::Int8 => Ok(Some(Datatype::Int8)),
                 api::grpc::qdrant::Datatype::Default => Ok(None),
             }
         } else {
@@ -651,10 +838,42 @@ impl TryFrom<api::grpc::qdrant::SparseVectorParams> for SparseVectorParams {
                     // XXX: Invalid values silently converted to None
                     api::grpc::qdrant::Modifier::try_from(x).ok())
                 .map(Modifier::from),
+            distance: sparse_vector_params
+                .distance
+                .map(|distance| {
+                    api::grpc::qdrant::SparseDistance::try_from(distance)
+                        .map(SparseDistance::from)
+                        .map_err(|_| {
+                            Status::invalid_argument(format!(
+                                "Cannot convert sparse distance: {distance}"
+                            ))
+                        })
+                })
+                .transpose()?,
         })
     }
 }
 
+impl From<api::grpc::qdrant::SparseDistance> for SparseDistance {
+    fn from(value: api::grpc::qdrant::SparseDistance) -> Self {
+        match value {
+            api::grpc::qdrant::SparseDistance::SparseDot => SparseDistance::Dot,
+            api::grpc::qdrant::SparseDistance::SparseCosine => SparseDistance::Cosine,
+            api::grpc::qdrant::SparseDistance::SparseJaccard => SparseDistance::Jaccard,
+        }
+    }
+}
+
+impl From<SparseDistance> for api::grpc::qdrant::SparseDistance {
+    fn from(value: SparseDistance) -> Self {
+        match value {
+            SparseDistance::Dot => api::grpc::qdrant::SparseDistance::SparseDot,
+            SparseDistance::Cosine => api::grpc::qdrant::SparseDistance::SparseCosine,
+            SparseDistance::Jaccard => api::grpc::qdrant::SparseDistance::SparseJaccard,
+        }
+    }
+}
+
 impl From<Modifier> for api::grpc::qdrant::Modifier {
     fn from(value: Modifier) -> Self {
         match value {
@@ -679,6 +898,9 @@ impl From<SparseVectorParams> for api::grpc::qdrant::SparseVectorParams {
             modifier: sparse_vector_params
                 .modifier
                 .map(|modifier| api::grpc::qdrant::Modifier::from(modifier) as i32),
+            distance: sparse_vector_params
+                .distance
+                .map(|distance| api::grpc::qdrant::SparseDistance::from(distance) as i32),
         }
     }
 }
@@ -765,6 +987,7 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                         .sharding_method
                         .map(sharding_method_from_proto)
                         .transpose()?,
+                    payload_compression: params.payload_compression.map(From::from),
                 },
             },
             hnsw_config: match config.hnsw_config {
@@ -787,6 +1010,12 @@ impl TryFrom<api::grpc::qdrant::CollectionConfig> for CollectionConfig {
                 }
             },
             strict_mode_config: config.strict_mode_config.map(StrictModeConfig::from),
+            ttl_config: config.ttl_config.map(TtlConfig::try_from).transpose()?,
+            shard_optimizer_config: config
+                .shard_optimizer_config
+                .into_iter()
+                .map(|(shard_id, diff)| (shard_id, diff.into()))
+                .collect(),
         })
     }
 }
@@ -842,16 +1071,39 @@ impl From<api::grpc::qdrant::StrictModeConfig> for StrictModeConfig {
         Self {
             enabled: value.enabled,
             max_query_limit: value.max_query_limit.map(|i| i as usize),
+            clamp_query_limit: value.clamp_query_limit,
             max_timeout: value.max_timeout.map(|i| i as usize),
             unindexed_filtering_retrieve: value.unindexed_filtering_retrieve,
             unindexed_filtering_update: value.unindexed_filtering_update,
             search_max_hnsw_ef: value.search_max_hnsw_ef.map(|i| i as usize),
             search_allow_exact: value.search_allow_exact,
             search_max_oversampling: value.search_max_oversampling.map(f64::from),
+            scroll_max_limit: value.scroll_max_limit.map(|i| i as usize),
         }
     }
 }
 
+impl TryFrom<api::grpc::qdrant::TtlConfig> for TtlConfig {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::TtlConfig) -> Result<Self, Self::Error> {
+        Ok(Self {
+            ttl_sec: value.ttl_sec,
+            timestamp_field: json_path_from_proto(&value.timestamp_field)?,
+            set_on_insert: value
+                .set_on_insert
+                .unwrap_or_else(default_ttl_set_on_insert),
+            refresh_on_update: value.refresh_on_update.unwrap_or_default(),
+            check_interval_sec: value
+                .check_interval_sec
+                .unwrap_or_else(default_ttl_check_interval_sec),
+            delete_batch_size: value
+                .delete_batch_size
+                .map_or_else(default_ttl_delete_batch_size, |size| size as usize),
+        })
+    }
+}
+
 impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
     type Error = Status;
 
@@ -860,6 +1112,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             id,
             vectors,
             payload,
+            vector_metadata,
         } = value;
 
         // empty payload means None in PointStruct
@@ -869,6 +1122,12 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
             Some(proto_to_payloads(payload)?)
         };
 
+        let vector_metadata = if vector_metadata.is_empty() {
+            None
+        } else {
+            Some(proto_to_vector_metadata(vector_metadata)?)
+        };
+
         let vector_struct: VectorStructInternal = match vectors {
             None => return Err(Status::invalid_argument("Expected some vectors")),
             Some(vectors) => vectors.try_into()?,
@@ -880,6 +1139,7 @@ impl TryFrom<api::grpc::qdrant::PointStruct> for PointStruct {
                 .try_into()?,
             vector: api::rest::VectorStruct::from(vector_struct),
             payload: converted_payload,
+            vector_metadata,
         })
     }
 }
@@ -903,10 +1163,71 @@ impl TryFrom<PointStruct> for api::grpc::qdrant::PointStruct {
             id: Some(id.into()),
             vectors: Some(vectors),
             payload: converted_payload,
+            vector_metadata: value
+                .vector_metadata
+                .map(vector_metadata_to_proto)
+                .unwrap_or_default(),
+        })
+    }
+}
+
+impl TryFrom<api::grpc::qdrant::PointVersion> for PointVersion {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::PointVersion) -> Result<Self, Self::Error> {
+        let api::grpc::qdrant::PointVersion { id, version } = value;
+        Ok(Self {
+            id: id
+                .ok_or_else(|| Status::invalid_argument("Empty ID is not allowed"))?
+                .try_into()?,
+            version,
         })
     }
 }
 
+impl From<PointVersion> for api::grpc::qdrant::PointVersion {
+    fn from(value: PointVersion) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            version: value.version,
+        }
+    }
+}
+
+impl From<api::grpc::qdrant::TtlStamp> for TtlStamp {
+    fn from(value: api::grpc::qdrant::TtlStamp) -> Self {
+        let api::grpc::qdrant::TtlStamp {
+            key,
+            timestamp,
+            set_on_insert,
+            refresh_on_update,
+        } = value;
+        Self {
+            key,
+            timestamp,
+            set_on_insert,
+            refresh_on_update,
+        }
+    }
+}
+
+impl From<TtlStamp> for api::grpc::qdrant::TtlStamp {
+    fn from(value: TtlStamp) -> Self {
+        let TtlStamp {
+            key,
+            timestamp,
+            set_on_insert,
+            refresh_on_update,
+        } = value;
+        Self {
+            key,
+            timestamp,
+            set_on_insert,
+            refresh_on_update,
+        }
+    }
+}
+
 impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
     type Error = Status;
 
@@ -929,6 +1250,7 @@ impl TryFrom<Batch> for Vec<api::grpc::qdrant::PointStruct> {
                 id,
                 vectors: vectors.map(|v| v.into()),
                 payload: payload.unwrap_or_default(),
+                vector_metadata: HashMap::new(),
             };
             points.push(point);
         }
@@ -974,7 +1296,21 @@ impl From<UpdateResult> for api::grpc::qdrant::UpdateResultInternal {
 
 impl From<UpdateResult> for api::grpc::qdrant::UpdateResult {
     fn from(res: UpdateResult) -> Self {
-        api::grpc::qdrant::UpdateResultInternal::from(res).into()
+        let session_token = res.session_token.as_ref().map(ToString::to_string);
+        let invalid_points = res
+            .invalid_points
+            .iter()
+            .map(|point| api::grpc::qdrant::InvalidPoint {
+                id: Some(point.id.into()),
+                error: point.error.clone(),
+            })
+            .collect();
+
+        api::grpc::qdrant::UpdateResult {
+            session_token,
+            invalid_points,
+            ..api::grpc::qdrant::UpdateResultInternal::from(res).into()
+        }
     }
 }
 
@@ -986,6 +1322,8 @@ impl TryFrom<api::grpc::qdrant::UpdateResultInternal> for UpdateResult {
             operation_id: res.operation_id,
             status: res.status.try_into()?,
             clock_tag: res.clock_tag.map(Into::into),
+            session_token: None,
+            invalid_points: Vec::new(),
         };
 
         Ok(res)
@@ -996,7 +1334,37 @@ impl TryFrom<api::grpc::qdrant::UpdateResult> for UpdateResult {
     type Error = Status;
 
     fn try_from(res: api::grpc::qdrant::UpdateResult) -> Result<Self, Self::Error> {
-        api::grpc::qdrant::UpdateResultInternal::from(res).try_into()
+        let session_token = res
+            .session_token
+            .as_deref()
+            .map(str::parse)
+            .transpose()
+            .map_err(|err: SessionTokenError| Status::invalid_argument(err.to_string()))?;
+
+        let invalid_points = res
+            .invalid_points
+            .iter()
+            .map(|point| {
+                let id = point
+                    .id
+                    .clone()
+                    .ok_or_else(|| Status::invalid_argument("Invalid point id is missing"))?
+                    .try_into()?;
+
+                Ok(InvalidPoint {
+                    id,
+                    error: point.error.clone(),
+                })
+            })
+            .collect::<Result<_, Status>>()?;
+
+        let res: Self = api::grpc::qdrant::UpdateResultInternal::from(res).try_into()?;
+
+        Ok(Self {
+            session_token,
+            invalid_points,
+            ..res
+        })
     }
 }
 
@@ -1037,6 +1405,8 @@ impl From<api::grpc::qdrant::CountResult> for CountResult {
     fn from(value: api::grpc::qdrant::CountResult) -> Self {
         Self {
             count: value.count as usize,
+            exact: value.exact.unwrap_or(false),
+            partial: value.partial.unwrap_or(false),
         }
     }
 }
@@ -1045,6 +1415,8 @@ impl From<CountResult> for api::grpc::qdrant::CountResult {
     fn from(value: CountResult) -> Self {
         Self {
             count: value.count as u64,
+            exact: Some(value.exact),
+            partial: Some(value.partial),
         }
     }
 }
@@ -1067,6 +1439,10 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             timeout: _,
             shard_key_selector: _,
             sparse_indices,
+            score_modifier,
+            rerank,
+            max_score_threshold,
+            with_distance,
         } = value;
 
         if let Some(sparse_indices) = &sparse_indices {
@@ -1087,6 +1463,10 @@ impl TryFrom<api::grpc::qdrant::SearchPoints> for CoreSearchRequest {
             with_payload: with_payload.map(TryInto::try_into).transpose()?,
             with_vector: with_vectors.map(Into::into),
             score_threshold: score_threshold.map(|s| s as ScoreType),
+            score_modifier: score_modifier.map(TryInto::try_into).transpose()?,
+            rerank: rerank.map(Into::into),
+            max_score_threshold,
+            with_distance: with_distance.unwrap_or_default(),
         })
     }
 }
@@ -1118,6 +1498,7 @@ impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
                             .map(|pair| api::grpc::qdrant::ContextPair {
                                 positive: { Some(pair.positive.into()) },
                                 negative: { Some(pair.negative.into()) },
+                                weight: Some(pair.weight),
                             })
                             .collect(),
                     },
@@ -1133,6 +1514,7 @@ impl From<QueryEnum> for api::grpc::qdrant::QueryEnum {
                             .map(|pair| api::grpc::qdrant::ContextPair {
                                 positive: { Some(pair.positive.into()) },
                                 negative: { Some(pair.negative.into()) },
+                                weight: Some(pair.weight),
                             })
                             .collect(),
                     },
@@ -1158,6 +1540,8 @@ impl<'a> From<CollectionCoreSearchRequest<'a>> for api::grpc::qdrant::CoreSearch
             offset: Some(request.offset as u64),
             vector_name: Some(request.query.get_vector_name().to_owned()),
             read_consistency: None,
+            max_score_threshold: request.max_score_threshold,
+            with_distance: Some(request.with_distance),
         }
     }
 }
@@ -1210,6 +1594,7 @@ fn try_context_pair_from_grpc(
         (Some(positive), Some(negative)) => Ok(ContextPair {
             positive: positive.try_into()?,
             negative: negative.try_into()?,
+            weight: pair.weight.unwrap_or(DEFAULT_CONTEXT_WEIGHT),
         }),
         _ => Err(Status::invalid_argument(
             "All context pairs must have both positive and negative parts",
@@ -1297,6 +1682,10 @@ impl TryFrom<api::grpc::qdrant::CoreSearchPoints> for CoreSearchRequest {
                     .unwrap_or_default(),
             ),
             score_threshold: value.score_threshold,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: value.max_score_threshold,
+            with_distance: value.with_distance.unwrap_or_default(),
         })
     }
 }
@@ -1339,6 +1728,7 @@ impl From<api::grpc::qdrant::ReplicaState> for ReplicaState {
             api::grpc::qdrant::ReplicaState::PartialSnapshot => Self::PartialSnapshot,
             api::grpc::qdrant::ReplicaState::Recovery => Self::Recovery,
             api::grpc::qdrant::ReplicaState::Resharding => Self::Resharding,
+            api::grpc::qdrant::ReplicaState::ReadOnly => Self::ReadOnly,
         }
     }
 }
@@ -1354,6 +1744,7 @@ impl From<ReplicaState> for api::grpc::qdrant::ReplicaState {
             ReplicaState::PartialSnapshot => Self::PartialSnapshot,
             ReplicaState::Recovery => Self::Recovery,
             ReplicaState::Resharding => Self::Resharding,
+            ReplicaState::ReadOnly => Self::ReadOnly,
         }
     }
 }
@@ -1464,6 +1855,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPoints> for RecommendRequestInternal {
             score_threshold: value.score_threshold,
             using: value.using.map(|name| name.into()),
             lookup_from: value.lookup_from.map(|x| x.into()),
+            mmr_lambda: value.mmr_lambda,
         })
     }
 }
@@ -1491,6 +1883,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             negative_vectors: value.negative_vectors,
             timeout: None, // Passed as query param
             shard_key_selector: None,
+            mmr_lambda: None, // Not enabled for groups
         };
 
         let RecommendRequestInternal {
@@ -1506,6 +1899,7 @@ impl TryFrom<api::grpc::qdrant::RecommendPointGroups> for RecommendGroupsRequest
             score_threshold,
             limit: _,
             offset: _,
+            mmr_lambda: _,
         } = recommend_points.try_into()?;
 
         Ok(RecommendGroupsRequestInternal {
@@ -1557,6 +1951,19 @@ impl From<VectorParams> for api::grpc::qdrant::VectorParams {
             multivector_config: value
                 .multivector_config
                 .map(api::grpc::qdrant::MultiVectorConfig::from),
+            zero_vector_policy: value
+                .zero_vector_policy
+                .map(|policy| api::grpc::qdrant::ZeroVectorPolicy::from(policy).into()),
+        }
+    }
+}
+
+impl From<ZeroVectorPolicy> for api::grpc::qdrant::ZeroVectorPolicy {
+    fn from(value: ZeroVectorPolicy) -> Self {
+        match value {
+            ZeroVectorPolicy::ScoreZero => api::grpc::qdrant::ZeroVectorPolicy::ScoreZero,
+            ZeroVectorPolicy::Exclude => api::grpc::qdrant::ZeroVectorPolicy::Exclude,
+            ZeroVectorPolicy::Reject => api::grpc::qdrant::ZeroVectorPolicy::Reject,
         }
     }
 }
@@ -1567,6 +1974,7 @@ impl From<Datatype> for api::grpc::qdrant::Datatype {
             Datatype::Float32 => api::grpc::qdrant::Datatype::Float32,
             Datatype::Uint8 => api::grpc::qdrant::Datatype::Uint8,
             Datatype::Float16 => api::grpc::qdrant::Datatype::Float16,
+            Datatype::Int8 => api::grpc::qdrant::Datatype::Int8,
         }
     }
 }
@@ -1770,6 +2178,20 @@ impl TryFrom<api::grpc::qdrant::DeleteShardKey> for DropShardingKey {
     }
 }
 
+impl TryFrom<api::grpc::qdrant::ChangeReplicationFactor> for ChangeReplicationFactor {
+    type Error = Status;
+
+    fn try_from(value: api::grpc::qdrant::ChangeReplicationFactor) -> Result<Self, Self::Error> {
+        let method = value.method.map(TryInto::try_into).transpose()?;
+        Ok(Self {
+            replication_factor: NonZeroU32::new(value.replication_factor).ok_or_else(|| {
+                Status::invalid_argument("Replication factor must be greater than 0")
+            })?,
+            method,
+        })
+    }
+}
+
 impl TryFrom<ClusterOperationsPb> for ClusterOperations {
     type Error = Status;
 
@@ -1819,6 +2241,27 @@ impl TryFrom<ClusterOperationsPb> for ClusterOperations {
                     },
                 })
             }
+            Operation::ChangeReplicationFactor(op) => {
+                ClusterOperations::ChangeReplicationFactor(ChangeReplicationFactorOperation {
+                    change_replication_factor: op.try_into()?,
+                })
+            }
+            Operation::MakeReplicaReadOnly(op) => {
+                ClusterOperations::MakeReplicaReadOnly(MakeReplicaReadOnlyOperation {
+                    make_replica_read_only: Replica {
+                        shard_id: op.shard_id,
+                        peer_id: op.peer_id,
+                    },
+                })
+            }
+            Operation::PromoteReplica(op) => {
+                ClusterOperations::PromoteReplica(PromoteReplicaOperation {
+                    promote_replica: Replica {
+                        shard_id: op.shard_id,
+                        peer_id: op.peer_id,
+                    },
+                })
+            }
         })
     }
 }
@@ -8,8 +8,10 @@ impl From<Record> for api::rest::Record {
             id: value.id,
             payload: value.payload,
             vector: value.vector.map(api::rest::VectorStruct::from),
+            vector_metadata: value.vector_metadata,
             shard_key: value.shard_key,
             order_value: value.order_value,
+            version: value.version,
         }
     }
 }
@@ -20,8 +22,10 @@ impl From<api::rest::Record> for Record {
             id: value.id,
             payload: value.payload,
             vector: value.vector.map(VectorStructInternal::from),
+            vector_metadata: value.vector_metadata,
             shard_key: value.shard_key,
             order_value: value.order_value,
+            version: value.version,
         }
     }
 }
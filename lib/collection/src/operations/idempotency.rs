@@ -0,0 +1,499 @@
+//! Idempotency keys for client update operations.
+//!
+//! A client may attach an [`IdempotencyKey`] to an update. Each shard replica set keeps a bounded
+//! LRU of keys it has recently applied, together with a fingerprint of the operation and its
+//! result. If the same key is seen again within the retention window, the operation is not
+//! applied again and the prior result is returned instead. Reusing a key for a *different*
+//! operation is rejected.
+//!
+//! Keys are only tracked on the peer that receives the client request, so retries must be sent
+//! to the same peer to be deduplicated.
+
+use std::collections::hash_map::DefaultHasher;
+use std::fs::{File, OpenOptions};
+use std::hash::{Hash, Hasher};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use indexmap::IndexMap;
+use parking_lot::Mutex;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest as _, Sha256};
+use tokio::sync::{Mutex as TokioMutex, MutexGuard as TokioMutexGuard};
+use validator::{Validate, ValidationError, ValidationErrors};
+
+use crate::common::sha_256::hash_json_value;
+
+use super::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use super::CollectionUpdateOperations;
+
+/// Maximum number of idempotency keys remembered per shard.
+pub const DEFAULT_IDEMPOTENCY_CACHE_CAPACITY: usize = 1024;
+
+/// How long an applied idempotency key is remembered.
+pub const DEFAULT_IDEMPOTENCY_RETENTION: Duration = Duration::from_secs(10 * 60);
+
+/// Maximum length of a user provided idempotency key.
+const MAX_IDEMPOTENCY_KEY_LENGTH: usize = 256;
+
+/// Number of locks idempotency keys are striped over.
+const IDEMPOTENCY_KEY_LOCKS: usize = 64;
+
+const IDEMPOTENCY_KEYS_FILE: &str = "idempotency_keys.jsonl";
+
+/// Client provided key, identifying an update operation across retries.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+#[serde(transparent)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    pub fn new(key: impl Into<String>) -> Self {
+        Self(key.into())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Validate for IdempotencyKey {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        let mut errors = ValidationErrors::new();
+        if self.0.is_empty() || self.0.len() > MAX_IDEMPOTENCY_KEY_LENGTH {
+            let mut error = ValidationError::new("idempotency_key_length");
+            error.message = Some(
+                format!("idempotency key must be 1 to {MAX_IDEMPOTENCY_KEY_LENGTH} bytes long")
+                    .into(),
+            );
+            errors.add("idempotency_key", error);
+        }
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Stable fingerprint of an update operation.
+///
+/// The operation is hashed through its JSON representation with object keys sorted, so that
+/// the fingerprint doesn't depend on hash map iteration order of named vectors and such.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct OperationFingerprint([u8; 32]);
+
+impl OperationFingerprint {
+    pub fn of(operation: &CollectionUpdateOperations) -> CollectionResult<Self> {
+        let value = serde_json::to_value(operation).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Failed to fingerprint operation for idempotency check: {err}"
+            ))
+        })?;
+        let mut hasher = Sha256::new();
+        hash_json_value(&value, &mut hasher);
+        Ok(Self(hasher.finalize().into()))
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+struct IdempotencyRecord {
+    fingerprint: OperationFingerprint,
+    operation_id: Option<u64>,
+    completed: bool,
+    /// Unix timestamp in seconds of when the operation was applied
+    applied_at: u64,
+}
+
+impl IdempotencyRecord {
+    fn to_update_result(&self) -> UpdateResult {
+        UpdateResult {
+            operation_id: self.operation_id,
+            status: if self.completed {
+                UpdateStatus::Completed
+            } else {
+                UpdateStatus::Acknowledged
+            },
+            clock_tag: None,
+            session_token: None,
+            invalid_points: Vec::new(),
+        }
+    }
+}
+
+/// Log of applied idempotency keys, records are appended on every keyed write.
+///
+/// The log is compacted to the remembered records once it grows to twice the capacity, so it
+/// stays bounded without rewriting the whole file on every write.
+struct IdempotencyLog {
+    path: PathBuf,
+    /// Log file opened for appending, `None` if records are only kept in memory
+    file: Option<File>,
+    /// Ordered from least to most recently used
+    records: IndexMap<IdempotencyKey, IdempotencyRecord>,
+    /// Number of records in the log file, including superseded ones
+    logged_records: usize,
+}
+
+impl IdempotencyLog {
+    fn open(path: PathBuf, capacity: usize, retention: Duration) -> CollectionResult<Self> {
+        let mut records = IndexMap::new();
+        if path.exists() {
+            let reader = BufReader::new(File::open(&path)?);
+            for line in reader.lines() {
+                // A record torn by a crash is only the last one, skip it
+                let Ok((key, record)) =
+                    serde_json::from_str::<(IdempotencyKey, IdempotencyRecord)>(&line?)
+                else {
+                    continue;
+                };
+                records.shift_remove(&key);
+                records.insert(key, record);
+            }
+        }
+
+        let mut log = Self {
+            path,
+            file: None,
+            records,
+            logged_records: 0,
+        };
+        log.evict(unix_now(), capacity, retention);
+        log.compact()?;
+        Ok(log)
+    }
+
+    /// Log which only keeps records in memory
+    fn in_memory(path: PathBuf) -> Self {
+        Self {
+            path,
+            file: None,
+            records: IndexMap::new(),
+            logged_records: 0,
+        }
+    }
+
+    fn evict(&mut self, now: u64, capacity: usize, retention: Duration) {
+        let retention = retention.as_secs();
+        self.records
+            .retain(|_, record| now.saturating_sub(record.applied_at) < retention);
+        while self.records.len() > capacity {
+            self.records.shift_remove_index(0);
+        }
+    }
+
+    fn append(&mut self, key: &IdempotencyKey, record: &IdempotencyRecord) -> CollectionResult<()> {
+        let mut line = serde_json::to_vec(&(key, record)).map_err(|err| {
+            CollectionError::service_error(format!("Failed to serialize idempotency key: {err}"))
+        })?;
+        line.push(b'\n');
+        if let Some(file) = &mut self.file {
+            file.write_all(&line)?;
+        }
+        self.logged_records += 1;
+        Ok(())
+    }
+
+    /// Rewrite the log with only the remembered records
+    fn compact(&mut self) -> CollectionResult<()> {
+        let tmp_path = self.path.with_extension("tmp");
+        {
+            let mut writer = BufWriter::new(File::create(&tmp_path)?);
+            for (key, record) in &self.records {
+                serde_json::to_writer(&mut writer, &(key, record)).map_err(|err| {
+                    CollectionError::service_error(format!(
+                        "Failed to serialize idempotency key: {err}"
+                    ))
+                })?;
+                writer.write_all(b"\n")?;
+            }
+            writer
+                .into_inner()
+                .map_err(|err| err.into_error())?
+                .sync_all()?;
+        }
+        std::fs::rename(&tmp_path, &self.path)?;
+
+        self.file = Some(OpenOptions::new().append(true).open(&self.path)?);
+        self.logged_records = self.records.len();
+        Ok(())
+    }
+}
+
+/// Bounded LRU of recently applied idempotency keys of a single shard.
+///
+/// Persisted in an append-only log in the shard directory, so that keys survive a restart of the
+/// peer.
+pub struct IdempotencyCache {
+    log: Mutex<IdempotencyLog>,
+    /// Locks held from checking a key until its operation is recorded, so that concurrent retries
+    /// with the same key are applied only once. Keys are striped over a fixed number of locks.
+    key_locks: Box<[TokioMutex<()>]>,
+    capacity: usize,
+    retention: Duration,
+}
+
+impl IdempotencyCache {
+    pub fn new(capacity: usize, retention: Duration, shard_path: &Path) -> CollectionResult<Self> {
+        let log =
+            IdempotencyLog::open(shard_path.join(IDEMPOTENCY_KEYS_FILE), capacity, retention)?;
+        Ok(Self::with_log(log, capacity, retention))
+    }
+
+    fn with_log(log: IdempotencyLog, capacity: usize, retention: Duration) -> Self {
+        Self {
+            log: Mutex::new(log),
+            key_locks: (0..IDEMPOTENCY_KEY_LOCKS)
+                .map(|_| TokioMutex::new(()))
+                .collect(),
+            capacity,
+            retention,
+        }
+    }
+
+    /// Load the cache of the given shard, starting empty if the persisted state is unreadable.
+    ///
+    /// Losing the remembered keys only weakens deduplication, so it must never fail shard load.
+    pub fn load_or_empty(shard_path: &Path) -> Self {
+        Self::new(
+            DEFAULT_IDEMPOTENCY_CACHE_CAPACITY,
+            DEFAULT_IDEMPOTENCY_RETENTION,
+            shard_path,
+        )
+        .unwrap_or_else(|err| {
+            log::warn!(
+                "Failed to load idempotency keys of shard {}, starting empty: {err}",
+                shard_path.display(),
+            );
+            let path = shard_path.join(IDEMPOTENCY_KEYS_FILE);
+            if let Err(err) = std::fs::remove_file(&path) {
+                log::warn!(
+                    "Failed to remove idempotency keys file {}: {err}",
+                    path.display()
+                );
+            }
+            Self::with_log(
+                IdempotencyLog::in_memory(path),
+                DEFAULT_IDEMPOTENCY_CACHE_CAPACITY,
+                DEFAULT_IDEMPOTENCY_RETENTION,
+            )
+        })
+    }
+
+    /// Lock the given key, until the guard is dropped.
+    ///
+    /// Must be held from [`IdempotencyCache::check`] until [`IdempotencyCache::record`] of an
+    /// operation, so that a concurrent retry sees the result instead of applying it again.
+    pub async fn lock_key(&self, key: &IdempotencyKey) -> TokioMutexGuard<'_, ()> {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        let index = hasher.finish() as usize % self.key_locks.len();
+        self.key_locks[index].lock().await
+    }
+
+    /// Check whether an operation with this key was already applied.
+    ///
+    /// Returns the prior result if the key was applied with the same operation, and an error if
+    /// it was applied with a different one.
+    pub fn check(
+        &self,
+        key: &IdempotencyKey,
+        fingerprint: &OperationFingerprint,
+    ) -> CollectionResult<Option<UpdateResult>> {
+        let now = unix_now();
+        let retention = self.retention.as_secs();
+
+        let log = self.log.lock();
+        let Some(record) = log.records.get(key) else {
+            return Ok(None);
+        };
+
+        if now.saturating_sub(record.applied_at) >= retention {
+            return Ok(None);
+        }
+
+        if record.fingerprint != *fingerprint {
+            return Err(CollectionError::bad_request(format!(
+                "Idempotency key {:?} was already used for a different operation",
+                key.as_str(),
+            )));
+        }
+
+        Ok(Some(record.to_update_result()))
+    }
+
+    /// Remember that an operation with this key was applied with the given result.
+    pub fn record(
+        &self,
+        key: IdempotencyKey,
+        fingerprint: OperationFingerprint,
+        result: &UpdateResult,
+    ) -> CollectionResult<()> {
+        let now = unix_now();
+        let record = IdempotencyRecord {
+            fingerprint,
+            operation_id: result.operation_id,
+            completed: result.status == UpdateStatus::Completed,
+            applied_at: now,
+        };
+
+        let mut log = self.log.lock();
+        log.append(&key, &record)?;
+        // Re-insert to move the key to the most recently used position
+        log.records.shift_remove(&key);
+        log.records.insert(key, record);
+        log.evict(now, self.capacity, self.retention);
+
+        if log.file.is_some() && log.logged_records >= 2 * self.capacity.max(1) {
+            log.compact()?;
+        }
+
+        Ok(())
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use api::rest::{Vector, VectorStruct};
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::operations::point_ops::{
+        PointInsertOperationsInternal, PointOperations, PointStruct,
+    };
+
+    fn upsert(id: u64, vectors: &[(&str, f32)]) -> CollectionUpdateOperations {
+        let vector: HashMap<_, _> = vectors
+            .iter()
+            .map(|(name, value)| (name.to_string(), Vector::Dense(vec![*value; 4])))
+            .collect();
+        let point = PointStruct {
+            id: id.into(),
+            vector: VectorStruct::Named(vector),
+            payload: None,
+            vector_metadata: None,
+        };
+        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+            PointInsertOperationsInternal::PointsList(vec![point]),
+        ))
+    }
+
+    fn completed(operation_id: u64) -> UpdateResult {
+        UpdateResult {
+            operation_id: Some(operation_id),
+            status: UpdateStatus::Completed,
+            clock_tag: None,
+            session_token: None,
+            invalid_points: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_map_order() {
+        let a = upsert(1, &[("image", 1.0), ("text", 2.0), ("audio", 3.0)]);
+        let b = upsert(1, &[("audio", 3.0), ("text", 2.0), ("image", 1.0)]);
+        let c = upsert(2, &[("audio", 3.0), ("text", 2.0), ("image", 1.0)]);
+
+        let fingerprint_a = OperationFingerprint::of(&a).unwrap();
+        assert_eq!(fingerprint_a, OperationFingerprint::of(&b).unwrap());
+        assert_ne!(fingerprint_a, OperationFingerprint::of(&c).unwrap());
+    }
+
+    #[test]
+    fn test_idempotency_cache() {
+        let dir = Builder::new().prefix("idempotency").tempdir().unwrap();
+        let cache = IdempotencyCache::new(2, DEFAULT_IDEMPOTENCY_RETENTION, dir.path()).unwrap();
+
+        let key = IdempotencyKey::new("retry-me");
+        let fingerprint = OperationFingerprint::of(&upsert(1, &[("", 1.0)])).unwrap();
+        let other_fingerprint = OperationFingerprint::of(&upsert(2, &[("", 1.0)])).unwrap();
+
+        assert!(cache.check(&key, &fingerprint).unwrap().is_none());
+        cache
+            .record(key.clone(), fingerprint, &completed(7))
+            .unwrap();
+
+        let prior = cache.check(&key, &fingerprint).unwrap().unwrap();
+        assert_eq!(prior.operation_id, Some(7));
+        assert_eq!(prior.status, UpdateStatus::Completed);
+
+        // Same key, different operation
+        assert!(cache.check(&key, &other_fingerprint).is_err());
+
+        // Survives reload
+        let cache = IdempotencyCache::new(2, DEFAULT_IDEMPOTENCY_RETENTION, dir.path()).unwrap();
+        assert!(cache.check(&key, &fingerprint).unwrap().is_some());
+
+        // Least recently used key is evicted over capacity
+        cache
+            .record(IdempotencyKey::new("b"), fingerprint, &completed(8))
+            .unwrap();
+        cache
+            .record(IdempotencyKey::new("c"), fingerprint, &completed(9))
+            .unwrap();
+        assert!(cache.check(&key, &fingerprint).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_idempotency_log_is_bounded() {
+        let dir = Builder::new().prefix("idempotency").tempdir().unwrap();
+        let cache = IdempotencyCache::new(4, DEFAULT_IDEMPOTENCY_RETENTION, dir.path()).unwrap();
+        let fingerprint = OperationFingerprint::of(&upsert(1, &[("", 1.0)])).unwrap();
+
+        for i in 0..100 {
+            cache
+                .record(
+                    IdempotencyKey::new(format!("key-{i}")),
+                    fingerprint,
+                    &completed(i),
+                )
+                .unwrap();
+        }
+
+        // Records are appended, and compacted once the log holds twice the capacity
+        let log = std::fs::read_to_string(dir.path().join(IDEMPOTENCY_KEYS_FILE)).unwrap();
+        assert!(log.lines().count() < 8);
+
+        // Only the most recent keys are remembered after reload, a torn record is skipped
+        std::fs::OpenOptions::new()
+            .append(true)
+            .open(dir.path().join(IDEMPOTENCY_KEYS_FILE))
+            .unwrap()
+            .write_all(b"[\"torn")
+            .unwrap();
+        let cache = IdempotencyCache::new(4, DEFAULT_IDEMPOTENCY_RETENTION, dir.path()).unwrap();
+        assert!(cache
+            .check(&IdempotencyKey::new("key-99"), &fingerprint)
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .check(&IdempotencyKey::new("key-95"), &fingerprint)
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_idempotency_key_lock() {
+        let dir = Builder::new().prefix("idempotency").tempdir().unwrap();
+        let cache = IdempotencyCache::load_or_empty(dir.path());
+        let key = IdempotencyKey::new("retry-me");
+
+        let guard = cache.lock_key(&key).await;
+        let concurrent = tokio::time::timeout(Duration::from_millis(50), cache.lock_key(&key));
+        assert!(concurrent.await.is_err());
+
+        drop(guard);
+        let concurrent = tokio::time::timeout(Duration::from_millis(50), cache.lock_key(&key));
+        assert!(concurrent.await.is_ok());
+    }
+}
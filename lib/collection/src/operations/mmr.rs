@@ -0,0 +1,232 @@
+//! Maximal Marginal Relevance, which diversifies results found by vector similarity.
+//!
+//! Points are selected greedily from the candidates, each time taking the one maximizing
+//! `lambda * relevance - (1 - lambda) * max_similarity`, where `relevance` is the score of the
+//! candidate normalized to `[0, 1]`, and `max_similarity` is the largest cosine similarity to the
+//! points selected before.
+
+use common::types::ScoreType;
+use segment::data_types::vectors::VectorRef;
+use segment::types::ScoredPoint;
+use sparse::common::sparse_vector::SparseVector;
+
+use crate::operations::rerank::{DEFAULT_CANDIDATES_FACTOR, MAX_RERANK_CANDIDATES};
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Number of candidates to diversify, to return `page_size` points
+pub fn mmr_candidates(page_size: usize) -> usize {
+    page_size
+        .saturating_mul(DEFAULT_CANDIDATES_FACTOR)
+        .clamp(page_size, MAX_RERANK_CANDIDATES.max(page_size))
+}
+
+/// Candidate vector, normalized so that the dot product is the cosine similarity
+enum NormalizedVector {
+    Dense(Vec<f32>),
+    Sparse(SparseVector),
+}
+
+impl NormalizedVector {
+    fn new(vector: VectorRef) -> CollectionResult<Self> {
+        match vector {
+            VectorRef::Dense(vector) => {
+                let norm = vector.iter().map(|x| x * x).sum::<f32>().sqrt();
+                Ok(Self::Dense(vector.iter().map(|x| x / norm).collect()))
+            }
+            VectorRef::Sparse(vector) => {
+                let norm = vector.values.iter().map(|x| x * x).sum::<f32>().sqrt();
+                let mut vector = vector.clone();
+                vector.values.iter_mut().for_each(|x| *x /= norm);
+                vector.sort_by_indices();
+                Ok(Self::Sparse(vector))
+            }
+            VectorRef::MultiDense(_) => Err(CollectionError::bad_input(
+                "Diversity of results is not supported for multivectors",
+            )),
+        }
+    }
+
+    fn similarity(&self, other: &Self) -> ScoreType {
+        let similarity = match (self, other) {
+            (Self::Dense(a), Self::Dense(b)) => a.iter().zip(b).map(|(a, b)| a * b).sum(),
+            (Self::Sparse(a), Self::Sparse(b)) => a.score(b).unwrap_or(0.0),
+            _ => 0.0,
+        };
+        // Zero vectors are not similar to anything
+        if similarity.is_nan() {
+            0.0
+        } else {
+            similarity
+        }
+    }
+}
+
+/// Select up to `limit` candidates by Maximal Marginal Relevance.
+///
+/// Candidates must be ordered by relevance, and contain the vector `vector_name`. Selected points
+/// keep their original score.
+///
+/// With `lambda = 1` the order of the candidates is kept, with `lambda = 0` only the first point
+/// is selected by relevance, and the rest are the least similar to the points selected before.
+pub fn mmr(
+    candidates: Vec<ScoredPoint>,
+    vector_name: &str,
+    lambda: f32,
+    limit: usize,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    if candidates.is_empty() || limit == 0 {
+        return Ok(Vec::new());
+    }
+
+    let vectors = candidates
+        .iter()
+        .map(|point| {
+            let vector = point
+                .vector
+                .as_ref()
+                .and_then(|vector| vector.get(vector_name))
+                .ok_or_else(|| {
+                    CollectionError::service_error(format!(
+                        "Vector `{vector_name}` of point {} is missing for diversity",
+                        point.id,
+                    ))
+                })?;
+            NormalizedVector::new(vector)
+        })
+        .collect::<CollectionResult<Vec<_>>>()?;
+
+    // Candidates are ordered by relevance, which also covers distances where smaller is better
+    let best_score = candidates[0].score;
+    let worst_score = candidates[candidates.len() - 1].score;
+    let relevance: Vec<_> = candidates
+        .iter()
+        .map(|point| {
+            if best_score != worst_score {
+                (point.score - worst_score) / (best_score - worst_score)
+            } else {
+                1.0
+            }
+        })
+        .collect();
+
+    let mut max_similarity = vec![ScoreType::NEG_INFINITY; candidates.len()];
+    let mut remaining: Vec<usize> = (0..candidates.len()).collect();
+    let mut selected = Vec::with_capacity(limit.min(candidates.len()));
+
+    while selected.len() < limit && !remaining.is_empty() {
+        let mmr_score = |idx: usize| {
+            let penalty = if selected.is_empty() {
+                0.0
+            } else {
+                max_similarity[idx]
+            };
+            lambda * relevance[idx] - (1.0 - lambda) * penalty
+        };
+
+        // Take the first best candidate, so that ties keep the relevance order
+        let mut best = 0;
+        for pos in 1..remaining.len() {
+            if mmr_score(remaining[pos]) > mmr_score(remaining[best]) {
+                best = pos;
+            }
+        }
+        let chosen = remaining.remove(best);
+
+        for &idx in &remaining {
+            let similarity = vectors[idx].similarity(&vectors[chosen]);
+            max_similarity[idx] = max_similarity[idx].max(similarity);
+        }
+        selected.push(chosen);
+    }
+
+    let mut candidates: Vec<_> = candidates.into_iter().map(Some).collect();
+    Ok(selected
+        .into_iter()
+        .filter_map(|idx| candidates[idx].take())
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::data_types::vectors::DEFAULT_VECTOR_NAME;
+
+    use super::*;
+
+    fn point(id: u64, score: ScoreType, vector: Vec<f32>) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: Some(vector.into()),
+            shard_key: None,
+            order_value: None,
+            distance: None,
+            provenance: None,
+        }
+    }
+
+    fn ids(points: &[ScoredPoint]) -> Vec<u64> {
+        points
+            .iter()
+            .map(|point| match point.id {
+                segment::types::ExtendedPointId::NumId(id) => id,
+                segment::types::ExtendedPointId::Uuid(_) => unreachable!(),
+            })
+            .collect()
+    }
+
+    /// Points 1 and 2 are near duplicates, point 3 is less relevant but different
+    fn candidates() -> Vec<ScoredPoint> {
+        vec![
+            point(1, 0.9, vec![1.0, 0.0]),
+            point(2, 0.89, vec![1.0, 0.01]),
+            point(3, 0.5, vec![0.0, 1.0]),
+        ]
+    }
+
+    #[test]
+    fn test_mmr_pure_relevance() {
+        let selected = mmr(candidates(), DEFAULT_VECTOR_NAME, 1.0, 3).unwrap();
+        assert_eq!(ids(&selected), vec![1, 2, 3]);
+        assert_eq!(selected[1].score, 0.89);
+
+        let selected = mmr(candidates(), DEFAULT_VECTOR_NAME, 1.0, 2).unwrap();
+        assert_eq!(ids(&selected), vec![1, 2]);
+    }
+
+    #[test]
+    fn test_mmr_diversity() {
+        let selected = mmr(candidates(), DEFAULT_VECTOR_NAME, 0.5, 2).unwrap();
+        assert_eq!(ids(&selected), vec![1, 3]);
+
+        let selected = mmr(candidates(), DEFAULT_VECTOR_NAME, 0.0, 3).unwrap();
+        assert_eq!(ids(&selected), vec![1, 3, 2]);
+    }
+
+    #[test]
+    fn test_mmr_smaller_is_better() {
+        // Euclidean distances
+        let candidates = vec![
+            point(1, 0.1, vec![1.0, 0.0]),
+            point(2, 0.11, vec![1.0, 0.01]),
+            point(3, 0.5, vec![0.0, 1.0]),
+        ];
+        let selected = mmr(candidates, DEFAULT_VECTOR_NAME, 0.5, 2).unwrap();
+        assert_eq!(ids(&selected), vec![1, 3]);
+    }
+
+    #[test]
+    fn test_mmr_missing_vector() {
+        let mut candidates = candidates();
+        candidates[1].vector = None;
+        assert!(mmr(candidates, DEFAULT_VECTOR_NAME, 0.5, 2).is_err());
+    }
+
+    #[test]
+    fn test_mmr_candidates() {
+        assert_eq!(mmr_candidates(10), 40);
+        assert_eq!(mmr_candidates(500), 1000);
+        assert_eq!(mmr_candidates(2000), 2000);
+    }
+}
@@ -3,10 +3,13 @@ pub mod config_diff;
 pub mod consistency_params;
 pub mod conversions;
 pub mod conversions_rest;
+pub mod idempotency;
+pub mod mmr;
 pub mod operation_effect;
 pub mod payload_ops;
 pub mod point_ops;
 pub mod query_enum;
+pub mod rerank;
 pub mod shard_selector_internal;
 pub mod shared_storage_config;
 pub mod snapshot_ops;
@@ -26,7 +29,9 @@ use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
+use crate::config::CollectionParams;
 use crate::hash_ring::{HashRingRouter, ShardIds};
+use crate::operations::types::CollectionError;
 use crate::shards::shard::{PeerId, ShardId};
 
 pub type ClockToken = u64;
@@ -161,6 +166,26 @@ impl CollectionUpdateOperations {
         )
     }
 
+    /// Set the timestamp versions of upserted points are derived from, if this is an upsert
+    pub fn with_version_timestamp(self, timestamp: u64) -> Self {
+        match self {
+            Self::PointOperation(operation) => {
+                Self::PointOperation(operation.with_version_timestamp(timestamp))
+            }
+            operation => operation,
+        }
+    }
+
+    /// Number of expected point versions, if this is a versioned upsert
+    pub fn expected_versions_count(&self) -> Option<usize> {
+        match self {
+            Self::PointOperation(point_ops::PointOperations::UpsertPointsVersioned(op)) => {
+                Some(op.expected_versions.len())
+            }
+            _ => None,
+        }
+    }
+
     pub fn point_ids(&self) -> Vec<PointIdType> {
         match self {
             Self::PointOperation(op) => op.point_ids(),
@@ -181,6 +206,46 @@ impl CollectionUpdateOperations {
             Self::FieldIndexOperation(_) => (),
         }
     }
+
+    /// Check vectors of all points in the operation against the collection parameters.
+    ///
+    /// Returns every point with an invalid vector, together with the error for that point.
+    pub fn invalid_vectors(
+        &self,
+        params: &CollectionParams,
+    ) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::PointOperation(op) => op.invalid_vectors(params),
+            Self::VectorOperation(op) => op.invalid_vectors(params),
+            Self::PayloadOperation(_) => Vec::new(),
+            Self::FieldIndexOperation(_) => Vec::new(),
+        }
+    }
+
+    /// Check payloads of all upserted points against the max payload size of a point, in bytes.
+    ///
+    /// Returns every point with an oversized payload, together with the error for that point.
+    pub fn oversized_payloads(&self, max_size: usize) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::PointOperation(op) => op.oversized_payloads(max_size),
+            Self::VectorOperation(_) => Vec::new(),
+            Self::PayloadOperation(_) => Vec::new(),
+            Self::FieldIndexOperation(_) => Vec::new(),
+        }
+    }
+
+    /// Remove zero vectors which the collection excludes by its zero vector policy.
+    ///
+    /// Vectors removed from updates of existing points are deleted from these points by the
+    /// same operation.
+    pub fn exclude_zero_vectors(&mut self, params: &CollectionParams) {
+        match self {
+            Self::PointOperation(op) => op.exclude_zero_vectors(params),
+            Self::VectorOperation(op) => op.exclude_zero_vectors(params),
+            Self::PayloadOperation(_) => (),
+            Self::FieldIndexOperation(_) => (),
+        }
+    }
 }
 
 /// A mapping of operation to shard.
@@ -417,6 +482,26 @@ mod tests {
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
             let upsert = Self::UpsertPoints(PointInsertOperationsInternal::PointsList(Vec::new()));
+            let upsert_versioned = Self::UpsertPointsVersioned(VersionedInsertOperationInternal {
+                points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                expected_versions: Vec::new(),
+                timestamp: Some(0),
+                versions: Vec::new(),
+            });
+            let upsert_with_ttl = Self::UpsertPointsWithTtl(TtlInsertOperationInternal {
+                upsert: VersionedInsertOperationInternal {
+                    points_op: PointInsertOperationsInternal::PointsList(Vec::new()),
+                    expected_versions: Vec::new(),
+                    timestamp: None,
+                    versions: Vec::new(),
+                },
+                stamp: TtlStamp {
+                    key: "created_at".to_string(),
+                    timestamp: 0,
+                    set_on_insert: true,
+                    refresh_on_update: false,
+                },
+            });
             let delete = Self::DeletePoints { ids: Vec::new() };
 
             let delete_by_filter = Self::DeletePointsByFilter(Filter {
@@ -430,10 +515,13 @@ mod tests {
                 from_id: None,
                 to_id: None,
                 points: Vec::new(),
+                versions: Vec::new(),
             });
 
             prop_oneof![
                 Just(upsert),
+                Just(upsert_versioned),
+                Just(upsert_with_ttl),
                 Just(delete),
                 Just(delete_by_filter),
                 Just(sync),
@@ -447,7 +535,7 @@ mod tests {
         type Strategy = BoxedStrategy<Self>;
 
         fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
-            let update = Self::UpdateVectors(UpdateVectorsOp { points: Vec::new() });
+            let update = Self::UpdateVectors(UpdateVectorsOp::default());
 
             let delete = Self::DeleteVectors(
                 PointIdsList {
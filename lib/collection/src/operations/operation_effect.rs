@@ -48,6 +48,12 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
             point_ops::PointOperations::UpsertPoints(insert_operations) => {
                 insert_operations.estimate_effect_area()
             }
+            point_ops::PointOperations::UpsertPointsVersioned(versioned_operation) => {
+                versioned_operation.points_op.estimate_effect_area()
+            }
+            point_ops::PointOperations::UpsertPointsWithTtl(ttl_operation) => {
+                ttl_operation.upsert.points_op.estimate_effect_area()
+            }
             point_ops::PointOperations::DeletePoints { ids } => {
                 OperationEffectArea::Points(ids.clone())
             }
@@ -68,9 +74,8 @@ impl EstimateOperationEffectArea for point_ops::PointOperations {
 impl EstimateOperationEffectArea for vector_ops::VectorOperations {
     fn estimate_effect_area(&self) -> OperationEffectArea {
         match self {
-            vector_ops::VectorOperations::UpdateVectors(update_operation) => {
-                let ids = update_operation.points.iter().map(|p| p.id).collect();
-                OperationEffectArea::Points(ids)
+            vector_ops::VectorOperations::UpdateVectors(_) => {
+                OperationEffectArea::Points(self.point_ids())
             }
             vector_ops::VectorOperations::DeleteVectors(ids, _) => {
                 OperationEffectArea::Points(ids.points.clone())
@@ -118,7 +123,8 @@ impl EstimateOperationEffectArea for PayloadOps {
             }
             PayloadOps::ClearPayload { points } => OperationEffectArea::Points(points.clone()),
             PayloadOps::ClearPayloadByFilter(filter) => OperationEffectArea::Filter(filter.clone()),
-            PayloadOps::OverwritePayload(set_payload) => {
+            PayloadOps::OverwritePayload(set_payload)
+            | PayloadOps::MergePatchPayload(set_payload) => {
                 if let Some(points) = &set_payload.points {
                     OperationEffectArea::Points(points.clone())
                 } else if let Some(filter) = &set_payload.filter {
@@ -127,6 +133,15 @@ impl EstimateOperationEffectArea for PayloadOps {
                     OperationEffectArea::Empty
                 }
             }
+            PayloadOps::CopyPayloadField(copy_field) => {
+                if let Some(points) = &copy_field.points {
+                    OperationEffectArea::Points(points.clone())
+                } else if let Some(filter) = &copy_field.filter {
+                    OperationEffectArea::Filter(filter.clone())
+                } else {
+                    OperationEffectArea::Empty
+                }
+            }
         }
     }
 }
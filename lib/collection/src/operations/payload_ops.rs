@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+
 use api::rest::ShardKeySelector;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
@@ -5,7 +7,7 @@ use segment::types::{Filter, Payload, PayloadKeyType, PointIdType};
 use serde;
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 use super::{split_iter_by_shard, OperationToShard, SplitByShard};
 use crate::hash_ring::HashRingRouter;
@@ -133,6 +135,101 @@ impl TryFrom<DeletePayloadShadow> for DeletePayload {
     }
 }
 
+/// Whether the value of a payload field is copied or moved to another field
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum PayloadCopyMode {
+    /// Keep the source field
+    #[default]
+    Copy,
+    /// Remove the source field, e.g. to rename a field
+    Move,
+}
+
+/// This data structure is used in API interface and applied across multiple shards
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(try_from = "CopyPayloadFieldShadow")]
+pub struct CopyPayloadField {
+    /// Field to copy the value from, points without this field are skipped
+    #[validate(custom(function = "validate_field_path"))]
+    pub from: JsonPath,
+    /// Field to copy the value to, the existing value is overwritten
+    #[validate(custom(function = "validate_field_path"))]
+    pub to: JsonPath,
+    #[serde(default)]
+    pub mode: PayloadCopyMode,
+    /// Copies the field of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Copies the field of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// This data structure is used inside shard operations queue
+/// and supposed to be written into WAL of individual shard.
+///
+/// Unlike `CopyPayloadField` it does not contain `shard_key` field
+/// as individual shard does not need to know about shard key
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+pub struct CopyPayloadFieldOp {
+    /// Field to copy the value from, points without this field are skipped
+    #[validate(custom(function = "validate_field_path"))]
+    pub from: JsonPath,
+    /// Field to copy the value to, the existing value is overwritten
+    #[validate(custom(function = "validate_field_path"))]
+    pub to: JsonPath,
+    pub mode: PayloadCopyMode,
+    /// Copies the field of each point in this list
+    pub points: Option<Vec<PointIdType>>,
+    /// Copies the field of points that satisfy this filter condition
+    pub filter: Option<Filter>,
+}
+
+#[derive(Deserialize)]
+struct CopyPayloadFieldShadow {
+    pub from: JsonPath,
+    pub to: JsonPath,
+    #[serde(default)]
+    pub mode: PayloadCopyMode,
+    pub points: Option<Vec<PointIdType>>,
+    pub filter: Option<Filter>,
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+impl TryFrom<CopyPayloadFieldShadow> for CopyPayloadField {
+    type Error = PointsSelectorValidationError;
+
+    fn try_from(value: CopyPayloadFieldShadow) -> Result<Self, Self::Error> {
+        if value.points.is_some() || value.filter.is_some() {
+            Ok(CopyPayloadField {
+                from: value.from,
+                to: value.to,
+                mode: value.mode,
+                points: value.points,
+                filter: value.filter,
+                shard_key: value.shard_key,
+            })
+        } else {
+            Err(PointsSelectorValidationError)
+        }
+    }
+}
+
+/// Values are copied between fields of nested objects, a path into arrays would be ambiguous.
+fn validate_field_path(path: &JsonPath) -> Result<(), ValidationError> {
+    if path.is_key_path() {
+        Ok(())
+    } else {
+        let mut err = ValidationError::new("field_path");
+        err.add_param(Cow::from("path"), &path.to_string());
+        err.message = Some(Cow::from(
+            "must only consist of keys, without array indices",
+        ));
+        Err(err)
+    }
+}
+
 /// Define operations description for point payloads manipulation
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
@@ -148,6 +245,11 @@ pub enum PayloadOps {
     ClearPayloadByFilter(Filter),
     /// Overwrite full payload with given keys
     OverwritePayload(SetPayloadOp),
+    /// Apply payload as JSON merge patch (RFC 7386): nested objects are merged recursively,
+    /// `null` values remove keys and any other value, including arrays, replaces the stored one
+    MergePatchPayload(SetPayloadOp),
+    /// Copy or move the value of a field to another field, skips points without the field
+    CopyPayloadField(CopyPayloadFieldOp),
 }
 
 impl PayloadOps {
@@ -158,6 +260,8 @@ impl PayloadOps {
             PayloadOps::ClearPayload { .. } => false,
             PayloadOps::ClearPayloadByFilter(_) => false,
             PayloadOps::OverwritePayload(_) => true,
+            PayloadOps::MergePatchPayload(_) => true,
+            PayloadOps::CopyPayloadField(_) => true,
         }
     }
 
@@ -168,6 +272,8 @@ impl PayloadOps {
             Self::ClearPayload { points } => points.clone(),
             Self::ClearPayloadByFilter(_) => Vec::new(),
             Self::OverwritePayload(op) => op.points.clone().unwrap_or(Vec::new()),
+            Self::MergePatchPayload(op) => op.points.clone().unwrap_or(Vec::new()),
+            Self::CopyPayloadField(op) => op.points.clone().unwrap_or(Vec::new()),
         }
     }
 
@@ -181,6 +287,8 @@ impl PayloadOps {
             Self::ClearPayload { points } => points.retain(filter),
             Self::ClearPayloadByFilter(_) => (),
             Self::OverwritePayload(op) => retain_opt(op.points.as_mut(), filter),
+            Self::MergePatchPayload(op) => retain_opt(op.points.as_mut(), filter),
+            Self::CopyPayloadField(op) => retain_opt(op.points.as_mut(), filter),
         }
     }
 }
@@ -202,6 +310,8 @@ impl Validate for PayloadOps {
             PayloadOps::ClearPayload { .. } => Ok(()),
             PayloadOps::ClearPayloadByFilter(_) => Ok(()),
             PayloadOps::OverwritePayload(operation) => operation.validate(),
+            PayloadOps::MergePatchPayload(operation) => operation.validate(),
+            PayloadOps::CopyPayloadField(operation) => operation.validate(),
         }
     }
 }
@@ -221,6 +331,32 @@ impl SplitByShard for PayloadOps {
             PayloadOps::OverwritePayload(operation) => operation
                 .split_by_shard(ring)
                 .map(PayloadOps::OverwritePayload),
+            PayloadOps::MergePatchPayload(operation) => operation
+                .split_by_shard(ring)
+                .map(PayloadOps::MergePatchPayload),
+            PayloadOps::CopyPayloadField(operation) => operation
+                .split_by_shard(ring)
+                .map(PayloadOps::CopyPayloadField),
+        }
+    }
+}
+
+impl SplitByShard for CopyPayloadFieldOp {
+    fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
+        match (&self.points, &self.filter) {
+            (Some(_), _) => {
+                split_iter_by_shard(self.points.unwrap(), |id| *id, ring).map(|points| {
+                    CopyPayloadFieldOp {
+                        points: Some(points),
+                        from: self.from.clone(),
+                        to: self.to.clone(),
+                        mode: self.mode,
+                        filter: self.filter.clone(),
+                    }
+                })
+            }
+            (None, Some(_)) => OperationToShard::to_all(self),
+            (None, None) => OperationToShard::to_none(),
         }
     }
 }
@@ -1,15 +1,20 @@
 use std::borrow::Cow;
+use std::collections::hash_map::Entry;
 use std::collections::{HashMap, HashSet};
-use std::iter;
+use std::{iter, mem};
 
 use api::rest::{BatchVectorStruct, ShardKeySelector, VectorStruct};
 use itertools::izip;
 use schemars::JsonSchema;
 use segment::common::utils::transpose_map_into_named_vector;
 use segment::data_types::named_vectors::NamedVectors;
-use segment::data_types::vectors::{MultiDenseVectorInternal, Vector, DEFAULT_VECTOR_NAME};
-use segment::types::{Filter, Payload, PointIdType};
+use segment::data_types::vectors::{
+    BatchVectorStructInternal, DenseVector, MultiDenseVectorInternal, Vector, VectorElementType,
+    VectorStructInternal, DEFAULT_VECTOR_NAME,
+};
+use segment::types::{Filter, Payload, PointIdType, SeqNumberType, VectorMetadata};
 use serde::{Deserialize, Serialize};
+use sparse::common::sparse_vector::SparseVector;
 use strum::{EnumDiscriminants, EnumIter};
 use validator::Validate;
 
@@ -19,8 +24,11 @@ use super::{
     point_to_shards, split_iter_by_shard, CollectionUpdateOperations, OperationToShard,
     SplitByShard,
 };
+use crate::config::CollectionParams;
 use crate::hash_ring::HashRingRouter;
-use crate::operations::types::Record;
+use crate::operations::types::{
+    CollectionError, CollectionResult, Record, VectorParams, ZeroVectorPolicy,
+};
 use crate::operations::{payload_ops, vector_ops};
 use crate::shards::shard::ShardId;
 
@@ -41,6 +49,24 @@ pub enum WriteOrdering {
     Strong,
 }
 
+/// Defines how many replicas of a shard must acknowledge a write operation
+/// If not specified, `write_consistency_factor` of the collection is used
+///
+/// * `local` - only the replica on the peer coordinating the update, or any single replica if that peer has none
+///
+/// * `majority` - more than half of all replicas of the shard
+///
+/// * `all` - all replicas of the shard
+///
+/// If there are not enough healthy replicas to satisfy the level, the operation fails without being applied
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum WriteConsistency {
+    Local,
+    Majority,
+    All,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, JsonSchema, Validate)]
 #[serde(rename_all = "snake_case")]
 pub struct PointStruct {
@@ -52,6 +78,9 @@ pub struct PointStruct {
     pub vector: VectorStruct,
     /// Payload values (optional)
     pub payload: Option<Payload>,
+    /// Metadata of individual vectors of the point, by vector name (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vector_metadata: Option<VectorMetadata>,
 }
 
 /// Warn: panics if the vector is empty
@@ -63,8 +92,10 @@ impl TryFrom<Record> for PointStruct {
             id,
             payload,
             vector,
+            vector_metadata,
             shard_key: _,
             order_value: _,
+            version: _,
         } = record;
 
         if vector.is_none() {
@@ -75,6 +106,7 @@ impl TryFrom<Record> for PointStruct {
             id,
             payload,
             vector: api::rest::VectorStruct::from(vector.unwrap()),
+            vector_metadata,
         })
     }
 }
@@ -130,6 +162,29 @@ impl Validate for PointsSelector {
     }
 }
 
+/// How an upsert handles multiple points with the same id
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone)]
+#[serde(rename_all = "snake_case")]
+pub enum DuplicateIdsPolicy {
+    /// Only the last point with the id is upserted
+    #[default]
+    LastWins,
+    /// Only the first point with the id is upserted
+    FirstWins,
+    /// The whole upsert is rejected
+    Error,
+}
+
+/// Expected version of a point, as returned in its record on read
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct PointVersion {
+    /// Point id
+    pub id: PointIdType,
+    /// Version the point is expected to have
+    pub version: SeqNumberType,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
 pub struct PointSyncOperation {
     /// Minimal id of the sync range
@@ -137,6 +192,9 @@ pub struct PointSyncOperation {
     /// Maximal id og
     pub to_id: Option<PointIdType>,
     pub points: Vec<PointStruct>,
+    /// Versions the points have in the source shard, assigned to them as they are
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<PointVersion>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, Validate, JsonSchema)]
@@ -145,6 +203,13 @@ pub struct PointsBatch {
     pub batch: Batch,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, the upsert is only applied if every listed point still has the given
+    /// version, the whole operation fails otherwise. All points must be in the same shard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_versions: Option<Vec<PointVersion>>,
+    /// How points with the same id are handled. Default: `last_wins`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_ids: Option<DuplicateIdsPolicy>,
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone, JsonSchema, Validate)]
@@ -153,6 +218,13 @@ pub struct PointsList {
     pub points: Vec<PointStruct>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub shard_key: Option<ShardKeySelector>,
+    /// If specified, the upsert is only applied if every listed point still has the given
+    /// version, the whole operation fails otherwise. All points must be in the same shard
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expected_versions: Option<Vec<PointVersion>>,
+    /// How points with the same id are handled. Default: `last_wins`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub duplicate_ids: Option<DuplicateIdsPolicy>,
 }
 
 impl<'de> serde::Deserialize<'de> for PointInsertOperations {
@@ -203,11 +275,40 @@ impl Validate for PointInsertOperations {
 }
 
 impl PointInsertOperations {
-    pub fn decompose(self) -> (Option<ShardKeySelector>, PointInsertOperationsInternal) {
-        match self {
-            PointInsertOperations::PointsBatch(batch) => (batch.shard_key, batch.batch.into()),
-            PointInsertOperations::PointsList(list) => (list.shard_key, list.points.into()),
-        }
+    /// Split into the shard key and the operation to apply.
+    ///
+    /// Points with the same id are resolved here, before the operation is split by shard and
+    /// replicated, so that every replica applies the same points.
+    pub fn decompose(self) -> CollectionResult<(Option<ShardKeySelector>, PointOperations)> {
+        let (shard_key, expected_versions, duplicate_ids, mut points_op) = match self {
+            PointInsertOperations::PointsBatch(batch) => (
+                batch.shard_key,
+                batch.expected_versions,
+                batch.duplicate_ids,
+                PointInsertOperationsInternal::from(batch.batch),
+            ),
+            PointInsertOperations::PointsList(list) => (
+                list.shard_key,
+                list.expected_versions,
+                list.duplicate_ids,
+                PointInsertOperationsInternal::from(list.points),
+            ),
+        };
+        points_op.deduplicate_ids(duplicate_ids.unwrap_or_default())?;
+
+        let operation = match expected_versions {
+            Some(expected_versions) => {
+                PointOperations::UpsertPointsVersioned(VersionedInsertOperationInternal {
+                    points_op,
+                    expected_versions,
+                    timestamp: None,
+                    versions: Vec::new(),
+                })
+            }
+            None => PointOperations::UpsertPoints(points_op),
+        };
+
+        Ok((shard_key, operation))
     }
 }
 
@@ -234,13 +335,54 @@ impl PointInsertOperationsInternal {
     pub fn retain_point_ids<F>(&mut self, filter: F)
     where
         F: Fn(&PointIdType) -> bool,
+    {
+        self.retain_points(|_, id| filter(id));
+    }
+
+    /// Resolve points with the same id by the policy.
+    ///
+    /// Only the order of the points decides which point is kept, so the result is the same
+    /// however the operation is applied later.
+    pub fn deduplicate_ids(&mut self, policy: DuplicateIdsPolicy) -> CollectionResult<()> {
+        let ids = self.point_ids();
+        let mut kept_indices: HashMap<PointIdType, usize> = HashMap::with_capacity(ids.len());
+        for (index, id) in ids.iter().enumerate() {
+            match kept_indices.entry(*id) {
+                Entry::Vacant(entry) => {
+                    entry.insert(index);
+                }
+                Entry::Occupied(mut entry) => match policy {
+                    DuplicateIdsPolicy::LastWins => {
+                        entry.insert(index);
+                    }
+                    DuplicateIdsPolicy::FirstWins => {}
+                    DuplicateIdsPolicy::Error => {
+                        return Err(CollectionError::bad_input(format!(
+                            "Point {id} occurs more than once in the upsert"
+                        )));
+                    }
+                },
+            }
+        }
+
+        if kept_indices.len() < ids.len() {
+            let kept_indices: HashSet<usize> = kept_indices.into_values().collect();
+            self.retain_points(|index, _| kept_indices.contains(&index));
+        }
+        Ok(())
+    }
+
+    /// Keep the points for which `filter` returns true, given the position and the id of each point
+    fn retain_points<F>(&mut self, mut filter: F)
+    where
+        F: FnMut(usize, &PointIdType) -> bool,
     {
         match self {
             Self::PointsBatch(batch) => {
                 let mut retain_indices = HashSet::new();
 
                 retain_with_index(&mut batch.ids, |index, id| {
-                    if filter(id) {
+                    if filter(index, id) {
                         retain_indices.insert(index);
                         true
                     } else {
@@ -273,7 +415,115 @@ impl PointInsertOperationsInternal {
                 }
             }
 
-            Self::PointsList(points) => points.retain(|point| filter(&point.id)),
+            Self::PointsList(points) => {
+                retain_with_index(points, |index, point| filter(index, &point.id));
+            }
+        }
+    }
+
+    /// Check vectors of all points against the collection parameters.
+    ///
+    /// Returns every point with an invalid vector, together with the error for that point.
+    pub fn invalid_vectors(
+        &self,
+        params: &CollectionParams,
+    ) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::PointsBatch(batch) => batch
+                .ids
+                .iter()
+                .enumerate()
+                .filter_map(|(index, id)| {
+                    let result = match &batch.vectors {
+                        BatchVectorStruct::Single(vectors) => {
+                            vectors.get(index).map_or(Ok(()), |vector| {
+                                check_dense_vector(params, *id, DEFAULT_VECTOR_NAME, vector)
+                            })
+                        }
+                        BatchVectorStruct::MultiDense(vectors) => {
+                            vectors.get(index).map_or(Ok(()), |vector| {
+                                check_multi_dense_vector(params, *id, DEFAULT_VECTOR_NAME, vector)
+                            })
+                        }
+                        BatchVectorStruct::Named(vectors) => {
+                            vectors.iter().try_for_each(|(name, vectors)| {
+                                vectors.get(index).map_or(Ok(()), |vector| {
+                                    check_vector(params, *id, name, vector)
+                                })
+                            })
+                        }
+                        BatchVectorStruct::Document(_) => Ok(()),
+                    };
+                    result.err().map(|err| (*id, err))
+                })
+                .collect(),
+            Self::PointsList(points) => invalid_point_vectors(params, points),
+        }
+    }
+
+    /// Check payloads of all points against the max payload size of a point, in bytes.
+    ///
+    /// Returns every point with an oversized payload, together with the error for that point.
+    pub fn oversized_payloads(&self, max_size: usize) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::PointsBatch(batch) => batch
+                .ids
+                .iter()
+                .zip(batch.payloads.iter().flatten())
+                .filter_map(|(id, payload)| {
+                    check_payload_size(*id, payload.as_ref()?, max_size)
+                        .err()
+                        .map(|err| (*id, err))
+                })
+                .collect(),
+            Self::PointsList(points) => oversized_point_payloads(points, max_size),
+        }
+    }
+
+    /// Remove zero vectors which the collection excludes by its zero vector policy.
+    ///
+    /// Upserts replace all vectors of a point, so excluded vectors are simply not stored.
+    pub fn exclude_zero_vectors(&mut self, params: &CollectionParams) {
+        if let Self::PointsBatch(batch) = self {
+            if !has_excluded_zero_batch_vectors(params, &batch.vectors) {
+                return;
+            }
+            let points = mem::replace(self, Self::PointsList(Vec::new())).into_point_list();
+            *self = Self::PointsList(points);
+        }
+
+        if let Self::PointsList(points) = self {
+            exclude_zero_point_vectors(params, points);
+        }
+    }
+
+    pub fn into_point_list(self) -> Vec<PointStruct> {
+        match self {
+            Self::PointsBatch(batch) => {
+                let batch_vectors: BatchVectorStructInternal = batch.vectors.into();
+                let all_vectors = batch_vectors.into_all_vectors(batch.ids.len());
+                let vectors_iter = batch.ids.into_iter().zip(all_vectors);
+                match batch.payloads {
+                    None => vectors_iter
+                        .map(|(id, vectors)| PointStruct {
+                            id,
+                            vector: VectorStructInternal::from(vectors).into(),
+                            payload: None,
+                            vector_metadata: None,
+                        })
+                        .collect(),
+                    Some(payloads) => vectors_iter
+                        .zip(payloads)
+                        .map(|((id, vectors), payload)| PointStruct {
+                            id,
+                            vector: VectorStructInternal::from(vectors).into(),
+                            payload,
+                            vector_metadata: None,
+                        })
+                        .collect(),
+                }
+            }
+            Self::PointsList(points) => points,
         }
     }
 
@@ -282,7 +532,7 @@ impl PointInsertOperationsInternal {
 
         match self {
             Self::PointsBatch(batch) => {
-                let mut update_vectors = UpdateVectorsOp { points: Vec::new() };
+                let mut update_vectors = UpdateVectorsOp::default();
 
                 match batch.vectors {
                     BatchVectorStruct::Single(vectors) => {
@@ -370,7 +620,7 @@ impl PointInsertOperationsInternal {
             }
 
             Self::PointsList(points) => {
-                let mut update_vectors = UpdateVectorsOp { points: Vec::new() };
+                let mut update_vectors = UpdateVectorsOp::default();
 
                 for point in points {
                     update_vectors.points.push(PointVectors {
@@ -508,6 +758,8 @@ impl From<Batch> for PointInsertOperations {
         PointInsertOperations::PointsBatch(PointsBatch {
             batch,
             shard_key: None,
+            expected_versions: None,
+            duplicate_ids: None,
         })
     }
 }
@@ -517,6 +769,8 @@ impl From<Vec<PointStruct>> for PointInsertOperations {
         PointInsertOperations::PointsList(PointsList {
             points,
             shard_key: None,
+            expected_versions: None,
+            duplicate_ids: None,
         })
     }
 }
@@ -533,12 +787,135 @@ impl From<Vec<PointStruct>> for PointInsertOperationsInternal {
     }
 }
 
+/// Upsert which assigns versions to points, and is only applied if points were not changed since
+/// they were read
+///
+/// Versions are checked by each shard on its own. When split, each shard only gets the versions
+/// of its own points, so a collection only accepts versioned upserts targeting a single shard.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct VersionedInsertOperationInternal {
+    pub points_op: PointInsertOperationsInternal,
+    /// Versions the points are expected to have, a deleted point has no version and never matches
+    pub expected_versions: Vec<PointVersion>,
+    /// Unix timestamp in microseconds, taken once by the replica set sending the upsert to all
+    /// replicas. Points get at least this version, so reinserted points never get an old one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Versions assigned to points as they are, when points are copied between shards
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub versions: Vec<PointVersion>,
+}
+
+impl VersionedInsertOperationInternal {
+    pub fn point_ids(&self) -> Vec<PointIdType> {
+        self.points_op.point_ids()
+    }
+
+    pub fn retain_point_ids<F>(&mut self, filter: F)
+    where
+        F: Fn(&PointIdType) -> bool,
+    {
+        self.expected_versions
+            .retain(|expected| filter(&expected.id));
+        self.versions.retain(|version| filter(&version.id));
+        self.points_op.retain_point_ids(filter);
+    }
+
+    pub fn invalid_vectors(
+        &self,
+        params: &CollectionParams,
+    ) -> Vec<(PointIdType, CollectionError)> {
+        self.points_op.invalid_vectors(params)
+    }
+
+    pub fn oversized_payloads(&self, max_size: usize) -> Vec<(PointIdType, CollectionError)> {
+        self.points_op.oversized_payloads(max_size)
+    }
+}
+
+impl SplitByShard for VersionedInsertOperationInternal {
+    fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
+        let Self {
+            points_op,
+            expected_versions,
+            timestamp,
+            versions,
+        } = self;
+
+        let split_versions = |versions: Vec<PointVersion>| {
+            let mut versions_by_shard: HashMap<ShardId, Vec<PointVersion>> = HashMap::new();
+            for version in versions {
+                for shard_id in point_to_shards(&version.id, ring) {
+                    versions_by_shard.entry(shard_id).or_default().push(version);
+                }
+            }
+            versions_by_shard
+        };
+        let mut expected_versions_by_shard = split_versions(expected_versions);
+        let mut versions_by_shard = split_versions(versions);
+
+        match points_op.split_by_shard(ring) {
+            OperationToShard::ByShard(by_shard) => {
+                OperationToShard::by_shard(by_shard.into_iter().map(|(shard_id, points_op)| {
+                    let expected_versions = expected_versions_by_shard
+                        .remove(&shard_id)
+                        .unwrap_or_default();
+                    let versions = versions_by_shard.remove(&shard_id).unwrap_or_default();
+                    (
+                        shard_id,
+                        Self {
+                            points_op,
+                            expected_versions,
+                            timestamp,
+                            versions,
+                        },
+                    )
+                }))
+            }
+            OperationToShard::ToAll(points_op) => OperationToShard::to_all(Self {
+                points_op,
+                expected_versions: expected_versions_by_shard.into_values().flatten().collect(),
+                timestamp,
+                versions: versions_by_shard.into_values().flatten().collect(),
+            }),
+        }
+    }
+}
+
+/// TTL timestamp to set on upserted points which don't set it in their payload
+///
+/// Whether a point exists, and its current timestamp, are only known to the shard applying the
+/// upsert, so the timestamp of each point is resolved there.
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TtlStamp {
+    /// Top-level payload key of the timestamp
+    pub key: String,
+    /// Unix timestamp in seconds, taken once for the whole operation
+    pub timestamp: u64,
+    /// Set the timestamp on points which don't exist yet
+    pub set_on_insert: bool,
+    /// Set the timestamp on existing points, instead of keeping their current one
+    pub refresh_on_update: bool,
+}
+
+/// Upsert which sets a TTL timestamp on points which don't set it explicitly
+#[derive(Clone, Debug, PartialEq, Deserialize, Serialize)]
+pub struct TtlInsertOperationInternal {
+    /// Versions are only checked if there are any
+    pub upsert: VersionedInsertOperationInternal,
+    pub stamp: TtlStamp,
+}
+
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
 #[strum_discriminants(derive(EnumIter))]
 #[serde(rename_all = "snake_case")]
 pub enum PointOperations {
     /// Insert or update points
     UpsertPoints(PointInsertOperationsInternal),
+    /// Insert or update points, if they still have the expected versions
+    UpsertPointsVersioned(VersionedInsertOperationInternal),
+    /// Insert or update points, and set their TTL timestamps
+    UpsertPointsWithTtl(TtlInsertOperationInternal),
     /// Delete point if exists
     DeletePoints { ids: Vec<PointIdType> },
     /// Delete points by given filter criteria
@@ -551,6 +928,8 @@ impl PointOperations {
     pub fn is_write_operation(&self) -> bool {
         match self {
             PointOperations::UpsertPoints(_) => true,
+            PointOperations::UpsertPointsVersioned(_) => true,
+            PointOperations::UpsertPointsWithTtl(_) => true,
             PointOperations::DeletePoints { .. } => false,
             PointOperations::DeletePointsByFilter(_) => false,
             PointOperations::SyncPoints(_) => true,
@@ -560,6 +939,8 @@ impl PointOperations {
     pub fn point_ids(&self) -> Vec<PointIdType> {
         match self {
             Self::UpsertPoints(op) => op.point_ids(),
+            Self::UpsertPointsVersioned(op) => op.point_ids(),
+            Self::UpsertPointsWithTtl(op) => op.upsert.point_ids(),
             Self::DeletePoints { ids } => ids.clone(),
             Self::DeletePointsByFilter(_) => Vec::new(),
             Self::SyncPoints(op) => op.points.iter().map(|point| point.id).collect(),
@@ -572,17 +953,132 @@ impl PointOperations {
     {
         match self {
             Self::UpsertPoints(op) => op.retain_point_ids(filter),
+            Self::UpsertPointsVersioned(op) => op.retain_point_ids(filter),
+            Self::UpsertPointsWithTtl(op) => op.upsert.retain_point_ids(filter),
             Self::DeletePoints { ids } => ids.retain(filter),
             Self::DeletePointsByFilter(_) => (),
             Self::SyncPoints(op) => op.points.retain(|point| filter(&point.id)),
         }
     }
+
+    pub fn invalid_vectors(
+        &self,
+        params: &CollectionParams,
+    ) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::UpsertPoints(op) => op.invalid_vectors(params),
+            Self::UpsertPointsVersioned(op) => op.invalid_vectors(params),
+            Self::UpsertPointsWithTtl(op) => op.upsert.invalid_vectors(params),
+            Self::DeletePoints { .. } => Vec::new(),
+            Self::DeletePointsByFilter(_) => Vec::new(),
+            Self::SyncPoints(op) => invalid_point_vectors(params, &op.points),
+        }
+    }
+
+    pub fn oversized_payloads(&self, max_size: usize) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::UpsertPoints(op) => op.oversized_payloads(max_size),
+            Self::UpsertPointsVersioned(op) => op.oversized_payloads(max_size),
+            Self::UpsertPointsWithTtl(op) => op.upsert.oversized_payloads(max_size),
+            Self::DeletePoints { .. } => Vec::new(),
+            Self::DeletePointsByFilter(_) => Vec::new(),
+            Self::SyncPoints(op) => oversized_point_payloads(&op.points, max_size),
+        }
+    }
+
+    /// Remove zero vectors which the collection excludes by its zero vector policy.
+    pub fn exclude_zero_vectors(&mut self, params: &CollectionParams) {
+        match self {
+            Self::UpsertPoints(op) => op.exclude_zero_vectors(params),
+            Self::UpsertPointsVersioned(op) => op.points_op.exclude_zero_vectors(params),
+            Self::UpsertPointsWithTtl(op) => op.upsert.points_op.exclude_zero_vectors(params),
+            Self::DeletePoints { .. } => (),
+            Self::DeletePointsByFilter(_) => (),
+            Self::SyncPoints(op) => exclude_zero_point_vectors(params, &mut op.points),
+        }
+    }
+
+    /// Set TTL timestamps of points, if this is an upsert
+    pub fn with_ttl_stamp(self, stamp: TtlStamp) -> Self {
+        let upsert = match self {
+            Self::UpsertPoints(points_op) => VersionedInsertOperationInternal {
+                points_op,
+                expected_versions: Vec::new(),
+                timestamp: None,
+                versions: Vec::new(),
+            },
+            Self::UpsertPointsVersioned(upsert) => upsert,
+            Self::UpsertPointsWithTtl(TtlInsertOperationInternal { upsert, .. }) => upsert,
+            Self::DeletePoints { .. } | Self::DeletePointsByFilter(_) | Self::SyncPoints(_) => {
+                return self;
+            }
+        };
+        Self::UpsertPointsWithTtl(TtlInsertOperationInternal { upsert, stamp })
+    }
+
+    /// Set the timestamp versions of upserted points are derived from, if this is an upsert
+    pub fn with_version_timestamp(self, timestamp: u64) -> Self {
+        match self {
+            Self::UpsertPoints(points_op) => {
+                Self::UpsertPointsVersioned(VersionedInsertOperationInternal {
+                    points_op,
+                    expected_versions: Vec::new(),
+                    timestamp: Some(timestamp),
+                    versions: Vec::new(),
+                })
+            }
+            Self::UpsertPointsVersioned(mut upsert) => {
+                upsert.timestamp = Some(timestamp);
+                Self::UpsertPointsVersioned(upsert)
+            }
+            Self::UpsertPointsWithTtl(mut upsert) => {
+                upsert.upsert.timestamp = Some(timestamp);
+                Self::UpsertPointsWithTtl(upsert)
+            }
+            Self::DeletePoints { .. } | Self::DeletePointsByFilter(_) | Self::SyncPoints(_) => self,
+        }
+    }
+
+    /// Assign the given versions to upserted points as they are, instead of deriving new ones
+    ///
+    /// Used when copying points between shards, to keep versions consistent across them.
+    pub fn with_versions(self, versions: Vec<PointVersion>) -> Self {
+        match self {
+            Self::UpsertPoints(points_op) => {
+                Self::UpsertPointsVersioned(VersionedInsertOperationInternal {
+                    points_op,
+                    expected_versions: Vec::new(),
+                    timestamp: None,
+                    versions,
+                })
+            }
+            Self::UpsertPointsVersioned(mut upsert) => {
+                upsert.versions = versions;
+                Self::UpsertPointsVersioned(upsert)
+            }
+            Self::UpsertPointsWithTtl(mut upsert) => {
+                upsert.upsert.versions = versions;
+                Self::UpsertPointsWithTtl(upsert)
+            }
+            Self::SyncPoints(mut sync) => {
+                sync.versions = versions;
+                Self::SyncPoints(sync)
+            }
+            Self::DeletePoints { .. } | Self::DeletePointsByFilter(_) => self,
+        }
+    }
 }
 
 impl Validate for PointOperations {
     fn validate(&self) -> Result<(), validator::ValidationErrors> {
         match self {
             PointOperations::UpsertPoints(upsert_points) => upsert_points.validate(),
+            PointOperations::UpsertPointsVersioned(upsert_points) => {
+                upsert_points.points_op.validate()
+            }
+            PointOperations::UpsertPointsWithTtl(upsert_points) => {
+                upsert_points.upsert.points_op.validate()
+            }
             PointOperations::DeletePoints { ids: _ } => Ok(()),
             PointOperations::DeletePointsByFilter(_) => Ok(()),
             PointOperations::SyncPoints(_) => Ok(()),
@@ -763,6 +1259,17 @@ impl SplitByShard for PointOperations {
             PointOperations::UpsertPoints(upsert_points) => upsert_points
                 .split_by_shard(ring)
                 .map(PointOperations::UpsertPoints),
+            PointOperations::UpsertPointsVersioned(upsert_points) => upsert_points
+                .split_by_shard(ring)
+                .map(PointOperations::UpsertPointsVersioned),
+            PointOperations::UpsertPointsWithTtl(TtlInsertOperationInternal { upsert, stamp }) => {
+                upsert.split_by_shard(ring).map(|upsert| {
+                    PointOperations::UpsertPointsWithTtl(TtlInsertOperationInternal {
+                        upsert,
+                        stamp: stamp.clone(),
+                    })
+                })
+            }
             PointOperations::DeletePoints { ids } => split_iter_by_shard(ids, |id| *id, ring)
                 .map(|ids| PointOperations::DeletePoints { ids }),
             by_filter @ PointOperations::DeletePointsByFilter(_) => {
@@ -818,11 +1325,341 @@ impl PointStruct {
     }
 }
 
+fn invalid_point_vectors(
+    params: &CollectionParams,
+    points: &[PointStruct],
+) -> Vec<(PointIdType, CollectionError)> {
+    points
+        .iter()
+        .filter_map(|point| {
+            check_point_vectors(params, point.id, &point.vector)
+                .and_then(|()| check_point_vector_metadata(point))
+                .err()
+                .map(|err| (point.id, err))
+        })
+        .collect()
+}
+
+fn oversized_point_payloads(
+    points: &[PointStruct],
+    max_size: usize,
+) -> Vec<(PointIdType, CollectionError)> {
+    points
+        .iter()
+        .filter_map(|point| {
+            check_payload_size(point.id, point.payload.as_ref()?, max_size)
+                .err()
+                .map(|err| (point.id, err))
+        })
+        .collect()
+}
+
+/// Check that payload of point `point_id`, serialized as JSON, is at most `max_size` bytes.
+fn check_payload_size(
+    point_id: PointIdType,
+    payload: &Payload,
+    max_size: usize,
+) -> CollectionResult<()> {
+    let size = serde_json::to_vec(payload).unwrap_or_default().len();
+    if size > max_size {
+        return Err(CollectionError::bad_input(format!(
+            "Payload size error: payload of {size} bytes exceeds the limit of {max_size} bytes, at point {point_id}",
+        )));
+    }
+    Ok(())
+}
+
+/// Check that metadata of point `point` is only given for vectors of the point itself.
+fn check_point_vector_metadata(point: &PointStruct) -> CollectionResult<()> {
+    let Some(vector_metadata) = &point.vector_metadata else {
+        return Ok(());
+    };
+
+    for name in vector_metadata.keys() {
+        let has_vector = match &point.vector {
+            VectorStruct::Single(_) | VectorStruct::MultiDense(_) | VectorStruct::Document(_) => {
+                name == DEFAULT_VECTOR_NAME
+            }
+            VectorStruct::Named(vectors) => vectors.contains_key(name),
+        };
+        if !has_vector {
+            return Err(CollectionError::bad_input(format!(
+                "Vector metadata error: no vector given for the metadata, {}",
+                describe_point_vector(point.id, name),
+            )));
+        }
+    }
+
+    Ok(())
+}
+
+fn exclude_zero_point_vectors(params: &CollectionParams, points: &mut [PointStruct]) {
+    for point in points {
+        let excluded = exclude_zero_vectors(params, &mut point.vector);
+        if let Some(vector_metadata) = &mut point.vector_metadata {
+            vector_metadata.retain(|name, _| !excluded.contains(name));
+        }
+    }
+}
+
+/// Remove zero vectors which the collection excludes by its zero vector policy from `vector`.
+///
+/// Returns names of the removed vectors.
+pub(crate) fn exclude_zero_vectors(
+    params: &CollectionParams,
+    vector: &mut VectorStruct,
+) -> Vec<String> {
+    let is_zero = match vector {
+        VectorStruct::Single(vector) => is_zero_vector(vector),
+        VectorStruct::MultiDense(vectors) => is_zero_multi_dense_vector(vectors),
+        VectorStruct::Named(vectors) => {
+            let mut excluded = Vec::new();
+            vectors.retain(|name, vector| {
+                let is_excluded = is_excluded_zero_vector(params, name, vector);
+                if is_excluded {
+                    excluded.push(name.clone());
+                }
+                !is_excluded
+            });
+            return excluded;
+        }
+        VectorStruct::Document(_) => false,
+    };
+
+    if !is_zero || !excludes_zero_vectors(params, DEFAULT_VECTOR_NAME) {
+        return Vec::new();
+    }
+
+    // A point without vectors has no named vectors
+    *vector = VectorStruct::Named(HashMap::new());
+    vec![DEFAULT_VECTOR_NAME.to_string()]
+}
+
+fn has_excluded_zero_batch_vectors(params: &CollectionParams, vectors: &BatchVectorStruct) -> bool {
+    match vectors {
+        BatchVectorStruct::Single(vectors) => {
+            excludes_zero_vectors(params, DEFAULT_VECTOR_NAME)
+                && vectors.iter().any(|vector| is_zero_vector(vector))
+        }
+        BatchVectorStruct::MultiDense(vectors) => {
+            excludes_zero_vectors(params, DEFAULT_VECTOR_NAME)
+                && vectors
+                    .iter()
+                    .any(|vector| is_zero_multi_dense_vector(vector))
+        }
+        BatchVectorStruct::Named(vectors) => vectors.iter().any(|(name, vectors)| {
+            vectors
+                .iter()
+                .any(|vector| is_excluded_zero_vector(params, name, vector))
+        }),
+        BatchVectorStruct::Document(_) => false,
+    }
+}
+
+fn is_excluded_zero_vector(
+    params: &CollectionParams,
+    name: &str,
+    vector: &api::rest::Vector,
+) -> bool {
+    let is_zero = match vector {
+        api::rest::Vector::Dense(vector) => is_zero_vector(vector),
+        api::rest::Vector::MultiDense(vectors) => is_zero_multi_dense_vector(vectors),
+        api::rest::Vector::Sparse(_) | api::rest::Vector::Document(_) => false,
+    };
+    is_zero && excludes_zero_vectors(params, name)
+}
+
+fn excludes_zero_vectors(params: &CollectionParams, name: &str) -> bool {
+    params
+        .vectors
+        .get_params(name)
+        .is_some_and(|vector_params| {
+            vector_params.zero_vector_policy() == ZeroVectorPolicy::Exclude
+        })
+}
+
+/// Check that all vectors of point `point_id` match the vectors configured in the collection.
+pub(crate) fn check_point_vectors(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    vector: &VectorStruct,
+) -> CollectionResult<()> {
+    match vector {
+        VectorStruct::Single(vector) => {
+            check_dense_vector(params, point_id, DEFAULT_VECTOR_NAME, vector)
+        }
+        VectorStruct::MultiDense(vector) => {
+            check_multi_dense_vector(params, point_id, DEFAULT_VECTOR_NAME, vector)
+        }
+        VectorStruct::Named(vectors) => vectors
+            .iter()
+            .try_for_each(|(name, vector)| check_vector(params, point_id, name, vector)),
+        // Rejected by validation
+        VectorStruct::Document(_) => Ok(()),
+    }
+}
+
+fn check_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    name: &str,
+    vector: &api::rest::Vector,
+) -> CollectionResult<()> {
+    match vector {
+        api::rest::Vector::Dense(vector) => check_dense_vector(params, point_id, name, vector),
+        api::rest::Vector::MultiDense(vector) => {
+            check_multi_dense_vector(params, point_id, name, vector)
+        }
+        api::rest::Vector::Sparse(vector) => check_sparse_vector(params, point_id, name, vector),
+        // Rejected by validation
+        api::rest::Vector::Document(_) => Ok(()),
+    }
+}
+
+fn check_dense_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    name: &str,
+    vector: &[VectorElementType],
+) -> CollectionResult<()> {
+    let vector_params = dense_vector_params(params, point_id, name)?;
+    check_vector_dim(
+        point_id,
+        name,
+        vector_params.size.get() as usize,
+        vector.len(),
+    )?;
+    if is_zero_vector(vector) {
+        check_zero_vector(vector_params, point_id, name)?;
+    }
+    Ok(())
+}
+
+fn check_multi_dense_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    name: &str,
+    vectors: &[DenseVector],
+) -> CollectionResult<()> {
+    let vector_params = dense_vector_params(params, point_id, name)?;
+    let expected_dim = vector_params.size.get() as usize;
+    vectors
+        .iter()
+        .try_for_each(|vector| check_vector_dim(point_id, name, expected_dim, vector.len()))?;
+    if is_zero_multi_dense_vector(vectors) {
+        check_zero_vector(vector_params, point_id, name)?;
+    }
+    Ok(())
+}
+
+fn check_sparse_vector(
+    params: &CollectionParams,
+    point_id: PointIdType,
+    name: &str,
+    vector: &SparseVector,
+) -> CollectionResult<()> {
+    if params.get_sparse_vector_params_opt(name).is_none() {
+        return Err(missing_point_vector_error(point_id, name));
+    }
+
+    if vector.indices.len() != vector.values.len() {
+        return Err(CollectionError::bad_input(format!(
+            "Sparse vector error: {} indices, but {} values, {}",
+            vector.indices.len(),
+            vector.values.len(),
+            describe_point_vector(point_id, name),
+        )));
+    }
+
+    let mut seen_indices = HashSet::with_capacity(vector.indices.len());
+    if let Some(duplicate) = vector
+        .indices
+        .iter()
+        .find(|index| !seen_indices.insert(**index))
+    {
+        return Err(CollectionError::bad_input(format!(
+            "Sparse vector error: duplicate index {duplicate}, {}",
+            describe_point_vector(point_id, name),
+        )));
+    }
+
+    Ok(())
+}
+
+fn dense_vector_params<'a>(
+    params: &'a CollectionParams,
+    point_id: PointIdType,
+    name: &str,
+) -> CollectionResult<&'a VectorParams> {
+    params
+        .vectors
+        .get_params(name)
+        .ok_or_else(|| missing_point_vector_error(point_id, name))
+}
+
+fn check_zero_vector(
+    vector_params: &VectorParams,
+    point_id: PointIdType,
+    name: &str,
+) -> CollectionResult<()> {
+    match vector_params.zero_vector_policy() {
+        ZeroVectorPolicy::ScoreZero | ZeroVectorPolicy::Exclude => Ok(()),
+        ZeroVectorPolicy::Reject => Err(CollectionError::bad_input(format!(
+            "Zero vector error: zero vectors are rejected with cosine distance, {}",
+            describe_point_vector(point_id, name),
+        ))),
+    }
+}
+
+/// Whether all components of the vector are zero
+pub(crate) fn is_zero_vector(vector: &[VectorElementType]) -> bool {
+    vector.iter().all(|&value| value == 0.0)
+}
+
+/// Whether all components of all vectors of the multivector are zero
+pub(crate) fn is_zero_multi_dense_vector(vectors: &[DenseVector]) -> bool {
+    vectors.iter().all(|vector| is_zero_vector(vector))
+}
+
+fn check_vector_dim(
+    point_id: PointIdType,
+    name: &str,
+    expected_dim: usize,
+    received_dim: usize,
+) -> CollectionResult<()> {
+    if expected_dim != received_dim {
+        return Err(CollectionError::bad_input(format!(
+            "Vector dimension error: expected dim: {expected_dim}, got {received_dim}, {}",
+            describe_point_vector(point_id, name),
+        )));
+    }
+    Ok(())
+}
+
+fn missing_point_vector_error(point_id: PointIdType, name: &str) -> CollectionError {
+    CollectionError::bad_input(format!(
+        "Not existing vector name error: {name}, at point {point_id}",
+    ))
+}
+
+fn describe_point_vector(point_id: PointIdType, name: &str) -> String {
+    if name == DEFAULT_VECTOR_NAME {
+        format!("at point {point_id}")
+    } else {
+        format!("at vector {name} of point {point_id}")
+    }
+}
+
 #[cfg(test)]
 mod tests {
+    use std::collections::BTreeMap;
+
     use segment::data_types::vectors::BatchVectorStructInternal;
+    use segment::types::Distance;
 
     use super::*;
+    use crate::operations::types::SparseVectorParams;
+    use crate::operations::vector_params_builder::VectorParamsBuilder;
 
     #[test]
     fn validate_batch() {
@@ -850,4 +1687,225 @@ mod tests {
         .into();
         assert!(batch.validate().is_err());
     }
+
+    #[test]
+    fn invalid_vectors() {
+        let params = CollectionParams {
+            vectors: VectorParamsBuilder::new(4, Distance::Dot).build().into(),
+            ..CollectionParams::empty()
+        };
+
+        let batch: PointInsertOperationsInternal = Batch {
+            ids: vec![PointIdType::NumId(0), PointIdType::NumId(1)],
+            vectors: BatchVectorStructInternal::from(vec![vec![0.1; 4], vec![0.1; 3]]).into(),
+            payloads: None,
+        }
+        .into();
+        let invalid = batch.invalid_vectors(&params);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, PointIdType::NumId(1));
+        assert_eq!(
+            invalid[0].1.to_string(),
+            "Wrong input: Vector dimension error: expected dim: 4, got 3, at point 1",
+        );
+
+        let list: PointInsertOperationsInternal = vec![
+            PointStruct {
+                id: PointIdType::NumId(2),
+                vector: VectorStruct::Named(HashMap::from([(
+                    "image".to_string(),
+                    api::rest::Vector::Dense(vec![0.1; 4]),
+                )])),
+                payload: None,
+                vector_metadata: None,
+            },
+            PointStruct {
+                id: PointIdType::NumId(3),
+                vector: VectorStruct::Single(vec![0.1; 4]),
+                payload: None,
+                vector_metadata: None,
+            },
+        ]
+        .into();
+        let invalid = list.invalid_vectors(&params);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, PointIdType::NumId(2));
+    }
+
+    #[test]
+    fn invalid_sparse_vectors() {
+        let params = CollectionParams {
+            sparse_vectors: Some(BTreeMap::from([(
+                "text".to_string(),
+                SparseVectorParams {
+                    index: None,
+                    modifier: None,
+                    distance: None,
+                },
+            )])),
+            ..CollectionParams::empty()
+        };
+
+        let sparse_point = |id, indices: Vec<u32>, values: Vec<f32>| PointStruct {
+            id: PointIdType::NumId(id),
+            vector: VectorStruct::Named(HashMap::from([(
+                "text".to_string(),
+                api::rest::Vector::Sparse(SparseVector { indices, values }),
+            )])),
+            payload: None,
+            vector_metadata: None,
+        };
+
+        let list: PointInsertOperationsInternal = vec![
+            sparse_point(0, vec![1, 2], vec![0.1, 0.2]),
+            sparse_point(1, vec![1, 2], vec![0.1]),
+            sparse_point(2, vec![1, 1], vec![0.1, 0.2]),
+        ]
+        .into();
+        let invalid = list.invalid_vectors(&params);
+        assert_eq!(invalid.len(), 2);
+        assert_eq!(invalid[0].0, PointIdType::NumId(1));
+        assert_eq!(invalid[1].0, PointIdType::NumId(2));
+        assert_eq!(
+            invalid[1].1.to_string(),
+            "Wrong input: Sparse vector error: duplicate index 1, at vector text of point 2",
+        );
+    }
+
+    #[test]
+    fn oversized_payloads() {
+        let payload = |text: &str| Some(Payload::from(serde_json::json!({ "text": text })));
+
+        let batch: PointInsertOperationsInternal = Batch {
+            ids: vec![
+                PointIdType::NumId(0),
+                PointIdType::NumId(1),
+                PointIdType::NumId(2),
+            ],
+            vectors: BatchVectorStructInternal::from(vec![vec![0.1; 4]; 3]).into(),
+            payloads: Some(vec![payload("short"), None, payload(&"long".repeat(10))]),
+        }
+        .into();
+        // `{"text":"short"}` is 16 bytes
+        assert!(batch.oversized_payloads(16).is_empty());
+        let invalid = batch.oversized_payloads(20);
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, PointIdType::NumId(2));
+        assert_eq!(
+            invalid[0].1.to_string(),
+            "Wrong input: Payload size error: payload of 51 bytes exceeds the limit of 20 bytes, at point 2",
+        );
+
+        let list: PointInsertOperationsInternal = batch.into_point_list().into();
+        let invalid = list.oversized_payloads(15);
+        let ids: Vec<_> = invalid.iter().map(|(id, _)| *id).collect();
+        assert_eq!(ids, [PointIdType::NumId(0), PointIdType::NumId(2)]);
+    }
+
+    #[test]
+    fn zero_vectors() {
+        let params = |policy| CollectionParams {
+            vectors: VectorParamsBuilder::new(2, Distance::Cosine)
+                .with_zero_vector_policy(policy)
+                .build()
+                .into(),
+            ..CollectionParams::empty()
+        };
+        let batch: PointInsertOperationsInternal = Batch {
+            ids: vec![PointIdType::NumId(0), PointIdType::NumId(1)],
+            vectors: BatchVectorStructInternal::from(vec![vec![0.1; 2], vec![0.0; 2]]).into(),
+            payloads: None,
+        }
+        .into();
+
+        assert!(batch
+            .invalid_vectors(&params(ZeroVectorPolicy::ScoreZero))
+            .is_empty());
+        assert!(batch
+            .invalid_vectors(&params(ZeroVectorPolicy::Exclude))
+            .is_empty());
+        let invalid = batch.invalid_vectors(&params(ZeroVectorPolicy::Reject));
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, PointIdType::NumId(1));
+        assert_eq!(
+            invalid[0].1.to_string(),
+            "Wrong input: Zero vector error: zero vectors are rejected with cosine distance, at point 1",
+        );
+
+        let mut excluded = batch.clone();
+        excluded.exclude_zero_vectors(&params(ZeroVectorPolicy::ScoreZero));
+        assert_eq!(excluded, batch);
+
+        excluded.exclude_zero_vectors(&params(ZeroVectorPolicy::Exclude));
+        let points = excluded.into_point_list();
+        assert_eq!(points[0].vector, VectorStruct::Single(vec![0.1; 2]));
+        assert!(points[1].vector.is_empty());
+
+        let mut update = vector_ops::VectorOperations::UpdateVectors(UpdateVectorsOp {
+            points: vec![
+                PointVectors {
+                    id: PointIdType::NumId(2),
+                    vector: VectorStruct::Single(vec![0.0; 2]),
+                },
+                PointVectors {
+                    id: PointIdType::NumId(3),
+                    vector: VectorStruct::Single(vec![0.1; 2]),
+                },
+            ],
+            delete_vectors: Vec::new(),
+        });
+        update.exclude_zero_vectors(&params(ZeroVectorPolicy::Exclude));
+        let vector_ops::VectorOperations::UpdateVectors(op) = &update else {
+            unreachable!();
+        };
+        assert_eq!(op.points.len(), 1);
+        assert_eq!(op.points[0].id, PointIdType::NumId(3));
+        assert_eq!(
+            op.delete_vectors,
+            vec![(PointIdType::NumId(2), DEFAULT_VECTOR_NAME.to_string())],
+        );
+        assert_eq!(
+            update.point_ids(),
+            vec![PointIdType::NumId(3), PointIdType::NumId(2)],
+        );
+    }
+
+    #[test]
+    fn test_deduplicate_ids() {
+        let batch: PointInsertOperationsInternal = Batch {
+            ids: vec![1.into(), 2.into(), 1.into()],
+            vectors: BatchVectorStructInternal::from(vec![vec![1.0], vec![2.0], vec![3.0]]).into(),
+            payloads: Some(vec![None, None, Some(Payload::default())]),
+        }
+        .into();
+
+        let deduplicated = |policy| {
+            let mut batch = batch.clone();
+            batch
+                .deduplicate_ids(policy)
+                .map(|()| batch.into_point_list())
+        };
+
+        let points = deduplicated(DuplicateIdsPolicy::LastWins).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].id, 2.into());
+        assert_eq!(points[1].id, 1.into());
+        assert_eq!(points[1].vector, VectorStruct::Single(vec![3.0]));
+        assert!(points[1].payload.is_some());
+
+        let points = deduplicated(DuplicateIdsPolicy::FirstWins).unwrap();
+        assert_eq!(points.len(), 2);
+        assert_eq!(points[0].id, 1.into());
+        assert_eq!(points[0].vector, VectorStruct::Single(vec![1.0]));
+        assert!(points[0].payload.is_none());
+
+        assert!(deduplicated(DuplicateIdsPolicy::Error).is_err());
+
+        // Operations without duplicates are not changed
+        let mut unique = batch.clone();
+        unique.retain_point_ids(|id| *id == 2.into());
+        let expected = unique.clone();
+        unique.deduplicate_ids(DuplicateIdsPolicy::Error).unwrap();
+        assert_eq!(unique, expected);
+    }
 }
@@ -0,0 +1,186 @@
+//! Second stage of a search, which reorders candidates found by vector similarity.
+//!
+//! Rerankers are registered on the server by name, and selected per search request with
+//! [`RerankParams`]. The search first retrieves `candidates` points by vector similarity, then the
+//! reranker scores them, and the best `offset + limit` of them are returned.
+
+use std::collections::HashMap;
+use std::sync::{Arc, OnceLock};
+
+use api::rest::RerankParams;
+use common::types::ScoreType;
+use parking_lot::RwLock;
+use segment::types::ScoredPoint;
+
+use crate::operations::query_enum::QueryEnum;
+use crate::operations::types::{CollectionError, CollectionResult};
+
+/// Number of candidates to rerank per returned point, if not specified in the request
+pub const DEFAULT_CANDIDATES_FACTOR: usize = 4;
+
+/// Upper bound of candidates to rerank, to keep the overhead of reranking bounded
+pub const MAX_RERANK_CANDIDATES: usize = 1000;
+
+pub trait Reranker: Send + Sync {
+    /// Score candidates of a search, ordered by vector similarity.
+    ///
+    /// Candidates contain all of their payload and vectors. Must return one score per candidate,
+    /// in the same order. Larger scores are better.
+    fn rerank(
+        &self,
+        query: &QueryEnum,
+        candidates: &[ScoredPoint],
+    ) -> CollectionResult<Vec<ScoreType>>;
+}
+
+fn registry() -> &'static RwLock<HashMap<String, Arc<dyn Reranker>>> {
+    static REGISTRY: OnceLock<RwLock<HashMap<String, Arc<dyn Reranker>>>> = OnceLock::new();
+    REGISTRY.get_or_init(Default::default)
+}
+
+/// Register a reranker under the given name, replacing a previous one with the same name
+pub fn register_reranker(
+    name: impl Into<String>,
+    reranker: Arc<dyn Reranker>,
+) -> Option<Arc<dyn Reranker>> {
+    registry().write().insert(name.into(), reranker)
+}
+
+pub fn unregister_reranker(name: &str) -> Option<Arc<dyn Reranker>> {
+    registry().write().remove(name)
+}
+
+pub fn get_reranker(name: &str) -> CollectionResult<Arc<dyn Reranker>> {
+    registry()
+        .read()
+        .get(name)
+        .cloned()
+        .ok_or_else(|| CollectionError::bad_input(format!("Reranker `{name}` is not registered")))
+}
+
+/// Number of candidates to retrieve for reranking, to return `page_size` points.
+///
+/// There must be more candidates than returned points, otherwise the reranker could not bring
+/// any other point into the results.
+pub fn rerank_candidates(params: &RerankParams, page_size: usize) -> CollectionResult<usize> {
+    let candidates = params.candidates.unwrap_or_else(|| {
+        page_size
+            .saturating_mul(DEFAULT_CANDIDATES_FACTOR)
+            .clamp(page_size + 1, MAX_RERANK_CANDIDATES.max(page_size + 1))
+    });
+
+    if candidates <= page_size {
+        return Err(CollectionError::bad_input(format!(
+            "Number of rerank candidates ({candidates}) must be larger than offset + limit ({page_size})"
+        )));
+    }
+    if candidates > MAX_RERANK_CANDIDATES {
+        return Err(CollectionError::bad_input(format!(
+            "Number of rerank candidates ({candidates}) must not exceed {MAX_RERANK_CANDIDATES}"
+        )));
+    }
+
+    Ok(candidates)
+}
+
+/// Score candidates with the reranker, and sort them by the new score.
+///
+/// Candidates with equal scores keep their vector similarity order.
+pub fn rerank(
+    reranker: &dyn Reranker,
+    query: &QueryEnum,
+    mut candidates: Vec<ScoredPoint>,
+) -> CollectionResult<Vec<ScoredPoint>> {
+    if candidates.is_empty() {
+        return Ok(candidates);
+    }
+
+    let scores = reranker.rerank(query, &candidates)?;
+    if scores.len() != candidates.len() {
+        return Err(CollectionError::service_error(format!(
+            "Reranker returned {} scores for {} candidates",
+            scores.len(),
+            candidates.len(),
+        )));
+    }
+
+    for (point, score) in candidates.iter_mut().zip(scores) {
+        point.score = score;
+    }
+    candidates.sort_by(|a, b| b.score.total_cmp(&a.score));
+
+    Ok(candidates)
+}
+
+#[cfg(test)]
+mod tests {
+    use segment::types::ExtendedPointId;
+
+    use super::*;
+
+    /// Prefers points with larger ids
+    struct ByIdReranker;
+
+    impl Reranker for ByIdReranker {
+        fn rerank(
+            &self,
+            _query: &QueryEnum,
+            candidates: &[ScoredPoint],
+        ) -> CollectionResult<Vec<ScoreType>> {
+            Ok(candidates
+                .iter()
+                .map(|point| match point.id {
+                    ExtendedPointId::NumId(id) => id as ScoreType,
+                    ExtendedPointId::Uuid(_) => 0.0,
+                })
+                .collect())
+        }
+    }
+
+    fn point(id: u64, score: ScoreType) -> ScoredPoint {
+        ScoredPoint {
+            id: id.into(),
+            version: 0,
+            score,
+            payload: None,
+            vector: None,
+            shard_key: None,
+            order_value: None,
+            distance: None,
+            provenance: None,
+        }
+    }
+
+    #[test]
+    fn test_rerank() {
+        register_reranker("test_by_id", Arc::new(ByIdReranker));
+        let reranker = get_reranker("test_by_id").unwrap();
+
+        let query = QueryEnum::Nearest(vec![1.0, 2.0].into());
+        let candidates = vec![point(1, 0.9), point(3, 0.8), point(2, 0.7)];
+        let reranked = rerank(reranker.as_ref(), &query, candidates).unwrap();
+        assert_eq!(
+            reranked.iter().map(|point| point.id).collect::<Vec<_>>(),
+            vec![3.into(), 2.into(), 1.into()],
+        );
+        assert_eq!(reranked[0].score, 3.0);
+
+        assert!(unregister_reranker("test_by_id").is_some());
+        assert!(get_reranker("test_by_id").is_err());
+    }
+
+    #[test]
+    fn test_rerank_candidates() {
+        let params = |candidates| RerankParams {
+            name: "test".to_string(),
+            candidates,
+        };
+
+        assert_eq!(rerank_candidates(&params(None), 10).unwrap(), 40);
+        assert_eq!(rerank_candidates(&params(None), 500).unwrap(), 1000);
+        assert_eq!(rerank_candidates(&params(Some(11)), 10).unwrap(), 11);
+        assert!(rerank_candidates(&params(Some(10)), 10).is_err());
+        assert!(rerank_candidates(&params(Some(2000)), 10).is_err());
+        assert!(rerank_candidates(&params(None), 1000).is_err());
+    }
+}
@@ -2,13 +2,17 @@ use std::default;
 use std::num::NonZeroUsize;
 use std::time::Duration;
 
+use crate::collection::query_cache::QueryCacheConfig;
 use crate::common::snapshots_manager::SnapShotsConfig;
 use crate::operations::types::NodeType;
+use crate::shards::local_shard::segment_load::SegmentLoadConfig;
 use crate::shards::transfer::ShardTransferMethod;
 
 /// Default timeout for search requests.
 /// In cluster mode, this should be aligned with collection timeout.
 const DEFAULT_SEARCH_TIMEOUT: Duration = Duration::from_secs(60);
+/// Default time a read with session consistency waits for a replica to apply the session updates
+const DEFAULT_SESSION_READ_TIMEOUT: Duration = Duration::from_secs(10);
 const DEFAULT_UPDATE_QUEUE_SIZE: usize = 100;
 const DEFAULT_UPDATE_QUEUE_SIZE_LISTENER: usize = 10_000;
 pub const DEFAULT_IO_SHARD_TRANSFER_LIMIT: Option<usize> = Some(1);
@@ -20,34 +24,49 @@ pub const DEFAULT_SNAPSHOTS_PATH: &str = "./snapshots";
 #[derive(Clone, Debug)]
 pub struct SharedStorageConfig {
     pub update_queue_size: usize,
+    pub max_pending_updates: Option<usize>,
     pub node_type: NodeType,
     pub handle_collection_load_errors: bool,
     pub recovery_mode: Option<String>,
     pub search_timeout: Duration,
+    pub session_read_timeout: Duration,
     pub update_concurrency: Option<NonZeroUsize>,
+    pub search_shard_concurrency: Option<NonZeroUsize>,
+    pub search_segment_concurrency: Option<NonZeroUsize>,
     pub is_distributed: bool,
     pub default_shard_transfer_method: Option<ShardTransferMethod>,
     pub incoming_shard_transfers_limit: Option<usize>,
     pub outgoing_shard_transfers_limit: Option<usize>,
     pub snapshots_path: String,
     pub snapshots_config: SnapShotsConfig,
+    pub query_cache: Option<QueryCacheConfig>,
+    /// Max size of the payload of an upserted point, serialized as JSON, in bytes
+    pub max_payload_size: Option<usize>,
+    pub segment_load: Option<SegmentLoadConfig>,
 }
 
 impl Default for SharedStorageConfig {
     fn default() -> Self {
         Self {
             update_queue_size: DEFAULT_UPDATE_QUEUE_SIZE,
+            max_pending_updates: None,
             node_type: Default::default(),
             handle_collection_load_errors: false,
             recovery_mode: None,
             search_timeout: DEFAULT_SEARCH_TIMEOUT,
+            session_read_timeout: DEFAULT_SESSION_READ_TIMEOUT,
             update_concurrency: None,
+            search_shard_concurrency: None,
+            search_segment_concurrency: None,
             is_distributed: false,
             default_shard_transfer_method: None,
             incoming_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             outgoing_shard_transfers_limit: DEFAULT_IO_SHARD_TRANSFER_LIMIT,
             snapshots_path: DEFAULT_SNAPSHOTS_PATH.to_string(),
             snapshots_config: default::Default::default(),
+            query_cache: None,
+            max_payload_size: None,
+            segment_load: None,
         }
     }
 }
@@ -56,17 +75,24 @@ impl SharedStorageConfig {
     #[allow(clippy::too_many_arguments)]
     pub fn new(
         update_queue_size: Option<usize>,
+        max_pending_updates: Option<usize>,
         node_type: NodeType,
         handle_collection_load_errors: bool,
         recovery_mode: Option<String>,
         search_timeout: Option<Duration>,
+        session_read_timeout: Option<Duration>,
         update_concurrency: Option<NonZeroUsize>,
+        search_shard_concurrency: Option<NonZeroUsize>,
+        search_segment_concurrency: Option<NonZeroUsize>,
         is_distributed: bool,
         default_shard_transfer_method: Option<ShardTransferMethod>,
         incoming_shard_transfers_limit: Option<usize>,
         outgoing_shard_transfers_limit: Option<usize>,
         snapshots_path: String,
         snapshots_config: SnapShotsConfig,
+        query_cache: Option<QueryCacheConfig>,
+        max_payload_size: Option<usize>,
+        segment_load: Option<SegmentLoadConfig>,
     ) -> Self {
         let update_queue_size = update_queue_size.unwrap_or(match node_type {
             NodeType::Normal => DEFAULT_UPDATE_QUEUE_SIZE,
@@ -74,17 +100,24 @@ impl SharedStorageConfig {
         });
         Self {
             update_queue_size,
+            max_pending_updates,
             node_type,
             handle_collection_load_errors,
             recovery_mode,
             search_timeout: search_timeout.unwrap_or(DEFAULT_SEARCH_TIMEOUT),
+            session_read_timeout: session_read_timeout.unwrap_or(DEFAULT_SESSION_READ_TIMEOUT),
             update_concurrency,
+            search_shard_concurrency,
+            search_segment_concurrency,
             is_distributed,
             default_shard_transfer_method,
             incoming_shard_transfers_limit,
             outgoing_shard_transfers_limit,
             snapshots_path,
             snapshots_config,
+            query_cache,
+            max_payload_size,
+            segment_load,
         }
     }
 }
@@ -8,7 +8,7 @@ use std::time::SystemTimeError;
 
 use api::grpc::transport_channel_pool::RequestError;
 use api::rest::{
-    BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy,
+    BaseGroupRequest, LookupLocation, OrderByInterface, RecommendStrategy, RerankParams,
     SearchGroupsRequestInternal, SearchRequestInternal, ShardKeySelector, VectorStruct,
 };
 use common::defaults;
@@ -19,17 +19,21 @@ use issues::IssueRecord;
 use merge::Merge;
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
-use segment::common::operation_error::OperationError;
+use segment::common::operation_error::{ErrorCode, OperationError};
 use segment::data_types::groups::GroupId;
 use segment::data_types::order_by::{OrderBy, OrderValue};
+use segment::data_types::score_modifier::ScoreModifier;
 use segment::data_types::vectors::{
     DenseVector, QueryVector, VectorRef, VectorStructInternal, DEFAULT_VECTOR_NAME,
 };
+use segment::entry::entry_point::SegmentEntry;
+use segment::segment_constructor::memory_estimation::{MemoryEstimation, SegmentMemoryEstimation};
 use segment::types::{
     Distance, Filter, MultiVectorConfig, Payload, PayloadIndexInfo, PayloadKeyType, PointIdType,
-    QuantizationConfig, SearchParams, SeqNumberType, ShardKey, VectorStorageDatatype,
-    WithPayloadInterface, WithVector,
+    QuantizationConfig, SearchParams, SegmentConfig, SegmentInfo, SeqNumberType, ShardKey,
+    SparseDistance, VectorMetadata, VectorStorageDatatype, WithPayloadInterface, WithVector,
 };
+use segment::vector_storage::query::DEFAULT_CONTEXT_WEIGHT;
 use semver::Version;
 use serde;
 use serde::{Deserialize, Serialize};
@@ -43,7 +47,10 @@ use tonic::codegen::http::uri::InvalidUri;
 use validator::{Validate, ValidationError, ValidationErrors};
 
 use super::config_diff::{self};
+use super::consistency_params::SessionToken;
 use super::ClockTag;
+use crate::collection_manager::holders::segment_holder::SegmentId;
+use crate::collection_manager::optimizers::TrackerTelemetry;
 use crate::config::{CollectionConfig, CollectionParams};
 use crate::operations::cluster_ops::ReshardingDirection;
 use crate::operations::config_diff::{HnswConfigDiff, QuantizationConfigDiff};
@@ -125,10 +132,14 @@ pub struct Record {
     pub payload: Option<Payload>,
     /// Vector of the point
     pub vector: Option<VectorStructInternal>,
+    /// Metadata of the returned vectors of the point
+    pub vector_metadata: Option<VectorMetadata>,
     /// Shard Key
     pub shard_key: Option<ShardKey>,
     /// Order value, if used for order_by
     pub order_value: Option<OrderValue>,
+    /// Version of the point, if requested, the same on all replicas
+    pub version: Option<SeqNumberType>,
 }
 
 /// Current statistics and configuration of the collection
@@ -319,7 +330,7 @@ pub enum UpdateStatus {
     ClockRejected,
 }
 
-#[derive(Copy, Clone, Debug, Serialize, JsonSchema)]
+#[derive(Clone, Debug, Serialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub struct UpdateResult {
     /// Sequential number of the operation
@@ -333,6 +344,24 @@ pub struct UpdateResult {
     /// Provided if incoming update request also specify clock tick
     #[serde(skip)]
     pub clock_tag: Option<ClockTag>,
+
+    /// Token to read own writes, can be used as `consistency` parameter of read requests
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub session_token: Option<SessionToken>,
+
+    /// Points left out of the update, because their vectors don't match the collection
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub invalid_points: Vec<InvalidPoint>,
+}
+
+/// Point rejected by an update, while the valid rest of the update was applied
+#[derive(Clone, Debug, PartialEq, Serialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct InvalidPoint {
+    /// Point id
+    pub id: PointIdType,
+    /// Reason why the point was rejected
+    pub error: String,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -377,7 +406,10 @@ pub enum ScrollOrder {
     #[default]
     ById,
     ByField(OrderBy),
-    Random,
+    /// Random sample, the same seed gives the same sample of the same points
+    Random {
+        seed: Option<u64>,
+    },
 }
 
 /// Scroll request, used as a part of query request
@@ -440,15 +472,19 @@ fn points_example() -> Vec<api::rest::Record> {
             id: PointIdType::NumId(40),
             payload: Some(Payload(payload_map_1)),
             vector: Some(VectorStruct::Single(vec![0.875, 0.140625, 0.897_6])),
+            vector_metadata: None,
             shard_key: Some("region_1".into()),
             order_value: None,
+            version: None,
         },
         api::rest::Record {
             id: PointIdType::NumId(41),
             payload: Some(Payload(payload_map_2)),
             vector: Some(VectorStruct::Single(vec![0.75, 0.640625, 0.8945])),
+            vector_metadata: None,
             shard_key: Some("region_1".into()),
             order_value: None,
+            version: None,
         },
     ]
 }
@@ -501,6 +537,16 @@ pub struct CoreSearchRequest {
     /// Options for specifying which vectors to include into response. Default is false.
     pub with_vector: Option<WithVector>,
     pub score_threshold: Option<ScoreType>,
+    /// Exclude results with a better score than this, applied to the final scores of each shard.
+    pub max_score_threshold: Option<ScoreType>,
+    /// Return the raw distance of found points, derived from their final scores by each shard.
+    pub with_distance: bool,
+    /// Adjust the final score of found points with payload values.
+    /// Applied by the collection after merging results from shards, never sent to shards.
+    pub score_modifier: Option<ScoreModifier>,
+    /// Reorder found points with a registered reranker.
+    /// Applied by the collection after merging results from shards, never sent to shards.
+    pub rerank: Option<RerankParams>,
 }
 
 #[derive(Debug, Clone)]
@@ -538,6 +584,9 @@ pub struct PointRequestInternal {
     /// Options for specifying which vectors to include into response. Default is false.
     #[serde(default, alias = "with_vectors")]
     pub with_vector: WithVector,
+    /// If true, return the version of each point, which can be used as expected version of a conditional upsert. Default is false.
+    #[serde(default)]
+    pub with_version: bool,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
@@ -666,6 +715,13 @@ pub struct RecommendRequestInternal {
     /// Note: the other collection should have the same vector size as the current collection
     #[serde(default)]
     pub lookup_from: Option<LookupLocation>,
+
+    /// Balance between relevance and diversity of the results, using Maximal Marginal Relevance.
+    /// 1 - results are ordered by relevance only (default), 0 - results are as dissimilar to each
+    /// other as possible. Scores of the results stay the relevance scores.
+    #[serde(default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub mmr_lambda: Option<f32>,
 }
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
@@ -675,6 +731,66 @@ pub struct RecommendRequestBatch {
     pub searches: Vec<RecommendRequest>,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct NeighborsRequest {
+    #[serde(flatten)]
+    #[validate(nested)]
+    pub neighbors_request: NeighborsRequestInternal,
+    /// Specify in which shards to look for the points, if not specified - look in all shards
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shard_key: Option<ShardKeySelector>,
+}
+
+/// Nearest neighbors request.
+/// Looks for the points closest to a point which is already stored in the collection, by its
+/// stored vector.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct NeighborsRequestInternal {
+    /// Look for the nearest neighbors of this point
+    pub id: PointIdType,
+
+    /// Look only for points which satisfies this conditions
+    #[validate(nested)]
+    pub filter: Option<Filter>,
+
+    /// Additional search params
+    #[validate(nested)]
+    pub params: Option<SearchParams>,
+
+    /// Max number of result to return
+    #[serde(alias = "top")]
+    #[validate(range(min = 1))]
+    pub limit: usize,
+
+    /// Offset of the first result to return.
+    /// May be used to paginate results.
+    /// Note: large offset values may cause performance issues.
+    pub offset: Option<usize>,
+
+    /// Select which payload to return with the response. Default is false.
+    pub with_payload: Option<WithPayloadInterface>,
+
+    /// Options for specifying which vectors to include into response. Default is false.
+    #[serde(default, alias = "with_vectors")]
+    pub with_vector: Option<WithVector>,
+
+    /// Define a minimal score threshold for the result.
+    /// If defined, less similar results will not be returned.
+    /// Score of the returned result might be higher or smaller than the threshold depending on the
+    /// Distance function used. E.g. for cosine similarity only higher scores will be returned.
+    pub score_threshold: Option<ScoreType>,
+
+    /// Define which vector of the point to search by, if not specified - try to use default vector
+    #[serde(default)]
+    pub using: Option<UsingVector>,
+
+    /// If true, the point itself may be returned as one of its neighbors. Default: false
+    #[serde(default)]
+    pub include_self: Option<bool>,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct RecommendGroupsRequest {
@@ -748,6 +864,19 @@ impl ContextExamplePair {
     }
 }
 
+/// Pairs of examples, which count in the score with a common weight
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+pub struct WeightedContext {
+    /// Pairs of { positive, negative } examples of this context
+    #[validate(nested)]
+    pub pairs: Vec<ContextExamplePair>,
+
+    /// How many times each pair of this context counts in the score, compared to a pair of the
+    /// unweighted `context`.
+    #[validate(range(min = 1, max = 100))]
+    pub weight: u32,
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct DiscoverRequest {
     #[serde(flatten)]
@@ -785,6 +914,16 @@ pub struct DiscoverRequestInternal {
     #[validate(nested)]
     pub context: Option<Vec<ContextExamplePair>>,
 
+    /// Additional contexts, each pair of which counts in the score as many times as the weight of
+    /// its context. Pairs of `context` have a weight of 1.
+    ///
+    /// The contributions of all pairs are summed, so if a point is on the positive side of a pair
+    /// in one context and on the negative side of a pair in another, the context with the larger
+    /// weight wins, and pairs with equal weights cancel out.
+    #[serde(default)]
+    #[validate(nested)]
+    pub weighted_context: Option<Vec<WeightedContext>>,
+
     /// Look only for points which satisfies this conditions
     #[validate(nested)]
     pub filter: Option<Filter>,
@@ -819,6 +958,23 @@ pub struct DiscoverRequestInternal {
     pub lookup_from: Option<LookupLocation>,
 }
 
+impl DiscoverRequestInternal {
+    /// All context pairs of the request with their weights, the unweighted `context` first
+    pub fn weighted_pairs(&self) -> impl Iterator<Item = (&ContextExamplePair, u32)> {
+        let context = self
+            .context
+            .iter()
+            .flatten()
+            .map(|pair| (pair, DEFAULT_CONTEXT_WEIGHT));
+        let weighted_context = self
+            .weighted_context
+            .iter()
+            .flatten()
+            .flat_map(|context| context.pairs.iter().map(|pair| (pair, context.weight)));
+        context.chain(weighted_context)
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
 pub struct DiscoverRequestBatch {
     #[validate(nested)]
@@ -865,6 +1021,15 @@ pub struct CountRequestInternal {
     /// Approximate count might be unreliable during the indexing process. Default: true
     #[serde(default = "default_exact_count")]
     pub exact: bool,
+    /// If true, count each shard at a single version of its data, counting the shard again if it
+    /// was updated while counting. The total is coherent under concurrent updates, but may take
+    /// longer to compute. Default: false
+    #[serde(default)]
+    pub consistent: bool,
+    /// If true, shards that are unavailable are left out of the count, and the result is marked
+    /// as partial. If false, the count fails if any shard is unavailable. Default: false
+    #[serde(default)]
+    pub allow_partial: bool,
 }
 
 pub const fn default_exact_count() -> bool {
@@ -876,6 +1041,187 @@ pub const fn default_exact_count() -> bool {
 pub struct CountResult {
     /// Number of points which satisfy the conditions
     pub count: usize,
+    /// Whether the count is exact, or an estimation based on index statistics
+    pub exact: bool,
+    /// Whether some shards were unavailable and are left out of the count
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub partial: bool,
+}
+
+/// Parameters of reading memory-mapped collection data into the page cache
+#[derive(Debug, Clone, Default)]
+pub struct WarmupRequest {
+    /// Only warm up these shards. If not set - warm up all shards stored on this peer
+    pub shard_ids: Option<Vec<ShardId>>,
+    /// Also read quantized vectors stored on disk
+    pub with_quantization: bool,
+    /// Maximum number of bytes to read. If not set - half of the currently available memory
+    pub max_bytes: Option<usize>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct WarmupResult {
+    /// Number of bytes read into the page cache
+    pub warmed_bytes: usize,
+    /// Number of bytes skipped because they didn't fit into the budget
+    pub skipped_bytes: usize,
+}
+
+impl WarmupResult {
+    pub fn merge(&mut self, other: WarmupResult) {
+        self.warmed_bytes += other.warmed_bytes;
+        self.skipped_bytes += other.skipped_bytes;
+    }
+}
+
+/// Health status of a collection or a shard, ordered from best to worst
+/// `Green` - all good. `Yellow` - available, but needs attention or is in transition, `Red` - not fully available or some operations failed
+#[derive(Debug, Serialize, JsonSchema, PartialEq, Eq, PartialOrd, Ord, Copy, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthStatus {
+    #[default]
+    Green,
+    Yellow,
+    Red,
+}
+
+/// Reason of a health status other than green
+#[derive(Debug, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct HealthIssue {
+    pub status: HealthStatus,
+    /// Shard the issue applies to
+    pub shard_id: ShardId,
+    pub reason: String,
+}
+
+/// Health of the replica of a shard stored on this peer
+#[derive(Debug, Serialize, JsonSchema, Clone, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct LocalShardHealth {
+    /// Number of operations in the WAL, which are not yet applied to the segments
+    pub wal_lag: u64,
+    /// Disk space used by the segments of the shard
+    pub disk_usage_bytes: usize,
+    /// Free space on the disk of the shard. Not set if it can't be determined
+    pub free_disk_space_bytes: Option<u64>,
+    /// Whether the free disk space is below the configured minimum, in which case updates are rejected
+    pub disk_full: bool,
+    /// Optimizations which are currently running
+    pub running_optimizations: Vec<TrackerTelemetry>,
+    /// Error of the last failed optimization
+    pub optimizer_error: Option<String>,
+    /// Error of loading the shard, if the shard or any of its segments failed to load
+    pub load_error: Option<String>,
+}
+
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardHealth {
+    pub shard_id: ShardId,
+    pub status: HealthStatus,
+    /// States of all replicas of the shard in the cluster
+    pub replicas: HashMap<PeerId, ReplicaState>,
+    /// Health of the replica stored on this peer, if any
+    pub local: Option<LocalShardHealth>,
+}
+
+/// Health report of a collection
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionHealth {
+    /// Worst status of all shards
+    pub status: HealthStatus,
+    /// Reasons of the status, empty if the collection is green
+    pub issues: Vec<HealthIssue>,
+    pub shards: Vec<ShardHealth>,
+}
+
+/// Description of a segment stored on this peer, see [`crate::collection::Collection::list_segments`]
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct SegmentDescription {
+    pub shard_id: ShardId,
+    /// Id of the segment within the shard. Not persisted, changes on restart and when the segment is optimized
+    pub segment_id: SegmentId,
+    /// Whether the segment is being optimized. Updates are written to a separate segment until the optimization is finished
+    pub optimizing: bool,
+    /// Point counts, index state and resource usage of the segment
+    pub info: SegmentInfo,
+    /// Storage and index configuration of the segment, including the HNSW config each vector index is built with
+    pub config: SegmentConfig,
+    /// Number of points which are deleted, but not yet removed from the segment
+    pub num_deleted_points: usize,
+    /// Share of the stored points which are deleted
+    pub deleted_ratio: f64,
+    /// Whether all vectors are stored on disk
+    pub vectors_on_disk: bool,
+    /// Whether payloads are stored on disk
+    pub payload_on_disk: bool,
+}
+
+impl SegmentDescription {
+    pub fn new(
+        shard_id: ShardId,
+        segment_id: SegmentId,
+        optimizing: bool,
+        segment: &dyn SegmentEntry,
+    ) -> Self {
+        let num_deleted_points = segment.deleted_point_count();
+        let num_stored_points = segment.available_point_count() + num_deleted_points;
+        let deleted_ratio = if num_stored_points == 0 {
+            0.0
+        } else {
+            num_deleted_points as f64 / num_stored_points as f64
+        };
+
+        let config = segment.config().clone();
+        let vectors_on_disk = config
+            .vector_data
+            .values()
+            .all(|vector_data| vector_data.storage_type.is_on_disk())
+            && config
+                .sparse_vector_data
+                .values()
+                .all(|sparse_vector_data| sparse_vector_data.index.index_type.is_on_disk());
+        let payload_on_disk = config.payload_storage_type.is_on_disk();
+
+        Self {
+            shard_id,
+            segment_id,
+            optimizing,
+            info: segment.info(),
+            config,
+            num_deleted_points,
+            deleted_ratio,
+            vectors_on_disk,
+            payload_on_disk,
+        }
+    }
+}
+
+/// Estimated memory of a shard stored on this peer once loaded
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct ShardMemoryEstimation {
+    pub shard_id: ShardId,
+    /// Number of segments which would be loaded
+    pub segments_count: usize,
+    /// Estimated memory of all segments of the shard, by component
+    pub components: SegmentMemoryEstimation,
+}
+
+/// Estimated memory of a collection once loaded, see [`crate::collection::Collection::estimate_memory`]
+#[derive(Debug, Serialize, JsonSchema, Clone)]
+#[serde(rename_all = "snake_case")]
+pub struct CollectionMemoryEstimation {
+    /// Shards stored on this peer
+    pub shards: Vec<ShardMemoryEstimation>,
+    /// Estimated memory of all shards, by component
+    pub components: SegmentMemoryEstimation,
+    /// Bytes loaded into RAM and kept on disk by all components
+    pub total: MemoryEstimation,
 }
 
 #[derive(Error, Debug, Clone, PartialEq)]
@@ -918,6 +1264,8 @@ pub enum CollectionError {
     ObjectStoreError { what: String },
     #[error("Strict mode error: {description}")]
     StrictMode { description: String },
+    #[error("Resource exhausted: {description}")]
+    ResourceExhausted { description: String },
 }
 
 impl CollectionError {
@@ -937,6 +1285,29 @@ impl CollectionError {
         }
     }
 
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            CollectionError::BadInput { .. }
+            | CollectionError::BadRequest { .. }
+            | CollectionError::BadShardSelection { .. } => ErrorCode::InvalidArgument,
+            CollectionError::NotFound { .. } | CollectionError::PointNotFound { .. } => {
+                ErrorCode::NotFound
+            }
+            CollectionError::ServiceError { .. } | CollectionError::ObjectStoreError { .. } => {
+                ErrorCode::Internal
+            }
+            CollectionError::Cancelled { .. } => ErrorCode::Cancelled,
+            CollectionError::InconsistentShardFailure { first_err, .. } => first_err.code(),
+            CollectionError::ForwardProxyError { error, .. } => error.code(),
+            CollectionError::OutOfMemory { .. } | CollectionError::ResourceExhausted { .. } => {
+                ErrorCode::ResourceExhausted
+            }
+            CollectionError::Timeout { .. } => ErrorCode::DeadlineExceeded,
+            CollectionError::PreConditionFailed { .. } => ErrorCode::FailedPrecondition,
+            CollectionError::StrictMode { .. } => ErrorCode::PermissionDenied,
+        }
+    }
+
     pub fn bad_input(description: impl Into<String>) -> CollectionError {
         CollectionError::BadInput {
             description: description.into(),
@@ -992,6 +1363,12 @@ impl CollectionError {
         }
     }
 
+    pub fn resource_exhausted(description: impl Into<String>) -> CollectionError {
+        CollectionError::ResourceExhausted {
+            description: description.into(),
+        }
+    }
+
     pub fn strict_mode(error: impl Into<String>, solution: impl Into<String>) -> Self {
         let description = format!("{}. Help: {}", error.into(), solution.into());
         Self::StrictMode { description }
@@ -1017,6 +1394,8 @@ impl CollectionError {
             Self::ForwardProxyError { .. } => false,
             Self::ObjectStoreError { .. } => false,
             Self::StrictMode { .. } => false,
+            // Rejected before the update is applied to any replica, the client may retry it later
+            Self::ResourceExhausted { .. } => false,
         }
     }
 
@@ -1199,6 +1578,9 @@ impl From<tonic::Status> for CollectionError {
             tonic::Code::FailedPrecondition => CollectionError::PreConditionFailed {
                 description: format!("{err}"),
             },
+            tonic::Code::ResourceExhausted => CollectionError::ResourceExhausted {
+                description: format!("{err}"),
+            },
             _other => CollectionError::ServiceError {
                 error: format!("Tonic status error: {err}"),
                 backtrace: Some(Backtrace::force_capture().to_string()),
@@ -1299,6 +1681,7 @@ pub enum Datatype {
     Float32,
     Uint8,
     Float16,
+    Int8,
 }
 
 impl From<Datatype> for VectorStorageDatatype {
@@ -1307,10 +1690,28 @@ impl From<Datatype> for VectorStorageDatatype {
             Datatype::Float32 => VectorStorageDatatype::Float32,
             Datatype::Uint8 => VectorStorageDatatype::Uint8,
             Datatype::Float16 => VectorStorageDatatype::Float16,
+            Datatype::Int8 => VectorStorageDatatype::Int8,
         }
     }
 }
 
+/// How vectors with all components equal to zero are treated in collections with `Cosine` distance.
+///
+/// Cosine similarity is undefined for zero vectors.
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum ZeroVectorPolicy {
+    /// Zero vectors are stored, and score 0 against any query. A zero query scores 0 against any
+    /// point.
+    #[default]
+    ScoreZero,
+    /// Zero vectors are not stored, so the points are never found by this vector. A zero query
+    /// returns no results.
+    Exclude,
+    /// Zero vectors are rejected on upsert. A zero query returns no results.
+    Reject,
+}
+
 /// Params of single vector data storage
 #[derive(Debug, Hash, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1347,10 +1748,28 @@ pub struct VectorParams {
     ///   2 bytes.
     /// - For `uint8` datatype - vectors are stored as unsigned 8-bit integers, 1 byte.
     ///   It expects vector elements to be in range `[0, 255]`.
+    /// - For `int8` datatype - vectors are stored as signed 8-bit integers, 1 byte.
+    ///   It expects vector elements to be in range `[-128, 127]`, already quantized by the client.
+    ///   Scores are computed on raw integer values, without undoing the client-side scaling.
     pub datatype: Option<Datatype>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub multivector_config: Option<MultiVectorConfig>,
+
+    /// How vectors with all components equal to zero are treated, only applies to `Cosine`
+    /// distance. Default: `score_zero`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub zero_vector_policy: Option<ZeroVectorPolicy>,
+}
+
+impl VectorParams {
+    /// Policy for zero vectors, if they need special treatment with the distance of this vector
+    pub fn zero_vector_policy(&self) -> ZeroVectorPolicy {
+        match self.distance {
+            Distance::Cosine => self.zero_vector_policy.unwrap_or_default(),
+            Distance::Euclid | Distance::Dot | Distance::Manhattan => ZeroVectorPolicy::ScoreZero,
+        }
+    }
 }
 
 /// Validate the value is in `[1, 65536]` or `None`.
@@ -1397,6 +1816,11 @@ pub struct SparseVectorParams {
     /// Default: none
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub modifier: Option<Modifier>,
+
+    /// Similarity function used to compare sparse vectors. Can't be changed after creation.
+    /// Default: Dot
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<SparseDistance>,
 }
 
 impl Anonymize for SparseVectorParams {
@@ -1404,6 +1828,7 @@ impl Anonymize for SparseVectorParams {
         Self {
             index: self.index.anonymize(),
             modifier: self.modifier.clone(),
+            distance: self.distance,
         }
     }
 }
@@ -1575,10 +2000,12 @@ pub fn check_sparse_compatible(
     self_config: &BTreeMap<String, SparseVectorParams>,
     other_config: &BTreeMap<String, SparseVectorParams>,
 ) -> CollectionResult<()> {
-    for (vector_name, _this) in self_config.iter() {
-        let Some(_other) = other_config.get(vector_name) else {
+    for (vector_name, this) in self_config.iter() {
+        let Some(other) = other_config.get(vector_name) else {
             return Err(missing_vector_error(vector_name));
         };
+
+        check_sparse_distance_compatibility(this.distance, other.distance, vector_name)?;
     }
 
     Ok(())
@@ -1596,15 +2023,34 @@ pub fn check_sparse_compatible_with_segment_config(
         ));
     }
 
-    for (vector_name, _) in self_config.iter() {
-        if other.get(vector_name).is_none() {
+    for (vector_name, this) in self_config.iter() {
+        let Some(other) = other.get(vector_name) else {
             return Err(missing_vector_error(vector_name));
         };
+
+        check_sparse_distance_compatibility(this.distance, other.distance, vector_name)?;
     }
 
     Ok(())
 }
 
+fn check_sparse_distance_compatibility(
+    this: Option<SparseDistance>,
+    other: Option<SparseDistance>,
+    vector_name: &str,
+) -> CollectionResult<()> {
+    let (this, other) = (this.unwrap_or_default(), other.unwrap_or_default());
+    if this != other {
+        return Err(CollectionError::BadInput {
+            description: format!(
+                "Sparse vectors configuration is not compatible: \
+                 origin vector {vector_name} distance: {this:?}, while other vector distance: {other:?}",
+            ),
+        });
+    }
+    Ok(())
+}
+
 fn incompatible_vectors_error<'a, 'b>(
     this: impl Iterator<Item = &'a str>,
     other: impl Iterator<Item = &'b str>,
@@ -1838,6 +2284,10 @@ impl From<SearchRequestInternal> for CoreSearchRequest {
             with_payload: request.with_payload,
             with_vector: request.with_vector,
             score_threshold: request.score_threshold,
+            score_modifier: request.score_modifier,
+            rerank: request.rerank,
+            max_score_threshold: request.max_score_threshold,
+            with_distance: request.with_distance.unwrap_or_default(),
         }
     }
 }
@@ -1853,6 +2303,10 @@ impl From<SearchRequestInternal> for ShardQueryRequest {
             params,
             with_vector,
             with_payload,
+            score_modifier: _,
+            rerank: _,
+            max_score_threshold: _,
+            with_distance: _,
         } = value;
 
         Self {
@@ -1865,6 +2319,7 @@ impl From<SearchRequestInternal> for ShardQueryRequest {
             params,
             with_vector: with_vector.unwrap_or_default(),
             with_payload: with_payload.unwrap_or_default(),
+            seed: None,
         }
     }
 }
@@ -1880,6 +2335,10 @@ impl From<CoreSearchRequest> for ShardQueryRequest {
             params,
             with_vector,
             with_payload,
+            score_modifier: _,
+            rerank: _,
+            max_score_threshold: _,
+            with_distance: _,
         } = value;
 
         Self {
@@ -1892,6 +2351,7 @@ impl From<CoreSearchRequest> for ShardQueryRequest {
             params,
             with_vector: with_vector.unwrap_or_default(),
             with_payload: with_payload.unwrap_or_default(),
+            seed: None,
         }
     }
 }
@@ -12,7 +12,9 @@ use segment::types::{
     Condition, ExtendedPointId, Filter, HasIdCondition, PointIdType, SearchParams,
     WithPayloadInterface, WithVector,
 };
-use segment::vector_storage::query::{ContextPair, ContextQuery, DiscoveryQuery, RecoQuery};
+use segment::vector_storage::query::{
+    ContextPair, ContextQuery, DiscoveryQuery, RecoQuery, DEFAULT_CONTEXT_WEIGHT,
+};
 
 use super::shard_query::{Fusion, Sample, ScoringQuery, ShardPrefetch, ShardQueryRequest};
 use crate::common::fetch_vectors::ReferencedVectors;
@@ -36,6 +38,8 @@ pub struct CollectionQueryRequest {
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
     pub lookup_from: Option<LookupLocation>,
+    /// Seed for random sampling
+    pub seed: Option<u64>,
 }
 
 impl CollectionQueryRequest {
@@ -203,6 +207,7 @@ impl VectorQuery<VectorInput> {
                         negative: ids_to_vectors
                             .resolve_reference(lookup_collection, lookup_vector_name, pair.negative)
                             .unwrap(),
+                        weight: pair.weight,
                     })
                     .collect();
 
@@ -219,6 +224,7 @@ impl VectorQuery<VectorInput> {
                         negative: ids_to_vectors
                             .resolve_reference(lookup_collection, lookup_vector_name, pair.negative)
                             .unwrap(),
+                        weight: pair.weight,
                     })
                     .collect();
 
@@ -303,6 +309,8 @@ pub struct CollectionPrefetch {
     /// Search params for when there is no prefetch
     pub params: Option<SearchParams>,
     pub lookup_from: Option<LookupLocation>,
+    /// Weight of the results of this prefetch, if they are fused by the parent query
+    pub weight: Option<f32>,
 }
 
 /// Exclude the referenced ids by editing the filter.
@@ -392,6 +400,7 @@ impl CollectionPrefetch {
             query,
             filter: self.filter,
             score_threshold: self.score_threshold,
+            weight: self.weight,
             limit: self.limit,
             params: self.params,
         })
@@ -513,6 +522,7 @@ impl CollectionQueryRequest {
             params: self.params,
             with_vector: self.with_vector,
             with_payload: self.with_payload,
+            seed: self.seed,
         })
     }
 
@@ -615,6 +625,7 @@ mod from_rest {
                 with_vector,
                 with_payload,
                 lookup_from,
+                seed,
             } = value;
 
             Self {
@@ -629,6 +640,7 @@ mod from_rest {
                 with_vector: with_vector.unwrap_or(Self::DEFAULT_WITH_VECTOR),
                 with_payload: with_payload.unwrap_or(Self::DEFAULT_WITH_PAYLOAD),
                 lookup_from: lookup_from.map(LookupLocation::from),
+                seed,
             }
         }
     }
@@ -644,6 +656,7 @@ mod from_rest {
                 params,
                 limit,
                 lookup_from,
+                weight,
             } = value;
 
             Self {
@@ -655,6 +668,7 @@ mod from_rest {
                 limit: limit.unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params,
                 lookup_from,
+                weight,
             }
         }
     }
@@ -756,6 +770,7 @@ mod from_rest {
         ContextPair {
             positive: VectorInput::from(positive),
             negative: VectorInput::from(negative),
+            weight: DEFAULT_CONTEXT_WEIGHT,
         }
     }
 
@@ -859,6 +874,7 @@ pub mod from_grpc {
                 shard_key_selector: _,
                 lookup_from,
                 timeout: _,
+                seed,
             } = value;
 
             let request = CollectionQueryRequest {
@@ -885,6 +901,7 @@ pub mod from_grpc {
                     .transpose()?
                     .unwrap_or(CollectionQueryRequest::DEFAULT_WITH_PAYLOAD),
                 lookup_from: lookup_from.map(From::from),
+                seed,
             };
             Ok(request)
         }
@@ -903,6 +920,7 @@ pub mod from_grpc {
                 score_threshold,
                 limit,
                 lookup_from,
+                weight,
             } = value;
 
             let collection_query = Self {
@@ -919,6 +937,7 @@ pub mod from_grpc {
                     .unwrap_or(CollectionQueryRequest::DEFAULT_LIMIT),
                 params: params.map(From::from),
                 lookup_from: lookup_from.map(From::from),
+                weight,
             };
 
             Ok(collection_query)
@@ -1073,6 +1092,7 @@ pub mod from_grpc {
         Ok(ContextPair {
             positive: VectorInput::try_from(positive)?,
             negative: VectorInput::try_from(negative)?,
+            weight: DEFAULT_CONTEXT_WEIGHT,
         })
     }
 }
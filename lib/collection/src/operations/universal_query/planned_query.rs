@@ -44,6 +44,12 @@ pub struct RescoreParams {
 
     /// The payload to return
     pub with_payload: WithPayloadInterface,
+
+    /// Seed for random sampling, of the request the plan is for
+    pub seed: Option<u64>,
+
+    /// Weights of the sources in a fusion, in the same order
+    pub weights: Vec<f32>,
 }
 
 #[derive(Debug, PartialEq)]
@@ -87,6 +93,7 @@ impl PlannedQuery {
             offset,
             with_vector,
             with_payload,
+            seed,
             params,
         } = request;
         // Final offset is handled at collection level
@@ -112,6 +119,7 @@ impl PlannedQuery {
                     offset,
                     filter,
                     Some((with_payload, with_vector)),
+                    seed,
                 )?;
 
                 MergePlan {
@@ -121,6 +129,10 @@ impl PlannedQuery {
                     rescore_params: None,
                 }
             } else {
+                let weights = prefetches
+                    .iter()
+                    .map(ShardPrefetch::fusion_weight)
+                    .collect();
                 let sources = recurse_prefetches(
                     &mut self.searches,
                     &mut self.scrolls,
@@ -128,6 +140,7 @@ impl PlannedQuery {
                     offset,
                     filter,
                     None,
+                    seed,
                 )?;
 
                 MergePlan {
@@ -138,6 +151,8 @@ impl PlannedQuery {
                         score_threshold,
                         with_vector,
                         with_payload,
+                        seed,
+                        weights,
                     }),
                 }
             }
@@ -154,6 +169,10 @@ impl PlannedQuery {
                         offset: 0, // offset is handled at collection level
                         params,
                         limit,
+                        score_modifier: None,
+                        rerank: None,
+                        max_score_threshold: None,
+                        with_distance: false,
                     };
 
                     let idx = self.searches.len();
@@ -184,7 +203,7 @@ impl PlannedQuery {
                 Some(ScoringQuery::Sample(Sample::Random)) => {
                     // Everything should come from 1 scroll
                     let scroll = QueryScrollRequestInternal {
-                        scroll_order: ScrollOrder::Random,
+                        scroll_order: ScrollOrder::Random { seed },
                         limit,
                         filter,
                         with_vector,
@@ -236,6 +255,7 @@ fn recurse_prefetches(
     // Top-level fusion requests won't be merged on shard level, so we pass these params down one level to fetch on the sources.
     // Otherwise we would miss to fetch the payload and vector.
     with_payload_and_vector: Option<(WithPayloadInterface, WithVector)>,
+    seed: Option<u64>,
 ) -> CollectionResult<Vec<Source>> {
     let mut sources = Vec::with_capacity(prefetches.len());
 
@@ -250,6 +270,7 @@ fn recurse_prefetches(
             params,
             filter,
             score_threshold,
+            weight: _,
         } = prefetch;
 
         // Offset is replicated at each step from the root to the leaves
@@ -260,6 +281,10 @@ fn recurse_prefetches(
 
         let source = if !prefetches.is_empty() {
             // This has nested prefetches. Recurse into them
+            let weights = prefetches
+                .iter()
+                .map(ShardPrefetch::fusion_weight)
+                .collect();
             let inner_sources = recurse_prefetches(
                 core_searches,
                 scrolls,
@@ -267,6 +292,7 @@ fn recurse_prefetches(
                 root_offset,
                 filter,
                 None,
+                seed,
             )?;
 
             let rescore = query.ok_or_else(|| {
@@ -281,6 +307,8 @@ fn recurse_prefetches(
                     score_threshold,
                     with_vector: with_vector.clone(),
                     with_payload: with_payload.clone(),
+                    seed,
+                    weights,
                 }),
             };
 
@@ -298,6 +326,10 @@ fn recurse_prefetches(
                         with_vector: Some(with_vector.clone()),
                         with_payload: Some(with_payload.clone()),
                         score_threshold,
+                        score_modifier: None,
+                        rerank: None,
+                        max_score_threshold: None,
+                        with_distance: false,
                     };
 
                     let idx = core_searches.len();
@@ -326,7 +358,7 @@ fn recurse_prefetches(
                 }
                 Some(ScoringQuery::Sample(Sample::Random)) => {
                     let scroll = QueryScrollRequestInternal {
-                        scroll_order: ScrollOrder::Random,
+                        scroll_order: ScrollOrder::Random { seed },
                         filter,
                         with_vector: with_vector.clone(),
                         with_payload: with_payload.clone(),
@@ -415,6 +447,7 @@ mod tests {
                     params: None,
                     filter: Some(filter_inner_inner.clone()),
                     score_threshold: None,
+                    weight: None,
                 }],
                 query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                     NamedVectorStruct::new_from_vector(Vector::Dense(dummy_vector.clone()), "full"),
@@ -423,6 +456,7 @@ mod tests {
                 params: None,
                 filter: Some(filter_inner.clone()),
                 score_threshold: None,
+                weight: None,
             }],
             query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                 NamedVectorStruct::new_from_vector(
@@ -439,6 +473,7 @@ mod tests {
             params: None,
             with_vector: WithVector::Bool(true),
             with_payload: WithPayloadInterface::Bool(true),
+            seed: None,
         };
 
         let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
@@ -461,6 +496,10 @@ mod tests {
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: Some(WithVector::Bool(false)),
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
             }]
         );
 
@@ -480,6 +519,8 @@ mod tests {
                         score_threshold: None,
                         with_vector: WithVector::Bool(false),
                         with_payload: WithPayloadInterface::Bool(false),
+                        seed: None,
+                        weights: vec![1.0],
                     })
                 })],
                 rescore_params: Some(RescoreParams {
@@ -495,6 +536,8 @@ mod tests {
                     score_threshold: None,
                     with_vector: WithVector::Bool(true),
                     with_payload: WithPayloadInterface::Bool(true),
+                    seed: None,
+                    weights: vec![1.0],
                 })
             }]
         );
@@ -515,6 +558,7 @@ mod tests {
             params: Some(SearchParams::default()),
             with_vector: WithVector::Bool(true),
             with_payload: WithPayloadInterface::Bool(true),
+            seed: None,
         };
 
         let planned_query = PlannedQuery::try_from(vec![request]).unwrap();
@@ -533,6 +577,10 @@ mod tests {
                 with_vector: Some(WithVector::Bool(true)),
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 score_threshold: Some(0.5),
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
             }]
         );
 
@@ -577,6 +625,7 @@ mod tests {
                     params: None,
                     filter: Some(filter_inner1.clone()),
                     score_threshold: None,
+                    weight: None,
                 },
                 ShardPrefetch {
                     prefetches: Vec::new(),
@@ -590,6 +639,7 @@ mod tests {
                     params: None,
                     filter: Some(filter_inner2.clone()),
                     score_threshold: None,
+                    weight: None,
                 },
             ],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
@@ -599,6 +649,7 @@ mod tests {
             offset: 0,
             params: None,
             with_payload: WithPayloadInterface::Bool(false),
+            seed: None,
             with_vector: WithVector::Bool(true),
         };
 
@@ -619,6 +670,10 @@ mod tests {
                     with_payload: Some(WithPayloadInterface::Bool(false)),
                     with_vector: Some(WithVector::Bool(true)),
                     score_threshold: None,
+                    score_modifier: None,
+                    rerank: None,
+                    max_score_threshold: None,
+                    with_distance: false,
                 },
                 CoreSearchRequest {
                     query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
@@ -632,6 +687,10 @@ mod tests {
                     with_payload: Some(WithPayloadInterface::Bool(false)),
                     with_vector: Some(WithVector::Bool(true)),
                     score_threshold: None,
+                    score_modifier: None,
+                    rerank: None,
+                    max_score_threshold: None,
+                    with_distance: false,
                 }
             ]
         );
@@ -657,6 +716,7 @@ mod tests {
             params: None,
             with_vector: WithVector::Bool(true),
             with_payload: WithPayloadInterface::Bool(false),
+            seed: None,
         };
 
         let planned_query = PlannedQuery::try_from(vec![request]);
@@ -691,6 +751,7 @@ mod tests {
                 params: dummy_params,
                 filter: dummy_filter.clone(),
                 score_threshold: Some(0.1),
+                weight: None,
             }],
             query: Some(ScoringQuery::Fusion(Fusion::Rrf)),
             filter: Some(Filter::default()),
@@ -704,6 +765,7 @@ mod tests {
                 ..Default::default()
             }),
             with_payload: WithPayloadInterface::Bool(true),
+            seed: None,
             with_vector: WithVector::Bool(false),
         };
 
@@ -730,7 +792,11 @@ mod tests {
                 offset: 0,
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(false)),
-                score_threshold: Some(0.1)
+                score_threshold: Some(0.1),
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
             }]
         )
     }
@@ -755,6 +821,7 @@ mod tests {
                         params: None,
                         filter: None,
                         score_threshold: None,
+                        weight: None,
                     },
                 )
             }
@@ -769,6 +836,7 @@ mod tests {
             params: None,
             filter: None,
             score_threshold: None,
+            weight: None,
         };
         make_prefetches_at_depth_acc(depth - 1, prefetch)
     }
@@ -788,6 +856,7 @@ mod tests {
             params: None,
             with_vector: WithVector::Bool(true),
             with_payload: WithPayloadInterface::Bool(false),
+            seed: None,
         };
         assert_eq!(request.prefetches_depth(), 0);
 
@@ -806,6 +875,7 @@ mod tests {
                     params: None,
                     filter: None,
                     score_threshold: None,
+                    weight: None,
                 }],
                 query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                     NamedVectorStruct::new_from_vector(Vector::Dense(vec![1.0, 2.0, 3.0]), "dense"),
@@ -814,6 +884,7 @@ mod tests {
                 params: None,
                 filter: None,
                 score_threshold: None,
+                weight: None,
             }],
             query: Some(ScoringQuery::Vector(QueryEnum::Nearest(
                 NamedVectorStruct::new_from_vector(Vector::Dense(vec![1.0, 2.0, 3.0]), "dense"),
@@ -822,6 +893,7 @@ mod tests {
             params: None,
             filter: None,
             score_threshold: None,
+            weight: None,
         }];
         assert_eq!(request.prefetches_depth(), 3);
 
@@ -849,6 +921,7 @@ mod tests {
             params: None,
             limit,
             score_threshold: None,
+            weight: None,
         }
     }
 
@@ -860,6 +933,7 @@ mod tests {
             params: None,
             filter: None,
             score_threshold: None,
+            weight: None,
         }
     }
 
@@ -882,6 +956,7 @@ mod tests {
                 offset: 0,
                 params: None,
                 with_payload: WithPayloadInterface::Bool(false),
+                seed: None,
                 with_vector: WithVector::Bool(false),
             },
             // A no-prefetch scroll query
@@ -894,6 +969,7 @@ mod tests {
                 offset: 0,
                 params: None,
                 with_payload: WithPayloadInterface::Bool(false),
+                seed: None,
                 with_vector: WithVector::Bool(false),
             },
             // A double fusion query
@@ -905,6 +981,7 @@ mod tests {
                         filter: None,
                         params: None,
                         score_threshold: None,
+                        weight: None,
                         limit: 10,
                     },
                     dummy_scroll_prefetch(50),
@@ -916,6 +993,7 @@ mod tests {
                 offset: 0,
                 params: None,
                 with_payload: WithPayloadInterface::Bool(true),
+                seed: None,
                 with_vector: WithVector::Bool(true),
             },
         ];
@@ -946,6 +1024,8 @@ mod tests {
                                 score_threshold: None,
                                 with_vector: WithVector::Bool(true),
                                 with_payload: WithPayloadInterface::Bool(true),
+                                seed: None,
+                                weights: vec![1.0, 1.0],
                             }),
                         }),
                         Source::ScrollsIdx(1),
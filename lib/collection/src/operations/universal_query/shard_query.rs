@@ -19,7 +19,7 @@ pub type ShardQueryResponse = Vec<Vec<ScoredPoint>>;
 /// Internal representation of a universal query request.
 ///
 /// Direct translation of the user-facing request, but with all point ids substituted with their corresponding vectors.
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ShardQueryRequest {
     pub prefetches: Vec<ShardPrefetch>,
     pub query: Option<ScoringQuery>,
@@ -31,6 +31,8 @@ pub struct ShardQueryRequest {
     pub params: Option<SearchParams>,
     pub with_vector: WithVector,
     pub with_payload: WithPayloadInterface,
+    /// Seed for random sampling, the same seed gives the same sample of the same points
+    pub seed: Option<u64>,
 }
 
 impl ShardQueryRequest {
@@ -41,6 +43,12 @@ impl ShardQueryRequest {
             .max()
             .unwrap_or(0)
     }
+
+    /// Whether the request or any of its prefetches samples points randomly
+    pub fn has_random_sample(&self) -> bool {
+        matches!(self.query, Some(ScoringQuery::Sample(_)))
+            || self.prefetches.iter().any(ShardPrefetch::has_random_sample)
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -94,6 +102,7 @@ impl ScoringQuery {
     /// Returns the expected order of results, depending on the type of query
     pub fn order(
         opt_self: Option<&Self>,
+        params: Option<&SearchParams>,
         collection_params: &CollectionParams,
     ) -> CollectionResult<Option<Order>> {
         let order = match opt_self {
@@ -102,7 +111,7 @@ impl ScoringQuery {
                     if query_enum.is_distance_scored() {
                         Some(
                             collection_params
-                                .get_distance(query_enum.get_vector_name())?
+                                .get_search_distance(query_enum.get_vector_name(), params)?
                                 .distance_order(),
                         )
                     } else {
@@ -125,7 +134,7 @@ impl ScoringQuery {
     }
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, PartialEq)]
 pub struct ShardPrefetch {
     pub prefetches: Vec<ShardPrefetch>,
     pub query: Option<ScoringQuery>,
@@ -133,9 +142,17 @@ pub struct ShardPrefetch {
     pub params: Option<SearchParams>,
     pub filter: Option<Filter>,
     pub score_threshold: Option<ScoreType>,
+    /// Weight of the results of this prefetch, if they are fused by the parent query
+    pub weight: Option<f32>,
 }
 
 impl ShardPrefetch {
+    pub const DEFAULT_FUSION_WEIGHT: f32 = 1.0;
+
+    pub fn fusion_weight(&self) -> f32 {
+        self.weight.unwrap_or(Self::DEFAULT_FUSION_WEIGHT)
+    }
+
     pub fn depth(&self) -> usize {
         let mut depth = 1;
         for prefetch in &self.prefetches {
@@ -143,6 +160,11 @@ impl ShardPrefetch {
         }
         depth
     }
+
+    fn has_random_sample(&self) -> bool {
+        matches!(self.query, Some(ScoringQuery::Sample(_)))
+            || self.prefetches.iter().any(Self::has_random_sample)
+    }
 }
 
 impl ShardQueryRequest {
@@ -187,6 +209,7 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
             offset,
             with_payload,
             with_vectors,
+            seed,
         } = value;
 
         let request = Self {
@@ -209,6 +232,7 @@ impl TryFrom<grpc::QueryShardPoints> for ShardQueryRequest {
                 .map(WithPayloadInterface::try_from)
                 .transpose()?
                 .unwrap_or(WithPayloadInterface::Bool(true)),
+            seed,
         };
 
         Ok(request)
@@ -227,6 +251,7 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
             filter,
             score_threshold,
             using,
+            weight,
         } = value;
 
         let shard_prefetch = Self {
@@ -241,6 +266,7 @@ impl TryFrom<grpc::query_shard_points::Prefetch> for ShardPrefetch {
             params: params.map(SearchParams::from),
             filter: filter.map(Filter::try_from).transpose()?,
             score_threshold,
+            weight,
         };
 
         Ok(shard_prefetch)
@@ -430,6 +456,7 @@ impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
             params,
             filter,
             score_threshold,
+            weight,
         } = value;
         Self {
             prefetch: prefetches.into_iter().map(Self::from).collect(),
@@ -441,6 +468,7 @@ impl From<ShardPrefetch> for grpc::query_shard_points::Prefetch {
             params: params.map(grpc::SearchParams::from),
             score_threshold,
             limit: limit as u64,
+            weight,
         }
     }
 }
@@ -457,6 +485,7 @@ impl From<ShardQueryRequest> for grpc::QueryShardPoints {
             params,
             with_vector,
             with_payload,
+            seed,
         } = value;
 
         Self {
@@ -475,6 +504,7 @@ impl From<ShardQueryRequest> for grpc::QueryShardPoints {
             offset: offset as u64,
             with_payload: Some(grpc::WithPayloadSelector::from(with_payload)),
             with_vectors: Some(grpc::WithVectorsSelector::from(with_vector)),
+            seed,
         }
     }
 }
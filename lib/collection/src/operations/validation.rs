@@ -166,6 +166,7 @@ mod tests {
         GeoPolygon {
             exterior: Some(exterior_line),
             interiors: interior_lines,
+            crosses_antimeridian: None,
         }
     }
 
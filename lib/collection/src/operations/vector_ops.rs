@@ -2,14 +2,17 @@ use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 
 use api::rest::schema::ShardKeySelector;
+use itertools::Itertools as _;
 use schemars::JsonSchema;
 use segment::types::{Filter, PointIdType};
 use serde::{Deserialize, Serialize};
 use strum::{EnumDiscriminants, EnumIter};
 use validator::{Validate, ValidationError, ValidationErrors};
 
-use super::point_ops::PointIdsList;
+use super::point_ops::{check_point_vectors, exclude_zero_vectors, PointIdsList};
+use super::types::CollectionError;
 use super::{point_to_shards, split_iter_by_shard, OperationToShard, SplitByShard};
+use crate::config::CollectionParams;
 use crate::hash_ring::HashRingRouter;
 
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone)]
@@ -60,12 +63,16 @@ pub struct DeleteVectors {
     pub shard_key: Option<ShardKeySelector>,
 }
 
-#[derive(Clone, Debug, PartialEq, Deserialize, Serialize, Validate)]
+#[derive(Clone, Debug, Default, PartialEq, Deserialize, Serialize, Validate)]
 pub struct UpdateVectorsOp {
     /// Points with named vectors
     #[validate(nested)]
     #[validate(length(min = 1, message = "must specify points to update"))]
     pub points: Vec<PointVectors>,
+    /// Vectors to delete from points as part of the update, in place of zero vectors excluded
+    /// by the zero vector policy of the collection
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub delete_vectors: Vec<(PointIdType, String)>,
 }
 
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, EnumDiscriminants)]
@@ -91,7 +98,13 @@ impl VectorOperations {
 
     pub fn point_ids(&self) -> Vec<PointIdType> {
         match self {
-            Self::UpdateVectors(op) => op.points.iter().map(|point| point.id).collect(),
+            Self::UpdateVectors(op) => op
+                .points
+                .iter()
+                .map(|point| point.id)
+                .chain(op.delete_vectors.iter().map(|(id, _)| *id))
+                .unique()
+                .collect(),
             Self::DeleteVectors(points, _) => points.points.clone(),
             Self::DeleteVectorsByFilter(_, _) => Vec::new(),
         }
@@ -102,11 +115,55 @@ impl VectorOperations {
         F: Fn(&PointIdType) -> bool,
     {
         match self {
-            Self::UpdateVectors(op) => op.points.retain(|point| filter(&point.id)),
+            Self::UpdateVectors(op) => {
+                op.points.retain(|point| filter(&point.id));
+                op.delete_vectors.retain(|(id, _)| filter(id));
+            }
             Self::DeleteVectors(points, _) => points.points.retain(filter),
             Self::DeleteVectorsByFilter(_, _) => (),
         }
     }
+
+    pub fn invalid_vectors(
+        &self,
+        params: &CollectionParams,
+    ) -> Vec<(PointIdType, CollectionError)> {
+        match self {
+            Self::UpdateVectors(op) => op
+                .points
+                .iter()
+                .filter_map(|point| {
+                    check_point_vectors(params, point.id, &point.vector)
+                        .err()
+                        .map(|err| (point.id, err))
+                })
+                .collect(),
+            Self::DeleteVectors(..) => Vec::new(),
+            Self::DeleteVectorsByFilter(..) => Vec::new(),
+        }
+    }
+
+    /// Remove zero vectors which the collection excludes by its zero vector policy.
+    ///
+    /// Vector updates keep other vectors of a point, so the removed vectors are deleted from
+    /// the points by the same operation instead.
+    pub fn exclude_zero_vectors(&mut self, params: &CollectionParams) {
+        match self {
+            Self::UpdateVectors(op) => {
+                let UpdateVectorsOp {
+                    points,
+                    delete_vectors,
+                } = op;
+                points.retain_mut(|point| {
+                    let names = exclude_zero_vectors(params, &mut point.vector);
+                    delete_vectors.extend(names.into_iter().map(|name| (point.id, name)));
+                    !point.vector.is_empty()
+                });
+            }
+            Self::DeleteVectors(..) => (),
+            Self::DeleteVectorsByFilter(..) => (),
+        }
+    }
 }
 
 impl Validate for VectorOperations {
@@ -129,28 +186,34 @@ impl SplitByShard for VectorOperations {
     fn split_by_shard(self, ring: &HashRingRouter) -> OperationToShard<Self> {
         match self {
             VectorOperations::UpdateVectors(update_vectors) => {
-                let shard_points = update_vectors
-                    .points
-                    .into_iter()
-                    .flat_map(|point| {
-                        point_to_shards(&point.id, ring)
-                            .into_iter()
-                            .map(move |shard_id| (shard_id, point.clone()))
-                    })
-                    .fold(
-                        HashMap::new(),
-                        |mut map: HashMap<u32, Vec<PointVectors>>, (shard_id, points)| {
-                            map.entry(shard_id).or_default().push(points);
-                            map
-                        },
-                    );
-                let shard_ops = shard_points.into_iter().map(|(shard_id, points)| {
-                    (
-                        shard_id,
-                        VectorOperations::UpdateVectors(UpdateVectorsOp { points }),
-                    )
-                });
-                OperationToShard::by_shard(shard_ops)
+                let UpdateVectorsOp {
+                    points,
+                    delete_vectors,
+                } = update_vectors;
+                let mut shard_ops: HashMap<u32, UpdateVectorsOp> = HashMap::new();
+                for point in points {
+                    for shard_id in point_to_shards(&point.id, ring) {
+                        shard_ops
+                            .entry(shard_id)
+                            .or_default()
+                            .points
+                            .push(point.clone());
+                    }
+                }
+                for (id, name) in delete_vectors {
+                    for shard_id in point_to_shards(&id, ring) {
+                        shard_ops
+                            .entry(shard_id)
+                            .or_default()
+                            .delete_vectors
+                            .push((id, name.clone()));
+                    }
+                }
+                OperationToShard::by_shard(
+                    shard_ops
+                        .into_iter()
+                        .map(|(shard_id, op)| (shard_id, VectorOperations::UpdateVectors(op))),
+                )
             }
             VectorOperations::DeleteVectors(ids, vector_names) => {
                 split_iter_by_shard(ids.points, |id| *id, ring)
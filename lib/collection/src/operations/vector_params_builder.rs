@@ -3,7 +3,7 @@ use std::num::NonZeroU64;
 use segment::types::{Distance, MultiVectorConfig, QuantizationConfig};
 
 use crate::operations::config_diff::HnswConfigDiff;
-use crate::operations::types::{Datatype, VectorParams};
+use crate::operations::types::{Datatype, VectorParams, ZeroVectorPolicy};
 
 pub struct VectorParamsBuilder {
     vector_params: VectorParams,
@@ -20,6 +20,7 @@ impl VectorParamsBuilder {
                 on_disk: None,
                 datatype: None,
                 multivector_config: None,
+                zero_vector_policy: None,
             },
         }
     }
@@ -49,6 +50,11 @@ impl VectorParamsBuilder {
         self
     }
 
+    pub fn with_zero_vector_policy(mut self, zero_vector_policy: ZeroVectorPolicy) -> Self {
+        self.vector_params.zero_vector_policy = Some(zero_vector_policy);
+        self
+    }
+
     pub fn build(self) -> VectorParams {
         self.vector_params
     }
@@ -10,6 +10,10 @@ impl StrictModeVerification for DiscoverRequestInternal {
         Some(self.limit)
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.min(max_limit);
+    }
+
     fn indexed_filter_read(&self) -> Option<&Filter> {
         self.filter.as_ref()
     }
@@ -45,6 +49,12 @@ impl StrictModeVerification for DiscoverRequestBatch {
         Ok(())
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        for i in self.searches.iter_mut() {
+            i.discover_request.clamp_query_limit(max_limit);
+        }
+    }
+
     fn query_limit(&self) -> Option<usize> {
         None
     }
@@ -7,6 +7,10 @@ impl StrictModeVerification for FacetRequestInternal {
         self.limit
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.map(|limit| limit.min(max_limit));
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
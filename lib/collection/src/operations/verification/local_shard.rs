@@ -1,11 +1,26 @@
 use super::StrictModeVerification;
+use crate::operations::config_diff::StrictModeConfig;
 use crate::operations::types::ScrollRequestInternal;
 
 impl StrictModeVerification for ScrollRequestInternal {
+    fn apply_custom(&mut self, strict_mode_config: &StrictModeConfig) {
+        // Unfiltered scrolls are commonly used to dump the whole collection
+        if let Some(scroll_max_limit) = strict_mode_config.scroll_max_limit {
+            if self.filter.is_none() {
+                let limit = self.limit.unwrap_or_else(Self::default_limit);
+                self.limit = Some(limit.min(scroll_max_limit));
+            }
+        }
+    }
+
     fn query_limit(&self) -> Option<usize> {
         self.limit
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.map(|limit| limit.min(max_limit));
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
@@ -26,3 +41,37 @@ impl StrictModeVerification for ScrollRequestInternal {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_apply_scroll_max_limit() {
+        let strict_mode_config = StrictModeConfig {
+            enabled: Some(true),
+            scroll_max_limit: Some(5),
+            ..Default::default()
+        };
+
+        // Missing limit falls back to the default one, which is capped
+        let mut request: ScrollRequestInternal =
+            serde_json::from_value(serde_json::json!({})).unwrap();
+        request.apply_custom(&strict_mode_config);
+        assert_eq!(request.limit, Some(5));
+
+        let mut request: ScrollRequestInternal =
+            serde_json::from_value(serde_json::json!({ "limit": 3 })).unwrap();
+        request.apply_custom(&strict_mode_config);
+        assert_eq!(request.limit, Some(3));
+
+        // Filtered scrolls are not affected
+        let mut request: ScrollRequestInternal = serde_json::from_value(serde_json::json!({
+            "limit": 100,
+            "filter": { "must": [{ "key": "a", "match": { "value": 1 } }] },
+        }))
+        .unwrap();
+        request.apply_custom(&strict_mode_config);
+        assert_eq!(request.limit, Some(100));
+    }
+}
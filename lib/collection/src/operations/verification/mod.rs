@@ -2,6 +2,7 @@ mod count;
 mod discovery;
 mod facet;
 mod local_shard;
+mod neighbors;
 mod recommend;
 mod search;
 mod update;
@@ -39,9 +40,16 @@ pub trait StrictModeVerification {
         Ok(())
     }
 
+    /// Implementing this method allows adjusting request specific values before they are checked.
+    fn apply_custom(&mut self, _strict_mode_config: &StrictModeConfig) {}
+
     /// Implement this to check the limit of a request.
     fn query_limit(&self) -> Option<usize>;
 
+    /// Implement this to reduce the limit of a request to `max_limit`, if strict mode clamps limits
+    /// instead of rejecting the request.
+    fn clamp_query_limit(&mut self, _max_limit: usize) {}
+
     /// Implement this to check the timeout of a request.
     fn timeout(&self) -> Option<usize>;
 
@@ -84,6 +92,17 @@ pub trait StrictModeVerification {
         )
     }
 
+    /// Clamps the request limit to `max_query_limit`, if enabled by `clamp_query_limit`.
+    /// Must be called before `check_strict_mode`, so that clamped limits pass the limit checks.
+    fn apply_query_limit(&mut self, strict_mode_config: &StrictModeConfig) {
+        if strict_mode_config.clamp_query_limit != Some(true) {
+            return;
+        }
+        if let Some(max_query_limit) = strict_mode_config.max_query_limit {
+            self.clamp_query_limit(max_query_limit);
+        }
+    }
+
     /// Checks search parameters.
     fn check_search_params(
         &self,
@@ -0,0 +1,32 @@
+use super::StrictModeVerification;
+use crate::operations::types::NeighborsRequestInternal;
+
+impl StrictModeVerification for NeighborsRequestInternal {
+    fn query_limit(&self) -> Option<usize> {
+        Some(self.limit)
+    }
+
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.min(max_limit);
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&segment::types::Filter> {
+        self.filter.as_ref()
+    }
+
+    fn indexed_filter_write(&self) -> Option<&segment::types::Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&segment::types::SearchParams> {
+        self.params.as_ref()
+    }
+}
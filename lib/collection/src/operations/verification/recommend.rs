@@ -6,6 +6,10 @@ impl StrictModeVerification for RecommendRequestInternal {
         Some(self.limit)
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.min(max_limit);
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
@@ -1,7 +1,7 @@
 use api::rest::{SearchGroupsRequestInternal, SearchRequestInternal};
 use segment::types::{Filter, SearchParams};
 
-use super::StrictModeVerification;
+use super::{check_limit_opt, StrictModeVerification};
 use crate::collection::Collection;
 use crate::operations::config_diff::StrictModeConfig;
 use crate::operations::types::{CollectionError, SearchRequestBatch};
@@ -15,6 +15,10 @@ impl StrictModeVerification for SearchRequestInternal {
         Some(self.limit)
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        self.limit = self.limit.min(max_limit);
+    }
+
     fn timeout(&self) -> Option<usize> {
         None
     }
@@ -46,6 +50,12 @@ impl StrictModeVerification for SearchRequestBatch {
         Ok(())
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        for search_request in &mut self.searches {
+            search_request.search_request.clamp_query_limit(max_limit);
+        }
+    }
+
     fn query_limit(&self) -> Option<usize> {
         None
     }
@@ -72,10 +82,35 @@ impl StrictModeVerification for SearchRequestBatch {
 }
 
 impl StrictModeVerification for SearchGroupsRequestInternal {
+    fn check_custom(
+        &self,
+        _collection: &Collection,
+        strict_mode_config: &StrictModeConfig,
+    ) -> Result<(), CollectionError> {
+        // Every group returns up to `group_size` points, so the number of points is bounded too
+        let points_limit = (self.group_request.limit as usize)
+            .saturating_mul(self.group_request.group_size as usize);
+        check_limit_opt(
+            Some(points_limit),
+            strict_mode_config.max_query_limit,
+            "limit * group_size",
+        )
+    }
+
     fn query_limit(&self) -> Option<usize> {
         Some(self.group_request.limit as usize)
     }
 
+    fn clamp_query_limit(&mut self, max_limit: usize) {
+        let max_limit = u32::try_from(max_limit).unwrap_or(u32::MAX);
+        let group_request = &mut self.group_request;
+        group_request.group_size = group_request.group_size.min(max_limit);
+        // Both are at least 1, so there is at least one group left
+        group_request.limit = group_request
+            .limit
+            .min(max_limit / group_request.group_size);
+    }
+
     fn indexed_filter_read(&self) -> Option<&Filter> {
         self.filter.as_ref()
     }
@@ -97,3 +132,28 @@ impl StrictModeVerification for SearchGroupsRequestInternal {
         None
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clamp_search_groups_limit() {
+        let mut request: SearchGroupsRequestInternal = serde_json::from_value(serde_json::json!({
+            "vector": [1.0, 0.0],
+            "group_by": "key",
+            "group_size": 10,
+            "limit": 50,
+        }))
+        .unwrap();
+
+        request.clamp_query_limit(100);
+        assert_eq!(request.group_request.group_size, 10);
+        assert_eq!(request.group_request.limit, 10);
+
+        // Groups larger than the cap are reduced to a single group
+        request.clamp_query_limit(5);
+        assert_eq!(request.group_request.group_size, 5);
+        assert_eq!(request.group_request.limit, 1);
+    }
+}
@@ -1,7 +1,7 @@
 use segment::types::Filter;
 
 use super::StrictModeVerification;
-use crate::operations::payload_ops::{DeletePayload, SetPayload};
+use crate::operations::payload_ops::{CopyPayloadField, DeletePayload, SetPayload};
 use crate::operations::point_ops::PointsSelector;
 use crate::operations::vector_ops::DeleteVectors;
 
@@ -86,6 +86,32 @@ impl StrictModeVerification for SetPayload {
     }
 }
 
+impl StrictModeVerification for CopyPayloadField {
+    fn indexed_filter_write(&self) -> Option<&Filter> {
+        self.filter.as_ref()
+    }
+
+    fn query_limit(&self) -> Option<usize> {
+        None
+    }
+
+    fn timeout(&self) -> Option<usize> {
+        None
+    }
+
+    fn indexed_filter_read(&self) -> Option<&Filter> {
+        None
+    }
+
+    fn request_exact(&self) -> Option<bool> {
+        None
+    }
+
+    fn request_search_params(&self) -> Option<&segment::types::SearchParams> {
+        None
+    }
+}
+
 impl StrictModeVerification for DeletePayload {
     fn indexed_filter_write(&self) -> Option<&Filter> {
         self.filter.as_ref()
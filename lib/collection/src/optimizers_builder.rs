@@ -4,6 +4,7 @@ use std::sync::Arc;
 use schemars::JsonSchema;
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::types::{HnswConfig, QuantizationConfig};
+use segment::vector_storage::dense::vector_deduplication::VectorDeduplication;
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -11,6 +12,7 @@ use crate::collection_manager::optimizers::config_mismatch_optimizer::ConfigMism
 use crate::collection_manager::optimizers::indexing_optimizer::IndexingOptimizer;
 use crate::collection_manager::optimizers::merge_optimizer::MergeOptimizer;
 use crate::collection_manager::optimizers::segment_optimizer::OptimizerThresholds;
+use crate::collection_manager::optimizers::split_optimizer::SplitOptimizer;
 use crate::collection_manager::optimizers::vacuum_optimizer::VacuumOptimizer;
 use crate::config::CollectionParams;
 use crate::update_handler::Optimizer;
@@ -25,6 +27,13 @@ pub struct OptimizersConfig {
     /// The minimal fraction of deleted vectors in a segment, required to perform segment optimization
     #[validate(range(min = 0.0, max = 1.0))]
     pub deleted_threshold: f64,
+    /// The minimal fraction of points removed from the payload index of a field in a segment,
+    /// required to perform segment optimization. Removed points are kept in payload indexes of
+    /// optimized segments until they are optimized again, which slows down filtering.
+    /// If not set, `deleted_threshold` is used.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub payload_index_deleted_threshold: Option<f64>,
     /// The minimal number of vectors in a segment, required to perform segment optimization
     #[validate(range(min = 100))]
     pub vacuum_min_vector_number: usize,
@@ -71,12 +80,49 @@ pub struct OptimizersConfig {
     pub indexing_threshold: Option<usize>,
     /// Minimum interval between forced flushes.
     pub flush_interval_sec: u64,
+    /// Minimal size (in kilobytes) of writes to buffer in appendable segments before they are flushed.
+    /// Small frequent flushes fragment the data into many small segments on ingest.
+    /// Writes are buffered in memory, and covered by the WAL, until this size is reached or
+    /// `max_flush_delay_sec` has passed. Buffered writes are always flushed on shutdown.
+    /// If not set, segments are flushed every `flush_interval_sec`.
+    ///
+    /// Note: 1Kb = 1 vector of size 256
+    #[serde(alias = "min_segment_size_kb")]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_segment_size: Option<usize>,
+    /// Maximum time (in seconds) writes are buffered for before they are flushed, when
+    /// `min_segment_size` is set.
+    /// Default: 60
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_flush_delay_sec: Option<u64>,
     /// Max number of threads (jobs) for running optimizations per shard.
     /// Note: each optimization job will also use `max_indexing_threads` threads by itself for index building.
     /// If null - have no limit and choose dynamically to saturate CPU.
     /// If 0 - no optimization threads, optimizations will be disabled.
     #[serde(default)]
     pub max_optimization_threads: Option<usize>,
+    /// Store exact duplicate vectors only once in optimized segments with on-disk vector storage.
+    /// Does not affect search results.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub deduplicate_vectors: Option<bool>,
+    /// If set along with `deduplicate_vectors`, also store vectors whose components all differ by
+    /// at most this value only once.
+    /// Changes scores of merged vectors, so only use it if near-duplicates are interchangeable.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 0.0))]
+    pub deduplication_epsilon: Option<f64>,
+    /// Pause vector indexing, e.g. for the duration of a bulk load.
+    /// New and optimized segments are kept plain, and searched by full scan, until indexing is resumed.
+    /// Segments which are large enough are indexed in the background once this is unset or set to false.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub defer_indexing: Option<bool>,
+    /// Split segments which grew beyond twice `max_segment_size` into several segments no larger
+    /// than `max_segment_size`.
+    /// Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub split_oversized_segments: Option<bool>,
 }
 
 impl OptimizersConfig {
@@ -84,13 +130,20 @@ impl OptimizersConfig {
     pub fn fixture() -> Self {
         Self {
             deleted_threshold: 0.1,
+            payload_index_deleted_threshold: None,
             vacuum_min_vector_number: 1000,
             default_segment_number: 0,
             max_segment_size: None,
             memmap_threshold: None,
             indexing_threshold: Some(100_000),
             flush_interval_sec: 60,
+            min_segment_size: None,
+            max_flush_delay_sec: None,
             max_optimization_threads: Some(0),
+            deduplicate_vectors: None,
+            deduplication_epsilon: None,
+            defer_indexing: None,
+            split_oversized_segments: None,
         }
     }
 
@@ -105,8 +158,20 @@ impl OptimizersConfig {
         }
     }
 
+    /// Whether vector indexing is paused, see [`OptimizersConfig::defer_indexing`]
+    pub fn is_indexing_deferred(&self) -> bool {
+        self.defer_indexing.unwrap_or_default()
+    }
+
+    /// Whether oversized segments are split, see [`OptimizersConfig::split_oversized_segments`]
+    pub fn is_segment_splitting_enabled(&self) -> bool {
+        self.split_oversized_segments.unwrap_or_default()
+    }
+
     pub fn optimizer_thresholds(&self, num_indexing_threads: usize) -> OptimizerThresholds {
         let indexing_threshold_kb = match self.indexing_threshold {
+            // indexing is paused, keep all segments plain
+            _ if self.is_indexing_deferred() => usize::MAX,
             None => DEFAULT_INDEXING_THRESHOLD_KB, // default value
             Some(0) => usize::MAX,                 // disable vector index
             Some(custom) => custom,
@@ -117,9 +182,18 @@ impl OptimizersConfig {
             Some(custom) => custom,
         };
 
+        let vector_deduplication = match (self.deduplicate_vectors, self.deduplication_epsilon) {
+            (Some(true), Some(epsilon)) if epsilon > 0.0 => {
+                Some(VectorDeduplication::Epsilon(epsilon as f32))
+            }
+            (Some(true), _) => Some(VectorDeduplication::Exact),
+            (Some(false) | None, _) => None,
+        };
+
         OptimizerThresholds {
             memmap_threshold_kb,
             indexing_threshold_kb,
+            vector_deduplication,
             max_segment_size_kb: self.get_max_segment_size_in_kilobytes(num_indexing_threads),
         }
     }
@@ -159,7 +233,7 @@ pub fn build_optimizers(
     let temp_segments_path = shard_path.join(TEMP_SEGMENTS_PATH);
     let threshold_config = optimizers_config.optimizer_thresholds(num_indexing_threads);
 
-    Arc::new(vec![
+    let mut optimizers: Vec<Arc<Optimizer>> = vec![
         Arc::new(MergeOptimizer::new(
             optimizers_config.get_number_segments(),
             threshold_config,
@@ -180,6 +254,9 @@ pub fn build_optimizers(
         )),
         Arc::new(VacuumOptimizer::new(
             optimizers_config.deleted_threshold,
+            optimizers_config
+                .payload_index_deleted_threshold
+                .unwrap_or(optimizers_config.deleted_threshold),
             optimizers_config.vacuum_min_vector_number,
             threshold_config,
             segments_path.clone(),
@@ -189,12 +266,88 @@ pub fn build_optimizers(
             quantization_config.clone(),
         )),
         Arc::new(ConfigMismatchOptimizer::new(
+            threshold_config,
+            segments_path.clone(),
+            temp_segments_path.clone(),
+            collection_params.clone(),
+            hnsw_config.clone(),
+            quantization_config.clone(),
+        )),
+    ];
+
+    if optimizers_config.is_segment_splitting_enabled() {
+        optimizers.push(Arc::new(SplitOptimizer::new(
             threshold_config,
             segments_path,
             temp_segments_path,
             collection_params.clone(),
             hnsw_config.clone(),
             quantization_config.clone(),
-        )),
-    ])
+        )));
+    }
+
+    Arc::new(optimizers)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_defer_indexing_thresholds() {
+        let config = OptimizersConfig::fixture();
+        assert_eq!(
+            config.optimizer_thresholds(1).indexing_threshold_kb,
+            100_000
+        );
+
+        let deferred = OptimizersConfig {
+            defer_indexing: Some(true),
+            ..config.clone()
+        };
+        assert_eq!(
+            deferred.optimizer_thresholds(1).indexing_threshold_kb,
+            usize::MAX
+        );
+
+        // Other thresholds are not affected
+        assert_eq!(
+            deferred.optimizer_thresholds(1).max_segment_size_kb,
+            config.optimizer_thresholds(1).max_segment_size_kb,
+        );
+
+        let resumed = OptimizersConfig {
+            defer_indexing: Some(false),
+            ..deferred
+        };
+        assert_eq!(
+            resumed.optimizer_thresholds(1).indexing_threshold_kb,
+            100_000
+        );
+    }
+
+    #[test]
+    fn test_split_optimizer_is_opt_in() {
+        let optimizer_names = |config: &OptimizersConfig| -> Vec<String> {
+            build_optimizers(
+                Path::new("shard"),
+                &CollectionParams::empty(),
+                config,
+                &HnswConfig::default(),
+                &None,
+            )
+            .iter()
+            .map(|optimizer| optimizer.name().to_owned())
+            .collect()
+        };
+
+        let config = OptimizersConfig::fixture();
+        assert!(!optimizer_names(&config).contains(&"split".to_owned()));
+
+        let config = OptimizersConfig {
+            split_oversized_segments: Some(true),
+            ..config
+        };
+        assert!(optimizer_names(&config).contains(&"split".to_owned()));
+    }
 }
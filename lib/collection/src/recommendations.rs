@@ -3,6 +3,7 @@ use std::iter::Peekable;
 use std::time::Duration;
 
 use api::rest::RecommendStrategy;
+use cancel::CancellationToken;
 use itertools::Itertools;
 use segment::data_types::vectors::{
     DenseVector, NamedQuery, NamedVectorStruct, TypedMultiDenseVector, Vector, VectorElementType,
@@ -10,6 +11,7 @@ use segment::data_types::vectors::{
 };
 use segment::types::{
     Condition, ExtendedPointId, Filter, HasIdCondition, PointIdType, ScoredPoint,
+    WithPayloadInterface, WithVector,
 };
 use segment::vector_storage::query::RecoQuery;
 use sparse::common::sparse_vector::SparseVector;
@@ -23,6 +25,7 @@ use crate::common::fetch_vectors::{
 };
 use crate::common::retrieve_request_trait::RetrieveRequest;
 use crate::operations::consistency_params::ReadConsistency;
+use crate::operations::mmr;
 use crate::operations::query_enum::QueryEnum;
 use crate::operations::shard_selector_internal::ShardSelectorInternal;
 use crate::operations::types::{
@@ -204,11 +207,11 @@ pub fn recommend_into_core_search(
             reference_vectors_ids_to_exclude,
             all_vectors_records_map,
         ),
-        RecommendStrategy::BestScore => Ok(recommend_by_best_score(
+        RecommendStrategy::BestScore => recommend_by_best_score(
             request,
             reference_vectors_ids_to_exclude,
             all_vectors_records_map,
-        )),
+        ),
     }
 }
 
@@ -229,7 +232,7 @@ pub fn recommend_into_core_search(
 /// * `timeout` - timeout for the whole batch, in the searching stage. E.g. time in preprocessing won't be counted
 ///
 pub async fn recommend_batch_by<'a, F, Fut>(
-    request_batch: Vec<(RecommendRequestInternal, ShardSelectorInternal)>,
+    mut request_batch: Vec<(RecommendRequestInternal, ShardSelectorInternal)>,
     collection: &Collection,
     collection_by_name: F,
     read_consistency: Option<ReadConsistency>,
@@ -273,7 +276,7 @@ where
         &request_batch,
         collection,
         collection_by_name,
-        read_consistency,
+        read_consistency.clone(),
         timeout,
     )
     .await?;
@@ -281,6 +284,11 @@ where
     // update timeout
     let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
 
+    let pending_mmrs: Vec<_> = request_batch
+        .iter_mut()
+        .map(|(request, shard_selector)| take_mmr(request, shard_selector))
+        .collect();
+
     let res = batch_requests::<
         (RecommendRequestInternal, ShardSelectorInternal),
         ShardSelectorInternal,
@@ -307,9 +315,10 @@ where
 
             requests.push(collection.core_search_batch(
                 core_search_batch_request,
-                read_consistency,
+                read_consistency.clone(),
                 shard_selector,
                 timeout,
+                CancellationToken::new(),
             ));
 
             Ok(())
@@ -318,7 +327,115 @@ where
 
     let results = futures::future::try_join_all(res).await?;
     let flatten_results: Vec<Vec<_>> = results.into_iter().flatten().collect();
-    Ok(flatten_results)
+
+    if pending_mmrs.iter().all(Option::is_none) {
+        return Ok(flatten_results);
+    }
+    apply_mmrs(
+        collection,
+        flatten_results,
+        pending_mmrs,
+        read_consistency,
+        timeout,
+    )
+    .await
+}
+
+/// Maximal Marginal Relevance taken out of a recommend request, with the page and the fields to
+/// return after selecting diverse results.
+struct PendingMmr {
+    lambda: f32,
+    vector_name: String,
+    offset: usize,
+    limit: usize,
+    with_payload: Option<WithPayloadInterface>,
+    with_vector: Option<WithVector>,
+    shard_selector: ShardSelectorInternal,
+}
+
+/// Takes Maximal Marginal Relevance out of the request, so that it searches for all candidates
+/// from the first result, with only the vector used to compare them.
+///
+/// Without diversity (`lambda = 1`) the request is a regular recommendation.
+fn take_mmr(
+    request: &mut RecommendRequestInternal,
+    shard_selector: &ShardSelectorInternal,
+) -> Option<PendingMmr> {
+    let lambda = request.mmr_lambda.filter(|&lambda| lambda < 1.0)?;
+    if request.limit == 0 {
+        return None;
+    }
+
+    let vector_name = match &request.using {
+        None => DEFAULT_VECTOR_NAME.to_string(),
+        Some(UsingVector::Name(name)) => name.clone(),
+    };
+    let offset = request.offset.take().unwrap_or_default();
+
+    let pending = PendingMmr {
+        lambda,
+        vector_name: vector_name.clone(),
+        offset,
+        limit: request.limit,
+        with_payload: request
+            .with_payload
+            .replace(WithPayloadInterface::Bool(false)),
+        with_vector: request
+            .with_vector
+            .replace(WithVector::Selector(vec![vector_name])),
+        shard_selector: shard_selector.clone(),
+    };
+    request.limit = mmr::mmr_candidates(offset + request.limit);
+
+    Some(pending)
+}
+
+async fn apply_mmrs(
+    collection: &Collection,
+    results: Vec<Vec<ScoredPoint>>,
+    pending_mmrs: Vec<Option<PendingMmr>>,
+    read_consistency: Option<ReadConsistency>,
+    timeout: Option<Duration>,
+) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+    let mut diversified_results = Vec::with_capacity(results.len());
+
+    for (candidates, pending) in results.into_iter().zip(pending_mmrs) {
+        let Some(PendingMmr {
+            lambda,
+            vector_name,
+            offset,
+            limit,
+            with_payload,
+            with_vector,
+            shard_selector,
+        }) = pending
+        else {
+            diversified_results.push(candidates);
+            continue;
+        };
+
+        // Comparing all candidates may be expensive, so it doesn't block the async runtime
+        let selected = tokio::task::spawn_blocking(move || {
+            mmr::mmr(candidates, &vector_name, lambda, offset + limit)
+        })
+        .await??;
+        let page = selected.into_iter().skip(offset).collect();
+
+        // Candidates only have the vector used for diversity, return the requested fields
+        let page = collection
+            .fill_search_result_with_payload(
+                page,
+                Some(with_payload.unwrap_or(WithPayloadInterface::Bool(false))),
+                with_vector.unwrap_or_default(),
+                read_consistency.clone(),
+                &shard_selector,
+                timeout,
+            )
+            .await?;
+        diversified_results.push(page);
+    }
+
+    Ok(diversified_results)
 }
 
 fn recommend_by_avg_vector(
@@ -350,14 +467,14 @@ fn recommend_by_avg_vector(
         all_vectors_records_map,
         &lookup_vector_name,
         lookup_collection_name,
-    );
+    )?;
 
     let negative_vectors = convert_to_vectors(
         negative.iter(),
         all_vectors_records_map,
         &lookup_vector_name,
         lookup_collection_name,
-    );
+    )?;
 
     let vector_name = match using {
         None => DEFAULT_VECTOR_NAME.to_string(),
@@ -365,7 +482,7 @@ fn recommend_by_avg_vector(
     };
 
     let search_vector =
-        avg_vector_for_recommendation(positive_vectors, negative_vectors.peekable())?;
+        avg_vector_for_recommendation(positive_vectors, negative_vectors.into_iter().peekable())?;
 
     Ok(CoreSearchRequest {
         query: QueryEnum::Nearest(NamedVectorStruct::new_from_vector(
@@ -387,6 +504,10 @@ fn recommend_by_avg_vector(
         limit,
         score_threshold,
         offset: offset.unwrap_or_default(),
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: false,
     })
 }
 
@@ -394,7 +515,7 @@ fn recommend_by_best_score(
     request: RecommendRequestInternal,
     reference_vectors_ids_to_exclude: Vec<PointIdType>,
     all_vectors_records_map: &ReferencedVectors,
-) -> CoreSearchRequest {
+) -> CollectionResult<CoreSearchRequest> {
     let lookup_vector_name = request.get_lookup_vector_name();
 
     let RecommendRequestInternal {
@@ -410,6 +531,7 @@ fn recommend_by_best_score(
         score_threshold,
         using,
         lookup_from,
+        mmr_lambda: _,
     } = request;
 
     let lookup_collection_name = lookup_from.as_ref().map(|x| &x.collection);
@@ -419,14 +541,14 @@ fn recommend_by_best_score(
         all_vectors_records_map,
         &lookup_vector_name,
         lookup_collection_name,
-    );
+    )?;
 
     let negative = convert_to_vectors_owned(
         negative,
         all_vectors_records_map,
         &lookup_vector_name,
         lookup_collection_name,
-    );
+    )?;
 
     let query = QueryEnum::RecommendBestScore(NamedQuery {
         query: RecoQuery::new(positive, negative),
@@ -435,7 +557,7 @@ fn recommend_by_best_score(
         }),
     });
 
-    CoreSearchRequest {
+    Ok(CoreSearchRequest {
         query,
         filter: Some(Filter {
             should: None,
@@ -451,7 +573,11 @@ fn recommend_by_best_score(
         with_payload,
         with_vector,
         score_threshold,
-    }
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: false,
+    })
 }
 
 #[cfg(test)]
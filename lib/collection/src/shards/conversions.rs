@@ -1,23 +1,26 @@
 use api::grpc::conversions::{convert_shard_key_from_grpc_opt, payload_to_proto};
 use api::grpc::qdrant::points_selector::PointsSelectorOneOf;
 use api::grpc::qdrant::{
-    ClearPayloadPoints, ClearPayloadPointsInternal, CreateFieldIndexCollection,
-    CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
+    ClearPayloadPoints, ClearPayloadPointsInternal, CopyPayloadFieldInternal,
+    CreateFieldIndexCollection, CreateFieldIndexCollectionInternal, DeleteFieldIndexCollection,
     DeleteFieldIndexCollectionInternal, DeletePayloadPoints, DeletePayloadPointsInternal,
-    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectors,
-    PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal, SyncPoints,
-    SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
+    DeletePointVectors, DeletePoints, DeletePointsInternal, DeleteVectorsInternal, PointVectorName,
+    PointVectors, PointsIdsList, PointsSelector, SetPayloadPoints, SetPayloadPointsInternal,
+    SyncPoints, SyncPointsInternal, UpdatePointVectors, UpdateVectorsInternal, UpsertPoints,
     UpsertPointsInternal, VectorsSelector,
 };
 use segment::data_types::vectors::VectorStructInternal;
 use segment::json_path::JsonPath;
-use segment::types::{Filter, PayloadFieldSchema, PointIdType, ScoredPoint};
+use segment::types::{Filter, Payload, PayloadFieldSchema, PointIdType, ScoredPoint};
 use tonic::Status;
 
-use crate::operations::conversions::write_ordering_to_proto;
-use crate::operations::payload_ops::{DeletePayloadOp, SetPayloadOp};
+use crate::operations::conversions::{write_consistency_to_proto, write_ordering_to_proto};
+use crate::operations::payload_ops::{
+    CopyPayloadFieldOp, DeletePayloadOp, PayloadCopyMode, SetPayloadOp,
+};
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointSyncOperation, WriteOrdering,
+    PointInsertOperationsInternal, PointSyncOperation, TtlInsertOperationInternal,
+    VersionedInsertOperationInternal, WriteConsistency, WriteOrdering,
 };
 use crate::operations::types::CollectionResult;
 use crate::operations::vector_ops::UpdateVectorsOp;
@@ -46,6 +49,11 @@ pub fn internal_sync_points(
             from_id: points_sync_operation.from_id.map(|x| x.into()),
             to_id: points_sync_operation.to_id.map(|x| x.into()),
             ordering: ordering.map(write_ordering_to_proto),
+            versions: points_sync_operation
+                .versions
+                .into_iter()
+                .map(Into::into)
+                .collect(),
         }),
     })
 }
@@ -57,6 +65,7 @@ pub fn internal_upsert_points(
     point_insert_operations: PointInsertOperationsInternal,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> CollectionResult<UpsertPointsInternal> {
     Ok(UpsertPointsInternal {
         shard_id,
@@ -72,11 +81,76 @@ pub fn internal_upsert_points(
                     .collect::<Result<Vec<_>, Status>>()?,
             },
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
+            expected_versions: Vec::new(),
+            duplicate_ids: None,
+            idempotency_key: None,
+            skip_invalid_points: None,
         }),
+        ttl_stamp: None,
+        version_timestamp: None,
+        versions: Vec::new(),
     })
 }
 
+pub fn internal_upsert_points_versioned(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    versioned_insert_operation: VersionedInsertOperationInternal,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
+) -> CollectionResult<UpsertPointsInternal> {
+    let VersionedInsertOperationInternal {
+        points_op,
+        expected_versions,
+        timestamp,
+        versions,
+    } = versioned_insert_operation;
+
+    let mut request = internal_upsert_points(
+        shard_id,
+        clock_tag,
+        collection_name,
+        points_op,
+        wait,
+        ordering,
+        consistency,
+    )?;
+    if let Some(upsert_points) = &mut request.upsert_points {
+        upsert_points.expected_versions = expected_versions.into_iter().map(Into::into).collect();
+    }
+    request.version_timestamp = timestamp;
+    request.versions = versions.into_iter().map(Into::into).collect();
+    Ok(request)
+}
+
+pub fn internal_upsert_points_with_ttl(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    ttl_insert_operation: TtlInsertOperationInternal,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
+) -> CollectionResult<UpsertPointsInternal> {
+    let TtlInsertOperationInternal { upsert, stamp } = ttl_insert_operation;
+
+    let mut request = internal_upsert_points_versioned(
+        shard_id,
+        clock_tag,
+        collection_name,
+        upsert,
+        wait,
+        ordering,
+        consistency,
+    )?;
+    request.ttl_stamp = Some(stamp.into());
+    Ok(request)
+}
+
 pub fn internal_delete_points(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
@@ -84,6 +158,7 @@ pub fn internal_delete_points(
     ids: Vec<PointIdType>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> DeletePointsInternal {
     DeletePointsInternal {
         shard_id,
@@ -97,7 +172,9 @@ pub fn internal_delete_points(
                 })),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
+            idempotency_key: None,
         }),
     }
 }
@@ -109,6 +186,7 @@ pub fn internal_delete_points_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> DeletePointsInternal {
     DeletePointsInternal {
         shard_id,
@@ -120,7 +198,9 @@ pub fn internal_delete_points_by_filter(
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
+            idempotency_key: None,
         }),
     }
 }
@@ -132,15 +212,20 @@ pub fn internal_update_vectors(
     update_vectors: UpdateVectorsOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> UpdateVectorsInternal {
+    let UpdateVectorsOp {
+        points,
+        delete_vectors,
+    } = update_vectors;
+
     UpdateVectorsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
         update_vectors: Some(UpdatePointVectors {
             collection_name,
             wait: Some(wait),
-            points: update_vectors
-                .points
+            points: points
                 .into_iter()
                 .map(|point| PointVectors {
                     id: Some(point.id.into()),
@@ -148,8 +233,17 @@ pub fn internal_update_vectors(
                 })
                 .collect(),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
+            skip_invalid_points: None,
         }),
+        delete_vectors: delete_vectors
+            .into_iter()
+            .map(|(id, vector_name)| PointVectorName {
+                id: Some(id.into()),
+                vector_name,
+            })
+            .collect(),
     }
 }
 
@@ -161,6 +255,7 @@ pub fn internal_delete_vectors(
     vector_names: Vec<String>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
         shard_id,
@@ -177,6 +272,7 @@ pub fn internal_delete_vectors(
                 names: vector_names,
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -190,6 +286,7 @@ pub fn internal_delete_vectors_by_filter(
     vector_names: Vec<String>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> DeleteVectorsInternal {
     DeleteVectorsInternal {
         shard_id,
@@ -204,6 +301,7 @@ pub fn internal_delete_vectors_by_filter(
                 names: vector_names,
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -216,6 +314,7 @@ pub fn internal_set_payload(
     set_payload: SetPayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> SetPayloadPointsInternal {
     let points_selector = if let Some(points) = set_payload.points {
         Some(PointsSelector {
@@ -232,18 +331,85 @@ pub fn internal_set_payload(
     SetPayloadPointsInternal {
         shard_id,
         clock_tag: clock_tag.map(Into::into),
+        merge_patch: None,
+        copy_field: None,
         set_payload_points: Some(SetPayloadPoints {
             collection_name,
             wait: Some(wait),
             payload: payload_to_proto(set_payload.payload),
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
             key: set_payload.key.map(|key| key.to_string()),
+            merge_patch: None,
         }),
     }
 }
 
+pub fn internal_merge_patch_payload(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    set_payload: SetPayloadOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
+) -> SetPayloadPointsInternal {
+    SetPayloadPointsInternal {
+        merge_patch: Some(true),
+        ..internal_set_payload(
+            shard_id,
+            clock_tag,
+            collection_name,
+            set_payload,
+            wait,
+            ordering,
+            consistency,
+        )
+    }
+}
+
+pub fn internal_copy_payload_field(
+    shard_id: Option<ShardId>,
+    clock_tag: Option<ClockTag>,
+    collection_name: String,
+    copy_field: CopyPayloadFieldOp,
+    wait: bool,
+    ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
+) -> SetPayloadPointsInternal {
+    let CopyPayloadFieldOp {
+        from,
+        to,
+        mode,
+        points,
+        filter,
+    } = copy_field;
+
+    SetPayloadPointsInternal {
+        copy_field: Some(CopyPayloadFieldInternal {
+            from: from.to_string(),
+            to: to.to_string(),
+            move_field: mode == PayloadCopyMode::Move,
+        }),
+        ..internal_set_payload(
+            shard_id,
+            clock_tag,
+            collection_name,
+            SetPayloadOp {
+                payload: Payload::default(),
+                points,
+                filter,
+                key: None,
+            },
+            wait,
+            ordering,
+            consistency,
+        )
+    }
+}
+
 pub fn internal_delete_payload(
     shard_id: Option<ShardId>,
     clock_tag: Option<ClockTag>,
@@ -251,6 +417,7 @@ pub fn internal_delete_payload(
     delete_payload: DeletePayloadOp,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> DeletePayloadPointsInternal {
     let points_selector = if let Some(points) = delete_payload.points {
         Some(PointsSelector {
@@ -277,6 +444,7 @@ pub fn internal_delete_payload(
                 .collect(),
             points_selector,
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -289,6 +457,7 @@ pub fn internal_clear_payload(
     points: Vec<PointIdType>,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
         shard_id,
@@ -302,6 +471,7 @@ pub fn internal_clear_payload(
                 })),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -314,6 +484,7 @@ pub fn internal_clear_payload_by_filter(
     filter: Filter,
     wait: bool,
     ordering: Option<WriteOrdering>,
+    consistency: Option<WriteConsistency>,
 ) -> ClearPayloadPointsInternal {
     ClearPayloadPointsInternal {
         shard_id,
@@ -325,6 +496,7 @@ pub fn internal_clear_payload_by_filter(
                 points_selector_one_of: Some(PointsSelectorOneOf::Filter(filter.into())),
             }),
             ordering: ordering.map(write_ordering_to_proto),
+            write_consistency: consistency.map(write_consistency_to_proto),
             shard_key_selector: None,
         }),
     }
@@ -415,5 +587,7 @@ pub fn try_scored_point_from_grpc(
         vector,
         shard_key: convert_shard_key_from_grpc_opt(point.shard_key),
         order_value: point.order_value.map(TryFrom::try_from).transpose()?,
+        distance: point.distance,
+        provenance: point.provenance.map(From::from),
     })
 }
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use common::tar_ext;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
@@ -13,7 +14,8 @@ use tokio::runtime::Handle;
 
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, ShardStatus, UpdateResult,
+    CountRequestInternal, CountResult, LocalShardHealth, PointRequestInternal, Record,
+    SegmentDescription, ShardStatus, UpdateResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -45,11 +47,24 @@ impl DummyShard {
         self.dummy()
     }
 
+    pub fn health(&self) -> LocalShardHealth {
+        LocalShardHealth {
+            load_error: Some(self.message.clone()),
+            ..Default::default()
+        }
+    }
+
+    /// Dummy shard has no segments
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        Vec::new()
+    }
+
     pub fn get_telemetry_data(&self) -> LocalShardTelemetry {
         LocalShardTelemetry {
             variant_name: Some("dummy shard".into()),
             status: Some(ShardStatus::Green),
             total_optimized_points: 0,
+            wal_lag: 0,
             segments: vec![],
             optimizations: Default::default(),
         }
@@ -90,6 +105,7 @@ impl ShardOperation for DummyShard {
         _: Arc<CoreSearchRequestBatch>,
         _: &Handle,
         _: Option<Duration>,
+        _: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         self.dummy()
     }
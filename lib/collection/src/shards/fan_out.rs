@@ -0,0 +1,78 @@
+//! Fan-out of read requests to the shards of a collection, with bounded concurrency.
+//!
+//! Without a limit, a request to a collection with many shards sends a request to every shard at
+//! once, which may exhaust resources of the peer coordinating the request.
+
+use std::future::Future;
+use std::num::NonZeroUsize;
+
+use futures::{future, stream, StreamExt as _, TryStreamExt as _};
+use segment::types::ShardKey;
+
+use super::replica_set::{ReplicaState, ShardReplicaSet};
+use crate::operations::types::CollectionResult;
+
+/// Await requests to shards, with at most `concurrency` of them running at the same time.
+///
+/// Requests are started in the given order, and results are returned in the same order.
+/// Fails on the first failed request, like [`future::try_join_all`].
+pub async fn try_join_shards<T, F>(
+    requests: impl IntoIterator<Item = F>,
+    concurrency: Option<NonZeroUsize>,
+) -> CollectionResult<Vec<T>>
+where
+    F: Future<Output = CollectionResult<T>>,
+{
+    match concurrency {
+        Some(concurrency) => {
+            stream::iter(requests)
+                .buffered(concurrency.get())
+                .try_collect()
+                .await
+        }
+        None => future::try_join_all(requests).await,
+    }
+}
+
+/// Sort shards in the order in which they should be requested when concurrency is limited.
+///
+/// Shards with an active local replica come first, as they are answered without going through
+/// the network, and are less likely to hold back requests queued after them. Otherwise the order
+/// of selection is kept, so that shards of shard keys listed first in a request are requested
+/// first.
+pub fn prioritize_shards(shards: &mut [(&ShardReplicaSet, Option<&ShardKey>)]) {
+    shards.sort_by_key(|(replica_set, _)| {
+        replica_set.peer_state(&replica_set.this_peer_id()) != Some(ReplicaState::Active)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_try_join_shards() {
+        let running = AtomicUsize::new(0);
+        let max_running = AtomicUsize::new(0);
+
+        let requests = (0..10).map(|i| {
+            let (running, max_running) = (&running, &max_running);
+            async move {
+                let now_running = running.fetch_add(1, Ordering::SeqCst) + 1;
+                max_running.fetch_max(now_running, Ordering::SeqCst);
+                tokio::time::sleep(Duration::from_millis(10 - i)).await;
+                running.fetch_sub(1, Ordering::SeqCst);
+                CollectionResult::Ok(i)
+            }
+        });
+
+        let results = try_join_shards(requests, NonZeroUsize::new(3))
+            .await
+            .unwrap();
+        assert_eq!(results, (0..10).collect::<Vec<_>>());
+        assert_eq!(max_running.load(Ordering::SeqCst), 3);
+    }
+}
@@ -3,6 +3,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use common::tar_ext;
 use common::types::TelemetryDetail;
 use segment::data_types::facets::{FacetParams, FacetResponse};
@@ -17,12 +18,14 @@ use tokio::sync::Mutex;
 use super::shard::ShardId;
 use super::update_tracker::UpdateTracker;
 use crate::hash_ring::HashRingRouter;
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::point_ops::{
-    PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation,
+    PointInsertOperationsInternal, PointOperations, PointStruct, PointSyncOperation, PointVersion,
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult, UpdateStatus,
+    CountRequestInternal, CountResult, LocalShardHealth, PointRequestInternal, Record,
+    SegmentDescription, UpdateResult, UpdateStatus, WarmupResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::{
@@ -158,6 +161,32 @@ impl ForwardProxyShard {
 
         let points = points?;
 
+        // Transfer point versions along with the points, so that they stay the same on all replicas
+        let versions_request = Arc::new(PointRequestInternal {
+            ids: points.iter().map(|point| point.id).collect(),
+            with_payload: None,
+            with_vector: false.into(),
+            with_version: true,
+        });
+        let versions = self
+            .wrapped_shard
+            .retrieve(
+                versions_request,
+                &false.into(),
+                &false.into(),
+                runtime_handle,
+                None,
+            )
+            .await?
+            .into_iter()
+            .filter_map(|record| {
+                Some(PointVersion {
+                    id: record.id,
+                    version: record.version?,
+                })
+            })
+            .collect();
+
         // Use sync API to leverage potentially existing points
         // Normally use SyncPoints, to completely replace everything in the target shard
         // For resharding we need to merge points from multiple transfers, requiring a different operation
@@ -166,9 +195,11 @@ impl ForwardProxyShard {
                 from_id: offset,
                 to_id: next_page_offset,
                 points,
+                versions,
             })
         } else {
             PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsList(points))
+                .with_versions(versions)
         };
         let insert_points_operation = CollectionUpdateOperations::PointOperation(point_operation);
 
@@ -203,6 +234,40 @@ impl ForwardProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn flush(&self) -> CollectionResult<()> {
+        self.wrapped_shard.flush().await
+    }
+
+    pub async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        self.wrapped_shard
+            .warmup(with_quantization, max_bytes)
+            .await
+    }
+
+    pub async fn has_applied_clocks(&self, clocks: &[SessionClock]) -> CollectionResult<bool> {
+        self.wrapped_shard.has_applied_clocks(clocks).await
+    }
+
+    pub async fn plunge(&self) -> CollectionResult<()> {
+        self.wrapped_shard.plunge().await
+    }
+
+    pub async fn health(&self) -> LocalShardHealth {
+        self.wrapped_shard.health().await
+    }
+
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        self.wrapped_shard.list_segments()
+    }
+
+    pub fn check_pending_updates(&self) -> CollectionResult<()> {
+        self.wrapped_shard.check_pending_updates()
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         self.wrapped_shard.get_telemetry_data(detail)
     }
@@ -348,10 +413,11 @@ impl ShardOperation for ForwardProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, cancel)
             .await
     }
 
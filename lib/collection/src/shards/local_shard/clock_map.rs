@@ -7,6 +7,7 @@ use io::file_operations;
 use serde::{Deserialize, Serialize};
 use tonic::Status;
 
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::types::CollectionError;
 use crate::operations::{ClockTag, ClockToken};
 use crate::shards::shard::PeerId;
@@ -141,7 +142,6 @@ impl ClockMap {
         }
     }
 
-    #[cfg(test)]
     pub fn current_tick(&self, peer_id: PeerId, clock_id: u32) -> Option<u64> {
         self.clocks
             .get(&Key::new(peer_id, clock_id))
@@ -198,7 +198,6 @@ impl Clock {
         (operation_accepted, self.current_tick)
     }
 
-    #[cfg(test)]
     fn current_tick(&self) -> u64 {
         self.current_tick
     }
@@ -266,6 +265,26 @@ impl RecoveryPoint {
         })
     }
 
+    /// Check if this recovery point has all given clocks with the same or a newer tick.
+    pub fn has_seen_clocks(&self, clocks: &[SessionClock]) -> bool {
+        clocks.iter().all(|clock| {
+            self.clocks
+                .get(&Key::new(clock.peer_id, clock.clock_id))
+                .map_or(false, |&(tick, _token)| tick >= clock.clock_tick)
+        })
+    }
+
+    /// Check if this recovery point has all clocks of the `other` with the same or a newer tick.
+    ///
+    /// In other words, check that everything the `other` has seen was seen here as well.
+    pub fn has_seen_recovery_point(&self, other: &Self) -> bool {
+        other.clocks.iter().all(|(key, &(other_tick, _token))| {
+            self.clocks
+                .get(key)
+                .map_or(false, |&(tick, _token)| tick >= other_tick)
+        })
+    }
+
     /// Extend this recovery point with clocks that are only present in the `other`.
     ///
     /// Clocks that are not present in this recovery point are initialized to the tick 1,
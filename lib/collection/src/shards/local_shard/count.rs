@@ -0,0 +1,118 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use segment::types::SeqNumberType;
+use tokio::runtime::Handle;
+use tokio::time::error::Elapsed;
+use tokio::time::Instant;
+
+use super::LocalShard;
+use crate::operations::types::{
+    CollectionError, CollectionResult, CountRequestInternal, CountResult,
+};
+
+/// Delay before counting again, if an update is being applied to the shard
+const CONSISTENT_COUNT_RETRY_DELAY: Duration = Duration::from_millis(10);
+
+impl LocalShard {
+    /// Count points of this shard.
+    ///
+    /// A `consistent` count is repeated until the shard is not updated while counting, so that it
+    /// matches a single version of the shard. Fails with a timeout if the shard is updated too
+    /// frequently to get such a count.
+    pub async fn do_count(
+        &self,
+        request: Arc<CountRequestInternal>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+    ) -> CollectionResult<CountResult> {
+        let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
+
+        if !request.consistent {
+            return self
+                .count_once(&request, search_runtime_handle, timeout)
+                .await;
+        }
+
+        let start = Instant::now();
+        loop {
+            // Read the version *before* checking for updates in progress: an update that starts
+            // later and changes points counted below also changes the version
+            let version = self.segments_version();
+
+            if !self.update_tracker.is_update_in_progress() {
+                let remaining = timeout.saturating_sub(start.elapsed());
+                let result = self
+                    .count_once(&request, search_runtime_handle, remaining)
+                    .await?;
+
+                if self.segments_version() == version {
+                    return Ok(result);
+                }
+            } else {
+                tokio::time::sleep(CONSISTENT_COUNT_RETRY_DELAY).await;
+            }
+
+            if start.elapsed() >= timeout {
+                return Err(CollectionError::timeout(
+                    timeout.as_secs() as usize,
+                    "consistent count",
+                ));
+            }
+        }
+    }
+
+    async fn count_once(
+        &self,
+        request: &CountRequestInternal,
+        search_runtime_handle: &Handle,
+        timeout: Duration,
+    ) -> CollectionResult<CountResult> {
+        // Without a filter, the precise count is known without reading any points
+        if request.filter.is_none() {
+            if let Some(count) = self.available_point_count() {
+                return Ok(CountResult {
+                    count,
+                    exact: true,
+                    partial: false,
+                });
+            }
+        }
+
+        if request.exact {
+            let all_points = tokio::time::timeout(
+                timeout,
+                self.read_filtered(request.filter.as_ref(), search_runtime_handle),
+            )
+            .await
+            .map_err(|_: Elapsed| {
+                CollectionError::timeout(timeout.as_secs() as usize, "count")
+            })??;
+            Ok(CountResult {
+                count: all_points.len(),
+                exact: true,
+                partial: false,
+            })
+        } else {
+            let estimation = self.estimate_cardinality(request.filter.as_ref())?;
+            Ok(CountResult {
+                count: estimation.exp,
+                exact: false,
+                partial: false,
+            })
+        }
+    }
+
+    /// Highest version of the segments of this shard.
+    ///
+    /// Every update that changes points sets the version of the changed segments to its
+    /// operation number, which is higher than all versions before.
+    fn segments_version(&self) -> SeqNumberType {
+        self.segments()
+            .read()
+            .iter()
+            .map(|(_, segment)| segment.get().read().version())
+            .max()
+            .unwrap_or_default()
+    }
+}
@@ -97,6 +97,11 @@ impl DiskUsageWatcher {
         }
     }
 
+    /// Minimal free disk space, below which the disk is considered full
+    pub fn min_free_disk_size_bytes(&self) -> u64 {
+        self.min_free_disk_size_mb as u64 * 1024 * 1024
+    }
+
     /// Return current disk usage in bytes, if available
     pub async fn get_free_space_bytes(&self) -> CollectionResult<Option<u64>> {
         if self.disabled {
@@ -1,16 +1,22 @@
 pub mod clock_map;
+pub(super) mod count;
 pub mod disk_usage_watcher;
 pub(super) mod facet;
 pub(super) mod query;
 pub(super) mod scroll;
 pub(super) mod search;
+pub mod segment_load;
 pub(super) mod shard_ops;
+pub(super) mod warmup;
+
+#[cfg(test)]
+mod tests;
 
 use std::collections::{BTreeSet, HashMap};
 use std::mem::size_of;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::thread;
 use std::time::{Duration, Instant};
@@ -20,16 +26,17 @@ use common::cpu::CpuBudget;
 use common::types::TelemetryDetail;
 use common::{panic, tar_ext};
 use indicatif::{ProgressBar, ProgressStyle};
+use io::storage_version::StorageVersion as _;
 use itertools::Itertools;
 use parking_lot::{Mutex as ParkingMutex, RwLock};
 use segment::data_types::vectors::VectorElementType;
 use segment::entry::entry_point::SegmentEntry as _;
 use segment::index::field_index::CardinalityEstimation;
-use segment::segment::Segment;
-use segment::segment_constructor::{build_segment, load_segment};
+use segment::segment::{Segment, SegmentVersion};
+use segment::segment_constructor::build_segment;
 use segment::types::{
     CompressionRatio, Filter, PayloadIndexInfo, PayloadKeyType, PayloadStorageType, PointIdType,
-    QuantizationConfig, SegmentConfig, SegmentType,
+    QuantizationConfig, SegmentConfig, SegmentType, VectorDataConfig,
 };
 use segment::utils::mem::Mem;
 use tokio::fs::{create_dir_all, remove_dir_all, remove_file};
@@ -40,20 +47,22 @@ use wal::{Wal, WalOptions};
 
 use self::clock_map::{ClockMap, RecoveryPoint};
 use self::disk_usage_watcher::DiskUsageWatcher;
+use self::segment_load::{load_shard_segment, QuarantinedSegment};
 use super::update_tracker::UpdateTracker;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
 use crate::collection_manager::holders::segment_holder::{
     LockedSegment, LockedSegmentHolder, SegmentHolder,
 };
-use crate::collection_manager::optimizers::TrackerLog;
+use crate::collection_manager::optimizers::{TrackerLog, TrackerStatus};
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::file_utils::{move_dir, move_file};
-use crate::config::CollectionConfig;
+use crate::config::{CollectionConfig, CollectionParams};
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{
     check_sparse_compatible_with_segment_config, CollectionError, CollectionResult,
-    OptimizersStatus, ShardInfoInternal, ShardStatus,
+    LocalShardHealth, OptimizersStatus, SegmentDescription, ShardInfoInternal, ShardStatus,
 };
 use crate::operations::OperationWithClockTag;
 use crate::optimizers_builder::{build_optimizers, clear_temp_segments, OptimizersConfig};
@@ -62,7 +71,7 @@ use crate::shards::shard::ShardId;
 use crate::shards::shard_config::ShardConfig;
 use crate::shards::telemetry::{LocalShardTelemetry, OptimizerTelemetry};
 use crate::shards::CollectionId;
-use crate::update_handler::{Optimizer, UpdateHandler, UpdateSignal};
+use crate::update_handler::{FlushBuffer, Optimizer, UpdateHandler, UpdateSignal};
 use crate::wal::SerdeWal;
 use crate::wal_delta::{LockedWal, RecoverableWal};
 
@@ -79,6 +88,7 @@ const SEGMENTS_PATH: &str = "segments";
 ///
 /// Holds all object, required for collection functioning
 pub struct LocalShard {
+    pub(super) shard_id: ShardId,
     pub(super) segments: LockedSegmentHolder,
     pub(super) collection_config: Arc<TokioRwLock<CollectionConfig>>,
     pub(super) shared_storage_config: Arc<SharedStorageConfig>,
@@ -86,14 +96,20 @@ pub struct LocalShard {
     pub(super) wal: RecoverableWal,
     pub(super) update_handler: Arc<Mutex<UpdateHandler>>,
     pub(super) update_sender: ArcSwap<Sender<UpdateSignal>>,
+    /// Number of updates waiting for space in the update queue
+    pub(super) waiting_updates: AtomicUsize,
     pub(super) update_tracker: UpdateTracker,
     pub(super) path: PathBuf,
     pub(super) optimizers: Arc<Vec<Arc<Optimizer>>>,
     pub(super) optimizers_log: Arc<ParkingMutex<TrackerLog>>,
     pub(super) total_optimized_points: Arc<AtomicUsize>,
+    /// Version of the last WAL operation applied to the segments, updated by the update handler
+    pub(super) applied_wal_version: Arc<AtomicU64>,
     update_runtime: Handle,
     pub(super) search_runtime: Handle,
     disk_usage_watcher: DiskUsageWatcher,
+    /// Segments which failed to load, the shard rejects updates if there are any
+    quarantined_segments: Vec<QuarantinedSegment>,
 }
 
 /// Shard holds information about segments and WAL.
@@ -112,6 +128,26 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Moves the parts of local shard data that are present at `from`
+    ///
+    /// Unlike [`Self::move_data`], doesn't fail if WAL or segments are missing, e.g. because
+    /// a previous move was interrupted.
+    pub async fn move_present_data(from: &Path, to: &Path) -> CollectionResult<()> {
+        let wal_from = Self::wal_path(from);
+        if wal_from.exists() {
+            move_dir(wal_from, Self::wal_path(to)).await?;
+        }
+
+        let segments_from = Self::segments_path(from);
+        if segments_from.exists() {
+            move_dir(segments_from, Self::segments_path(to)).await?;
+        }
+
+        LocalShardClocks::move_data(from, to).await?;
+
+        Ok(())
+    }
+
     /// Checks if path have local shard data present
     pub fn check_data(shard_path: &Path) -> bool {
         let wal_path = Self::wal_path(shard_path);
@@ -142,11 +178,13 @@ impl LocalShard {
 
     #[allow(clippy::too_many_arguments)]
     pub async fn new(
+        shard_id: ShardId,
         segment_holder: SegmentHolder,
         collection_config: Arc<TokioRwLock<CollectionConfig>>,
         shared_storage_config: Arc<SharedStorageConfig>,
         payload_index_schema: Arc<SaveOnDisk<PayloadIndexSchema>>,
         wal: SerdeWal<OperationWithClockTag>,
+        optimizers_config: &OptimizersConfig,
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizer_cpu_budget: CpuBudget,
         shard_path: &Path,
@@ -155,7 +193,8 @@ impl LocalShard {
         search_runtime: Handle,
     ) -> Self {
         let segment_holder = Arc::new(RwLock::new(segment_holder));
-        let config = collection_config.read().await;
+        // All operations already in the WAL are applied on load, see `load_from_wal`
+        let applied_wal_version = Arc::new(AtomicU64::new(wal.last_index()));
         let locked_wal = Arc::new(ParkingMutex::new(wal));
         let optimizers_log = Arc::new(ParkingMutex::new(Default::default()));
         let total_optimized_points = Arc::new(AtomicUsize::new(0));
@@ -176,12 +215,14 @@ impl LocalShard {
             optimizers.clone(),
             optimizers_log.clone(),
             total_optimized_points.clone(),
+            applied_wal_version.clone(),
             optimizer_cpu_budget.clone(),
             update_runtime.clone(),
             segment_holder.clone(),
             locked_wal.clone(),
-            config.optimizer_config.flush_interval_sec,
-            config.optimizer_config.max_optimization_threads,
+            optimizers_config.flush_interval_sec,
+            FlushBuffer::from_config(optimizers_config),
+            optimizers_config.max_optimization_threads,
             clocks.clone(),
             shard_path.into(),
         );
@@ -192,9 +233,8 @@ impl LocalShard {
 
         let update_tracker = segment_holder.read().update_tracker();
 
-        drop(config); // release `shared_config` from borrow checker
-
         Self {
+            shard_id,
             segments: segment_holder,
             collection_config,
             shared_storage_config,
@@ -202,6 +242,7 @@ impl LocalShard {
             wal: RecoverableWal::new(locked_wal, clocks.newest_clocks, clocks.oldest_clocks),
             update_handler: Arc::new(Mutex::new(update_handler)),
             update_sender: ArcSwap::from_pointee(update_sender),
+            waiting_updates: AtomicUsize::new(0),
             update_tracker,
             path: shard_path.to_owned(),
             update_runtime,
@@ -209,7 +250,9 @@ impl LocalShard {
             optimizers,
             optimizers_log,
             total_optimized_points,
+            applied_wal_version,
             disk_usage_watcher,
+            quarantined_segments: Vec::new(),
         }
     }
 
@@ -251,6 +294,7 @@ impl LocalShard {
         })?;
 
         let mut load_handlers = vec![];
+        let segment_load_config = shared_storage_config.segment_load;
 
         // This semaphore is used to limit the number of threads that load segments concurrently.
         // Uncomment it if you need to debug segment loading.
@@ -260,44 +304,52 @@ impl LocalShard {
             let segments_path = entry.unwrap().path();
             let payload_index_schema = payload_index_schema.clone();
             // let semaphore_clone = semaphore.clone();
-            load_handlers.push(
+            load_handlers.push((
+                segments_path.clone(),
                 thread::Builder::new()
                     .name(format!("shard-load-{collection_id}-{id}"))
                     .spawn(move || {
                         // let _guard = semaphore_clone.lock();
-                        let mut res = load_segment(&segments_path, &AtomicBool::new(false))?;
-                        if let Some(segment) = &mut res {
-                            segment.check_consistency_and_repair()?;
-                            segment.update_all_field_indices(
-                                &payload_index_schema.read().schema.clone(),
-                            )?;
-                        } else {
-                            std::fs::remove_dir_all(&segments_path).map_err(|err| {
-                                CollectionError::service_error(format!(
-                                    "Can't remove leftover segment {}, due to {err}",
-                                    segments_path.to_str().unwrap(),
-                                ))
-                            })?;
-                        }
-                        Ok::<_, CollectionError>(res)
+                        load_shard_segment(
+                            &segments_path,
+                            &payload_index_schema,
+                            segment_load_config.as_ref(),
+                        )
                     })?,
-            );
+            ));
         }
 
         let mut segment_holder = SegmentHolder::default();
+        let mut quarantined_segments = Vec::new();
+        let vector_data = collection_config_read.params.to_base_vector_data()?;
 
-        for handler in load_handlers {
-            let segment = handler.join().map_err(|err| {
+        for (segment_path, handler) in load_handlers {
+            let res = handler.join().map_err(|err| {
                 CollectionError::service_error(format!(
                     "Can't join segment load thread: {:?}",
                     err.type_id()
                 ))
-            })??;
+            })?;
 
-            let Some(segment) = segment else {
+            let segment = match res {
+                Ok(segment) => segment,
+                Err(err) if segment_load_config.is_some_and(|config| config.quarantine) => {
+                    quarantined_segments.push(QuarantinedSegment::quarantine(
+                        shard_path,
+                        &segment_path,
+                        &err,
+                    )?);
+                    continue;
+                }
+                Err(err) => return Err(err),
+            };
+
+            let Some(mut segment) = segment else {
                 continue;
             };
 
+            create_missing_segment_vectors(&mut segment, &vector_data)?;
+
             collection_config_read
                 .params
                 .vectors
@@ -324,10 +376,12 @@ impl LocalShard {
         }
 
         clear_temp_segments(shard_path);
+        let optimizers_config =
+            collection_config_read.optimizer_config_for_shard(id, &effective_optimizers_config)?;
         let optimizers = build_optimizers(
             shard_path,
             &collection_config_read.params,
-            &effective_optimizers_config,
+            &optimizers_config,
             &collection_config_read.hnsw_config,
             &collection_config_read.quantization_config,
         );
@@ -353,12 +407,14 @@ impl LocalShard {
             )?;
         }
 
-        let local_shard = LocalShard::new(
+        let mut local_shard = LocalShard::new(
+            id,
             segment_holder,
             collection_config,
             shared_storage_config,
             payload_index_schema,
             wal,
+            &optimizers_config,
             optimizers,
             optimizer_cpu_budget,
             shard_path,
@@ -368,6 +424,8 @@ impl LocalShard {
         )
         .await;
 
+        local_shard.quarantined_segments = quarantined_segments;
+
         // Apply outstanding operations from WAL
         local_shard.load_from_wal(collection_id).await?;
 
@@ -488,6 +546,7 @@ impl LocalShard {
                 } else {
                     PayloadStorageType::InMemory
                 },
+                payload_compression: config.params.payload_compression,
             };
             let segment = thread::Builder::new()
                 .name(format!("shard-build-{collection_id}-{id}"))
@@ -517,10 +576,12 @@ impl LocalShard {
         let wal: SerdeWal<OperationWithClockTag> =
             SerdeWal::new(wal_path.to_str().unwrap(), (&config.wal_config).into())?;
 
+        let optimizers_config =
+            config.optimizer_config_for_shard(id, &effective_optimizers_config)?;
         let optimizers = build_optimizers(
             shard_path,
             &config.params,
-            &effective_optimizers_config,
+            &optimizers_config,
             &config.hnsw_config,
             &config.quantization_config,
         );
@@ -528,11 +589,13 @@ impl LocalShard {
         drop(config); // release `shared_config` from borrow checker
 
         let collection = LocalShard::new(
+            id,
             segment_holder,
             collection_config,
             shared_storage_config,
             payload_index_schema,
             wal,
+            &optimizers_config,
             optimizers,
             optimizer_cpu_budget,
             shard_path,
@@ -706,6 +769,8 @@ impl LocalShard {
 
     pub async fn on_optimizer_config_update(&self) -> CollectionResult<()> {
         let config = self.collection_config.read().await;
+        let optimizers_config =
+            config.optimizer_config_for_shard(self.shard_id, &config.optimizer_config)?;
         let mut update_handler = self.update_handler.lock().await;
 
         let (update_sender, update_receiver) =
@@ -716,19 +781,42 @@ impl LocalShard {
         update_handler.stop_flush_worker();
 
         update_handler.wait_workers_stops().await?;
+
+        // Optimizations are stopped, so no segment is wrapped into a proxy
+        let create_vectors_result = self.create_missing_vectors(&config.params);
+
         let new_optimizers = build_optimizers(
             &self.path,
             &config.params,
-            &config.optimizer_config,
+            &optimizers_config,
             &config.hnsw_config,
             &config.quantization_config,
         );
         update_handler.optimizers = new_optimizers;
-        update_handler.flush_interval_sec = config.optimizer_config.flush_interval_sec;
-        update_handler.max_optimization_threads = config.optimizer_config.max_optimization_threads;
+        update_handler.flush_interval_sec = optimizers_config.flush_interval_sec;
+        update_handler.flush_buffer = FlushBuffer::from_config(&optimizers_config);
+        update_handler.max_optimization_threads = optimizers_config.max_optimization_threads;
         update_handler.run_workers(update_receiver);
         self.update_sender.load().send(UpdateSignal::Nop).await?;
 
+        create_vectors_result
+    }
+
+    /// Adds vectors, which were added to the collection, to all segments
+    ///
+    /// Must only be called while optimizations are stopped.
+    fn create_missing_vectors(&self, collection_params: &CollectionParams) -> CollectionResult<()> {
+        let vector_data = collection_params.to_base_vector_data()?;
+        for (_, segment) in self.segments.read().iter() {
+            match segment {
+                LockedSegment::Original(segment) => {
+                    create_missing_segment_vectors(&mut segment.write(), &vector_data)?;
+                }
+                LockedSegment::Proxy(_) => {
+                    debug_assert!(false, "Segments must not be optimized while adding vectors");
+                }
+            }
+        }
         Ok(())
     }
 
@@ -768,6 +856,79 @@ impl LocalShard {
         Ok(())
     }
 
+    /// Check that local shard data at `shard_path` can be loaded into a collection with the given
+    /// config, without loading the shard.
+    ///
+    /// Verifies that WAL and segments are present, that no segment was created by a newer version
+    /// and that all segments are compatible with the vectors config of the collection.
+    ///
+    /// This method performs blocking IO.
+    pub fn check_data_compatibility(
+        shard_path: &Path,
+        collection_config: &CollectionConfig,
+    ) -> CollectionResult<()> {
+        if !Self::check_data(shard_path) {
+            return Err(CollectionError::bad_input(format!(
+                "Shard data is missing at {}",
+                shard_path.display(),
+            )));
+        }
+
+        let app_version = SegmentVersion::current();
+
+        for entry in std::fs::read_dir(Self::segments_path(shard_path))? {
+            let segment_path = entry?.path();
+
+            let is_deleted = segment_path
+                .extension()
+                .map_or(false, |ext| ext == "deleted");
+            if !segment_path.is_dir() || is_deleted {
+                continue;
+            }
+
+            // Segments without version file are incomplete, they are skipped on load
+            let Some(stored_version) = SegmentVersion::load(&segment_path)? else {
+                continue;
+            };
+
+            if stored_version > app_version {
+                return Err(CollectionError::bad_input(format!(
+                    "Segment {} has version {stored_version}, which is newer than application \
+                     version {app_version}",
+                    segment_path.display(),
+                )));
+            }
+
+            // Legacy segment state format, it is migrated and checked on load
+            if stored_version.major == 0 && stored_version.minor <= 5 {
+                continue;
+            }
+
+            let segment_config = Segment::load_state(&segment_path)
+                .map_err(|err| {
+                    CollectionError::bad_input(format!(
+                        "Can't read segment {}: {err}",
+                        segment_path.display(),
+                    ))
+                })?
+                .config;
+
+            collection_config
+                .params
+                .vectors
+                .check_compatible_with_segment_config(&segment_config.vector_data, true)?;
+            if let Some(sparse_vectors) = &collection_config.params.sparse_vectors {
+                check_sparse_compatible_with_segment_config(
+                    sparse_vectors,
+                    &segment_config.sparse_vector_data,
+                    true,
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Create snapshot for local shard into `target_path`
     pub async fn create_snapshot(
         &self,
@@ -782,10 +943,7 @@ impl LocalShard {
             // If we are not saving WAL, we still need to make sure that all submitted by this point
             // updates have made it to the segments. So we use the Plunger to achieve that.
             // It will notify us when all submitted updates so far have been processed.
-            let (tx, rx) = oneshot::channel();
-            let plunger = UpdateSignal::Plunger(tx);
-            self.update_sender.load().send(plunger).await?;
-            rx.await?;
+            self.plunge().await?;
         }
 
         let segments_path = Self::segments_path(&self.path);
@@ -905,6 +1063,22 @@ impl LocalShard {
         Ok(cardinality)
     }
 
+    /// Number of points in the shard, taken from segment metadata.
+    ///
+    /// Returns `None` while segments are being optimized, as a point might then be present in
+    /// both the optimized segment and the temporary segment for new writes.
+    pub fn available_point_count(&self) -> Option<usize> {
+        let segments = self.segments().read();
+        let mut count = 0;
+        for (_id, segment) in segments.iter() {
+            match segment {
+                LockedSegment::Original(segment) => count += segment.read().available_point_count(),
+                LockedSegment::Proxy(_) => return None,
+            }
+        }
+        Some(count)
+    }
+
     pub async fn read_filtered<'a>(
         &'a self,
         filter: Option<&'a Filter>,
@@ -943,6 +1117,7 @@ impl LocalShard {
             variant_name: None,
             status: None,
             total_optimized_points,
+            wal_lag: self.wal_lag(),
             segments,
             optimizations: OptimizerTelemetry {
                 status: optimizer_status,
@@ -952,6 +1127,160 @@ impl LocalShard {
         }
     }
 
+    /// Number of operations in the WAL, which are not yet applied to the segments
+    ///
+    /// Constantly growing lag indicates that the update handler is stuck.
+    pub fn wal_lag(&self) -> u64 {
+        let wal = self.wal.wal.lock();
+        if wal.is_empty() {
+            return 0;
+        }
+        wal.last_index()
+            .saturating_sub(self.applied_wal_version.load(Ordering::Relaxed))
+    }
+
+    /// Persist the WAL, segments and clock maps of this shard to disk.
+    ///
+    /// Returns once all data is synced to disk, so that the shard directory can be copied
+    /// consistently, i.e. by a filesystem-level snapshot.
+    ///
+    /// Segments which are being built by an optimization are not flushed. Until an optimization
+    /// finishes, all of its data is still in the flushed original segments. An optimized
+    /// segment is only loaded once its version file is saved, so a partially written one is
+    /// ignored on load.
+    pub async fn flush(&self) -> CollectionResult<()> {
+        let wal = self.wal.wal.clone();
+        let segments = self.segments.clone();
+        self.update_runtime
+            .spawn_blocking(move || -> CollectionResult<()> {
+                wal.lock().flush()?;
+                segments.read().flush_all(true, true)?;
+                Ok(())
+            })
+            .await??;
+
+        let clocks = LocalShardClocks {
+            newest_clocks: self.wal.newest_clocks.clone(),
+            oldest_clocks: self.wal.oldest_clocks.clone(),
+        };
+        clocks.store_if_changed(&self.path).await
+    }
+
+    /// Number of updates in the update queue, or waiting for space in it
+    pub fn pending_updates(&self) -> usize {
+        let update_sender = self.update_sender.load();
+        let queued = update_sender.max_capacity() - update_sender.capacity();
+        queued + self.waiting_updates.load(Ordering::Relaxed)
+    }
+
+    /// Reject new updates if there are too many pending updates already, see
+    /// [`SharedStorageConfig::max_pending_updates`]
+    ///
+    /// Only meant to be checked for new updates, before they are sent to any replica. Updates
+    /// from other peers and internal signals of the update handler are never rejected, so
+    /// replicas are not marked dead and optimizations can't be blocked by it.
+    pub fn check_pending_updates(&self) -> CollectionResult<()> {
+        let Some(max_pending_updates) = self.shared_storage_config.max_pending_updates else {
+            return Ok(());
+        };
+
+        let pending_updates = self.pending_updates();
+        if pending_updates >= max_pending_updates {
+            return Err(CollectionError::resource_exhausted(format!(
+                "Too many pending updates ({pending_updates}), \
+                 limit is {max_pending_updates}, retry later",
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Describe the segments which failed to load, if any.
+    ///
+    /// Such a shard rejects updates, and its replica is disabled for reads, the points of the
+    /// quarantined segments are missing.
+    pub fn quarantine_error(&self) -> Option<String> {
+        if self.quarantined_segments.is_empty() {
+            return None;
+        }
+        let segments = self
+            .quarantined_segments
+            .iter()
+            .map(|segment| format!("{}: {}", segment.path.display(), segment.error))
+            .collect::<Vec<_>>()
+            .join(", ");
+        Some(format!(
+            "Shard is disabled, segments failed to load and were quarantined: {segments}"
+        ))
+    }
+
+    /// Collect the health of this shard, see [`crate::collection::Collection::health`]
+    pub async fn health(&self) -> LocalShardHealth {
+        let (disk_usage_bytes, optimizer_error) = {
+            let segments = self.segments.read();
+            let disk_usage_bytes = segments
+                .iter()
+                .map(|(_id, segment)| segment.get().read().info().disk_usage_bytes)
+                .sum();
+            let optimizer_error = segments
+                .optimizer_errors
+                .as_ref()
+                .map(|error| error.to_string());
+            (disk_usage_bytes, optimizer_error)
+        };
+
+        let running_optimizations = self
+            .optimizers_log
+            .lock()
+            .to_telemetry()
+            .into_iter()
+            .filter(|tracker| tracker.status == TrackerStatus::Optimizing)
+            .collect();
+
+        let free_disk_space_bytes = self
+            .disk_usage_watcher
+            .get_free_space_bytes()
+            .await
+            .unwrap_or_else(|err| {
+                log::warn!(
+                    "Failed to get free disk space of shard {}: {err}",
+                    self.path.display()
+                );
+                None
+            });
+        let disk_full = free_disk_space_bytes.is_some_and(|free_space| {
+            free_space < self.disk_usage_watcher.min_free_disk_size_bytes()
+        });
+
+        LocalShardHealth {
+            wal_lag: self.wal_lag(),
+            disk_usage_bytes,
+            free_disk_space_bytes,
+            disk_full,
+            running_optimizations,
+            optimizer_error,
+            load_error: self.quarantine_error(),
+        }
+    }
+
+    /// Describe all segments of this shard, see [`crate::collection::Collection::list_segments`]
+    ///
+    /// The segment holder is locked for the whole call, so that optimizations can't swap segments
+    /// in the meantime. Every segment is listed exactly once, either as the segment being
+    /// optimized or as the optimized one.
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        let segments = self.segments.read();
+        segments
+            .iter()
+            .map(|(&segment_id, segment)| {
+                let optimizing = matches!(segment, LockedSegment::Proxy(_));
+                let segment = segment.get();
+                let segment = segment.read();
+                SegmentDescription::new(self.shard_id, segment_id, optimizing, &*segment)
+            })
+            .collect()
+    }
+
     /// Returns estimated size of vector data in bytes
     async fn estimate_vector_data_size(&self) -> usize {
         let info = self.local_shard_info().await;
@@ -990,6 +1319,11 @@ impl LocalShard {
     }
 
     pub async fn local_shard_status(&self) -> (ShardStatus, OptimizersStatus) {
+        // Red status if some segments failed to load
+        if !self.quarantined_segments.is_empty() {
+            return (ShardStatus::Red, OptimizersStatus::Ok);
+        }
+
         {
             let segments = self.segments().read();
 
@@ -1088,6 +1422,31 @@ impl LocalShard {
         self.wal.recovery_point().await
     }
 
+    /// Check whether this shard has applied operations with all given clocks
+    ///
+    /// Operations which are in the WAL, but not yet processed by the update handler, are waited
+    /// for.
+    pub async fn has_applied_clocks(&self, clocks: &[SessionClock]) -> CollectionResult<bool> {
+        if !self.wal.has_seen_clocks(clocks).await {
+            return Ok(false);
+        }
+
+        self.plunge().await?;
+
+        Ok(true)
+    }
+
+    /// Wait until all operations sent to the update handler so far are applied to the segments
+    pub async fn plunge(&self) -> CollectionResult<()> {
+        // All operations sent to the update handler so far are applied once the plunger is
+        // processed
+        let (tx, rx) = oneshot::channel();
+        let plunger = UpdateSignal::Plunger(tx);
+        self.update_sender.load().send(plunger).await?;
+        rx.await?;
+        Ok(())
+    }
+
     /// Update the cutoff point on the current shard
     ///
     /// This also updates the highest seen clocks.
@@ -1096,6 +1455,25 @@ impl LocalShard {
     }
 }
 
+/// Adds dense vectors of the collection, which are missing in the segment
+///
+/// Vectors are missing if they were added to the collection after the segment was created.
+fn create_missing_segment_vectors(
+    segment: &mut Segment,
+    vector_data: &HashMap<String, VectorDataConfig>,
+) -> CollectionResult<()> {
+    for (vector_name, vector_config) in vector_data {
+        if !segment.config().vector_data.contains_key(vector_name) {
+            log::debug!(
+                "Adding vector {vector_name} to segment {}",
+                segment.current_path.display(),
+            );
+            segment.create_vector_name(vector_name, vector_config)?;
+        }
+    }
+    Ok(())
+}
+
 impl Drop for LocalShard {
     fn drop(&mut self) {
         thread::scope(|s| {
@@ -3,10 +3,11 @@ use std::mem;
 use std::sync::Arc;
 use std::time::Duration;
 
+use cancel::CancellationToken;
 use futures::future::BoxFuture;
 use futures::FutureExt;
 use parking_lot::Mutex;
-use segment::common::reciprocal_rank_fusion::rrf_scoring;
+use segment::common::reciprocal_rank_fusion::weighted_rrf_scoring;
 use segment::common::score_fusion::{score_fusion, ScoreFusion};
 use segment::types::{Filter, HasIdCondition, ScoredPoint, WithPayloadInterface, WithVector};
 use tokio::runtime::Handle;
@@ -62,12 +63,14 @@ impl LocalShard {
         let start_time = std::time::Instant::now();
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
+        // Queries are not cancelled by a token, their searches stop once the query is dropped
         let searches_f = self.do_search(
             Arc::new(CoreSearchRequestBatch {
                 searches: request.searches,
             }),
             search_runtime_handle,
             Some(timeout),
+            CancellationToken::new(),
         );
 
         let scrolls_f =
@@ -121,6 +124,7 @@ impl LocalShard {
                 &point_ids,
                 &(&with_payload).into(),
                 &with_vector,
+                false,
                 &self.search_runtime,
             ),
         )
@@ -218,6 +222,8 @@ impl LocalShard {
             limit,
             with_vector,
             with_payload,
+            seed,
+            weights,
         } = rescore_params;
 
         match rescore {
@@ -225,6 +231,7 @@ impl LocalShard {
                 self.fusion_rescore(
                     sources.into_iter(),
                     fusion,
+                    weights,
                     score_threshold,
                     limit,
                     with_payload,
@@ -273,6 +280,10 @@ impl LocalShard {
                     with_payload: Some(with_payload),
                     with_vector: Some(with_vector),
                     score_threshold,
+                    score_modifier: None,
+                    rerank: None,
+                    max_score_threshold: None,
+                    with_distance: false,
                 };
 
                 let rescoring_core_search_request = CoreSearchRequestBatch {
@@ -283,6 +294,7 @@ impl LocalShard {
                     Arc::new(rescoring_core_search_request),
                     search_runtime_handle,
                     Some(timeout),
+                    CancellationToken::new(),
                 )
                 .await?
                 // One search request is sent. We expect only one result
@@ -304,7 +316,7 @@ impl LocalShard {
                         filter: Some(filter),
                         with_payload,
                         with_vector,
-                        scroll_order: ScrollOrder::Random,
+                        scroll_order: ScrollOrder::Random { seed },
                     };
 
                     self.query_scroll_batch(
@@ -329,6 +341,7 @@ impl LocalShard {
         &self,
         sources: impl Iterator<Item = Vec<ScoredPoint>>,
         fusion: Fusion,
+        weights: Vec<f32>,
         score_threshold: Option<f32>,
         limit: usize,
         with_payload: WithPayloadInterface,
@@ -336,8 +349,14 @@ impl LocalShard {
         timeout: Duration,
     ) -> Result<Vec<ScoredPoint>, CollectionError> {
         let fused = match fusion {
-            Fusion::Rrf => rrf_scoring(sources),
-            Fusion::Dbsf => score_fusion(sources, ScoreFusion::dbsf()),
+            Fusion::Rrf => weighted_rrf_scoring(sources, &weights),
+            Fusion::Dbsf => score_fusion(
+                sources,
+                ScoreFusion {
+                    weights,
+                    ..ScoreFusion::dbsf()
+                },
+            ),
         };
 
         let top_fused: Vec<_> = if let Some(score_threshold) = score_threshold {
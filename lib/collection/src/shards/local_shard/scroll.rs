@@ -2,11 +2,11 @@ use std::collections::HashSet;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use common::random::seeded_rng;
 use futures::future::try_join_all;
 use itertools::Itertools as _;
 use rand::distributions::WeightedIndex;
-use rand::rngs::StdRng;
-use rand::{Rng, SeedableRng};
+use rand::Rng;
 use segment::data_types::order_by::{Direction, OrderBy, OrderValue};
 use segment::types::{
     ExtendedPointId, Filter, ScoredPoint, WithPayload, WithPayloadInterface, WithVector,
@@ -87,6 +87,8 @@ impl LocalShard {
                     vector: record.vector,
                     shard_key: record.shard_key,
                     order_value: None,
+                    distance: None,
+                    provenance: None,
                 })
                 .collect(),
             ScrollOrder::ByField(order_by) => {
@@ -113,16 +115,19 @@ impl LocalShard {
                         vector: record.vector,
                         shard_key: record.shard_key,
                         order_value: Some(value),
+                        distance: None,
+                        provenance: None,
                     })
                     .collect()
             }
-            ScrollOrder::Random => {
+            ScrollOrder::Random { seed } => {
                 let records = self
                     .scroll_randomly(
                         limit,
                         with_payload,
                         with_vector,
                         filter.as_ref(),
+                        *seed,
                         search_runtime_handle,
                         timeout,
                     )
@@ -138,6 +143,8 @@ impl LocalShard {
                         vector: record.vector,
                         shard_key: record.shard_key,
                         order_value: None,
+                        distance: None,
+                        provenance: None,
                     })
                     .collect()
             }
@@ -209,6 +216,7 @@ impl LocalShard {
                 &point_ids,
                 &with_payload,
                 with_vector,
+                false,
                 search_runtime_handle,
             ),
         )
@@ -295,6 +303,7 @@ impl LocalShard {
                 &point_ids,
                 &with_payload,
                 with_vector,
+                false,
                 search_runtime_handle,
             ),
         )
@@ -309,12 +318,18 @@ impl LocalShard {
         Ok((ordered_records, values))
     }
 
+    /// Sample points randomly.
+    ///
+    /// With a `seed`, the same points are sampled in the same order, as long as the shard is not
+    /// changed. Segments are read in parallel, each one with its own seed derived from `seed`.
+    #[allow(clippy::too_many_arguments)]
     async fn scroll_randomly(
         &self,
         limit: usize,
         with_payload_interface: &WithPayloadInterface,
         with_vector: &WithVector,
         filter: Option<&Filter>,
+        seed: Option<u64>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<Vec<Record>> {
@@ -324,8 +339,15 @@ impl LocalShard {
         let segments = self.segments.clone();
 
         let (non_appendable, appendable) = segments.read().split_segments();
+        let all_segments: Vec<_> = non_appendable.into_iter().chain(appendable).collect();
 
-        let read_filtered = |segment: LockedSegment| {
+        let mut rng = seeded_rng(seed);
+        let segment_seeds: Vec<_> = all_segments
+            .iter()
+            .map(|_| seed.map(|_| rng.gen::<u64>()))
+            .collect();
+
+        let read_filtered = |(segment, segment_seed): (LockedSegment, Option<u64>)| {
             let is_stopped = stopping_guard.get_is_stopped();
             let filter = filter.cloned();
 
@@ -335,7 +357,12 @@ impl LocalShard {
 
                 (
                     read_segment.available_point_count(),
-                    read_segment.read_random_filtered(limit, filter.as_ref(), &is_stopped),
+                    read_segment.read_random_filtered(
+                        limit,
+                        filter.as_ref(),
+                        segment_seed,
+                        &is_stopped,
+                    ),
                 )
             })
         };
@@ -343,9 +370,9 @@ impl LocalShard {
         let all_reads = tokio::time::timeout(
             timeout,
             try_join_all(
-                non_appendable
+                all_segments
                     .into_iter()
-                    .chain(appendable)
+                    .zip(segment_seeds)
                     .map(read_filtered),
             ),
         )
@@ -367,8 +394,9 @@ impl LocalShard {
             ))
         })?;
 
-        let mut rng = StdRng::from_entropy();
-        let mut random_points = HashSet::with_capacity(limit);
+        // Points in the order they are sampled, so that a seeded sample is returned in a stable order
+        let mut random_points = Vec::with_capacity(limit);
+        let mut sampled = HashSet::with_capacity(limit);
 
         // Randomly sample points in two stages
         //
@@ -382,7 +410,9 @@ impl LocalShard {
             let segment_offset = rng.sample(&distribution);
             let points = segments_reads.get_mut(segment_offset).unwrap();
             if let Some(point) = points.pop() {
-                random_points.insert(point);
+                if sampled.insert(point) {
+                    random_points.push(point);
+                }
             } else {
                 // It seems that some segments are empty early,
                 // so distribution does not make sense anymore.
@@ -397,31 +427,35 @@ impl LocalShard {
         if random_points.len() < limit {
             let rest_points = segments_reads.into_iter().flatten();
             for point in rest_points {
-                random_points.insert(point);
+                if sampled.insert(point) {
+                    random_points.push(point);
+                }
                 if random_points.len() >= limit {
                     break;
                 }
             }
         }
 
-        let selected_points: Vec<_> = random_points.into_iter().collect();
-
         let with_payload = WithPayload::from(with_payload_interface);
         // update timeout
         let timeout = timeout.saturating_sub(start.elapsed());
-        let records_map = tokio::time::timeout(
+        let mut records_map = tokio::time::timeout(
             timeout,
             SegmentsSearcher::retrieve(
                 segments,
-                &selected_points,
+                &random_points,
                 &with_payload,
                 with_vector,
+                false,
                 search_runtime_handle,
             ),
         )
         .await
         .map_err(|_: Elapsed| CollectionError::timeout(timeout.as_secs() as usize, "retrieve"))??;
 
-        Ok(records_map.into_values().collect())
+        Ok(random_points
+            .iter()
+            .filter_map(|point_id| records_map.remove(point_id))
+            .collect())
     }
 }
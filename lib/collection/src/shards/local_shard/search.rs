@@ -1,14 +1,23 @@
 use std::sync::Arc;
 use std::time::Duration;
 
-use segment::types::ScoredPoint;
+use cancel::CancellationToken;
+use segment::types::{Distance, ScoreType, ScoredPoint};
 use tokio::runtime::Handle;
+use tokio::time::Instant;
 
 use super::LocalShard;
 use crate::collection_manager::segments_searcher::SegmentsSearcher;
 use crate::common::stopping_guard::StoppingGuard;
+use crate::config::CollectionParams;
 use crate::operations::query_enum::QueryEnum;
-use crate::operations::types::{CollectionError, CollectionResult, CoreSearchRequestBatch};
+use crate::operations::types::{
+    CollectionError, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
+};
+
+/// How many times a search is repeated with a larger limit, to replace points excluded by the
+/// maximum score threshold
+const MAX_SCORE_THRESHOLD_RETRIES: usize = 3;
 
 impl LocalShard {
     pub async fn do_search(
@@ -16,6 +25,100 @@ impl LocalShard {
         core_request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
+    ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
+        let start = Instant::now();
+        let collection_params = self.collection_config.read().await.params.clone();
+
+        let mut results = self
+            .do_search_unfiltered(
+                Arc::clone(&core_request),
+                search_runtime_handle,
+                timeout,
+                cancel.clone(),
+            )
+            .await?;
+
+        // Points above the maximum score threshold take places of other results, so search
+        // again with a larger limit to replace them
+        let mut limits: Vec<_> = core_request.searches.iter().map(|req| req.limit).collect();
+        for _ in 0..MAX_SCORE_THRESHOLD_RETRIES {
+            let (indices, searches): (Vec<_>, Vec<_>) = core_request
+                .searches
+                .iter()
+                .zip(&results)
+                .enumerate()
+                .filter_map(|(index, (request, result))| {
+                    let page_size = request.offset + request.limit;
+                    let excluded = count_above_max_threshold(&collection_params, request, result);
+                    let missing = page_size.saturating_sub(result.len() - excluded);
+                    // There are no more points to find if the result is not full
+                    let is_full = result.len() >= request.offset + limits[index];
+                    (missing > 0 && is_full).then(|| {
+                        let mut search = request.clone();
+                        search.limit = limits[index] + missing;
+                        (index, search)
+                    })
+                })
+                .unzip();
+
+            if searches.is_empty() {
+                break;
+            }
+
+            let new_limits: Vec<_> = searches.iter().map(|search| search.limit).collect();
+            let timeout = timeout.map(|timeout| timeout.saturating_sub(start.elapsed()));
+            let retried = self
+                .do_search_unfiltered(
+                    Arc::new(CoreSearchRequestBatch { searches }),
+                    search_runtime_handle,
+                    timeout,
+                    cancel.clone(),
+                )
+                .await?;
+
+            for ((index, limit), result) in indices.into_iter().zip(new_limits).zip(retried) {
+                limits[index] = limit;
+                results[index] = result;
+            }
+        }
+
+        let top_results = results
+            .into_iter()
+            .zip(core_request.searches.iter())
+            .map(|(result, req)| {
+                let distance = get_distance(&collection_params, req);
+                let max_threshold = req.max_score_threshold;
+                let in_range = result
+                    .into_iter()
+                    .filter(|scored_point| {
+                        max_threshold.map_or(true, |threshold| {
+                            !distance.check_threshold(scored_point.score, threshold)
+                        })
+                    })
+                    .take(req.offset + req.limit);
+
+                if let Some(threshold) = req.score_threshold {
+                    in_range
+                        .take_while(|scored_point| {
+                            distance.check_threshold(scored_point.score, threshold)
+                        })
+                        .collect()
+                } else {
+                    in_range.collect()
+                }
+            })
+            .collect();
+        Ok(top_results)
+    }
+
+    /// Search without applying score thresholds, scores are post-processed
+    async fn do_search_unfiltered(
+        &self,
+        core_request: Arc<CoreSearchRequestBatch>,
+        search_runtime_handle: &Handle,
+        timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let is_stopped_guard = StoppingGuard::new();
 
@@ -44,8 +147,14 @@ impl LocalShard {
             search_runtime_handle,
             true,
             query_context,
+            self.shared_storage_config.search_segment_concurrency,
         );
 
+        // Once cancelled, the search request is dropped and segments stop searching, as
+        // StoppingGuard sets is_stopped to true when this function returns
+        let search_request =
+            async { cancel::future::cancel_on_token(cancel, search_request).await? };
+
         let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
 
         let res = tokio::time::timeout(timeout, search_request)
@@ -56,36 +165,83 @@ impl LocalShard {
                 CollectionError::timeout(timeout.as_secs() as usize, "Search")
             })??;
 
-        let top_results = res
+        let processed_results = res
             .into_iter()
             .zip(core_request.searches.iter())
             .map(|(vector_res, req)| {
-                let vector_name = req.query.get_vector_name();
-                let distance = collection_params.get_distance(vector_name).unwrap();
-                let processed_res = vector_res.into_iter().map(|mut scored_point| {
-                    match req.query {
-                        QueryEnum::Nearest(_) => {
-                            scored_point.score = distance.postprocess_score(scored_point.score);
-                        }
-                        // Don't post-process if we are dealing with custom scoring
-                        QueryEnum::RecommendBestScore(_)
-                        | QueryEnum::Discover(_)
-                        | QueryEnum::Context(_) => {}
-                    };
-                    scored_point
-                });
-
-                if let Some(threshold) = req.score_threshold {
-                    processed_res
-                        .take_while(|scored_point| {
-                            distance.check_threshold(scored_point.score, threshold)
-                        })
-                        .collect()
-                } else {
-                    processed_res.collect()
-                }
+                let distance = get_distance(&collection_params, req);
+                vector_res
+                    .into_iter()
+                    .map(|mut scored_point| {
+                        match req.query {
+                            QueryEnum::Nearest(_) => {
+                                scored_point.score = distance.postprocess_score(scored_point.score);
+                                if req.with_distance {
+                                    scored_point.distance =
+                                        raw_distance(&collection_params, req, scored_point.score);
+                                }
+                            }
+                            // Don't post-process if we are dealing with custom scoring
+                            QueryEnum::RecommendBestScore(_)
+                            | QueryEnum::Discover(_)
+                            | QueryEnum::Context(_) => {}
+                        };
+                        scored_point
+                    })
+                    .collect()
             })
             .collect();
-        Ok(top_results)
+        Ok(processed_results)
     }
 }
+
+fn get_distance(collection_params: &CollectionParams, request: &CoreSearchRequest) -> Distance {
+    collection_params
+        .get_search_distance(request.query.get_vector_name(), request.params.as_ref())
+        .unwrap()
+}
+
+/// Raw distance of a found point to the query vector, from its post-processed score.
+///
+/// The distance is as exact as the score: with quantization, it is approximate unless the
+/// search rescores with original vectors. Not defined for multivectors, which are scored by a
+/// sum of similarities.
+fn raw_distance(
+    collection_params: &CollectionParams,
+    request: &CoreSearchRequest,
+    score: ScoreType,
+) -> Option<ScoreType> {
+    let vector_name = request.query.get_vector_name();
+    if let Some(params) = collection_params.vectors.get_params(vector_name) {
+        return params
+            .multivector_config
+            .is_none()
+            .then(|| get_distance(collection_params, request).score_to_distance(score));
+    }
+    let sparse_params = collection_params
+        .sparse_vectors
+        .as_ref()?
+        .get(vector_name)?;
+    Some(
+        sparse_params
+            .distance
+            .unwrap_or_default()
+            .score_to_distance(score),
+    )
+}
+
+/// Number of found points with a better score than the maximum score threshold
+fn count_above_max_threshold(
+    collection_params: &CollectionParams,
+    request: &CoreSearchRequest,
+    result: &[ScoredPoint],
+) -> usize {
+    let Some(threshold) = request.max_score_threshold else {
+        return 0;
+    };
+    let distance = get_distance(collection_params, request);
+    result
+        .iter()
+        .filter(|scored_point| distance.check_threshold(scored_point.score, threshold))
+        .count()
+}
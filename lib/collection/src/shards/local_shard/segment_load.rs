@@ -0,0 +1,180 @@
+//! Loading segments of a local shard, with retries of failed loads.
+//!
+//! A segment may fail to load due to a transient storage issue, in which case loading it again
+//! after a delay succeeds. A segment which keeps failing is likely corrupt, it can be quarantined
+//! so that the rest of the collection still loads, while the replica of its shard is disabled and
+//! recovered from another replica.
+
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+use std::thread;
+use std::time::Duration;
+
+use segment::segment::Segment;
+use segment::segment_constructor::load_segment;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::collection::payload_index_schema::PayloadIndexSchema;
+use crate::operations::types::{CollectionError, CollectionResult};
+use crate::save_on_disk::SaveOnDisk;
+
+const QUARANTINE_PATH: &str = "quarantine";
+
+const fn default_retry_delay_ms() -> u64 {
+    1000
+}
+
+/// Configuration of loading segments of local shards.
+#[derive(Debug, Deserialize, Validate, Clone, Copy, PartialEq, Eq)]
+pub struct SegmentLoadConfig {
+    /// Number of retries of a segment which failed to load, e.g. due to an I/O error.
+    pub retries: usize,
+    /// Delay before the first retry, in milliseconds. Doubled for every next retry.
+    #[serde(default = "default_retry_delay_ms")]
+    #[validate(range(min = 1))]
+    pub retry_delay_ms: u64,
+    /// If true, a segment which still fails to load after all retries is quarantined: it is moved
+    /// out of the shard, which is loaded without it and its replica is disabled. If false, the
+    /// shard fails to load.
+    #[serde(default)]
+    pub quarantine: bool,
+}
+
+/// Segment which failed to load, and was moved out of its shard
+#[derive(Debug, Clone)]
+pub struct QuarantinedSegment {
+    /// Where the segment was moved to
+    pub path: PathBuf,
+    /// Error of the last attempt to load the segment
+    pub error: String,
+}
+
+impl QuarantinedSegment {
+    /// Directory of quarantined segments of the shard
+    pub fn quarantine_path(shard_path: &Path) -> PathBuf {
+        shard_path.join(QUARANTINE_PATH)
+    }
+
+    /// Move a segment which failed to load into the quarantine directory of the shard.
+    ///
+    /// The segment is kept, so that it can be inspected and restored manually.
+    pub fn quarantine(
+        shard_path: &Path,
+        segment_path: &Path,
+        error: &CollectionError,
+    ) -> CollectionResult<Self> {
+        let quarantine_path = Self::quarantine_path(shard_path);
+        std::fs::create_dir_all(&quarantine_path)?;
+
+        let path = quarantine_path.join(segment_path.file_name().unwrap_or_default());
+        std::fs::rename(segment_path, &path).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Can't quarantine segment {}, due to {err}",
+                segment_path.display(),
+            ))
+        })?;
+
+        log::error!(
+            "Segment {} failed to load and was quarantined at {}: {error}",
+            segment_path.display(),
+            path.display(),
+        );
+
+        Ok(Self {
+            path,
+            error: error.to_string(),
+        })
+    }
+}
+
+/// Load a segment of a shard, repair it and update its payload indexes to the schema.
+///
+/// Failed attempts are retried with exponential backoff, as configured. `None` if the directory
+/// is a leftover of a segment which was not fully created, it is removed.
+pub(super) fn load_shard_segment(
+    segment_path: &Path,
+    payload_index_schema: &SaveOnDisk<PayloadIndexSchema>,
+    config: Option<&SegmentLoadConfig>,
+) -> CollectionResult<Option<Segment>> {
+    let retries = config.map_or(0, |config| config.retries);
+    let mut delay = Duration::from_millis(config.map_or(0, |config| config.retry_delay_ms));
+
+    let mut attempt = 0;
+    let segment = loop {
+        match try_load_segment(segment_path, payload_index_schema) {
+            Ok(segment) => break segment,
+            Err(err) if attempt < retries => {
+                attempt += 1;
+                log::warn!(
+                    "Failed to load segment {}, retrying in {delay:?} ({attempt}/{retries}): {err}",
+                    segment_path.display(),
+                );
+                thread::sleep(delay);
+                delay *= 2;
+            }
+            Err(err) => return Err(err),
+        }
+    };
+
+    if segment.is_none() {
+        std::fs::remove_dir_all(segment_path).map_err(|err| {
+            CollectionError::service_error(format!(
+                "Can't remove leftover segment {}, due to {err}",
+                segment_path.display(),
+            ))
+        })?;
+    }
+
+    Ok(segment)
+}
+
+fn try_load_segment(
+    segment_path: &Path,
+    payload_index_schema: &SaveOnDisk<PayloadIndexSchema>,
+) -> CollectionResult<Option<Segment>> {
+    let mut segment = load_segment(segment_path, &AtomicBool::new(false))?;
+    if let Some(segment) = &mut segment {
+        segment.check_consistency_and_repair()?;
+        segment.update_all_field_indices(&payload_index_schema.read().schema.clone())?;
+    }
+    Ok(segment)
+}
+
+#[cfg(test)]
+mod tests {
+    use io::storage_version::VERSION_FILE;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_quarantine_segment() {
+        let shard_dir = Builder::new().prefix("shard_dir").tempdir().unwrap();
+        let payload_index_schema =
+            SaveOnDisk::load_or_init_default(shard_dir.path().join("payload_index.json")).unwrap();
+
+        let segment_path = shard_dir.path().join("segments").join("broken");
+        std::fs::create_dir_all(&segment_path).unwrap();
+        std::fs::write(segment_path.join(VERSION_FILE), "not a version").unwrap();
+
+        let config = SegmentLoadConfig {
+            retries: 2,
+            retry_delay_ms: 1,
+            quarantine: true,
+        };
+        let err = load_shard_segment(&segment_path, &payload_index_schema, Some(&config))
+            .err()
+            .unwrap();
+
+        let quarantined =
+            QuarantinedSegment::quarantine(shard_dir.path(), &segment_path, &err).unwrap();
+        assert!(!segment_path.exists());
+        assert_eq!(
+            quarantined.path,
+            QuarantinedSegment::quarantine_path(shard_dir.path()).join("broken"),
+        );
+        assert!(quarantined.path.join(VERSION_FILE).exists());
+        assert_eq!(quarantined.error, err.to_string());
+    }
+}
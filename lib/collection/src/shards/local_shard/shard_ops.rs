@@ -1,7 +1,9 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::{
@@ -47,6 +49,10 @@ impl ShardOperation for LocalShard {
             (None, None)
         };
 
+        if let Some(error) = self.quarantine_error() {
+            return Err(CollectionError::service_error(error));
+        }
+
         if self
             .disk_usage_watcher
             .is_disk_full()
@@ -60,7 +66,10 @@ impl ShardOperation for LocalShard {
 
         let operation_id = {
             let update_sender = self.update_sender.load();
-            let channel_permit = update_sender.reserve().await?;
+            let channel_permit = {
+                let _waiting = WaitingUpdateGuard::new(&self.waiting_updates);
+                update_sender.reserve().await?
+            };
 
             // It is *critical* to hold `_wal_lock` while sending operation to the update handler!
             //
@@ -74,6 +83,8 @@ impl ShardOperation for LocalShard {
                         operation_id: None,
                         status: UpdateStatus::ClockRejected,
                         clock_tag: operation.clock_tag,
+                        session_token: None,
+                        invalid_points: Vec::new(),
                     });
                 }
 
@@ -96,12 +107,16 @@ impl ShardOperation for LocalShard {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Completed,
                 clock_tag: operation.clock_tag,
+                session_token: None,
+                invalid_points: Vec::new(),
             })
         } else {
             Ok(UpdateResult {
                 operation_id: Some(operation_id),
                 status: UpdateStatus::Acknowledged,
                 clock_tag: operation.clock_tag,
+                session_token: None,
+                invalid_points: Vec::new(),
             })
         }
     }
@@ -168,8 +183,9 @@ impl ShardOperation for LocalShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
-        self.do_search(request, search_runtime_handle, timeout)
+        self.do_search(request, search_runtime_handle, timeout, cancel)
             .await
     }
 
@@ -179,21 +195,7 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<CountResult> {
-        let total_count = if request.exact {
-            let timeout = timeout.unwrap_or(self.shared_storage_config.search_timeout);
-            let all_points = tokio::time::timeout(
-                timeout,
-                self.read_filtered(request.filter.as_ref(), search_runtime_handle),
-            )
-            .await
-            .map_err(|_: Elapsed| {
-                CollectionError::timeout(timeout.as_secs() as usize, "count")
-            })??;
-            all_points.len()
-        } else {
-            self.estimate_cardinality(request.filter.as_ref())?.exp
-        };
-        Ok(CountResult { count: total_count })
+        self.do_count(request, search_runtime_handle, timeout).await
     }
 
     async fn retrieve(
@@ -212,6 +214,7 @@ impl ShardOperation for LocalShard {
                 &request.ids,
                 with_payload,
                 with_vector,
+                request.with_version,
                 search_runtime_handle,
             ),
         )
@@ -245,13 +248,36 @@ impl ShardOperation for LocalShard {
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
     ) -> CollectionResult<FacetResponse> {
-        let hits = if request.exact {
+        let exact = request.exact;
+        let hits = if exact {
             self.exact_facet(request, search_runtime_handle, timeout)
                 .await?
         } else {
             self.approx_facet(request, search_runtime_handle, timeout)
                 .await?
         };
-        Ok(FacetResponse { hits })
+        Ok(FacetResponse {
+            hits,
+            other_count: 0,
+            exact,
+        })
+    }
+}
+
+/// Counts an update as waiting for space in the update queue, until dropped
+struct WaitingUpdateGuard<'a> {
+    waiting_updates: &'a AtomicUsize,
+}
+
+impl<'a> WaitingUpdateGuard<'a> {
+    fn new(waiting_updates: &'a AtomicUsize) -> Self {
+        waiting_updates.fetch_add(1, Ordering::Relaxed);
+        Self { waiting_updates }
+    }
+}
+
+impl Drop for WaitingUpdateGuard<'_> {
+    fn drop(&mut self) {
+        self.waiting_updates.fetch_sub(1, Ordering::Relaxed);
     }
 }
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use common::cpu::CpuBudget;
+use common::types::TelemetryDetail;
+use tempfile::Builder;
+use tokio::runtime::Handle;
+use tokio::sync::RwLock;
+
+use super::LocalShard;
+use crate::operations::OperationWithClockTag;
+use crate::save_on_disk::SaveOnDisk;
+use crate::shards::shard_trait::ShardOperation;
+use crate::tests::fixtures::{create_collection_config, upsert_operation};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wal_lag_telemetry() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let config = create_collection_config();
+    let current_runtime: Handle = Handle::current();
+
+    let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
+    let payload_index_schema_file = payload_index_schema_dir.path().join("payload-schema.json");
+    let payload_index_schema =
+        Arc::new(SaveOnDisk::load_or_init_default(payload_index_schema_file).unwrap());
+
+    let shard = LocalShard::build(
+        0,
+        "test".to_string(),
+        collection_dir.path(),
+        Arc::new(RwLock::new(config.clone())),
+        Arc::new(Default::default()),
+        payload_index_schema,
+        current_runtime.clone(),
+        current_runtime,
+        CpuBudget::default(),
+        config.optimizer_config.clone(),
+    )
+    .await
+    .unwrap();
+
+    let wal_lag = |shard: &LocalShard| shard.get_telemetry_data(TelemetryDetail::default()).wal_lag;
+
+    // Empty WAL
+    assert_eq!(wal_lag(&shard), 0);
+
+    // All operations in the WAL are applied
+    shard.update(upsert_operation().into(), true).await.unwrap();
+    shard.update(upsert_operation().into(), true).await.unwrap();
+    assert_eq!(wal_lag(&shard), 0);
+
+    // Operations written to the WAL, but not yet applied by the update handler
+    for _ in 0..3 {
+        let mut operation = OperationWithClockTag::from(upsert_operation());
+        shard.wal.lock_and_write(&mut operation).await.unwrap();
+    }
+    assert_eq!(wal_lag(&shard), 3);
+}
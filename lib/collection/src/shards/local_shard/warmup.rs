@@ -0,0 +1,47 @@
+use super::LocalShard;
+use crate::collection_manager::holders::segment_holder::LockedSegment;
+use crate::operations::types::{CollectionResult, WarmupResult};
+
+impl LocalShard {
+    /// Read memory-mapped vector and index data of this shard into the page cache.
+    ///
+    /// Files are read sequentially, one at a time, so that warmup does not compete with
+    /// searches for IO. Files that don't fit into the remaining `max_bytes` budget are skipped,
+    /// to avoid evicting data which is already cached.
+    pub async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        let tasks = {
+            let segments = self.segments.read();
+            let mut tasks = Vec::new();
+            for (_, segment) in segments.iter() {
+                // Proxy segments only exist during optimization, their data is about to be replaced
+                if let LockedSegment::Original(segment) = segment {
+                    tasks.extend(segment.read().warmup_tasks(with_quantization)?);
+                }
+            }
+            tasks
+        };
+
+        let result = self
+            .search_runtime
+            .spawn_blocking(move || {
+                let mut result = WarmupResult::default();
+                for task in tasks {
+                    let size_bytes = task.size_bytes();
+                    if result.warmed_bytes + size_bytes > max_bytes {
+                        result.skipped_bytes += size_bytes;
+                        continue;
+                    }
+                    task.exec();
+                    result.warmed_bytes += size_bytes;
+                }
+                result
+            })
+            .await?;
+
+        Ok(result)
+    }
+}
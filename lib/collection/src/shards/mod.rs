@@ -2,6 +2,7 @@ pub mod channel_service;
 pub mod collection_shard_distribution;
 mod conversions;
 pub mod dummy_shard;
+pub mod fan_out;
 pub mod forward_proxy_shard;
 pub mod local_shard;
 pub mod proxy_shard;
@@ -5,6 +5,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use common::tar_ext;
 use common::types::TelemetryDetail;
 use segment::data_types::facets::{FacetParams, FacetResponse};
@@ -18,12 +19,14 @@ use tokio::sync::{oneshot, RwLock};
 use tokio::time::timeout;
 
 use super::update_tracker::UpdateTracker;
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::operation_effect::{
     EstimateOperationEffectArea, OperationEffectArea, PointsOperationEffect,
 };
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, LocalShardHealth, PointRequestInternal, Record,
+    SegmentDescription, UpdateResult, WarmupResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -83,6 +86,40 @@ impl ProxyShard {
         self.wrapped_shard.on_optimizer_config_update().await
     }
 
+    pub async fn flush(&self) -> CollectionResult<()> {
+        self.wrapped_shard.flush().await
+    }
+
+    pub async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        self.wrapped_shard
+            .warmup(with_quantization, max_bytes)
+            .await
+    }
+
+    pub async fn has_applied_clocks(&self, clocks: &[SessionClock]) -> CollectionResult<bool> {
+        self.wrapped_shard.has_applied_clocks(clocks).await
+    }
+
+    pub async fn plunge(&self) -> CollectionResult<()> {
+        self.wrapped_shard.plunge().await
+    }
+
+    pub async fn health(&self) -> LocalShardHealth {
+        self.wrapped_shard.health().await
+    }
+
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        self.wrapped_shard.list_segments()
+    }
+
+    pub fn check_pending_updates(&self) -> CollectionResult<()> {
+        self.wrapped_shard.check_pending_updates()
+    }
+
     pub async fn reinit_changelog(&self) -> CollectionResult<()> {
         // Blocks updates in the wrapped shard.
         let mut changed_points_guard = self.changed_points.write().await;
@@ -228,10 +265,11 @@ impl ShardOperation for ProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, cancel)
             .await
     }
 
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use common::tar_ext;
 use common::types::TelemetryDetail;
 use parking_lot::Mutex as ParkingMutex;
@@ -19,10 +20,12 @@ use super::remote_shard::RemoteShard;
 use super::transfer::driver::MAX_RETRY_COUNT;
 use super::transfer::transfer_tasks_pool::TransferTaskProgress;
 use super::update_tracker::UpdateTracker;
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::point_ops::WriteOrdering;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequestBatch,
-    CountRequestInternal, CountResult, PointRequestInternal, Record, UpdateResult,
+    CountRequestInternal, CountResult, LocalShardHealth, PointRequestInternal, Record,
+    SegmentDescription, UpdateResult, WarmupResult,
 };
 use crate::operations::universal_query::shard_query::{ShardQueryRequest, ShardQueryResponse};
 use crate::operations::OperationWithClockTag;
@@ -155,6 +158,44 @@ impl QueueProxyShard {
             .await
     }
 
+    pub async fn flush(&self) -> CollectionResult<()> {
+        self.inner_unchecked().wrapped_shard.flush().await
+    }
+
+    pub async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .warmup(with_quantization, max_bytes)
+            .await
+    }
+
+    pub async fn has_applied_clocks(&self, clocks: &[SessionClock]) -> CollectionResult<bool> {
+        self.inner_unchecked()
+            .wrapped_shard
+            .has_applied_clocks(clocks)
+            .await
+    }
+
+    pub async fn plunge(&self) -> CollectionResult<()> {
+        self.inner_unchecked().wrapped_shard.plunge().await
+    }
+
+    pub async fn health(&self) -> LocalShardHealth {
+        self.inner_unchecked().wrapped_shard.health().await
+    }
+
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        self.inner_unchecked().wrapped_shard.list_segments()
+    }
+
+    pub fn check_pending_updates(&self) -> CollectionResult<()> {
+        self.inner_unchecked().wrapped_shard.check_pending_updates()
+    }
+
     pub fn get_telemetry_data(&self, detail: TelemetryDetail) -> LocalShardTelemetry {
         self.inner_unchecked()
             .wrapped_shard
@@ -246,9 +287,10 @@ impl ShardOperation for QueueProxyShard {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         self.inner_unchecked()
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, cancel)
             .await
     }
 
@@ -557,10 +599,11 @@ impl ShardOperation for Inner {
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let local_shard = &self.wrapped_shard;
         local_shard
-            .core_search(request, search_runtime_handle, timeout)
+            .core_search(request, search_runtime_handle, timeout, cancel)
             .await
     }
 
@@ -646,7 +689,7 @@ async fn transfer_operations_batch(
         }
 
         remote_shard
-            .forward_update(operation, true, WriteOrdering::Weak)
+            .forward_update(operation, true, WriteOrdering::Weak, None)
             .await?;
     }
     Ok(())
@@ -11,13 +11,14 @@ use api::grpc::qdrant::shard_snapshots_client::ShardSnapshotsClient;
 use api::grpc::qdrant::{
     CollectionOperationResponse, CoreSearchBatchPointsInternal, CountPoints, CountPointsInternal,
     FacetCountsInternal, GetCollectionInfoRequest, GetCollectionInfoRequestInternal, GetPoints,
-    GetPointsInternal, GetShardRecoveryPointRequest, HealthCheckRequest,
+    GetPointsInternal, GetShardRecoveryPointRequest, HasAppliedSessionRequest, HealthCheckRequest,
     InitiateShardTransferRequest, QueryBatchPointsInternal, QueryShardPoints,
     RecoverShardSnapshotRequest, RecoverSnapshotResponse, ScrollPoints, ScrollPointsInternal,
     ShardSnapshotLocation, UpdateShardCutoffPointRequest, WaitForShardStateRequest,
 };
 use api::grpc::transport_channel_pool::{AddTimeout, MAX_GRPC_CHANNEL_TIMEOUT};
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use common::types::TelemetryDetail;
 use itertools::Itertools;
 use parking_lot::Mutex;
@@ -40,9 +41,10 @@ use super::conversions::{
 };
 use super::local_shard::clock_map::RecoveryPoint;
 use super::replica_set::ReplicaState;
+use crate::operations::consistency_params::SessionToken;
 use crate::operations::conversions::try_record_from_grpc;
 use crate::operations::payload_ops::PayloadOps;
-use crate::operations::point_ops::{PointOperations, WriteOrdering};
+use crate::operations::point_ops::{PointOperations, WriteConsistency, WriteOrdering};
 use crate::operations::snapshot_ops::SnapshotPriority;
 use crate::operations::types::{
     CollectionError, CollectionInfo, CollectionResult, CoreSearchRequest, CoreSearchRequestBatch,
@@ -53,10 +55,11 @@ use crate::operations::vector_ops::VectorOperations;
 use crate::operations::{CollectionUpdateOperations, FieldIndexOperations, OperationWithClockTag};
 use crate::shards::channel_service::ChannelService;
 use crate::shards::conversions::{
-    internal_clear_payload, internal_clear_payload_by_filter, internal_create_index,
-    internal_delete_index, internal_delete_payload, internal_delete_points,
-    internal_delete_points_by_filter, internal_set_payload, internal_sync_points,
-    internal_upsert_points, try_scored_point_from_grpc,
+    internal_clear_payload, internal_clear_payload_by_filter, internal_copy_payload_field,
+    internal_create_index, internal_delete_index, internal_delete_payload, internal_delete_points,
+    internal_delete_points_by_filter, internal_merge_patch_payload, internal_set_payload,
+    internal_sync_points, internal_upsert_points, internal_upsert_points_versioned,
+    internal_upsert_points_with_ttl, try_scored_point_from_grpc,
 };
 use crate::shards::shard::{PeerId, ShardId};
 use crate::shards::shard_trait::ShardOperation;
@@ -221,6 +224,7 @@ impl RemoteShard {
         operation: OperationWithClockTag,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
     ) -> CollectionResult<UpdateResult> {
         // `RemoteShard::execute_update_operation` is cancel safe, so this method is cancel safe.
 
@@ -230,6 +234,7 @@ impl RemoteShard {
             operation,
             wait,
             Some(ordering),
+            consistency,
         )
         .await
     }
@@ -244,6 +249,7 @@ impl RemoteShard {
         operation: OperationWithClockTag,
         wait: bool,
         ordering: Option<WriteOrdering>,
+        consistency: Option<WriteConsistency>,
     ) -> CollectionResult<UpdateResult> {
         // Cancelling remote request should always be safe on the client side and update API
         // *should be* cancel safe on the server side, so this method is cancel safe.
@@ -261,6 +267,39 @@ impl RemoteShard {
                         point_insert_operations,
                         wait,
                         ordering,
+                        consistency,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsVersioned(versioned_insert_operation) => {
+                    let request = &internal_upsert_points_versioned(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        versioned_insert_operation,
+                        wait,
+                        ordering,
+                        consistency,
+                    )?;
+                    self.with_points_client(|mut client| async move {
+                        client.upsert(tonic::Request::new(request.clone())).await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PointOperations::UpsertPointsWithTtl(ttl_insert_operation) => {
+                    let request = &internal_upsert_points_with_ttl(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        ttl_insert_operation,
+                        wait,
+                        ordering,
+                        consistency,
                     )?;
                     self.with_points_client(|mut client| async move {
                         client.upsert(tonic::Request::new(request.clone())).await
@@ -276,6 +315,7 @@ impl RemoteShard {
                         ids,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
@@ -291,6 +331,7 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client.delete(tonic::Request::new(request.clone())).await
@@ -323,6 +364,7 @@ impl RemoteShard {
                         update_operation,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -341,6 +383,7 @@ impl RemoteShard {
                         vector_names.clone(),
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -359,6 +402,7 @@ impl RemoteShard {
                         vector_names.clone(),
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -378,6 +422,7 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -395,6 +440,7 @@ impl RemoteShard {
                         delete_payload,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -412,6 +458,7 @@ impl RemoteShard {
                         points,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -429,6 +476,7 @@ impl RemoteShard {
                         filter,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -446,6 +494,7 @@ impl RemoteShard {
                         set_payload,
                         wait,
                         ordering,
+                        consistency,
                     );
                     self.with_points_client(|mut client| async move {
                         client
@@ -455,6 +504,42 @@ impl RemoteShard {
                     .await?
                     .into_inner()
                 }
+                PayloadOps::MergePatchPayload(set_payload) => {
+                    let request = &internal_merge_patch_payload(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        set_payload,
+                        wait,
+                        ordering,
+                        consistency,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .set_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
+                PayloadOps::CopyPayloadField(copy_field) => {
+                    let request = &internal_copy_payload_field(
+                        shard_id,
+                        operation.clock_tag,
+                        collection_name,
+                        copy_field,
+                        wait,
+                        ordering,
+                        consistency,
+                    );
+                    self.with_points_client(|mut client| async move {
+                        client
+                            .set_payload(tonic::Request::new(request.clone()))
+                            .await
+                    })
+                    .await?
+                    .into_inner()
+                }
             },
             CollectionUpdateOperations::FieldIndexOperation(field_index_op) => match field_index_op
             {
@@ -599,6 +684,32 @@ impl RemoteShard {
         Ok(recovery_point.try_into()?)
     }
 
+    /// Check whether the remote shard has applied all updates of the session to its segments
+    ///
+    /// Unlike the recovery point, which only tells that updates are written to the WAL, this
+    /// waits for the updates queued for the segments to be applied.
+    pub async fn has_applied_session(
+        &self,
+        collection_name: &str,
+        shard_id: ShardId,
+        session_token: &SessionToken,
+    ) -> CollectionResult<bool> {
+        let res = self
+            .with_collections_client(|mut client| async move {
+                client
+                    .has_applied_session(HasAppliedSessionRequest {
+                        collection_name: collection_name.into(),
+                        shard_id,
+                        session_token: session_token.to_string(),
+                    })
+                    .await
+            })
+            .await?
+            .into_inner();
+
+        Ok(res.applied)
+    }
+
     /// Update the shard cutoff point on the remote shard
     pub async fn update_shard_cutoff_point(
         &self,
@@ -648,8 +759,15 @@ impl ShardOperation for RemoteShard {
 
         // targets the shard explicitly
         let shard_id = Some(self.id);
-        self.execute_update_operation(shard_id, self.collection_id.clone(), operation, wait, None)
-            .await
+        self.execute_update_operation(
+            shard_id,
+            self.collection_id.clone(),
+            operation,
+            wait,
+            None,
+            None,
+        )
+        .await
     }
 
     async fn scroll_by(
@@ -726,6 +844,7 @@ impl ShardOperation for RemoteShard {
         batch_request: Arc<CoreSearchRequestBatch>,
         _search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         let mut timer = ScopeDurationMeasurer::new(&self.telemetry_search_durations);
         timer.set_success(false);
@@ -742,8 +861,10 @@ impl ShardOperation for RemoteShard {
             shard_id: Some(self.id),
             timeout: timeout.map(|t| t.as_secs()),
         };
-        let search_batch_response = self
-            .with_points_client(|mut client| async move {
+        // Dropping the request cancels it on the remote peer as well
+        let search_batch_response = cancel::future::cancel_on_token(
+            cancel,
+            self.with_points_client(|mut client| async move {
                 let mut request = tonic::Request::new(request.clone());
 
                 if let Some(timeout) = timeout {
@@ -751,9 +872,10 @@ impl ShardOperation for RemoteShard {
                 }
 
                 client.core_search_batch(request).await
-            })
-            .await?
-            .into_inner();
+            }),
+        )
+        .await??
+        .into_inner();
 
         let result: Result<Vec<Vec<ScoredPoint>>, Status> = search_batch_response
             .result
@@ -792,6 +914,8 @@ impl ShardOperation for RemoteShard {
             read_consistency: None,
             shard_key_selector: None,
             timeout: timeout.map(|t| t.as_secs()),
+            consistent: Some(request.consistent),
+            allow_partial: None,
         };
 
         let count_request = &CountPointsInternal {
@@ -814,7 +938,14 @@ impl ShardOperation for RemoteShard {
                     "Unexpected empty CountResult".to_string(),
                 ))
             },
-            |count_result| Ok(count_result.into()),
+            |count_result| {
+                // Peers of older versions don't report exactness, but follow the requested mode
+                let exact = count_result.exact.unwrap_or(request.exact);
+                Ok(CountResult {
+                    exact,
+                    ..count_result.into()
+                })
+            },
         )
     }
 
@@ -834,6 +965,7 @@ impl ShardOperation for RemoteShard {
             read_consistency: None,
             shard_key_selector: None,
             timeout: timeout.map(|t| t.as_secs()),
+            with_version: Some(request.with_version),
         };
         let get_request = &GetPointsInternal {
             get_points: Some(get_points),
@@ -967,7 +1099,11 @@ impl ShardOperation for RemoteShard {
             .map(FacetValueHit::try_from)
             .try_collect()?;
 
-        let result = FacetResponse { hits };
+        let result = FacetResponse {
+            hits,
+            other_count: response.other_count as usize,
+            exact: *exact,
+        };
 
         timer.set_success(true);
 
@@ -1,5 +1,6 @@
 use std::fmt::Write as _;
 use std::ops::Deref as _;
+use std::time::{Duration, Instant};
 
 use futures::future::{self, BoxFuture};
 use futures::stream::FuturesUnordered;
@@ -7,17 +8,22 @@ use futures::{FutureExt as _, StreamExt as _};
 use rand::seq::SliceRandom as _;
 
 use super::ShardReplicaSet;
-use crate::operations::consistency_params::{ReadConsistency, ReadConsistencyType};
+use crate::operations::consistency_params::{
+    ReadConsistency, ReadConsistencyType, SessionClock, SessionToken,
+};
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::shards::remote_shard::RemoteShard;
 use crate::shards::resolve::{Resolve, ResolveCondition};
-use crate::shards::shard::Shard;
+use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_trait::ShardOperation;
 
+/// How often replicas are checked while waiting for session updates
+const SESSION_READ_CHECK_INTERVAL: Duration = Duration::from_millis(50);
+
 impl ShardReplicaSet {
     /// Execute read op. on replica set:
     /// 1 - Prefer local replica
-    /// 2 - Otherwise uses `read_fan_out_ratio` to compute list of active remote shards.
+    /// 2 - Otherwise uses `read_fan_out_ratio` to compute list of active or read-only remote shards.
     /// 3 - Fallbacks to all remaining shards if the optimisations fails.
     /// It does not report failing peer_ids to the consensus.
     pub async fn execute_read_operation<Res, F>(
@@ -33,7 +39,7 @@ impl ShardReplicaSet {
         }
 
         let mut responses = self
-            .execute_cluster_read_operation(read_operation, 1, None)
+            .execute_cluster_read_operation(read_operation, 1, true, None)
             .await?;
 
         Ok(responses.pop().unwrap())
@@ -55,17 +61,44 @@ impl ShardReplicaSet {
 
         let read_consistency = read_consistency.unwrap_or_default();
 
-        let local_count = usize::from(self.peer_state(&self.this_peer_id()).is_some());
-        let active_local_count = usize::from(self.peer_is_active(&self.this_peer_id()));
+        if let ReadConsistency::Session(token) = &read_consistency {
+            let clocks = token.shard_clocks(self.shard_id);
+
+            // Session has no updates in this shard, any replica can serve the read
+            if !clocks.is_empty() {
+                return self
+                    .execute_session_read_operation(read_operation, &clocks)
+                    .await;
+            }
+        }
+
+        // Read-only replicas may lag behind active replicas, so they only serve reads which are
+        // answered by a single replica, and are not counted for read consistency
+        let include_read_only = match read_consistency {
+            ReadConsistency::Factor(factor) => factor <= 1,
+            ReadConsistency::Session(_) => true,
+            ReadConsistency::Type(_) => false,
+        };
+        let read_only_peers = self.replica_state.read().read_only_peers();
+
+        let this_peer_id = self.this_peer_id();
+        let local_count = usize::from(
+            self.peer_state(&this_peer_id).is_some() && !read_only_peers.contains(&this_peer_id),
+        );
+        let active_local_count =
+            usize::from(self.peer_is_readable_for(&this_peer_id, include_read_only));
 
         let remotes = self.remotes.read().await;
 
-        let remotes_count = remotes.len();
+        let remotes_count = remotes
+            .iter()
+            .filter(|remote| !read_only_peers.contains(&remote.peer_id))
+            .count();
 
         // TODO(resharding): Handle resharded shard?
         let active_remotes_count = remotes
             .iter()
-            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter(|remote| self.peer_is_readable_for(&remote.peer_id, include_read_only))
             .count();
 
         let total_count = local_count + remotes_count;
@@ -85,6 +118,8 @@ impl ShardReplicaSet {
             ReadConsistency::Factor(factor) => {
                 (factor.clamp(1, total_count), ResolveCondition::All)
             }
+
+            ReadConsistency::Session(_) => (1, ResolveCondition::All),
         };
 
         if active_count < required_successful_results {
@@ -99,6 +134,7 @@ impl ShardReplicaSet {
             .execute_cluster_read_operation(
                 read_operation,
                 required_successful_results,
+                include_read_only,
                 Some(remotes),
             )
             .await?;
@@ -110,6 +146,87 @@ impl ShardReplicaSet {
         }
     }
 
+    /// Execute read op. on a replica, which has applied all session clocks of this shard
+    ///
+    /// Local replica is preferred. Remote replicas are asked whether they have applied the
+    /// session updates to their segments, having them in the WAL is not enough. Read-only
+    /// replicas are used as well, as they are checked to have the session updates too.
+    ///
+    /// Waits up to the configured session read timeout for any replica to catch up.
+    async fn execute_session_read_operation<Res, F>(
+        &self,
+        read_operation: F,
+        clocks: &[SessionClock],
+    ) -> CollectionResult<Res>
+    where
+        F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
+    {
+        let timeout = self.shared_storage_config.session_read_timeout;
+        let deadline = Instant::now() + timeout;
+        let session_token: SessionToken = clocks.iter().copied().collect();
+
+        loop {
+            if self.peer_is_readable(&self.this_peer_id()) {
+                let local = self.local.read().await;
+
+                if let Some(local) = local.deref() {
+                    if local.has_applied_clocks(clocks).await? {
+                        return read_operation(local.get()).await;
+                    }
+                }
+            }
+
+            {
+                let remotes = self.remotes.read().await;
+
+                let active_remotes = remotes
+                    .iter()
+                    .filter(|remote| self.peer_is_readable(&remote.peer_id));
+
+                for remote in active_remotes {
+                    let has_applied = match remote
+                        .has_applied_session(&self.collection_id, self.shard_id, &session_token)
+                        .await
+                    {
+                        Ok(has_applied) => has_applied,
+                        Err(err) => {
+                            log::debug!(
+                                "Failed to check session of shard {} on peer {}: {err}",
+                                self.shard_id,
+                                remote.peer_id,
+                            );
+                            continue;
+                        }
+                    };
+
+                    if has_applied {
+                        return read_operation(remote).await;
+                    }
+                }
+            }
+
+            if Instant::now() >= deadline {
+                return Err(CollectionError::Timeout {
+                    description: format!(
+                        "No replica of shard {} applied updates of the session token within {} ms",
+                        self.shard_id,
+                        timeout.as_millis(),
+                    ),
+                });
+            }
+
+            tokio::time::sleep(SESSION_READ_CHECK_INTERVAL).await;
+        }
+    }
+
+    fn peer_is_readable_for(&self, peer_id: &PeerId, include_read_only: bool) -> bool {
+        if include_read_only {
+            self.peer_is_readable(peer_id)
+        } else {
+            self.peer_is_active(peer_id)
+        }
+    }
+
     async fn execute_local_read_operation<Res, F>(&self, read_operation: F) -> CollectionResult<Res>
     where
         F: Fn(&(dyn ShardOperation + Send + Sync)) -> BoxFuture<'_, CollectionResult<Res>>,
@@ -130,6 +247,7 @@ impl ShardReplicaSet {
         &self,
         read_operation: F,
         required_successful_results: usize,
+        include_read_only: bool,
         remotes: Option<tokio::sync::RwLockReadGuard<'_, Vec<RemoteShard>>>,
     ) -> CollectionResult<Vec<Res>>
     where
@@ -159,7 +277,7 @@ impl ShardReplicaSet {
             Err(_) => (self.local.read().right_future(), false, None),
         };
 
-        let local_is_active = self.peer_is_active(&self.this_peer_id());
+        let local_is_active = self.peer_is_readable_for(&self.this_peer_id(), include_read_only);
 
         let local_operation = if local_is_active {
             let local_operation = async {
@@ -183,7 +301,7 @@ impl ShardReplicaSet {
         // TODO(resharding): Handle resharded shard?
         let mut active_remotes: Vec<_> = remotes
             .iter()
-            .filter(|remote| self.peer_is_active(&remote.peer_id))
+            .filter(|remote| self.peer_is_readable_for(&remote.peer_id, include_read_only))
             .collect();
 
         active_remotes.shuffle(&mut rand::thread_rng());
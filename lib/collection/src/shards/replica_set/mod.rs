@@ -1,6 +1,7 @@
 pub mod clock_set;
 mod execute_read_operation;
 mod locally_disabled_peers;
+mod read_only;
 mod read_ops;
 mod shard_transfer;
 mod snapshots;
@@ -28,9 +29,14 @@ use super::CollectionId;
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::common::snapshots_manager::SnapshotStorageManager;
 use crate::config::CollectionConfig;
+use crate::operations::consistency_params::SessionClock;
+use crate::operations::idempotency::IdempotencyCache;
 use crate::operations::point_ops::{self};
 use crate::operations::shared_storage_config::SharedStorageConfig;
-use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use crate::operations::types::{
+    CollectionError, CollectionResult, LocalShardHealth, SegmentDescription, UpdateResult,
+    UpdateStatus, WarmupResult,
+};
 use crate::operations::CollectionUpdateOperations;
 use crate::optimizers_builder::OptimizersConfig;
 use crate::save_on_disk::SaveOnDisk;
@@ -108,6 +114,8 @@ pub struct ShardReplicaSet {
     write_ordering_lock: Mutex<()>,
     /// Local clock set, used to tag new operations on this shard.
     clock_set: Mutex<ClockSet>,
+    /// Recently applied idempotency keys of client updates on this shard.
+    idempotency_cache: IdempotencyCache,
 }
 
 pub type AbortShardTransfer = Arc<dyn Fn(ShardTransfer, &str) + Send + Sync>;
@@ -183,6 +191,8 @@ impl ShardReplicaSet {
         let replica_set_shard_config = ShardConfig::new_replica_set();
         replica_set_shard_config.save(&shard_path)?;
 
+        let idempotency_cache = IdempotencyCache::load_or_empty(&shard_path);
+
         Ok(Self {
             shard_id,
             local: RwLock::new(local),
@@ -203,6 +213,7 @@ impl ShardReplicaSet {
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
             clock_set: Default::default(),
+            idempotency_cache,
         })
     }
 
@@ -255,26 +266,44 @@ impl ShardReplicaSet {
         );
 
         let mut local_load_failure = false;
+        let mut local_quarantined = false;
         let local = if replica_state.read().is_local {
             let shard = if let Some(recovery_reason) = &shared_storage_config.recovery_mode {
                 Shard::Dummy(DummyShard::new(recovery_reason))
             } else {
-                let res = LocalShard::load(
-                    shard_id,
-                    collection_id.clone(),
-                    shard_path,
-                    collection_config.clone(),
-                    effective_optimizers_config.clone(),
-                    shared_storage_config.clone(),
-                    payload_index_schema.clone(),
-                    update_runtime.clone(),
-                    search_runtime.clone(),
-                    optimizer_cpu_budget.clone(),
-                )
+                let res = async {
+                    // Bring back previous data, if a snapshot restore was interrupted
+                    Self::recover_interrupted_restore(shard_path).await?;
+
+                    LocalShard::load(
+                        shard_id,
+                        collection_id.clone(),
+                        shard_path,
+                        collection_config.clone(),
+                        effective_optimizers_config.clone(),
+                        shared_storage_config.clone(),
+                        payload_index_schema.clone(),
+                        update_runtime.clone(),
+                        search_runtime.clone(),
+                        optimizer_cpu_budget.clone(),
+                    )
+                    .await
+                }
                 .await;
 
                 match res {
-                    Ok(shard) => Shard::Local(shard),
+                    Ok(shard) => {
+                        if let Some(err) = shard.quarantine_error() {
+                            local_quarantined = true;
+
+                            log::error!(
+                                "Local shard {shard_path:?} is missing quarantined segments, \
+                                 disabling its replica: {err}"
+                            );
+                        }
+
+                        Shard::Local(shard)
+                    }
                     Err(err) => {
                         if !shared_storage_config.handle_collection_load_errors {
                             panic!("Failed to load local shard {shard_path:?}: {err}")
@@ -321,9 +350,14 @@ impl ShardReplicaSet {
             optimizer_cpu_budget,
             write_ordering_lock: Mutex::new(()),
             clock_set: Default::default(),
+            idempotency_cache: IdempotencyCache::load_or_empty(shard_path),
         };
 
-        if local_load_failure && replica_set.active_remote_shards().await.is_empty() {
+        // A local shard with quarantined segments is missing points, it must not serve reads.
+        // It is reported as dead, and recovered from another replica.
+        if local_quarantined
+            || (local_load_failure && replica_set.active_remote_shards().await.is_empty())
+        {
             replica_set
                 .locally_disabled_peers
                 .write()
@@ -333,6 +367,10 @@ impl ShardReplicaSet {
         replica_set
     }
 
+    pub fn idempotency_cache(&self) -> &IdempotencyCache {
+        &self.idempotency_cache
+    }
+
     pub fn this_peer_id(&self) -> PeerId {
         self.replica_state.read().this_peer_id
     }
@@ -681,7 +719,7 @@ impl ShardReplicaSet {
                 )
                 .await?;
                 match state {
-                    ReplicaState::Active | ReplicaState::Listener => {
+                    ReplicaState::Active | ReplicaState::Listener | ReplicaState::ReadOnly => {
                         // No way we can provide up-to-date replica right away at this point,
                         // so we report a failure to consensus
                         self.set_local(local_shard, Some(state)).await?;
@@ -832,6 +870,8 @@ impl ShardReplicaSet {
                 operation_id: None,
                 status: UpdateStatus::Completed,
                 clock_tag: None,
+                session_token: None,
+                invalid_points: Vec::new(),
             });
         }
 
@@ -880,6 +920,16 @@ impl ShardReplicaSet {
         self.peer_state(peer_id) == Some(ReplicaState::Active) && !self.is_locally_disabled(peer_id)
     }
 
+    /// Check whether a peer can serve reads, which don't have to be confirmed by other replicas.
+    /// These are active replicas and read-only replicas which didn't miss any update.
+    fn peer_is_readable(&self, peer_id: &PeerId) -> bool {
+        match self.peer_state(peer_id) {
+            Some(ReplicaState::Active) => !self.is_locally_disabled(peer_id),
+            Some(ReplicaState::ReadOnly) => !self.is_lagging_read_only(peer_id),
+            _ => false,
+        }
+    }
+
     fn peer_is_active_or_resharding(&self, peer_id: &PeerId) -> bool {
         let is_active_or_resharding = matches!(
             self.peer_state(peer_id),
@@ -968,6 +1018,90 @@ impl ShardReplicaSet {
         local_shard.shard_recovery_point().await
     }
 
+    /// Check whether the local replica has applied all given session clocks to its segments
+    pub(crate) async fn local_has_applied_clocks(
+        &self,
+        clocks: &[SessionClock],
+    ) -> CollectionResult<bool> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Err(CollectionError::NotFound {
+                what: "Peer does not have local shard".into(),
+            });
+        };
+
+        local_shard.has_applied_clocks(clocks).await
+    }
+
+    /// Wait until the local replica applied all updates it received so far.
+    ///
+    /// Does nothing if this peer doesn't have a local replica.
+    pub(crate) async fn plunge_local(&self) -> CollectionResult<()> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Ok(());
+        };
+
+        local_shard.plunge().await
+    }
+
+    /// Persist all data of the local replica to disk.
+    ///
+    /// Does nothing if this peer doesn't have a local replica.
+    pub(crate) async fn flush(&self) -> CollectionResult<()> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Ok(());
+        };
+
+        local_shard.flush().await
+    }
+
+    /// Read memory-mapped data of the local replica into the page cache.
+    ///
+    /// Does nothing if this peer doesn't have a local replica.
+    pub(crate) async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        let local_shard = self.local.read().await;
+        let Some(local_shard) = local_shard.as_ref() else {
+            return Ok(WarmupResult::default());
+        };
+
+        local_shard.warmup(with_quantization, max_bytes).await
+    }
+
+    /// Health of the local replica, `None` if this peer doesn't have a local replica.
+    pub(crate) async fn local_health(&self) -> Option<LocalShardHealth> {
+        let local_shard = self.local.read().await;
+        match local_shard.as_ref() {
+            Some(local_shard) => Some(local_shard.health().await),
+            None => None,
+        }
+    }
+
+    /// Segments of the local replica, empty if this peer doesn't have a local replica.
+    pub(crate) async fn local_segments(&self) -> Vec<SegmentDescription> {
+        let local_shard = self.local.read().await;
+        local_shard
+            .as_ref()
+            .map(|local_shard| local_shard.list_segments())
+            .unwrap_or_default()
+    }
+
+    /// Number of points in the local replica, `None` if this peer doesn't have a local replica,
+    /// or if the replica is being optimized or transferred.
+    pub(crate) async fn local_available_point_count(&self) -> Option<usize> {
+        match self.local.read().await.as_ref()? {
+            Shard::Local(local_shard) => local_shard.available_point_count(),
+            Shard::Proxy(_) | Shard::ForwardProxy(_) | Shard::QueueProxy(_) | Shard::Dummy(_) => {
+                None
+            }
+        }
+    }
+
     /// Update the cutoff point for the local shard.
     pub(crate) async fn update_shard_cutoff_point(
         &self,
@@ -994,6 +1128,11 @@ pub struct ReplicaSetState {
     pub is_local: bool,
     pub this_peer_id: PeerId,
     peers: HashMap<PeerId, ReplicaState>,
+    /// Read-only replicas which missed an update sent from this peer.
+    /// They are not reported to the consensus, as it would recover them into active replicas.
+    /// Read operations are not performed on them, until their state is changed in the consensus.
+    #[serde(default, skip_serializing_if = "HashSet::is_empty")]
+    lagging_read_only_peers: HashSet<PeerId>,
 }
 
 impl ReplicaSetState {
@@ -1003,9 +1142,12 @@ impl ReplicaSetState {
 
     pub fn set_peer_state(&mut self, peer_id: PeerId, state: ReplicaState) {
         self.peers.insert(peer_id, state);
+        // State of the peer was changed in the consensus, it has to be used for reads again
+        self.lagging_read_only_peers.remove(&peer_id);
     }
 
     pub fn remove_peer_state(&mut self, peer_id: &PeerId) -> Option<ReplicaState> {
+        self.lagging_read_only_peers.remove(peer_id);
         self.peers.remove(peer_id)
     }
 
@@ -1022,6 +1164,15 @@ impl ReplicaSetState {
             .collect()
     }
 
+    pub fn read_only_peers(&self) -> HashSet<PeerId> {
+        self.peers
+            .iter()
+            .filter_map(|(peer_id, state)| {
+                matches!(state, ReplicaState::ReadOnly).then_some(*peer_id)
+            })
+            .collect()
+    }
+
     pub fn active_or_resharding_peers(&self) -> impl Iterator<Item = PeerId> + '_ {
         self.peers.iter().filter_map(|(peer_id, state)| {
             matches!(state, ReplicaState::Active | ReplicaState::Resharding).then_some(*peer_id)
@@ -1029,8 +1180,19 @@ impl ReplicaSetState {
     }
 
     pub fn set_peers(&mut self, peers: HashMap<PeerId, ReplicaState>) {
+        // Replicas which are still read-only have not been recovered, they keep lagging
+        self.lagging_read_only_peers
+            .retain(|peer_id| peers.get(peer_id) == Some(&ReplicaState::ReadOnly));
         self.peers = peers;
     }
+
+    pub fn is_lagging_read_only(&self, peer_id: &PeerId) -> bool {
+        self.lagging_read_only_peers.contains(peer_id)
+    }
+
+    pub fn add_lagging_read_only(&mut self, peer_id: PeerId) {
+        self.lagging_read_only_peers.insert(peer_id);
+    }
 }
 
 /// State of the single shard within a replica set.
@@ -1060,6 +1222,9 @@ pub enum ReplicaState {
     // Points are being migrated to this shard as part of resharding
     #[schemars(skip)]
     Resharding,
+    // A shard which receives data and is used for search, but is not counted for write consistency
+    // Can only become active by an explicit promotion, once it caught up with active replicas
+    ReadOnly,
 }
 
 impl ReplicaState {
@@ -1074,7 +1239,8 @@ impl ReplicaState {
             | ReplicaState::Partial
             | ReplicaState::PartialSnapshot
             | ReplicaState::Recovery
-            | ReplicaState::Resharding => false,
+            | ReplicaState::Resharding
+            | ReplicaState::ReadOnly => false,
         }
     }
 
@@ -1090,7 +1256,8 @@ impl ReplicaState {
             ReplicaState::Active
             | ReplicaState::Dead
             | ReplicaState::Initializing
-            | ReplicaState::Listener => false,
+            | ReplicaState::Listener
+            | ReplicaState::ReadOnly => false,
         }
     }
 }
@@ -0,0 +1,146 @@
+use std::time::{Duration, Instant};
+
+use super::{ReplicaState, ShardReplicaSet};
+use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
+use crate::shards::local_shard::clock_map::RecoveryPoint;
+use crate::shards::shard::PeerId;
+
+/// How often a read-only replica is checked while waiting for it to catch up
+const READ_ONLY_CATCH_UP_CHECK_INTERVAL: Duration = Duration::from_millis(100);
+
+impl ShardReplicaSet {
+    pub(super) fn is_lagging_read_only(&self, peer_id: &PeerId) -> bool {
+        self.replica_state.read().is_lagging_read_only(peer_id)
+    }
+
+    /// Check results of an update sent to read-only replicas
+    ///
+    /// Read-only replicas don't count towards write consistency, so their failures don't fail the
+    /// update. A replica which didn't apply the update is stale, it is no longer used for reads.
+    /// This is persisted, so that the replica is not read from after a restart either.
+    pub(super) fn handle_read_only_results(
+        &self,
+        results: Vec<Result<(PeerId, UpdateResult), (PeerId, CollectionError)>>,
+    ) {
+        for result in results {
+            let (peer_id, reason) = match result {
+                Ok((peer_id, res)) if matches!(res.status, UpdateStatus::ClockRejected) => (
+                    peer_id,
+                    "update was rejected because of an old clock".to_string(),
+                ),
+                Ok(_) => continue,
+                Err((peer_id, err)) => (peer_id, err.to_string()),
+            };
+
+            log::warn!(
+                "Failed to update read-only replica of shard {}:{} on peer {peer_id}, \
+                 it is no longer used for reads, error: {reason}",
+                self.collection_id,
+                self.shard_id,
+            );
+
+            if let Err(err) = self
+                .replica_state
+                .write(|rs| rs.add_lagging_read_only(peer_id))
+            {
+                log::error!(
+                    "Failed to persist lagging read-only replica of shard {}:{} on peer {peer_id}: \
+                     {err}",
+                    self.collection_id,
+                    self.shard_id,
+                );
+            }
+        }
+    }
+
+    /// Wait until the read-only replica on `peer_id` has seen all updates applied by an active
+    /// replica, so that it can be promoted without losing any of them.
+    ///
+    /// Fails if the replica missed an update, as it can't catch up without being recovered.
+    pub async fn wait_for_read_only_catch_up(
+        &self,
+        peer_id: PeerId,
+        timeout: Duration,
+    ) -> CollectionResult<()> {
+        if self.peer_state(&peer_id) != Some(ReplicaState::ReadOnly) {
+            return Err(CollectionError::bad_input(format!(
+                "Replica {peer_id} of shard {} is not read-only",
+                self.shard_id,
+            )));
+        }
+
+        let target = self.active_recovery_point().await?;
+        let deadline = Instant::now() + timeout;
+
+        loop {
+            if self.is_lagging_read_only(&peer_id) {
+                return Err(CollectionError::bad_input(format!(
+                    "Read-only replica {peer_id} of shard {} missed updates, \
+                     it has to be recovered before it can be promoted",
+                    self.shard_id,
+                )));
+            }
+
+            if self
+                .peer_recovery_point(peer_id)
+                .await?
+                .has_seen_recovery_point(&target)
+            {
+                return Ok(());
+            }
+
+            if Instant::now() >= deadline {
+                return Err(CollectionError::Timeout {
+                    description: format!(
+                        "Read-only replica {peer_id} of shard {} did not catch up with active \
+                         replicas within {} seconds",
+                        self.shard_id,
+                        timeout.as_secs(),
+                    ),
+                });
+            }
+
+            tokio::time::sleep(READ_ONLY_CATCH_UP_CHECK_INTERVAL).await;
+        }
+    }
+
+    /// Recovery point of an active replica, local replica is preferred
+    async fn active_recovery_point(&self) -> CollectionResult<RecoveryPoint> {
+        if self.peer_is_active(&self.this_peer_id()) {
+            return self.shard_recovery_point().await;
+        }
+
+        let remotes = self.remotes.read().await;
+        let Some(remote) = remotes
+            .iter()
+            .find(|remote| self.peer_is_active(&remote.peer_id))
+        else {
+            return Err(CollectionError::service_error(format!(
+                "The replica set for shard {} on peer {} has no active replica",
+                self.shard_id,
+                self.this_peer_id(),
+            )));
+        };
+
+        remote
+            .shard_recovery_point(&self.collection_id, self.shard_id)
+            .await
+    }
+
+    async fn peer_recovery_point(&self, peer_id: PeerId) -> CollectionResult<RecoveryPoint> {
+        if peer_id == self.this_peer_id() {
+            return self.shard_recovery_point().await;
+        }
+
+        let remotes = self.remotes.read().await;
+        let Some(remote) = remotes.iter().find(|remote| remote.peer_id == peer_id) else {
+            return Err(CollectionError::NotFound {
+                what: format!("Replica {peer_id} of shard {}", self.shard_id),
+            });
+        };
+
+        remote
+            .shard_recovery_point(&self.collection_id, self.shard_id)
+            .await
+    }
+}
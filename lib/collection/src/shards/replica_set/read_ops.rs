@@ -1,6 +1,7 @@
 use std::sync::Arc;
 use std::time::Duration;
 
+use cancel::CancellationToken;
 use futures::FutureExt as _;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
@@ -66,13 +67,20 @@ impl ShardReplicaSet {
         read_consistency: Option<ReadConsistency>,
         local_only: bool,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>> {
         self.execute_and_resolve_read_operation(
             |shard| {
                 let request = Arc::clone(&request);
                 let search_runtime = self.search_runtime.clone();
+                let cancel = cancel.clone();
 
-                async move { shard.core_search(request, &search_runtime, timeout).await }.boxed()
+                async move {
+                    shard
+                        .core_search(request, &search_runtime, timeout, cancel)
+                        .await
+                }
+                .boxed()
             },
             read_consistency,
             local_only,
@@ -11,6 +11,16 @@ use crate::shards::local_shard::LocalShard;
 use crate::shards::shard::{PeerId, Shard};
 use crate::shards::shard_config::ShardConfig;
 
+/// Directory inside of the shard directory to keep current replica data while restoring a snapshot
+const RESTORE_BACKUP_PATH: &str = "restore_backup";
+
+/// Directory to move current replica data into, renamed to [`RESTORE_BACKUP_PATH`] once complete
+const RESTORE_BACKUP_TMP_PATH: &str = "restore_backup.tmp";
+
+/// Directory to rename the backup to once the restore is done, so that it's never rolled back to
+/// after being partially removed
+const RESTORE_BACKUP_REMOVE_PATH: &str = "restore_backup.remove";
+
 impl ShardReplicaSet {
     pub async fn create_snapshot(
         &self,
@@ -71,6 +81,14 @@ impl ShardReplicaSet {
         Ok(())
     }
 
+    /// Replace local replica data with the shard data at `replica_path`
+    ///
+    /// Restore is transactional: the data is validated before the current replica is touched,
+    /// the current replica data is moved aside and only removed once the restored replica is
+    /// loaded successfully. If loading fails, the previous data is moved back and reloaded.
+    /// If the restore is interrupted by a crash, the previous data is moved back on load, see
+    /// [`Self::recover_interrupted_restore`].
+    ///
     /// # Cancel safety
     ///
     /// This method is *not* cancel safe.
@@ -85,9 +103,15 @@ impl ShardReplicaSet {
             return Ok(false);
         }
 
-        // TODO:
-        //   Check that shard snapshot is compatible with the collection
-        //   (see `VectorsConfig::check_compatible_with_segment_config`)
+        // Validate restored data before touching the current replica
+        {
+            let replica_path = replica_path.to_path_buf();
+            let collection_config = self.collection_config.read().await.clone();
+            cancel::blocking::spawn_cancel_on_token(cancel.child_token(), move |_| {
+                LocalShard::check_data_compatibility(&replica_path, &collection_config)
+            })
+            .await??;
+        }
 
         let mut local = cancel::future::cancel_on_token(cancel.clone(), self.local.write()).await?;
 
@@ -96,78 +120,170 @@ impl ShardReplicaSet {
             return Err(cancel::Error::Cancelled.into());
         }
 
-        // Drop `LocalShard` instance to free resources and clear shard data
-        let clear = local.take().is_some();
+        let backup_path = self.shard_path.join(RESTORE_BACKUP_PATH);
+        let backup_tmp_path = self.shard_path.join(RESTORE_BACKUP_TMP_PATH);
+        let backup_remove_path = self.shard_path.join(RESTORE_BACKUP_REMOVE_PATH);
+
+        // Drop `LocalShard` instance to free resources
+        let had_local = local.take().is_some();
+
+        // Bring back previous data of an interrupted restore, which wasn't recovered on load
+        Self::recover_interrupted_restore(&self.shard_path).await?;
+
+        let has_backup = had_local && LocalShard::check_data(&self.shard_path);
 
         // Try to restore local replica from specified shard snapshot directory
         let restore = async {
-            if clear {
+            // Move current shard data aside, so it can be brought back if restore fails.
+            // The backup is only considered complete once renamed, which is atomic.
+            if has_backup {
+                tokio::fs::create_dir_all(&backup_tmp_path).await?;
+                LocalShard::move_data(&self.shard_path, &backup_tmp_path).await?;
+                tokio::fs::rename(&backup_tmp_path, &backup_path).await?;
+            } else if had_local {
                 LocalShard::clear(&self.shard_path).await?;
             }
 
             LocalShard::move_data(replica_path, &self.shard_path).await?;
-
-            LocalShard::load(
-                self.shard_id,
-                self.collection_id.clone(),
-                &self.shard_path,
-                self.collection_config.clone(),
-                self.optimizers_config.clone(),
-                self.shared_storage_config.clone(),
-                self.payload_index_schema.clone(),
-                self.update_runtime.clone(),
-                self.search_runtime.clone(),
-                self.optimizer_cpu_budget.clone(),
-            )
-            .await
+            self.load_local_shard().await
         };
 
-        match restore.await {
+        let restore_err = match restore.await {
             Ok(new_local) => {
                 local.replace(Shard::Local(new_local));
-                Ok(true)
-            }
 
-            Err(restore_err) => {
-                // Initialize "dummy" replica
-                local.replace(Shard::Dummy(DummyShard::new(
-                    "Failed to restore local replica",
-                )));
+                if has_backup {
+                    let remove_backup = async {
+                        tokio::fs::rename(&backup_path, &backup_remove_path).await?;
+                        tokio::fs::remove_dir_all(&backup_remove_path).await
+                    };
 
-                // TODO: Handle single-node mode!? (How!? 😰)
+                    if let Err(err) = remove_backup.await {
+                        log::error!(
+                            "Failed to remove backup of shard {} data ({}) after restore: {err}",
+                            self.shard_id,
+                            backup_path.display(),
+                        );
+                    }
+                }
+
+                return Ok(true);
+            }
+            Err(restore_err) => restore_err,
+        };
 
-                // Mark this peer as "locally disabled"...
-                let has_other_active_peers = self.active_remote_shards().await.is_empty();
+        // Roll back to the previous replica data, if there was any
+        if has_backup {
+            log::warn!(
+                "Failed to restore shard {}, rolling back to previous data: {restore_err}",
+                self.shard_id,
+            );
 
-                // ...if this peer is *not* the last active replica
-                if has_other_active_peers {
-                    let notify = self
-                        .locally_disabled_peers
-                        .write()
-                        .disable_peer_and_notify_if_elapsed(self.this_peer_id());
+            let rollback = async {
+                Self::recover_interrupted_restore(&self.shard_path).await?;
+                self.load_local_shard().await
+            };
 
-                    if notify {
-                        self.notify_peer_failure_cb.deref()(self.this_peer_id(), self.shard_id);
-                    }
+            match rollback.await {
+                Ok(old_local) => {
+                    local.replace(Shard::Local(old_local));
+                    return Err(restore_err);
                 }
+                Err(rollback_err) => {
+                    log::error!(
+                        "Failed to roll back shard {} to previous data: {rollback_err}",
+                        self.shard_id,
+                    );
+                }
+            }
+        }
 
-                // Remove shard directory, so we don't leave empty directory/corrupted data
-                match tokio::fs::remove_dir_all(&self.shard_path).await {
-                    Ok(()) => Err(restore_err),
+        // Initialize "dummy" replica
+        local.replace(Shard::Dummy(DummyShard::new(
+            "Failed to restore local replica",
+        )));
 
-                    Err(cleanup_err) => {
-                        log::error!(
-                            "Failed to cleanup shard {} directory ({}) after restore failed: \
-                             {cleanup_err}",
-                            self.shard_id,
-                            self.shard_path.display(),
-                        );
+        // TODO: Handle single-node mode!? (How!? 😰)
 
-                        // TODO: Contextualize `restore_err` with `cleanup_err` details!?
-                        Err(restore_err)
-                    }
-                }
+        // Mark this peer as "locally disabled"...
+        let has_other_active_peers = self.active_remote_shards().await.is_empty();
+
+        // ...if this peer is *not* the last active replica
+        if has_other_active_peers {
+            let notify = self
+                .locally_disabled_peers
+                .write()
+                .disable_peer_and_notify_if_elapsed(self.this_peer_id());
+
+            if notify {
+                self.notify_peer_failure_cb.deref()(self.this_peer_id(), self.shard_id);
             }
         }
+
+        // Remove shard directory, so we don't leave empty directory/corrupted data
+        match tokio::fs::remove_dir_all(&self.shard_path).await {
+            Ok(()) => Err(restore_err),
+
+            Err(cleanup_err) => {
+                log::error!(
+                    "Failed to cleanup shard {} directory ({}) after restore failed: \
+                     {cleanup_err}",
+                    self.shard_id,
+                    self.shard_path.display(),
+                );
+
+                // TODO: Contextualize `restore_err` with `cleanup_err` details!?
+                Err(restore_err)
+            }
+        }
+    }
+
+    /// Roll back a restore of the local replica that was interrupted, e.g. by a crash
+    ///
+    /// Brings back the previous replica data from the backup, and removes the restored data.
+    /// Does nothing if there's no backup left in the shard directory.
+    pub(super) async fn recover_interrupted_restore(shard_path: &Path) -> CollectionResult<()> {
+        let backup_path = shard_path.join(RESTORE_BACKUP_PATH);
+        let backup_tmp_path = shard_path.join(RESTORE_BACKUP_TMP_PATH);
+        let backup_remove_path = shard_path.join(RESTORE_BACKUP_REMOVE_PATH);
+
+        // Restore was done, only the removal of the backup was interrupted
+        if backup_remove_path.exists() {
+            tokio::fs::remove_dir_all(&backup_remove_path).await?;
+        }
+
+        // Backup is complete, shard data might be partially replaced with restored data
+        if backup_path.exists() {
+            log::warn!(
+                "Rolling back interrupted restore of shard {}",
+                shard_path.display(),
+            );
+            LocalShard::clear(shard_path).await?;
+            tokio::fs::rename(&backup_path, &backup_tmp_path).await?;
+        }
+
+        // Shard data is partially moved into the backup, but no restored data was moved in yet
+        if backup_tmp_path.exists() {
+            LocalShard::move_present_data(&backup_tmp_path, shard_path).await?;
+            tokio::fs::remove_dir_all(&backup_tmp_path).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn load_local_shard(&self) -> CollectionResult<LocalShard> {
+        LocalShard::load(
+            self.shard_id,
+            self.collection_id.clone(),
+            &self.shard_path,
+            self.collection_config.clone(),
+            self.optimizers_config.clone(),
+            self.shared_storage_config.clone(),
+            self.payload_index_schema.clone(),
+            self.update_runtime.clone(),
+            self.search_runtime.clone(),
+            self.optimizer_cpu_budget.clone(),
+        )
+        .await
     }
 }
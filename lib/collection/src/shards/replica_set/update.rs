@@ -1,12 +1,12 @@
 use std::ops::Deref as _;
-use std::time::Duration;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
 use futures::stream::FuturesUnordered;
 use futures::{FutureExt as _, StreamExt as _};
 use itertools::Itertools as _;
 
 use super::{clock_set, ReplicaSetState, ReplicaState, ShardReplicaSet};
-use crate::operations::point_ops::WriteOrdering;
+use crate::operations::point_ops::{WriteConsistency, WriteOrdering};
 use crate::operations::types::{CollectionError, CollectionResult, UpdateResult, UpdateStatus};
 use crate::operations::{ClockTag, CollectionUpdateOperations, OperationWithClockTag};
 use crate::shards::shard::PeerId;
@@ -42,7 +42,8 @@ impl ShardReplicaSet {
                     ReplicaState::Active
                     | ReplicaState::Partial
                     | ReplicaState::Initializing
-                    | ReplicaState::Resharding,
+                    | ReplicaState::Resharding
+                    | ReplicaState::ReadOnly,
                 ) => Ok(Some(local_shard.get().update(operation, wait).await?)),
                 Some(ReplicaState::Listener) => {
                     Ok(Some(local_shard.get().update(operation, false).await?))
@@ -71,6 +72,7 @@ impl ShardReplicaSet {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
         update_only_existing: bool,
     ) -> CollectionResult<UpdateResult> {
         // `ShardReplicaSet::update` is not cancel safe, so this method is not cancel safe.
@@ -92,10 +94,11 @@ impl ShardReplicaSet {
                 WriteOrdering::Weak => None,
             };
 
-            self.update(operation, wait, update_only_existing).await
+            self.update(operation, wait, consistency, update_only_existing)
+                .await
         } else {
             // Forward the update to the designated leader
-            self.forward_update(leader_peer, operation, wait, ordering)
+            self.forward_update(leader_peer, operation, wait, ordering, consistency)
                 .await
                 .map_err(|err| {
                     if err.is_transient() {
@@ -114,7 +117,7 @@ impl ShardReplicaSet {
     }
 
     /// Designated a leader replica for the update based on the WriteOrdering
-    fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
+    pub(crate) fn leader_peer_for_update(&self, ordering: WriteOrdering) -> Option<PeerId> {
         match ordering {
             WriteOrdering::Weak => Some(self.this_peer_id()), // no requirement for consistency
             WriteOrdering::Medium => self.highest_alive_replica_peer_id(), // consistency with highest alive replica
@@ -133,8 +136,15 @@ impl ShardReplicaSet {
             .max()
     }
 
+    /// Highest replica, which may confirm updates. Read-only replicas never lead updates.
     fn highest_replica_peer_id(&self) -> Option<PeerId> {
-        self.replica_state.read().peers.keys().max().cloned()
+        self.replica_state
+            .read()
+            .peers
+            .iter()
+            .filter(|(_, state)| **state != ReplicaState::ReadOnly)
+            .map(|(peer_id, _)| *peer_id)
+            .max()
     }
 
     /// # Cancel safety
@@ -144,6 +154,7 @@ impl ShardReplicaSet {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        consistency: Option<WriteConsistency>,
         update_only_existing: bool,
     ) -> CollectionResult<UpdateResult> {
         // `ShardRepilcaSet::update_impl` is not cancel safe, so this method is not cancel safe.
@@ -160,7 +171,13 @@ impl ShardReplicaSet {
             let is_non_zero_tick = clock.current_tick().is_some();
 
             let res = self
-                .update_impl(operation.clone(), wait, &mut clock, update_only_existing)
+                .update_impl(
+                    operation.clone(),
+                    wait,
+                    consistency,
+                    &mut clock,
+                    update_only_existing,
+                )
                 .await?;
 
             if let Some(res) = res {
@@ -191,6 +208,7 @@ impl ShardReplicaSet {
         &self,
         operation: CollectionUpdateOperations,
         wait: bool,
+        consistency: Option<WriteConsistency>,
         clock: &mut clock_set::ClockGuard,
         update_only_existing: bool,
     ) -> CollectionResult<Option<UpdateResult>> {
@@ -212,13 +230,61 @@ impl ShardReplicaSet {
         // local is defined AND the peer itself can receive updates
         let local_is_updatable = local.is_some() && self.peer_is_active_or_pending(&this_peer_id);
 
-        if active_remote_shards.is_empty() && !local_is_updatable {
+        // Read-only replicas receive updates, but never count towards write consistency
+        let read_only_peers = self.replica_state.read().read_only_peers();
+
+        let writable_remotes_count = active_remote_shards
+            .iter()
+            .filter(|remote| !read_only_peers.contains(&remote.peer_id))
+            .count();
+        let local_is_writable = local_is_updatable && !read_only_peers.contains(&this_peer_id);
+
+        if writable_remotes_count == 0 && !local_is_writable {
             return Err(CollectionError::service_error(format!(
                 "The replica set for shard {} on peer {this_peer_id} has no active replica",
                 self.shard_id,
             )));
         }
 
+        // Fail fast if requested write consistency can't be satisfied by replicas available
+        // right now, instead of applying the update to some of them
+        let required_successes = match consistency {
+            Some(consistency) => {
+                let required_successes = self.write_consistency_required_successes(consistency);
+                let updatable_count = writable_remotes_count + usize::from(local_is_writable);
+
+                if updatable_count < required_successes {
+                    return Err(CollectionError::bad_request(format!(
+                        "Cannot update shard {}:{} with {consistency:?} write consistency, \
+                         {required_successes} replicas required, but only {updatable_count} available",
+                        self.collection_id, self.shard_id,
+                    )));
+                }
+
+                Some(required_successes)
+            }
+            None => None,
+        };
+
+        // With local write consistency, the local replica has to confirm the update if there is one
+        let local_success_required =
+            consistency == Some(WriteConsistency::Local) && local_is_writable;
+
+        // Apply backpressure before the update is sent to any replica, so no replica fails it
+        if let Some(local) = local.deref() {
+            if local_is_updatable {
+                local.check_pending_updates()?;
+            }
+        }
+
+        // Versions of upserted points are derived from a timestamp taken here once, so that all
+        // replicas receiving the operation assign the same ones
+        let version_timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_micros() as u64;
+        let operation = operation.with_version_timestamp(version_timestamp);
+
         let current_clock_tick = clock.tick_once();
         let clock_tag = ClockTag::new(this_peer_id, clock.id() as _, current_clock_tick);
         let operation = OperationWithClockTag::new(operation, Some(clock_tag));
@@ -227,6 +293,7 @@ impl ShardReplicaSet {
 
         if let Some(local) = local.deref() {
             if self.peer_is_active_or_pending(&this_peer_id) {
+                // Read-only replicas acknowledge updates, so that their failures are not missed
                 let local_wait = if self.peer_state(&this_peer_id) == Some(ReplicaState::Listener) {
                     false
                 } else {
@@ -250,7 +317,6 @@ impl ShardReplicaSet {
 
         for remote in active_remote_shards {
             let operation = operation.clone();
-
             let remote_update = async move {
                 remote
                     .update(operation, wait)
@@ -276,20 +342,38 @@ impl ShardReplicaSet {
         drop(remotes);
         drop(local);
 
+        let (read_only_res, all_res): (Vec<_>, Vec<_>) =
+            all_res.into_iter().partition(|res| match res {
+                Ok((peer_id, _)) | Err((peer_id, _)) => read_only_peers.contains(peer_id),
+            });
+
+        self.handle_read_only_results(read_only_res);
+
         let total_results = all_res.len();
 
-        let write_consistency_factor = self
-            .collection_config
-            .read()
-            .await
-            .params
-            .write_consistency_factor
-            .get() as usize;
+        let minimal_success_count = match required_successes {
+            Some(required_successes) => required_successes,
+            None => {
+                let write_consistency_factor = self
+                    .collection_config
+                    .read()
+                    .await
+                    .params
+                    .write_consistency_factor
+                    .get() as usize;
 
-        let minimal_success_count = write_consistency_factor.min(total_results);
+                write_consistency_factor.min(total_results)
+            }
+        };
 
         let (successes, failures): (Vec<_>, Vec<_>) = all_res.into_iter().partition_result();
 
+        let is_consistent = successes.len() >= minimal_success_count
+            && (!local_success_required
+                || successes
+                    .iter()
+                    .any(|(peer_id, _)| *peer_id == this_peer_id));
+
         // Advance clock if some replica echoed *newer* tick
 
         let new_clock_tick = successes
@@ -332,7 +416,7 @@ impl ShardReplicaSet {
             "".to_string()
         };
 
-        if successes.len() >= minimal_success_count {
+        if is_consistent {
             let wait_for_deactivation = self.handle_failed_replicas(
                 &failures,
                 &self.replica_state.read(),
@@ -373,7 +457,7 @@ impl ShardReplicaSet {
             }
         }
 
-        if !failures.is_empty() && successes.len() < minimal_success_count {
+        if !failures.is_empty() && !is_consistent {
             self.handle_failed_replicas(
                 failures
                     .iter()
@@ -414,6 +498,19 @@ impl ShardReplicaSet {
         Ok(Some(res))
     }
 
+    /// Number of replicas which have to confirm an update with the given write consistency
+    ///
+    /// Read-only replicas are not counted, as they never confirm updates.
+    fn write_consistency_required_successes(&self, consistency: WriteConsistency) -> usize {
+        let replica_state = self.replica_state.read();
+        let replica_count = replica_state.peers.len() - replica_state.read_only_peers().len();
+        match consistency {
+            WriteConsistency::Local => 1,
+            WriteConsistency::Majority => replica_count / 2 + 1,
+            WriteConsistency::All => replica_count,
+        }
+    }
+
     fn peer_is_active_or_pending(&self, peer_id: &PeerId) -> bool {
         let res = match self.peer_state(peer_id) {
             Some(ReplicaState::Active) => true,
@@ -424,6 +521,7 @@ impl ShardReplicaSet {
             Some(ReplicaState::PartialSnapshot) => false,
             Some(ReplicaState::Recovery) => false,
             Some(ReplicaState::Resharding) => true,
+            Some(ReplicaState::ReadOnly) => true,
             None => false,
         };
         res && !self.is_locally_disabled(peer_id)
@@ -501,6 +599,7 @@ impl ShardReplicaSet {
         operation: CollectionUpdateOperations,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
     ) -> CollectionResult<UpdateResult> {
         // `RemoteShard::forward_update` is cancel safe, so this method is cancel safe.
 
@@ -514,7 +613,12 @@ impl ShardReplicaSet {
         };
 
         remote_leader
-            .forward_update(OperationWithClockTag::from(operation), wait, ordering) // `clock_tag` *have to* be `None`!
+            .forward_update(
+                OperationWithClockTag::from(operation), // `clock_tag` *have to* be `None`!
+                wait,
+                ordering,
+                consistency,
+            )
             .await
     }
 }
@@ -523,7 +627,9 @@ impl ShardReplicaSet {
 mod tests {
     use std::collections::HashSet;
     use std::num::NonZeroU32;
+    use std::sync::atomic::Ordering;
     use std::sync::Arc;
+    use std::time::Instant;
 
     use common::cpu::CpuBudget;
     use segment::types::Distance;
@@ -533,16 +639,20 @@ mod tests {
 
     use super::*;
     use crate::config::*;
-    use crate::operations::types::VectorsConfig;
+    use crate::operations::consistency_params::{ReadConsistency, SessionToken};
+    use crate::operations::shared_storage_config::SharedStorageConfig;
+    use crate::operations::types::{CountRequestInternal, VectorsConfig};
     use crate::operations::vector_params_builder::VectorParamsBuilder;
     use crate::optimizers_builder::OptimizersConfig;
     use crate::save_on_disk::SaveOnDisk;
-    use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState};
+    use crate::shards::replica_set::{AbortShardTransfer, ChangePeerState, REPLICA_STATE_FILE};
+    use crate::shards::shard::{Shard, ShardId};
+    use crate::tests::fixtures::upsert_operation;
 
     #[tokio::test]
     async fn test_highest_replica_peer_id() {
         let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
-        let rs = new_shard_replica_set(&collection_dir).await;
+        let rs = new_shard_replica_set(&collection_dir, false, Default::default()).await;
 
         assert_eq!(rs.highest_replica_peer_id(), Some(5));
         // at build time the replicas are all dead, they need to be activated
@@ -557,18 +667,253 @@ mod tests {
         assert_eq!(rs.highest_alive_replica_peer_id(), Some(4));
     }
 
+    #[tokio::test]
+    async fn test_write_consistency() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir, true, Default::default()).await;
+
+        // Only the local replica is healthy
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        for peer_id in [2, 3, 4, 5] {
+            rs.set_replica_state(&peer_id, ReplicaState::Dead).unwrap();
+        }
+
+        for consistency in [WriteConsistency::Majority, WriteConsistency::All] {
+            let err = rs
+                .update_with_consistency(
+                    upsert_operation(),
+                    true,
+                    WriteOrdering::Weak,
+                    Some(consistency),
+                    false,
+                )
+                .await
+                .unwrap_err();
+            assert!(matches!(err, CollectionError::BadRequest { .. }), "{err}");
+        }
+
+        rs.update_with_consistency(
+            upsert_operation(),
+            true,
+            WriteOrdering::Weak,
+            Some(WriteConsistency::Local),
+            false,
+        )
+        .await
+        .unwrap();
+
+        // Without explicit consistency, write consistency factor is capped by available replicas
+        rs.update_with_consistency(upsert_operation(), true, WriteOrdering::Weak, None, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_max_pending_updates() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let shared_storage_config = SharedStorageConfig {
+            max_pending_updates: Some(2),
+            ..Default::default()
+        };
+        let rs = new_shard_replica_set(&collection_dir, true, shared_storage_config).await;
+
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        for peer_id in [2, 3, 4, 5] {
+            rs.set_replica_state(&peer_id, ReplicaState::Dead).unwrap();
+        }
+
+        rs.update_with_consistency(upsert_operation(), true, WriteOrdering::Weak, None, false)
+            .await
+            .unwrap();
+
+        // Simulate updates waiting for space in a full update queue
+        let local = rs.local.read().await;
+        let Some(Shard::Local(local_shard)) = local.as_ref() else {
+            panic!("local shard expected");
+        };
+        local_shard.waiting_updates.fetch_add(2, Ordering::Relaxed);
+        assert_eq!(local_shard.pending_updates(), 2);
+        drop(local);
+
+        let err = rs
+            .update_with_consistency(upsert_operation(), true, WriteOrdering::Weak, None, false)
+            .await
+            .unwrap_err();
+        assert!(
+            matches!(err, CollectionError::ResourceExhausted { .. }),
+            "{err}",
+        );
+
+        // Rejected update must not deactivate the local replica
+        assert_eq!(rs.peer_state(&1), Some(ReplicaState::Active));
+
+        let local = rs.local.read().await;
+        let Some(Shard::Local(local_shard)) = local.as_ref() else {
+            panic!("local shard expected");
+        };
+        local_shard.waiting_updates.fetch_sub(2, Ordering::Relaxed);
+        drop(local);
+
+        rs.update_with_consistency(upsert_operation(), true, WriteOrdering::Weak, None, false)
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_read_only_replicas() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let rs = new_shard_replica_set(&collection_dir, true, Default::default()).await;
+
+        // Only the local replica confirms updates, remote replicas are unreachable
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        for peer_id in [2, 3, 4, 5] {
+            rs.set_replica_state(&peer_id, ReplicaState::ReadOnly)
+                .unwrap();
+        }
+
+        assert_eq!(rs.highest_replica_peer_id(), Some(1));
+        assert_eq!(
+            rs.write_consistency_required_successes(WriteConsistency::All),
+            1,
+        );
+        assert!(rs.peer_is_readable(&2));
+
+        // Failed read-only replicas don't fail the update, but are no longer read from
+        rs.update_with_consistency(
+            upsert_operation(),
+            true,
+            WriteOrdering::Weak,
+            Some(WriteConsistency::All),
+            false,
+        )
+        .await
+        .unwrap();
+
+        for peer_id in [2, 3, 4, 5] {
+            assert_eq!(rs.peer_state(&peer_id), Some(ReplicaState::ReadOnly));
+            assert!(!rs.peer_is_readable(&peer_id));
+        }
+        assert!(rs.peer_is_readable(&1));
+
+        // Replica which missed updates can't be promoted
+        let err = rs
+            .wait_for_read_only_catch_up(2, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CollectionError::BadInput { .. }), "{err}");
+
+        // Only read-only replicas can be promoted
+        let err = rs
+            .wait_for_read_only_catch_up(1, Duration::from_secs(1))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CollectionError::BadInput { .. }), "{err}");
+
+        // Lag is kept when the same state is applied by the consensus, and persisted
+        rs.apply_state(rs.peers()).await.unwrap();
+        assert!(!rs.peer_is_readable(&2));
+
+        let replica_state: SaveOnDisk<ReplicaSetState> =
+            SaveOnDisk::load_or_init_default(rs.shard_path.join(REPLICA_STATE_FILE)).unwrap();
+        assert!(replica_state.read().is_lagging_read_only(&2));
+
+        // Changing the state in consensus makes the replica readable again
+        rs.set_replica_state(&2, ReplicaState::ReadOnly).unwrap();
+        assert!(rs.peer_is_readable(&2));
+    }
+
+    #[tokio::test]
+    async fn test_session_read() {
+        let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+        let shared_storage_config = SharedStorageConfig {
+            session_read_timeout: Duration::from_millis(200),
+            ..Default::default()
+        };
+        let rs = new_shard_replica_set(&collection_dir, true, shared_storage_config).await;
+
+        rs.set_replica_state(&1, ReplicaState::Active).unwrap();
+        for peer_id in [2, 3, 4, 5] {
+            rs.set_replica_state(&peer_id, ReplicaState::Dead).unwrap();
+        }
+
+        let result = rs
+            .update_with_consistency(
+                upsert_operation(),
+                true,
+                WriteOrdering::Weak,
+                Some(WriteConsistency::Local),
+                false,
+            )
+            .await
+            .unwrap();
+        let clock_tag = result.clock_tag.unwrap();
+
+        let count_request = Arc::new(CountRequestInternal {
+            filter: None,
+            exact: true,
+            consistent: false,
+        });
+        let session_count = |clock_tag: ClockTag, shard_id: ShardId| {
+            let mut session_token = SessionToken::default();
+            session_token.add(shard_id, clock_tag);
+            rs.count(
+                count_request.clone(),
+                Some(ReadConsistency::Session(session_token)),
+                None,
+                false,
+            )
+        };
+
+        // Local replica has applied the update, so it serves the read
+        let count = session_count(clock_tag, rs.shard_id).await.unwrap();
+        assert_eq!(count.count, 5);
+
+        // Session without updates in this shard is served without waiting
+        let unseen_clock_tag = ClockTag::new(clock_tag.peer_id, clock_tag.clock_id, 1000);
+        let count = session_count(unseen_clock_tag, rs.shard_id + 1)
+            .await
+            .unwrap();
+        assert_eq!(count.count, 5);
+
+        // No replica has applied the update, the read times out
+        let started = Instant::now();
+        let err = session_count(unseen_clock_tag, rs.shard_id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, CollectionError::Timeout { .. }), "{err}");
+        assert!(started.elapsed() >= Duration::from_millis(200));
+
+        // Local replica which is not readable doesn't serve the read, even if it has applied the
+        // update, and unreachable remote replicas can't confirm they have applied it
+        rs.set_replica_state(&1, ReplicaState::Dead).unwrap();
+        rs.set_replica_state(&2, ReplicaState::Active).unwrap();
+        let err = session_count(clock_tag, rs.shard_id).await.unwrap_err();
+        assert!(matches!(err, CollectionError::Timeout { .. }), "{err}");
+    }
+
     const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
         deleted_threshold: 0.9,
+        payload_index_deleted_threshold: None,
         vacuum_min_vector_number: 1000,
         default_segment_number: 2,
         max_segment_size: None,
         memmap_threshold: None,
         indexing_threshold: Some(50_000),
         flush_interval_sec: 30,
+        min_segment_size: None,
+        max_flush_delay_sec: None,
         max_optimization_threads: Some(2),
+        deduplicate_vectors: None,
+        deduplication_epsilon: None,
+        defer_indexing: None,
+        split_oversized_segments: None,
     };
 
-    async fn new_shard_replica_set(collection_dir: &TempDir) -> ShardReplicaSet {
+    async fn new_shard_replica_set(
+        collection_dir: &TempDir,
+        local: bool,
+        shared_storage_config: SharedStorageConfig,
+    ) -> ShardReplicaSet {
         let update_runtime = Handle::current();
         let search_runtime = Handle::current();
 
@@ -592,6 +937,8 @@ mod tests {
             hnsw_config: Default::default(),
             quantization_config: None,
             strict_mode_config: None,
+            ttl_config: None,
+            shard_optimizer_config: Default::default(),
         };
 
         let payload_index_schema_dir = Builder::new().prefix("qdrant-test").tempdir().unwrap();
@@ -605,14 +952,14 @@ mod tests {
             1,
             "test_collection".to_string(),
             1,
-            false,
+            local,
             remotes,
             dummy_on_replica_failure(),
             dummy_abort_shard_transfer(),
             collection_dir.path(),
             shared_config,
             config.optimizer_config.clone(),
-            Default::default(),
+            Arc::new(shared_storage_config),
             payload_index_schema,
             Default::default(),
             update_runtime,
@@ -44,6 +44,10 @@ pub struct ReshardState {
     pub shard_key: Option<ShardKey>,
     pub direction: ReshardingDirection,
     pub stage: ReshardStage,
+    /// Started automatically to split an oversized shard, driven by the target peer until it
+    /// finishes. Resharding started through the API is driven externally, stage by stage.
+    #[serde(default)]
+    pub automatic: bool,
 }
 
 impl ReshardState {
@@ -52,6 +56,7 @@ impl ReshardState {
         peer_id: PeerId,
         shard_id: ShardId,
         shard_key: Option<ShardKey>,
+        automatic: bool,
     ) -> Self {
         Self {
             direction,
@@ -59,6 +64,7 @@ impl ReshardState {
             shard_id,
             shard_key,
             stage: ReshardStage::MigratingPoints,
+            automatic,
         }
     }
 
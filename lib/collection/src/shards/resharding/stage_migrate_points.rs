@@ -182,6 +182,7 @@ async fn drive_up(
                         to: this_peer_id,
                         sync: true,
                         method: Some(ShardTransferMethod::ReshardingStreamRecords),
+                        target_created: false,
                     };
                     (Some(transfer), true)
                 } else {
@@ -353,7 +354,7 @@ async fn drive_down(
             // Wait on all updates here, not just the last batch
             // If we don't wait on all updates it somehow results in inconsistent results
             target_replica_set
-                .update_with_consistency(operation, true, WriteOrdering::Weak, false)
+                .update_with_consistency(operation, true, WriteOrdering::Weak, None, false)
                 .await?;
 
             if offset.is_none() {
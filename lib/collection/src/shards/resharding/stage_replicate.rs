@@ -126,6 +126,7 @@ pub(super) async fn drive(
                     .default_shard_transfer_method
                     .unwrap_or_default(),
             ),
+            target_created: false,
         };
 
         // Create listener for transfer end before proposing to start the transfer
@@ -40,6 +40,8 @@ impl Resolve for CountResult {
                     .map(|result| result.count)
                     .min()
                     .unwrap_or_default(),
+                exact: records.iter().all(|result| result.exact),
+                partial: records.iter().any(|result| result.partial),
             },
             ResolveCondition::Majority => {
                 let mut counts = records
@@ -50,6 +52,8 @@ impl Resolve for CountResult {
                 let middle = counts.len() / 2;
                 Self {
                     count: counts.get(middle).copied().unwrap_or_default(),
+                    exact: records.iter().all(|result| result.exact),
+                    partial: records.iter().any(|result| result.partial),
                 }
             }
         }
@@ -80,17 +84,25 @@ impl Resolve for FacetResponse {
 
         let resolved_counts: HashMap<_, _> = responses
             .iter()
-            .flat_map(|FacetResponse { hits }| hits)
+            .flat_map(|FacetResponse { hits, .. }| hits)
             // Collect all hits into a Hashmap of {value -> Vec<CountResult>}
             .fold(
                 HashMap::new(),
                 |mut map: HashMap<FacetValue, Vec<CountResult>>, hit| {
                     if let Some(counts) = map.get_mut(&hit.value) {
-                        counts.push(CountResult { count: hit.count });
+                        counts.push(CountResult {
+                            count: hit.count,
+                            exact: true,
+                            partial: false,
+                        });
                     } else {
                         map.entry(hit.value.clone())
                             .or_insert(Vec::with_capacity(num_replicas))
-                            .push(CountResult { count: hit.count });
+                            .push(CountResult {
+                                count: hit.count,
+                                exact: true,
+                                partial: false,
+                            });
                     };
                     map
                 },
@@ -106,7 +118,14 @@ impl Resolve for FacetResponse {
             })
             .collect();
 
-        let filtered_iters = responses.into_iter().map(|FacetResponse { hits }| {
+        let exact = responses.iter().all(|response| response.exact);
+        let other_count = responses
+            .iter()
+            .map(|response| response.other_count)
+            .max()
+            .unwrap_or_default();
+
+        let filtered_iters = responses.into_iter().map(|FacetResponse { hits, .. }| {
             hits.into_iter().filter_map(|mut hit| {
                 resolved_counts.get(&hit.value).map(|&count| {
                     // Use the resolved count
@@ -128,6 +147,8 @@ impl Resolve for FacetResponse {
 
         FacetResponse {
             hits: resolved_hits,
+            other_count,
+            exact,
         }
     }
 }
@@ -543,6 +564,8 @@ mod test {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
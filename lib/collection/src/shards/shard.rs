@@ -7,7 +7,10 @@ use common::types::TelemetryDetail;
 
 use super::local_shard::clock_map::RecoveryPoint;
 use super::update_tracker::UpdateTracker;
-use crate::operations::types::{CollectionError, CollectionResult};
+use crate::operations::consistency_params::SessionClock;
+use crate::operations::types::{
+    CollectionError, CollectionResult, LocalShardHealth, SegmentDescription, WarmupResult,
+};
 use crate::shards::dummy_shard::DummyShard;
 use crate::shards::forward_proxy_shard::ForwardProxyShard;
 use crate::shards::local_shard::LocalShard;
@@ -117,6 +120,90 @@ impl Shard {
         }
     }
 
+    pub async fn flush(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.flush().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.flush().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.flush().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.flush().await,
+            // Dummy shard has no data to flush
+            Shard::Dummy(_) => Ok(()),
+        }
+    }
+
+    pub async fn warmup(
+        &self,
+        with_quantization: bool,
+        max_bytes: usize,
+    ) -> CollectionResult<WarmupResult> {
+        match self {
+            Shard::Local(local_shard) => local_shard.warmup(with_quantization, max_bytes).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.warmup(with_quantization, max_bytes).await,
+            Shard::ForwardProxy(proxy_shard) => {
+                proxy_shard.warmup(with_quantization, max_bytes).await
+            }
+            Shard::QueueProxy(proxy_shard) => {
+                proxy_shard.warmup(with_quantization, max_bytes).await
+            }
+            // Dummy shard has no data to warm up
+            Shard::Dummy(_) => Ok(WarmupResult::default()),
+        }
+    }
+
+    pub async fn health(&self) -> LocalShardHealth {
+        match self {
+            Shard::Local(local_shard) => local_shard.health().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.health().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.health().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.health().await,
+            Shard::Dummy(dummy_shard) => dummy_shard.health(),
+        }
+    }
+
+    pub fn list_segments(&self) -> Vec<SegmentDescription> {
+        match self {
+            Shard::Local(local_shard) => local_shard.list_segments(),
+            Shard::Proxy(proxy_shard) => proxy_shard.list_segments(),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.list_segments(),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.list_segments(),
+            Shard::Dummy(dummy_shard) => dummy_shard.list_segments(),
+        }
+    }
+
+    pub fn check_pending_updates(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.check_pending_updates(),
+            Shard::Proxy(proxy_shard) => proxy_shard.check_pending_updates(),
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.check_pending_updates(),
+            Shard::QueueProxy(proxy_shard) => proxy_shard.check_pending_updates(),
+            // Dummy shard rejects all updates anyway
+            Shard::Dummy(_) => Ok(()),
+        }
+    }
+
+    pub async fn has_applied_clocks(&self, clocks: &[SessionClock]) -> CollectionResult<bool> {
+        match self {
+            Shard::Local(local_shard) => local_shard.has_applied_clocks(clocks).await,
+            Shard::Proxy(proxy_shard) => proxy_shard.has_applied_clocks(clocks).await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.has_applied_clocks(clocks).await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.has_applied_clocks(clocks).await,
+            // Dummy shard does not apply any operations
+            Shard::Dummy(_) => Ok(false),
+        }
+    }
+
+    /// Wait until all updates received by this shard so far are applied
+    pub async fn plunge(&self) -> CollectionResult<()> {
+        match self {
+            Shard::Local(local_shard) => local_shard.plunge().await,
+            Shard::Proxy(proxy_shard) => proxy_shard.plunge().await,
+            Shard::ForwardProxy(proxy_shard) => proxy_shard.plunge().await,
+            Shard::QueueProxy(proxy_shard) => proxy_shard.plunge().await,
+            // Dummy shard does not apply any operations
+            Shard::Dummy(_) => Ok(()),
+        }
+    }
+
     pub fn is_update_in_progress(&self) -> bool {
         self.update_tracker()
             .map_or(false, UpdateTracker::is_update_in_progress)
@@ -980,10 +980,6 @@ impl ShardHolder {
         shard_id: ShardId,
         cancel: cancel::CancellationToken,
     ) -> CollectionResult<bool> {
-        // TODO:
-        //   Check that shard snapshot is compatible with the collection
-        //   (see `VectorsConfig::check_compatible_with_segment_config`)
-
         let replica_set = self
             .get_shard(&shard_id)
             .ok_or_else(|| shard_not_found_error(shard_id))?;
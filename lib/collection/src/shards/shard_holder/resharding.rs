@@ -90,6 +90,7 @@ impl ShardHolder {
         &mut self,
         resharding_key: ReshardKey,
         new_shard: Option<ShardReplicaSet>,
+        automatic: bool,
     ) -> CollectionResult<()> {
         let ReshardKey {
             direction,
@@ -115,7 +116,9 @@ impl ShardHolder {
                 "resharding is already in progress:\n{state:#?}",
             );
 
-            *state = Some(ReshardState::new(direction, peer_id, shard_id, shard_key));
+            *state = Some(ReshardState::new(
+                direction, peer_id, shard_id, shard_key, automatic,
+            ));
         })?;
 
         Ok(())
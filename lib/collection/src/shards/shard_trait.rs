@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use cancel::CancellationToken;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::data_types::order_by::OrderBy;
 use segment::types::*;
@@ -34,11 +35,13 @@ pub trait ShardOperation {
 
     async fn info(&self) -> CollectionResult<CollectionInfo>;
 
+    /// Search the shard, stops early once `cancel` is triggered
     async fn core_search(
         &self,
         request: Arc<CoreSearchRequestBatch>,
         search_runtime_handle: &Handle,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> CollectionResult<Vec<Vec<ScoredPoint>>>;
 
     async fn count(
@@ -33,6 +33,8 @@ pub struct LocalShardTelemetry {
     pub status: Option<ShardStatus>,
     /// Total number of optimized points since the last start.
     pub total_optimized_points: usize,
+    /// Number of operations in the WAL, which are not yet applied to the segments.
+    pub wal_lag: u64,
     pub segments: Vec<SegmentTelemetry>,
     pub optimizations: OptimizerTelemetry,
 }
@@ -60,6 +62,7 @@ impl Anonymize for LocalShardTelemetry {
             variant_name: self.variant_name.clone(),
             status: self.status,
             total_optimized_points: self.total_optimized_points.anonymize(),
+            wal_lag: self.wal_lag,
             segments: self.segments.anonymize(),
             optimizations: self.optimizations.anonymize(),
         }
@@ -44,6 +44,11 @@ pub struct ShardTransfer {
     /// Method to transfer shard with. `None` to choose automatically.
     #[serde(default)]
     pub method: Option<ShardTransferMethod>,
+    /// Whether the target replica was created by this transfer, set when the transfer starts
+    /// If the transfer is aborted, such replica is removed instead of being marked as dead
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    #[schemars(skip)]
+    pub target_created: bool,
 }
 
 impl ShardTransfer {
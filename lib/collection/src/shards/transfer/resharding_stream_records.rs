@@ -67,6 +67,8 @@ pub(crate) async fn transfer_resharding_stream_records(
                 Arc::new(CountRequestInternal {
                     filter: None,
                     exact: true,
+                    consistent: false,
+                    allow_partial: false,
                 }),
                 None,
             )
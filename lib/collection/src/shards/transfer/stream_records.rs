@@ -52,6 +52,8 @@ pub(super) async fn transfer_stream_records(
                 Arc::new(CountRequestInternal {
                     filter: None,
                     exact: true,
+                    consistent: false,
+                    allow_partial: false,
                 }),
                 None, // no timeout
             )
@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use segment::common::anonymize::Anonymize;
 use serde::Serialize;
@@ -14,6 +15,25 @@ pub struct CollectionTelemetry {
     pub shards: Vec<ReplicaSetTelemetry>,
     pub transfers: Vec<ShardTransferInfo>,
     pub resharding: Vec<ReshardingInfo>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scheduled_snapshots: Option<ScheduledSnapshotsTelemetry>,
+}
+
+/// Outcomes of the scheduled snapshots of the collection on this peer
+#[derive(Serialize, Clone, Debug, Default, JsonSchema)]
+pub struct ScheduledSnapshotsTelemetry {
+    /// Number of scheduled snapshots created
+    pub succeeded: usize,
+    /// Number of scheduled snapshots which failed
+    pub failed: usize,
+    /// Number of scheduled snapshots skipped, because the previous one was still running
+    pub skipped: usize,
+    /// Time when the last scheduled snapshot was created
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_success: Option<DateTime<Utc>>,
+    /// Error of the last failed scheduled snapshot
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
 }
 
 impl CollectionTelemetry {
@@ -36,6 +56,16 @@ impl Anonymize for CollectionTelemetry {
             shards: self.shards.anonymize(),
             transfers: vec![],
             resharding: vec![],
+            scheduled_snapshots: self.scheduled_snapshots.anonymize(),
+        }
+    }
+}
+
+impl Anonymize for ScheduledSnapshotsTelemetry {
+    fn anonymize(&self) -> Self {
+        Self {
+            last_error: None,
+            ..self.clone()
         }
     }
 }
@@ -49,6 +79,8 @@ impl Anonymize for CollectionConfig {
             wal_config: self.wal_config.clone(),
             quantization_config: self.quantization_config.clone(),
             strict_mode_config: self.strict_mode_config.clone(),
+            ttl_config: self.ttl_config.clone(),
+            shard_optimizer_config: self.shard_optimizer_config.clone(),
         }
     }
 }
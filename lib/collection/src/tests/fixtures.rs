@@ -14,13 +14,20 @@ use crate::optimizers_builder::OptimizersConfig;
 
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
+    payload_index_deleted_threshold: None,
     vacuum_min_vector_number: 1000,
     default_segment_number: 2,
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
+    min_segment_size: None,
+    max_flush_delay_sec: None,
     max_optimization_threads: Some(2),
+    deduplicate_vectors: None,
+    deduplication_epsilon: None,
+    defer_indexing: None,
+    split_oversized_segments: None,
 };
 
 pub fn create_collection_config() -> CollectionConfig {
@@ -46,6 +53,8 @@ pub fn create_collection_config() -> CollectionConfig {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     }
 }
 
@@ -58,6 +67,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 10.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                vector_metadata: None,
             },
             PointStruct {
                 id: 2.into(),
@@ -65,6 +75,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 11.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                vector_metadata: None,
             },
             PointStruct {
                 id: 3.into(),
@@ -72,6 +83,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": [ { "lat": 12.12, "lon": 34.82  }, { "lat": 12.2, "lon": 12.82  }] }"#).unwrap(),
                 ),
+                vector_metadata: None,
             },
             PointStruct {
                 id: 4.into(),
@@ -79,6 +91,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 13.12, "lon": 34.82  } }"#).unwrap(),
                 ),
+                vector_metadata: None,
             },
             PointStruct {
                 id: 5.into(),
@@ -86,6 +99,7 @@ pub fn upsert_operation() -> CollectionUpdateOperations {
                 payload: Some(
                     serde_json::from_str(r#"{ "location": { "lat": 14.12, "lon": 32.12  } }"#).unwrap(),
                 ),
+                vector_metadata: None,
             },
 
         ]
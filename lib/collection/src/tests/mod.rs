@@ -34,7 +34,8 @@ use crate::collection_manager::optimizers::TrackerStatus;
 use crate::config::CollectionParams;
 use crate::operations::types::VectorsConfig;
 use crate::operations::vector_params_builder::VectorParamsBuilder;
-use crate::update_handler::{Optimizer, UpdateHandler};
+use crate::optimizers_builder::OptimizersConfig;
+use crate::update_handler::{FlushBuffer, Optimizer, UpdateHandler};
 
 #[tokio::test]
 async fn test_optimization_process() {
@@ -234,6 +235,7 @@ async fn test_new_segment_when_all_over_capacity() {
         max_segment_size_kb: 1,
         memmap_threshold_kb: 1_000_000,
         indexing_threshold_kb: 1_000_000,
+        vector_deduplication: None,
     };
     let payload_index_schema = PayloadIndexSchema::default();
     let mut holder = SegmentHolder::default();
@@ -310,6 +312,51 @@ async fn test_new_segment_when_all_over_capacity() {
     assert_eq!(segments.read().len(), 7);
 }
 
+#[test]
+fn test_flush_buffer() {
+    let mut optimizers_config = OptimizersConfig::fixture();
+    assert_eq!(FlushBuffer::from_config(&optimizers_config), None);
+
+    optimizers_config.min_segment_size = Some(4);
+    let flush_buffer = FlushBuffer::from_config(&optimizers_config).unwrap();
+    assert_eq!(flush_buffer.min_size_bytes, 4096);
+    assert_eq!(flush_buffer.max_delay, Duration::from_secs(60));
+
+    // Writes are buffered until the minimal size or the maximum delay is reached
+    assert!(!flush_buffer.should_flush(0, Duration::ZERO));
+    assert!(!flush_buffer.should_flush(4095, Duration::from_secs(59)));
+    assert!(flush_buffer.should_flush(4096, Duration::ZERO));
+    assert!(flush_buffer.should_flush(0, Duration::from_secs(60)));
+
+    // Buffered writes are measured by the size of appendable segments
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let dim = 256;
+    let mut holder = SegmentHolder::default();
+    let segment_id = holder.add_new(random_segment(dir.path(), 100, 3, dim));
+    let segments: Arc<RwLock<_>> = Arc::new(RwLock::new(holder));
+
+    let flushed_size = UpdateHandler::appendable_segments_size(&segments);
+    assert_eq!(flushed_size, 3 * dim * std::mem::size_of::<f32>());
+
+    {
+        let segment = segments.read().get(segment_id).unwrap().clone();
+        let mut rnd = rand::thread_rng();
+        for _ in 0..4 {
+            let point_id: PointIdType = PointIdGenerator::default().unique();
+            let random_vector: Vec<_> = (0..dim).map(|_| rnd.gen()).collect();
+            segment
+                .get()
+                .write()
+                .upsert_point(101, point_id, only_default_vector(&random_vector))
+                .unwrap();
+        }
+    }
+
+    let buffered_bytes = UpdateHandler::appendable_segments_size(&segments) - flushed_size;
+    assert_eq!(buffered_bytes, 4 * dim * std::mem::size_of::<f32>());
+    assert!(flush_buffer.should_flush(buffered_bytes, Duration::ZERO));
+}
+
 #[test]
 fn check_version_upgrade() {
     assert!(!Collection::can_upgrade_storage(
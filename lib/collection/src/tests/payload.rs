@@ -57,6 +57,7 @@ async fn test_payload_missing_index_check() {
         GeoRadius {
             center: GeoPoint::new(12.0, 34.0).ok().unwrap(),
             radius: 50.0,
+            inner_radius: None,
         },
     )));
 
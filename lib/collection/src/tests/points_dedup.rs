@@ -56,6 +56,8 @@ async fn fixture() -> Collection {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
@@ -114,6 +116,7 @@ async fn fixture() -> Collection {
                         "num".to_string(),
                         Value::from(-(*shard_id as i32)),
                     )]))),
+                    vector_metadata: None,
                 },
                 PointStruct {
                     id: DUPLICATE_POINT_ID,
@@ -124,6 +127,7 @@ async fn fixture() -> Collection {
                         "num".to_string(),
                         Value::from(100 - *shard_id as i32),
                     )]))),
+                    vector_metadata: None,
                 },
             ])),
         ));
@@ -216,6 +220,7 @@ async fn test_retrieve_dedup() {
                     .collect(),
                 with_payload: Some(false.into()),
                 with_vector: false.into(),
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
@@ -252,6 +257,10 @@ async fn test_search_dedup() {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: false,
             },
             None,
             &ShardSelectorInternal::All,
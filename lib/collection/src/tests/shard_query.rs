@@ -62,6 +62,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -84,6 +85,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
     let outer_limit = 2;
     let query = ShardQueryRequest {
@@ -96,6 +98,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -127,6 +130,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
     let outer_limit = 2;
     let query = ShardQueryRequest {
@@ -142,6 +146,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -185,6 +190,7 @@ async fn test_shard_query_rrf_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -249,6 +255,7 @@ async fn test_shard_query_vector_rescoring() {
         params: None,
         filter: None,
         score_threshold: None,
+        weight: None,
     };
 
     // rescoring against a vector without prefetches
@@ -263,6 +270,7 @@ async fn test_shard_query_vector_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -289,6 +297,7 @@ async fn test_shard_query_vector_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -318,6 +327,7 @@ async fn test_shard_query_vector_rescoring() {
         params: None,
         with_vector: WithVector::Bool(false),
         with_payload: WithPayloadInterface::Bool(false),
+        seed: None,
     };
 
     let sources_scores = shard
@@ -384,7 +394,8 @@ async fn test_shard_query_payload_vector() {
         offset: 0,
         params: None,
         with_vector: WithVector::Bool(true), // requesting vector
-        with_payload: WithPayloadInterface::Bool(true), // requesting payload
+        with_payload: WithPayloadInterface::Bool(true),
+        seed: None, // requesting payload
     };
 
     let sources_scores = shard
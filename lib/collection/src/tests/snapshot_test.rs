@@ -53,6 +53,8 @@ async fn _test_snapshot_collection(node_type: NodeType) {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
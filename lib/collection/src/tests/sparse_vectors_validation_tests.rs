@@ -32,6 +32,7 @@ fn wrong_point_struct() -> PointStruct {
         id: 0.into(),
         vector: VectorStructInternal::Named(vector_data).into(),
         payload: None,
+        vector_metadata: None,
     }
 }
 
@@ -64,6 +65,8 @@ fn validate_error_sparse_vector_points_batch() {
             payloads: None,
         },
         shard_key: None,
+        expected_versions: None,
+        duplicate_ids: None,
     });
 }
 
@@ -72,6 +75,8 @@ fn validate_error_sparse_vector_points_list() {
     check_validation_error(PointsList {
         points: vec![wrong_point_struct()],
         shard_key: None,
+        expected_versions: None,
+        duplicate_ids: None,
     });
 }
 
@@ -86,6 +91,10 @@ fn validate_error_sparse_vector_search_request_internal() {
         with_payload: None,
         with_vector: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     });
 }
 
@@ -127,6 +136,7 @@ fn validate_error_sparse_vector_recommend_request_internal() {
         score_threshold: None,
         using: None,
         lookup_from: None,
+        mmr_lambda: None,
     });
 }
 
@@ -146,6 +156,7 @@ fn validate_error_sparse_vector_discover_request_internal() {
             positive: wrong_recommend_example(),
             negative: wrong_recommend_example(),
         }]),
+        weighted_context: None,
         filter: None,
         params: None,
         limit: 5,
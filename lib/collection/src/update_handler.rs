@@ -17,7 +17,7 @@ use tokio::sync::mpsc::{self, Receiver, Sender};
 use tokio::sync::{oneshot, Mutex as TokioMutex};
 use tokio::task::{self, JoinHandle};
 use tokio::time::error::Elapsed;
-use tokio::time::{timeout, Duration};
+use tokio::time::{timeout, Duration, Instant};
 
 use crate::collection::payload_index_schema::PayloadIndexSchema;
 use crate::collection_manager::collection_updater::CollectionUpdater;
@@ -31,6 +31,7 @@ use crate::config::CollectionParams;
 use crate::operations::shared_storage_config::SharedStorageConfig;
 use crate::operations::types::{CollectionError, CollectionResult};
 use crate::operations::CollectionUpdateOperations;
+use crate::optimizers_builder::OptimizersConfig;
 use crate::save_on_disk::SaveOnDisk;
 use crate::shards::local_shard::LocalShardClocks;
 use crate::wal::WalError;
@@ -41,6 +42,9 @@ use crate::wal_delta::LockedWal;
 /// The longer the duration, the longer it  takes for panicked tasks to be reported.
 const OPTIMIZER_CLEANUP_INTERVAL: Duration = Duration::from_secs(5);
 
+/// Default maximum time writes are buffered for before they are flushed
+const DEFAULT_MAX_FLUSH_DELAY_SEC: u64 = 60;
+
 pub type Optimizer = dyn SegmentOptimizer + Sync + Send;
 
 /// Information, required to perform operation and notify regarding the result
@@ -80,6 +84,38 @@ pub enum OptimizerSignal {
     Nop,
 }
 
+/// Buffering of writes in appendable segments before they are flushed
+///
+/// Small frequent flushes fragment the data into many small segments on ingest. Buffered writes
+/// are covered by the WAL, which is still flushed on every flush interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FlushBuffer {
+    /// Minimal size of writes to buffer before they are flushed, in bytes
+    pub min_size_bytes: usize,
+    /// Maximum time writes are buffered for before they are flushed
+    pub max_delay: Duration,
+}
+
+impl FlushBuffer {
+    /// Buffering configured by `min_segment_size`, `None` if it is not set
+    pub fn from_config(config: &OptimizersConfig) -> Option<Self> {
+        let min_segment_size_kb = config.min_segment_size?;
+        Some(Self {
+            min_size_bytes: min_segment_size_kb.saturating_mul(segment::common::BYTES_IN_KB),
+            max_delay: Duration::from_secs(
+                config
+                    .max_flush_delay_sec
+                    .unwrap_or(DEFAULT_MAX_FLUSH_DELAY_SEC),
+            ),
+        })
+    }
+
+    /// Whether buffered writes have to be flushed now
+    pub fn should_flush(&self, buffered_bytes: usize, since_last_flush: Duration) -> bool {
+        buffered_bytes >= self.min_size_bytes || since_last_flush >= self.max_delay
+    }
+}
+
 /// Structure, which holds object, required for processing updates of the collection
 pub struct UpdateHandler {
     shared_storage_config: Arc<SharedStorageConfig>,
@@ -90,12 +126,17 @@ pub struct UpdateHandler {
     optimizers_log: Arc<Mutex<TrackerLog>>,
     /// Total number of optimized points since last start
     total_optimized_points: Arc<AtomicUsize>,
+    /// Version of the last WAL operation applied to the segments
+    applied_wal_version: Arc<AtomicU64>,
     /// Global CPU budget in number of cores for all optimization tasks.
     /// Assigns CPU permits to tasks to limit overall resource utilization.
     optimizer_cpu_budget: CpuBudget,
     /// How frequent can we flush data
     /// This parameter depends on the optimizer config and should be updated accordingly.
     pub flush_interval_sec: u64,
+    /// Buffer writes before flushing them, flushed on every flush interval if not set
+    /// This parameter depends on the optimizer config and should be updated accordingly.
+    pub flush_buffer: Option<FlushBuffer>,
     segments: LockedSegmentHolder,
     /// Process, that listens updates signals and perform updates
     update_worker: Option<JoinHandle<()>>,
@@ -132,11 +173,13 @@ impl UpdateHandler {
         optimizers: Arc<Vec<Arc<Optimizer>>>,
         optimizers_log: Arc<Mutex<TrackerLog>>,
         total_optimized_points: Arc<AtomicUsize>,
+        applied_wal_version: Arc<AtomicU64>,
         optimizer_cpu_budget: CpuBudget,
         runtime_handle: Handle,
         segments: LockedSegmentHolder,
         wal: LockedWal,
         flush_interval_sec: u64,
+        flush_buffer: Option<FlushBuffer>,
         max_optimization_threads: Option<usize>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
@@ -150,6 +193,7 @@ impl UpdateHandler {
             optimizer_worker: None,
             optimizers_log,
             total_optimized_points,
+            applied_wal_version,
             optimizer_cpu_budget,
             flush_worker: None,
             flush_stop: None,
@@ -157,6 +201,7 @@ impl UpdateHandler {
             wal,
             wal_keep_from: Arc::new(u64::MAX.into()),
             flush_interval_sec,
+            flush_buffer,
             optimization_handles: Arc::new(TokioMutex::new(vec![])),
             max_optimization_threads,
             clocks,
@@ -186,6 +231,7 @@ impl UpdateHandler {
             tx,
             self.wal.clone(),
             self.segments.clone(),
+            self.applied_wal_version.clone(),
         )));
         let (flush_tx, flush_rx) = oneshot::channel();
         self.flush_worker = Some(self.runtime_handle.spawn(Self::flush_worker(
@@ -193,6 +239,7 @@ impl UpdateHandler {
             self.wal.clone(),
             self.wal_keep_from.clone(),
             self.flush_interval_sec,
+            self.flush_buffer,
             flush_rx,
             self.clocks.clone(),
             self.shard_path.clone(),
@@ -659,6 +706,7 @@ impl UpdateHandler {
         optimize_sender: Sender<OptimizerSignal>,
         wal: LockedWal,
         segments: LockedSegmentHolder,
+        applied_wal_version: Arc<AtomicU64>,
     ) {
         while let Some(signal) = receiver.recv().await {
             match signal {
@@ -681,6 +729,9 @@ impl UpdateHandler {
                     let operation_result = flush_res
                         .and_then(|_| CollectionUpdater::update(&segments, op_num, operation));
 
+                    // Failed operations are processed too, they are re-applied on recovery
+                    applied_wal_version.fetch_max(op_num, Ordering::Relaxed);
+
                     let res = match operation_result {
                         Ok(update_res) => optimize_sender
                             .send(OptimizerSignal::Operation(op_num))
@@ -728,15 +779,21 @@ impl UpdateHandler {
             .unwrap_or_else(|_| debug!("Optimizer already stopped"));
     }
 
+    #[allow(clippy::too_many_arguments)]
     async fn flush_worker(
         segments: LockedSegmentHolder,
         wal: LockedWal,
         wal_keep_from: Arc<AtomicU64>,
         flush_interval_sec: u64,
+        flush_buffer: Option<FlushBuffer>,
         mut stop_receiver: oneshot::Receiver<()>,
         clocks: LocalShardClocks,
         shard_path: PathBuf,
     ) {
+        // Size of appendable segments and time of the last flush, to measure buffered writes
+        let mut flushed_size = Self::appendable_segments_size(&segments);
+        let mut last_flush = Instant::now();
+
         loop {
             // Stop flush worker on signal or if sender was dropped
             // Even if timer did not finish
@@ -744,57 +801,119 @@ impl UpdateHandler {
                 _ = tokio::time::sleep(Duration::from_secs(flush_interval_sec)) => {},
                 _ = &mut stop_receiver => {
                     debug!("Stopping flush worker.");
-                    return;
+                    break;
                 }
             }
 
             trace!("Attempting flushing");
-            let wal_flash_job = wal.lock().flush_async();
-
-            if let Err(err) = wal_flash_job.join() {
-                error!("Failed to flush wal: {:?}", err);
-                segments
-                    .write()
-                    .report_optimizer_error(WalError::WriteWalError(format!(
-                        "WAL flush error: {err:?}"
-                    )));
+            if !Self::flush_wal(&segments, &wal) {
                 continue;
             }
 
-            let confirmed_version = Self::flush_segments(segments.clone());
-            let confirmed_version = match confirmed_version {
-                Ok(version) => version,
-                Err(err) => {
-                    error!("Failed to flush: {err}");
-                    segments.write().report_optimizer_error(err);
+            if let Some(flush_buffer) = flush_buffer {
+                // Appendable segments shrink when they are optimized, which flushes their data
+                let current_size = Self::appendable_segments_size(&segments);
+                flushed_size = flushed_size.min(current_size);
+
+                let buffered_bytes = current_size - flushed_size;
+                if !flush_buffer.should_flush(buffered_bytes, last_flush.elapsed()) {
+                    trace!("Buffering {buffered_bytes} bytes of writes, postponing flush");
                     continue;
                 }
-            };
 
-            // Acknowledge confirmed version in WAL, but don't acknowledge the specified
-            // `keep_from` index or higher.
-            // This is to prevent truncating WAL entries that other bits of code still depend on
-            // such as the queue proxy shard.
-            // Default keep_from is `u64::MAX` to allow acknowledging all confirmed.
-            let keep_from = wal_keep_from.load(std::sync::atomic::Ordering::Relaxed);
-
-            // If we should keep the first message, do not acknowledge at all
-            if keep_from == 0 {
-                continue;
+                flushed_size = current_size;
+                last_flush = Instant::now();
             }
 
-            let ack = confirmed_version.min(keep_from.saturating_sub(1));
+            Self::flush_and_ack(&segments, &wal, &wal_keep_from, &clocks, &shard_path).await;
+        }
 
-            if let Err(err) = clocks.store_if_changed(&shard_path).await {
-                log::warn!("Failed to store clock maps to disk: {err}");
-                segments.write().report_optimizer_error(err);
-            }
+        // Buffered writes are flushed on shutdown, regardless of their size
+        if flush_buffer.is_some() && Self::flush_wal(&segments, &wal) {
+            debug!("Flushing buffered writes of stopped flush worker");
+            Self::flush_and_ack(&segments, &wal, &wal_keep_from, &clocks, &shard_path).await;
+        }
+    }
+
+    /// Flush the WAL, returns whether it succeeded
+    ///
+    /// Errors are reported to the segment holder.
+    fn flush_wal(segments: &LockedSegmentHolder, wal: &LockedWal) -> bool {
+        let wal_flash_job = wal.lock().flush_async();
+
+        if let Err(err) = wal_flash_job.join() {
+            error!("Failed to flush wal: {:?}", err);
+            segments
+                .write()
+                .report_optimizer_error(WalError::WriteWalError(format!(
+                    "WAL flush error: {err:?}"
+                )));
+            return false;
+        }
 
-            if let Err(err) = wal.lock().ack(ack) {
-                log::warn!("Failed to acknowledge WAL version: {err}");
+        true
+    }
+
+    /// Flush segments and acknowledge the flushed version in the WAL
+    ///
+    /// Errors are reported to the segment holder.
+    async fn flush_and_ack(
+        segments: &LockedSegmentHolder,
+        wal: &LockedWal,
+        wal_keep_from: &AtomicU64,
+        clocks: &LocalShardClocks,
+        shard_path: &Path,
+    ) {
+        let confirmed_version = Self::flush_segments(segments.clone());
+        let confirmed_version = match confirmed_version {
+            Ok(version) => version,
+            Err(err) => {
+                error!("Failed to flush: {err}");
                 segments.write().report_optimizer_error(err);
+                return;
             }
+        };
+
+        // Acknowledge confirmed version in WAL, but don't acknowledge the specified
+        // `keep_from` index or higher.
+        // This is to prevent truncating WAL entries that other bits of code still depend on
+        // such as the queue proxy shard.
+        // Default keep_from is `u64::MAX` to allow acknowledging all confirmed.
+        let keep_from = wal_keep_from.load(std::sync::atomic::Ordering::Relaxed);
+
+        // If we should keep the first message, do not acknowledge at all
+        if keep_from == 0 {
+            return;
+        }
+
+        let ack = confirmed_version.min(keep_from.saturating_sub(1));
+
+        if let Err(err) = clocks.store_if_changed(shard_path).await {
+            log::warn!("Failed to store clock maps to disk: {err}");
+            segments.write().report_optimizer_error(err);
         }
+
+        if let Err(err) = wal.lock().ack(ack) {
+            log::warn!("Failed to acknowledge WAL version: {err}");
+            segments.write().report_optimizer_error(err);
+        }
+    }
+
+    /// Total size of vectors in appendable segments, in bytes
+    pub(super) fn appendable_segments_size(segments: &LockedSegmentHolder) -> usize {
+        let segments_read = segments.read();
+        segments_read
+            .appendable_segments_ids()
+            .into_iter()
+            .filter_map(|segment_id| segments_read.get(segment_id))
+            .map(|segment| {
+                segment
+                    .get()
+                    .read()
+                    .max_available_vectors_size_in_bytes()
+                    .unwrap_or_default()
+            })
+            .sum()
     }
 
     /// Returns confirmed version after flush of all segments
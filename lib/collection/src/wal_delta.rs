@@ -5,6 +5,7 @@ use parking_lot::{Mutex as ParkingMutex, MutexGuard as ParkingMutexGuard};
 use thiserror::Error;
 use tokio::sync::Mutex;
 
+use crate::operations::consistency_params::SessionClock;
 use crate::operations::{ClockTag, OperationWithClockTag};
 use crate::shards::local_shard::clock_map::{ClockMap, RecoveryPoint};
 use crate::wal::SerdeWal;
@@ -55,26 +56,56 @@ impl RecoverableWal {
         operation: &mut OperationWithClockTag,
     ) -> crate::wal::Result<(u64, ParkingMutexGuard<'a, SerdeWal<OperationWithClockTag>>)> {
         // Update last seen clock map and correct clock tag if necessary
-        if let Some(clock_tag) = &mut operation.clock_tag {
+        let newest_clocks = if let Some(clock_tag) = &mut operation.clock_tag {
+            let mut newest_clocks = self.newest_clocks.lock().await;
+
             // TODO: Do not manually advance here!
             //
             // TODO: What does the above `TODO` mean? "Make sure to call `advance_clock_and_correct_tag`, but not `advance_clock`?"
-            let operation_accepted = self
-                .newest_clocks
-                .lock()
-                .await
-                .advance_clock_and_correct_tag(clock_tag);
+            let operation_accepted = newest_clocks.advance_clock_and_correct_tag(clock_tag);
 
             if !operation_accepted {
                 return Err(crate::wal::WalError::ClockRejected);
             }
-        }
+
+            Some(newest_clocks)
+        } else {
+            None
+        };
 
         // Write operation to WAL
+        //
+        // Clock map is released only after WAL is locked, so that anyone who has seen the new
+        // clock tick can wait for the operation by locking the WAL (see `has_seen_clocks`)
         let mut wal_lock = self.wal.lock();
+        drop(newest_clocks);
+
         wal_lock.write(operation).map(|op_num| (op_num, wal_lock))
     }
 
+    /// Check whether this WAL has seen all given clocks
+    ///
+    /// If it returns `true`, operations with these clocks are written to the WAL and are already
+    /// sent to the update handler.
+    pub async fn has_seen_clocks(&self, clocks: &[SessionClock]) -> bool {
+        let has_seen_clocks = {
+            let newest_clocks = self.newest_clocks.lock().await;
+
+            clocks.iter().all(|clock| {
+                newest_clocks
+                    .current_tick(clock.peer_id, clock.clock_id)
+                    .map_or(false, |tick| tick >= clock.clock_tick)
+            })
+        };
+
+        if has_seen_clocks {
+            // Wait for the writer of the latest operation to release the WAL lock
+            drop(self.wal.lock());
+        }
+
+        has_seen_clocks
+    }
+
     /// Update the cutoff clock map based on the given recovery point
     ///
     /// This can only increase clock ticks in the cutoff clock map. If there already are higher
@@ -298,6 +329,7 @@ mod tests {
                 id: id.into(),
                 vector: VectorStructInternal::from(vec![1.0, 2.0, 3.0]).into(),
                 payload: None,
+                vector_metadata: None,
             }]),
         ))
     }
@@ -1273,6 +1305,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            vector_metadata: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -1319,6 +1352,7 @@ mod tests {
                             )
                             .into(),
                             payload: None,
+                            vector_metadata: None,
                         }]),
                     ));
                 let operation = OperationWithClockTag::new(bare_operation, Some(clock_tag));
@@ -1,9 +1,18 @@
+use std::fs;
+use std::sync::Arc;
+
+use collection::collection::Collection;
 use collection::operations::point_ops::{
     Batch, PointInsertOperationsInternal, PointOperations, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
-use collection::operations::types::ScrollRequestInternal;
+use collection::operations::shared_storage_config::SharedStorageConfig;
+use collection::operations::types::{CountRequestInternal, HealthStatus, ScrollRequestInternal};
 use collection::operations::CollectionUpdateOperations;
+use collection::shards::channel_service::ChannelService;
+use collection::shards::local_shard::segment_load::{QuarantinedSegment, SegmentLoadConfig};
+use common::cpu::CpuBudget;
+use io::storage_version::VERSION_FILE;
 use itertools::Itertools;
 use segment::data_types::vectors::BatchVectorStructInternal;
 use segment::json_path::JsonPath;
@@ -11,7 +20,10 @@ use segment::types::{PayloadContainer, PayloadSelectorExclude, WithPayloadInterf
 use serde_json::Value;
 use tempfile::Builder;
 
-use crate::common::{load_local_collection, simple_collection_fixture, N_SHARDS};
+use crate::common::{
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer,
+    load_local_collection, simple_collection_fixture, N_SHARDS, REST_PORT,
+};
 
 #[tokio::test(flavor = "multi_thread")]
 async fn test_collection_reloading() {
@@ -261,3 +273,87 @@ async fn test_collection_payload_custom_payload_with_shards(shard_number: u32) {
         _ => panic!("unexpected type"),
     };
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_reloading_with_quarantined_segment() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    {
+        let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+        let insert_points = CollectionUpdateOperations::PointOperation(
+            PointOperations::UpsertPoints(PointInsertOperationsInternal::PointsBatch(Batch {
+                ids: vec![0, 1].into_iter().map(|x| x.into()).collect_vec(),
+                vectors: BatchVectorStructInternal::from(vec![
+                    vec![1.0, 0.0, 1.0, 1.0],
+                    vec![1.0, 0.0, 1.0, 0.0],
+                ])
+                .into(),
+                payloads: None,
+            })),
+        );
+        collection
+            .update_from_client_simple(insert_points, true, WriteOrdering::default())
+            .await
+            .unwrap();
+    }
+
+    let collection_path = collection_dir.path();
+    let shard_path = collection_path.join("0");
+
+    // Break one of the segments of the shard
+    let segment_path = fs::read_dir(shard_path.join("segments"))
+        .unwrap()
+        .next()
+        .unwrap()
+        .unwrap()
+        .path();
+    fs::write(segment_path.join(VERSION_FILE), "not a version").unwrap();
+
+    let shared_storage_config = SharedStorageConfig {
+        segment_load: Some(SegmentLoadConfig {
+            retries: 1,
+            retry_delay_ms: 1,
+            quarantine: true,
+        }),
+        ..Default::default()
+    };
+    let collection = Collection::load(
+        "test".to_string(),
+        0,
+        collection_path,
+        &collection_path.join("snapshots"),
+        Arc::new(shared_storage_config),
+        ChannelService::new(REST_PORT, None),
+        dummy_on_replica_failure(),
+        dummy_request_shard_transfer(),
+        dummy_abort_shard_transfer(),
+        None,
+        None,
+        CpuBudget::default(),
+        None,
+    )
+    .await;
+
+    // The broken segment is moved out of the shard, which is loaded without it
+    assert!(!segment_path.exists());
+    let quarantined_path =
+        QuarantinedSegment::quarantine_path(&shard_path).join(segment_path.file_name().unwrap());
+    assert!(quarantined_path.join(VERSION_FILE).exists());
+
+    let health = collection.health().await;
+    assert_eq!(health.status, HealthStatus::Red);
+    let local_health = health.shards[0].local.as_ref().unwrap();
+    let load_error = local_health.load_error.as_ref().unwrap();
+    assert!(load_error.contains(&quarantined_path.display().to_string()));
+
+    // The only replica of the shard is missing points, it must not serve reads
+    let count_request = CountRequestInternal {
+        filter: None,
+        exact: true,
+        consistent: false,
+        allow_partial: false,
+    };
+    let count_res = collection
+        .count(count_request, None, &ShardSelectorInternal::All, None)
+        .await;
+    assert!(count_res.is_err());
+}
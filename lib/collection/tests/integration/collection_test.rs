@@ -1,23 +1,34 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::File;
+use std::sync::Arc;
 
-use api::rest::{OrderByInterface, SearchRequestInternal};
+use api::rest::{OrderByInterface, RerankParams, SearchRequestInternal};
+use cancel::CancellationToken;
+use collection::collection::Collection;
+use collection::operations::config_diff::OptimizersConfigDiff;
 use collection::operations::payload_ops::{PayloadOps, SetPayloadOp};
 use collection::operations::point_ops::{Batch, PointOperations, PointStruct, WriteOrdering};
+use collection::operations::query_enum::QueryEnum;
+use collection::operations::rerank::{register_reranker, Reranker};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CountRequestInternal, PointRequestInternal, RecommendRequestInternal, ScrollRequestInternal,
-    UpdateStatus,
+    CollectionError, CollectionResult, CoreSearchRequestBatch, CountRequestInternal, HealthStatus,
+    PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
+    UpdateStatus, WarmupRequest, WarmupResult,
 };
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
 use collection::shards::replica_set::{ReplicaSetState, ReplicaState};
+use common::types::ScoreType;
 use itertools::Itertools;
 use segment::data_types::order_by::{Direction, OrderBy};
+use segment::data_types::score_modifier::ScoreModifier;
 use segment::data_types::vectors::{BatchVectorStructInternal, VectorStructInternal};
+use segment::segment::SEGMENT_STATE_FILE;
 use segment::types::{
     Condition, ExtendedPointId, FieldCondition, Filter, HasIdCondition, Payload,
-    PayloadFieldSchema, PayloadSchemaType, PointIdType, WithPayloadInterface,
+    PayloadFieldSchema, PayloadSchemaType, PointIdType, ScoredPoint, SegmentState,
+    WithPayloadInterface,
 };
 use serde_json::Map;
 use tempfile::Builder;
@@ -74,6 +85,10 @@ async fn test_collection_updater_with_shards(shard_number: u32) {
         limit: 3,
         offset: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let search_res = collection
@@ -142,6 +157,10 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         limit: 3,
         offset: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let search_res = collection
@@ -175,6 +194,8 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
             ),
         ))),
         exact: true,
+        consistent: false,
+        allow_partial: false,
     };
 
     let count_res = collection
@@ -182,6 +203,242 @@ async fn test_collection_search_with_payload_and_vector_with_shards(shard_number
         .await
         .unwrap();
     assert_eq!(count_res.count, 1);
+    assert!(count_res.exact);
+
+    // Without a filter, the count is exact even if an estimation is requested
+    let count_request = CountRequestInternal {
+        filter: None,
+        exact: false,
+        consistent: false,
+        allow_partial: false,
+    };
+
+    let count_res = collection
+        .count(count_request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(count_res.count, 2);
+    assert!(count_res.exact);
+
+    // Consistent count matches the plain one without concurrent updates
+    let count_request = CountRequestInternal {
+        filter: Some(Filter::new_must(Condition::Field(
+            FieldCondition::new_match(
+                "k".parse().unwrap(),
+                serde_json::from_str(r#"{ "value": "v2" }"#).unwrap(),
+            ),
+        ))),
+        exact: true,
+        consistent: true,
+        allow_partial: true,
+    };
+
+    let count_res = collection
+        .count(count_request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(count_res.count, 1);
+    assert!(count_res.exact);
+    assert!(!count_res.partial);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_with_score_modifier() {
+    test_collection_search_with_score_modifier_with_shards(1).await;
+    test_collection_search_with_score_modifier_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_with_score_modifier_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    // Vector similarity decreases with the id, popular points are the least similar ones
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10).map(|x: u64| x.into()).collect_vec(),
+            vectors: BatchVectorStructInternal::from(
+                (0..10)
+                    .map(|x| vec![1.0 - x as f32 * 0.05, 0.0, 0.0, 0.0])
+                    .collect_vec(),
+            )
+            .into(),
+            payloads: Some(
+                (0..10)
+                    .map(|x| {
+                        (x >= 5).then(|| Payload::from(serde_json::json!({ "popularity": x })))
+                    })
+                    .collect_vec(),
+            ),
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search = |offset: Option<usize>, limit: usize, oversampling: Option<f64>| {
+        let search_request = SearchRequestInternal {
+            vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit,
+            offset,
+            score_threshold: None,
+            score_modifier: Some(ScoreModifier {
+                expression: "score * (1 + popularity)".parse().unwrap(),
+                default_multiplier: None,
+                oversampling,
+            }),
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
+        };
+
+        collection.search(
+            search_request.into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    // Modified order is 9, 8, 7, 6, 5, 0, 1, 2, 3, 4
+    let result = search(None, 3, None).await.unwrap();
+    assert_eq!(
+        result.iter().map(|point| point.id).collect_vec(),
+        vec![9.into(), 8.into(), 7.into()],
+    );
+    assert!((result[0].score - 5.5).abs() < 1e-5, "{result:?}");
+
+    let result = search(Some(2), 5, None).await.unwrap();
+    assert_eq!(
+        result.iter().map(|point| point.id).collect_vec(),
+        vec![7.into(), 6.into(), 5.into(), 0.into(), 1.into()],
+    );
+    // Points without the field keep their original score
+    assert!((result[3].score - 1.0).abs() < 1e-5, "{result:?}");
+    assert!((result[4].score - 0.95).abs() < 1e-5, "{result:?}");
+
+    // Without oversampling, only the most similar points are rescored
+    let result = search(None, 3, Some(1.0)).await.unwrap();
+    assert_eq!(
+        result.iter().map(|point| point.id).collect_vec(),
+        vec![0.into(), 1.into(), 2.into()],
+    );
+}
+
+/// Scores candidates by their `popularity` payload field
+struct PopularityReranker;
+
+impl Reranker for PopularityReranker {
+    fn rerank(
+        &self,
+        _query: &QueryEnum,
+        candidates: &[ScoredPoint],
+    ) -> CollectionResult<Vec<ScoreType>> {
+        Ok(candidates
+            .iter()
+            .map(|point| {
+                point
+                    .payload
+                    .as_ref()
+                    .and_then(|payload| payload.0.get("popularity"))
+                    .and_then(|value| value.as_f64())
+                    .unwrap_or_default() as ScoreType
+            })
+            .collect())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_search_with_rerank() {
+    test_collection_search_with_rerank_with_shards(1).await;
+    test_collection_search_with_rerank_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_search_with_rerank_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+    register_reranker("popularity", Arc::new(PopularityReranker));
+
+    // The least similar points are the most popular ones
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..10).map(|x: u64| x.into()).collect_vec(),
+            vectors: BatchVectorStructInternal::from(
+                (0..10)
+                    .map(|x| vec![1.0, x as f32 / 10.0, 0.0, 0.0])
+                    .collect_vec(),
+            )
+            .into(),
+            payloads: Some(
+                (0..10)
+                    .map(|x| Some(Payload::from(serde_json::json!({ "popularity": x }))))
+                    .collect_vec(),
+            ),
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search = |name: &str, candidates: Option<usize>| {
+        let search_request = SearchRequestInternal {
+            vector: vec![1.0, 0.0, 0.0, 0.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit: 2,
+            offset: None,
+            score_threshold: None,
+            score_modifier: None,
+            rerank: Some(RerankParams {
+                name: name.to_string(),
+                candidates,
+            }),
+            max_score_threshold: None,
+            with_distance: None,
+        };
+
+        collection.search(
+            search_request.into(),
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    // Only the candidates are reranked, so the best ones are the least similar of them
+    let result = search("popularity", Some(4)).await.unwrap();
+    assert_eq!(
+        result.iter().map(|point| point.id).collect_vec(),
+        vec![3.into(), 2.into()],
+    );
+    assert_eq!(result[0].score, 3.0);
+    // Payload was only retrieved for the reranker
+    assert!(result.iter().all(|point| point.payload.is_none()));
+
+    let result = search("popularity", None).await.unwrap();
+    assert_eq!(
+        result.iter().map(|point| point.id).collect_vec(),
+        vec![7.into(), 6.into()],
+    );
+
+    let result = search("popularity", Some(2)).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    let result = search("missing", None).await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
 }
 
 // FIXME: does not work
@@ -247,6 +504,7 @@ async fn test_collection_loading_with_shards(shard_number: u32) {
         ids: vec![1.into(), 2.into()],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        with_version: false,
     };
     let retrieved = loaded_collection
         .retrieve(request, None, &ShardSelectorInternal::All, None)
@@ -296,11 +554,13 @@ fn test_deserialization2() {
                 id: 0.into(),
                 vector: VectorStructInternal::from(vec![1.0, 0.0, 1.0, 1.0]).into(),
                 payload: None,
+                vector_metadata: None,
             },
             PointStruct {
                 id: 1.into(),
                 vector: VectorStructInternal::from(vec![1.0, 0.0, 1.0, 0.0]).into(),
                 payload: None,
+                vector_metadata: None,
             },
         ]
         .into(),
@@ -372,6 +632,28 @@ async fn test_recommendation_api_with_shards(shard_number: u32) {
     let top1 = &result[0];
 
     assert!(top1.id == 5.into() || top1.id == 6.into());
+
+    // Raw vectors and point ids can be mixed as examples
+    let result = recommend_by(
+        RecommendRequestInternal {
+            positive: vec![RecommendExample::Dense(vec![0.0, 0.0, 2.0, 0.0]), 5.into()],
+            negative: vec![8.into()],
+            limit: 5,
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("Should not be called in this test") },
+        None,
+        ShardSelectorInternal::All,
+        None,
+    )
+    .await
+    .unwrap();
+    assert!(!result.is_empty());
+    assert!(result
+        .iter()
+        .all(|hit| hit.id != 5.into() && hit.id != 8.into()));
+    assert_eq!(result[0].id, 6.into());
 }
 
 #[tokio::test(flavor = "multi_thread")]
@@ -845,3 +1127,476 @@ async fn test_collection_local_load_initializing_not_stuck() {
         }
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_warmup() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    // In-memory collection has nothing to page in
+    let result = collection.warmup(WarmupRequest::default()).await.unwrap();
+    assert_eq!(result, WarmupResult::default());
+
+    let result = collection
+        .warmup(WarmupRequest {
+            shard_ids: Some(vec![0]),
+            with_quantization: true,
+            max_bytes: Some(0),
+        })
+        .await
+        .unwrap();
+    assert_eq!(result.warmed_bytes, 0);
+
+    let result = collection
+        .warmup(WarmupRequest {
+            shard_ids: Some(vec![N_SHARDS + 1]),
+            ..Default::default()
+        })
+        .await;
+    assert!(matches!(result, Err(CollectionError::NotFound { .. })));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_health() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let health = collection.health().await;
+    assert_eq!(health.status, HealthStatus::Green);
+    assert!(health.issues.is_empty());
+    assert_eq!(health.shards.len(), N_SHARDS as usize);
+
+    for shard in &health.shards {
+        assert_eq!(shard.status, HealthStatus::Green);
+        let local = shard.local.as_ref().unwrap();
+        assert_eq!(local.wal_lag, 0);
+        assert!(local.load_error.is_none());
+        assert!(local.optimizer_error.is_none());
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_list_segments() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let segments = collection.list_segments().await;
+
+    // Every local shard has segments, segment ids are unique within a shard
+    let shard_ids: HashSet<_> = segments.iter().map(|segment| segment.shard_id).collect();
+    assert_eq!(shard_ids.len(), N_SHARDS as usize);
+    let segment_ids: HashSet<_> = segments
+        .iter()
+        .map(|segment| (segment.shard_id, segment.segment_id))
+        .collect();
+    assert_eq!(segment_ids.len(), segments.len());
+
+    let num_points: usize = segments.iter().map(|segment| segment.info.num_points).sum();
+    assert_eq!(num_points, 5);
+
+    for segment in &segments {
+        assert!(!segment.optimizing);
+        assert!(!segment.vectors_on_disk);
+        assert_eq!(segment.deleted_ratio, 0.0);
+        assert_eq!(segment.config.vector_data.len(), 1);
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_estimate_memory() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: (0..100).map(|x| x.into()).collect_vec(),
+            vectors: BatchVectorStructInternal::from(
+                (0..100).map(|x| vec![x as f32; 4]).collect_vec(),
+            )
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+    collection.flush().await.unwrap();
+
+    let estimation = Collection::estimate_memory(collection_dir.path()).unwrap();
+    assert_eq!(estimation.shards.len(), N_SHARDS as usize);
+    for (shard_id, shard) in estimation.shards.iter().enumerate() {
+        assert_eq!(shard.shard_id, shard_id as u32);
+        assert!(shard.segments_count > 0);
+    }
+
+    // Vectors of the fixture are stored in memory, in RocksDB
+    assert!(estimation.components.database.ram_bytes > 0);
+    assert_eq!(estimation.components.vectors.disk_bytes, 0);
+    assert_eq!(
+        estimation.total.ram_bytes,
+        estimation
+            .shards
+            .iter()
+            .map(|shard| shard.components.total().ram_bytes)
+            .sum::<u64>(),
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_flush() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: vec![4.into()],
+    });
+    let last_operation_id = collection
+        .update_from_client_simple(delete_points, true, WriteOrdering::default())
+        .await
+        .unwrap()
+        .operation_id
+        .unwrap();
+
+    collection.flush().await.unwrap();
+
+    // Segments are persisted up to the last operation
+    let segments_path = collection_dir.path().join("0").join("segments");
+    let persisted_version = std::fs::read_dir(segments_path)
+        .unwrap()
+        .map(|entry| entry.unwrap().path().join(SEGMENT_STATE_FILE))
+        .filter(|state_path| state_path.exists())
+        .map(|state_path| {
+            let state: SegmentState =
+                serde_json::from_reader(File::open(state_path).unwrap()).unwrap();
+            state.version.unwrap_or_default()
+        })
+        .max();
+    assert_eq!(persisted_version, Some(last_operation_id));
+
+    // Copy files of the running collection, as an external snapshot would
+    let copy_dir = Builder::new().prefix("collection_copy").tempdir().unwrap();
+    fs_extra::dir::copy(
+        collection_dir.path(),
+        copy_dir.path(),
+        &fs_extra::dir::CopyOptions::new().content_only(true),
+    )
+    .unwrap();
+
+    let loaded_collection = load_local_collection(
+        "test".to_string(),
+        copy_dir.path(),
+        &copy_dir.path().join("snapshots"),
+    )
+    .await;
+    let request = PointRequestInternal {
+        ids: (0..5).map(|x: u64| x.into()).collect(),
+        with_payload: None,
+        with_vector: false.into(),
+        with_version: false,
+    };
+    let retrieved = loaded_collection
+        .retrieve(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap();
+    assert_eq!(retrieved.len(), 4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_shard_optimizer_config() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let diff =
+        |diff: serde_json::Value| -> OptimizersConfigDiff { serde_json::from_value(diff).unwrap() };
+
+    let result = collection
+        .update_shard_optimizer_params_from_diff(
+            [(N_SHARDS, diff(serde_json::json!({"indexing_threshold": 0})))].into(),
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::NotFound { .. })));
+
+    // Overrides must result in a valid optimizer config
+    let result = collection
+        .update_shard_optimizer_params_from_diff(
+            [
+                (0, diff(serde_json::json!({"indexing_threshold": 0}))),
+                (1, diff(serde_json::json!({"deleted_threshold": 2.0}))),
+            ]
+            .into(),
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    let config = collection
+        .info(&ShardSelectorInternal::All)
+        .await
+        .unwrap()
+        .config;
+    assert!(config.shard_optimizer_config.is_empty());
+
+    collection
+        .update_shard_optimizer_params_from_diff(
+            [(0, diff(serde_json::json!({"indexing_threshold": 0})))].into(),
+        )
+        .await
+        .unwrap();
+    collection.recreate_optimizers_blocking().await.unwrap();
+
+    let config = collection
+        .info(&ShardSelectorInternal::All)
+        .await
+        .unwrap()
+        .config;
+    let shard_config = config
+        .optimizer_config_for_shard(0, &config.optimizer_config)
+        .unwrap();
+    assert_eq!(shard_config.indexing_threshold, Some(0));
+    assert_eq!(
+        shard_config.deleted_threshold,
+        config.optimizer_config.deleted_threshold,
+    );
+    assert_eq!(
+        config
+            .optimizer_config_for_shard(1, &config.optimizer_config)
+            .unwrap(),
+        config.optimizer_config,
+    );
+
+    // Overrides are persisted
+    drop(collection);
+    let collection = load_local_collection(
+        "test".to_string(),
+        collection_dir.path(),
+        &collection_dir.path().join("snapshots"),
+    )
+    .await;
+    let config = collection
+        .info(&ShardSelectorInternal::All)
+        .await
+        .unwrap()
+        .config;
+    assert_eq!(config.shard_optimizer_config.len(), 1);
+
+    // Empty override is removed
+    collection
+        .update_shard_optimizer_params_from_diff([(0, diff(serde_json::json!({})))].into())
+        .await
+        .unwrap();
+    let config = collection
+        .info(&ShardSelectorInternal::All)
+        .await
+        .unwrap()
+        .config;
+    assert!(config.shard_optimizer_config.is_empty());
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_oversized_local_shard() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0, 1, 2, 3, 4]
+                .into_iter()
+                .map(|x| x.into())
+                .collect_vec(),
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+                vec![1.0, 1.0, 1.0, 1.0],
+                vec![1.0, 1.0, 0.0, 1.0],
+                vec![1.0, 0.0, 0.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // Only shards with more points than the limit are split
+    assert_eq!(collection.oversized_local_shard(4).await, Some((0, None)));
+    assert_eq!(collection.oversized_local_shard(5).await, None);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_cancelled_search() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), N_SHARDS).await;
+
+    let insert_points = CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids: vec![0.into(), 1.into()],
+            vectors: BatchVectorStructInternal::from(vec![
+                vec![1.0, 0.0, 1.0, 1.0],
+                vec![1.0, 0.0, 1.0, 0.0],
+            ])
+            .into(),
+            payloads: None,
+        }
+        .into(),
+    );
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search = |cancel| {
+        let search_request = SearchRequestInternal {
+            vector: vec![1.0, 1.0, 1.0, 1.0].into(),
+            with_payload: None,
+            with_vector: None,
+            filter: None,
+            params: None,
+            limit: 2,
+            offset: None,
+            score_threshold: None,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
+        };
+        collection.core_search_batch(
+            CoreSearchRequestBatch {
+                searches: vec![search_request.into()],
+            },
+            None,
+            ShardSelectorInternal::All,
+            None,
+            cancel,
+        )
+    };
+
+    // A search cancelled before it completes, e.g. by a client disconnect, returns no results
+    let cancel = CancellationToken::new();
+    cancel.cancel();
+    let result = search(cancel).await;
+    assert!(
+        matches!(result, Err(CollectionError::Cancelled { .. })),
+        "{result:?}",
+    );
+
+    let result = search(CancellationToken::new()).await.unwrap();
+    assert_eq!(result[0].len(), 2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_skip_invalid_points() {
+    let collection_dir = Builder::new().prefix("collection").tempdir().unwrap();
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let insert_points = || {
+        CollectionUpdateOperations::PointOperation(
+            Batch {
+                ids: (0..3).map(|x: u64| x.into()).collect_vec(),
+                vectors: BatchVectorStructInternal::from(vec![
+                    vec![1.0, 0.0, 0.0, 0.0],
+                    vec![1.0, 0.0, 0.0],
+                    vec![0.0, 1.0, 0.0, 0.0],
+                ])
+                .into(),
+                payloads: None,
+            }
+            .into(),
+        )
+    };
+
+    // An invalid point fails the whole update by default
+    let result = collection
+        .update_from_client_idempotent(
+            insert_points(),
+            None,
+            false,
+            true,
+            WriteOrdering::default(),
+            None,
+            None,
+        )
+        .await;
+    assert!(matches!(result, Err(CollectionError::BadInput { .. })));
+
+    // Unless the request asks to skip invalid points
+    let result = collection
+        .update_from_client_idempotent(
+            insert_points(),
+            None,
+            true,
+            true,
+            WriteOrdering::default(),
+            None,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(result.invalid_points.len(), 1);
+    assert_eq!(result.invalid_points[0].id, 1.into());
+
+    let count = collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+                consistent: false,
+                allow_partial: false,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count.count, 2);
+}
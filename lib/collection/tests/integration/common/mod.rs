@@ -23,13 +23,20 @@ pub const REST_PORT: u16 = 6333;
 
 pub const TEST_OPTIMIZERS_CONFIG: OptimizersConfig = OptimizersConfig {
     deleted_threshold: 0.9,
+    payload_index_deleted_threshold: None,
     vacuum_min_vector_number: 1000,
     default_segment_number: 2,
     max_segment_size: None,
     memmap_threshold: None,
     indexing_threshold: Some(50_000),
     flush_interval_sec: 30,
+    min_segment_size: None,
+    max_flush_delay_sec: None,
     max_optimization_threads: Some(2),
+    deduplicate_vectors: None,
+    deduplication_epsilon: None,
+    defer_indexing: None,
+    split_oversized_segments: None,
 };
 
 #[cfg(test)]
@@ -52,6 +59,8 @@ pub async fn simple_collection_fixture(collection_path: &Path, shard_number: u32
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let snapshot_path = collection_path.join("snapshots");
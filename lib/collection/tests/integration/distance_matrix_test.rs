@@ -25,6 +25,7 @@ async fn distance_matrix_empty() {
         limit_per_sample,
         filter: None,
         using: "".to_string(), // default vector name
+        seed: None,
     };
     let matrix = collection
         .search_points_matrix(request, ShardSelectorInternal::All, None, None)
@@ -36,6 +37,55 @@ async fn distance_matrix_empty() {
     assert!(matrix.nearests.is_empty());
 }
 
+#[tokio::test(flavor = "multi_thread")]
+async fn distance_matrix_sample_too_large() {
+    let collection_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+
+    let point_count = 20;
+    let ids = (0..point_count).map_into().collect();
+    let mut rng = SmallRng::seed_from_u64(SEED);
+
+    let vectors = (0..point_count)
+        .map(|_| rng.gen::<[f32; 4]>().to_vec())
+        .collect_vec();
+
+    let upsert_points = collection::operations::CollectionUpdateOperations::PointOperation(
+        Batch {
+            ids,
+            vectors: BatchVectorStructInternal::from(vectors).into(),
+            payloads: None,
+        }
+        .into(),
+    );
+
+    collection
+        .update_from_client_simple(upsert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    // oversized samples are reduced instead of rejected
+    let limit_per_sample = 5;
+    let request = CollectionSearchMatrixRequest {
+        sample_size: CollectionSearchMatrixRequest::MAX_SAMPLE + 1,
+        limit_per_sample,
+        filter: None,
+        using: "".to_string(), // default vector name
+        seed: None,
+    };
+    let matrix = collection
+        .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+        .await
+        .unwrap();
+
+    assert_eq!(matrix.sample_ids.len(), point_count as usize);
+    assert_eq!(matrix.nearests.len(), point_count as usize);
+    for nearest in matrix.nearests {
+        assert_eq!(nearest.len(), limit_per_sample);
+    }
+}
+
 #[tokio::test(flavor = "multi_thread")]
 async fn distance_matrix_anonymous_vector() {
     let collection_dir = Builder::new().prefix("storage").tempdir().unwrap();
@@ -71,6 +121,7 @@ async fn distance_matrix_anonymous_vector() {
         limit_per_sample,
         filter: None,
         using: "".to_string(), // default vector name
+        seed: None,
     };
     let matrix = collection
         .search_points_matrix(request, ShardSelectorInternal::All, None, None)
@@ -96,4 +147,23 @@ async fn distance_matrix_anonymous_vector() {
             assert!(prev.score >= next.score);
         });
     }
+
+    // the same seed gives the same sample
+    let mut seeded_samples = Vec::new();
+    for _ in 0..2 {
+        let request = CollectionSearchMatrixRequest {
+            sample_size,
+            limit_per_sample,
+            filter: None,
+            using: "".to_string(), // default vector name
+            seed: Some(SEED),
+        };
+        let matrix = collection
+            .search_points_matrix(request, ShardSelectorInternal::All, None, None)
+            .await
+            .unwrap();
+        assert_eq!(matrix.sample_ids.len(), sample_size);
+        seeded_samples.push(matrix.sample_ids);
+    }
+    assert_eq!(seeded_samples[0], seeded_samples[1]);
 }
@@ -42,6 +42,10 @@ mod group_by {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
         });
 
         let request = GroupRequest::with_limit_from_request(source, JsonPath::new("docId"), 3);
@@ -142,6 +146,7 @@ mod group_by {
                 negative: Vec::new(),
                 using: None,
                 lookup_from: None,
+                mmr_lambda: None,
             }),
             JsonPath::new("docId"),
             2,
@@ -202,6 +207,10 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             3,
@@ -234,6 +243,10 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             3,
@@ -272,6 +285,10 @@ mod group_by {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("other_stuff"),
             3,
@@ -308,6 +325,10 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             0,
@@ -340,6 +361,10 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             3,
@@ -372,6 +397,10 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             3,
@@ -408,6 +437,10 @@ mod group_by {
                 with_payload: None,
                 with_vector: None,
                 score_threshold: None,
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             JsonPath::new("docId"),
             400,
@@ -465,6 +498,10 @@ mod group_by_builder {
             with_payload: None,
             with_vector: None,
             score_threshold: None,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
         });
 
         let request =
@@ -6,4 +6,5 @@ mod grouping_test;
 mod lookup_test;
 mod multi_vec_test;
 mod pagination_test;
+mod point_dump_test;
 mod snapshot_recovery_test;
@@ -12,12 +12,13 @@ use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CollectionError, PointRequestInternal, RecommendRequestInternal, VectorsConfig,
 };
+use collection::operations::vector_ops::{PointVectors, UpdateVectorsOp, VectorOperations};
 use collection::operations::vector_params_builder::VectorParamsBuilder;
 use collection::operations::CollectionUpdateOperations;
 use collection::recommendations::recommend_by;
 use segment::data_types::named_vectors::NamedVectors;
 use segment::data_types::vectors::{NamedVector, VectorStructInternal};
-use segment::types::{Distance, WithPayloadInterface, WithVector};
+use segment::types::{Distance, ExtendedPointId, VectorMetadata, WithPayloadInterface, WithVector};
 use tempfile::Builder;
 
 use crate::common::{new_local_collection, N_SHARDS, TEST_OPTIMIZERS_CONFIG};
@@ -59,6 +60,8 @@ pub async fn multi_vec_collection_fixture(collection_path: &Path, shard_number:
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let snapshot_path = collection_path.join("snapshots");
@@ -93,6 +96,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStructInternal::from(vectors).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            vector_metadata: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -118,6 +122,10 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let result = collection
@@ -152,6 +160,10 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let result = collection
@@ -181,6 +193,10 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
         with_vector: Some(true.into()),
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let result = collection
@@ -210,6 +226,7 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
                 ids: vec![6.into()],
                 with_payload: Some(WithPayloadInterface::Bool(false)),
                 with_vector: WithVector::Selector(vec![VEC_NAME1.to_string()]),
+                with_version: false,
             },
             None,
             &ShardSelectorInternal::All,
@@ -282,4 +299,365 @@ async fn test_multi_vec_with_shards(shard_number: u32) {
             }
         }
     }
+
+    // Point which only has a vector for another name can't be used as example
+    let mut vectors = NamedVectors::default();
+    vectors.insert(VEC_NAME1.to_string(), vec![1.0, 0.0, 0.0, 0.0].into());
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: 1000.into(),
+            vector: VectorStructInternal::from(vectors).into(),
+            payload: None,
+            vector_metadata: None,
+        }]),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let recommend_result = recommend_by(
+        RecommendRequestInternal {
+            positive: vec![6.into(), 1000.into()],
+            limit: 10,
+            using: Some(VEC_NAME2.to_string().into()),
+            ..Default::default()
+        },
+        &collection,
+        |_name| async { unreachable!("should not be called in this test") },
+        None,
+        ShardSelectorInternal::All,
+        None,
+    )
+    .await;
+
+    match recommend_result {
+        Err(CollectionError::BadInput { description }) => {
+            assert!(description.contains(VEC_NAME2), "{description}");
+        }
+        Err(error) => panic!("Unexpected error {error}"),
+        Ok(_) => panic!("Error expected"),
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_vec_payloads_and_delete() {
+    test_multi_vec_payloads_and_delete_with_shards(1).await;
+    test_multi_vec_payloads_and_delete_with_shards(N_SHARDS).await;
+}
+
+async fn test_multi_vec_payloads_and_delete_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new()
+        .prefix("test_multi_vec_payloads_and_delete")
+        .tempdir()
+        .unwrap();
+
+    let collection = multi_vec_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let points = (0..100)
+        .map(|i| {
+            let mut vectors = NamedVectors::default();
+            vectors.insert(VEC_NAME1.to_string(), vec![i as f32, 0.0, 0.0, 0.0].into());
+            vectors.insert(VEC_NAME2.to_string(), vec![0.0, i as f32, 0.0, 0.0].into());
+
+            PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vectors).into(),
+                payload: Some(serde_json::json!({ "num": i }).into()),
+                vector_metadata: None,
+            }
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let search = |vector_name: &str, query_vector: Vec<f32>| {
+        let request = SearchRequestInternal {
+            vector: NamedVector {
+                name: vector_name.to_string(),
+                vector: query_vector,
+            }
+            .into(),
+            filter: None,
+            limit: 5,
+            offset: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: None,
+            params: None,
+            score_threshold: None,
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: None,
+        };
+        collection.search(request.into(), None, &ShardSelectorInternal::All, None)
+    };
+
+    // Both vectors are searched independently, but resolve to the same point and payload
+    for (vector_name, query_vector) in [
+        (VEC_NAME1, vec![1.0, 0.0, 0.0, 0.0]),
+        (VEC_NAME2, vec![0.0, 1.0, 0.0, 0.0]),
+    ] {
+        let result = search(vector_name, query_vector).await.unwrap();
+        assert_eq!(result.len(), 5);
+        assert_eq!(result[0].id, 99.into());
+        for hit in result {
+            let ExtendedPointId::NumId(id) = hit.id else {
+                panic!("expected numeric id");
+            };
+            assert_eq!(hit.payload.unwrap().0["num"], serde_json::json!(id));
+        }
+    }
+
+    // Deleting a point removes it from all named vectors at once
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: vec![99.into()],
+    });
+    collection
+        .update_from_client_simple(delete_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    for (vector_name, query_vector) in [
+        (VEC_NAME1, vec![1.0, 0.0, 0.0, 0.0]),
+        (VEC_NAME2, vec![0.0, 1.0, 0.0, 0.0]),
+    ] {
+        let result = search(vector_name, query_vector).await.unwrap();
+        assert_eq!(result[0].id, 98.into());
+        assert!(result.iter().all(|hit| hit.id != 99.into()));
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_vec_metadata() {
+    test_multi_vec_metadata_with_shards(1).await;
+    test_multi_vec_metadata_with_shards(N_SHARDS).await;
+}
+
+async fn test_multi_vec_metadata_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new()
+        .prefix("test_multi_vec_metadata")
+        .tempdir()
+        .unwrap();
+
+    let collection = multi_vec_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let points = (0..10)
+        .map(|i| {
+            let mut vectors = NamedVectors::default();
+            vectors.insert(VEC_NAME1.to_string(), vec![i as f32, 0.0, 0.0, 0.0].into());
+            vectors.insert(VEC_NAME2.to_string(), vec![0.0, i as f32, 0.0, 0.0].into());
+
+            PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vectors).into(),
+                payload: None,
+                vector_metadata: Some(VectorMetadata::from([
+                    (
+                        VEC_NAME1.to_string(),
+                        serde_json::json!({ "model": "a", "num": i }).into(),
+                    ),
+                    (
+                        VEC_NAME2.to_string(),
+                        serde_json::json!({ "model": "b", "num": i }).into(),
+                    ),
+                ])),
+            }
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let retrieve = |with_vector: WithVector| {
+        collection.retrieve(
+            PointRequestInternal {
+                ids: vec![6.into()],
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector,
+                with_version: false,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+    };
+
+    // Metadata is only returned for the selected vectors
+    let result = retrieve(WithVector::Selector(vec![VEC_NAME1.to_string()]))
+        .await
+        .unwrap();
+    let vector_metadata = result[0].vector_metadata.as_ref().unwrap();
+    assert_eq!(vector_metadata.len(), 1);
+    assert_eq!(
+        vector_metadata[VEC_NAME1].0,
+        serde_json::json!({ "model": "a", "num": 6 })
+            .as_object()
+            .unwrap()
+            .clone(),
+    );
+
+    let result = retrieve(WithVector::Bool(false)).await.unwrap();
+    assert!(result[0].vector_metadata.is_none());
+
+    // Updating a vector drops its metadata, metadata of other vectors is kept
+    let mut vectors = NamedVectors::default();
+    vectors.insert(VEC_NAME1.to_string(), vec![1.0, 1.0, 0.0, 0.0].into());
+    let update_vectors = CollectionUpdateOperations::VectorOperation(
+        VectorOperations::UpdateVectors(UpdateVectorsOp {
+            points: vec![PointVectors {
+                id: 6.into(),
+                vector: VectorStructInternal::from(vectors).into(),
+            }],
+            delete_vectors: Vec::new(),
+        }),
+    );
+    collection
+        .update_from_client_simple(update_vectors, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let result = retrieve(WithVector::Bool(true)).await.unwrap();
+    let vector_metadata = result[0].vector_metadata.as_ref().unwrap();
+    assert!(!vector_metadata.contains_key(VEC_NAME1));
+    assert!(vector_metadata.contains_key(VEC_NAME2));
+
+    // Metadata for a vector the point does not have is rejected
+    let mut vectors = NamedVectors::default();
+    vectors.insert(VEC_NAME1.to_string(), vec![1.0, 0.0, 0.0, 0.0].into());
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(vec![PointStruct {
+            id: 100.into(),
+            vector: VectorStructInternal::from(vectors).into(),
+            payload: None,
+            vector_metadata: Some(VectorMetadata::from([(
+                VEC_NAME2.to_string(),
+                serde_json::json!({ "model": "b" }).into(),
+            )])),
+        }]),
+    ));
+    let result = collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await;
+    assert!(
+        matches!(result, Err(CollectionError::BadInput { .. })),
+        "{result:?}",
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_multi_vec_add_vector() {
+    test_multi_vec_add_vector_with_shards(1).await;
+    test_multi_vec_add_vector_with_shards(N_SHARDS).await;
+}
+
+async fn test_multi_vec_add_vector_with_shards(shard_number: u32) {
+    const VEC_NAME3: &str = "vec3";
+
+    let collection_dir = Builder::new()
+        .prefix("test_multi_vec_add_vector")
+        .tempdir()
+        .unwrap();
+
+    let collection = multi_vec_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let points = (0..100)
+        .map(|i| {
+            let mut vectors = NamedVectors::default();
+            vectors.insert(VEC_NAME1.to_string(), vec![i as f32, 0.0, 0.0, 0.0].into());
+            vectors.insert(VEC_NAME2.to_string(), vec![0.0, i as f32, 0.0, 0.0].into());
+
+            PointStruct {
+                id: i.into(),
+                vector: VectorStructInternal::from(vectors).into(),
+                payload: Some(serde_json::json!({ "num": i }).into()),
+                vector_metadata: None,
+            }
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let new_vectors = BTreeMap::from([(
+        VEC_NAME3.to_string(),
+        VectorParamsBuilder::new(2, Distance::Dot).build(),
+    )]);
+    collection.add_vectors(&new_vectors).await.unwrap();
+    collection.recreate_optimizers_blocking().await.unwrap();
+
+    // Vectors can't be added twice
+    let result = collection.add_vectors(&new_vectors).await;
+    assert!(
+        matches!(result, Err(CollectionError::BadInput { .. })),
+        "{result:?}",
+    );
+
+    let search = |vector_name: &str, query_vector: Vec<f32>| {
+        let request = SearchRequestInternal {
+            vector: NamedVector {
+                name: vector_name.to_string(),
+                vector: query_vector,
+            }
+            .into(),
+            filter: None,
+            limit: 5,
+            offset: None,
+            with_payload: Some(WithPayloadInterface::Bool(true)),
+            with_vector: None,
+            params: None,
+            score_threshold: None,
+        };
+        collection.search(request.into(), None, &ShardSelectorInternal::All, None)
+    };
+
+    // Existing points have no value for the new vector
+    let result = search(VEC_NAME3, vec![1.0, 1.0]).await.unwrap();
+    assert!(result.is_empty());
+
+    for i in [7, 42] {
+        let mut vectors = NamedVectors::default();
+        vectors.insert(VEC_NAME3.to_string(), vec![i as f32, 1.0].into());
+        let update_vectors = CollectionUpdateOperations::VectorOperation(
+            VectorOperations::UpdateVectors(UpdateVectorsOp {
+                points: vec![PointVectors {
+                    id: i.into(),
+                    vector: VectorStructInternal::from(vectors).into(),
+                }],
+                delete_vectors: Vec::new(),
+            }),
+        );
+        collection
+            .update_from_client_simple(update_vectors, true, WriteOrdering::default())
+            .await
+            .unwrap();
+    }
+
+    let result = search(VEC_NAME3, vec![1.0, 1.0]).await.unwrap();
+    let ids: Vec<_> = result.iter().map(|hit| hit.id).collect();
+    assert_eq!(ids, vec![42.into(), 7.into()]);
+    for hit in result {
+        let ExtendedPointId::NumId(id) = hit.id else {
+            panic!("expected numeric id");
+        };
+        assert_eq!(hit.payload.unwrap().0["num"], serde_json::json!(id));
+    }
+
+    // Existing vectors are untouched
+    let result = search(VEC_NAME1, vec![1.0, 0.0, 0.0, 0.0]).await.unwrap();
+    assert_eq!(result.len(), 5);
+    assert_eq!(result[0].id, 99.into());
 }
@@ -1,11 +1,13 @@
-use api::rest::SearchRequestInternal;
+use api::rest::{OrderByInterface, SearchRequestInternal};
 use collection::operations::point_ops::{
     PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::ScrollRequestInternal;
 use collection::operations::CollectionUpdateOperations;
+use futures::{StreamExt as _, TryStreamExt as _};
 use segment::data_types::vectors::VectorStructInternal;
-use segment::types::WithPayloadInterface;
+use segment::types::{PointIdType, WithPayloadInterface, WithVector};
 use tempfile::Builder;
 
 use crate::common::{simple_collection_fixture, N_SHARDS};
@@ -31,6 +33,7 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
             id: i.into(),
             vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            vector_metadata: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -52,6 +55,10 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let reference_result = collection
@@ -78,6 +85,10 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let page_1_result = collection
@@ -105,6 +116,10 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         with_vector: None,
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let page_9_result = collection
@@ -123,3 +138,85 @@ async fn test_collection_paginated_search_with_shards(shard_number: u32) {
         assert_eq!(page_9_result[i], reference_result[page_size * 9 + i]);
     }
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_collection_scroll_stream() {
+    test_collection_scroll_stream_with_shards(1).await;
+    test_collection_scroll_stream_with_shards(N_SHARDS).await;
+}
+
+async fn test_collection_scroll_stream_with_shards(shard_number: u32) {
+    let collection_dir = Builder::new()
+        .prefix("test_collection_scroll_stream")
+        .tempdir()
+        .unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), shard_number).await;
+
+    let points = (0..250)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: Some(serde_json::json!({ "number": i }).into()),
+            vector_metadata: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    collection
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let request = ScrollRequestInternal {
+        offset: Some(10.into()),
+        limit: Some(64),
+        filter: None,
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(false),
+        order_by: None,
+    };
+
+    let ids: Vec<PointIdType> = collection
+        .scroll_stream(request.clone(), None, ShardSelectorInternal::All, None)
+        .map_ok(|record| record.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(ids, (10..250).map(PointIdType::from).collect::<Vec<_>>());
+
+    // Points deleted ahead of the cursor are not streamed
+    let stream = collection.scroll_stream(request.clone(), None, ShardSelectorInternal::All, None);
+    tokio::pin!(stream);
+    let first = stream.next().await.unwrap().unwrap();
+    assert_eq!(first.id, 10.into());
+
+    let delete_points = CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
+        ids: vec![200.into()],
+    });
+    collection
+        .update_from_client_simple(delete_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let rest: Vec<PointIdType> = stream
+        .map_ok(|record| record.id)
+        .try_collect()
+        .await
+        .unwrap();
+    assert_eq!(rest.len(), 238);
+    assert!(!rest.contains(&200.into()));
+
+    // Ordering by payload has no stable cursor
+    let order_by_request = ScrollRequestInternal {
+        offset: None,
+        order_by: Some(OrderByInterface::Key("number".parse().unwrap())),
+        ..request
+    };
+    let result: Result<Vec<_>, _> = collection
+        .scroll_stream(order_by_request, None, ShardSelectorInternal::All, None)
+        .try_collect()
+        .await;
+    assert!(result.is_err());
+}
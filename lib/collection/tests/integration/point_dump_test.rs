@@ -0,0 +1,79 @@
+use collection::collection::Collection;
+use collection::operations::point_ops::{
+    PointInsertOperationsInternal, PointOperations, PointStruct, WriteOrdering,
+};
+use collection::operations::shard_selector_internal::ShardSelectorInternal;
+use collection::operations::types::ScrollRequestInternal;
+use collection::operations::CollectionUpdateOperations;
+use segment::data_types::vectors::VectorStructInternal;
+use segment::types::{WithPayloadInterface, WithVector};
+use tempfile::Builder;
+
+use crate::common::{simple_collection_fixture, N_SHARDS};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_point_dump_export_import() {
+    test_point_dump_export_import_with_shards(1).await;
+    test_point_dump_export_import_with_shards(N_SHARDS).await;
+}
+
+async fn test_point_dump_export_import_with_shards(shard_number: u32) {
+    let source_dir = Builder::new().prefix("test_dump_source").tempdir().unwrap();
+    let target_dir = Builder::new().prefix("test_dump_target").tempdir().unwrap();
+
+    let source = simple_collection_fixture(source_dir.path(), shard_number).await;
+    let target = simple_collection_fixture(target_dir.path(), shard_number).await;
+
+    let points = (0..250)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 1.0, 2.0, 3.0]).into(),
+            payload: Some(serde_json::json!({ "number": i }).into()),
+            vector_metadata: None,
+        })
+        .collect();
+    let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+        PointInsertOperationsInternal::PointsList(points),
+    ));
+    source
+        .update_from_client_simple(insert_points, true, WriteOrdering::default())
+        .await
+        .unwrap();
+
+    let mut dump = Vec::new();
+    let exported = source.export_points(&mut dump, 64).await.unwrap();
+    assert_eq!(exported, 250);
+
+    let imported = target
+        .import_points(&mut dump.as_slice(), 64)
+        .await
+        .unwrap();
+    assert_eq!(imported, 250);
+
+    assert_eq!(all_points(&source).await, all_points(&target).await);
+}
+
+async fn all_points(collection: &Collection) -> Vec<PointStruct> {
+    let request = ScrollRequestInternal {
+        offset: None,
+        limit: Some(1000),
+        filter: None,
+        with_payload: Some(WithPayloadInterface::Bool(true)),
+        with_vector: WithVector::Bool(true),
+        order_by: None,
+    };
+
+    collection
+        .scroll_by(request, None, &ShardSelectorInternal::All, None)
+        .await
+        .unwrap()
+        .points
+        .into_iter()
+        .map(|record| PointStruct {
+            id: record.id,
+            vector: record.vector.unwrap(),
+            payload: record.payload,
+            vector_metadata: record.vector_metadata,
+        })
+        .collect()
+}
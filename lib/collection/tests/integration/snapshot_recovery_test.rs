@@ -1,6 +1,8 @@
+use std::path::Path;
 use std::sync::Arc;
 
 use api::rest::SearchRequestInternal;
+use cancel::CancellationToken;
 use collection::collection::Collection;
 use collection::config::{CollectionConfig, CollectionParams, WalConfig};
 use collection::operations::point_ops::{
@@ -8,19 +10,22 @@ use collection::operations::point_ops::{
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::shared_storage_config::SharedStorageConfig;
-use collection::operations::types::{NodeType, VectorsConfig};
+use collection::operations::types::{CountRequestInternal, NodeType, VectorsConfig};
 use collection::operations::vector_params_builder::VectorParamsBuilder;
 use collection::operations::CollectionUpdateOperations;
 use collection::shards::channel_service::ChannelService;
 use collection::shards::collection_shard_distribution::CollectionShardDistribution;
+use collection::shards::local_shard::LocalShard;
 use collection::shards::replica_set::ReplicaState;
+use collection::shards::shard_versioning::versioned_shard_path;
 use common::cpu::CpuBudget;
 use segment::data_types::vectors::VectorStructInternal;
 use segment::types::{Distance, WithPayloadInterface, WithVector};
 use tempfile::Builder;
 
 use crate::common::{
-    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer, REST_PORT,
+    dummy_abort_shard_transfer, dummy_on_replica_failure, dummy_request_shard_transfer,
+    load_local_collection, new_local_collection, simple_collection_fixture, REST_PORT,
     TEST_OPTIMIZERS_CONFIG,
 };
 
@@ -42,6 +47,8 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         hnsw_config: Default::default(),
         quantization_config: Default::default(),
         strict_mode_config: Default::default(),
+        ttl_config: None,
+        shard_optimizer_config: Default::default(),
     };
 
     let snapshots_path = Builder::new().prefix("test_snapshots").tempdir().unwrap();
@@ -99,6 +106,7 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
             id: i.into(),
             vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
             payload: Some(serde_json::from_str(r#"{"number": "John Doe"}"#).unwrap()),
+            vector_metadata: None,
         });
     }
     let insert_points = CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
@@ -153,6 +161,10 @@ async fn _test_snapshot_and_recover_collection(node_type: NodeType) {
         with_vector: Some(WithVector::Bool(true)),
         params: None,
         score_threshold: None,
+        score_modifier: None,
+        rerank: None,
+        max_score_threshold: None,
+        with_distance: None,
     };
 
     let reference_result = collection
@@ -193,3 +205,212 @@ async fn test_snapshot_and_recover_collection_normal() {
 async fn test_snapshot_and_recover_collection_listener() {
     _test_snapshot_and_recover_collection(NodeType::Listener).await;
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_recover_incompatible_shard_keeps_data() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+    let recover_dir = Builder::new()
+        .prefix("test_collection_rec")
+        .tempdir()
+        .unwrap();
+    let target_dir = Builder::new()
+        .prefix("test_collection_target")
+        .tempdir()
+        .unwrap();
+
+    // Snapshot of a collection with 4 dimensional vectors
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+    let points = (0..10)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+            vector_metadata: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let snapshots_temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
+    let snapshot_description = collection
+        .create_snapshot(snapshots_temp_dir.path(), 0)
+        .await
+        .unwrap();
+    Collection::restore_snapshot(
+        &collection_dir
+            .path()
+            .join("snapshots")
+            .join(snapshot_description.name),
+        recover_dir.path(),
+        0,
+        false,
+    )
+    .unwrap();
+
+    // Existing collection with 8 dimensional vectors
+    let config = CollectionConfig {
+        params: CollectionParams {
+            vectors: VectorsConfig::Single(VectorParamsBuilder::new(8, Distance::Dot).build()),
+            ..CollectionParams::empty()
+        },
+        optimizer_config: TEST_OPTIMIZERS_CONFIG.clone(),
+        wal_config: WalConfig {
+            wal_capacity_mb: 1,
+            wal_segments_ahead: 0,
+        },
+        hnsw_config: Default::default(),
+        quantization_config: Default::default(),
+        strict_mode_config: Default::default(),
+        ttl_config: None,
+    };
+    let target = new_local_collection(
+        "target".to_string(),
+        target_dir.path(),
+        &target_dir.path().join("snapshots"),
+        &config,
+    )
+    .await
+    .unwrap();
+    let points = (0..5)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![1.0; 8]).into(),
+            payload: None,
+            vector_metadata: None,
+        })
+        .collect();
+    target
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+
+    let snapshot_shard_path = versioned_shard_path(recover_dir.path(), 0, 0);
+
+    assert!(target
+        .check_local_shard_snapshot(&snapshot_shard_path)
+        .await
+        .is_err());
+
+    let result = target
+        .recover_local_shard_from(&snapshot_shard_path, 0, CancellationToken::new())
+        .await;
+    assert!(result.is_err());
+
+    // Restore is rejected before touching existing data
+    let count = target
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+                consistent: false,
+                allow_partial: false,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap();
+    assert_eq!(count.count, 5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_interrupted_restore_rolls_back_on_load() {
+    let collection_dir = Builder::new().prefix("test_collection").tempdir().unwrap();
+
+    let collection = simple_collection_fixture(collection_dir.path(), 1).await;
+    let points = (0..10)
+        .map(|i| PointStruct {
+            id: i.into(),
+            vector: VectorStructInternal::from(vec![i as f32, 0.0, 0.0, 0.0]).into(),
+            payload: None,
+        })
+        .collect();
+    collection
+        .update_from_client_simple(
+            CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(
+                PointInsertOperationsInternal::PointsList(points),
+            )),
+            true,
+            WriteOrdering::default(),
+        )
+        .await
+        .unwrap();
+    collection.flush().await.unwrap();
+
+    // Crash after the backup was complete, while moving in restored data
+    let copy_dir = Builder::new()
+        .prefix("test_collection_copy")
+        .tempdir()
+        .unwrap();
+    copy_collection(collection_dir.path(), copy_dir.path());
+    let shard_path = versioned_shard_path(copy_dir.path(), 0, 0);
+    let backup_path = shard_path.join("restore_backup");
+    std::fs::create_dir_all(&backup_path).unwrap();
+    LocalShard::move_data(&shard_path, &backup_path)
+        .await
+        .unwrap();
+    std::fs::create_dir_all(shard_path.join("wal")).unwrap();
+    assert_eq!(count_loaded_points(copy_dir.path()).await, 10);
+    assert!(!backup_path.exists());
+
+    // Crash while moving current data into the backup
+    let copy_dir = Builder::new()
+        .prefix("test_collection_copy")
+        .tempdir()
+        .unwrap();
+    copy_collection(collection_dir.path(), copy_dir.path());
+    let shard_path = versioned_shard_path(copy_dir.path(), 0, 0);
+    let backup_tmp_path = shard_path.join("restore_backup.tmp");
+    std::fs::create_dir_all(&backup_tmp_path).unwrap();
+    std::fs::rename(shard_path.join("wal"), backup_tmp_path.join("wal")).unwrap();
+    assert_eq!(count_loaded_points(copy_dir.path()).await, 10);
+    assert!(!backup_tmp_path.exists());
+}
+
+fn copy_collection(from: &Path, to: &Path) {
+    fs_extra::dir::copy(
+        from,
+        to,
+        &fs_extra::dir::CopyOptions::new().content_only(true),
+    )
+    .unwrap();
+}
+
+async fn count_loaded_points(collection_path: &Path) -> usize {
+    let collection = load_local_collection(
+        "test".to_string(),
+        collection_path,
+        &collection_path.join("snapshots"),
+    )
+    .await;
+    collection
+        .count(
+            CountRequestInternal {
+                filter: None,
+                exact: true,
+                consistent: false,
+                allow_partial: false,
+            },
+            None,
+            &ShardSelectorInternal::All,
+            None,
+        )
+        .await
+        .unwrap()
+        .count
+}
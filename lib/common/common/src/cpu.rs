@@ -1,4 +1,5 @@
 use std::cmp::Ordering;
+use std::sync::atomic::{self, AtomicBool};
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -57,11 +58,16 @@ pub fn get_cpu_budget(cpu_budget_param: isize) -> usize {
 ///
 /// Assigns CPU permits to tasks to limit overall resource utilization, making optimization
 /// workloads more predictable and efficient.
+///
+/// The budget can be paused, in which case no new permits are given out until it is resumed.
+/// Permits which are already leased out are not affected.
 #[derive(Debug, Clone)]
 pub struct CpuBudget {
     semaphore: Arc<Semaphore>,
     /// Total CPU budget, available and leased out.
     cpu_budget: usize,
+    /// Whether giving out new permits is paused, shared between all clones of this budget.
+    paused: Arc<AtomicBool>,
 }
 
 impl CpuBudget {
@@ -69,9 +75,19 @@ impl CpuBudget {
         Self {
             semaphore: Arc::new(Semaphore::new(cpu_budget)),
             cpu_budget,
+            paused: Arc::new(AtomicBool::new(false)),
         }
     }
 
+    /// Pause or resume giving out new permits. Returns whether the budget was paused before.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        self.paused.swap(paused, atomic::Ordering::Relaxed)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(atomic::Ordering::Relaxed)
+    }
+
     /// Returns the total CPU budget.
     pub fn available_cpu_budget(&self) -> usize {
         self.cpu_budget
@@ -88,11 +104,13 @@ impl CpuBudget {
     /// - it will prefer to acquire the maximum number of CPUs
     /// - it will never be higher than the total CPU budget
     /// - it will never be lower than `min_permits(desired_cpus)`
+    ///
+    /// Returns `None` while the budget is paused.
     pub fn try_acquire(&self, desired_cpus: usize) -> Option<CpuPermit> {
         // Determine what number of CPUs to acquire based on available budget
         let min_required = self.min_permits(desired_cpus) as u32;
         let num_cpus = self.semaphore.available_permits().min(desired_cpus) as u32;
-        if desired_cpus == 0 || num_cpus < min_required {
+        if desired_cpus == 0 || num_cpus < min_required || self.is_paused() {
             return None;
         }
 
@@ -119,9 +137,10 @@ impl CpuBudget {
 
     /// Check if there are at least `budget` available CPUs in this budget.
     ///
-    /// A budget of `0` will always return `true`.
+    /// A budget of `0` will always return `true`. Otherwise returns `false` while the budget is
+    /// paused.
     pub fn has_budget_exact(&self, budget: usize) -> bool {
-        self.semaphore.available_permits() >= budget
+        budget == 0 || (!self.is_paused() && self.semaphore.available_permits() >= budget)
     }
 
     /// Notify when we have CPU budget available for the given number of desired CPUs.
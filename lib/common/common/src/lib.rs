@@ -6,6 +6,7 @@ pub mod iterator_ext;
 pub mod math;
 pub mod mmap_hashmap;
 pub mod panic;
+pub mod random;
 pub mod tar_ext;
 pub mod top_k;
 pub mod types;
@@ -0,0 +1,36 @@
+use rand::rngs::StdRng;
+use rand::{Rng as _, SeedableRng as _};
+
+/// Random number generator of an operation, seeded with `seed` if given, for reproducible results
+pub fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Seeds for `N` parts of an operation which may run in any order, derived from `seed`
+pub fn split_seed<const N: usize>(seed: Option<u64>) -> [Option<u64>; N] {
+    let mut rng = seed.map(StdRng::seed_from_u64);
+    std::array::from_fn(|_| rng.as_mut().map(|rng| rng.gen()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seeded_rng() {
+        let sample = |seed| -> Vec<u32> {
+            let mut rng = seeded_rng(seed);
+            (0..10).map(|_| rand::Rng::gen(&mut rng)).collect()
+        };
+        assert_eq!(sample(Some(42)), sample(Some(42)));
+        assert_ne!(sample(Some(42)), sample(Some(43)));
+
+        let seeds: [_; 3] = split_seed(Some(42));
+        assert_eq!(seeds, split_seed(Some(42)));
+        assert_ne!(seeds[0], seeds[1]);
+        assert_eq!(split_seed::<2>(None), [None, None]);
+    }
+}
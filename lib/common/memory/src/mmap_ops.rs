@@ -107,6 +107,11 @@ impl PrefaultMmapPages {
     pub fn exec(&self) {
         prefault_mmap_pages(self.mmap.as_ref(), self.path.as_deref());
     }
+
+    /// Number of bytes that would be read by `exec`
+    pub fn size_bytes(&self) -> usize {
+        self.mmap.len()
+    }
 }
 
 fn prefault_mmap_pages<T>(mmap: &T, path: Option<&Path>)
@@ -192,3 +197,7 @@ pub fn transmute_from_u8_to_mut_slice<T>(data: &mut [u8]) -> &mut [T] {
 pub fn transmute_to_u8_slice<T>(v: &[T]) -> &[u8] {
     unsafe { std::slice::from_raw_parts(v.as_ptr().cast::<u8>(), mem::size_of_val(v)) }
 }
+
+pub fn transmute_to_u8_slice_mut<T>(v: &mut [T]) -> &mut [u8] {
+    unsafe { std::slice::from_raw_parts_mut(v.as_mut_ptr().cast::<u8>(), mem::size_of_val(v)) }
+}
@@ -52,6 +52,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(segment_dir.path(), &segment_config, true).unwrap();
@@ -67,11 +68,14 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
     // build HNSW index
     let hnsw_config = HnswConfig {
         m: 8,
+        m0: None,
         ef_construct: 16,
         full_scan_threshold: 10, // low value to trigger index usage by default
         max_indexing_threads: 0,
         on_disk: None,
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
@@ -86,6 +90,7 @@ fn multi_vector_search_benchmark(c: &mut Criterion) {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
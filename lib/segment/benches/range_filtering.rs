@@ -1,6 +1,7 @@
 #[cfg(not(target_os = "windows"))]
 mod prof;
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
@@ -63,6 +64,7 @@ fn range_filtering(c: &mut Criterion) {
     let mut index = StructPayloadIndex::open(
         payload_storage.clone(),
         id_tracker.clone(),
+        HashMap::new(),
         dir.path(),
         true,
     )
@@ -112,7 +114,14 @@ fn range_filtering(c: &mut Criterion) {
     drop(index);
 
     // reload as IMMUTABLE index
-    let index = StructPayloadIndex::open(payload_storage, id_tracker, dir.path(), false).unwrap();
+    let index = StructPayloadIndex::open(
+        payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        false,
+    )
+    .unwrap();
 
     group.bench_function("float-immutable-index", |b| {
         b.iter_batched(
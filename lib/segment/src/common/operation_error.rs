@@ -7,6 +7,8 @@ use atomicwrites::Error as AtomicIoError;
 use io::file_operations::FileStorageError;
 use memory::mmap_type::Error as MmapError;
 use rayon::ThreadPoolBuildError;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
 use crate::types::{PayloadKeyType, PointIdType, SeqNumberType};
@@ -14,6 +16,49 @@ use crate::utils::mem::Mem;
 
 pub const PROCESS_CANCELLED_BY_SERVICE_MESSAGE: &str = "process cancelled by service";
 
+/// Stable category of an error, allows clients to handle errors without matching on messages.
+///
+/// Codes are part of the API, existing ones must not be renamed or repurposed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    /// Requested entity, such as a point or collection, does not exist
+    NotFound,
+    /// Request is malformed or not applicable to the data it targets
+    InvalidArgument,
+    /// Entity to be created already exists
+    AlreadyExists,
+    /// Operation is not allowed by access rules or strict mode
+    PermissionDenied,
+    /// System is not in a state which allows the operation, it may succeed later
+    FailedPrecondition,
+    /// Operation was cancelled before completion
+    Cancelled,
+    /// Operation did not complete in time
+    DeadlineExceeded,
+    /// Not enough memory or other resources to complete the operation
+    ResourceExhausted,
+    /// Unexpected internal error, such as IO failure or data corruption
+    Internal,
+}
+
+impl ErrorCode {
+    /// Name of the code, the same as in its serialized form
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::InvalidArgument => "invalid_argument",
+            ErrorCode::AlreadyExists => "already_exists",
+            ErrorCode::PermissionDenied => "permission_denied",
+            ErrorCode::FailedPrecondition => "failed_precondition",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::DeadlineExceeded => "deadline_exceeded",
+            ErrorCode::ResourceExhausted => "resource_exhausted",
+            ErrorCode::Internal => "internal",
+        }
+    }
+}
+
 #[derive(Error, Debug, Clone)]
 #[error("{0}")]
 pub enum OperationError {
@@ -67,6 +112,27 @@ impl OperationError {
             backtrace: Some(Backtrace::force_capture().to_string()),
         }
     }
+
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            OperationError::WrongVectorDimension { .. }
+            | OperationError::VectorNameNotExists { .. }
+            | OperationError::MissedVectorName { .. }
+            | OperationError::TypeError { .. }
+            | OperationError::TypeInferenceError { .. }
+            | OperationError::ValidationError { .. }
+            | OperationError::WrongSparse
+            | OperationError::WrongMulti
+            | OperationError::MissingRangeIndexForOrderBy { .. }
+            | OperationError::MissingMapIndexForFacet { .. } => ErrorCode::InvalidArgument,
+            OperationError::PointIdError { .. } => ErrorCode::NotFound,
+            OperationError::Cancelled { .. } => ErrorCode::Cancelled,
+            OperationError::OutOfMemory { .. } => ErrorCode::ResourceExhausted,
+            OperationError::ServiceError { .. } | OperationError::InconsistentStorage { .. } => {
+                ErrorCode::Internal
+            }
+        }
+    }
 }
 
 pub fn check_process_stopped(stopped: &AtomicBool) -> OperationResult<()> {
@@ -193,3 +259,34 @@ pub fn get_service_error<T>(err: &OperationResult<T>) -> Option<OperationError>
         },
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_code() {
+        let err = OperationError::from(IoError::from(ErrorKind::OutOfMemory));
+        assert_eq!(err.code(), ErrorCode::ResourceExhausted);
+
+        let err = OperationError::from(quantization::EncodingError::Stopped);
+        assert_eq!(err.code(), ErrorCode::Cancelled);
+
+        let err = OperationError::from(IoError::from(ErrorKind::NotFound));
+        assert_eq!(err.code(), ErrorCode::Internal);
+
+        let err = OperationError::PointIdError {
+            missed_point_id: 1.into(),
+        };
+        assert_eq!(err.code(), ErrorCode::NotFound);
+
+        assert_eq!(
+            serde_json::to_value(ErrorCode::ResourceExhausted).unwrap(),
+            "resource_exhausted",
+        );
+        assert_eq!(
+            serde_json::to_value(ErrorCode::FailedPrecondition).unwrap(),
+            ErrorCode::FailedPrecondition.as_str(),
+        );
+    }
+}
@@ -2,6 +2,7 @@
 //! See https://plg.uwaterloo.ca/~gvcormac/cormacksigir09-rrf.pdf
 
 use std::collections::hash_map::Entry;
+use std::iter;
 
 use ahash::{HashMap, HashMapExt};
 use ordered_float::OrderedFloat;
@@ -23,12 +24,24 @@ fn position_score(position: usize) -> f32 {
 /// The output is a single sorted list of ScoredPoint.
 /// Does not break ties.
 pub fn rrf_scoring(responses: impl IntoIterator<Item = Vec<ScoredPoint>>) -> Vec<ScoredPoint> {
+    weighted_rrf_scoring(responses, &[])
+}
+
+/// Same as [`rrf_scoring`], but the RRF scores of each response are multiplied by its weight.
+///
+/// Responses without a weight have a weight of 1.0.
+pub fn weighted_rrf_scoring(
+    responses: impl IntoIterator<Item = Vec<ScoredPoint>>,
+    weights: &[f32],
+) -> Vec<ScoredPoint> {
     // track scored points by id
     let mut points_by_id: HashMap<ExtendedPointId, ScoredPoint> = HashMap::new();
 
-    for response in responses {
+    let weights = weights.iter().copied().chain(iter::repeat(1.0));
+
+    for (response, weight) in responses.into_iter().zip(weights) {
         for (pos, mut point) in response.into_iter().enumerate() {
-            let rrf_score = position_score(pos);
+            let rrf_score = position_score(pos) * weight;
             match points_by_id.entry(point.id) {
                 Entry::Occupied(mut entry) => {
                     // accumulate score
@@ -66,6 +79,8 @@ mod tests {
             vector: None,
             shard_key: None,
             order_value: None,
+            distance: None,
+            provenance: None,
         }
     }
 
@@ -120,4 +135,34 @@ mod tests {
         assert_eq!(scored_points[3].id, 5.into());
         assert_eq!(scored_points[3].score, 0.5);
     }
+
+    #[test]
+    fn test_weighted_rrf_scoring() {
+        let responses = vec![
+            vec![make_scored_point(1, 0.9), make_scored_point(2, 0.8)],
+            vec![make_scored_point(2, 0.7), make_scored_point(3, 0.6)],
+        ];
+
+        // Unweighted, point 2 is found by both responses
+        let scored_points = rrf_scoring(responses.clone());
+        assert_eq!(scored_points[0].id, 2.into());
+
+        // Missing weights are 1.0
+        let scored_points = weighted_rrf_scoring(responses.clone(), &[4.0]);
+        assert_eq!(scored_points.len(), 3);
+
+        assert_eq!(scored_points[0].id, 1.into());
+        assert_eq!(scored_points[0].score, 2.0); // 4 * 1 / (0 + 2)
+
+        assert_eq!(scored_points[1].id, 2.into());
+        assert_eq!(scored_points[1].score, 1.8333334); // 4 * 1 / (1 + 2) + 1 / (0 + 2)
+
+        assert_eq!(scored_points[2].id, 3.into());
+        assert_eq!(scored_points[2].score, 1.0 / 3.0);
+
+        // Responses with a zero weight don't contribute
+        let scored_points = weighted_rrf_scoring(responses, &[0.0, 1.0]);
+        assert_eq!(scored_points[0].id, 2.into());
+        assert_eq!(scored_points[0].score, 0.5);
+    }
 }
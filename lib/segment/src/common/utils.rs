@@ -1,5 +1,7 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
@@ -8,10 +10,13 @@ use smallvec::SmallVec;
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::Vector;
 use crate::index::field_index::FieldIndex;
-use crate::types::PayloadKeyType;
+use crate::types::{PayloadKeyType, VectorName};
+use crate::vector_storage::VectorStorageEnum;
 
 pub type IndexesMap = HashMap<PayloadKeyType, Vec<FieldIndex>>;
 
+pub type VectorStoragesMap = HashMap<VectorName, Arc<AtomicRefCell<VectorStorageEnum>>>;
+
 /// A container for JSON values, optimized for the common case of a single value.
 pub type MultiValue<T> = SmallVec<[T; 1]>;
 
@@ -47,6 +52,37 @@ pub fn merge_map(
     }
 }
 
+/// Apply JSON merge patch (RFC 7386) to destination map
+///
+/// Unlike [`merge_map`], nested objects are merged recursively instead of being replaced.
+/// Null values remove the key, any other non-object value (including arrays) replaces it.
+pub fn merge_patch_map(
+    dest: &mut serde_json::Map<String, Value>,
+    patch: &serde_json::Map<String, Value>,
+) {
+    for (key, value) in patch {
+        match value {
+            Value::Null => {
+                dest.remove(key);
+            }
+            Value::Object(patch) => {
+                let entry = dest
+                    .entry(key.to_owned())
+                    .or_insert_with(|| Value::Object(Default::default()));
+                if !entry.is_object() {
+                    *entry = Value::Object(Default::default());
+                }
+                if let Value::Object(dest) = entry {
+                    merge_patch_map(dest, patch);
+                }
+            }
+            _ => {
+                dest.insert(key.to_owned(), value.to_owned());
+            }
+        }
+    }
+}
+
 pub fn transpose_map_into_named_vector<TVector: Into<Vector>>(
     map: HashMap<String, Vec<TVector>>,
 ) -> Vec<NamedVectors<'static>> {
@@ -118,8 +154,9 @@ impl<T: JsonSchema> JsonSchema for MaybeOneOrMany<T> {
 mod tests {
     use schemars::{schema_for, JsonSchema};
     use serde::{Deserialize, Serialize};
+    use serde_json::{json, Value};
 
-    use crate::common::utils::MaybeOneOrMany;
+    use crate::common::utils::{merge_patch_map, MaybeOneOrMany};
 
     #[test]
     fn test_deserialize_one_or_many() {
@@ -211,4 +248,53 @@ mod tests {
             schema_for!(()).schema.instance_type
         );
     }
+
+    #[test]
+    fn test_merge_patch_map() {
+        // Examples from RFC 7386, Appendix A
+        let cases = [
+            (json!({"a": "b"}), json!({"a": "c"}), json!({"a": "c"})),
+            (
+                json!({"a": "b"}),
+                json!({"b": "c"}),
+                json!({"a": "b", "b": "c"}),
+            ),
+            (json!({"a": "b"}), json!({"a": null}), json!({})),
+            (
+                json!({"a": "b", "b": "c"}),
+                json!({"a": null}),
+                json!({"b": "c"}),
+            ),
+            (json!({"a": ["b"]}), json!({"a": "c"}), json!({"a": "c"})),
+            (json!({"a": "c"}), json!({"a": ["b"]}), json!({"a": ["b"]})),
+            (
+                json!({"a": {"b": "c"}}),
+                json!({"a": {"b": "d", "c": null}}),
+                json!({"a": {"b": "d"}}),
+            ),
+            (
+                json!({"a": [{"b": "c"}]}),
+                json!({"a": [1]}),
+                json!({"a": [1]}),
+            ),
+            (
+                json!({"e": null}),
+                json!({"a": 1}),
+                json!({"e": null, "a": 1}),
+            ),
+            (
+                json!({"a": "foo"}),
+                json!({"a": {"bb": {"ccc": null}}}),
+                json!({"a": {"bb": {}}}),
+            ),
+        ];
+
+        for (target, patch, expected) in cases {
+            let (Value::Object(mut target), Value::Object(patch)) = (target, patch) else {
+                unreachable!();
+            };
+            merge_patch_map(&mut target, &patch);
+            assert_eq!(Value::Object(target), expected);
+        }
+    }
 }
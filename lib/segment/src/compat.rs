@@ -64,6 +64,7 @@ impl From<SegmentConfigV5> for SegmentConfig {
             vector_data,
             sparse_vector_data: Default::default(),
             payload_storage_type: old_segment.payload_storage_type,
+            payload_compression: None,
         }
     }
 }
@@ -147,11 +148,14 @@ mod tests {
                         distance: Distance::Dot,
                         hnsw_config: Some(HnswConfig {
                             m: 20,
+                            m0: None,
                             ef_construct: 100,
                             full_scan_threshold: 10000,
                             max_indexing_threads: 0,
                             on_disk: None,
                             payload_m: Some(10),
+                            level_multiplier: None,
+                            deterministic_build: None,
                         }),
                         quantization_config: None,
                         on_disk: None,
@@ -178,11 +182,14 @@ mod tests {
             .collect(),
             index: Indexes::Hnsw(HnswConfig {
                 m: 25,
+                m0: None,
                 ef_construct: 120,
                 full_scan_threshold: 10000,
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                level_multiplier: None,
+                deterministic_build: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -253,11 +260,14 @@ mod tests {
             .collect(),
             index: Indexes::Hnsw(HnswConfig {
                 m: 25,
+                m0: None,
                 ef_construct: 120,
                 full_scan_threshold: 10000,
                 max_indexing_threads: 0,
                 on_disk: None,
                 payload_m: None,
+                level_multiplier: None,
+                deterministic_build: None,
             }),
             storage_type: StorageTypeV5::InMemory,
             payload_storage_type: PayloadStorageType::default(),
@@ -68,6 +68,10 @@ pub struct FacetHit<T: FacetValueTrait> {
 
 pub struct FacetResponse {
     pub hits: Vec<FacetValueHit>,
+    /// Sum of the counts of values left out of `hits` by the limit
+    pub other_count: usize,
+    /// Whether the counts are exact, otherwise they are estimations
+    pub exact: bool,
 }
 
 impl<T: FacetValueTrait> Ord for FacetHit<T> {
@@ -1,5 +1,8 @@
+use std::borrow::Cow;
+
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationError};
 
 // Keyword
 
@@ -23,6 +26,12 @@ pub struct KeywordIndexParams {
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
+
+    /// Values of the field to build a separate HNSW graph for, one graph per value.
+    /// Filtered searches which must match one of these values search its graph, searches with
+    /// other values search the main graph. Meant for a few very common values.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hnsw_partitions: Option<Vec<String>>,
 }
 
 // Integer
@@ -141,9 +150,78 @@ pub enum TokenizerType {
     #[default]
     Word,
     Multilingual,
+    /// Split words into overlapping character n-grams of `min_token_len` to `max_token_len`
+    /// characters. Works for languages without word separators, such as Chinese or Japanese.
+    Ngram,
 }
 
-#[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Hash, Eq)]
+/// Default shortest n-gram of the `ngram` tokenizer
+pub const DEFAULT_NGRAM_MIN: usize = 2;
+/// Default longest n-gram of the `ngram` tokenizer
+pub const DEFAULT_NGRAM_MAX: usize = 3;
+
+#[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum Snowball {
+    #[default]
+    Snowball,
+}
+
+/// Languages supported by the snowball stemmer
+#[derive(
+    Debug,
+    Deserialize,
+    Serialize,
+    JsonSchema,
+    Clone,
+    Copy,
+    PartialEq,
+    Hash,
+    Eq,
+    strum::EnumString,
+    strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SnowballLanguage {
+    Arabic,
+    Danish,
+    Dutch,
+    English,
+    Finnish,
+    French,
+    German,
+    Greek,
+    Hungarian,
+    Italian,
+    Norwegian,
+    Portuguese,
+    Romanian,
+    Russian,
+    Spanish,
+    Swedish,
+    Tamil,
+    Turkish,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct SnowballParams {
+    // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
+    pub r#type: Snowball,
+    pub language: SnowballLanguage,
+}
+
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
+#[serde(untagged)]
+pub enum StemmingAlgorithm {
+    Snowball(SnowballParams),
+}
+
+#[derive(
+    Debug, Default, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Hash, Eq,
+)]
+#[validate(schema(function = "validate_text_index_params"))]
 #[serde(rename_all = "snake_case")]
 pub struct TextIndexParams {
     // Required for OpenAPI schema without anonymous types, versus #[serde(tag = "type")]
@@ -154,6 +232,7 @@ pub struct TextIndexParams {
 
     /// Minimum characters to be tokenized.
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(range(min = 1))]
     pub min_token_len: Option<usize>,
 
     /// Maximum characters to be tokenized.
@@ -164,11 +243,36 @@ pub struct TextIndexParams {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub lowercase: Option<bool>,
 
+    /// Stemming algorithm to reduce words to their root form. Only applies to `word`,
+    /// `whitespace` and `multilingual` tokenizers. Default: none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stemmer: Option<StemmingAlgorithm>,
+
     /// If true, store the index on disk. Default: false.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub on_disk: Option<bool>,
 }
 
+fn validate_text_index_params(params: &TextIndexParams) -> Result<(), ValidationError> {
+    let (default_min, default_max) = match params.tokenizer {
+        TokenizerType::Ngram => (DEFAULT_NGRAM_MIN, DEFAULT_NGRAM_MAX),
+        TokenizerType::Prefix
+        | TokenizerType::Whitespace
+        | TokenizerType::Word
+        | TokenizerType::Multilingual => (1, usize::MAX),
+    };
+    let min_token_len = params.min_token_len.unwrap_or(default_min);
+    let max_token_len = params.max_token_len.unwrap_or(default_max);
+    if min_token_len > max_token_len {
+        let mut error = ValidationError::new("min_token_len");
+        error.message = Some(Cow::from(format!(
+            "min_token_len ({min_token_len}) must not be greater than max_token_len ({max_token_len})",
+        )));
+        return Err(error);
+    }
+    Ok(())
+}
+
 // Bool
 
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Hash, Eq)]
@@ -185,8 +289,8 @@ pub struct BoolIndexParams {
     pub r#type: BoolIndexType,
 
     /// If true, store the index on disk. Default: false.
+    /// On-disk index uses less RAM, but filtering may be slower if the data is not in page cache.
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    #[cfg(any())]
     pub on_disk: Option<bool>,
 }
 
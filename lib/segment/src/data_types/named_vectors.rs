@@ -7,7 +7,8 @@ use super::primitive::PrimitiveVectorElement;
 use super::tiny_map;
 use super::vectors::{
     DenseVector, MultiDenseVectorInternal, TypedMultiDenseVector, TypedMultiDenseVectorRef, Vector,
-    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorElementTypeInt8,
+    VectorRef,
 };
 use crate::common::operation_error::OperationError;
 use crate::spaces::metric::Metric;
@@ -345,6 +346,20 @@ impl<'a> NamedVectors<'a> {
                     <ManhattanMetric as Metric<VectorElementTypeByte>>::preprocess(dense_vector)
                 }
             },
+            Some(VectorStorageDatatype::Int8) => match config.distance {
+                Distance::Cosine => {
+                    <CosineMetric as Metric<VectorElementTypeInt8>>::preprocess(dense_vector)
+                }
+                Distance::Euclid => {
+                    <EuclidMetric as Metric<VectorElementTypeInt8>>::preprocess(dense_vector)
+                }
+                Distance::Dot => {
+                    <DotProductMetric as Metric<VectorElementTypeInt8>>::preprocess(dense_vector)
+                }
+                Distance::Manhattan => {
+                    <ManhattanMetric as Metric<VectorElementTypeInt8>>::preprocess(dense_vector)
+                }
+            },
             Some(VectorStorageDatatype::Float16) => match config.distance {
                 Distance::Cosine => {
                     <CosineMetric as Metric<VectorElementTypeHalf>>::preprocess(dense_vector)
@@ -6,7 +6,9 @@ use serde::{Deserialize, Serialize};
 
 use super::named_vectors::CowMultiVector;
 use super::vectors::TypedMultiDenseVector;
-use crate::data_types::vectors::{VectorElementType, VectorElementTypeByte, VectorElementTypeHalf};
+use crate::data_types::vectors::{
+    VectorElementType, VectorElementTypeByte, VectorElementTypeHalf, VectorElementTypeInt8,
+};
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 use crate::types::{Distance, QuantizationConfig, VectorStorageDatatype};
@@ -196,3 +198,79 @@ impl PrimitiveVectorElement for VectorElementTypeByte {
         ))
     }
 }
+
+impl PrimitiveVectorElement for VectorElementTypeInt8 {
+    /// Float components are converted with a saturating cast: values are rounded toward zero
+    /// and clamped to `[-128, 127]`, `NaN` becomes `0`.
+    fn slice_from_float_cow(vector: Cow<[VectorElementType]>) -> Cow<[Self]> {
+        Cow::Owned(vector.iter().map(|&x| x as i8).collect())
+    }
+
+    fn slice_to_float_cow(vector: Cow<[Self]>) -> Cow<[VectorElementType]> {
+        Cow::Owned(
+            vector
+                .iter()
+                .map(|&x| VectorElementType::from(x))
+                .collect_vec(),
+        )
+    }
+
+    fn quantization_preprocess<'a>(
+        quantization_config: &QuantizationConfig,
+        distance: Distance,
+        vector: &'a [Self],
+    ) -> Cow<'a, [f32]> {
+        let vector = vector
+            .iter()
+            .map(|&x| VectorElementType::from(x))
+            .collect_vec();
+        if let QuantizationConfig::Binary(_) = quantization_config {
+            // Signed values are already centered around zero
+            Cow::from(vector)
+        } else {
+            let preprocessed_vector = match distance {
+                Distance::Cosine => <CosineMetric as Metric<VectorElementType>>::preprocess(vector),
+                Distance::Euclid => <EuclidMetric as Metric<VectorElementType>>::preprocess(vector),
+                Distance::Dot => {
+                    <DotProductMetric as Metric<VectorElementType>>::preprocess(vector)
+                }
+                Distance::Manhattan => {
+                    <ManhattanMetric as Metric<VectorElementType>>::preprocess(vector)
+                }
+            };
+            Cow::from(preprocessed_vector)
+        }
+    }
+
+    fn datatype() -> VectorStorageDatatype {
+        VectorStorageDatatype::Int8
+    }
+
+    fn from_float_multivector(
+        multivector: CowMultiVector<VectorElementType>,
+    ) -> CowMultiVector<Self> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| x as Self)
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+
+    fn into_float_multivector(
+        multivector: CowMultiVector<Self>,
+    ) -> CowMultiVector<VectorElementType> {
+        CowMultiVector::Owned(TypedMultiDenseVector::new(
+            multivector
+                .as_vec_ref()
+                .flattened_vectors
+                .iter()
+                .map(|&x| VectorElementType::from(x))
+                .collect_vec(),
+            multivector.as_vec_ref().dim,
+        ))
+    }
+}
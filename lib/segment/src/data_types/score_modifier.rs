@@ -0,0 +1,556 @@
+use std::fmt::{self, Display, Formatter};
+use std::str::FromStr;
+
+use schemars::gen::SchemaGenerator;
+use schemars::schema::Schema;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::json_path::JsonPath;
+use crate::types::{Payload, ScoreType};
+
+/// Max length of an expression, in bytes.
+const MAX_EXPRESSION_LENGTH: usize = 1024;
+
+/// Max nesting depth of an expression, protects the recursive parser and evaluator.
+const MAX_EXPRESSION_DEPTH: usize = 32;
+
+/// Variable name which refers to the vector score of a point.
+const SCORE_VARIABLE: &str = "score";
+
+/// Adjusts the vector score of found points with an expression over numeric payload fields.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub struct ScoreModifier {
+    /// Expression to compute the final score, e.g. `score * ln(1 + popularity)`.
+    pub expression: ScoreExpression,
+    /// Multiplier for the vector score of points for which the expression can't be evaluated,
+    /// for example if a payload field is missing or not a number. Default is 1.0.
+    pub default_multiplier: Option<ScoreType>,
+    /// How many times more candidates than `offset + limit` to select by vector similarity
+    /// and rescore. Points outside of the candidates are never returned. Default is 4.0.
+    #[validate(range(min = 1.0))]
+    pub oversampling: Option<f64>,
+}
+
+impl ScoreModifier {
+    pub const DEFAULT_MULTIPLIER: ScoreType = 1.0;
+    pub const DEFAULT_OVERSAMPLING: f64 = 4.0;
+
+    /// Number of candidates to rescore for a page of `offset + limit` points.
+    pub fn candidates(&self, offset: usize, limit: usize) -> usize {
+        let oversampling = self.oversampling.unwrap_or(Self::DEFAULT_OVERSAMPLING);
+        ((offset + limit) as f64 * oversampling).ceil() as usize
+    }
+
+    /// Computes the modified score of a point.
+    ///
+    /// Falls back to the default multiplier if the expression can't be evaluated for the payload.
+    pub fn apply(&self, score: ScoreType, payload: Option<&Payload>) -> ScoreType {
+        self.expression
+            .evaluate(score, payload)
+            .unwrap_or_else(|| score * self.default_multiplier.unwrap_or(Self::DEFAULT_MULTIPLIER))
+    }
+}
+
+/// Parsed arithmetic expression over the vector score and numeric payload fields.
+///
+/// Supports numbers, `score`, payload field paths, `+ - * /`, parentheses and the functions
+/// `ln`, `log` (natural), `log10`, `exp`, `sqrt`, `abs`, `min`, `max` and `pow`.
+#[derive(Debug, Clone)]
+pub struct ScoreExpression {
+    source: String,
+    root: Expr,
+    fields: Vec<JsonPath>,
+}
+
+#[derive(Debug, Clone)]
+enum Expr {
+    Number(f64),
+    Score,
+    Field(usize),
+    Neg(Box<Expr>),
+    Binary(BinaryOp, Box<Expr>, Box<Expr>),
+    Function(Function, Vec<Expr>),
+}
+
+#[derive(Debug, Clone, Copy)]
+enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Function {
+    Ln,
+    Log10,
+    Exp,
+    Sqrt,
+    Abs,
+    Min,
+    Max,
+    Pow,
+}
+
+impl Function {
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "ln" | "log" => Some(Self::Ln),
+            "log10" => Some(Self::Log10),
+            "exp" => Some(Self::Exp),
+            "sqrt" => Some(Self::Sqrt),
+            "abs" => Some(Self::Abs),
+            "min" => Some(Self::Min),
+            "max" => Some(Self::Max),
+            "pow" => Some(Self::Pow),
+            _ => None,
+        }
+    }
+
+    fn arity(self) -> usize {
+        match self {
+            Self::Ln | Self::Log10 | Self::Exp | Self::Sqrt | Self::Abs => 1,
+            Self::Min | Self::Max | Self::Pow => 2,
+        }
+    }
+
+    fn call(self, args: &[f64]) -> f64 {
+        match self {
+            Self::Ln => args[0].ln(),
+            Self::Log10 => args[0].log10(),
+            Self::Exp => args[0].exp(),
+            Self::Sqrt => args[0].sqrt(),
+            Self::Abs => args[0].abs(),
+            Self::Min => args[0].min(args[1]),
+            Self::Max => args[0].max(args[1]),
+            Self::Pow => args[0].powf(args[1]),
+        }
+    }
+}
+
+impl ScoreExpression {
+    /// Payload fields referenced by the expression.
+    pub fn fields(&self) -> &[JsonPath] {
+        &self.fields
+    }
+
+    /// Evaluates the expression for a point.
+    ///
+    /// Returns `None` if a referenced payload field is missing or not a number, or if the result
+    /// is not a finite number.
+    pub fn evaluate(&self, score: ScoreType, payload: Option<&Payload>) -> Option<ScoreType> {
+        let values = self
+            .fields
+            .iter()
+            .map(|path| {
+                path.value_get(&payload?.0)
+                    .iter()
+                    .find_map(|value| value.as_f64())
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        let result = eval(&self.root, f64::from(score), &values) as ScoreType;
+        result.is_finite().then_some(result)
+    }
+}
+
+fn eval(expr: &Expr, score: f64, values: &[f64]) -> f64 {
+    match expr {
+        Expr::Number(number) => *number,
+        Expr::Score => score,
+        Expr::Field(index) => values[*index],
+        Expr::Neg(expr) => -eval(expr, score, values),
+        Expr::Binary(op, left, right) => {
+            let left = eval(left, score, values);
+            let right = eval(right, score, values);
+            match op {
+                BinaryOp::Add => left + right,
+                BinaryOp::Sub => left - right,
+                BinaryOp::Mul => left * right,
+                BinaryOp::Div => left / right,
+            }
+        }
+        Expr::Function(function, args) => {
+            let args: Vec<_> = args.iter().map(|arg| eval(arg, score, values)).collect();
+            function.call(&args)
+        }
+    }
+}
+
+impl FromStr for ScoreExpression {
+    type Err = String;
+
+    fn from_str(source: &str) -> Result<Self, Self::Err> {
+        if source.len() > MAX_EXPRESSION_LENGTH {
+            return Err(format!(
+                "expression is longer than {MAX_EXPRESSION_LENGTH} characters",
+            ));
+        }
+
+        let mut parser = Parser {
+            input: source.as_bytes(),
+            pos: 0,
+            depth: 0,
+            fields: Vec::new(),
+        };
+        let root = parser.expr()?;
+        parser.skip_whitespace();
+        if parser.pos < parser.input.len() {
+            return Err(format!("unexpected character at position {}", parser.pos));
+        }
+
+        Ok(Self {
+            source: source.to_string(),
+            root,
+            fields: parser.fields,
+        })
+    }
+}
+
+/// Recursive descent parser:
+///
+/// ```text
+/// expr   := term (('+' | '-') term)*
+/// term   := unary (('*' | '/') unary)*
+/// unary  := '-' unary | atom
+/// atom   := number | name '(' expr (',' expr)* ')' | name | '(' expr ')'
+/// ```
+struct Parser<'a> {
+    input: &'a [u8],
+    pos: usize,
+    depth: usize,
+    fields: Vec<JsonPath>,
+}
+
+impl<'a> Parser<'a> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(u8::is_ascii_whitespace)
+        {
+            self.pos += 1;
+        }
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        self.skip_whitespace();
+        self.input.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, expected: u8) -> Result<(), String> {
+        if self.peek() == Some(expected) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(format!(
+                "expected '{}' at position {}",
+                expected as char, self.pos,
+            ))
+        }
+    }
+
+    fn enter(&mut self) -> Result<(), String> {
+        self.depth += 1;
+        if self.depth > MAX_EXPRESSION_DEPTH {
+            return Err(format!(
+                "expression is nested deeper than {MAX_EXPRESSION_DEPTH} levels",
+            ));
+        }
+        Ok(())
+    }
+
+    fn expr(&mut self) -> Result<Expr, String> {
+        self.enter()?;
+        let mut left = self.term()?;
+        loop {
+            let op = match self.peek() {
+                Some(b'+') => BinaryOp::Add,
+                Some(b'-') => BinaryOp::Sub,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.term()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        self.depth -= 1;
+        Ok(left)
+    }
+
+    fn term(&mut self) -> Result<Expr, String> {
+        let mut left = self.unary()?;
+        loop {
+            let op = match self.peek() {
+                Some(b'*') => BinaryOp::Mul,
+                Some(b'/') => BinaryOp::Div,
+                _ => break,
+            };
+            self.pos += 1;
+            let right = self.unary()?;
+            left = Expr::Binary(op, Box::new(left), Box::new(right));
+        }
+        Ok(left)
+    }
+
+    fn unary(&mut self) -> Result<Expr, String> {
+        if self.peek() == Some(b'-') {
+            self.pos += 1;
+            self.enter()?;
+            let expr = self.unary()?;
+            self.depth -= 1;
+            return Ok(Expr::Neg(Box::new(expr)));
+        }
+        self.atom()
+    }
+
+    fn atom(&mut self) -> Result<Expr, String> {
+        match self.peek() {
+            Some(b'(') => {
+                self.pos += 1;
+                let expr = self.expr()?;
+                self.expect(b')')?;
+                Ok(expr)
+            }
+            Some(c) if c.is_ascii_digit() || c == b'.' => self.number(),
+            Some(c) if c.is_ascii_alphabetic() || c == b'_' => self.name(),
+            Some(_) => Err(format!("unexpected character at position {}", self.pos)),
+            None => Err("unexpected end of expression".to_string()),
+        }
+    }
+
+    fn number(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_digit() || *c == b'.')
+        {
+            self.pos += 1;
+        }
+        // Exponent, e.g. `1e-3`
+        if self
+            .input
+            .get(self.pos)
+            .is_some_and(|c| *c == b'e' || *c == b'E')
+        {
+            self.pos += 1;
+            if self
+                .input
+                .get(self.pos)
+                .is_some_and(|c| *c == b'+' || *c == b'-')
+            {
+                self.pos += 1;
+            }
+            while self.input.get(self.pos).is_some_and(u8::is_ascii_digit) {
+                self.pos += 1;
+            }
+        }
+
+        let text = self.text(start);
+        text.parse()
+            .map(Expr::Number)
+            .map_err(|_| format!("invalid number '{text}' at position {start}"))
+    }
+
+    fn name(&mut self) -> Result<Expr, String> {
+        let start = self.pos;
+        while self
+            .input
+            .get(self.pos)
+            .is_some_and(|c| c.is_ascii_alphanumeric() || matches!(*c, b'_' | b'.' | b'[' | b']'))
+        {
+            self.pos += 1;
+        }
+        let name = self.text(start);
+
+        if self.peek() == Some(b'(') {
+            let function = Function::from_name(name)
+                .ok_or_else(|| format!("unknown function '{name}' at position {start}"))?;
+            self.pos += 1;
+
+            let mut args = vec![self.expr()?];
+            while self.peek() == Some(b',') {
+                self.pos += 1;
+                args.push(self.expr()?);
+            }
+            self.expect(b')')?;
+
+            if args.len() != function.arity() {
+                return Err(format!(
+                    "function '{name}' takes {} argument(s), got {}",
+                    function.arity(),
+                    args.len(),
+                ));
+            }
+            return Ok(Expr::Function(function, args));
+        }
+
+        if name == SCORE_VARIABLE {
+            return Ok(Expr::Score);
+        }
+
+        let path: JsonPath = name
+            .parse()
+            .map_err(|()| format!("invalid payload field '{name}' at position {start}"))?;
+        let index = match self.fields.iter().position(|field| field == &path) {
+            Some(index) => index,
+            None => {
+                self.fields.push(path);
+                self.fields.len() - 1
+            }
+        };
+        Ok(Expr::Field(index))
+    }
+
+    fn text(&self, start: usize) -> &'a str {
+        let input: &'a [u8] = self.input;
+        // Only ASCII characters are consumed, so this is always valid UTF-8
+        std::str::from_utf8(&input[start..self.pos]).unwrap_or_default()
+    }
+}
+
+impl PartialEq for ScoreExpression {
+    fn eq(&self, other: &Self) -> bool {
+        self.source == other.source
+    }
+}
+
+impl Display for ScoreExpression {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.source)
+    }
+}
+
+impl Serialize for ScoreExpression {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ScoreExpression {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let expression = String::deserialize(deserializer)?;
+        expression.parse().map_err(serde::de::Error::custom)
+    }
+}
+
+impl JsonSchema for ScoreExpression {
+    fn schema_name() -> String {
+        "ScoreExpression".to_string()
+    }
+
+    fn json_schema(gen: &mut SchemaGenerator) -> Schema {
+        let mut schema = String::json_schema(gen).into_object();
+        schema.metadata().description = Some(
+            "Arithmetic expression over `score` and numeric payload fields\n\n\
+             Supports numbers, `+ - * /`, parentheses and the functions `ln`, `log`, `log10`, \
+             `exp`, `sqrt`, `abs`, `min`, `max` and `pow`."
+                .to_string(),
+        );
+        schema.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn eval_expression(
+        expression: &str,
+        score: ScoreType,
+        payload: serde_json::Value,
+    ) -> Option<ScoreType> {
+        let expression: ScoreExpression = expression.parse().unwrap();
+        expression.evaluate(score, Some(&payload.into()))
+    }
+
+    #[test]
+    fn test_evaluate() {
+        let payload = json!({ "popularity": 3, "meta": { "boost": 0.5 } });
+
+        assert_eq!(eval_expression("score", 2.0, payload.clone()), Some(2.0));
+        assert_eq!(
+            eval_expression("1 + 2 * 3", 0.0, payload.clone()),
+            Some(7.0)
+        );
+        assert_eq!(
+            eval_expression("(1 + 2) * 3", 0.0, payload.clone()),
+            Some(9.0)
+        );
+        assert_eq!(
+            eval_expression("-score - -1", 2.0, payload.clone()),
+            Some(-1.0)
+        );
+        assert_eq!(
+            eval_expression("score * popularity + meta.boost", 2.0, payload.clone()),
+            Some(6.5),
+        );
+        assert_eq!(
+            eval_expression("max(score, pow(popularity, 2))", 2.0, payload.clone()),
+            Some(9.0)
+        );
+        assert_eq!(
+            eval_expression("ln(1 + popularity) / log(4)", 1.0, payload.clone()),
+            Some(1.0)
+        );
+        assert_eq!(eval_expression("1e-1 * 10", 0.0, payload), Some(1.0));
+    }
+
+    #[test]
+    fn test_missing_field() {
+        let payload = json!({ "popularity": "high" });
+
+        assert_eq!(eval_expression("score * views", 1.0, payload.clone()), None);
+        assert_eq!(
+            eval_expression("score * popularity", 1.0, payload.clone()),
+            None
+        );
+        assert_eq!(eval_expression("ln(0)", 1.0, payload), None);
+
+        let modifier = ScoreModifier {
+            expression: "score * views".parse().unwrap(),
+            default_multiplier: Some(0.5),
+            oversampling: None,
+        };
+        assert_eq!(modifier.apply(2.0, None), 1.0);
+        assert_eq!(
+            modifier.apply(2.0, Some(&json!({ "views": 3 }).into())),
+            6.0
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for expression in [
+            "",
+            "score *",
+            "(score",
+            "score)",
+            "unknown(score)",
+            "min(score)",
+            "score $ 2",
+            "1..2",
+            &"(".repeat(MAX_EXPRESSION_DEPTH + 1),
+            &"1+".repeat(MAX_EXPRESSION_LENGTH),
+        ] {
+            assert!(
+                expression.parse::<ScoreExpression>().is_err(),
+                "{expression}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_fields() {
+        let expression: ScoreExpression = "a * b + a".parse().unwrap();
+        assert_eq!(
+            expression.fields(),
+            &["a".parse::<JsonPath>().unwrap(), "b".parse().unwrap()],
+        );
+    }
+}
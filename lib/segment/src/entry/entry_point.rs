@@ -16,8 +16,8 @@ use crate::json_path::JsonPath;
 use crate::telemetry::SegmentTelemetry;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PointIdType,
-    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType, WithPayload,
-    WithVector,
+    ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
+    VectorMetadata, WithPayload, WithVector,
 };
 
 /// Define all operations which can be performed with Segment or Segment-like entity.
@@ -99,6 +99,24 @@ pub trait SegmentEntry {
         point_id: PointIdType,
     ) -> OperationResult<bool>;
 
+    /// Replace metadata of all vectors of the point.
+    /// Metadata of a vector is dropped whenever the vector itself is replaced or deleted.
+    fn set_vector_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &VectorMetadata,
+    ) -> OperationResult<bool>;
+
+    /// Set the revision of the point, its version exposed to users.
+    /// Revisions are assigned by the caller, so that they are the same on all replicas.
+    fn set_point_revision(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        revision: SeqNumberType,
+    ) -> OperationResult<bool>;
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>>;
 
     fn all_vectors(&self, point_id: PointIdType) -> OperationResult<NamedVectors>;
@@ -107,6 +125,22 @@ pub trait SegmentEntry {
     /// If not found, return empty payload
     fn payload(&self, point_id: PointIdType) -> OperationResult<Payload>;
 
+    /// Retrieve only the fields of point payload selected by `include`
+    /// If not found, return empty payload
+    fn payload_projected(
+        &self,
+        point_id: PointIdType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload>;
+
+    /// Retrieve metadata of all vectors of the point
+    /// If not found, return empty metadata
+    fn vector_metadata(&self, point_id: PointIdType) -> OperationResult<VectorMetadata>;
+
+    /// Retrieve revision of the point
+    /// If the point has none, return 0
+    fn point_revision(&self, point_id: PointIdType) -> OperationResult<SeqNumberType>;
+
     /// Iterator over all points in segment in ascending order.
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_>;
 
@@ -136,11 +170,14 @@ pub trait SegmentEntry {
 
     /// Return random points which satisfies filtering condition.
     ///
+    /// With a `seed`, the same points are returned in the same order for the same segment.
+    ///
     /// Cancelled by `is_stopped` flag.
     fn read_random_filtered(
         &self,
         limit: usize,
         filter: Option<&Filter>,
+        seed: Option<u64>,
         is_stopped: &AtomicBool,
     ) -> Vec<PointIdType>;
 
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -120,7 +121,10 @@ impl IdTracker for FixtureIdTracker {
         )
     }
 
-    fn iter_random(&self) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+    fn iter_random(
+        &self,
+        _seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
         unimplemented!("Not used for tests yet")
     }
 
@@ -243,7 +247,8 @@ pub fn create_struct_payload_index(
     ));
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(num_points)));
 
-    let mut index = StructPayloadIndex::open(payload_storage, id_tracker, path, true).unwrap();
+    let mut index =
+        StructPayloadIndex::open(payload_storage, id_tracker, HashMap::new(), path, true).unwrap();
 
     index
         .set_indexed(&STR_KEY.parse().unwrap(), PayloadSchemaType::Keyword)
@@ -131,6 +131,16 @@ pub fn random_dense_byte_vector<R: Rng + ?Sized>(rnd_gen: &mut R, size: usize) -
         .collect()
 }
 
+pub fn random_dense_int8_vector<R: Rng + ?Sized>(rnd_gen: &mut R, size: usize) -> DenseVector {
+    (0..size)
+        .map(|_| {
+            rnd_gen
+                .gen_range::<VectorElementType, _>(-128.0..=127.0)
+                .round()
+        })
+        .collect()
+}
+
 pub fn random_multi_vector<R: Rng + ?Sized>(
     rnd_gen: &mut R,
     vector_size: usize,
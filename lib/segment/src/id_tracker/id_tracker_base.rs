@@ -11,7 +11,7 @@ use crate::common::operation_error::OperationResult;
 use crate::common::Flusher;
 use crate::id_tracker::immutable_id_tracker::ImmutableIdTracker;
 use crate::id_tracker::simple_id_tracker::SimpleIdTracker;
-use crate::types::{PointIdType, SeqNumberType};
+use crate::types::{ExtendedPointId, IdRange, PointIdType, SeqNumberType};
 
 /// Sampling randomness seed
 ///
@@ -70,7 +70,32 @@ pub trait IdTracker: fmt::Debug {
     /// - excludes removed points
     fn iter_ids(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_>;
 
-    fn iter_random(&self) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_>;
+    /// Iterate over points in random order, the same order for the same `seed`
+    fn iter_random(
+        &self,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_>;
+
+    /// Iterate over points with numeric IDs in the given range, ordered by external ID
+    ///
+    /// Seeks in the ID mapping, so only points in the range are visited.
+    fn iter_id_range(
+        &self,
+        id_range: &IdRange,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        let Some((start, end)) = id_range.bounds() else {
+            return Box::new(std::iter::empty());
+        };
+        Box::new(
+            self.iter_from(Some(start.into())).take_while(
+                move |(external_id, _)| match external_id {
+                    ExtendedPointId::NumId(id) => *id <= end,
+                    // UUIDs are ordered after all numeric IDs
+                    ExtendedPointId::Uuid(_) => false,
+                },
+            ),
+        )
+    }
 
     /// Iterate over internal IDs (offsets)
     ///
@@ -272,11 +297,14 @@ impl IdTracker for IdTrackerEnum {
         }
     }
 
-    fn iter_random(&self) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+    fn iter_random(
+        &self,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
         match self {
-            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_random(),
-            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_random(),
-            IdTrackerEnum::InMemoryIdTracker(id_tracker) => id_tracker.iter_random(),
+            IdTrackerEnum::MutableIdTracker(id_tracker) => id_tracker.iter_random(seed),
+            IdTrackerEnum::ImmutableIdTracker(id_tracker) => id_tracker.iter_random(seed),
+            IdTrackerEnum::InMemoryIdTracker(id_tracker) => id_tracker.iter_random(seed),
         }
     }
 
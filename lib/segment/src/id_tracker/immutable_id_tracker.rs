@@ -428,8 +428,11 @@ impl IdTracker for ImmutableIdTracker {
         self.iter_internal()
     }
 
-    fn iter_random(&self) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
-        self.mappings.iter_random()
+    fn iter_random(
+        &self,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        self.mappings.iter_random(seed)
     }
 
     /// Creates a flusher function, that writes the deleted points bitvec to disk.
@@ -3,6 +3,7 @@ use std::iter;
 
 use bitvec::prelude::{BitSlice, BitVec};
 use byteorder::LittleEndian;
+use common::random::seeded_rng;
 use common::types::PointOffsetType;
 use itertools::Itertools;
 use rand::distributions::Distribution;
@@ -84,8 +85,9 @@ impl PointMappings {
 
     pub(crate) fn iter_random(
         &self,
+        seed: Option<u64>,
     ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
-        let rng = rand::thread_rng();
+        let rng = seeded_rng(seed);
         let max_internal = self.internal_to_external.len();
         if max_internal == 0 {
             return Box::new(iter::empty());
@@ -283,8 +283,11 @@ impl IdTracker for SimpleIdTracker {
         self.mappings.iter_from(external_id)
     }
 
-    fn iter_random(&self) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
-        self.mappings.iter_random()
+    fn iter_random(
+        &self,
+        seed: Option<u64>,
+    ) -> Box<dyn Iterator<Item = (PointIdType, PointOffsetType)> + '_> {
+        self.mappings.iter_random(seed)
     }
 
     fn total_point_count(&self) -> usize {
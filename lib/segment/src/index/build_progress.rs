@@ -0,0 +1,15 @@
+/// Receives progress of a vector index build, e.g. to render a progress bar in CLI tools.
+///
+/// Builders may report from multiple worker threads at once.
+pub trait BuildProgress: Sync {
+    /// Set the total number of points to insert.
+    ///
+    /// May be raised during the build, once the builder discovers more work, such as additional
+    /// graphs for payload blocks.
+    fn set_total(&self, total: usize);
+
+    /// Record that `delta` more points were inserted.
+    ///
+    /// Builders which insert points in batches report once per batch.
+    fn inc(&self, delta: usize);
+}
@@ -1,17 +1,21 @@
-use std::path::PathBuf;
+mod mmap_binary_index;
+
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
+use bitvec::vec::BitVec;
 use common::types::PointOffsetType;
 use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
 use self::memory::{BinaryItem, BinaryMemory};
+use self::mmap_binary_index::MmapBinaryIndex;
 use super::{
     CardinalityEstimation, FieldIndexBuilderTrait, PayloadFieldIndex, PrimaryCondition,
     ValueIndexer,
 };
-use crate::common::operation_error::OperationResult;
+use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::telemetry::PayloadIndexTelemetry;
@@ -165,7 +169,13 @@ mod memory {
     }
 }
 
-pub struct BinaryIndex {
+pub enum BinaryIndex {
+    Mutable(MutableBinaryIndex),
+    Mmap(Box<MmapBinaryIndex>),
+}
+
+/// Boolean index kept in memory and persisted in RocksDB
+pub struct MutableBinaryIndex {
     memory: BinaryMemory,
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
 }
@@ -177,31 +187,85 @@ impl BinaryIndex {
             db,
             &store_cf_name,
         ));
-        Self {
+        BinaryIndex::Mutable(MutableBinaryIndex {
             memory: BinaryMemory::new(),
             db_wrapper,
-        }
+        })
+    }
+
+    pub fn new_mmap(path: &Path) -> OperationResult<BinaryIndex> {
+        Ok(BinaryIndex::Mmap(Box::new(MmapBinaryIndex::load(path)?)))
     }
 
     pub fn builder(db: Arc<RwLock<DB>>, field_name: &str) -> BinaryIndexBuilder {
         BinaryIndexBuilder(Self::new(db, field_name))
     }
 
+    pub fn builder_mmap(path: &Path) -> BinaryIndexMmapBuilder {
+        BinaryIndexMmapBuilder {
+            path: path.to_owned(),
+            trues: BitVec::new(),
+            falses: BitVec::new(),
+        }
+    }
+
     fn storage_cf_name(field: &str) -> String {
         format!("{field}_binary")
     }
 
+    fn get(&self, point_id: PointOffsetType) -> BinaryItem {
+        match self {
+            BinaryIndex::Mutable(index) => index.memory.get(point_id),
+            BinaryIndex::Mmap(index) => index.get(point_id),
+        }
+    }
+
+    fn trues_count(&self) -> usize {
+        match self {
+            BinaryIndex::Mutable(index) => index.memory.trues_count(),
+            BinaryIndex::Mmap(index) => index.trues_count(),
+        }
+    }
+
+    fn falses_count(&self) -> usize {
+        match self {
+            BinaryIndex::Mutable(index) => index.memory.falses_count(),
+            BinaryIndex::Mmap(index) => index.falses_count(),
+        }
+    }
+
+    fn indexed_count(&self) -> usize {
+        match self {
+            BinaryIndex::Mutable(index) => index.memory.indexed_count(),
+            BinaryIndex::Mmap(index) => index.indexed_count(),
+        }
+    }
+
+    fn iter_has_true(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryIndex::Mutable(index) => Box::new(index.memory.iter_has_true()),
+            BinaryIndex::Mmap(index) => Box::new(index.iter_has_true()),
+        }
+    }
+
+    fn iter_has_false(&self) -> Box<dyn Iterator<Item = PointOffsetType> + '_> {
+        match self {
+            BinaryIndex::Mutable(index) => Box::new(index.memory.iter_has_false()),
+            BinaryIndex::Mmap(index) => Box::new(index.iter_has_false()),
+        }
+    }
+
     pub fn get_telemetry_data(&self) -> PayloadIndexTelemetry {
         PayloadIndexTelemetry {
             field_name: None,
-            points_count: self.memory.indexed_count(),
-            points_values_count: self.memory.trues_count() + self.memory.falses_count(),
+            points_count: self.indexed_count(),
+            points_values_count: self.trues_count() + self.falses_count(),
             histogram_bucket_size: None,
         }
     }
 
     pub fn values_count(&self, point_id: PointOffsetType) -> usize {
-        let binary_item = self.memory.get(point_id);
+        let binary_item = self.get(point_id);
         usize::from(binary_item.has_true()) + usize::from(binary_item.has_false())
     }
 
@@ -211,12 +275,12 @@ impl BinaryIndex {
 
     /// Check if the point has a true value
     pub fn values_has_true(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_true()
+        self.get(point_id).has_true()
     }
 
     /// Check if the point has a false value
     pub fn values_has_false(&self, point_id: PointOffsetType) -> bool {
-        self.memory.get(point_id).has_false()
+        self.get(point_id).has_false()
     }
 }
 
@@ -226,7 +290,10 @@ impl FieldIndexBuilderTrait for BinaryIndexBuilder {
     type FieldIndexType = BinaryIndex;
 
     fn init(&mut self) -> OperationResult<()> {
-        self.0.db_wrapper.recreate_column_family()
+        match &self.0 {
+            BinaryIndex::Mutable(index) => index.db_wrapper.recreate_column_family(),
+            BinaryIndex::Mmap(_) => unreachable!(),
+        }
     }
 
     fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
@@ -238,33 +305,91 @@ impl FieldIndexBuilderTrait for BinaryIndexBuilder {
     }
 }
 
+pub struct BinaryIndexMmapBuilder {
+    path: PathBuf,
+    trues: BitVec,
+    falses: BitVec,
+}
+
+impl FieldIndexBuilderTrait for BinaryIndexMmapBuilder {
+    type FieldIndexType = BinaryIndex;
+
+    fn init(&mut self) -> OperationResult<()> {
+        Ok(())
+    }
+
+    fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
+        let values: Vec<bool> = payload
+            .iter()
+            .flat_map(|value| <BinaryIndex as ValueIndexer>::get_values(value))
+            .collect();
+
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        if self.trues.len() <= id as usize {
+            self.trues.resize(id as usize + 1, false);
+            self.falses.resize(id as usize + 1, false);
+        }
+
+        self.trues.set(id as usize, values.iter().any(|v| *v));
+        self.falses.set(id as usize, values.iter().any(|v| !*v));
+
+        Ok(())
+    }
+
+    fn finalize(self) -> OperationResult<Self::FieldIndexType> {
+        Ok(BinaryIndex::Mmap(Box::new(MmapBinaryIndex::build(
+            &self.path,
+            self.trues,
+            self.falses,
+        )?)))
+    }
+}
+
 impl PayloadFieldIndex for BinaryIndex {
     fn load(&mut self) -> OperationResult<bool> {
-        if !self.db_wrapper.has_column_family()? {
+        let index = match self {
+            BinaryIndex::Mutable(index) => index,
+            // mmap index is always loaded
+            BinaryIndex::Mmap(_) => return Ok(true),
+        };
+
+        if !index.db_wrapper.has_column_family()? {
             return Ok(false);
         }
 
-        for (key, value) in self.db_wrapper.lock_db().iter()? {
+        for (key, value) in index.db_wrapper.lock_db().iter()? {
             let idx = PointOffsetType::from_be_bytes(key.as_ref().try_into().unwrap());
 
             debug_assert_eq!(value.len(), 1);
 
             let item = BinaryItem::from(value[0]);
-            self.memory.set_or_insert(idx, &item);
+            index.memory.set_or_insert(idx, &item);
         }
         Ok(true)
     }
 
     fn clear(self) -> OperationResult<()> {
-        self.db_wrapper.remove_column_family()
+        match self {
+            BinaryIndex::Mutable(index) => index.db_wrapper.remove_column_family(),
+            BinaryIndex::Mmap(index) => index.clear(),
+        }
     }
 
     fn flusher(&self) -> crate::common::Flusher {
-        self.db_wrapper.flusher()
+        match self {
+            BinaryIndex::Mutable(index) => index.db_wrapper.flusher(),
+            BinaryIndex::Mmap(index) => index.flusher(),
+        }
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![]
+        match self {
+            BinaryIndex::Mutable(_) => vec![],
+            BinaryIndex::Mmap(index) => index.files(),
+        }
     }
 
     fn filter<'a>(
@@ -276,9 +401,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 if *value {
-                    Some(Box::new(self.memory.iter_has_true()))
+                    Some(self.iter_has_true())
                 } else {
-                    Some(Box::new(self.memory.iter_has_false()))
+                    Some(self.iter_has_false())
                 }
             }
             _ => None,
@@ -291,9 +416,9 @@ impl PayloadFieldIndex for BinaryIndex {
                 value: ValueVariants::Bool(value),
             })) => {
                 let count = if *value {
-                    self.memory.trues_count()
+                    self.trues_count()
                 } else {
-                    self.memory.falses_count()
+                    self.falses_count()
                 };
 
                 let estimation = CardinalityEstimation::exact(count)
@@ -328,8 +453,8 @@ impl PayloadFieldIndex for BinaryIndex {
 
         // just two possible blocks: true and false
         let iter = [
-            make_block(self.memory.trues_count(), true, key.clone()),
-            make_block(self.memory.falses_count(), false, key),
+            make_block(self.trues_count(), true, key.clone()),
+            make_block(self.falses_count(), false, key),
         ]
         .into_iter()
         .flatten();
@@ -338,7 +463,7 @@ impl PayloadFieldIndex for BinaryIndex {
     }
 
     fn count_indexed_points(&self) -> usize {
-        self.memory.indexed_count()
+        self.indexed_count()
     }
 }
 
@@ -346,6 +471,15 @@ impl ValueIndexer for BinaryIndex {
     type ValueType = bool;
 
     fn add_many(&mut self, id: PointOffsetType, values: Vec<bool>) -> OperationResult<()> {
+        let index = match self {
+            BinaryIndex::Mutable(index) => index,
+            BinaryIndex::Mmap(_) => {
+                return Err(OperationError::service_error(
+                    "Can't add values to mmap binary index",
+                ))
+            }
+        };
+
         if values.is_empty() {
             return Ok(());
         }
@@ -355,9 +489,9 @@ impl ValueIndexer for BinaryIndex {
 
         let item = BinaryItem::from_bools(has_true, has_false);
 
-        self.memory.set_or_insert(id, &item);
+        index.memory.set_or_insert(id, &item);
 
-        self.db_wrapper.put(id.to_be_bytes(), item.as_bytes())?;
+        index.db_wrapper.put(id.to_be_bytes(), item.as_bytes())?;
 
         Ok(())
     }
@@ -367,8 +501,13 @@ impl ValueIndexer for BinaryIndex {
     }
 
     fn remove_point(&mut self, id: PointOffsetType) -> OperationResult<()> {
-        self.memory.remove(id);
-        self.db_wrapper.remove(id.to_be_bytes())?;
+        match self {
+            BinaryIndex::Mutable(index) => {
+                index.memory.remove(id);
+                index.db_wrapper.remove(id.to_be_bytes())?;
+            }
+            BinaryIndex::Mmap(index) => index.remove_point(id),
+        }
         Ok(())
     }
 }
@@ -388,6 +527,17 @@ mod tests {
     const FIELD_NAME: &str = "bool_field";
     const DB_NAME: &str = "test_db";
 
+    fn new_mmap_binary_index(values: &[serde_json::Value]) -> (TempDir, BinaryIndex) {
+        let tmp_dir = Builder::new().prefix("test_mmap_binary").tempdir().unwrap();
+        let mut builder = BinaryIndex::builder_mmap(tmp_dir.path());
+        builder.init().unwrap();
+        for (i, value) in values.iter().enumerate() {
+            builder.add_point(i as u32, &[value]).unwrap();
+        }
+        let index = builder.finalize().unwrap();
+        (tmp_dir, index)
+    }
+
     fn new_binary_index() -> (TempDir, BinaryIndex) {
         let tmp_dir = Builder::new().prefix(DB_NAME).tempdir().unwrap();
         let db = open_db_with_existing_cf(tmp_dir.path()).unwrap();
@@ -469,7 +619,10 @@ mod tests {
             });
 
         index.flusher()().unwrap();
-        let db = index.db_wrapper.get_database();
+        let db = match &index {
+            BinaryIndex::Mutable(index) => index.db_wrapper.get_database(),
+            BinaryIndex::Mmap(_) => unreachable!(),
+        };
 
         let mut new_index = BinaryIndex::new(db, FIELD_NAME);
         assert!(new_index.load().unwrap());
@@ -552,4 +705,50 @@ mod tests {
         let cardinality = index.estimate_cardinality(&match_bool(false)).unwrap();
         assert_eq!(cardinality.exp, 6);
     }
+
+    #[test]
+    fn mmap_filter_and_counts() {
+        let (_tmp_dir, index) = new_mmap_binary_index(&bools_fixture());
+
+        let point_offsets = index.filter(&match_bool(false)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![1, 2, 3, 5, 6, 10]);
+
+        let point_offsets = index.filter(&match_bool(true)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![0, 2, 3, 4, 6, 11]);
+
+        assert_eq!(index.count_indexed_points(), 9);
+        assert_eq!(
+            index.estimate_cardinality(&match_bool(true)).unwrap().exp,
+            6
+        );
+        assert_eq!(
+            index.estimate_cardinality(&match_bool(false)).unwrap().exp,
+            6
+        );
+    }
+
+    #[test]
+    fn mmap_remove_and_reload() {
+        let (tmp_dir, mut index) = new_mmap_binary_index(&bools_fixture());
+
+        assert!(index.add_point(100, &[&json!(true)]).is_err());
+
+        index.remove_point(2).unwrap();
+        index.remove_point(11).unwrap();
+        assert!(index.values_is_empty(2));
+        assert_eq!(index.count_indexed_points(), 7);
+
+        index.flusher()().unwrap();
+        drop(index);
+
+        let index = BinaryIndex::new_mmap(tmp_dir.path()).unwrap();
+
+        let point_offsets = index.filter(&match_bool(false)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![1, 3, 5, 6, 10]);
+
+        let point_offsets = index.filter(&match_bool(true)).unwrap().collect_vec();
+        assert_eq!(point_offsets, vec![0, 3, 4, 6]);
+
+        assert_eq!(index.count_indexed_points(), 7);
+    }
 }
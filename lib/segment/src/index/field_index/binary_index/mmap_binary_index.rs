@@ -0,0 +1,162 @@
+use std::fs::{create_dir_all, remove_dir};
+use std::path::{Path, PathBuf};
+
+use bitvec::vec::BitVec;
+use common::types::PointOffsetType;
+use memory::madvise::AdviceSetting;
+use memory::mmap_ops;
+use memory::mmap_type::MmapBitSlice;
+
+use super::memory::BinaryItem;
+use crate::common::mmap_bitslice_buffered_update_wrapper::MmapBitSliceBufferedUpdateWrapper;
+use crate::common::operation_error::OperationResult;
+use crate::common::Flusher;
+
+const TRUES_PATH: &str = "trues.bin";
+const FALSES_PATH: &str = "falses.bin";
+const DELETED_PATH: &str = "deleted.bin";
+
+/// Read-only boolean index, stored as memory-mapped bit flags.
+///
+/// Only point removal is supported after the index is built.
+pub struct MmapBinaryIndex {
+    path: PathBuf,
+    trues: MmapBitSlice,
+    falses: MmapBitSlice,
+    deleted: MmapBitSliceBufferedUpdateWrapper,
+    trues_count: usize,
+    falses_count: usize,
+    indexed_count: usize,
+}
+
+impl MmapBinaryIndex {
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        let trues = open_bitslice(&path.join(TRUES_PATH))?;
+        let falses = open_bitslice(&path.join(FALSES_PATH))?;
+        let deleted = open_bitslice(&path.join(DELETED_PATH))?;
+
+        let is_deleted = |idx: usize| deleted.get(idx).as_deref().copied().unwrap_or(false);
+
+        let trues_count = trues.iter_ones().filter(|&idx| !is_deleted(idx)).count();
+        let falses_count = falses.iter_ones().filter(|&idx| !is_deleted(idx)).count();
+        let indexed_count = (0..trues.len().max(falses.len()))
+            .filter(|&idx| !is_deleted(idx))
+            .filter(|&idx| bit(&trues, idx) || bit(&falses, idx))
+            .count();
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            trues,
+            falses,
+            deleted: MmapBitSliceBufferedUpdateWrapper::new(deleted),
+            trues_count,
+            falses_count,
+            indexed_count,
+        })
+    }
+
+    pub fn build(path: &Path, trues: BitVec, falses: BitVec) -> OperationResult<Self> {
+        debug_assert_eq!(trues.len(), falses.len());
+
+        create_dir_all(path)?;
+
+        MmapBitSlice::create(&path.join(TRUES_PATH), &trues)?;
+        MmapBitSlice::create(&path.join(FALSES_PATH), &falses)?;
+        MmapBitSlice::create(
+            &path.join(DELETED_PATH),
+            &BitVec::repeat(false, trues.len()),
+        )?;
+
+        Self::load(path)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.deleted.flusher()
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        let files = self.files();
+        let Self { path, .. } = self;
+        for file in files {
+            std::fs::remove_file(file)?;
+        }
+        let _ = remove_dir(path);
+        Ok(())
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        vec![
+            self.path.join(TRUES_PATH),
+            self.path.join(FALSES_PATH),
+            self.path.join(DELETED_PATH),
+        ]
+    }
+
+    fn is_deleted(&self, idx: PointOffsetType) -> bool {
+        self.deleted.get(idx as usize).unwrap_or(false)
+    }
+
+    pub fn get(&self, idx: PointOffsetType) -> BinaryItem {
+        if self.is_deleted(idx) {
+            return BinaryItem::empty();
+        }
+        BinaryItem::from_bools(
+            bit(&self.trues, idx as usize),
+            bit(&self.falses, idx as usize),
+        )
+    }
+
+    pub fn remove_point(&mut self, idx: PointOffsetType) {
+        if (idx as usize) >= self.deleted.len() || self.is_deleted(idx) {
+            return;
+        }
+
+        let item = self.get(idx);
+        if item.has_true() {
+            self.trues_count -= 1;
+        }
+        if item.has_false() {
+            self.falses_count -= 1;
+        }
+        if item.has_true() || item.has_false() {
+            self.indexed_count -= 1;
+        }
+
+        self.deleted.set(idx as usize, true);
+    }
+
+    pub fn trues_count(&self) -> usize {
+        self.trues_count
+    }
+
+    pub fn falses_count(&self) -> usize {
+        self.falses_count
+    }
+
+    pub fn indexed_count(&self) -> usize {
+        self.indexed_count
+    }
+
+    pub fn iter_has_true(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.trues
+            .iter_ones()
+            .map(|idx| idx as PointOffsetType)
+            .filter(|&idx| !self.is_deleted(idx))
+    }
+
+    pub fn iter_has_false(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.falses
+            .iter_ones()
+            .map(|idx| idx as PointOffsetType)
+            .filter(|&idx| !self.is_deleted(idx))
+    }
+}
+
+fn open_bitslice(path: &Path) -> OperationResult<MmapBitSlice> {
+    let mmap = mmap_ops::open_write_mmap(path, AdviceSetting::Global, false)?;
+    Ok(MmapBitSlice::from(mmap, 0))
+}
+
+fn bit(bitslice: &MmapBitSlice, idx: usize) -> bool {
+    bitslice.get(idx).as_deref().copied().unwrap_or(false)
+}
@@ -4,7 +4,7 @@ use std::path::PathBuf;
 use common::types::PointOffsetType;
 use serde_json::Value;
 
-use super::binary_index::BinaryIndexBuilder;
+use super::binary_index::{BinaryIndexBuilder, BinaryIndexMmapBuilder};
 use super::facet_index::FacetIndex;
 use super::full_text_index::mmap_text_index::FullTextMmapIndexBuilder;
 use super::full_text_index::text_index::{FullTextIndex, FullTextIndexBuilder};
@@ -424,6 +424,7 @@ pub enum FieldIndexBuilder {
     FullTextIndex(FullTextIndexBuilder),
     FullTextMmapIndex(FullTextMmapIndexBuilder),
     BinaryIndex(BinaryIndexBuilder),
+    BinaryMmapIndex(BinaryIndexMmapBuilder),
     UuidIndex(MapIndexBuilder<UuidIntType>),
     UuidMmapIndex(MapIndexMmapBuilder<UuidIntType>),
 }
@@ -445,6 +446,7 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             Self::FloatMmapIndex(index) => index.init(),
             Self::GeoIndex(index) => index.init(),
             Self::BinaryIndex(index) => index.init(),
+            Self::BinaryMmapIndex(index) => index.init(),
             Self::FullTextIndex(index) => index.init(),
             Self::FullTextMmapIndex(builder) => builder.init(),
             Self::UuidIndex(index) => index.init(),
@@ -466,6 +468,7 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             Self::FloatMmapIndex(index) => index.add_point(id, payload),
             Self::GeoIndex(index) => index.add_point(id, payload),
             Self::BinaryIndex(index) => index.add_point(id, payload),
+            Self::BinaryMmapIndex(index) => index.add_point(id, payload),
             Self::FullTextIndex(index) => index.add_point(id, payload),
             Self::FullTextMmapIndex(builder) => {
                 FieldIndexBuilderTrait::add_point(builder, id, payload)
@@ -489,6 +492,7 @@ impl FieldIndexBuilderTrait for FieldIndexBuilder {
             Self::FloatMmapIndex(index) => FieldIndex::FloatIndex(index.finalize()?),
             Self::GeoIndex(index) => FieldIndex::GeoIndex(index.finalize()?),
             Self::BinaryIndex(index) => FieldIndex::BinaryIndex(index.finalize()?),
+            Self::BinaryMmapIndex(index) => FieldIndex::BinaryIndex(index.finalize()?),
             Self::FullTextIndex(index) => FieldIndex::FullTextIndex(index.finalize()?),
             Self::FullTextMmapIndex(builder) => FieldIndex::FullTextIndex(builder.finalize()?),
             Self::UuidIndex(index) => FieldIndex::UuidMapIndex(index.finalize()?),
@@ -125,6 +125,7 @@ mod tests {
             min_token_len: None,
             max_token_len: None,
             lowercase: None,
+            stemmer: None,
             on_disk: None,
         };
 
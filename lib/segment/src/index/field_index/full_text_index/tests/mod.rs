@@ -161,6 +161,7 @@ fn test_prefix_search(#[case] immutable: bool) {
         min_token_len: None,
         max_token_len: None,
         lowercase: None,
+        stemmer: None,
         on_disk: None,
     };
 
@@ -1,6 +1,12 @@
+use std::borrow::Cow;
+
 use charabia::Tokenize;
+use rust_stemmers::{Algorithm, Stemmer};
 
-use crate::data_types::index::{TextIndexParams, TokenizerType};
+use crate::data_types::index::{
+    SnowballLanguage, SnowballParams, StemmingAlgorithm, TextIndexParams, TokenizerType,
+    DEFAULT_NGRAM_MAX, DEFAULT_NGRAM_MIN,
+};
 
 struct WhiteSpaceTokenizer;
 
@@ -62,6 +68,64 @@ impl PrefixTokenizer {
     }
 }
 
+struct NgramTokenizer;
+
+impl NgramTokenizer {
+    fn tokenize<C: FnMut(&str)>(text: &str, min_ngram: usize, max_ngram: usize, mut callback: C) {
+        text.split(|c| !char::is_alphanumeric(c))
+            .filter(|token| !token.is_empty())
+            .for_each(|word| {
+                let bounds = Self::char_bounds(word);
+                let chars = bounds.len() - 1;
+                if chars < min_ngram {
+                    callback(word);
+                    return;
+                }
+                for n in min_ngram..=max_ngram.min(chars) {
+                    for start in 0..=chars - n {
+                        callback(&word[bounds[start]..bounds[start + n]]);
+                    }
+                }
+            });
+    }
+
+    /// For querying, it is enough to use the longest ngrams only, as they contain all shorter ones.
+    /// E.g. with ngrams of 2 to 3 characters
+    ///
+    /// Docs. tokens: `"hello"` -> `["he", "el", "ll", "lo", "hel", "ell", "llo"]`
+    /// Query tokens: `"ello"`  -> `["ell", "llo"]`
+    /// Query tokens: `"lo"`    -> `["lo"]`
+    fn tokenize_query<C: FnMut(&str)>(
+        text: &str,
+        min_ngram: usize,
+        max_ngram: usize,
+        mut callback: C,
+    ) {
+        text.split(|c| !char::is_alphanumeric(c))
+            .filter(|token| !token.is_empty())
+            .for_each(|word| {
+                let bounds = Self::char_bounds(word);
+                let chars = bounds.len() - 1;
+                if chars < min_ngram {
+                    callback(word);
+                    return;
+                }
+                let n = max_ngram.min(chars);
+                for start in 0..=chars - n {
+                    callback(&word[bounds[start]..bounds[start + n]]);
+                }
+            });
+    }
+
+    /// Byte offsets of all characters in the word, including the end of the word
+    fn char_bounds(word: &str) -> Vec<usize> {
+        word.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(word.len()))
+            .collect()
+    }
+}
+
 struct MultilingualTokenizer;
 
 impl MultilingualTokenizer {
@@ -74,11 +138,52 @@ impl MultilingualTokenizer {
     }
 }
 
+fn snowball_algorithm(language: SnowballLanguage) -> Algorithm {
+    match language {
+        SnowballLanguage::Arabic => Algorithm::Arabic,
+        SnowballLanguage::Danish => Algorithm::Danish,
+        SnowballLanguage::Dutch => Algorithm::Dutch,
+        SnowballLanguage::English => Algorithm::English,
+        SnowballLanguage::Finnish => Algorithm::Finnish,
+        SnowballLanguage::French => Algorithm::French,
+        SnowballLanguage::German => Algorithm::German,
+        SnowballLanguage::Greek => Algorithm::Greek,
+        SnowballLanguage::Hungarian => Algorithm::Hungarian,
+        SnowballLanguage::Italian => Algorithm::Italian,
+        SnowballLanguage::Norwegian => Algorithm::Norwegian,
+        SnowballLanguage::Portuguese => Algorithm::Portuguese,
+        SnowballLanguage::Romanian => Algorithm::Romanian,
+        SnowballLanguage::Russian => Algorithm::Russian,
+        SnowballLanguage::Spanish => Algorithm::Spanish,
+        SnowballLanguage::Swedish => Algorithm::Swedish,
+        SnowballLanguage::Tamil => Algorithm::Tamil,
+        SnowballLanguage::Turkish => Algorithm::Turkish,
+    }
+}
+
 pub struct Tokenizer;
 
 impl Tokenizer {
+    /// Stemmer to apply to tokens, if configured.
+    ///
+    /// Stemming partial words makes no sense, so it is not applied to `prefix` and `ngram` tokens.
+    fn stemmer(config: &TextIndexParams) -> Option<Stemmer> {
+        match config.tokenizer {
+            TokenizerType::Prefix | TokenizerType::Ngram => None,
+            TokenizerType::Whitespace | TokenizerType::Word | TokenizerType::Multilingual => {
+                config.stemmer.map(|stemmer| match stemmer {
+                    StemmingAlgorithm::Snowball(SnowballParams {
+                        r#type: _,
+                        language,
+                    }) => Stemmer::create(snowball_algorithm(language)),
+                })
+            }
+        }
+    }
+
     fn doc_token_filter<'a, C: FnMut(&str) + 'a>(
         config: &'a TextIndexParams,
+        stemmer: Option<&'a Stemmer>,
         mut callback: C,
     ) -> impl FnMut(&str) + 'a {
         move |token: &str| {
@@ -96,16 +201,21 @@ impl Tokenizer {
             {
                 return;
             }
-            if config.lowercase.unwrap_or(true) {
-                callback(&token.to_lowercase());
+            let token = if config.lowercase.unwrap_or(true) {
+                Cow::Owned(token.to_lowercase())
             } else {
-                callback(token);
+                Cow::Borrowed(token)
+            };
+            match stemmer {
+                Some(stemmer) => callback(&stemmer.stem(&token)),
+                None => callback(&token),
             }
         }
     }
 
     pub fn tokenize_doc<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
+        let stemmer = Self::stemmer(config);
+        let token_filter = Self::doc_token_filter(config, stemmer.as_ref(), &mut callback);
         match config.tokenizer {
             TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
             TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
@@ -116,11 +226,18 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Ngram => NgramTokenizer::tokenize(
+                text,
+                config.min_token_len.unwrap_or(DEFAULT_NGRAM_MIN),
+                config.max_token_len.unwrap_or(DEFAULT_NGRAM_MAX),
+                token_filter,
+            ),
         }
     }
 
     pub fn tokenize_query<C: FnMut(&str)>(text: &str, config: &TextIndexParams, mut callback: C) {
-        let token_filter = Self::doc_token_filter(config, &mut callback);
+        let stemmer = Self::stemmer(config);
+        let token_filter = Self::doc_token_filter(config, stemmer.as_ref(), &mut callback);
         match config.tokenizer {
             TokenizerType::Whitespace => WhiteSpaceTokenizer::tokenize(text, token_filter),
             TokenizerType::Word => WordTokenizer::tokenize(text, token_filter),
@@ -130,12 +247,20 @@ impl Tokenizer {
                 config.max_token_len.unwrap_or(usize::MAX),
                 token_filter,
             ),
+            TokenizerType::Ngram => NgramTokenizer::tokenize_query(
+                text,
+                config.min_token_len.unwrap_or(DEFAULT_NGRAM_MIN),
+                config.max_token_len.unwrap_or(DEFAULT_NGRAM_MAX),
+                token_filter,
+            ),
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
+    use validator::Validate;
+
     use super::*;
     use crate::data_types::index::TextIndexType;
 
@@ -188,6 +313,88 @@ mod tests {
         assert_eq!(tokens.get(1), Some(&"мир".to_owned()));
     }
 
+    #[test]
+    fn test_ngram_tokenizer() {
+        let text = "hello, 本日の日付は";
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize(text, 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(
+            tokens,
+            vec![
+                "he",
+                "el",
+                "ll",
+                "lo",
+                "hel",
+                "ell",
+                "llo",
+                "本日",
+                "日の",
+                "の日",
+                "日付",
+                "付は",
+                "本日の",
+                "日の日",
+                "の日付",
+                "日付は",
+            ],
+        );
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize_query("日付, a", 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["日付", "a"]);
+
+        let mut tokens = Vec::new();
+        NgramTokenizer::tokenize_query("ello", 2, 3, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["ell", "llo"]);
+    }
+
+    #[test]
+    fn test_ngram_params_validation() {
+        let params = |min_token_len, max_token_len| TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Ngram,
+            min_token_len,
+            max_token_len,
+            lowercase: None,
+            stemmer: None,
+            on_disk: None,
+        };
+
+        assert!(params(None, None).validate().is_ok());
+        assert!(params(Some(1), Some(1)).validate().is_ok());
+        assert!(params(Some(0), Some(3)).validate().is_err());
+        assert!(params(Some(3), Some(2)).validate().is_err());
+        // Default shortest n-gram is longer than the given longest one
+        assert!(params(None, Some(1)).validate().is_err());
+    }
+
+    #[test]
+    fn test_stemming_tokenizer() {
+        let config = TextIndexParams {
+            r#type: TextIndexType::Text,
+            tokenizer: TokenizerType::Word,
+            min_token_len: None,
+            max_token_len: None,
+            lowercase: Some(true),
+            stemmer: Some(StemmingAlgorithm::Snowball(SnowballParams {
+                r#type: Default::default(),
+                language: SnowballLanguage::English,
+            })),
+            on_disk: None,
+        };
+
+        let mut tokens = Vec::new();
+        Tokenizer::tokenize_doc("Running runners run", &config, |token| {
+            tokens.push(token.to_owned())
+        });
+        assert_eq!(tokens, vec!["run", "runner", "run"]);
+
+        let mut tokens = Vec::new();
+        Tokenizer::tokenize_query("RUNS", &config, |token| tokens.push(token.to_owned()));
+        assert_eq!(tokens, vec!["run"]);
+    }
+
     #[cfg(feature = "multiling-japanese")]
     #[test]
     fn test_multilingual_tokenizer_japanese() {
@@ -254,6 +461,7 @@ mod tests {
                 min_token_len: Some(1),
                 max_token_len: Some(4),
                 lowercase: Some(true),
+                stemmer: None,
                 on_disk: None,
             },
             |token| tokens.push(token.to_owned()),
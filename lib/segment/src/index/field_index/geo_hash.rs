@@ -1,7 +1,7 @@
 use std::ops::{Index, Range};
 
 use geo::algorithm::haversine_distance::HaversineDistance;
-use geo::{Coord, Intersects, LineString, Point, Polygon};
+use geo::{Coord, Intersects, LineString, Point, Polygon, Rect};
 use geohash::{decode, decode_bbox, encode, Direction, GeohashError};
 use itertools::Itertools;
 use smol_str::SmolStr;
@@ -367,7 +367,26 @@ fn check_polygon_intersection(geohash: &str, polygon: &Polygon) -> bool {
     if precision == 0 {
         return true;
     }
-    let rect = decode_bbox(geohash).unwrap();
+    let mut rect = decode_bbox(geohash).unwrap();
+
+    // Polygons crossing the antimeridian have western longitudes shifted by 360 degrees,
+    // see `GeoPoint::to_coord`. Geohash tiles never span the prime meridian.
+    let crosses_antimeridian = polygon
+        .exterior()
+        .coords()
+        .any(|coord| coord.x > LON_RANGE.end);
+    if crosses_antimeridian && rect.min().x < 0.0 {
+        rect = Rect::new(
+            Coord {
+                x: rect.min().x + 360.0,
+                y: rect.min().y,
+            },
+            Coord {
+                x: rect.max().x + 360.0,
+                y: rect.max().y,
+            },
+        );
+    }
 
     rect.intersects(polygon)
 }
@@ -573,6 +592,12 @@ fn minimum_bounding_rectangle_for_boundary(boundary: &LineString) -> GeoBounding
         }
     }
 
+    // Boundaries crossing the antimeridian have western longitudes shifted by 360 degrees,
+    // wrap them back to get a rectangle crossing the antimeridian
+    if max_lon > LON_RANGE.end {
+        max_lon -= 360.0;
+    }
+
     let top_left = GeoPoint {
         lon: min_lon,
         lat: max_lat,
@@ -691,6 +716,7 @@ mod tests {
         let near_nyc_circle = GeoRadius {
             center: NYC,
             radius: 800.0,
+            inner_radius: None,
         };
 
         let bounding_box = minimum_bounding_rectangle_for_circle(&near_nyc_circle);
@@ -939,6 +965,7 @@ mod tests {
                     lat: rnd.gen_range(LAT_RANGE),
                 },
                 radius: r_meters,
+                inner_radius: None,
             };
             let max_hashes = rnd.gen_range(1..32);
             let hashes = circle_hashes(&query, max_hashes);
@@ -1065,6 +1092,7 @@ mod tests {
                 lat: 44.9811609411936,
             },
             radius: 100000.,
+            inner_radius: None,
         };
 
         let max_hashes = 10;
@@ -1088,6 +1116,7 @@ mod tests {
                 lat: 89.9811609411936,
             },
             radius: 9199.481636468849,
+            inner_radius: None,
         };
 
         let max_hashes = 10;
@@ -1117,6 +1146,7 @@ mod tests {
                 lat: 89.9811609411936,
             },
             radius: 1000.0,
+            inner_radius: None,
         };
 
         let max_hashes = 10;
@@ -1144,6 +1174,7 @@ mod tests {
                 lat: -74.19418872656166,
             },
             radius: 7133.775526733084,
+            inner_radius: None,
         };
         let max_hashes = 10;
         let hashes_result = circle_hashes(&query, max_hashes);
@@ -1168,6 +1199,7 @@ mod tests {
                 lat: -74.19418872656166,
             },
             radius: 1000.0,
+            inner_radius: None,
         };
         let max_hashes = 10;
         let hashes_result = circle_hashes(&query, max_hashes);
@@ -1188,6 +1220,7 @@ mod tests {
         let near_nyc_circle = GeoRadius {
             center: NYC,
             radius: 800.0,
+            inner_radius: None,
         };
 
         let nyc_hashes_result = circle_hashes(&near_nyc_circle, 200).unwrap();
@@ -1336,6 +1369,7 @@ mod tests {
                 lat: 44.9811609411936,
             },
             radius: 100000.,
+            inner_radius: None,
         };
         let circle_hashes = circle_hashes(&sample_circle, invalid_max_hashes);
         assert!(circle_hashes.is_err());
@@ -1376,6 +1410,7 @@ mod tests {
                 lat: 80.0,
             },
             radius: 1000.0,
+            inner_radius: None,
         };
         let hashes = circle_hashes(&circle, GEOHASH_MAX_LENGTH);
         assert!(hashes.is_ok());
@@ -1386,6 +1421,7 @@ mod tests {
                 lat: 90.0,
             },
             radius: -1.0,
+            inner_radius: None,
         };
         let hashes2 = circle_hashes(&circle2, GEOHASH_MAX_LENGTH);
         assert!(hashes2.is_err());
@@ -425,6 +425,17 @@ impl PayloadFieldIndex for GeoMapIndex {
         if let Some(geo_radius) = &condition.geo_radius {
             let geo_hashes = circle_hashes(geo_radius, GEO_QUERY_MAX_REGION).ok()?;
             let mut estimation = self.match_cardinality(&geo_hashes);
+            if let Some(inner_circle) = geo_radius.inner_circle() {
+                // Hashes of the inner circle cover more than the excluded area itself,
+                // so they can only lower the minimal and expected estimations.
+                let inner_hashes = circle_hashes(&inner_circle, GEO_QUERY_MAX_REGION).ok()?;
+                let inner_estimation = self.match_cardinality(&inner_hashes);
+                estimation.min = estimation.min.saturating_sub(inner_estimation.max);
+                estimation.exp = estimation
+                    .exp
+                    .saturating_sub(inner_estimation.exp)
+                    .max(estimation.min);
+            }
             estimation
                 .primary_clauses
                 .push(PrimaryCondition::Condition(condition.clone()));
@@ -745,6 +756,7 @@ mod tests {
         let europe_no_berlin = GeoPolygon {
             exterior: europe.clone(),
             interiors: Some(vec![berlin.clone()]),
+            crosses_antimeridian: None,
         };
         check_cardinality_match(
             polygon_hashes(&europe_no_berlin, GEO_QUERY_MAX_REGION).unwrap(),
@@ -784,6 +796,7 @@ mod tests {
         let geo_radius = GeoRadius {
             center: NYC,
             radius: r_meters,
+            inner_radius: None,
         };
         let nyc_hashes = circle_hashes(&geo_radius, GEO_QUERY_MAX_REGION).unwrap();
         check_cardinality_match(
@@ -840,6 +853,7 @@ mod tests {
         let geo_radius = GeoRadius {
             center: NYC,
             radius: r_meters,
+            inner_radius: None,
         };
         check_geo_indexed_filtering(
             condition_for_geo_radius("test", geo_radius.clone()),
@@ -847,6 +861,17 @@ mod tests {
             is_appendable,
         );
 
+        let geo_ring = GeoRadius {
+            center: NYC,
+            radius: r_meters,
+            inner_radius: Some(r_meters / 2.0),
+        };
+        check_geo_indexed_filtering(
+            condition_for_geo_radius("test", geo_ring.clone()),
+            |geo_point| geo_ring.check_point(geo_point),
+            is_appendable,
+        );
+
         let geo_polygon: GeoPolygon = build_polygon(vec![
             (-60.0, 37.0),
             (-60.0, 45.0),
@@ -909,6 +934,7 @@ mod tests {
         let nyc_geo_radius = GeoRadius {
             center: NYC,
             radius: r_meters,
+            inner_radius: None,
         };
         let field_condition = condition_for_geo_radius("test", nyc_geo_radius.clone());
         let card = index.estimate_cardinality(&field_condition);
@@ -928,6 +954,7 @@ mod tests {
         let berlin_geo_radius = GeoRadius {
             center: BERLIN,
             radius: r_meters,
+            inner_radius: None,
         };
         let field_condition = condition_for_geo_radius("test", berlin_geo_radius.clone());
         let card = index.estimate_cardinality(&field_condition);
@@ -948,6 +975,7 @@ mod tests {
         let tokyo_geo_radius = GeoRadius {
             center: TOKYO,
             radius: r_meters,
+            inner_radius: None,
         };
         let field_condition = condition_for_geo_radius("test", tokyo_geo_radius.clone());
         let card = index.estimate_cardinality(&field_condition);
@@ -989,6 +1017,7 @@ mod tests {
         let berlin_geo_radius = GeoRadius {
             center: BERLIN,
             radius: 50_000.0, // Berlin <-> Potsdam is 27 km
+            inner_radius: None,
         };
         // check with geo_radius
         let field_condition = condition_for_geo_radius("test", berlin_geo_radius.clone());
@@ -1041,6 +1070,7 @@ mod tests {
         let berlin_geo_radius = GeoRadius {
             center: BERLIN,
             radius: 50_000.0, // Berlin <-> Potsdam is 27 km
+            inner_radius: None,
         };
 
         // check with geo_radius
@@ -1118,6 +1148,7 @@ mod tests {
                 ],
             },
             interiors: None,
+            crosses_antimeridian: None,
         };
         let polygon_with_interior = GeoPolygon {
             exterior: polygon.exterior.clone(),
@@ -1141,6 +1172,7 @@ mod tests {
                     },
                 ],
             }]),
+            crosses_antimeridian: None,
         };
         let hashes = polygon_hashes(&polygon, GEO_QUERY_MAX_REGION).unwrap();
         let hashes_with_interior =
@@ -1235,5 +1267,22 @@ mod tests {
         let point_offsets = new_index.filter(&field_condition).unwrap().collect_vec();
         // Only LOS_ANGELES is in the bounding box
         assert_eq!(point_offsets, vec![2]);
+
+        // Polygon over the Pacific, from Japan to California
+        let pacific = build_polygon(vec![
+            (130.0, 50.0),
+            (-110.0, 50.0),
+            (-110.0, 20.0),
+            (130.0, 20.0),
+            (130.0, 50.0),
+        ]);
+        let field_condition = condition_for_geo_polygon("test", pacific);
+        let mut point_offsets = new_index.filter(&field_condition).unwrap().collect_vec();
+        point_offsets.sort_unstable();
+        // LOS_ANGELES and TOKYO are in the polygon
+        assert_eq!(point_offsets, vec![2, 3]);
+
+        let card = new_index.estimate_cardinality(&field_condition).unwrap();
+        assert!(card.min <= 2 && 2 <= card.max);
     }
 }
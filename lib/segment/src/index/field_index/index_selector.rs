@@ -73,10 +73,7 @@ impl<'a> IndexSelector<'a> {
                 )]
             }
             PayloadSchemaParams::Bool(_) => {
-                vec![FieldIndex::BinaryIndex(BinaryIndex::new(
-                    self.as_rocksdb()?.db.clone(),
-                    &field.to_string(),
-                ))]
+                vec![FieldIndex::BinaryIndex(self.binary_new(field)?)]
             }
             PayloadSchemaParams::Datetime(_) => {
                 vec![FieldIndex::DatetimeIndex(self.numeric_new(field)?)]
@@ -135,10 +132,7 @@ impl<'a> IndexSelector<'a> {
                 vec![self.text_builder(field, text_index_params.clone())]
             }
             PayloadSchemaParams::Bool(_) => {
-                vec![FieldIndexBuilder::BinaryIndex(BinaryIndex::builder(
-                    self.as_rocksdb()?.db.clone(),
-                    &field.to_string(),
-                ))]
+                vec![self.binary_builder(field)]
             }
             PayloadSchemaParams::Datetime(_) => {
                 vec![self.numeric_builder(
@@ -252,6 +246,33 @@ impl<'a> IndexSelector<'a> {
         }
     }
 
+    fn binary_new(&self, field: &JsonPath) -> OperationResult<BinaryIndex> {
+        Ok(match self {
+            IndexSelector::RocksDb(IndexSelectorRocksDb { db, .. }) => {
+                BinaryIndex::new(Arc::clone(db), &field.to_string())
+            }
+            IndexSelector::OnDisk(IndexSelectorOnDisk { dir }) => {
+                BinaryIndex::new_mmap(&binary_dir(dir, field))?
+            }
+        })
+    }
+
+    fn binary_builder(&self, field: &JsonPath) -> FieldIndexBuilder {
+        match self {
+            IndexSelector::RocksDb(IndexSelectorRocksDb { db, .. }) => {
+                FieldIndexBuilder::BinaryIndex(BinaryIndex::builder(
+                    Arc::clone(db),
+                    &field.to_string(),
+                ))
+            }
+            IndexSelector::OnDisk(IndexSelectorOnDisk { dir }) => {
+                FieldIndexBuilder::BinaryMmapIndex(BinaryIndex::builder_mmap(&binary_dir(
+                    dir, field,
+                )))
+            }
+        }
+    }
+
     fn as_rocksdb(&self) -> OperationResult<&IndexSelectorRocksDb> {
         match self {
             IndexSelector::RocksDb(mode) => Ok(mode),
@@ -271,3 +292,7 @@ fn numeric_dir(dir: &Path, field: &JsonPath) -> PathBuf {
 fn text_dir(dir: &Path, field: &JsonPath) -> PathBuf {
     dir.join(format!("{}-text", &field.filename()))
 }
+
+fn binary_dir(dir: &Path, field: &JsonPath) -> PathBuf {
+    dir.join(format!("{}-binary", &field.filename()))
+}
@@ -31,8 +31,8 @@ use crate::index::field_index::{
 use crate::index::query_estimator::combine_should_estimations;
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
-    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAny, MatchExcept, MatchValue,
-    PayloadKeyType, UuidIntType, ValueVariants,
+    AnyVariants, FieldCondition, IntPayloadType, Match, MatchAll, MatchAny, MatchExcept,
+    MatchValue, PayloadKeyType, UuidIntType, ValueVariants,
 };
 
 pub mod immutable_map_index;
@@ -409,6 +409,75 @@ impl<N: MapIndexKey + ?Sized> MapIndex<N> {
                 .unique(),
         )
     }
+
+    /// Estimates cardinality for `all` clause
+    ///
+    /// # Arguments
+    ///
+    /// * 'required' - values, which all must be present in a matching point
+    fn all_cardinality<K: Borrow<N>>(&self, required: &[K]) -> CardinalityEstimation {
+        let indexed_points = self.get_indexed_points();
+        let value_counts: Vec<_> = required
+            .iter()
+            .map(|val| self.get_count_for_value(val.borrow()).unwrap_or(0))
+            .collect();
+
+        // Maximal case: all points with the rarest value have all other values too
+        let max = value_counts.iter().min().copied().unwrap_or(0);
+
+        // Minimal case: points with different values overlap as little as possible
+        let min = value_counts
+            .iter()
+            .sum::<usize>()
+            .saturating_sub(value_counts.len().saturating_sub(1) * indexed_points);
+
+        // Expected case: values are assigned to points independently of each other
+        let exp = if indexed_points == 0 {
+            0
+        } else {
+            value_counts
+                .iter()
+                .fold(indexed_points as f64, |acc, count| {
+                    acc * *count as f64 / indexed_points as f64
+                })
+                .round() as usize
+        };
+
+        CardinalityEstimation {
+            primary_clauses: vec![],
+            min,
+            exp: exp.max(min).min(max),
+            max,
+        }
+    }
+
+    /// Iterates points, which have all of the `required` values
+    fn all_set<'a, K>(
+        &'a self,
+        mut required: Vec<K>,
+    ) -> Box<dyn Iterator<Item = PointOffsetType> + 'a>
+    where
+        K: Borrow<N> + 'a,
+    {
+        // Iterate over points of the rarest value, and check the others point by point
+        required.sort_by_key(|val| self.get_count_for_value(val.borrow()).unwrap_or(0));
+        let mut required = required.into_iter();
+        let Some(rarest) = required.next() else {
+            return Box::new(iter::empty());
+        };
+        let others: Vec<K> = required.collect();
+
+        Box::new(
+            self.get_iterator(rarest.borrow())
+                .copied()
+                .filter(move |&point_id| {
+                    others.iter().all(|val| {
+                        let val: &N = val.borrow();
+                        self.check_values_any(point_id, |stored| stored == val)
+                    })
+                }),
+        )
+    }
 }
 
 pub struct MapIndexBuilder<N: MapIndexKey + ?Sized>(MapIndex<N>);
@@ -539,6 +608,11 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             },
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Strings(keywords),
+            })) if !keywords.is_empty() => {
+                Some(self.all_set(keywords.iter().map(|k| k.as_str()).collect()))
+            }
             _ => None,
         }
     }
@@ -600,6 +674,15 @@ impl PayloadFieldIndex for MapIndex<str> {
                     }
                 }
             }
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Strings(keywords),
+            })) if !keywords.is_empty() => {
+                let keywords: Vec<_> = keywords.iter().map(|k| k.as_str()).collect();
+                Some(
+                    self.all_cardinality(&keywords)
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())),
+                )
+            }
             _ => None,
         }
     }
@@ -702,6 +785,16 @@ impl PayloadFieldIndex for MapIndex<UuidIntType> {
                     }
                 }
             },
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Strings(uuids_string),
+            })) if !uuids_string.is_empty() => {
+                let uuids: Result<Vec<u128>, _> = uuids_string
+                    .iter()
+                    .map(|uuid_string| Uuid::from_str(uuid_string).map(|x| x.as_u128()))
+                    .collect();
+
+                Some(self.all_set(uuids.ok()?))
+            }
             _ => None,
         }
     }
@@ -778,6 +871,21 @@ impl PayloadFieldIndex for MapIndex<UuidIntType> {
                     }
                 }
             }
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Strings(uuids_string),
+            })) if !uuids_string.is_empty() => {
+                let uuids: Result<Vec<u128>, _> = uuids_string
+                    .iter()
+                    .map(|uuid_string| Uuid::from_str(uuid_string).map(|x| x.as_u128()))
+                    .collect();
+
+                let uuids = uuids.ok()?;
+
+                Some(
+                    self.all_cardinality(&uuids)
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())),
+                )
+            }
             _ => None,
         }
     }
@@ -860,6 +968,9 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                 }
                 AnyVariants::Integers(integers) => Some(self.except_set(integers)),
             },
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Integers(integers),
+            })) if !integers.is_empty() => Some(self.all_set(integers.iter().collect())),
             _ => None,
         }
     }
@@ -919,6 +1030,15 @@ impl PayloadFieldIndex for MapIndex<IntPayloadType> {
                 }
                 AnyVariants::Integers(integers) => Some(self.except_cardinality(integers.iter())),
             },
+            Some(Match::All(MatchAll {
+                all: AnyVariants::Integers(integers),
+            })) if !integers.is_empty() => {
+                let integers: Vec<_> = integers.iter().collect();
+                Some(
+                    self.all_cardinality(&integers)
+                        .with_primary_clause(PrimaryCondition::Condition(condition.clone())),
+                )
+            }
             _ => None,
         }
     }
@@ -1036,6 +1156,7 @@ mod tests {
 
     use super::*;
     use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
+    use crate::json_path::JsonPath;
 
     const FIELD_NAME: &str = "test";
 
@@ -1197,6 +1318,37 @@ mod tests {
             .equals_min_exp_max(&CardinalityEstimation::exact(0)));
     }
 
+    #[rstest]
+    #[case(IndexType::Mutable)]
+    #[case(IndexType::Immutable)]
+    #[case(IndexType::Mmap)]
+    fn test_match_all_map_index(#[case] index_type: IndexType) {
+        let data = vec![vec![1, 2, 3], vec![1, 3], vec![2, 3, 4], vec![1, 2, 3, 4]];
+
+        let temp_dir = Builder::new().prefix("store_dir").tempdir().unwrap();
+        save_map_index::<IntPayloadType>(&data, temp_dir.path(), index_type, |v| (*v).into());
+        let index = load_map_index::<IntPayloadType>(&data, temp_dir.path(), index_type);
+
+        let condition =
+            FieldCondition::new_match(JsonPath::new(FIELD_NAME), Match::All(vec![1, 2].into()));
+
+        let mut points: Vec<_> = index.filter(&condition).unwrap().collect();
+        points.sort_unstable();
+        assert_eq!(points, vec![0, 3]);
+
+        let estimation = index.estimate_cardinality(&condition).unwrap();
+        assert!(estimation.min <= 2 && 2 <= estimation.max);
+        assert_eq!(estimation.max, 3);
+
+        // Empty list also matches empty arrays, which only the payload can tell
+        let condition = FieldCondition::new_match(
+            JsonPath::new(FIELD_NAME),
+            Match::All(Vec::<IntPayloadType>::new().into()),
+        );
+        assert!(index.filter(&condition).is_none());
+        assert!(index.estimate_cardinality(&condition).is_none());
+    }
+
     #[rstest]
     #[case(IndexType::Mutable)]
     #[case(IndexType::Immutable)]
@@ -2,7 +2,7 @@ use std::collections::HashSet;
 
 use common::types::PointOffsetType;
 
-use crate::types::{FieldCondition, IsEmptyCondition, IsNullCondition};
+use crate::types::{FieldCondition, HasFieldCondition, IdRange, IsEmptyCondition, IsNullCondition};
 
 pub(super) mod facet_index;
 mod field_index_base;
@@ -15,6 +15,7 @@ pub mod index_selector;
 pub mod map_index;
 mod mmap_point_to_values;
 pub mod numeric_index;
+pub mod presence_index;
 mod stat_tools;
 
 pub mod binary_index;
@@ -30,7 +31,9 @@ pub enum PrimaryCondition {
     Condition(FieldCondition),
     IsEmpty(IsEmptyCondition),
     IsNull(IsNullCondition),
+    HasField(HasFieldCondition),
     Ids(HashSet<PointOffsetType>),
+    IdRange(IdRange),
 }
 
 #[derive(Debug, Clone)]
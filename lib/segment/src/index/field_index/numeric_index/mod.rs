@@ -124,13 +124,11 @@ impl Encodable for DateTimePayloadType {
 
     fn decode_key(key: &[u8]) -> (PointOffsetType, Self) {
         let (id, timestamp) = decode_i64_key_ascending(key);
-        let datetime =
-            DateTime::from_timestamp(timestamp / 1000, (timestamp % 1000) as u32 * 1_000_000)
-                .unwrap_or_else(|| {
-                    log::warn!("Failed to decode timestamp {timestamp}, fallback to UNIX_EPOCH");
-                    DateTime::UNIX_EPOCH
-                });
-        (id, datetime.into())
+        let datetime = DateTimePayloadType::from_timestamp(timestamp).unwrap_or_else(|| {
+            log::warn!("Failed to decode timestamp {timestamp}, fallback to UNIX_EPOCH");
+            DateTime::UNIX_EPOCH.into()
+        });
+        (id, datetime)
     }
 
     fn cmp_encoded(&self, other: &Self) -> std::cmp::Ordering {
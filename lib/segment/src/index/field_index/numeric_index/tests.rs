@@ -114,6 +114,19 @@ fn cardinality_request(
     estimation
 }
 
+#[test]
+fn test_datetime_key_encoding() {
+    for datetime in [
+        "2020-03-01T01:02:03.123456Z",
+        "1960-03-01T01:02:03.654321+02:00",
+        "1970-01-01T00:00:00Z",
+    ] {
+        let datetime = DateTimePayloadType::from_str(datetime).unwrap();
+        let key = datetime.encode_key(42);
+        assert_eq!(DateTimePayloadType::decode_key(&key), (42, datetime));
+    }
+}
+
 #[test]
 fn test_set_empty_payload() {
     let (_temp_dir, mut index) = random_index(1000, 1, IndexType::Mutable);
@@ -0,0 +1,208 @@
+//! Presence of an indexed payload field in points, used for the `has_field` condition.
+//!
+//! A field is present if it has at least one value which is not `null` or an empty array, so
+//! presence is the opposite of the `is_empty` condition. Presence does not depend on the type of
+//! the values, so it is kept separately from the typed field indexes, and is shared by all of
+//! them.
+
+use std::fs::{remove_dir_all, rename};
+use std::path::{Path, PathBuf};
+
+use common::types::PointOffsetType;
+use serde_json::Value;
+
+use super::FieldIndexBuilderTrait;
+use crate::common::operation_error::OperationResult;
+use crate::common::utils::check_is_empty;
+use crate::common::Flusher;
+use crate::vector_storage::dense::dynamic_mmap_flags::DynamicMmapFlags;
+
+#[derive(Debug)]
+pub struct PresenceIndex {
+    /// Flag for each point, set if the field is present
+    flags: DynamicMmapFlags,
+    /// Number of points with the field present
+    present_count: usize,
+    path: PathBuf,
+}
+
+impl PresenceIndex {
+    /// Open presence flags, which were built with [`PresenceIndexBuilder`]
+    pub fn open(path: &Path) -> OperationResult<Self> {
+        let flags = DynamicMmapFlags::open(path)?;
+        let present_count = flags.count_flags();
+        Ok(Self {
+            flags,
+            present_count,
+            path: path.to_owned(),
+        })
+    }
+
+    /// Update presence of the field in a point by its new values
+    pub fn set_point(
+        &mut self,
+        point_id: PointOffsetType,
+        values: &[&Value],
+    ) -> OperationResult<()> {
+        let present = !check_is_empty(values.iter().copied());
+        if present && point_id as usize >= self.flags.len() {
+            self.flags.set_len(point_id as usize + 1)?;
+        }
+        if point_id as usize >= self.flags.len() {
+            return Ok(());
+        }
+
+        let was_present = self.flags.set(point_id, present);
+        match (was_present, present) {
+            (false, true) => self.present_count += 1,
+            (true, false) => self.present_count -= 1,
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn remove_point(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
+        self.set_point(point_id, &[])
+    }
+
+    pub fn is_present(&self, point_id: PointOffsetType) -> bool {
+        self.flags.get(point_id)
+    }
+
+    /// Number of points with the field present
+    pub fn present_count(&self) -> usize {
+        self.present_count
+    }
+
+    /// Iterate points with the field present
+    pub fn iter_present(&self) -> impl Iterator<Item = PointOffsetType> + '_ {
+        self.flags.get_bitslice()[..self.flags.len()]
+            .iter_ones()
+            .map(|point_id| point_id as PointOffsetType)
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.flags.flusher()
+    }
+
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.flags.files()
+    }
+
+    pub fn clear(self) -> OperationResult<()> {
+        let Self { flags, path, .. } = self;
+        drop(flags);
+        remove_dir_all(path)?;
+        Ok(())
+    }
+}
+
+/// Builds presence flags in a temporary directory first, so that an interrupted build never
+/// leaves incomplete flags behind.
+pub struct PresenceIndexBuilder {
+    path: PathBuf,
+    tmp_path: PathBuf,
+    index: Option<PresenceIndex>,
+}
+
+impl PresenceIndexBuilder {
+    pub fn new(path: &Path) -> Self {
+        let mut tmp_path = path.as_os_str().to_owned();
+        tmp_path.push(".tmp");
+        Self {
+            path: path.to_owned(),
+            tmp_path: PathBuf::from(tmp_path),
+            index: None,
+        }
+    }
+}
+
+impl FieldIndexBuilderTrait for PresenceIndexBuilder {
+    type FieldIndexType = PresenceIndex;
+
+    fn init(&mut self) -> OperationResult<()> {
+        if self.tmp_path.exists() {
+            remove_dir_all(&self.tmp_path)?;
+        }
+        self.index = Some(PresenceIndex::open(&self.tmp_path)?);
+        Ok(())
+    }
+
+    fn add_point(&mut self, id: PointOffsetType, payload: &[&Value]) -> OperationResult<()> {
+        let index = self
+            .index
+            .as_mut()
+            .expect("presence index builder is initialized");
+        index.set_point(id, payload)
+    }
+
+    fn finalize(self) -> OperationResult<PresenceIndex> {
+        let Self {
+            path,
+            tmp_path,
+            index,
+        } = self;
+        if let Some(index) = index {
+            index.flusher()()?;
+        }
+
+        if path.exists() {
+            remove_dir_all(&path)?;
+        }
+        rename(&tmp_path, &path)?;
+        PresenceIndex::open(&path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+
+    #[test]
+    fn test_presence_index() {
+        let dir = Builder::new().prefix("presence_index").tempdir().unwrap();
+        let path = dir.path().join("field-presence");
+
+        let values = [
+            json!(1),
+            json!(null),
+            json!([]),
+            json!([null, "a"]),
+            json!({"a": 1}),
+        ];
+        let mut builder = PresenceIndexBuilder::new(&path);
+        builder.init().unwrap();
+        for (point_id, value) in values.iter().enumerate() {
+            builder
+                .add_point(point_id as PointOffsetType, &[value])
+                .unwrap();
+        }
+        let mut index = builder.finalize().unwrap();
+
+        assert_eq!(index.iter_present().collect::<Vec<_>>(), vec![0, 3, 4]);
+        assert_eq!(index.present_count(), 3);
+        assert!(!index.is_present(1));
+        assert!(!index.is_present(100));
+
+        index.remove_point(0).unwrap();
+        index.set_point(1, &[&json!("b")]).unwrap();
+        index.set_point(2000, &[&json!(true)]).unwrap();
+        index.remove_point(3000).unwrap();
+        assert_eq!(index.present_count(), 4);
+        index.flusher()().unwrap();
+        drop(index);
+
+        let index = PresenceIndex::open(&path).unwrap();
+        assert_eq!(
+            index.iter_present().collect::<Vec<_>>(),
+            vec![1, 3, 4, 2000],
+        );
+        assert_eq!(index.present_count(), 4);
+
+        index.clear().unwrap();
+        assert!(!path.exists());
+    }
+}
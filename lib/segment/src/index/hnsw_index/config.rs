@@ -7,7 +7,7 @@ use crate::common::operation_error::OperationResult;
 
 pub const HNSW_INDEX_CONFIG_FILE: &str = "hnsw_config.json";
 
-#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, Copy, Clone, PartialEq)]
 pub struct HnswGraphConfig {
     pub m: usize,
     /// Requested M
@@ -29,20 +29,31 @@ pub struct HnswGraphConfig {
     pub payload_m0: Option<usize>,
     #[serde(default)]
     pub indexed_vector_count: Option<usize>,
+    /// Level generation multiplier (mL). If not set - `1 / ln(M)` is used
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level_multiplier: Option<f64>,
 }
 
+impl Eq for HnswGraphConfig {}
+
 impl HnswGraphConfig {
+    /// Create graph config from the index parameters.
+    ///
+    /// If `m0` is not set, `2 * m` is used on level 0. It is never smaller than `m`.
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         m: usize,
+        m0: Option<usize>,
         ef_construct: usize,
         full_scan_threshold: usize,
         max_indexing_threads: usize,
         payload_m: Option<usize>,
+        level_multiplier: Option<f64>,
         indexed_vector_count: usize,
     ) -> Self {
         HnswGraphConfig {
             m,
-            m0: m * 2,
+            m0: m0.unwrap_or(m * 2).max(m),
             ef_construct,
             ef: ef_construct,
             full_scan_threshold,
@@ -50,6 +61,7 @@ impl HnswGraphConfig {
             payload_m,
             payload_m0: payload_m.map(|v| v * 2),
             indexed_vector_count: Some(indexed_vector_count),
+            level_multiplier,
         }
     }
 
@@ -0,0 +1,101 @@
+use std::path::Path;
+
+use bitvec::prelude::BitVec;
+use io::file_operations::read_bin;
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::id_tracker::IdTrackerSS;
+use crate::index::hnsw_index::graph_layers::LayersContainer;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// HNSW graph built outside of Qdrant, to be imported instead of building the index.
+///
+/// Points are identified by their internal offsets in the segment, and links have the same layout
+/// as the links of a graph built by Qdrant.
+#[derive(Deserialize, Serialize, Debug, Clone, Default)]
+pub struct ImportedGraph {
+    /// Level of each point
+    pub levels: Vec<usize>,
+    /// Links of each point on each of its levels, from level 0 to the level of the point
+    pub links: Vec<LayersContainer>,
+}
+
+impl ImportedGraph {
+    /// Load a graph serialized with bincode
+    pub fn load(path: &Path) -> OperationResult<Self> {
+        Ok(read_bin(path)?)
+    }
+
+    /// Check that the graph matches the vectors of the segment.
+    ///
+    /// There must be levels and links for every vector of the storage. Links must only connect
+    /// existing points, on levels which both of them are on. Deleted points must not be linked.
+    pub fn validate(
+        &self,
+        id_tracker: &IdTrackerSS,
+        vector_storage: &VectorStorageEnum,
+    ) -> OperationResult<()> {
+        let total_vector_count = vector_storage.total_vector_count();
+
+        if self.levels.len() != total_vector_count || self.links.len() != total_vector_count {
+            return Err(invalid_graph(format!(
+                "graph has levels of {} and links of {} points, but segment has {total_vector_count} vectors",
+                self.levels.len(),
+                self.links.len(),
+            )));
+        }
+
+        let mut present = BitVec::repeat(false, total_vector_count);
+        for offset in id_tracker.iter_ids_excluding(vector_storage.deleted_vector_bitslice()) {
+            if let Some(mut bit) = present.get_mut(offset as usize) {
+                *bit = true;
+            }
+        }
+
+        for (point_id, (&level, layers)) in self.levels.iter().zip(&self.links).enumerate() {
+            if !present[point_id] {
+                if layers.iter().any(|links| !links.is_empty()) {
+                    return Err(invalid_graph(format!(
+                        "point {point_id} is not present in the segment, but has links"
+                    )));
+                }
+                continue;
+            }
+
+            if layers.len() != level + 1 {
+                return Err(invalid_graph(format!(
+                    "point {point_id} is on level {level}, but has links on {} levels",
+                    layers.len(),
+                )));
+            }
+
+            for (link_level, links) in layers.iter().enumerate() {
+                for &link in links {
+                    let is_present = present.get(link as usize).is_some_and(|bit| *bit);
+                    if !is_present {
+                        return Err(invalid_graph(format!(
+                            "point {point_id} links to point {link}, which is not present in the segment"
+                        )));
+                    }
+                    if link as usize == point_id {
+                        return Err(invalid_graph(format!("point {point_id} links to itself")));
+                    }
+                    if self.levels[link as usize] < link_level {
+                        return Err(invalid_graph(format!(
+                            "point {point_id} links to point {link} on level {link_level}, which is above the level of point {link}"
+                        )));
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn invalid_graph(reason: String) -> OperationError {
+    OperationError::ValidationError {
+        description: format!("Invalid HNSW graph: {reason}"),
+    }
+}
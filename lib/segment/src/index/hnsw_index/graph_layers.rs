@@ -70,7 +70,7 @@ pub trait GraphLayersBase {
         let mut points_ids: Vec<PointOffsetType> = Vec::with_capacity(2 * limit);
 
         while let Some(candidate) = searcher.candidates.pop() {
-            if candidate.score < searcher.lower_bound() {
+            if candidate.score < searcher.lower_bound() || !searcher.expand_candidate() {
                 break;
             }
 
@@ -95,10 +95,38 @@ pub trait GraphLayersBase {
         level: usize,
         ef: usize,
         points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        self.search_on_level_from(&[level_entry], level, ef, points_scorer)
+    }
+
+    /// Same as [`Self::search_on_level`], but starts from multiple entry points at once
+    fn search_on_level_from(
+        &self,
+        level_entries: &[ScoredPointOffset],
+        level: usize,
+        ef: usize,
+        points_scorer: &mut FilteredScorer,
+    ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
+        self.search_on_level_bounded(level_entries, level, ef, None, 0, points_scorer)
+    }
+
+    /// Same as [`Self::search_on_level_from`], but expands at most `max_candidates` candidates,
+    /// see [`SearchContext::with_max_candidates`]
+    fn search_on_level_bounded(
+        &self,
+        level_entries: &[ScoredPointOffset],
+        level: usize,
+        ef: usize,
+        max_candidates: Option<usize>,
+        min_nearest: usize,
+        points_scorer: &mut FilteredScorer,
     ) -> FixedLengthPriorityQueue<ScoredPointOffset> {
         let mut visited_list = self.get_visited_list_from_pool();
-        visited_list.check_and_update_visited(level_entry.idx);
-        let mut search_context = SearchContext::new(level_entry, ef);
+        for level_entry in level_entries {
+            visited_list.check_and_update_visited(level_entry.idx);
+        }
+        let mut search_context = SearchContext::with_entries(level_entries.iter().copied(), ef)
+            .with_max_candidates(max_candidates, min_nearest);
 
         self._search_on_level(&mut search_context, level, &mut visited_list, points_scorer);
         search_context.nearest
@@ -142,6 +170,28 @@ pub trait GraphLayersBase {
         }
         current_point
     }
+
+    /// Beam searches for entry points of level `target_level`.
+    /// Keeps `beam_width` best candidates on each level instead of a single one.
+    fn search_entry_beam(
+        &self,
+        entry_point: PointOffsetType,
+        top_level: usize,
+        target_level: usize,
+        beam_width: usize,
+        points_scorer: &mut FilteredScorer,
+    ) -> Vec<ScoredPointOffset> {
+        let mut entries = vec![ScoredPointOffset {
+            idx: entry_point,
+            score: points_scorer.score_point(entry_point),
+        }];
+        for level in rev_range(top_level, target_level) {
+            entries = self
+                .search_on_level_from(&entries, level, beam_width, points_scorer)
+                .into_vec();
+        }
+        entries
+    }
 }
 
 impl<TGraphLinks: GraphLinks> GraphLayersBase for GraphLayers<TGraphLinks> {
@@ -204,6 +254,26 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
         &self,
         top: usize,
         ef: usize,
+        points_scorer: FilteredScorer,
+        custom_entry_points: Option<&[PointOffsetType]>,
+    ) -> Vec<ScoredPointOffset> {
+        self.search_with_params(top, ef, None, None, points_scorer, custom_entry_points)
+    }
+
+    /// Search the graph, optionally descending the upper layers with a beam instead of greedily.
+    ///
+    /// `ef` is the size of the candidate list on the bottom layer, where results are collected.
+    /// `beam_width` is the number of candidates kept on each of the upper layers, all of which
+    /// are used as entry points of the bottom layer. It is capped by `ef`, so a beam never holds
+    /// more candidates than the bottom layer search can accept. `None` or `1` is a greedy descent.
+    /// `max_candidates` bounds the number of candidates expanded on the bottom layer, the search
+    /// stops there with the best points found so far, but not before `top` points are found.
+    pub fn search_with_params(
+        &self,
+        top: usize,
+        ef: usize,
+        beam_width: Option<usize>,
+        max_candidates: Option<usize>,
         mut points_scorer: FilteredScorer,
         custom_entry_points: Option<&[PointOffsetType]>,
     ) -> Vec<ScoredPointOffset> {
@@ -211,13 +281,30 @@ impl<TGraphLinks: GraphLinks> GraphLayers<TGraphLinks> {
             return Vec::default();
         };
 
-        let zero_level_entry = self.search_entry(
-            entry_point.point_id,
-            entry_point.level,
+        let ef = max(top, ef);
+        let zero_level_entries = match beam_width.map(|beam_width| beam_width.min(ef)) {
+            None | Some(0 | 1) => vec![self.search_entry(
+                entry_point.point_id,
+                entry_point.level,
+                0,
+                &mut points_scorer,
+            )],
+            Some(beam_width) => self.search_entry_beam(
+                entry_point.point_id,
+                entry_point.level,
+                0,
+                beam_width,
+                &mut points_scorer,
+            ),
+        };
+        let nearest = self.search_on_level_bounded(
+            &zero_level_entries,
             0,
+            ef,
+            max_candidates,
+            top,
             &mut points_scorer,
         );
-        let nearest = self.search_on_level(zero_level_entry, 0, max(top, ef), &mut points_scorer);
         nearest.into_iter().take(top).collect_vec()
     }
 
@@ -469,6 +556,93 @@ mod tests {
         assert_eq!(reference_top.into_vec(), graph_search);
     }
 
+    #[test]
+    fn test_search_with_beam() {
+        let num_vectors = 1000;
+        let num_queries = 10;
+        let dim = 8;
+        let top = 5;
+        let ef = 16;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers) =
+            create_graph_layer_fixture::<M, _>(num_vectors, M, dim, false, &mut rng, None);
+
+        let fake_filter_context = FakeFilterContext {};
+        let mut hits = 0;
+        for _ in 0..num_queries {
+            let query = random_vector(&mut rng, dim);
+            let processed_query = <M as Metric<VectorElementType>>::preprocess(query.clone());
+            let mut reference_top = FixedLengthPriorityQueue::new(top);
+            for idx in 0..vector_holder.vectors.len() as PointOffsetType {
+                let vec = &vector_holder.vectors.get(idx as VectorOffsetType);
+                reference_top.push(ScoredPointOffset {
+                    idx,
+                    score: M::similarity(vec, &processed_query),
+                });
+            }
+            let reference_top = reference_top.into_vec();
+
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let search = |beam_width| {
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                graph_layers.search_with_params(top, ef, beam_width, None, scorer, None)
+            };
+
+            // Beam of width 1 is the same as greedy search
+            assert_eq!(search(None), search(Some(1)));
+
+            // Beam width is capped by ef
+            let beam_search = search(Some(ef));
+            assert_eq!(beam_search, search(Some(4 * ef)));
+
+            assert_eq!(beam_search.len(), top);
+            hits += beam_search
+                .iter()
+                .filter(|point| reference_top.contains(point))
+                .count();
+        }
+
+        let recall = hits as f64 / (top * num_queries) as f64;
+        assert!(recall >= 0.9, "recall {recall}");
+    }
+
+    #[test]
+    fn test_search_with_max_candidates() {
+        let num_vectors = 1000;
+        let num_queries = 10;
+        let dim = 8;
+        let top = 5;
+        let ef = 64;
+
+        let mut rng = StdRng::seed_from_u64(42);
+
+        type M = CosineMetric;
+
+        let (vector_holder, graph_layers) =
+            create_graph_layer_fixture::<M, _>(num_vectors, M, dim, false, &mut rng, None);
+
+        let fake_filter_context = FakeFilterContext {};
+        for _ in 0..num_queries {
+            let query = random_vector(&mut rng, dim);
+            let raw_scorer = vector_holder.get_raw_scorer(query).unwrap();
+            let search = |top, max_candidates| {
+                let scorer = FilteredScorer::new(raw_scorer.as_ref(), Some(&fake_filter_context));
+                graph_layers.search_with_params(top, ef, None, max_candidates, scorer, None)
+            };
+
+            // A search which converges before the cutoff is not affected
+            assert_eq!(search(top, None), search(top, Some(num_vectors)));
+
+            // A search cut off after the first candidate still returns enough results
+            assert_eq!(search(top, Some(1)).len(), top);
+            assert_eq!(search(ef, Some(1)).len(), ef);
+        }
+    }
+
     #[test]
     #[ignore]
     fn test_draw_hnsw_graph() {
@@ -13,7 +13,9 @@ use rand::Rng;
 use super::graph_links::GraphLinks;
 use crate::common::operation_error::OperationResult;
 use crate::index::hnsw_index::entry_points::EntryPoints;
-use crate::index::hnsw_index::graph_layers::{GraphLayers, GraphLayersBase, LinkContainer};
+use crate::index::hnsw_index::graph_layers::{
+    GraphLayers, GraphLayersBase, LayersContainer, LinkContainer,
+};
 use crate::index::hnsw_index::graph_links::GraphLinksConverter;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::hnsw_index::search_context::SearchContext;
@@ -31,6 +33,8 @@ pub struct GraphLayersBuilder {
     ef_construct: usize,
     // Factor of level probability
     level_factor: f64,
+    // Highest level a point can be assigned to
+    max_level_cap: usize,
     // Exclude points according to "not closer than base" heuristic?
     use_heuristic: bool,
     links_layers: Vec<LockedLayersContainer>,
@@ -101,6 +105,7 @@ impl GraphLayersBuilder {
         })
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new_with_params(
         num_vectors: usize, // Initial number of points in index
         m: usize,           // Expected M for non-first layer
@@ -109,6 +114,7 @@ impl GraphLayersBuilder {
         entry_points_num: usize, // Depends on number of points
         use_heuristic: bool,
         reserve: bool,
+        level_multiplier: Option<f64>, // Defaults to 1 / ln(M)
     ) -> Self {
         let links_layers = std::iter::repeat_with(|| {
             vec![RwLock::new(if reserve {
@@ -127,7 +133,8 @@ impl GraphLayersBuilder {
             m,
             m0,
             ef_construct,
-            level_factor: 1.0 / (max(m, 2) as f64).ln(),
+            level_factor: level_multiplier.unwrap_or_else(|| Self::default_level_factor(m)),
+            max_level_cap: Self::max_level_cap(num_vectors),
             use_heuristic,
             links_layers,
             entry_points: Mutex::new(EntryPoints::new(entry_points_num)),
@@ -152,9 +159,24 @@ impl GraphLayersBuilder {
             entry_points_num,
             use_heuristic,
             true,
+            None,
         )
     }
 
+    /// Standard level generation multiplier (mL) from the HNSW paper
+    pub fn default_level_factor(m: usize) -> f64 {
+        1.0 / (max(m, 2) as f64).ln()
+    }
+
+    /// Upper bound for the level of a single point.
+    ///
+    /// With M >= 2 each layer is expected to hold at most half of the points of the layer below,
+    /// so levels above `log2(num_vectors)` would only contain single-point chains.
+    /// Prevents large custom level multipliers from producing too many levels on small datasets.
+    fn max_level_cap(num_vectors: usize) -> usize {
+        (max(num_vectors, 2) as f64).log2().ceil() as usize
+    }
+
     pub fn merge_from_other(&mut self, other: GraphLayersBuilder) {
         self.max_level = AtomicUsize::new(max(
             self.max_level.load(std::sync::atomic::Ordering::Relaxed),
@@ -203,7 +225,7 @@ impl GraphLayersBuilder {
         let distribution = Uniform::new(0.0, 1.0);
         let sample: f64 = rng.sample(distribution);
         let picked_level = -sample.ln() * self.level_factor;
-        picked_level.round() as usize
+        min(picked_level.round() as usize, self.max_level_cap)
     }
 
     fn get_point_level(&self, point_id: PointOffsetType) -> usize {
@@ -225,6 +247,23 @@ impl GraphLayersBuilder {
             .fetch_max(level, std::sync::atomic::Ordering::Relaxed);
     }
 
+    /// Set links of a point from a graph built elsewhere, instead of linking it.
+    ///
+    /// `layers` contains links of the point from level 0 to the level of the point.
+    pub fn set_point_links(&mut self, point_id: PointOffsetType, layers: LayersContainer) {
+        let level = layers.len().saturating_sub(1);
+        self.set_levels(point_id, level);
+
+        for (point_links, links) in self.links_layers[point_id as usize].iter_mut().zip(layers) {
+            *point_links.get_mut() = links;
+        }
+
+        self.entry_points
+            .get_mut()
+            .new_point(point_id, level, |_| true);
+        self.ready_list.get_mut().set(point_id as usize, true);
+    }
+
     /// Connect new point to links, so that links contains only closest points
     fn connect_new_point<F>(
         links: &mut LinkContainer,
@@ -890,4 +929,49 @@ mod tests {
             .for_each(|x| result.push(*x));
         assert_eq!(&result, &vec![1, 2, 3, 4, 5, 6]);
     }
+
+    #[test]
+    fn test_custom_level_multiplier() {
+        let num_points = 100;
+        let m = 16;
+        let mut rng = StdRng::seed_from_u64(42);
+
+        let default_builder = GraphLayersBuilder::new(num_points, m, m * 2, 100, 1, true);
+        assert_eq!(
+            default_builder.level_factor,
+            GraphLayersBuilder::default_level_factor(m)
+        );
+
+        // Flat graph: all points are on the bottom layer
+        let flat_builder = GraphLayersBuilder::new_with_params(
+            num_points,
+            m,
+            m * 2,
+            100,
+            1,
+            true,
+            false,
+            Some(0.0),
+        );
+        for _ in 0..1000 {
+            assert_eq!(flat_builder.get_random_layer(&mut rng), 0);
+        }
+
+        // Huge multiplier: levels are capped by log2(num_points)
+        let tall_builder = GraphLayersBuilder::new_with_params(
+            num_points,
+            m,
+            m * 2,
+            100,
+            1,
+            true,
+            false,
+            Some(1000.0),
+        );
+        let max_level = (0..1000)
+            .map(|_| tall_builder.get_random_layer(&mut rng))
+            .max()
+            .unwrap();
+        assert_eq!(max_level, 7);
+    }
 }
@@ -14,7 +14,8 @@ use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
 use log::debug;
 use memory::mmap_ops;
 use parking_lot::Mutex;
-use rand::thread_rng;
+use rand::rngs::StdRng;
+use rand::SeedableRng as _;
 use rayon::prelude::*;
 use rayon::ThreadPool;
 
@@ -27,16 +28,20 @@ use crate::common::BYTES_IN_KB;
 use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::{QueryVector, Vector, VectorRef};
 use crate::id_tracker::IdTrackerSS;
+use crate::index::build_progress::BuildProgress;
 use crate::index::hnsw_index::build_condition_checker::BuildConditionChecker;
 use crate::index::hnsw_index::config::HnswGraphConfig;
+use crate::index::hnsw_index::graph_import::ImportedGraph;
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::partitions::HnswPartition;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
 use crate::index::query_estimator::adjust_to_available_vectors;
 use crate::index::sample_estimation::sample_check_cardinality;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::visited_pool::{VisitedListHandle, VisitedPool};
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::payload_storage::FilterContext;
 use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::Condition::Field;
 use crate::types::{
@@ -51,6 +56,9 @@ use crate::vector_storage::{
 
 const HNSW_USE_HEURISTIC: bool = true;
 
+/// Seed of point levels, for a deterministic build
+const DETERMINISTIC_BUILD_SEED: u64 = 42;
+
 /// Build first N points in HNSW graph using only a single thread, to avoid
 /// disconnected components in the graph.
 #[cfg(debug_assertions)]
@@ -58,6 +66,9 @@ const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 32;
 #[cfg(not(debug_assertions))]
 const SINGLE_THREADED_HNSW_BUILD_THRESHOLD: usize = 256;
 
+/// Max factor by which `ef` is increased, when a filtered search finds fewer points than requested
+const MAX_EF_ESCALATION: usize = 8;
+
 #[derive(Debug)]
 pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
@@ -67,6 +78,8 @@ pub struct HNSWIndex<TGraphLinks: GraphLinks> {
     config: HnswGraphConfig,
     path: PathBuf,
     graph: GraphLayers<TGraphLinks>,
+    /// Separate graphs of the points with some keyword values, see [`HnswPartition`]
+    partitions: Vec<(HnswPartition, GraphLayers<TGraphLinks>)>,
     searches_telemetry: HNSWSearchesTelemetry,
 }
 
@@ -89,6 +102,8 @@ pub struct HnswIndexOpenArgs<'a> {
     pub hnsw_config: HnswConfig,
     pub permit: Option<Arc<CpuPermit>>,
     pub stopped: &'a AtomicBool,
+    /// Receives progress of the build, if the index is built rather than loaded
+    pub progress: Option<&'a dyn BuildProgress>,
 }
 
 impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
@@ -102,6 +117,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             hnsw_config,
             permit,
             stopped,
+            progress,
         } = args;
 
         create_dir_all(path)?;
@@ -109,7 +125,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let config_path = HnswGraphConfig::get_config_path(path);
         let graph_path = GraphLayers::<TGraphLinks>::get_path(path);
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
-        let (config, graph) = if graph_path.exists() {
+        let (config, graph, partitions) = if graph_path.exists() {
             let config = if config_path.exists() {
                 HnswGraphConfig::load(&config_path)?
             } else {
@@ -128,15 +144,19 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
                 HnswGraphConfig::new(
                     hnsw_config.m,
+                    hnsw_config.m0,
                     hnsw_config.ef_construct,
                     full_scan_threshold,
                     hnsw_config.max_indexing_threads,
                     hnsw_config.payload_m,
+                    hnsw_config.level_multiplier,
                     available_vectors,
                 )
             };
 
-            (config, GraphLayers::load(&graph_path, &graph_links_path)?)
+            let graph = GraphLayers::load(&graph_path, &graph_links_path)?;
+            let partitions = Self::load_partitions(path)?;
+            (config, graph, partitions)
         } else {
             let num_cpus = match permit {
                 Some(p) => p.num_cpus as usize,
@@ -151,7 +171,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                     get_num_cpus()
                 }
             };
-            let (config, graph) = Self::build_index(
+            let (config, graph, partitions) = Self::build_index(
                 path,
                 id_tracker.as_ref().borrow().deref(),
                 &vector_storage.borrow(),
@@ -160,12 +180,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 hnsw_config,
                 num_cpus,
                 stopped,
+                progress,
             )?;
 
             config.save(&config_path)?;
             graph.save(&graph_path)?;
 
-            (config, graph)
+            (config, graph, partitions)
         };
 
         Ok(HNSWIndex {
@@ -176,6 +197,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             config,
             path: path.to_owned(),
             graph,
+            partitions,
             searches_telemetry: HNSWSearchesTelemetry {
                 unfiltered_hnsw: OperationDurationsAggregator::new(),
                 unfiltered_plain: OperationDurationsAggregator::new(),
@@ -187,11 +209,105 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         })
     }
 
+    /// Create the index from a graph built outside of Qdrant, instead of building it.
+    ///
+    /// The graph is validated against the vectors of the segment, and replaces the graph stored
+    /// in the index directory, if any.
+    pub fn import(args: HnswIndexOpenArgs<'_>, graph: ImportedGraph) -> OperationResult<Self> {
+        let path = args.path;
+        create_dir_all(path)?;
+
+        let (config, graph_layers) = {
+            let id_tracker = args.id_tracker.borrow();
+            let vector_storage = args.vector_storage.borrow();
+            graph.validate(id_tracker.deref(), &vector_storage)?;
+
+            let total_vector_count = vector_storage.total_vector_count();
+            let full_scan_threshold = Self::full_scan_threshold(&vector_storage, &args.hnsw_config);
+            let present_ids: Vec<_> = id_tracker
+                .iter_ids_excluding(vector_storage.deleted_vector_bitslice())
+                .collect();
+
+            let config = HnswGraphConfig::new(
+                args.hnsw_config.m,
+                args.hnsw_config.m0,
+                args.hnsw_config.ef_construct,
+                full_scan_threshold,
+                args.hnsw_config.max_indexing_threads,
+                args.hnsw_config.payload_m,
+                args.hnsw_config.level_multiplier,
+                present_ids.len(),
+            );
+
+            let mut graph_layers_builder = GraphLayersBuilder::new_with_params(
+                total_vector_count,
+                config.m,
+                config.m0,
+                config.ef_construct,
+                Self::entry_points_num(total_vector_count, full_scan_threshold),
+                HNSW_USE_HEURISTIC,
+                false,
+                config.level_multiplier,
+            );
+
+            let mut links = graph.links;
+            for point_id in present_ids {
+                check_process_stopped(args.stopped)?;
+                let layers = std::mem::take(&mut links[point_id as usize]);
+                graph_layers_builder.set_point_links(point_id, layers);
+            }
+
+            let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
+            let graph_layers: GraphLayers<TGraphLinks> =
+                graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
+
+            (config, graph_layers)
+        };
+
+        config.save(&HnswGraphConfig::get_config_path(path))?;
+        graph_layers.save(&GraphLayers::<TGraphLinks>::get_path(path))?;
+        // The imported graph replaces partitions of a previous index as well
+        HnswPartition::save_list(path, &[])?;
+
+        // Load the index from the saved graph
+        Self::open(args)
+    }
+
+    /// Max number of vectors to search with a full scan rather than the graph
+    fn full_scan_threshold(vector_storage: &VectorStorageEnum, hnsw_config: &HnswConfig) -> usize {
+        vector_storage
+            .available_size_in_bytes()
+            .checked_div(vector_storage.total_vector_count())
+            .and_then(|avg_vector_size| {
+                hnsw_config
+                    .full_scan_threshold
+                    .saturating_mul(BYTES_IN_KB)
+                    .checked_div(avg_vector_size)
+            })
+            .unwrap_or(1)
+    }
+
+    /// Number of extra entry points of the graph
+    fn entry_points_num(total_vector_count: usize, full_scan_threshold: usize) -> usize {
+        std::cmp::max(
+            1,
+            total_vector_count
+                .checked_div(full_scan_threshold)
+                .unwrap_or(0)
+                * 10,
+        )
+    }
+
     #[cfg(test)]
     pub(super) fn graph(&self) -> &GraphLayers<TGraphLinks> {
         &self.graph
     }
 
+    #[cfg(test)]
+    pub(super) fn partitions(&self) -> &[(HnswPartition, GraphLayers<TGraphLinks>)] {
+        &self.partitions
+    }
+
     pub fn get_quantized_vectors(&self) -> Arc<AtomicRefCell<Option<QuantizedVectors>>> {
         self.quantized_vectors.clone()
     }
@@ -206,75 +322,60 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         hnsw_config: HnswConfig,
         num_cpus: usize,
         stopped: &AtomicBool,
-    ) -> OperationResult<(HnswGraphConfig, GraphLayers<TGraphLinks>)> {
+        progress: Option<&dyn BuildProgress>,
+    ) -> OperationResult<(
+        HnswGraphConfig,
+        GraphLayers<TGraphLinks>,
+        Vec<(HnswPartition, GraphLayers<TGraphLinks>)>,
+    )> {
         let total_vector_count = vector_storage.total_vector_count();
 
-        let full_scan_threshold = vector_storage
-            .available_size_in_bytes()
-            .checked_div(total_vector_count)
-            .and_then(|avg_vector_size| {
-                hnsw_config
-                    .full_scan_threshold
-                    .saturating_mul(BYTES_IN_KB)
-                    .checked_div(avg_vector_size)
-            })
-            .unwrap_or(1);
+        let full_scan_threshold = Self::full_scan_threshold(vector_storage, &hnsw_config);
 
         let mut config = HnswGraphConfig::new(
             hnsw_config.m,
+            hnsw_config.m0,
             hnsw_config.ef_construct,
             full_scan_threshold,
             hnsw_config.max_indexing_threads,
             hnsw_config.payload_m,
+            hnsw_config.level_multiplier,
             total_vector_count,
         );
 
+        // A deterministic build inserts points in a single thread, with seeded point levels
+        let deterministic = hnsw_config.deterministic_build.unwrap_or(false);
+
         // Build main index graph
-        let mut rng = thread_rng();
+        let mut rng = if deterministic {
+            StdRng::seed_from_u64(DETERMINISTIC_BUILD_SEED)
+        } else {
+            StdRng::from_entropy()
+        };
         let deleted_bitslice = vector_storage.deleted_vector_bitslice();
 
-        debug!("building HNSW for {total_vector_count} vectors with {num_cpus} CPUs");
+        if deterministic {
+            debug!("building HNSW for {total_vector_count} vectors deterministically");
+        } else {
+            debug!("building HNSW for {total_vector_count} vectors with {num_cpus} CPUs");
+        }
 
-        let mut graph_layers_builder = GraphLayersBuilder::new(
+        let mut graph_layers_builder = GraphLayersBuilder::new_with_params(
             total_vector_count,
             config.m,
             config.m0,
             config.ef_construct,
-            std::cmp::max(
-                1,
-                total_vector_count
-                    .checked_div(full_scan_threshold)
-                    .unwrap_or(0)
-                    * 10,
-            ),
+            Self::entry_points_num(total_vector_count, full_scan_threshold),
             HNSW_USE_HEURISTIC,
+            true,
+            config.level_multiplier,
         );
 
-        let pool = rayon::ThreadPoolBuilder::new()
-            .thread_name(|idx| format!("hnsw-build-{idx}"))
-            .num_threads(num_cpus)
-            .spawn_handler(|thread| {
-                let mut b = thread::Builder::new();
-                if let Some(name) = thread.name() {
-                    b = b.name(name.to_owned());
-                }
-                if let Some(stack_size) = thread.stack_size() {
-                    b = b.stack_size(stack_size);
-                }
-                b.spawn(|| {
-                    // On Linux, use lower thread priority so we interfere less with serving traffic
-                    #[cfg(target_os = "linux")]
-                    if let Err(err) = linux_low_thread_priority() {
-                        log::debug!(
-                            "Failed to set low thread priority for HNSW building, ignoring: {err}"
-                        );
-                    }
-
-                    thread.run()
-                })?;
-                Ok(())
-            })
-            .build()?;
+        let pool = if deterministic {
+            None
+        } else {
+            Some(Self::build_pool(num_cpus)?)
+        };
 
         for vector_id in id_tracker.iter_ids_excluding(deleted_bitslice) {
             check_process_stopped(stopped)?;
@@ -283,6 +384,7 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
 
         let mut indexed_vectors = 0;
+        let mut total_progress = 0;
 
         if config.m > 0 {
             let mut ids_iterator = id_tracker.iter_ids_excluding(deleted_bitslice);
@@ -294,6 +396,10 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             let ids: Vec<_> = ids_iterator.collect();
 
             indexed_vectors = ids.len() + first_few_ids.len();
+            total_progress = indexed_vectors;
+            if let Some(progress) = progress {
+                progress.set_total(total_progress);
+            }
 
             let insert_point = |vector_id| {
                 check_process_stopped(stopped)?;
@@ -312,6 +418,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), None);
 
                 graph_layers_builder.link_new_point(vector_id, points_scorer);
+                if let Some(progress) = progress {
+                    progress.inc(1);
+                }
                 Ok::<_, OperationError>(())
             };
 
@@ -319,8 +428,11 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 insert_point(vector_id)?;
             }
 
-            if !ids.is_empty() {
-                pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+            match &pool {
+                Some(pool) if !ids.is_empty() => {
+                    pool.install(|| ids.into_par_iter().try_for_each(insert_point))?;
+                }
+                _ => ids.into_iter().try_for_each(insert_point)?,
             }
 
             debug!("finish main graph");
@@ -341,7 +453,11 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 graph_layers_builder.get_average_connectivity_on_level(0);
             let average_links_per_0_level_int = (average_links_per_0_level as usize).max(1);
 
-            for (field, _) in payload_index.indexed_fields() {
+            // Merge additional graphs in a stable order, for a deterministic build
+            let mut indexed_fields: Vec<_> = payload_index.indexed_fields().into_keys().collect();
+            indexed_fields.sort_by_cached_key(|field| field.to_string());
+
+            for field in indexed_fields {
                 debug!("building additional index for field {}", &field);
 
                 // It is expected, that graph will become disconnected less than
@@ -369,17 +485,20 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                         1,
                         HNSW_USE_HEURISTIC,
                         false,
+                        config.level_multiplier,
                     );
                     Self::build_filtered_graph(
                         id_tracker,
                         vector_storage,
                         quantized_vectors,
                         payload_index,
-                        &pool,
+                        pool.as_ref(),
                         stopped,
+                        progress.map(|progress| (progress, &mut total_progress)),
                         &mut additional_graph,
                         payload_block.condition,
                         &mut block_filter_list,
+                        None,
                     )?;
                     graph_layers_builder.merge_from_other(additional_graph);
                 }
@@ -396,6 +515,60 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         config.indexed_vector_count.replace(indexed_vectors);
 
+        let declared_partitions = if config.m > 0 {
+            HnswPartition::declared(&payload_index.indexed_fields())
+        } else {
+            debug!("skip building HNSW partitions");
+            Vec::new()
+        };
+        let mut partitions = Vec::with_capacity(declared_partitions.len());
+        for (number, partition) in declared_partitions.into_iter().enumerate() {
+            debug!(
+                "building HNSW partition {}={}",
+                &partition.key, &partition.value
+            );
+            // Same parameters as the main graph, but only points of the partition are linked,
+            // and get levels of their own
+            let mut partition_graph = GraphLayersBuilder::new_with_params(
+                total_vector_count,
+                config.m,
+                config.m0,
+                config.ef_construct,
+                Self::entry_points_num(total_vector_count, full_scan_threshold),
+                HNSW_USE_HEURISTIC,
+                false,
+                config.level_multiplier,
+            );
+            Self::build_filtered_graph(
+                id_tracker,
+                vector_storage,
+                quantized_vectors,
+                payload_index,
+                pool.as_ref(),
+                stopped,
+                progress.map(|progress| (progress, &mut total_progress)),
+                &mut partition_graph,
+                partition.condition(),
+                &mut block_filter_list,
+                Some(&mut rng),
+            )?;
+
+            let partition_dir = HnswPartition::graph_dir(path, number);
+            create_dir_all(&partition_dir)?;
+            let graph: GraphLayers<TGraphLinks> = partition_graph.into_graph_layers(Some(
+                &GraphLayers::<TGraphLinks>::get_links_path(&partition_dir),
+            ))?;
+            graph.save(&GraphLayers::<TGraphLinks>::get_path(&partition_dir))?;
+            partitions.push((partition, graph));
+        }
+        HnswPartition::save_list(
+            path,
+            &partitions
+                .iter()
+                .map(|(partition, _)| partition.clone())
+                .collect::<Vec<_>>(),
+        )?;
+
         let graph_links_path = GraphLayers::<TGraphLinks>::get_links_path(path);
         let graph: GraphLayers<TGraphLinks> =
             graph_layers_builder.into_graph_layers(Some(&graph_links_path))?;
@@ -410,7 +583,36 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         }
 
         debug!("finish additional payload field indexing");
-        Ok((config, graph))
+        Ok((config, graph, partitions))
+    }
+
+    fn load_partitions(
+        path: &Path,
+    ) -> OperationResult<Vec<(HnswPartition, GraphLayers<TGraphLinks>)>> {
+        HnswPartition::load_list(path)?
+            .into_iter()
+            .enumerate()
+            .map(|(number, partition)| {
+                let partition_dir = HnswPartition::graph_dir(path, number);
+                let graph = GraphLayers::load(
+                    &GraphLayers::<TGraphLinks>::get_path(&partition_dir),
+                    &GraphLayers::<TGraphLinks>::get_links_path(&partition_dir),
+                )?;
+                Ok((partition, graph))
+            })
+            .collect()
+    }
+
+    /// Graph to search with the given filter: the graph of the partition containing all points
+    /// matching the filter if there is one, the main graph otherwise
+    fn graph_for_filter(&self, filter: Option<&Filter>) -> &GraphLayers<TGraphLinks> {
+        filter
+            .and_then(|filter| {
+                self.partitions
+                    .iter()
+                    .find(|(partition, _)| partition.contains_filter(filter))
+            })
+            .map_or(&self.graph, |(_, graph)| graph)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -419,11 +621,13 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         vector_storage: &VectorStorageEnum,
         quantized_vectors: &Option<QuantizedVectors>,
         payload_index: &StructPayloadIndex,
-        pool: &ThreadPool,
+        pool: Option<&ThreadPool>,
         stopped: &AtomicBool,
+        progress: Option<(&dyn BuildProgress, &mut usize)>,
         graph_layers_builder: &mut GraphLayersBuilder,
         condition: FieldCondition,
         block_filter_list: &mut VisitedListHandle,
+        levels_rng: Option<&mut StdRng>,
     ) -> OperationResult<()> {
         block_filter_list.next_iteration();
 
@@ -447,6 +651,20 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
             block_filter_list.check_and_update_visited(block_point_id);
         }
 
+        // A standalone graph rather than links to merge into the main graph, needs point levels
+        if let Some(rng) = levels_rng {
+            for point_id in points_to_index.iter().copied() {
+                let level = graph_layers_builder.get_random_layer(rng);
+                graph_layers_builder.set_levels(point_id, level);
+            }
+        }
+
+        let progress = progress.map(|(progress, total)| {
+            *total += points_to_index.len();
+            progress.set_total(*total);
+            progress
+        });
+
         let insert_points = |block_point_id| {
             check_process_stopped(stopped)?;
 
@@ -469,6 +687,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
                 FilteredScorer::new(raw_scorer.as_ref(), Some(&block_condition_checker));
 
             graph_layers_builder.link_new_point(block_point_id, points_scorer);
+            if let Some(progress) = progress {
+                progress.inc(1);
+            }
             Ok::<_, OperationError>(())
         };
 
@@ -484,20 +705,58 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         // So that each thread will insert points in different parts of the graph,
         // it is less likely that they will compete for the same locks
         if points_to_index.len() > first_points {
-            pool.install(|| {
-                points_to_index
-                    .into_par_iter()
-                    .skip(first_points)
-                    .try_for_each(insert_points)
-            })?;
+            match pool {
+                Some(pool) => pool.install(|| {
+                    points_to_index
+                        .into_par_iter()
+                        .skip(first_points)
+                        .try_for_each(insert_points)
+                })?,
+                None => points_to_index[first_points..]
+                    .iter()
+                    .copied()
+                    .try_for_each(insert_points)?,
+            }
         }
         Ok(())
     }
 
+    /// Thread pool to insert points into the graph in parallel
+    fn build_pool(num_cpus: usize) -> OperationResult<ThreadPool> {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .thread_name(|idx| format!("hnsw-build-{idx}"))
+            .num_threads(num_cpus)
+            .spawn_handler(|thread| {
+                let mut b = thread::Builder::new();
+                if let Some(name) = thread.name() {
+                    b = b.name(name.to_owned());
+                }
+                if let Some(stack_size) = thread.stack_size() {
+                    b = b.stack_size(stack_size);
+                }
+                b.spawn(|| {
+                    // On Linux, use lower thread priority so we interfere less with serving traffic
+                    #[cfg(target_os = "linux")]
+                    if let Err(err) = linux_low_thread_priority() {
+                        log::debug!(
+                            "Failed to set low thread priority for HNSW building, ignoring: {err}"
+                        );
+                    }
+
+                    thread.run()
+                })?;
+                Ok(())
+            })
+            .build()?;
+        Ok(pool)
+    }
+
+    #[allow(clippy::too_many_arguments)]
     fn search_with_graph(
         &self,
+        graph: &GraphLayers<TGraphLinks>,
         vector: &QueryVector,
-        filter: Option<&Filter>,
+        filter_context: Option<&dyn FilterContext>,
         top: usize,
         params: Option<&SearchParams>,
         custom_entry_points: Option<&[PointOffsetType]>,
@@ -506,11 +765,12 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let ef = params
             .and_then(|params| params.hnsw_ef)
             .unwrap_or(self.config.ef);
+        let beam_width = params.and_then(|params| params.hnsw_beam_width);
+        let max_candidates = params.and_then(|params| params.hnsw_max_candidates);
 
         let is_stopped = vector_query_context.is_stopped();
 
         let id_tracker = self.id_tracker.borrow();
-        let payload_index = self.payload_index.borrow();
         let vector_storage = self.vector_storage.borrow();
         let quantized_vectors = self.quantized_vectors.borrow();
 
@@ -528,12 +788,16 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         )?;
         let oversampled_top = Self::get_oversampled_top(quantized_vectors.as_ref(), params, top);
 
-        let filter_context = filter.map(|f| payload_index.filter_context(f));
-        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context.as_deref());
+        let points_scorer = FilteredScorer::new(raw_scorer.as_ref(), filter_context);
 
-        let search_result =
-            self.graph
-                .search(oversampled_top, ef, points_scorer, custom_entry_points);
+        let search_result = graph.search_with_params(
+            oversampled_top,
+            ef,
+            beam_width,
+            max_candidates,
+            points_scorer,
+            custom_entry_points,
+        );
         self.postprocess_search_result(search_result, vector, params, top, &is_stopped)
     }
 
@@ -545,23 +809,100 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        // All vectors of the batch share the same filter, build its context only once.
+        // Every search still gets its own scorer and visited list, so no state leaks between them.
+        let payload_index = self.payload_index.borrow();
+        let filter_context = filter.map(|f| payload_index.filter_context(f));
+        let filter_context = filter_context.as_deref();
+        let graph = self.graph_for_filter(filter);
+
         vectors
             .iter()
             .map(|&vector| match vector {
-                QueryVector::Discovery(discovery_query) => self.discovery_search_with_graph(
-                    discovery_query.clone(),
-                    filter,
+                QueryVector::Discovery(discovery_query) => {
+                    let search = |params: Option<&SearchParams>| {
+                        self.discovery_search_with_graph(
+                            graph,
+                            discovery_query.clone(),
+                            filter_context,
+                            top,
+                            params,
+                            vector_query_context,
+                        )
+                    };
+                    match filter {
+                        None => search(params),
+                        Some(filter) => self.search_filtered_with_ef_escalation(
+                            vector,
+                            filter,
+                            top,
+                            params,
+                            vector_query_context,
+                            search,
+                        ),
+                    }
+                }
+                other => self.search_with_graph(
+                    graph,
+                    other,
+                    filter_context,
                     top,
                     params,
+                    None,
                     vector_query_context,
                 ),
-                other => {
-                    self.search_with_graph(other, filter, top, params, None, vector_query_context)
-                }
             })
             .collect()
     }
 
+    /// Filtered graph search, repeated with a larger `ef` while it finds fewer than `top` points.
+    /// If that doesn't find enough points either, falls back to a plain search of the points
+    /// matching the filter.
+    ///
+    /// The graph is only traversed through points matching the filter, so with a restrictive
+    /// filter there may be fewer than `top` of them reachable from the entry points.
+    fn search_filtered_with_ef_escalation(
+        &self,
+        vector: &QueryVector,
+        filter: &Filter,
+        top: usize,
+        params: Option<&SearchParams>,
+        vector_query_context: &VectorQueryContext,
+        search: impl Fn(Option<&SearchParams>) -> OperationResult<Vec<ScoredPointOffset>>,
+    ) -> OperationResult<Vec<ScoredPointOffset>> {
+        let mut result = search(params)?;
+
+        let base_ef = params
+            .and_then(|params| params.hnsw_ef)
+            .unwrap_or(self.config.ef)
+            .max(top);
+        let mut ef = base_ef;
+        while result.len() < top && ef < base_ef * MAX_EF_ESCALATION {
+            ef *= 2;
+            let escalated_params = SearchParams {
+                hnsw_ef: Some(ef),
+                ..params.copied().unwrap_or_default()
+            };
+            let escalated_result = search(Some(&escalated_params))?;
+            // A larger `ef` doesn't reach more points, escalating further won't help
+            let found_more = escalated_result.len() > result.len();
+            result = escalated_result;
+            if !found_more {
+                break;
+            }
+        }
+
+        if result.len() < top {
+            let filtered_points = self.payload_index.borrow().query_points(filter);
+            if filtered_points.len() > result.len() {
+                result =
+                    self.search_plain(vector, &filtered_points, top, params, vector_query_context)?;
+            }
+        }
+
+        Ok(result)
+    }
+
     fn search_plain(
         &self,
         vector: &QueryVector,
@@ -617,8 +958,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
     fn discovery_search_with_graph(
         &self,
+        graph: &GraphLayers<TGraphLinks>,
         discovery_query: DiscoveryQuery<Vector>,
-        filter: Option<&Filter>,
+        filter_context: Option<&dyn FilterContext>,
         top: usize,
         params: Option<&SearchParams>,
         vector_query_context: &VectorQueryContext,
@@ -630,8 +972,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
 
         let custom_entry_points: Vec<_> = self
             .search_with_graph(
+                graph,
                 &query_vector,
-                filter,
+                filter_context,
                 DISCOVERY_ENTRY_POINT_COUNT,
                 params,
                 None,
@@ -643,8 +986,9 @@ impl<TGraphLinks: GraphLinks> HNSWIndex<TGraphLinks> {
         let query_vector = QueryVector::Discovery(discovery_query);
 
         self.search_with_graph(
+            graph,
             &query_vector,
-            filter,
+            filter_context,
             top,
             params,
             Some(&custom_entry_points),
@@ -918,12 +1262,21 @@ impl<TGraphLinks: GraphLinks> VectorIndex for HNSWIndex<TGraphLinks> {
     }
 
     fn files(&self) -> Vec<PathBuf> {
+        let partition_files = (0..self.partitions.len()).flat_map(|number| {
+            let partition_dir = HnswPartition::graph_dir(&self.path, number);
+            [
+                GraphLayers::<TGraphLinks>::get_path(&partition_dir),
+                GraphLayers::<TGraphLinks>::get_links_path(&partition_dir),
+            ]
+        });
         [
             GraphLayers::<TGraphLinks>::get_path(&self.path),
             GraphLayers::<TGraphLinks>::get_links_path(&self.path),
             HnswGraphConfig::get_config_path(&self.path),
+            HnswPartition::get_list_path(&self.path),
         ]
         .into_iter()
+        .chain(partition_files)
         .filter(|p| p.exists())
         .collect()
     }
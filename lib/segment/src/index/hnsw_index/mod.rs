@@ -4,10 +4,12 @@ mod build_cache;
 pub mod build_condition_checker;
 mod config;
 mod entry_points;
+pub mod graph_import;
 pub mod graph_layers;
 pub mod graph_layers_builder;
 pub mod graph_links;
 pub mod hnsw;
+mod partitions;
 pub mod point_scorer;
 mod search_context;
 
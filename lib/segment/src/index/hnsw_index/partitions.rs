@@ -0,0 +1,152 @@
+//! Separate HNSW graphs for a few values of keyword fields, declared with
+//! [`hnsw_partitions`](crate::data_types::index::KeywordIndexParams::hnsw_partitions).
+//!
+//! Each graph only links the points having its value, so searches filtered by that value don't
+//! have to traverse the links of other points. Searches with any other filter use the main graph.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use crate::common::operation_error::OperationResult;
+use crate::types::{
+    Condition, FieldCondition, Filter, Match, MatchValue, PayloadFieldSchema, PayloadKeyType,
+    PayloadSchemaParams, ValueVariants,
+};
+
+const HNSW_PARTITIONS_FILE: &str = "hnsw_partitions.json";
+const HNSW_PARTITIONS_DIR: &str = "partitions";
+
+/// Points of the segment with a given keyword value
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct HnswPartition {
+    pub key: PayloadKeyType,
+    pub value: String,
+}
+
+impl HnswPartition {
+    /// Partitions declared in the schema of the indexed fields, in a stable order
+    pub fn declared(indexed_fields: &HashMap<PayloadKeyType, PayloadFieldSchema>) -> Vec<Self> {
+        let mut partitions: Vec<_> = indexed_fields
+            .iter()
+            .filter_map(|(key, schema)| match schema {
+                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(params)) => {
+                    Some((key, params.hnsw_partitions.as_ref()?))
+                }
+                _ => None,
+            })
+            .flat_map(|(key, values)| {
+                values.iter().map(|value| HnswPartition {
+                    key: key.clone(),
+                    value: value.clone(),
+                })
+            })
+            .collect();
+        partitions
+            .sort_by_cached_key(|partition| (partition.key.to_string(), partition.value.clone()));
+        partitions.dedup();
+        partitions
+    }
+
+    /// Condition matching the points of the partition
+    pub fn condition(&self) -> FieldCondition {
+        FieldCondition::new_match(
+            self.key.clone(),
+            Match::new_value(ValueVariants::String(self.value.clone())),
+        )
+    }
+
+    /// Whether all points matching the filter are in this partition.
+    ///
+    /// Only a `must` condition matching exactly the value of the partition is recognized.
+    pub fn contains_filter(&self, filter: &Filter) -> bool {
+        filter
+            .must
+            .iter()
+            .flatten()
+            .any(|condition| match condition {
+                Condition::Field(FieldCondition {
+                    key,
+                    r#match:
+                        Some(Match::Value(MatchValue {
+                            value: ValueVariants::String(value),
+                        })),
+                    ..
+                }) => key == &self.key && value == &self.value,
+                _ => false,
+            })
+    }
+
+    /// Directory of the graph of the partition with the given number
+    pub fn graph_dir(index_path: &Path, number: usize) -> PathBuf {
+        index_path
+            .join(HNSW_PARTITIONS_DIR)
+            .join(number.to_string())
+    }
+
+    pub fn get_list_path(index_path: &Path) -> PathBuf {
+        index_path.join(HNSW_PARTITIONS_FILE)
+    }
+
+    /// Partitions of the index, numbered by their position. An index without the list of
+    /// partitions, built before partitions were supported, has none.
+    pub fn load_list(index_path: &Path) -> OperationResult<Vec<Self>> {
+        let path = Self::get_list_path(index_path);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+        Ok(read_json(&path)?)
+    }
+
+    pub fn save_list(index_path: &Path, partitions: &[Self]) -> OperationResult<()> {
+        Ok(atomic_save_json(
+            &Self::get_list_path(index_path),
+            &partitions,
+        )?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data_types::index::KeywordIndexParams;
+    use crate::json_path::JsonPath;
+    use crate::types::PayloadSchemaType;
+
+    #[test]
+    fn test_declared_partitions() {
+        let indexed_fields = HashMap::from([
+            (
+                JsonPath::new("lang"),
+                PayloadFieldSchema::FieldParams(PayloadSchemaParams::Keyword(KeywordIndexParams {
+                    hnsw_partitions: Some(vec![
+                        "en".to_string(),
+                        "de".to_string(),
+                        "en".to_string(),
+                    ]),
+                    ..Default::default()
+                })),
+            ),
+            (
+                JsonPath::new("city"),
+                PayloadFieldSchema::FieldType(PayloadSchemaType::Keyword),
+            ),
+        ]);
+
+        let partitions = HnswPartition::declared(&indexed_fields);
+        let values: Vec<_> = partitions.iter().map(|p| p.value.as_str()).collect();
+        assert_eq!(values, ["de", "en"]);
+
+        let en = &partitions[1];
+        assert!(en.contains_filter(&Filter::new_must(Condition::Field(en.condition()))));
+
+        let fr = FieldCondition::new_match(
+            JsonPath::new("lang"),
+            Match::new_value(ValueVariants::String("fr".to_string())),
+        );
+        assert!(!en.contains_filter(&Filter::new_must(Condition::Field(fr))));
+        assert!(!en.contains_filter(&Filter::new_should(Condition::Field(en.condition()))));
+    }
+}
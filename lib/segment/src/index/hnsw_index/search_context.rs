@@ -11,16 +11,61 @@ pub struct SearchContext {
     pub nearest: FixedLengthPriorityQueue<ScoredPointOffset>,
     /// Current candidates to process
     pub candidates: BinaryHeap<ScoredPointOffset>,
+    /// Maximum number of candidates to expand, see [`SearchContext::with_max_candidates`]
+    max_candidates: Option<usize>,
+    /// Number of nearest points to find before stopping at `max_candidates`
+    min_nearest: usize,
+    /// Number of candidates expanded so far
+    expanded_candidates: usize,
 }
 
 impl SearchContext {
     pub fn new(entry_point: ScoredPointOffset, ef: usize) -> Self {
-        let mut nearest = FixedLengthPriorityQueue::new(ef);
-        nearest.push(entry_point);
-        SearchContext {
-            nearest,
-            candidates: BinaryHeap::from_iter([entry_point]),
+        Self::with_entries([entry_point], ef)
+    }
+
+    pub fn with_entries(
+        entry_points: impl IntoIterator<Item = ScoredPointOffset>,
+        ef: usize,
+    ) -> Self {
+        let mut search_context = SearchContext {
+            nearest: FixedLengthPriorityQueue::new(ef),
+            candidates: BinaryHeap::new(),
+            max_candidates: None,
+            min_nearest: 0,
+            expanded_candidates: 0,
+        };
+        for entry_point in entry_points {
+            search_context.process_candidate(entry_point);
+        }
+        search_context
+    }
+
+    /// Expand at most `max_candidates` candidates, then stop with the nearest points found so far,
+    /// even if the search has not converged yet.
+    ///
+    /// The search goes beyond `max_candidates` while less than `min_nearest` points are found, so
+    /// that a cut off search still returns as many results as requested, if there are enough.
+    pub fn with_max_candidates(
+        mut self,
+        max_candidates: Option<usize>,
+        min_nearest: usize,
+    ) -> Self {
+        self.max_candidates = max_candidates;
+        self.min_nearest = min_nearest;
+        self
+    }
+
+    /// Count a candidate to expand, returns `false` if the search must stop instead
+    pub fn expand_candidate(&mut self) -> bool {
+        if let Some(max_candidates) = self.max_candidates {
+            if self.expanded_candidates >= max_candidates && self.nearest.len() >= self.min_nearest
+            {
+                return false;
+            }
         }
+        self.expanded_candidates += 1;
+        true
     }
 
     pub fn lower_bound(&self) -> ScoreType {
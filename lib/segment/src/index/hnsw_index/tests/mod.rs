@@ -1,18 +1,29 @@
 mod test_compact_graph_layer;
+mod test_deterministic_build;
 mod test_graph_connectivity;
+mod test_graph_import;
+mod test_partitions;
 
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 
+use common::cpu::CpuPermit;
 use common::types::PointOffsetType;
 use rand::Rng;
 
 use super::graph_links::GraphLinksRam;
-use crate::data_types::vectors::VectorElementType;
-use crate::fixtures::index_fixtures::{FakeFilterContext, TestRawScorerProducer};
+use crate::data_types::vectors::{only_default_vector, VectorElementType, DEFAULT_VECTOR_NAME};
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::{random_vector, FakeFilterContext, TestRawScorerProducer};
 use crate::index::hnsw_index::graph_layers::GraphLayers;
 use crate::index::hnsw_index::graph_layers_builder::GraphLayersBuilder;
+use crate::index::hnsw_index::hnsw::HnswIndexOpenArgs;
 use crate::index::hnsw_index::point_scorer::FilteredScorer;
+use crate::segment::Segment;
+use crate::segment_constructor::simple_segment_constructor::build_simple_segment;
 use crate::spaces::metric::Metric;
+use crate::types::{Distance, HnswConfig, SeqNumberType};
 use crate::vector_storage::chunked_vector_storage::VectorOffsetType;
 
 pub(crate) fn create_graph_layer_builder_fixture<TMetric: Metric<VectorElementType>, R>(
@@ -70,3 +81,61 @@ where
         graph_layers_builder.into_graph_layers(links_path).unwrap(),
     )
 }
+
+/// Segment with `num_vectors` random vectors of `dim` dimensions, point `n` is upserted at op `n`
+pub(crate) fn build_random_segment<R>(
+    path: &Path,
+    dim: usize,
+    num_vectors: u64,
+    rng: &mut R,
+) -> Segment
+where
+    R: Rng + ?Sized,
+{
+    let mut segment = build_simple_segment(path, dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let vector = random_vector(rng, dim);
+        segment
+            .upsert_point(n as SeqNumberType, n.into(), only_default_vector(&vector))
+            .unwrap();
+    }
+    segment
+}
+
+/// Small HNSW config, which builds a graph even for a few hundred points
+pub(crate) fn test_hnsw_config() -> HnswConfig {
+    HnswConfig {
+        m: 8,
+        m0: None,
+        ef_construct: 32,
+        full_scan_threshold: 1,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
+    }
+}
+
+/// Args to open an HNSW index of the default vector of `segment` in `path`
+pub(crate) fn hnsw_open_args_fixture<'a>(
+    segment: &Segment,
+    path: &'a Path,
+    hnsw_config: HnswConfig,
+    stopped: &'a AtomicBool,
+) -> HnswIndexOpenArgs<'a> {
+    let permit = CpuPermit::dummy(hnsw_config.max_indexing_threads as u32);
+    HnswIndexOpenArgs {
+        path,
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: segment.vector_data[DEFAULT_VECTOR_NAME]
+            .vector_storage
+            .clone(),
+        quantized_vectors: Default::default(),
+        payload_index: segment.payload_index.clone(),
+        hnsw_config,
+        permit: Some(Arc::new(permit)),
+        stopped,
+        progress: None,
+    }
+}
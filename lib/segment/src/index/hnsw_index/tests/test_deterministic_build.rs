@@ -0,0 +1,68 @@
+use std::sync::atomic::AtomicBool;
+
+use common::types::PointOffsetType;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use super::{build_random_segment, hnsw_open_args_fixture, test_hnsw_config};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::segment::Segment;
+use crate::types::HnswConfig;
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 500;
+
+fn build_index(
+    segment: &Segment,
+    stopped: &AtomicBool,
+) -> OperationResult<Vec<Vec<PointOffsetType>>> {
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let hnsw_config = HnswConfig {
+        full_scan_threshold: 10_000,
+        max_indexing_threads: 4,
+        deterministic_build: Some(true),
+        ..test_hnsw_config()
+    };
+
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(hnsw_open_args_fixture(
+        segment,
+        hnsw_dir.path(),
+        hnsw_config,
+        stopped,
+    ))?;
+
+    let graph = hnsw_index.graph();
+    let links = (0..NUM_VECTORS as PointOffsetType)
+        .flat_map(|point_id| {
+            (0..=graph.point_level(point_id))
+                .map(move |level| graph.links.links(point_id, level).to_vec())
+        })
+        .collect();
+    Ok(links)
+}
+
+#[test]
+fn test_deterministic_build() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment =
+        build_random_segment(dir.path(), DIM, NUM_VECTORS, &mut StdRng::seed_from_u64(42));
+    let stopped = AtomicBool::new(false);
+
+    let links = build_index(&segment, &stopped).unwrap();
+    assert_eq!(links, build_index(&segment, &stopped).unwrap());
+}
+
+#[test]
+fn test_deterministic_build_cancelled() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment =
+        build_random_segment(dir.path(), DIM, NUM_VECTORS, &mut StdRng::seed_from_u64(42));
+    let stopped = AtomicBool::new(true);
+
+    let result = build_index(&segment, &stopped);
+    assert!(matches!(result, Err(OperationError::Cancelled { .. })));
+}
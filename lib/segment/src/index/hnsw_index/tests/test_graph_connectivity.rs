@@ -1,5 +1,5 @@
 use std::collections::HashMap;
-use std::sync::atomic::AtomicBool;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::sync::Arc;
 
 use common::cpu::CpuPermit;
@@ -10,6 +10,7 @@ use tempfile::Builder;
 use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use crate::entry::entry_point::SegmentEntry;
 use crate::fixtures::index_fixtures::random_vector;
+use crate::index::build_progress::BuildProgress;
 use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
 use crate::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
 use crate::index::hnsw_index::num_rayon_threads;
@@ -19,6 +20,22 @@ use crate::types::{
     VectorStorageType,
 };
 
+#[derive(Default)]
+struct CountingProgress {
+    total: AtomicUsize,
+    done: AtomicUsize,
+}
+
+impl BuildProgress for CountingProgress {
+    fn set_total(&self, total: usize) {
+        self.total.store(total, Ordering::Relaxed);
+    }
+
+    fn inc(&self, delta: usize) {
+        self.done.fetch_add(delta, Ordering::Relaxed);
+    }
+}
+
 #[test]
 fn test_graph_connectivity() {
     let stopped = AtomicBool::new(false);
@@ -50,6 +67,7 @@ fn test_graph_connectivity() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -66,15 +84,19 @@ fn test_graph_connectivity() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 4,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
     let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+    let progress = CountingProgress::default();
 
     let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
         path: hnsw_dir.path(),
@@ -87,9 +109,13 @@ fn test_graph_connectivity() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: Some(&progress),
     })
     .unwrap();
 
+    assert_eq!(progress.total.load(Ordering::Relaxed), num_vectors as usize);
+    assert_eq!(progress.done.load(Ordering::Relaxed), num_vectors as usize);
+
     let mut reverse_links = vec![vec![]; num_vectors as usize];
 
     for point_id in 0..num_vectors {
@@ -0,0 +1,149 @@
+use std::sync::atomic::AtomicBool;
+
+use common::types::PointOffsetType;
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use tempfile::Builder;
+
+use super::{build_random_segment, hnsw_open_args_fixture, test_hnsw_config};
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::data_types::vectors::QueryVector;
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_import::ImportedGraph;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::VectorIndex;
+use crate::segment::Segment;
+use crate::types::{SearchParams, SeqNumberType};
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 300;
+
+/// Export the graph of an index built by Qdrant, as if it was built offline
+fn export_graph(index: &HNSWIndex<GraphLinksRam>) -> ImportedGraph {
+    let graph = index.graph();
+    let levels: Vec<_> = (0..NUM_VECTORS as PointOffsetType)
+        .map(|point_id| graph.point_level(point_id))
+        .collect();
+    let links = levels
+        .iter()
+        .enumerate()
+        .map(|(point_id, &level)| {
+            (0..=level)
+                .map(|level| {
+                    graph
+                        .links
+                        .links(point_id as PointOffsetType, level)
+                        .to_vec()
+                })
+                .collect()
+        })
+        .collect();
+    ImportedGraph { levels, links }
+}
+
+fn import(segment: &Segment, graph: ImportedGraph) -> OperationResult<HNSWIndex<GraphLinksRam>> {
+    let dir = Builder::new().prefix("hnsw_import").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    HNSWIndex::import(
+        hnsw_open_args_fixture(segment, dir.path(), test_hnsw_config(), &stopped),
+        graph,
+    )
+}
+
+#[test]
+fn test_graph_import() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let segment =
+        build_random_segment(dir.path(), DIM, NUM_VECTORS, &mut StdRng::seed_from_u64(42));
+    let stopped = AtomicBool::new(false);
+
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let built = HNSWIndex::<GraphLinksRam>::open(hnsw_open_args_fixture(
+        &segment,
+        hnsw_dir.path(),
+        test_hnsw_config(),
+        &stopped,
+    ))
+    .unwrap();
+    let graph = export_graph(&built);
+
+    let import_dir = Builder::new().prefix("hnsw_import").tempdir().unwrap();
+    let imported = HNSWIndex::<GraphLinksRam>::import(
+        hnsw_open_args_fixture(&segment, import_dir.path(), test_hnsw_config(), &stopped),
+        graph.clone(),
+    )
+    .unwrap();
+    assert_eq!(imported.indexed_vector_count(), NUM_VECTORS as usize);
+
+    // Imported graph is persisted and searched like the one it was exported from
+    let reopened = HNSWIndex::<GraphLinksRam>::open(hnsw_open_args_fixture(
+        &segment,
+        import_dir.path(),
+        test_hnsw_config(),
+        &stopped,
+    ))
+    .unwrap();
+    assert_eq!(export_graph(&reopened).links, graph.links);
+
+    let mut rng = StdRng::seed_from_u64(7);
+    let params = SearchParams {
+        hnsw_ef: Some(64),
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let query: QueryVector = random_vector(&mut rng, DIM).into();
+        let expected = built
+            .search(&[&query], None, 10, Some(&params), &Default::default())
+            .unwrap();
+        let result = reopened
+            .search(&[&query], None, 10, Some(&params), &Default::default())
+            .unwrap();
+        assert_eq!(result, expected);
+    }
+}
+
+#[test]
+fn test_graph_import_invalid() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment =
+        build_random_segment(dir.path(), DIM, NUM_VECTORS, &mut StdRng::seed_from_u64(42));
+    let stopped = AtomicBool::new(false);
+
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let built = HNSWIndex::<GraphLinksRam>::open(hnsw_open_args_fixture(
+        &segment,
+        hnsw_dir.path(),
+        test_hnsw_config(),
+        &stopped,
+    ))
+    .unwrap();
+    let graph = export_graph(&built);
+
+    let is_invalid =
+        |result: OperationResult<_>| matches!(result, Err(OperationError::ValidationError { .. }));
+
+    // Link to a point which is not in the segment
+    let mut unknown_link = graph.clone();
+    unknown_link.links[0][0].push(NUM_VECTORS as PointOffsetType);
+    assert!(is_invalid(import(&segment, unknown_link)));
+
+    // Graph of fewer points than the segment
+    let mut missing_point = graph.clone();
+    missing_point.levels.pop();
+    missing_point.links.pop();
+    assert!(is_invalid(import(&segment, missing_point)));
+
+    // Level assignment does not match the links
+    let mut wrong_level = graph.clone();
+    wrong_level.levels[0] += 1;
+    assert!(is_invalid(import(&segment, wrong_level)));
+
+    // Link to a deleted point
+    let linked_point = graph.links[0][0][0];
+    segment
+        .delete_point(NUM_VECTORS as SeqNumberType, (linked_point as u64).into())
+        .unwrap();
+    assert!(is_invalid(import(&segment, graph)));
+}
@@ -0,0 +1,117 @@
+use std::sync::atomic::AtomicBool;
+
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use serde_json::json;
+use tempfile::Builder;
+
+use super::{build_random_segment, hnsw_open_args_fixture, test_hnsw_config};
+use crate::data_types::index::KeywordIndexParams;
+use crate::data_types::vectors::QueryVector;
+use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::index_fixtures::random_vector;
+use crate::index::hnsw_index::graph_links::{GraphLinks, GraphLinksRam};
+use crate::index::hnsw_index::hnsw::HNSWIndex;
+use crate::index::{PayloadIndex, VectorIndex};
+use crate::json_path::JsonPath;
+use crate::types::{
+    Condition, FieldCondition, Filter, Match, Payload, PayloadFieldSchema, PayloadSchemaParams,
+    SearchParams, SeqNumberType, ValueVariants,
+};
+
+const DIM: usize = 16;
+const NUM_VECTORS: u64 = 600;
+const LANGUAGES: [&str; 3] = ["en", "de", "fr"];
+
+fn lang_filter(lang: &str) -> Filter {
+    Filter::new_must(Condition::Field(FieldCondition::new_match(
+        JsonPath::new("lang"),
+        Match::new_value(ValueVariants::String(lang.to_string())),
+    )))
+}
+
+#[test]
+fn test_hnsw_partitions() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut rng = StdRng::seed_from_u64(42);
+
+    let mut segment = build_random_segment(dir.path(), DIM, NUM_VECTORS, &mut rng);
+    for n in 0..NUM_VECTORS {
+        let payload: Payload = json!({ "lang": LANGUAGES[n as usize % LANGUAGES.len()] }).into();
+        segment
+            .set_payload(n as SeqNumberType, n.into(), &payload, &None)
+            .unwrap();
+    }
+    segment
+        .create_field_index(
+            NUM_VECTORS as SeqNumberType,
+            &JsonPath::new("lang"),
+            Some(&PayloadFieldSchema::FieldParams(
+                PayloadSchemaParams::Keyword(KeywordIndexParams {
+                    hnsw_partitions: Some(vec!["en".to_string(), "de".to_string()]),
+                    ..Default::default()
+                }),
+            )),
+        )
+        .unwrap();
+
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+    let stopped = AtomicBool::new(false);
+    let open_args =
+        || hnsw_open_args_fixture(&segment, hnsw_dir.path(), test_hnsw_config(), &stopped);
+
+    let built = HNSWIndex::<GraphLinksRam>::open(open_args()).unwrap();
+    // Partitions are persisted with the main graph
+    let index = HNSWIndex::<GraphLinksRam>::open(open_args()).unwrap();
+    assert_eq!(index.files(), built.files());
+
+    let partitions = index.partitions();
+    assert_eq!(partitions.len(), 2);
+    let payload_index = segment.payload_index.borrow();
+    for (partition, graph) in partitions {
+        let points =
+            payload_index.query_points(&Filter::new_must(Condition::Field(partition.condition())));
+        assert!(!points.is_empty());
+        // Points of the partition are only linked to each other
+        for &point_id in &points {
+            let links = graph.links.links(point_id, 0);
+            assert!(!links.is_empty());
+            assert!(links.iter().all(|link| points.contains(link)));
+        }
+    }
+
+    let params = SearchParams {
+        hnsw_ef: Some(128),
+        ..Default::default()
+    };
+    let exact_params = SearchParams {
+        exact: true,
+        ..Default::default()
+    };
+    for _ in 0..10 {
+        let query: QueryVector = random_vector(&mut rng, DIM).into();
+        // "fr" is not a partition, it is searched in the main graph
+        for lang in LANGUAGES {
+            let filter = lang_filter(lang);
+            let expected = index
+                .search(
+                    &[&query],
+                    Some(&filter),
+                    5,
+                    Some(&exact_params),
+                    &Default::default(),
+                )
+                .unwrap();
+            let result = index
+                .search(
+                    &[&query],
+                    Some(&filter),
+                    5,
+                    Some(&params),
+                    &Default::default(),
+                )
+                .unwrap();
+            assert_eq!(result, expected, "search with lang={lang}");
+        }
+    }
+}
@@ -1,3 +1,4 @@
+pub mod build_progress;
 pub mod field_index;
 pub mod hnsw_index;
 mod key_encoding;
@@ -101,6 +101,13 @@ pub trait PayloadIndex {
     /// Get payload for point
     fn get_payload(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get only the fields of point payload selected by `include`
+    fn get_payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload>;
+
     /// Delete payload by key
     fn delete_payload(
         &mut self,
@@ -187,6 +187,14 @@ impl PayloadIndex for PlainPayloadIndex {
         unreachable!()
     }
 
+    fn get_payload_projected(
+        &self,
+        _point_id: PointOffsetType,
+        _include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        unreachable!()
+    }
+
     fn delete_payload(
         &mut self,
         _point_id: PointOffsetType,
@@ -1,30 +1,34 @@
 mod match_converter;
 
 use std::collections::HashSet;
+use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 use match_converter::get_match_checkers;
 use serde_json::Value;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::index::query_optimization::payload_provider::PayloadProvider;
 use crate::payload_storage::query_checker::{
-    check_field_condition, check_is_empty_condition, check_is_null_condition, check_payload,
-    select_nested_indexes,
+    check_field_condition, check_has_field_condition, check_is_empty_condition,
+    check_is_null_condition, check_payload, select_nested_indexes,
 };
 use crate::types::{
     Condition, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPolygon,
     GeoRadius, IntPayloadType, OwnedPayloadRef, PayloadContainer, Range, RangeInterface,
 };
+use crate::vector_storage::VectorStorage;
 
 pub fn condition_converter<'a>(
     condition: &'a Condition,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
 ) -> ConditionCheckerFn<'a> {
     match condition {
         Condition::Field(field_condition) => field_indexes
@@ -60,6 +64,24 @@ pub fn condition_converter<'a>(
             }
         }
 
+        // Same as for `is_empty`: the index is only conclusive when it has values for the point.
+        Condition::HasField(has_field) => {
+            let first_field_index = field_indexes
+                .get(&has_field.has_field.key)
+                .and_then(|indexes| indexes.first());
+
+            let fallback = Box::new(move |point_id| {
+                payload_provider.with_payload(point_id, |payload| {
+                    check_has_field_condition(has_field, &payload)
+                })
+            });
+
+            match first_field_index {
+                Some(index) => get_has_field_checker(index, fallback),
+                None => fallback,
+            }
+        }
+
         Condition::IsNull(is_null) => Box::new(move |point_id| {
             payload_provider.with_payload(point_id, |payload| {
                 check_is_null_condition(is_null, &payload)
@@ -67,13 +89,32 @@ pub fn condition_converter<'a>(
         }),
         // ToDo: It might be possible to make this condition faster by using `VisitedPool` instead of HashSet
         Condition::HasId(has_id) => {
+            let id_tracker_ref = id_tracker.borrow();
             let segment_ids: HashSet<_> = has_id
                 .has_id
                 .iter()
-                .filter_map(|external_id| id_tracker.internal_id(*external_id))
+                .filter_map(|external_id| id_tracker_ref.internal_id(*external_id))
                 .collect();
             Box::new(move |point_id| segment_ids.contains(&point_id))
         }
+        Condition::IdRange(id_range) => {
+            let id_tracker = id_tracker.clone();
+            Box::new(move |point_id| {
+                id_tracker
+                    .borrow()
+                    .external_id(point_id)
+                    .is_some_and(|external_id| id_range.id_range.check(external_id))
+            })
+        }
+        // Absent vectors are marked as deleted in the vector storage
+        Condition::HasVector(has_vector) => {
+            match vector_storages.get(&has_vector.has_vector).cloned() {
+                Some(vector_storage) => {
+                    Box::new(move |point_id| !vector_storage.borrow().is_deleted_vector(point_id))
+                }
+                None => Box::new(|_| false),
+            }
+        }
         Condition::Nested(nested) => {
             // Select indexes for nested fields. Trim nested part from key, so
             // that nested condition can address fields without nested part.
@@ -96,6 +137,7 @@ pub fn condition_converter<'a>(
             let nested_path = nested.array_key();
 
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
+            let vector_storages = vector_storages.clone();
 
             Box::new(move |point_id| {
                 payload_provider.with_payload(point_id, |payload| {
@@ -109,6 +151,7 @@ pub fn condition_converter<'a>(
                                 // None because has_id in nested is not supported. So retrieving
                                 // IDs through the tracker would always return None.
                                 None,
+                                &vector_storages,
                                 &nested.nested.filter,
                                 point_id,
                                 &nested_indexes,
@@ -123,10 +166,11 @@ pub fn condition_converter<'a>(
             })
         }
         Condition::CustomIdChecker(cond) => {
-            let segment_ids: HashSet<_> = id_tracker
+            let id_tracker_ref = id_tracker.borrow();
+            let segment_ids: HashSet<_> = id_tracker_ref
                 .iter_external()
                 .filter(|&point_id| cond.check(point_id))
-                .filter_map(|external_id| id_tracker.internal_id(external_id))
+                .filter_map(|external_id| id_tracker_ref.internal_id(external_id))
                 .collect();
 
             Box::new(move |internal_id| segment_ids.contains(&internal_id))
@@ -311,3 +355,13 @@ fn get_is_empty_checker<'a>(
         index.values_is_empty(point_id) && fallback(point_id)
     })
 }
+
+fn get_has_field_checker<'a>(
+    index: &'a FieldIndex,
+    fallback: ConditionCheckerFn<'a>,
+) -> ConditionCheckerFn<'a> {
+    Box::new(move |point_id: PointOffsetType| {
+        // Only check the fallback if the index seems to be empty
+        !index.values_is_empty(point_id) || fallback(point_id)
+    })
+}
@@ -6,7 +6,7 @@ use crate::index::field_index::FieldIndex;
 use crate::index::query_optimization::optimized_filter::ConditionCheckerFn;
 use crate::payload_storage::condition_checker::INDEXSET_ITER_THRESHOLD;
 use crate::types::{
-    AnyVariants, Match, MatchAny, MatchExcept, MatchText, MatchValue, ValueVariants,
+    AnyVariants, Match, MatchAll, MatchAny, MatchExcept, MatchText, MatchValue, ValueVariants,
 };
 
 pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<ConditionCheckerFn> {
@@ -15,6 +15,7 @@ pub fn get_match_checkers(index: &FieldIndex, cond_match: Match) -> Option<Condi
         Match::Text(MatchText { text }) => get_match_text_checker(text, index),
         Match::Any(MatchAny { any }) => get_match_any_checker(any, index),
         Match::Except(MatchExcept { except }) => get_match_except_checker(except, index),
+        Match::All(MatchAll { all }) => get_match_all_checker(all, index),
     }
 }
 
@@ -213,6 +214,56 @@ fn get_match_except_checker(except: AnyVariants, index: &FieldIndex) -> Option<C
     checker
 }
 
+fn get_match_all_checker(all: AnyVariants, index: &FieldIndex) -> Option<ConditionCheckerFn> {
+    if all.is_empty() {
+        // Only the payload can tell an empty array apart from a missing field
+        return None;
+    }
+
+    match (all, index) {
+        (AnyVariants::Strings(list), FieldIndex::KeywordIndex(index)) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                list.iter()
+                    .all(|keyword| index.check_values_any(point_id, |value| value == keyword))
+            }))
+        }
+        (AnyVariants::Strings(list), FieldIndex::UuidMapIndex(index)) => {
+            let list = list
+                .iter()
+                .map(|s| Uuid::parse_str(s).map(|uuid| uuid.as_u128()).ok())
+                .collect::<Option<Vec<_>>>()?;
+
+            Some(Box::new(move |point_id: PointOffsetType| {
+                list.iter()
+                    .all(|uuid| index.check_values_any(point_id, |value| value == uuid))
+            }))
+        }
+        (AnyVariants::Integers(list), FieldIndex::IntMapIndex(index)) => {
+            Some(Box::new(move |point_id: PointOffsetType| {
+                list.iter()
+                    .all(|integer| index.check_values_any(point_id, |value| value == integer))
+            }))
+        }
+        (AnyVariants::Integers(_), FieldIndex::BinaryIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::DatetimeIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::FloatIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::FullTextIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::GeoIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::IntIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::KeywordIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::UuidIndex(_))
+        | (AnyVariants::Integers(_), FieldIndex::UuidMapIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::BinaryIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::DatetimeIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::FloatIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::FullTextIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::GeoIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::IntIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::IntMapIndex(_))
+        | (AnyVariants::Strings(_), FieldIndex::UuidIndex(_)) => None,
+    }
+}
+
 fn get_match_text_checker(text: String, index: &FieldIndex) -> Option<ConditionCheckerFn> {
     match index {
         FieldIndex::FullTextIndex(full_text_index) => {
@@ -1,8 +1,10 @@
 use std::cmp::Reverse;
+use std::sync::Arc;
 
+use atomic_refcell::AtomicRefCell;
 use itertools::Itertools;
 
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::query_estimator::{
@@ -29,7 +31,8 @@ use crate::types::{Condition, Filter, MinShould};
 /// # Arguments
 ///
 /// * `filter` - original filter
-/// * `id_tracker` - used for converting collection-level ids into segment-level offsets of HasId condition
+/// * `id_tracker` - used for converting collection-level ids into segment-level offsets of HasId and IdRange conditions
+/// * `vector_storages` - used for checking HasVector condition
 /// * `estimator` - function to estimate cardinality of individual conditions
 /// * `total` - total number of points in segment (used for cardinality estimation)
 ///
@@ -38,7 +41,8 @@ use crate::types::{Condition, Filter, MinShould};
 /// Optimized query + Cardinality estimation
 pub fn optimize_filter<'a, F>(
     filter: &'a Filter,
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -55,6 +59,7 @@ where
                 let (optimized_conditions, estimation) = optimize_should(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -76,6 +81,7 @@ where
                         conditions,
                         *min_count,
                         id_tracker,
+                        vector_storages,
                         field_indexes,
                         payload_provider.clone(),
                         estimator,
@@ -96,6 +102,7 @@ where
                 let (optimized_conditions, estimation) = optimize_must(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -112,6 +119,7 @@ where
                 let (optimized_conditions, estimation) = optimize_must_not(
                     conditions,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -133,7 +141,8 @@ where
 
 fn convert_conditions<'a, F>(
     conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -149,6 +158,7 @@ where
                 let (optimized_filter, estimation) = optimize_filter(
                     filter,
                     id_tracker,
+                    vector_storages,
                     field_indexes,
                     payload_provider.clone(),
                     estimator,
@@ -163,6 +173,7 @@ where
                     field_indexes,
                     payload_provider.clone(),
                     id_tracker,
+                    vector_storages,
                 );
                 (OptimizedCondition::Checker(condition_checker), estimation)
             }
@@ -172,7 +183,8 @@ where
 
 fn optimize_should<'a, F>(
     conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -184,6 +196,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
@@ -199,7 +212,8 @@ where
 fn optimize_min_should<'a, F>(
     conditions: &'a [Condition],
     min_count: usize,
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -211,6 +225,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
@@ -233,7 +248,8 @@ where
 
 fn optimize_must<'a, F>(
     conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -245,6 +261,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
@@ -259,7 +276,8 @@ where
 
 fn optimize_must_not<'a, F>(
     conditions: &'a [Condition],
-    id_tracker: &IdTrackerSS,
+    id_tracker: &Arc<AtomicRefCell<IdTrackerSS>>,
+    vector_storages: &VectorStoragesMap,
     field_indexes: &'a IndexesMap,
     payload_provider: PayloadProvider,
     estimator: &F,
@@ -271,6 +289,7 @@ where
     let mut converted = convert_conditions(
         conditions,
         id_tracker,
+        vector_storages,
         field_indexes,
         payload_provider,
         estimator,
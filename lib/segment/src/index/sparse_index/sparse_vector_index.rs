@@ -6,7 +6,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use atomic_refcell::AtomicRefCell;
-use common::types::{PointOffsetType, ScoredPointOffset, TelemetryDetail};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset, TelemetryDetail};
 use io::storage_version::{StorageVersion as _, VERSION_FILE};
 use itertools::Itertools;
 use semver::Version;
@@ -35,7 +35,8 @@ use crate::telemetry::VectorIndexSearchesTelemetry;
 use crate::types::{Filter, SearchParams, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD};
 use crate::vector_storage::query::TransformInto;
 use crate::vector_storage::{
-    check_deleted_condition, new_stoppable_raw_scorer, VectorStorage, VectorStorageEnum,
+    check_deleted_condition, new_stoppable_raw_scorer, SparseVectorStorage, VectorStorage,
+    VectorStorageEnum,
 };
 
 /// Whether to use the new compressed format.
@@ -343,6 +344,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         .filter(|&idx| check_deleted_condition(idx, deleted_vectors, deleted_point_bitslice))
         .collect_vec();
 
+        let score_transform = sparse_score_transform(&vector_storage, sparse_vector);
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
         let memory_handle = self.scores_memory_pool.get();
         let mut search_context = SearchContext::new(
@@ -352,6 +354,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             memory_handle,
             &is_stopped,
         );
+        if let Some(score_transform) = &score_transform {
+            search_context = search_context.with_score_transform(score_transform);
+        }
         Ok(search_context.plain_search(&ids))
     }
 
@@ -376,6 +381,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
 
         let is_stopped = vector_query_context.is_stopped();
 
+        let score_transform = sparse_score_transform(&vector_storage, sparse_vector);
         let sparse_vector = self.indices_tracker.remap_vector(sparse_vector.to_owned());
         let memory_handle = self.scores_memory_pool.get();
         let mut search_context = SearchContext::new(
@@ -385,6 +391,9 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             memory_handle,
             &is_stopped,
         );
+        if let Some(score_transform) = &score_transform {
+            search_context = search_context.with_score_transform(score_transform);
+        }
 
         match filter {
             Some(filter) => {
@@ -404,6 +413,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         vector: &SparseVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -411,6 +421,16 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
             return Ok(vec![]);
         }
         let mut vector = vector.clone();
+        if let Some(sparse_top_k) = params.and_then(|params| params.sparse_top_k) {
+            let query_dims = vector.indices.len();
+            vector.retain_top_weights(sparse_top_k);
+            if vector.indices.len() < query_dims {
+                log::debug!(
+                    "Searching with {} of {query_dims} dimensions of the sparse query",
+                    vector.indices.len(),
+                );
+            }
+        }
         vector.sort_by_indices();
 
         match filter {
@@ -449,6 +469,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
         query_vector: &QueryVector,
         filter: Option<&Filter>,
         top: usize,
+        params: Option<&SearchParams>,
         prefiltered_points: &mut Option<Vec<PointOffsetType>>,
         vector_query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<ScoredPointOffset>> {
@@ -461,6 +482,7 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
                 vector.try_into()?,
                 filter,
                 top,
+                params,
                 prefiltered_points,
                 vector_query_context,
             ),
@@ -495,13 +517,31 @@ impl<TInvertedIndex: InvertedIndex> SparseVectorIndex<TInvertedIndex> {
     }
 }
 
+/// Scoring by the distance of the sparse vectors, from the dot product found by the inverted index.
+///
+/// Returns `None` if the dot product is the score.
+fn sparse_score_transform<'a>(
+    vector_storage: &'a VectorStorageEnum,
+    query: &SparseVector,
+) -> Option<impl Fn(PointOffsetType, ScoreType) -> ScoreType + 'a> {
+    let VectorStorageEnum::SparseSimple(vector_storage) = vector_storage else {
+        return None;
+    };
+    let distance = vector_storage.sparse_distance();
+    if !distance.requires_norms() {
+        return None;
+    }
+    let query_norm = query.squared_norm();
+    Some(move |idx, dot| distance.score_from_dot(dot, query_norm, vector_storage.squared_norm(idx)))
+}
+
 impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedIndex> {
     fn search(
         &self,
         vectors: &[&QueryVector],
         filter: Option<&Filter>,
         top: usize,
-        _params: Option<&SearchParams>,
+        params: Option<&SearchParams>,
         query_context: &VectorQueryContext,
     ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
         let mut results = Vec::with_capacity(vectors.len());
@@ -523,9 +563,23 @@ impl<TInvertedIndex: InvertedIndex> VectorIndex for SparseVectorIndex<TInvertedI
                     Ok(vector)
                 })?;
 
-                self.search_query(&vector, filter, top, &mut prefiltered_points, query_context)?
+                self.search_query(
+                    &vector,
+                    filter,
+                    top,
+                    params,
+                    &mut prefiltered_points,
+                    query_context,
+                )?
             } else {
-                self.search_query(vector, filter, top, &mut prefiltered_points, query_context)?
+                self.search_query(
+                    vector,
+                    filter,
+                    top,
+                    params,
+                    &mut prefiltered_points,
+                    query_context,
+                )?
             };
 
             results.push(search_results);
@@ -1,6 +1,5 @@
 use std::collections::{HashMap, HashSet};
 use std::fs::create_dir_all;
-use std::ops::Deref;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -16,10 +15,11 @@ use super::field_index::facet_index::FacetIndex;
 use super::field_index::index_selector::{
     IndexSelector, IndexSelectorOnDisk, IndexSelectorRocksDb,
 };
+use super::field_index::presence_index::{PresenceIndex, PresenceIndexBuilder};
 use super::field_index::FieldIndexBuilderTrait as _;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_wrapper::open_db_with_existing_cf;
-use crate::common::utils::IndexesMap;
+use crate::common::utils::{IndexesMap, VectorStoragesMap};
 use crate::common::Flusher;
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::{
@@ -37,9 +37,10 @@ use crate::payload_storage::{FilterContext, PayloadStorage};
 use crate::telemetry::PayloadIndexTelemetry;
 use crate::types::{
     infer_collection_value_type, infer_value_type, Condition, FieldCondition, Filter,
-    IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField, PayloadFieldSchema,
-    PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    HasFieldCondition, IsEmptyCondition, IsNullCondition, Payload, PayloadContainer, PayloadField,
+    PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
 };
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
 /// `PayloadIndex` implementation, which actually uses index structures for providing faster search
 #[derive(Debug)]
@@ -48,8 +49,16 @@ pub struct StructPayloadIndex {
     payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
     /// Used for `has_id` condition and estimating cardinality
     id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+    /// Used for `has_vector` condition and estimating cardinality
+    vector_storages: VectorStoragesMap,
     /// Indexes, associated with fields
     pub field_indexes: IndexesMap,
+    /// Presence of indexed fields, used for `has_field` condition
+    field_presence: HashMap<PayloadKeyType, PresenceIndex>,
+    /// Number of points removed from the index of each field since it was loaded.
+    /// Indexes of non-appendable segments keep removed points in their posting lists, marked as
+    /// deleted, until the segment is optimized.
+    removed_points: HashMap<PayloadKeyType, usize>,
     config: PayloadConfig,
     /// Root of index persistence dir
     path: PathBuf,
@@ -103,17 +112,40 @@ impl StructPayloadIndex {
         self.config.save(&config_path)
     }
 
+    fn presence_path(&self, field: PayloadKeyTypeRef) -> PathBuf {
+        self.path.join(format!("{}-presence", field.filename()))
+    }
+
     fn load_all_fields(&mut self) -> OperationResult<()> {
         let mut field_indexes: IndexesMap = Default::default();
+        let mut field_presence: HashMap<PayloadKeyType, PresenceIndex> = Default::default();
 
         for (field, payload_schema) in &self.config.indexed_fields {
             let field_index = self.load_from_db(field, payload_schema)?;
             field_indexes.insert(field.clone(), field_index);
+            field_presence.insert(field.clone(), self.load_presence(field)?);
         }
         self.field_indexes = field_indexes;
+        self.field_presence = field_presence;
         Ok(())
     }
 
+    fn load_presence(&self, field: PayloadKeyTypeRef) -> OperationResult<PresenceIndex> {
+        let presence_path = self.presence_path(field);
+        if presence_path.exists() {
+            return PresenceIndex::open(&presence_path);
+        }
+
+        debug!("Presence of `{field}` was not loaded. Building...");
+        let mut builder = PresenceIndexBuilder::new(&presence_path);
+        builder.init()?;
+        self.payload.borrow().iter(|point_id, point_payload| {
+            builder.add_point(point_id, &point_payload.get_value(field))?;
+            Ok(true)
+        })?;
+        builder.finalize()
+    }
+
     fn load_from_db(
         &self,
         field: PayloadKeyTypeRef,
@@ -139,9 +171,20 @@ impl StructPayloadIndex {
         Ok(indexes)
     }
 
+    /// Register storage of a vector, which was added to the segment after opening the index.
+    pub(crate) fn add_vector_storage(
+        &mut self,
+        vector_name: &str,
+        vector_storage: Arc<AtomicRefCell<VectorStorageEnum>>,
+    ) {
+        self.vector_storages
+            .insert(vector_name.to_owned(), vector_storage);
+    }
+
     pub fn open(
         payload: Arc<AtomicRefCell<PayloadStorageEnum>>,
         id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
+        vector_storages: VectorStoragesMap,
         path: &Path,
         is_appendable: bool,
     ) -> OperationResult<Self> {
@@ -159,7 +202,10 @@ impl StructPayloadIndex {
         let mut index = StructPayloadIndex {
             payload,
             id_tracker,
+            vector_storages,
             field_indexes: Default::default(),
+            field_presence: Default::default(),
+            removed_points: Default::default(),
             config,
             path: path.to_owned(),
             visited_pool: Default::default(),
@@ -191,14 +237,29 @@ impl StructPayloadIndex {
             index.init()?;
         }
 
+        // Presence does not depend on the schema, so it is only built once per field.
+        // It is persisted here and opened when the index is applied.
+        let mut presence_builder = (!self.field_presence.contains_key(field))
+            .then(|| PresenceIndexBuilder::new(&self.presence_path(field)));
+        if let Some(presence_builder) = &mut presence_builder {
+            presence_builder.init()?;
+        }
+
         payload_storage.iter(|point_id, point_payload| {
             let field_value = &point_payload.get_value(field);
             for builder in builders.iter_mut() {
                 builder.add_point(point_id, field_value)?;
             }
+            if let Some(presence_builder) = &mut presence_builder {
+                presence_builder.add_point(point_id, field_value)?;
+            }
             Ok(true)
         })?;
 
+        if let Some(presence_builder) = presence_builder {
+            presence_builder.finalize()?;
+        }
+
         builders
             .into_iter()
             .map(|builder| builder.finalize())
@@ -214,11 +275,11 @@ impl StructPayloadIndex {
 
     pub fn struct_filtered_context<'a>(&'a self, filter: &'a Filter) -> StructFilterContext<'a> {
         let estimator = |condition: &Condition| self.condition_cardinality(condition, None);
-        let id_tracker = self.id_tracker.borrow();
         let payload_provider = PayloadProvider::new(self.payload.clone());
         StructFilterContext::new(
             filter,
-            id_tracker.deref(),
+            &self.id_tracker,
+            &self.vector_storages,
             payload_provider,
             &self.field_indexes,
             &estimator,
@@ -294,6 +355,30 @@ impl StructPayloadIndex {
                     }
                 }
             }
+            Condition::HasField(HasFieldCondition { has_field: field }) => {
+                let full_path = JsonPath::extend_or_new(nested_path, &field.key);
+                let primary_clauses = vec![PrimaryCondition::HasField(HasFieldCondition {
+                    has_field: PayloadField {
+                        key: full_path.clone(),
+                    },
+                })];
+
+                match self.field_presence.get(&full_path) {
+                    Some(presence) => CardinalityEstimation {
+                        primary_clauses,
+                        ..CardinalityEstimation::exact(presence.present_count())
+                    },
+                    None => {
+                        let available_points = self.available_point_count();
+                        CardinalityEstimation {
+                            primary_clauses,
+                            min: 0,
+                            exp: available_points / 2,
+                            max: available_points,
+                        }
+                    }
+                }
+            }
             Condition::HasId(has_id) => {
                 let id_tracker_ref = self.id_tracker.borrow();
                 let mapped_ids: HashSet<PointOffsetType> = has_id
@@ -309,6 +394,29 @@ impl StructPayloadIndex {
                     max: num_ids,
                 }
             }
+            Condition::IdRange(id_range) => {
+                let num_ids = self
+                    .id_tracker
+                    .borrow()
+                    .iter_id_range(&id_range.id_range)
+                    .count();
+                CardinalityEstimation {
+                    primary_clauses: vec![PrimaryCondition::IdRange(id_range.id_range.clone())],
+                    min: num_ids,
+                    exp: num_ids,
+                    max: num_ids,
+                }
+            }
+            Condition::HasVector(has_vector) => {
+                let available_vectors = self
+                    .vector_storages
+                    .get(&has_vector.has_vector)
+                    .map_or(0, |vector_storage| {
+                        vector_storage.borrow().available_vector_count()
+                    });
+                // There is no index over vectors presence, so it is a full scan
+                CardinalityEstimation::exact(available_vectors)
+            }
 
             Condition::Field(field_condition) => self
                 .estimate_field_condition(field_condition, nested_path)
@@ -339,12 +447,22 @@ impl StructPayloadIndex {
         crate::rocksdb_backup::restore(snapshot_path, &segment_path.join("payload_index"))
     }
 
+    /// Number of points removed from the index of each field, which are still kept in its
+    /// posting lists. Removed points are only counted since the segment was loaded.
+    pub fn removed_points(&self) -> &HashMap<PayloadKeyType, usize> {
+        &self.removed_points
+    }
+
     fn clear_index_for_point(&mut self, point_id: PointOffsetType) -> OperationResult<()> {
-        for (_, field_indexes) in self.field_indexes.iter_mut() {
-            for index in field_indexes {
-                index.remove_point(point_id)?;
+        for (field, field_indexes) in self.field_indexes.iter_mut() {
+            // Indexes of appendable segments are mutable, they drop removed points right away
+            if remove_point_from_indexes(field_indexes, point_id)? && !self.is_appendable {
+                *self.removed_points.entry(field.clone()).or_default() += 1;
             }
         }
+        for presence in self.field_presence.values_mut() {
+            presence.remove_point(point_id)?;
+        }
         Ok(())
     }
     pub fn config(&self) -> &PayloadConfig {
@@ -382,8 +500,19 @@ impl StructPayloadIndex {
                             )
                         }
                         PrimaryCondition::Ids(ids) => Box::new(ids.iter().copied()),
+                        PrimaryCondition::IdRange(id_range) => Box::new(
+                            id_tracker
+                                .iter_id_range(id_range)
+                                .map(|(_external_id, internal_id)| internal_id),
+                        ),
                         PrimaryCondition::IsEmpty(_) => id_tracker.iter_ids(), /* there are no fast index for IsEmpty */
                         PrimaryCondition::IsNull(_) => id_tracker.iter_ids(),  /* no fast index for IsNull too */
+                        PrimaryCondition::HasField(has_field) => {
+                            match self.field_presence.get(&has_field.has_field.key) {
+                                Some(presence) => Box::new(presence.iter_present()),
+                                None => id_tracker.iter_ids(),
+                            }
+                        }
                     }
                 })
                 .filter(move |&id| !visited_list.check_and_update_visited(id))
@@ -414,6 +543,22 @@ impl StructPayloadIndex {
     }
 }
 
+/// Remove a point from all indexes of a field.
+///
+/// Returns true if the point was indexed.
+fn remove_point_from_indexes(
+    indexes: &mut [FieldIndex],
+    point_id: PointOffsetType,
+) -> OperationResult<bool> {
+    let mut was_indexed = false;
+    for index in indexes {
+        let indexed_points = index.count_indexed_points();
+        index.remove_point(point_id)?;
+        was_indexed |= index.count_indexed_points() < indexed_points;
+    }
+    Ok(was_indexed)
+}
+
 impl PayloadIndex for StructPayloadIndex {
     fn indexed_fields(&self) -> HashMap<PayloadKeyType, PayloadFieldSchema> {
         self.config.indexed_fields.clone()
@@ -444,6 +589,12 @@ impl PayloadIndex for StructPayloadIndex {
         field_index: Vec<FieldIndex>,
     ) -> OperationResult<()> {
         self.field_indexes.insert(field.clone(), field_index);
+        self.removed_points.remove(&field);
+
+        if !self.field_presence.contains_key(&field) {
+            let presence = PresenceIndex::open(&self.presence_path(&field))?;
+            self.field_presence.insert(field.clone(), presence);
+        }
 
         self.config.indexed_fields.insert(field, payload_schema);
 
@@ -462,6 +613,12 @@ impl PayloadIndex for StructPayloadIndex {
             }
         }
 
+        self.removed_points.remove(field);
+
+        if let Some(presence) = self.field_presence.remove(field) {
+            presence.clear()?;
+        }
+
         self.save_config()?;
         Ok(())
     }
@@ -533,14 +690,15 @@ impl PayloadIndex for StructPayloadIndex {
 
         for (field, field_index) in &mut self.field_indexes {
             let field_value = payload.get_value(field);
+            if let Some(presence) = self.field_presence.get_mut(field) {
+                presence.set_point(point_id, &field_value)?;
+            }
             if !field_value.is_empty() {
                 for index in field_index {
                     index.add_point(point_id, &field_value)?;
                 }
-            } else {
-                for index in field_index {
-                    index.remove_point(point_id)?;
-                }
+            } else if remove_point_from_indexes(field_index, point_id)? && !self.is_appendable {
+                *self.removed_points.entry(field.clone()).or_default() += 1;
             }
         }
         Ok(())
@@ -566,14 +724,15 @@ impl PayloadIndex for StructPayloadIndex {
                 continue;
             }
             let field_value = updated_payload.get_value(field);
+            if let Some(presence) = self.field_presence.get_mut(field) {
+                presence.set_point(point_id, &field_value)?;
+            }
             if !field_value.is_empty() {
                 for index in field_index {
                     index.add_point(point_id, &field_value)?;
                 }
-            } else {
-                for index in field_index {
-                    index.remove_point(point_id)?;
-                }
+            } else if remove_point_from_indexes(field_index, point_id)? && !self.is_appendable {
+                *self.removed_points.entry(field.clone()).or_default() += 1;
             }
         }
         Ok(())
@@ -583,16 +742,27 @@ impl PayloadIndex for StructPayloadIndex {
         self.payload.borrow().get(point_id)
     }
 
+    fn get_payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        self.payload.borrow().get_projected(point_id, include)
+    }
+
     fn delete_payload(
         &mut self,
         point_id: PointOffsetType,
         key: PayloadKeyTypeRef,
     ) -> OperationResult<Vec<Value>> {
         if let Some(indexes) = self.field_indexes.get_mut(key) {
-            for index in indexes {
-                index.remove_point(point_id)?;
+            if remove_point_from_indexes(indexes, point_id)? && !self.is_appendable {
+                *self.removed_points.entry(key.clone()).or_default() += 1;
             }
         }
+        if let Some(presence) = self.field_presence.get_mut(key) {
+            presence.remove_point(point_id)?;
+        }
         self.payload.borrow_mut().delete(point_id, key)
     }
 
@@ -608,6 +778,9 @@ impl PayloadIndex for StructPayloadIndex {
                 flushers.push(index.flusher());
             }
         }
+        for presence in self.field_presence.values() {
+            flushers.push(presence.flusher());
+        }
         flushers.push(self.payload.borrow().flusher());
         Box::new(move || {
             for flusher in flushers {
@@ -644,6 +817,11 @@ impl PayloadIndex for StructPayloadIndex {
             .values()
             .flat_map(|indexes| indexes.iter().flat_map(|index| index.files().into_iter()))
             .collect::<Vec<PathBuf>>();
+        files.extend(
+            self.field_presence
+                .values()
+                .flat_map(|presence| presence.files()),
+        );
         files.push(self.config_path());
         files
     }
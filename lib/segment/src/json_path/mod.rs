@@ -171,6 +171,13 @@ impl JsonPath {
         result
     }
 
+    /// Check if the path only consists of object keys, e.g. `a.b` but not `a[0].b` or `a[]`.
+    pub fn is_key_path(&self) -> bool {
+        self.rest
+            .iter()
+            .all(|item| matches!(item, JsonPathItem::Key(_)))
+    }
+
     /// Check if a path is included in a list of patterns.
     ///
     /// Basically, it checks if either the pattern or path is a prefix of the other.
@@ -6,7 +6,7 @@ use serde_json::Value;
 
 use crate::types::{
     AnyVariants, DateTimePayloadType, FieldCondition, FloatPayloadType, GeoBoundingBox, GeoPoint,
-    GeoPolygon, GeoRadius, Match, MatchAny, MatchExcept, MatchText, MatchValue, Range,
+    GeoPolygon, GeoRadius, Match, MatchAll, MatchAny, MatchExcept, MatchText, MatchValue, Range,
     RangeInterface, ValueVariants, ValuesCount,
 };
 
@@ -73,12 +73,51 @@ impl ValueChecker for FieldCondition {
                 .as_ref()
                 .unwrap()
                 .check_count_from(payload)
+        } else if let Some(Match::All(match_all)) = &self.r#match {
+            // Arrays are checked as a whole, not element by element
+            match_all.check_values([payload])
         } else {
             self._check(payload)
         }
     }
 }
 
+impl MatchAll {
+    /// Check that all required values are present among the given values of a field.
+    ///
+    /// Arrays are flattened one level deep. `null` values are ignored, so a missing or `null`
+    /// field never matches, while an empty array matches an empty list of required values.
+    pub fn check_values<'a>(&self, values: impl IntoIterator<Item = &'a Value>) -> bool {
+        let mut is_present = false;
+        let mut found = vec![false; self.all.len()];
+
+        for value in values {
+            let elements = match value {
+                Value::Null => continue,
+                Value::Array(array) => array.as_slice(),
+                _ => std::slice::from_ref(value),
+            };
+            is_present = true;
+            for element in elements {
+                let position = match (element, &self.all) {
+                    (Value::String(stored), AnyVariants::Strings(list)) => {
+                        list.get_index_of(stored.as_str())
+                    }
+                    (Value::Number(stored), AnyVariants::Integers(list)) => {
+                        stored.as_i64().and_then(|num| list.get_index_of(&num))
+                    }
+                    _ => None,
+                };
+                if let Some(position) = position {
+                    found[position] = true;
+                }
+            }
+        }
+
+        is_present && found.into_iter().all(|x| x)
+    }
+}
+
 impl ValueChecker for Match {
     fn check_match(&self, payload: &Value) -> bool {
         match self {
@@ -139,6 +178,7 @@ impl ValueChecker for Match {
                 (Value::Number(_), _) => true,
                 (Value::String(_), _) => true,
             },
+            Match::All(match_all) => match_all.check_values([payload]),
         }
     }
 }
@@ -251,6 +291,7 @@ mod tests {
                 lon: 13.423637,
             },
             radius: 2000.0,
+            inner_radius: None,
         };
         let miss_geo_query = GeoRadius {
             center: GeoPoint {
@@ -258,6 +299,7 @@ mod tests {
                 lon: 20.423637,
             },
             radius: 2000.0,
+            inner_radius: None,
         };
 
         assert!(near_berlin_query.check(&berlin_and_moscow));
@@ -299,4 +341,26 @@ mod tests {
         };
         assert!(gte_two_countries_query.check(&countries));
     }
+
+    #[test]
+    fn test_match_all() {
+        let match_all = |values: Vec<&str>| {
+            MatchAll::from(values.into_iter().map(String::from).collect::<Vec<_>>())
+        };
+
+        let tags = json!(["red", "green", "blue"]);
+        assert!(match_all(vec!["red", "blue"]).check_values([&tags]));
+        assert!(!match_all(vec!["red", "black"]).check_values([&tags]));
+
+        // Values may be spread across multiple values of the field
+        assert!(match_all(vec!["red", "blue"]).check_values([&json!("red"), &json!(["blue"])]));
+
+        // Empty array is present, but has no values
+        assert!(match_all(vec![]).check_values([&json!([])]));
+        assert!(!match_all(vec!["red"]).check_values([&json!([])]));
+
+        // Missing and null fields never match
+        assert!(!match_all(vec![]).check_values([]));
+        assert!(!match_all(vec![]).check_values([&Value::Null]));
+    }
 }
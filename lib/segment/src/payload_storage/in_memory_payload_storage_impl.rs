@@ -82,7 +82,7 @@ mod tests {
     use serde_json::json;
 
     use super::*;
-    use crate::common::utils::IndexesMap;
+    use crate::common::utils::{IndexesMap, VectorStoragesMap};
     use crate::fixtures::payload_context_fixture::FixtureIdTracker;
     use crate::payload_storage::query_checker::check_payload;
     use crate::types::{Condition, FieldCondition, Filter, OwnedPayloadRef};
@@ -132,6 +132,7 @@ mod tests {
                 payload.borrow().as_ref().cloned().unwrap()
             }),
             Some(&id_tracker),
+            &VectorStoragesMap::new(),
             &query,
             0,
             &IndexesMap::new(),
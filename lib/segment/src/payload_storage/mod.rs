@@ -4,10 +4,14 @@ pub mod in_memory_payload_storage;
 #[cfg(feature = "testing")]
 pub mod in_memory_payload_storage_impl;
 pub mod on_disk_payload_storage;
+pub mod payload_codec;
+mod payload_projection;
 mod payload_storage_base;
 pub mod payload_storage_enum;
+pub mod point_revision_storage;
 pub mod query_checker;
 pub mod simple_payload_storage;
 pub mod simple_payload_storage_impl;
+pub mod vector_metadata_storage;
 
 pub use payload_storage_base::*;
@@ -5,28 +5,40 @@ use parking_lot::RwLock;
 use rocksdb::DB;
 use serde_json::Value;
 
-use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::operation_error::OperationResult;
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
 use crate::common::Flusher;
 use crate::json_path::JsonPath;
+use crate::payload_storage::payload_codec::PayloadCodec;
 use crate::payload_storage::PayloadStorage;
-use crate::types::Payload;
+use crate::types::{Payload, PayloadCompressionConfig};
 
 /// On-disk implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, does not keep payload in memory
 #[derive(Debug)]
 pub struct OnDiskPayloadStorage {
     db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    codec: PayloadCodec,
 }
 
 impl OnDiskPayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        compression: Option<PayloadCompressionConfig>,
+    ) -> OperationResult<Self> {
         let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
             database,
             DB_PAYLOAD_CF,
         ));
-        Ok(OnDiskPayloadStorage { db_wrapper })
+        Ok(OnDiskPayloadStorage {
+            db_wrapper,
+            codec: PayloadCodec::new(compression),
+        })
+    }
+
+    pub fn codec(&self) -> &PayloadCodec {
+        &self.codec
     }
 
     pub fn remove_from_storage(&self, point_id: PointOffsetType) -> OperationResult<()> {
@@ -41,16 +53,27 @@ impl OnDiskPayloadStorage {
     ) -> OperationResult<()> {
         self.db_wrapper.put(
             serde_cbor::to_vec(&point_id).unwrap(),
-            serde_cbor::to_vec(payload).unwrap(),
+            self.codec.encode(payload)?,
         )
     }
 
     pub fn read_payload(&self, point_id: PointOffsetType) -> OperationResult<Option<Payload>> {
         let key = serde_cbor::to_vec(&point_id).unwrap();
         self.db_wrapper
-            .get_pinned(&key, |raw| serde_cbor::from_slice(raw))?
+            .get_pinned(&key, |raw| self.codec.decode(raw))?
+            .transpose()
+    }
+
+    /// Read only the selected fields of point payload, without deserializing the rest of it
+    pub fn read_payload_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Option<Payload>> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        self.db_wrapper
+            .get_pinned(&key, |raw| self.codec.decode_projected(raw, include))?
             .transpose()
-            .map_err(OperationError::from)
     }
 
     pub fn iter<F>(&self, mut callback: F) -> OperationResult<()>
@@ -58,10 +81,7 @@ impl OnDiskPayloadStorage {
         F: FnMut(PointOffsetType, &Payload) -> OperationResult<bool>,
     {
         for (key, val) in self.db_wrapper.lock_db().iter()? {
-            let do_continue = callback(
-                serde_cbor::from_slice(&key)?,
-                &serde_cbor::from_slice(&val)?,
-            )?;
+            let do_continue = callback(serde_cbor::from_slice(&key)?, &self.codec.decode(&val)?)?;
             if !do_continue {
                 return Ok(());
             }
@@ -115,6 +135,15 @@ impl PayloadStorage for OnDiskPayloadStorage {
         }
     }
 
+    fn get_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        let payload = self.read_payload_projected(point_id, include)?;
+        Ok(payload.unwrap_or_default())
+    }
+
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
         let stored_payload = self.read_payload(point_id)?;
 
@@ -0,0 +1,192 @@
+//! Binary encoding of payload records in RocksDB.
+//!
+//! Records are stored as CBOR. If compression is enabled, CBOR records above
+//! [`MIN_COMPRESSIBLE_RECORD_SIZE`] are compressed with zstd and prefixed with
+//! [`COMPRESSED_RECORD_MARKER`]. A CBOR encoded payload is always a map and thus never starts with
+//! this marker, so compressed and uncompressed records may be mixed freely within a single storage.
+//! This also keeps storages written before compression was enabled readable.
+
+use serde::de::DeserializeSeed as _;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::json_path::JsonPath;
+use crate::payload_storage::payload_projection::PayloadProjection;
+use crate::telemetry::PayloadCompressionTelemetry;
+use crate::types::{Payload, PayloadCompressionConfig};
+
+/// First byte of a zstd compressed record.
+///
+/// In CBOR this byte encodes the unsigned integer `0`, while payloads are always encoded as maps.
+const COMPRESSED_RECORD_MARKER: u8 = 0x00;
+
+/// Records smaller than this are stored uncompressed, as zstd framing overhead would outweigh
+/// the savings.
+const MIN_COMPRESSIBLE_RECORD_SIZE: usize = 64;
+
+#[derive(Debug, Default)]
+pub struct PayloadCodec {
+    compression: Option<PayloadCompressionConfig>,
+}
+
+impl PayloadCodec {
+    pub fn new(compression: Option<PayloadCompressionConfig>) -> Self {
+        Self { compression }
+    }
+
+    pub fn encode(&self, payload: &Payload) -> OperationResult<Vec<u8>> {
+        let raw = serde_cbor::to_vec(payload)?;
+
+        let Some(config) = self.compression else {
+            return Ok(raw);
+        };
+
+        if raw.len() < MIN_COMPRESSIBLE_RECORD_SIZE {
+            return Ok(raw);
+        }
+
+        let mut compressed = Vec::with_capacity(raw.len() / 2);
+        compressed.push(COMPRESSED_RECORD_MARKER);
+        zstd::stream::copy_encode(raw.as_slice(), &mut compressed, config.level).map_err(
+            |err| OperationError::service_error(format!("Failed to compress payload: {err}")),
+        )?;
+
+        // Keep the record uncompressed if compression didn't pay off
+        if compressed.len() < raw.len() {
+            Ok(compressed)
+        } else {
+            Ok(raw)
+        }
+    }
+
+    pub fn decode(&self, stored: &[u8]) -> OperationResult<Payload> {
+        match stored.split_first() {
+            Some((&COMPRESSED_RECORD_MARKER, compressed)) => {
+                Ok(serde_cbor::from_slice(&decompress(compressed)?)?)
+            }
+            _ => Ok(serde_cbor::from_slice(stored)?),
+        }
+    }
+
+    /// Decode only the fields of a record selected by `include`.
+    ///
+    /// Same as applying [`PayloadSelectorInclude`](crate::types::PayloadSelectorInclude) to the
+    /// decoded record, but fields which are not selected are skipped without being deserialized.
+    pub fn decode_projected(
+        &self,
+        stored: &[u8],
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        match stored.split_first() {
+            Some((&COMPRESSED_RECORD_MARKER, compressed)) => {
+                decode_cbor_projected(&decompress(compressed)?, include)
+            }
+            _ => decode_cbor_projected(stored, include),
+        }
+    }
+
+    pub fn get_telemetry_data(&self) -> Option<PayloadCompressionTelemetry> {
+        let config = self.compression?;
+        Some(PayloadCompressionTelemetry {
+            level: config.level,
+        })
+    }
+}
+
+fn decompress(compressed: &[u8]) -> OperationResult<Vec<u8>> {
+    zstd::stream::decode_all(compressed).map_err(|err| {
+        OperationError::service_error(format!("Failed to decompress payload: {err}"))
+    })
+}
+
+fn decode_cbor_projected(raw: &[u8], include: &[JsonPath]) -> OperationResult<Payload> {
+    let mut deserializer = serde_cbor::Deserializer::from_slice(raw);
+    let payload = PayloadProjection(include).deserialize(&mut deserializer)?;
+    deserializer.end()?;
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::types::PayloadSelector;
+
+    fn payload(value: serde_json::Value) -> Payload {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn test_small_payload_stored_uncompressed() {
+        let codec = PayloadCodec::new(Some(PayloadCompressionConfig::default()));
+        let small = payload(json!({"a": 1}));
+
+        let stored = codec.encode(&small).unwrap();
+        assert_eq!(stored, serde_cbor::to_vec(&small).unwrap());
+        assert_eq!(codec.decode(&stored).unwrap(), small);
+    }
+
+    #[test]
+    fn test_large_payload_roundtrip() {
+        let codec = PayloadCodec::new(Some(PayloadCompressionConfig::default()));
+        let large = payload(json!({
+            "description": "metadata ".repeat(100),
+            "tags": vec!["tag"; 50],
+        }));
+
+        let stored = codec.encode(&large).unwrap();
+        assert_eq!(stored[0], COMPRESSED_RECORD_MARKER);
+        assert!(stored.len() < serde_cbor::to_vec(&large).unwrap().len());
+        assert_eq!(codec.decode(&stored).unwrap(), large);
+        assert!(codec.get_telemetry_data().is_some());
+
+        // Uncompressed codec still reads compressed records
+        let plain = PayloadCodec::default();
+        assert_eq!(plain.decode(&stored).unwrap(), large);
+        assert!(plain.get_telemetry_data().is_none());
+    }
+
+    #[test]
+    fn test_decode_projected() {
+        let record = payload(json!({
+            "a": 1,
+            "b": {"c": {"d": "text", "e": [1, 2, 3]}, "f": true},
+            "g": [{"h": 1, "i": 2}, {"h": 3}, 4],
+            "j": "metadata ".repeat(100),
+        }));
+
+        let selections = [
+            vec!["a"],
+            vec!["b.c.d"],
+            vec!["b.c", "b.c.d"],
+            vec!["b.c.e[]", "j"],
+            vec!["g[].h"],
+            vec!["g[0]"],
+            vec!["a.x", "missing"],
+        ];
+
+        for compression in [None, Some(PayloadCompressionConfig::default())] {
+            let codec = PayloadCodec::new(compression);
+            let stored = codec.encode(&record).unwrap();
+
+            for selection in &selections {
+                let include: Vec<JsonPath> = selection.iter().map(|p| p.parse().unwrap()).collect();
+                let expected =
+                    PayloadSelector::new_include(include.clone()).process(record.clone());
+                assert_eq!(
+                    codec.decode_projected(&stored, &include).unwrap(),
+                    expected,
+                    "selection {selection:?}",
+                );
+            }
+        }
+
+        let codec = PayloadCodec::default();
+        let stored = codec.encode(&record).unwrap();
+        let include = vec!["b.c.d".parse().unwrap()];
+        assert_eq!(
+            codec.decode_projected(&stored, &include).unwrap(),
+            payload(json!({"b": {"c": {"d": "text"}}})),
+        );
+    }
+}
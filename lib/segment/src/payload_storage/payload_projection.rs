@@ -0,0 +1,159 @@
+//! Deserialization of selected payload fields only.
+//!
+//! Selection follows [`PayloadSelectorInclude`](crate::types::PayloadSelectorInclude): a value is
+//! kept if its path and one of the included paths are a prefix of one another. Values outside of
+//! the selection are skipped by the deserializer, so they are never allocated. Nested paths, such
+//! as `a.b.c`, only materialize the subtree under `a.b.c` and the objects leading to it.
+
+use std::fmt;
+
+use serde::de::{DeserializeSeed, Deserializer, IgnoredAny, MapAccess, SeqAccess, Visitor};
+use serde::Deserialize as _;
+use serde_json::{Map, Value};
+
+use crate::json_path::{JsonPath, JsonPathItem};
+use crate::types::Payload;
+
+/// Deserializes a payload, keeping only the fields selected by the included paths.
+pub struct PayloadProjection<'a>(pub &'a [JsonPath]);
+
+impl<'de> DeserializeSeed<'de> for PayloadProjection<'_> {
+    type Value = Payload;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Payload, D::Error> {
+        deserializer.deserialize_map(self)
+    }
+}
+
+impl<'de> Visitor<'de> for PayloadProjection<'_> {
+    type Value = Payload;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a payload map")
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Payload, A::Error> {
+        let mut payload = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let rests: Vec<_> = self
+                .0
+                .iter()
+                .filter(|path| path.first_key == key)
+                .map(|path| path.rest.as_slice())
+                .collect();
+            if rests.is_empty() {
+                map.next_value::<IgnoredAny>()?;
+            } else {
+                let value = map.next_value_seed(ValueProjection(&rests))?;
+                payload.insert(key, value);
+            }
+        }
+        Ok(Payload(payload))
+    }
+}
+
+/// Deserializes a selected value, given the remainders of the included paths which lead to it.
+#[derive(Clone, Copy)]
+struct ValueProjection<'a>(&'a [&'a [JsonPathItem]]);
+
+impl<'a> ValueProjection<'a> {
+    /// Whether one of the included paths ends at this value, so it is selected as a whole
+    fn selects_all(&self) -> bool {
+        self.0.iter().any(|rest| rest.is_empty())
+    }
+
+    /// Remainders of the included paths which continue with a matching item
+    fn descend(&self, matches: impl Fn(&JsonPathItem) -> bool) -> Vec<&'a [JsonPathItem]> {
+        self.0
+            .iter()
+            .copied()
+            .filter_map(<[JsonPathItem]>::split_first)
+            .filter(|&(head, _)| matches(head))
+            .map(|(_, tail)| tail)
+            .collect()
+    }
+}
+
+impl<'de> DeserializeSeed<'de> for ValueProjection<'_> {
+    type Value = Value;
+
+    fn deserialize<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        if self.selects_all() {
+            Value::deserialize(deserializer)
+        } else {
+            deserializer.deserialize_any(self)
+        }
+    }
+}
+
+impl<'de> Visitor<'de> for ValueProjection<'_> {
+    type Value = Value;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("a payload value")
+    }
+
+    fn visit_bool<E>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_u64<E>(self, value: u64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_f64<E>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::from(value))
+    }
+
+    fn visit_str<E>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_unit<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Value, D::Error> {
+        self.deserialize(deserializer)
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let rests = self.descend(|item| *item == JsonPathItem::WildcardIndex);
+        let mut array = Vec::new();
+        if rests.is_empty() {
+            while seq.next_element::<IgnoredAny>()?.is_some() {}
+        } else {
+            while let Some(value) = seq.next_element_seed(ValueProjection(&rests))? {
+                array.push(value);
+            }
+        }
+        Ok(Value::Array(array))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut object = Map::new();
+        while let Some(key) = map.next_key::<String>()? {
+            let rests = self
+                .descend(|item| matches!(item, JsonPathItem::Key(item_key) if *item_key == key));
+            if rests.is_empty() {
+                map.next_value::<IgnoredAny>()?;
+            } else {
+                let value = map.next_value_seed(ValueProjection(&rests))?;
+                object.insert(key, value);
+            }
+        }
+        Ok(Value::Object(object))
+    }
+}
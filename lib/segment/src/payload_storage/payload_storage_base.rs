@@ -25,6 +25,16 @@ pub trait PayloadStorage {
     /// Get payload for point. If no payload found, return empty payload
     fn get(&self, point_id: PointOffsetType) -> OperationResult<Payload>;
 
+    /// Get only the fields of point payload selected by `include`.
+    /// If no payload found, return empty payload
+    fn get_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        Ok(self.get(point_id)?.project(include))
+    }
+
     /// Delete payload by point_id and key
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>>;
 
@@ -9,6 +9,7 @@ use crate::payload_storage::in_memory_payload_storage::InMemoryPayloadStorage;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
 use crate::payload_storage::PayloadStorage;
+use crate::telemetry::PayloadCompressionTelemetry;
 use crate::types::Payload;
 
 #[derive(Debug)]
@@ -50,6 +51,15 @@ impl PayloadStorageEnum {
             PayloadStorageEnum::OnDiskPayloadStorage(s) => s.iter(callback),
         }
     }
+
+    pub fn get_compression_telemetry(&self) -> Option<PayloadCompressionTelemetry> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(_) => None,
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.codec().get_telemetry_data(),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.codec().get_telemetry_data(),
+        }
+    }
 }
 
 impl PayloadStorage for PayloadStorageEnum {
@@ -94,6 +104,19 @@ impl PayloadStorage for PayloadStorageEnum {
         }
     }
 
+    fn get_projected(
+        &self,
+        point_id: PointOffsetType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        match self {
+            #[cfg(feature = "testing")]
+            PayloadStorageEnum::InMemoryPayloadStorage(s) => s.get_projected(point_id, include),
+            PayloadStorageEnum::SimplePayloadStorage(s) => s.get_projected(point_id, include),
+            PayloadStorageEnum::OnDiskPayloadStorage(s) => s.get_projected(point_id, include),
+        }
+    }
+
     fn delete(&mut self, point_id: PointOffsetType, key: &JsonPath) -> OperationResult<Vec<Value>> {
         match self {
             #[cfg(feature = "testing")]
@@ -144,7 +167,7 @@ mod tests {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
 
-        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db).unwrap().into();
+        let mut storage: PayloadStorageEnum = SimplePayloadStorage::open(db, None).unwrap().into();
         let payload: Payload = serde_json::from_str(r#"{"name": "John Doe"}"#).unwrap();
         storage.set(100, &payload).unwrap();
         storage.wipe().unwrap();
@@ -163,7 +186,7 @@ mod tests {
 
         {
             let mut storage: PayloadStorageEnum =
-                SimplePayloadStorage::open(db.clone()).unwrap().into();
+                SimplePayloadStorage::open(db.clone(), None).unwrap().into();
             let payload: Payload = serde_json::from_str(
                 r#"{
                 "name": "John Doe",
@@ -194,7 +217,8 @@ mod tests {
         }
 
         {
-            let mut storage: PayloadStorageEnum = OnDiskPayloadStorage::open(db).unwrap().into();
+            let mut storage: PayloadStorageEnum =
+                OnDiskPayloadStorage::open(db, None).unwrap().into();
 
             let res = storage.get(100).unwrap();
 
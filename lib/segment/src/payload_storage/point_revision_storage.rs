@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_POINT_REVISION_CF};
+use crate::common::Flusher;
+use crate::types::SeqNumberType;
+
+/// On-disk storage of point revisions, the versions of points exposed to users.
+///
+/// Unlike internal point versions, which are operation numbers of the WAL of each replica,
+/// revisions are derived from the operations themselves, so they are the same on all replicas.
+/// Does not keep anything in memory, revisions are only read when requested.
+#[derive(Debug)]
+pub struct PointRevisionStorage {
+    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+}
+
+impl PointRevisionStorage {
+    pub fn open(database: Arc<RwLock<DB>>) -> Self {
+        let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
+            database,
+            DB_POINT_REVISION_CF,
+        ));
+        Self { db_wrapper }
+    }
+
+    /// Revision of the point, 0 if it has none
+    pub fn get(&self, point_id: PointOffsetType) -> OperationResult<SeqNumberType> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let revision = self
+            .db_wrapper
+            .get_pinned(&key, |raw| <[u8; 8]>::try_from(raw))?
+            .transpose()
+            .map_err(|err| {
+                OperationError::service_error(format!("Failed to decode point revision: {err}"))
+            })?;
+        Ok(revision.map_or(0, SeqNumberType::from_le_bytes))
+    }
+
+    pub fn set(&self, point_id: PointOffsetType, revision: SeqNumberType) -> OperationResult<()> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        self.db_wrapper.put(key, revision.to_le_bytes())
+    }
+
+    pub fn clear(&self, point_id: PointOffsetType) -> OperationResult<()> {
+        self.db_wrapper
+            .remove(serde_cbor::to_vec(&point_id).unwrap())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+
+    #[test]
+    fn test_point_revision_storage() {
+        let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = PointRevisionStorage::open(db);
+
+        assert_eq!(storage.get(1).unwrap(), 0);
+
+        storage.set(1, 1_700_000_000_000_000).unwrap();
+        storage.set(2, 7).unwrap();
+        assert_eq!(storage.get(1).unwrap(), 1_700_000_000_000_000);
+        assert_eq!(storage.get(2).unwrap(), 7);
+
+        storage.clear(1).unwrap();
+        assert_eq!(storage.get(1).unwrap(), 0);
+
+        storage.set(1, 8).unwrap();
+        storage.flusher()().unwrap();
+        assert_eq!(storage.get(1).unwrap(), 8);
+        assert_eq!(storage.get(2).unwrap(), 7);
+    }
+}
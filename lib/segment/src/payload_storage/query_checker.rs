@@ -8,16 +8,17 @@ use std::sync::Arc;
 use atomic_refcell::AtomicRefCell;
 use common::types::PointOffsetType;
 
-use crate::common::utils::{check_is_empty, check_is_null, IndexesMap};
+use crate::common::utils::{check_is_empty, check_is_null, IndexesMap, VectorStoragesMap};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::field_index::FieldIndex;
 use crate::payload_storage::condition_checker::ValueChecker;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
 use crate::payload_storage::ConditionChecker;
 use crate::types::{
-    Condition, FieldCondition, Filter, IsEmptyCondition, IsNullCondition, MinShould,
-    OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
+    Condition, FieldCondition, Filter, HasFieldCondition, HasVectorCondition, IsEmptyCondition,
+    IsNullCondition, Match, MinShould, OwnedPayloadRef, Payload, PayloadContainer, PayloadKeyType,
 };
+use crate::vector_storage::VectorStorage;
 
 fn check_condition<F>(checker: &F, condition: &Condition) -> bool
 where
@@ -113,6 +114,7 @@ where
 pub fn check_payload<'a, R>(
     get_payload: Box<dyn Fn() -> OwnedPayloadRef<'a> + 'a>,
     id_tracker: Option<&IdTrackerSS>,
+    vector_storages: &VectorStoragesMap,
     query: &Filter,
     point_id: PointOffsetType,
     field_indexes: &HashMap<PayloadKeyType, R>,
@@ -126,9 +128,18 @@ where
         }
         Condition::IsEmpty(is_empty) => check_is_empty_condition(is_empty, get_payload().deref()),
         Condition::IsNull(is_null) => check_is_null_condition(is_null, get_payload().deref()),
+        Condition::HasField(has_field) => {
+            check_has_field_condition(has_field, get_payload().deref())
+        }
         Condition::HasId(has_id) => id_tracker
             .and_then(|id_tracker| id_tracker.external_id(point_id))
             .map_or(false, |id| has_id.has_id.contains(&id)),
+        Condition::IdRange(id_range) => id_tracker
+            .and_then(|id_tracker| id_tracker.external_id(point_id))
+            .map_or(false, |id| id_range.id_range.check(id)),
+        Condition::HasVector(has_vector) => {
+            check_has_vector_condition(has_vector, vector_storages, point_id)
+        }
         Condition::Nested(nested) => {
             let nested_path = nested.array_key();
             let nested_indexes = select_nested_indexes(&nested_path, field_indexes);
@@ -140,6 +151,7 @@ where
                     check_payload(
                         Box::new(|| OwnedPayloadRef::from(object)),
                         None,
+                        vector_storages,
                         &nested.nested.filter,
                         point_id,
                         &nested_indexes,
@@ -164,6 +176,27 @@ pub fn check_is_empty_condition(
     check_is_empty(payload.get_value(&is_empty.is_empty.key).iter().copied())
 }
 
+pub fn check_has_field_condition(
+    has_field: &HasFieldCondition,
+    payload: &impl PayloadContainer,
+) -> bool {
+    !check_is_empty(payload.get_value(&has_field.has_field.key).iter().copied())
+}
+
+/// Vectors which were not provided for a point are marked as deleted in the vector storage,
+/// so a present zero vector is distinguished from an absent one.
+pub fn check_has_vector_condition(
+    has_vector: &HasVectorCondition,
+    vector_storages: &VectorStoragesMap,
+    point_id: PointOffsetType,
+) -> bool {
+    vector_storages
+        .get(&has_vector.has_vector)
+        .map_or(false, |vector_storage| {
+            !vector_storage.borrow().is_deleted_vector(point_id)
+        })
+}
+
 pub fn check_is_null_condition(is_null: &IsNullCondition, payload: &impl PayloadContainer) -> bool {
     check_is_null(payload.get_value(&is_null.is_null.key).iter().copied())
 }
@@ -177,6 +210,12 @@ where
     R: AsRef<Vec<FieldIndex>>,
 {
     let field_values = payload.get_value(&field_condition.key);
+
+    // Values must be present across all values of the field, not within any single one of them
+    if let Some(Match::All(match_all)) = &field_condition.r#match {
+        return match_all.check_values(field_values.iter().copied());
+    }
+
     let field_indexes = field_indexes.get(&field_condition.key);
 
     // This covers a case, when a field index affects the result of the condition.
@@ -276,6 +315,7 @@ impl ConditionChecker for SimpleConditionChecker {
                 payload_ref_cell.borrow().as_ref().cloned().unwrap()
             }),
             Some(id_tracker.deref()),
+            &VectorStoragesMap::new(),
             query,
             point_id,
             &IndexesMap::new(),
@@ -326,7 +366,7 @@ mod tests {
         .into();
 
         let mut payload_storage: PayloadStorageEnum =
-            SimplePayloadStorage::open(db.clone()).unwrap().into();
+            SimplePayloadStorage::open(db.clone(), None).unwrap().into();
         let mut id_tracker = SimpleIdTracker::open(db).unwrap();
 
         id_tracker.set_link(0.into(), 0).unwrap();
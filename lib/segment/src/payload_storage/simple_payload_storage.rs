@@ -8,7 +8,8 @@ use rocksdb::DB;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
 use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_PAYLOAD_CF};
-use crate::types::Payload;
+use crate::payload_storage::payload_codec::PayloadCodec;
+use crate::types::{Payload, PayloadCompressionConfig};
 
 /// In-memory implementation of `PayloadStorage`.
 /// Persists all changes to disk using `store`, but only uses this storage during the initial load
@@ -16,10 +17,15 @@ use crate::types::Payload;
 pub struct SimplePayloadStorage {
     pub(crate) payload: HashMap<PointOffsetType, Payload>,
     pub(crate) db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+    codec: PayloadCodec,
 }
 
 impl SimplePayloadStorage {
-    pub fn open(database: Arc<RwLock<DB>>) -> OperationResult<Self> {
+    pub fn open(
+        database: Arc<RwLock<DB>>,
+        compression: Option<PayloadCompressionConfig>,
+    ) -> OperationResult<Self> {
+        let codec = PayloadCodec::new(compression);
         let mut payload_map: HashMap<PointOffsetType, Payload> = Default::default();
 
         let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
@@ -30,7 +36,8 @@ impl SimplePayloadStorage {
         for (key, val) in db_wrapper.lock_db().iter()? {
             let point_id: PointOffsetType = serde_cbor::from_slice(&key)
                 .map_err(|_| OperationError::service_error("cannot deserialize point id"))?;
-            let payload: Payload = serde_cbor::from_slice(&val)
+            let payload: Payload = codec
+                .decode(&val)
                 .map_err(|_| OperationError::service_error("cannot deserialize payload"))?;
             payload_map.insert(point_id, payload);
         }
@@ -38,6 +45,7 @@ impl SimplePayloadStorage {
         Ok(SimplePayloadStorage {
             payload: payload_map,
             db_wrapper,
+            codec,
         })
     }
 
@@ -48,11 +56,15 @@ impl SimplePayloadStorage {
                 .remove(serde_cbor::to_vec(&point_id).unwrap()),
             Some(payload) => self.db_wrapper.put(
                 serde_cbor::to_vec(&point_id).unwrap(),
-                serde_cbor::to_vec(payload).unwrap(),
+                self.codec.encode(payload)?,
             ),
         }
     }
 
+    pub fn codec(&self) -> &PayloadCodec {
+        &self.codec
+    }
+
     pub fn payload_ptr(&self, point_id: PointOffsetType) -> Option<&Payload> {
         self.payload.get(&point_id)
     }
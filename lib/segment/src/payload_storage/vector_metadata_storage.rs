@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use common::types::PointOffsetType;
+use parking_lot::RwLock;
+use rocksdb::DB;
+
+use crate::common::operation_error::{OperationError, OperationResult};
+use crate::common::rocksdb_buffered_delete_wrapper::DatabaseColumnScheduledDeleteWrapper;
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_VECTOR_METADATA_CF};
+use crate::common::Flusher;
+use crate::types::VectorMetadata;
+
+/// On-disk storage of metadata attached to individual named vectors of points.
+///
+/// Kept apart from vector storages, so that metadata never gets loaded along with vectors
+/// during search. Does not keep anything in memory, metadata is only read on retrieval.
+#[derive(Debug)]
+pub struct VectorMetadataStorage {
+    db_wrapper: DatabaseColumnScheduledDeleteWrapper,
+}
+
+impl VectorMetadataStorage {
+    pub fn open(database: Arc<RwLock<DB>>) -> Self {
+        let db_wrapper = DatabaseColumnScheduledDeleteWrapper::new(DatabaseColumnWrapper::new(
+            database,
+            DB_VECTOR_METADATA_CF,
+        ));
+        Self { db_wrapper }
+    }
+
+    /// Metadata of all vectors of the point, empty if there is none
+    pub fn get(&self, point_id: PointOffsetType) -> OperationResult<VectorMetadata> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        let metadata = self
+            .db_wrapper
+            .get_pinned(&key, |raw| serde_json::from_slice::<VectorMetadata>(raw))?
+            .transpose()
+            .map_err(|err| {
+                OperationError::service_error(format!("Failed to decode vector metadata: {err}"))
+            })?;
+        Ok(metadata.unwrap_or_default())
+    }
+
+    /// Replaces metadata of all vectors of the point
+    pub fn set(&self, point_id: PointOffsetType, metadata: &VectorMetadata) -> OperationResult<()> {
+        let key = serde_cbor::to_vec(&point_id).unwrap();
+        if metadata.is_empty() {
+            return self.db_wrapper.remove(key);
+        }
+        let value = serde_json::to_vec(metadata).map_err(|err| {
+            OperationError::service_error(format!("Failed to encode vector metadata: {err}"))
+        })?;
+        self.db_wrapper.put(key, value)
+    }
+
+    /// Removes metadata of the given vectors of the point, keeps metadata of other vectors
+    pub fn remove_vectors<'a>(
+        &self,
+        point_id: PointOffsetType,
+        vector_names: impl IntoIterator<Item = &'a str>,
+    ) -> OperationResult<()> {
+        let mut metadata = self.get(point_id)?;
+        if metadata.is_empty() {
+            return Ok(());
+        }
+        let count = metadata.len();
+        for vector_name in vector_names {
+            metadata.remove(vector_name);
+        }
+        if metadata.len() == count {
+            return Ok(());
+        }
+        self.set(point_id, &metadata)
+    }
+
+    /// Removes metadata of all vectors of the point
+    pub fn clear(&self, point_id: PointOffsetType) -> OperationResult<()> {
+        self.db_wrapper
+            .remove(serde_cbor::to_vec(&point_id).unwrap())
+    }
+
+    pub fn flusher(&self) -> Flusher {
+        self.db_wrapper.flusher()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
+    use crate::types::Payload;
+
+    #[test]
+    fn test_vector_metadata_storage() {
+        let dir = Builder::new().prefix("db_dir").tempdir().unwrap();
+        let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
+        let storage = VectorMetadataStorage::open(db);
+
+        assert!(storage.get(1).unwrap().is_empty());
+
+        let metadata = VectorMetadata::from([
+            ("image".to_string(), Payload::from(json!({"model": "clip"}))),
+            ("text".to_string(), Payload::from(json!({"model": "bert"}))),
+        ]);
+        storage.set(1, &metadata).unwrap();
+        assert_eq!(storage.get(1).unwrap(), metadata);
+        assert!(storage.get(2).unwrap().is_empty());
+
+        storage.remove_vectors(1, ["image"]).unwrap();
+        let stored = storage.get(1).unwrap();
+        assert_eq!(stored.len(), 1);
+        assert!(stored.contains_key("text"));
+
+        storage.clear(1).unwrap();
+        assert!(storage.get(1).unwrap().is_empty());
+
+        storage.set(1, &metadata).unwrap();
+        storage.flusher()().unwrap();
+        assert_eq!(storage.get(1).unwrap(), metadata);
+    }
+}
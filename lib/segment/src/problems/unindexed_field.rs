@@ -204,7 +204,10 @@ fn infer_schema_from_any_variants(value: &AnyVariants) -> Vec<PayloadFieldSchema
     }
 }
 
-fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<PayloadFieldSchema> {
+/// Index schemas which could speed up the condition, the most specific one first
+pub fn infer_schema_from_field_condition(
+    field_condition: &FieldCondition,
+) -> Vec<PayloadFieldSchema> {
     let FieldCondition {
         key: _key,
         r#match,
@@ -228,12 +231,14 @@ fn infer_schema_from_field_condition(field_condition: &FieldCondition) -> Vec<Pa
                         min_token_len: None,
                         max_token_len: None,
                         lowercase: None,
+                        stemmer: None,
                         on_disk: None,
                     },
                 ))]
             }
             Match::Any(match_any) => infer_schema_from_any_variants(&match_any.any),
             Match::Except(match_except) => infer_schema_from_any_variants(&match_except.except),
+            Match::All(match_all) => infer_schema_from_any_variants(&match_all.all),
         })
     }
     if let Some(range_interface) = range {
@@ -378,8 +383,14 @@ impl<'a> Extractor<'a> {
                 key = &is_null.is_null.key;
                 inferred = all_indexes().collect();
             }
+            Condition::HasField(has_field) => {
+                key = &has_field.has_field.key;
+                inferred = all_indexes().collect();
+            }
             // No index needed
             Condition::HasId(_) => return,
+            Condition::HasVector(_) => return,
+            Condition::IdRange(_) => return,
             Condition::CustomIdChecker(_) => return,
         };
 
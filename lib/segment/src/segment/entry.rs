@@ -29,7 +29,7 @@ use crate::telemetry::SegmentTelemetry;
 use crate::types::{
     Filter, Payload, PayloadFieldSchema, PayloadIndexInfo, PayloadKeyType, PayloadKeyTypeRef,
     PointIdType, ScoredPoint, SearchParams, SegmentConfig, SegmentInfo, SegmentType, SeqNumberType,
-    VectorDataInfo, WithPayload, WithVector,
+    VectorDataInfo, VectorMetadata, WithPayload, WithVector,
 };
 use crate::utils;
 use crate::vector_storage::VectorStorage;
@@ -62,13 +62,33 @@ impl SegmentEntry for Segment {
         check_query_vectors(vector_name, query_vectors, &self.segment_config)?;
         let vector_data = &self.vector_data[vector_name];
         let vector_query_context = query_context.get_vector_context(vector_name);
-        let internal_results = vector_data.vector_index.borrow().search(
-            query_vectors,
-            filter,
-            top,
-            params,
-            &vector_query_context,
-        )?;
+
+        let distance_override = params
+            .and_then(|params| params.distance)
+            .filter(|distance| {
+                self.segment_config
+                    .vector_data
+                    .get(vector_name)
+                    .map_or(true, |config| config.distance != *distance)
+            });
+
+        let internal_results = match distance_override {
+            Some(distance) => self.search_with_distance(
+                vector_name,
+                query_vectors,
+                distance,
+                filter,
+                top,
+                &vector_query_context,
+            )?,
+            None => vector_data.vector_index.borrow().search(
+                query_vectors,
+                filter,
+                top,
+                params,
+                &vector_query_context,
+            )?,
+        };
 
         check_stopped(&vector_query_context.is_stopped())?;
 
@@ -119,6 +139,8 @@ impl SegmentEntry for Segment {
                         .payload_index
                         .borrow_mut()
                         .clear_payload(internal_id)?;
+                    segment.vector_metadata.clear(internal_id)?;
+                    segment.point_revisions.clear(internal_id)?;
                     segment.id_tracker.borrow_mut().drop(point_id)?;
 
                     // Before, we propagated point deletions to also delete its vectors. This turns
@@ -182,6 +204,9 @@ impl SegmentEntry for Segment {
                     )?;
                     let mut vector_storage = vector_data.vector_storage.borrow_mut();
                     let is_deleted = vector_storage.delete_vector(internal_id)?;
+                    segment
+                        .vector_metadata
+                        .remove_vectors(internal_id, [vector_name])?;
                     Ok((is_deleted, Some(internal_id)))
                 })
             }
@@ -272,6 +297,45 @@ impl SegmentEntry for Segment {
         })
     }
 
+    fn set_vector_metadata(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        metadata: &VectorMetadata,
+    ) -> OperationResult<bool> {
+        for vector_name in metadata.keys() {
+            check_vector_name(vector_name, &self.segment_config)?;
+        }
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment.vector_metadata.set(internal_id, metadata)?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
+    fn set_point_revision(
+        &mut self,
+        op_num: SeqNumberType,
+        point_id: PointIdType,
+        revision: SeqNumberType,
+    ) -> OperationResult<bool> {
+        let internal_id = self.id_tracker.borrow().internal_id(point_id);
+        self.handle_point_version_and_failure(op_num, internal_id, |segment| match internal_id {
+            Some(internal_id) => {
+                segment.point_revisions.set(internal_id, revision)?;
+                Ok((true, Some(internal_id)))
+            }
+            None => Err(OperationError::PointIdError {
+                missed_point_id: point_id,
+            }),
+        })
+    }
+
     fn vector(&self, vector_name: &str, point_id: PointIdType) -> OperationResult<Option<Vector>> {
         check_vector_name(vector_name, &self.segment_config)?;
         let internal_id = self.lookup_internal_id(point_id)?;
@@ -294,6 +358,27 @@ impl SegmentEntry for Segment {
         self.payload_by_offset(internal_id)
     }
 
+    fn payload_projected(
+        &self,
+        point_id: PointIdType,
+        include: &[JsonPath],
+    ) -> OperationResult<Payload> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.payload_index
+            .borrow()
+            .get_payload_projected(internal_id, include)
+    }
+
+    fn vector_metadata(&self, point_id: PointIdType) -> OperationResult<VectorMetadata> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.vector_metadata.get(internal_id)
+    }
+
+    fn point_revision(&self, point_id: PointIdType) -> OperationResult<SeqNumberType> {
+        let internal_id = self.lookup_internal_id(point_id)?;
+        self.point_revisions.get(internal_id)
+    }
+
     fn iter_points(&self) -> Box<dyn Iterator<Item = PointIdType> + '_> {
         // Sorry for that, but I didn't find any way easier.
         // If you try simply return iterator - it won't work because AtomicRef should exist
@@ -344,15 +429,16 @@ impl SegmentEntry for Segment {
         &self,
         limit: usize,
         filter: Option<&Filter>,
+        seed: Option<u64>,
         is_stopped: &AtomicBool,
     ) -> Vec<PointIdType> {
         match filter {
-            None => self.read_by_random_id(limit),
+            None => self.read_by_random_id(limit, seed),
             Some(condition) => {
                 if self.should_pre_filter(condition, Some(limit)) {
-                    self.filtered_read_by_index_shuffled(limit, condition, is_stopped)
+                    self.filtered_read_by_index_shuffled(limit, condition, seed, is_stopped)
                 } else {
-                    self.filtered_read_by_random_stream(limit, condition, is_stopped)
+                    self.filtered_read_by_random_stream(limit, condition, seed, is_stopped)
                 }
             }
         }
@@ -525,6 +611,8 @@ impl SegmentEntry for Segment {
         let current_path = self.current_path.clone();
         let id_tracker_mapping_flusher = self.id_tracker.borrow().mapping_flusher();
         let payload_index_flusher = self.payload_index.borrow().flusher();
+        let vector_metadata_flusher = self.vector_metadata.flusher();
+        let point_revisions_flusher = self.point_revisions.flusher();
         let id_tracker_versions_flusher = self.id_tracker.borrow().versions_flusher();
         let persisted_version = self.persisted_version.clone();
 
@@ -588,6 +676,12 @@ impl SegmentEntry for Segment {
             payload_index_flusher().map_err(|err| {
                 OperationError::service_error(format!("Failed to flush payload_index: {err}"))
             })?;
+            vector_metadata_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush vector_metadata: {err}"))
+            })?;
+            point_revisions_flusher().map_err(|err| {
+                OperationError::service_error(format!("Failed to flush point_revisions: {err}"))
+            })?;
             // Id Tracker contains versions of points. We need to flush it after vector_storage and payload_index flush.
             // This is because vector_storage and payload_index flush are not atomic.
             // If payload or vector flush fails, we will be able to recover data from WAL.
@@ -864,11 +958,22 @@ impl SegmentEntry for Segment {
             })
             .collect();
 
+        let num_deleted_points = self.deleted_point_count();
+        let num_stored_points = self.available_point_count() + num_deleted_points;
+        let deleted_ratio = if num_stored_points == 0 {
+            0.0
+        } else {
+            num_deleted_points as f64 / num_stored_points as f64
+        };
+
         SegmentTelemetry {
             info: self.info(),
             config: self.config().clone(),
+            num_deleted_points,
+            deleted_ratio,
             vector_index_searches,
             payload_field_indices: self.payload_index.borrow().get_telemetry_data(),
+            payload_compression: self.payload_storage.borrow().get_compression_telemetry(),
         }
     }
 
@@ -25,6 +25,8 @@ use crate::common::operation_error::{OperationResult, SegmentFailedState};
 use crate::id_tracker::IdTrackerSS;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
+use crate::payload_storage::point_revision_storage::PointRevisionStorage;
+use crate::payload_storage::vector_metadata_storage::VectorMetadataStorage;
 use crate::types::{SegmentConfig, SegmentType, SeqNumberType, VectorName};
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::VectorStorageEnum;
@@ -65,6 +67,10 @@ pub struct Segment {
     pub id_tracker: Arc<AtomicRefCell<IdTrackerSS>>,
     pub vector_data: HashMap<VectorName, VectorData>,
     pub payload_index: Arc<AtomicRefCell<StructPayloadIndex>>,
+    /// Metadata of individual vectors, stored apart from the vectors themselves
+    pub vector_metadata: VectorMetadataStorage,
+    /// Revisions of points, their versions exposed to users
+    pub point_revisions: PointRevisionStorage,
     /// Shows if it is possible to insert more points into this segment
     pub appendable_flag: bool,
     /// Shows what kind of indexes and storages are used in this segment
@@ -103,6 +109,15 @@ impl VectorData {
 
         index_task.into_iter().chain(storage_task)
     }
+
+    pub fn prefault_quantized_mmap_pages(
+        &self,
+    ) -> OperationResult<Option<mmap_ops::PrefaultMmapPages>> {
+        match &*self.quantized_vectors.borrow() {
+            Some(quantized_vectors) => quantized_vectors.prefault_mmap_pages(),
+            None => Ok(None),
+        }
+    }
 }
 
 impl Drop for Segment {
@@ -1,6 +1,7 @@
 use std::sync::atomic::{AtomicBool, Ordering};
 
 use common::iterator_ext::IteratorExt;
+use common::random::seeded_rng;
 use rand::seq::{IteratorRandom, SliceRandom};
 
 use super::Segment;
@@ -12,6 +13,7 @@ impl Segment {
         &self,
         limit: usize,
         condition: &Filter,
+        seed: Option<u64>,
         is_stopped: &AtomicBool,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
@@ -23,7 +25,7 @@ impl Segment {
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .filter_map(|internal_id| id_tracker.external_id(internal_id));
 
-        let mut rng = rand::thread_rng();
+        let mut rng = seeded_rng(seed);
         let mut shuffled = ids_iterator.choose_multiple(&mut rng, limit);
         shuffled.shuffle(&mut rng);
 
@@ -34,13 +36,14 @@ impl Segment {
         &self,
         limit: usize,
         condition: &Filter,
+        seed: Option<u64>,
         is_stopped: &AtomicBool,
     ) -> Vec<PointIdType> {
         let payload_index = self.payload_index.borrow();
         let filter_context = payload_index.filter_context(condition);
         self.id_tracker
             .borrow()
-            .iter_random()
+            .iter_random(seed)
             .check_stop(|| is_stopped.load(Ordering::Relaxed))
             .filter(move |(_, internal_id)| filter_context.check(*internal_id))
             .map(|(external_id, _)| external_id)
@@ -48,10 +51,10 @@ impl Segment {
             .collect()
     }
 
-    pub(super) fn read_by_random_id(&self, limit: usize) -> Vec<PointIdType> {
+    pub(super) fn read_by_random_id(&self, limit: usize, seed: Option<u64>) -> Vec<PointIdType> {
         self.id_tracker
             .borrow()
-            .iter_random()
+            .iter_random(seed)
             .map(|x| x.0)
             .take(limit)
             .collect()
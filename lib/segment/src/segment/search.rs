@@ -3,15 +3,57 @@ use common::types::ScoredPointOffset;
 use super::Segment;
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::named_vectors::NamedVectors;
-#[cfg(feature = "testing")]
+use crate::data_types::query_context::VectorQueryContext;
 use crate::data_types::vectors::QueryVector;
 #[cfg(feature = "testing")]
 use crate::entry::entry_point::SegmentEntry;
+use crate::index::PayloadIndex;
 #[cfg(feature = "testing")]
-use crate::types::{Filter, SearchParams};
-use crate::types::{ScoredPoint, WithPayload, WithVector};
+use crate::types::SearchParams;
+use crate::types::{Distance, Filter, ScoredPoint, WithPayload, WithVector};
+use crate::vector_storage::new_raw_scorer_with_distance;
 
 impl Segment {
+    /// Exact search, which scores original vectors with `distance` instead of the configured one.
+    ///
+    /// The vector index is built for the configured distance, so it is bypassed.
+    pub(super) fn search_with_distance(
+        &self,
+        vector_name: &str,
+        query_vectors: &[&QueryVector],
+        distance: Distance,
+        filter: Option<&Filter>,
+        top: usize,
+        query_context: &VectorQueryContext,
+    ) -> OperationResult<Vec<Vec<ScoredPointOffset>>> {
+        let vector_storage = self.vector_data[vector_name].vector_storage.borrow();
+        let id_tracker = self.id_tracker.borrow();
+        let payload_index = self.payload_index.borrow();
+        let deleted_points = query_context
+            .deleted_points()
+            .unwrap_or(id_tracker.deleted_point_bitslice());
+        let is_stopped = query_context.is_stopped();
+
+        let filtered_ids = filter.map(|filter| payload_index.query_points(filter));
+
+        query_vectors
+            .iter()
+            .map(|&query_vector| {
+                let scorer = new_raw_scorer_with_distance(
+                    query_vector.to_owned(),
+                    distance,
+                    &vector_storage,
+                    deleted_points,
+                    &is_stopped,
+                )?;
+                Ok(match &filtered_ids {
+                    Some(ids) => scorer.peek_top_iter(&mut ids.iter().copied(), top),
+                    None => scorer.peek_top_all(top),
+                })
+            })
+            .collect()
+    }
+
     /// Converts raw ScoredPointOffset search result into ScoredPoint result
     pub(super) fn process_search_result(
         &self,
@@ -44,13 +86,10 @@ impl Segment {
                     ))
                 })?;
                 let payload = if with_payload.enable {
-                    let initial_payload = self.payload_by_offset(point_offset)?;
-                    let processed_payload = if let Some(i) = &with_payload.payload_selector {
-                        i.process(initial_payload)
-                    } else {
-                        initial_payload
-                    };
-                    Some(processed_payload)
+                    Some(self.selected_payload_by_offset(
+                        point_offset,
+                        with_payload.payload_selector.as_ref(),
+                    )?)
                 } else {
                     None
                 };
@@ -78,6 +117,8 @@ impl Segment {
                     vector,
                     shard_key: None,
                     order_value: None,
+                    distance: None,
+                    provenance: None,
                 })
             })
             .collect()
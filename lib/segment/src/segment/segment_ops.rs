@@ -2,20 +2,24 @@ use std::cmp::max;
 use std::collections::{HashMap, HashSet};
 use std::fs::{self};
 use std::path::Path;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use std::thread::{self, JoinHandle};
 
+use atomic_refcell::AtomicRefCell;
 use bitvec::prelude::BitVec;
 use common::types::PointOffsetType;
 use io::file_operations::{atomic_save_json, read_json};
 use memory::mmap_ops;
 
 use super::{
-    Segment, DB_BACKUP_PATH, PAYLOAD_DB_BACKUP_PATH, SEGMENT_STATE_FILE, SNAPSHOT_FILES_PATH,
-    SNAPSHOT_PATH,
+    Segment, VectorData, DB_BACKUP_PATH, PAYLOAD_DB_BACKUP_PATH, SEGMENT_STATE_FILE,
+    SNAPSHOT_FILES_PATH, SNAPSHOT_PATH,
 };
 use crate::common::operation_error::{
     get_service_error, OperationError, OperationResult, SegmentFailedState,
 };
+use crate::common::rocksdb_wrapper::{DatabaseColumnWrapper, DB_VECTOR_CF};
 use crate::common::validate_snapshot_archive::open_snapshot_archive_with_validation;
 use crate::common::{check_named_vectors, check_vector_name};
 use crate::data_types::named_vectors::NamedVectors;
@@ -23,9 +27,13 @@ use crate::data_types::vectors::Vector;
 use crate::entry::entry_point::SegmentEntry;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::{PayloadIndex, VectorIndex};
+use crate::segment_constructor::{
+    create_vector_index, get_vector_index_path, get_vector_name_with_prefix,
+    get_vector_storage_path, open_vector_storage,
+};
 use crate::types::{
-    Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType, PointIdType,
-    SegmentState, SeqNumberType,
+    Indexes, Payload, PayloadFieldSchema, PayloadKeyType, PayloadKeyTypeRef, PayloadSchemaType,
+    PayloadSelector, PointIdType, SegmentState, SeqNumberType, VectorDataConfig, VectorStorageType,
 };
 use crate::utils;
 use crate::utils::fs::find_symlink;
@@ -57,6 +65,7 @@ impl Segment {
             let mut vector_index = vector_data.vector_index.borrow_mut();
             vector_index.update_vector(internal_id, vector)?;
         }
+        self.vector_metadata.clear(internal_id)?;
         Ok(())
     }
 
@@ -80,6 +89,8 @@ impl Segment {
     ) -> OperationResult<()> {
         debug_assert!(self.is_appendable());
         check_named_vectors(&vectors, &self.segment_config)?;
+        self.vector_metadata
+            .remove_vectors(internal_id, vectors.keys())?;
         for (vector_name, new_vector) in vectors {
             let vector_data = &self.vector_data[vector_name.as_ref()];
             let mut vector_index = vector_data.vector_index.borrow_mut();
@@ -412,10 +423,114 @@ impl Segment {
         self.payload_index.borrow().get_payload(point_offset)
     }
 
+    /// Retrieve payload by internal ID, with the selector applied
+    ///
+    /// Included fields are selected while reading the payload, so that other fields are not
+    /// deserialized at all.
+    pub(super) fn selected_payload_by_offset(
+        &self,
+        point_offset: PointOffsetType,
+        selector: Option<&PayloadSelector>,
+    ) -> OperationResult<Payload> {
+        match selector {
+            None => self.payload_by_offset(point_offset),
+            Some(PayloadSelector::Include(selector)) => self
+                .payload_index
+                .borrow()
+                .get_payload_projected(point_offset, &selector.include),
+            Some(selector @ PayloadSelector::Exclude(_)) => {
+                Ok(selector.process(self.payload_by_offset(point_offset)?))
+            }
+        }
+    }
+
     pub fn save_current_state(&self) -> OperationResult<()> {
         Self::save_state(&self.get_state(), &self.current_path)
     }
 
+    /// Add a new named dense vector to the segment
+    ///
+    /// Existing points have no value for the new vector. It gets a plain index, regardless of the
+    /// given config, like any unoptimized vector.
+    ///
+    /// Repeating this after a failure is safe, as long as the segment state was not saved.
+    pub fn create_vector_name(
+        &mut self,
+        vector_name: &str,
+        vector_config: &VectorDataConfig,
+    ) -> OperationResult<()> {
+        if self.segment_config.vector_data.contains_key(vector_name)
+            || self
+                .segment_config
+                .sparse_vector_data
+                .contains_key(vector_name)
+        {
+            return Err(OperationError::service_error(format!(
+                "Vector {vector_name} already exists in segment {}",
+                self.current_path.display(),
+            )));
+        }
+
+        let vector_config = VectorDataConfig {
+            index: Indexes::Plain {},
+            quantization_config: None,
+            ..vector_config.clone()
+        };
+
+        if vector_config.storage_type == VectorStorageType::Memory {
+            let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
+            DatabaseColumnWrapper::new(self.database.clone(), &db_column_name)
+                .create_column_family_if_not_exists()?;
+        }
+
+        let stopped = AtomicBool::new(false);
+        let vector_storage = Arc::new(AtomicRefCell::new(open_vector_storage(
+            &self.database,
+            &vector_config,
+            &stopped,
+            &get_vector_storage_path(&self.current_path, vector_name),
+            vector_name,
+        )?));
+        let quantized_vectors = Arc::new(AtomicRefCell::new(None));
+        let vector_index = Arc::new(AtomicRefCell::new(create_vector_index(
+            &vector_config,
+            &get_vector_index_path(&self.current_path, vector_name),
+            self.id_tracker.clone(),
+            vector_storage.clone(),
+            self.payload_index.clone(),
+            quantized_vectors.clone(),
+            None,
+            &stopped,
+        )?));
+
+        // Mark the vector as missing for all existing points, continuing after a previous attempt
+        let point_count = self.id_tracker.borrow().total_point_count();
+        let vector_count = vector_storage.borrow().total_vector_count();
+        for internal_id in vector_count..point_count {
+            vector_index
+                .borrow_mut()
+                .update_vector(internal_id as PointOffsetType, None)?;
+        }
+        vector_storage.borrow().flusher()()?;
+
+        self.payload_index
+            .borrow_mut()
+            .add_vector_storage(vector_name, vector_storage.clone());
+        self.vector_data.insert(
+            vector_name.to_owned(),
+            VectorData {
+                vector_index,
+                vector_storage,
+                quantized_vectors,
+            },
+        );
+        self.segment_config
+            .vector_data
+            .insert(vector_name.to_owned(), vector_config);
+
+        self.save_current_state()
+    }
+
     pub(super) fn infer_from_payload_data(
         &self,
         key: PayloadKeyTypeRef,
@@ -689,6 +804,32 @@ impl Segment {
             .spawn(move || tasks.iter().for_each(mmap_ops::PrefaultMmapPages::exec));
     }
 
+    /// Collect tasks reading memory-mapped vector and index data into the page cache.
+    ///
+    /// Unlike `prefault_mmap_pages`, tasks are returned to the caller instead of being spawned,
+    /// so that they can be executed within a memory budget.
+    /// Quantized vectors are the cheapest to keep resident, so their tasks go first.
+    pub fn warmup_tasks(
+        &self,
+        with_quantization: bool,
+    ) -> OperationResult<Vec<mmap_ops::PrefaultMmapPages>> {
+        let mut tasks = Vec::new();
+
+        if with_quantization {
+            for data in self.vector_data.values() {
+                tasks.extend(data.prefault_quantized_mmap_pages()?);
+            }
+        }
+
+        tasks.extend(
+            self.vector_data
+                .values()
+                .flat_map(|data| data.prefault_mmap_pages()),
+        );
+
+        Ok(tasks)
+    }
+
     pub fn cleanup_versions(&mut self) -> OperationResult<()> {
         self.id_tracker.borrow_mut().cleanup_versions()
     }
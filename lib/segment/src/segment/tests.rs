@@ -3,6 +3,7 @@ use std::fs::File;
 use std::sync::atomic::AtomicBool;
 
 use common::tar_ext;
+use common::types::TelemetryDetail;
 use tempfile::Builder;
 
 use super::*;
@@ -11,10 +12,14 @@ use crate::common::{check_named_vectors, check_vector, check_vector_name};
 use crate::data_types::named_vectors::NamedVectors;
 use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
 use crate::entry::entry_point::SegmentEntry;
+use crate::fixtures::segment_fixtures::random_segment;
+use crate::segment_constructor::simple_segment_constructor::{
+    build_multivec_segment, build_simple_segment,
+};
 use crate::segment_constructor::{build_segment, load_segment};
 use crate::types::{
-    Distance, Filter, Indexes, Payload, SegmentConfig, VectorDataConfig, VectorStorageType,
-    WithPayload, WithVector,
+    Distance, ExtendedPointId, Filter, Indexes, Payload, SegmentConfig, VectorDataConfig,
+    VectorStorageType, WithPayload, WithVector,
 };
 
 #[test]
@@ -36,6 +41,7 @@ fn test_search_batch_equivalence_single() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -110,6 +116,7 @@ fn test_from_filter_attributes() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -202,6 +209,7 @@ fn test_snapshot() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -314,6 +322,7 @@ fn test_background_flush() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(segment_base_dir.path(), &config, true).unwrap();
@@ -348,6 +357,7 @@ fn test_check_consistency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -443,6 +453,7 @@ fn test_point_vector_count() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -511,6 +522,7 @@ fn test_point_vector_count_multivec() {
         ]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -628,6 +640,7 @@ fn test_vector_compatibility_checks() {
         ]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -789,6 +802,7 @@ fn test_handle_point_version() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
     segment
@@ -827,3 +841,260 @@ fn test_handle_point_version() {
         .unwrap();
     assert!(applied);
 }
+
+#[test]
+fn test_deleted_points_telemetry() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+
+    let telemetry = segment.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(telemetry.num_deleted_points, 0);
+    assert_eq!(telemetry.deleted_ratio, 0.0);
+
+    for i in 0..4 {
+        segment
+            .upsert_point(i, i.into(), only_default_vector(&[i as f32, 1.0]))
+            .unwrap();
+    }
+    segment.delete_point(4, 0.into()).unwrap();
+
+    let telemetry = segment.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(telemetry.num_deleted_points, 1);
+    assert_eq!(telemetry.deleted_ratio, 0.25);
+
+    // Fully deleted segment
+    for i in 1..4 {
+        segment.delete_point(4 + i, i.into()).unwrap();
+    }
+
+    let telemetry = segment.get_telemetry_data(TelemetryDetail::default());
+    assert_eq!(telemetry.info.num_points, 0);
+    assert_eq!(telemetry.num_deleted_points, 4);
+    assert_eq!(telemetry.deleted_ratio, 1.0);
+}
+
+#[test]
+fn test_has_vector_condition() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_multivec_segment(dir.path(), 2, 2, Distance::Dot).unwrap();
+
+    segment
+        .upsert_point(
+            1,
+            1.into(),
+            NamedVectors::from_pairs([
+                ("vector1".into(), vec![1.0, 0.0]),
+                ("vector2".into(), vec![0.0, 1.0]),
+            ]),
+        )
+        .unwrap();
+    segment
+        .upsert_point(
+            2,
+            2.into(),
+            NamedVectors::from_pairs([("vector1".into(), vec![1.0, 1.0])]),
+        )
+        .unwrap();
+    // Zero vector is still a present vector
+    segment
+        .upsert_point(
+            3,
+            3.into(),
+            NamedVectors::from_pairs([
+                ("vector1".into(), vec![0.5, 0.5]),
+                ("vector2".into(), vec![0.0, 0.0]),
+            ]),
+        )
+        .unwrap();
+    segment
+        .upsert_point(
+            4,
+            4.into(),
+            NamedVectors::from_pairs([
+                ("vector1".into(), vec![0.1, 0.1]),
+                ("vector2".into(), vec![1.0, 1.0]),
+            ]),
+        )
+        .unwrap();
+    segment.delete_vector(5, 4.into(), "vector2").unwrap();
+
+    let has_vector2: Filter =
+        serde_json::from_str(r#"{"must": [{"has_vector": "vector2"}]}"#).unwrap();
+    let missing_vector2: Filter =
+        serde_json::from_str(r#"{"must_not": [{"has_vector": "vector2"}]}"#).unwrap();
+    let has_unknown: Filter =
+        serde_json::from_str(r#"{"must": [{"has_vector": "unknown"}]}"#).unwrap();
+
+    let check = |segment: &Segment| {
+        let is_stopped = AtomicBool::new(false);
+        let read = |filter: &Filter| -> HashSet<_> {
+            segment
+                .read_filtered(None, None, Some(filter), &is_stopped)
+                .into_iter()
+                .collect()
+        };
+        assert_eq!(read(&has_vector2), HashSet::from([1.into(), 3.into()]));
+        assert_eq!(read(&missing_vector2), HashSet::from([2.into(), 4.into()]));
+        assert!(read(&has_unknown).is_empty());
+
+        let results = segment
+            .search(
+                "vector1",
+                &[1.0, 1.0].into(),
+                &WithPayload::default(),
+                &false.into(),
+                Some(&missing_vector2),
+                10,
+                None,
+            )
+            .unwrap();
+        let found: HashSet<_> = results.iter().map(|point| point.id).collect();
+        assert_eq!(found, HashSet::from([2.into(), 4.into()]));
+    };
+
+    check(&segment);
+
+    // Presence of vectors survives reload
+    segment.flush(true, false).unwrap();
+    let segment_path = segment.current_path.clone();
+    drop(segment);
+    let segment = load_segment(&segment_path, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    check(&segment);
+}
+
+#[test]
+fn test_id_range_condition() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+
+    for id in 0..30u64 {
+        segment
+            .upsert_point(id, id.into(), only_default_vector(&[1.0, id as f32]))
+            .unwrap();
+    }
+    // UUIDs are never in a range of numeric ids
+    segment
+        .upsert_point(
+            30,
+            ExtendedPointId::Uuid(uuid::Uuid::from_u128(15)),
+            only_default_vector(&[1.0, 15.0]),
+        )
+        .unwrap();
+    segment.delete_point(31, 12.into()).unwrap();
+
+    let is_stopped = AtomicBool::new(false);
+    let read = |filter: &str| -> HashSet<_> {
+        let filter: Filter = serde_json::from_str(filter).unwrap();
+        segment
+            .read_filtered(None, None, Some(&filter), &is_stopped)
+            .into_iter()
+            .collect()
+    };
+    let ids = |ids: &[u64]| -> HashSet<_> { ids.iter().map(|&id| id.into()).collect() };
+
+    assert_eq!(
+        read(r#"{"must": [{"id_range": {"gte": 10, "lt": 15}}]}"#),
+        ids(&[10, 11, 13, 14]),
+    );
+    assert_eq!(
+        read(r#"{"must": [{"id_range": {"gt": 27}}]}"#),
+        ids(&[28, 29]),
+    );
+    assert_eq!(
+        read(r#"{"must_not": [{"id_range": {"gte": 2}}]}"#).len(),
+        // 0, 1 and the UUID
+        3,
+    );
+    assert!(read(r#"{"must": [{"id_range": {"gt": 5, "lt": 6}}]}"#).is_empty());
+
+    let filter: Filter =
+        serde_json::from_str(r#"{"must": [{"id_range": {"gte": 10, "lte": 19}}]}"#).unwrap();
+    let results = segment
+        .search(
+            DEFAULT_VECTOR_NAME,
+            &[0.0, 1.0].into(),
+            &WithPayload::default(),
+            &false.into(),
+            Some(&filter),
+            3,
+            None,
+        )
+        .unwrap();
+    let found: Vec<_> = results.iter().map(|point| point.id).collect();
+    assert_eq!(found, vec![19.into(), 18.into(), 17.into()]);
+}
+
+#[test]
+fn test_create_vector_name() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = random_segment(dir.path(), 10);
+
+    let vector_config = VectorDataConfig {
+        size: 2,
+        distance: Distance::Dot,
+        storage_type: VectorStorageType::Memory,
+        index: Indexes::Plain {},
+        quantization_config: None,
+        multivector_config: None,
+        datatype: None,
+    };
+    segment.create_vector_name("new", &vector_config).unwrap();
+    assert!(segment.create_vector_name("new", &vector_config).is_err());
+    assert!(segment
+        .create_vector_name(DEFAULT_VECTOR_NAME, &vector_config)
+        .is_err());
+
+    segment
+        .update_vectors(
+            101,
+            3.into(),
+            NamedVectors::from_pairs([("new".into(), vec![1.0, 1.0])]),
+        )
+        .unwrap();
+
+    let has_new: Filter = serde_json::from_str(r#"{"must": [{"has_vector": "new"}]}"#).unwrap();
+
+    let check = |segment: &Segment| {
+        assert!(segment.config().vector_data.contains_key("new"));
+
+        // Existing points have no value for the new vector
+        let is_stopped = AtomicBool::new(false);
+        let with_new = segment.read_filtered(None, None, Some(&has_new), &is_stopped);
+        assert_eq!(with_new, vec![3.into()]);
+
+        let results = segment
+            .search(
+                "new",
+                &[1.0, 1.0].into(),
+                &WithPayload::from(true),
+                &false.into(),
+                None,
+                10,
+                None,
+            )
+            .unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, 3.into());
+        assert!(results[0].payload.is_some());
+
+        // Old vector is untouched
+        assert_eq!(segment.available_point_count(), 10);
+        assert!(segment
+            .vector(DEFAULT_VECTOR_NAME, 5.into())
+            .unwrap()
+            .is_some());
+    };
+
+    check(&segment);
+
+    // New vector survives reload
+    segment.flush(true, false).unwrap();
+    let segment_path = segment.current_path.clone();
+    drop(segment);
+    let segment = load_segment(&segment_path, &AtomicBool::new(false))
+        .unwrap()
+        .unwrap();
+    check(&segment);
+}
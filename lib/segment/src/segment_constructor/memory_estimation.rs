@@ -0,0 +1,286 @@
+//! Estimation of the memory a segment takes once loaded, without loading it.
+//!
+//! Only the state file and sizes of the files of the segment are read. Each component is placed
+//! in RAM or on disk by the storage config of the segment: components on disk are memory-mapped
+//! or read on demand, they only take memory in the page cache, which the OS can evict.
+
+use std::ops::AddAssign;
+use std::path::Path;
+
+use common::disk::dir_size;
+use io::storage_version::StorageVersion;
+use schemars::JsonSchema;
+use serde::Serialize;
+
+use crate::common::operation_error::OperationResult;
+use crate::id_tracker::immutable_id_tracker::{
+    DELETED_FILE_NAME, MAPPINGS_FILE_NAME, VERSION_MAPPING_FILE_NAME,
+};
+use crate::segment::{Segment, SegmentVersion};
+use crate::segment_constructor::{
+    get_vector_index_path, get_vector_storage_path, PAYLOAD_INDEX_PATH,
+};
+use crate::types::{Indexes, QuantizationConfig, VectorStorageType};
+use crate::vector_storage::quantized::quantized_vectors::{
+    QuantizedVectors, QUANTIZED_CONFIG_PATH, QUANTIZED_DATA_PATH, QUANTIZED_META_PATH,
+    QUANTIZED_OFFSETS_PATH,
+};
+
+const ID_TRACKER_FILES: [&str; 3] = [
+    DELETED_FILE_NAME,
+    MAPPINGS_FILE_NAME,
+    VERSION_MAPPING_FILE_NAME,
+];
+
+/// Estimated size of a loaded component, split by where its data is kept
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct MemoryEstimation {
+    /// Bytes loaded into RAM
+    pub ram_bytes: u64,
+    /// Bytes kept on disk, memory-mapped or read on demand
+    pub disk_bytes: u64,
+}
+
+impl MemoryEstimation {
+    fn new(bytes: u64, on_disk: bool) -> Self {
+        if on_disk {
+            Self {
+                ram_bytes: 0,
+                disk_bytes: bytes,
+            }
+        } else {
+            Self {
+                ram_bytes: bytes,
+                disk_bytes: 0,
+            }
+        }
+    }
+}
+
+impl AddAssign for MemoryEstimation {
+    fn add_assign(&mut self, other: Self) {
+        self.ram_bytes += other.ram_bytes;
+        self.disk_bytes += other.disk_bytes;
+    }
+}
+
+/// Estimated memory of a segment once loaded, by component
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, JsonSchema)]
+pub struct SegmentMemoryEstimation {
+    /// Dense vector storages, except those stored in RocksDB
+    pub vectors: MemoryEstimation,
+    /// HNSW graphs and sparse inverted indexes
+    pub vector_index: MemoryEstimation,
+    /// Quantized vectors
+    pub quantized_vectors: MemoryEstimation,
+    /// Payload field indexes, mmap indexes are kept on disk
+    pub payload_index: MemoryEstimation,
+    /// Immutable ID tracker
+    pub id_tracker: MemoryEstimation,
+    /// RocksDB of the segment, with in-memory vectors, sparse vectors, payloads and the mutable ID
+    /// tracker. Counted as loaded into RAM if in-memory vectors or payloads are stored in it.
+    pub database: MemoryEstimation,
+}
+
+impl SegmentMemoryEstimation {
+    pub fn total(&self) -> MemoryEstimation {
+        let mut total = self.vectors;
+        total += self.vector_index;
+        total += self.quantized_vectors;
+        total += self.payload_index;
+        total += self.id_tracker;
+        total += self.database;
+        total
+    }
+}
+
+impl AddAssign for SegmentMemoryEstimation {
+    fn add_assign(&mut self, other: Self) {
+        self.vectors += other.vectors;
+        self.vector_index += other.vector_index;
+        self.quantized_vectors += other.quantized_vectors;
+        self.payload_index += other.payload_index;
+        self.id_tracker += other.id_tracker;
+        self.database += other.database;
+    }
+}
+
+/// Size of a file or directory, 0 if it doesn't exist
+fn path_size(path: &Path) -> OperationResult<u64> {
+    if path.is_dir() {
+        Ok(dir_size(path)?)
+    } else if path.is_file() {
+        Ok(path.metadata()?.len())
+    } else {
+        Ok(0)
+    }
+}
+
+fn always_ram(quantization_config: &QuantizationConfig) -> Option<bool> {
+    match quantization_config {
+        QuantizationConfig::Scalar(scalar) => scalar.scalar.always_ram,
+        QuantizationConfig::Product(product) => product.product.always_ram,
+        QuantizationConfig::Binary(binary) => binary.binary.always_ram,
+    }
+}
+
+/// Estimate the memory a segment would take once loaded, from its config and the size of its files.
+///
+/// `None` if the segment would be skipped on load, because it is marked as deleted or was not
+/// fully created.
+pub fn estimate_segment_memory(
+    segment_path: &Path,
+) -> OperationResult<Option<SegmentMemoryEstimation>> {
+    if segment_path.extension().is_some_and(|ext| ext == "deleted")
+        || SegmentVersion::load(segment_path)?.is_none()
+    {
+        return Ok(None);
+    }
+
+    let config = Segment::load_state(segment_path)?.config;
+    let mut estimation = SegmentMemoryEstimation::default();
+    // Files of the segment which belong to a component, all other files belong to RocksDB
+    let mut component_paths = vec![segment_path.join(PAYLOAD_INDEX_PATH)];
+
+    for (vector_name, vector_config) in &config.vector_data {
+        let storage_path = get_vector_storage_path(segment_path, vector_name);
+        let index_path = get_vector_index_path(segment_path, vector_name);
+        let vectors_on_disk = vector_config.storage_type.is_on_disk();
+
+        // Quantized vectors are stored in the directory of the vector storage
+        let mut quantized_size = 0;
+        for file in [
+            QUANTIZED_CONFIG_PATH,
+            QUANTIZED_DATA_PATH,
+            QUANTIZED_META_PATH,
+            QUANTIZED_OFFSETS_PATH,
+        ] {
+            quantized_size += path_size(&storage_path.join(file))?;
+        }
+        if let Some(quantization_config) = &vector_config.quantization_config {
+            let in_ram = QuantizedVectors::is_ram(always_ram(quantization_config), vectors_on_disk);
+            estimation.quantized_vectors += MemoryEstimation::new(quantized_size, !in_ram);
+        }
+
+        let storage_size = path_size(&storage_path)?.saturating_sub(quantized_size);
+        estimation.vectors += MemoryEstimation::new(storage_size, vectors_on_disk);
+
+        let index_on_disk = match &vector_config.index {
+            Indexes::Plain {} => false,
+            Indexes::Hnsw(hnsw_config) => hnsw_config.on_disk.unwrap_or(false),
+        };
+        estimation.vector_index += MemoryEstimation::new(path_size(&index_path)?, index_on_disk);
+
+        component_paths.extend([storage_path, index_path]);
+    }
+
+    for (vector_name, sparse_vector_config) in &config.sparse_vector_data {
+        let index_path = get_vector_index_path(segment_path, vector_name);
+        let index_on_disk = sparse_vector_config.index.index_type.is_on_disk();
+        estimation.vector_index += MemoryEstimation::new(path_size(&index_path)?, index_on_disk);
+        component_paths.push(index_path);
+    }
+
+    // Mmap field indexes are directories, files are the RocksDB of the other field indexes
+    let payload_index_path = segment_path.join(PAYLOAD_INDEX_PATH);
+    if payload_index_path.is_dir() {
+        for entry in std::fs::read_dir(&payload_index_path)? {
+            let path = entry?.path();
+            estimation.payload_index += MemoryEstimation::new(path_size(&path)?, path.is_dir());
+        }
+    }
+
+    // Mappings of the immutable ID tracker are loaded into RAM
+    for file in ID_TRACKER_FILES {
+        let path = segment_path.join(file);
+        estimation.id_tracker += MemoryEstimation::new(path_size(&path)?, false);
+        component_paths.push(path);
+    }
+
+    let database_in_ram = !config.payload_storage_type.is_on_disk()
+        || config
+            .vector_data
+            .values()
+            .any(|vector_config| vector_config.storage_type == VectorStorageType::Memory);
+    for entry in std::fs::read_dir(segment_path)? {
+        let path = entry?.path();
+        if !component_paths.contains(&path) {
+            estimation.database += MemoryEstimation::new(path_size(&path)?, !database_in_ram);
+        }
+    }
+
+    Ok(Some(estimation))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use tempfile::Builder;
+
+    use super::*;
+    use crate::data_types::vectors::{only_default_vector, DEFAULT_VECTOR_NAME};
+    use crate::entry::entry_point::SegmentEntry;
+    use crate::segment_constructor::build_segment;
+    use crate::types::{Distance, Payload, PayloadStorageType, SegmentConfig, VectorDataConfig};
+
+    fn estimate(
+        storage_type: VectorStorageType,
+        payload_storage_type: PayloadStorageType,
+    ) -> SegmentMemoryEstimation {
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        let config = SegmentConfig {
+            vector_data: HashMap::from([(
+                DEFAULT_VECTOR_NAME.to_owned(),
+                VectorDataConfig {
+                    size: 4,
+                    distance: Distance::Dot,
+                    storage_type,
+                    index: Indexes::Plain {},
+                    quantization_config: None,
+                    multivector_config: None,
+                    datatype: None,
+                },
+            )]),
+            payload_storage_type,
+            sparse_vector_data: Default::default(),
+            payload_compression: None,
+        };
+
+        let mut segment = build_segment(dir.path(), &config, true).unwrap();
+        let payload: Payload = serde_json::json!({ "color": "red" }).into();
+        for n in 0..100u64 {
+            let vector = [n as f32; 4];
+            segment
+                .upsert_point(n, n.into(), only_default_vector(&vector))
+                .unwrap();
+            segment.set_payload(n, n.into(), &payload, &None).unwrap();
+        }
+        segment.flush(true, false).unwrap();
+        let segment_path = segment.data_path();
+        drop(segment);
+
+        estimate_segment_memory(&segment_path).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_estimate_segment_memory() {
+        // In-memory vectors and payloads are loaded from RocksDB
+        let estimation = estimate(VectorStorageType::Memory, PayloadStorageType::InMemory);
+        assert_eq!(estimation.vectors, MemoryEstimation::default());
+        assert!(estimation.database.ram_bytes > 0);
+        assert_eq!(estimation.database.disk_bytes, 0);
+        assert_eq!(estimation.total().disk_bytes, 0);
+
+        // Mmap vectors and on-disk payloads stay on disk
+        let estimation = estimate(VectorStorageType::ChunkedMmap, PayloadStorageType::OnDisk);
+        assert_eq!(estimation.vectors.ram_bytes, 0);
+        assert!(estimation.vectors.disk_bytes >= 100 * 4 * 4);
+        assert_eq!(estimation.database.ram_bytes, 0);
+        assert!(estimation.database.disk_bytes > 0);
+
+        // Segments which were not fully created are skipped on load
+        let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+        assert_eq!(estimate_segment_memory(dir.path()).unwrap(), None);
+    }
+}
@@ -31,12 +31,15 @@ use crate::index::sparse_index::sparse_vector_index::SparseVectorIndexOpenArgs;
 use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::PayloadIndex;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
+use crate::payload_storage::point_revision_storage::PointRevisionStorage;
+use crate::payload_storage::vector_metadata_storage::VectorMetadataStorage;
 use crate::payload_storage::PayloadStorage;
 use crate::segment::{Segment, SegmentVersion};
 use crate::segment_constructor::load_segment;
 use crate::types::{
     ExtendedPointId, PayloadFieldSchema, PayloadKeyType, SegmentConfig, SegmentState, SeqNumberType,
 };
+use crate::vector_storage::dense::vector_deduplication::VectorDeduplication;
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
 
@@ -46,6 +49,8 @@ pub struct SegmentBuilder {
     id_tracker: IdTrackerEnum,
     payload_storage: PayloadStorageEnum,
     vector_storages: HashMap<String, VectorStorageEnum>,
+    vector_metadata: VectorMetadataStorage,
+    point_revisions: PointRevisionStorage,
     segment_config: SegmentConfig,
 
     // The path, where fully created segment will be moved
@@ -79,6 +84,8 @@ impl SegmentBuilder {
         };
 
         let payload_storage = create_payload_storage(database.clone(), segment_config)?;
+        let vector_metadata = VectorMetadataStorage::open(database.clone());
+        let point_revisions = PointRevisionStorage::open(database.clone());
 
         let mut vector_storages = HashMap::new();
 
@@ -95,13 +102,13 @@ impl SegmentBuilder {
             vector_storages.insert(vector_name.to_owned(), vector_storage);
         }
 
-        #[allow(clippy::for_kv_map)]
-        for (vector_name, _sparse_vector_config) in &segment_config.sparse_vector_data {
-            // `_sparse_vector_config` should be used, once we are able to initialize storage with
-            // different datatypes
-
-            let vector_storage =
-                create_sparse_vector_storage(database.clone(), vector_name, &stopped)?;
+        for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
+            let vector_storage = create_sparse_vector_storage(
+                database.clone(),
+                vector_name,
+                sparse_vector_config,
+                &stopped,
+            )?;
             vector_storages.insert(vector_name.to_owned(), vector_storage);
         }
 
@@ -112,6 +119,8 @@ impl SegmentBuilder {
             id_tracker,
             payload_storage,
             vector_storages,
+            vector_metadata,
+            point_revisions,
             segment_config: segment_config.clone(),
 
             destination_path,
@@ -125,6 +134,24 @@ impl SegmentBuilder {
         self.defragment_keys = keys;
     }
 
+    /// Store duplicate vectors only once, in vector storages which support it.
+    ///
+    /// Must be set before adding points.
+    pub fn set_vector_deduplication(&mut self, deduplication: VectorDeduplication) {
+        for (vector_name, vector_storage) in &mut self.vector_storages {
+            if !vector_storage.set_deduplication(Some(deduplication)) {
+                log::trace!("Vector storage of {vector_name} doesn't support deduplication");
+            }
+        }
+    }
+
+    /// Index of the partition the point belongs to, out of `count` partitions
+    pub fn point_partition(point_id: ExtendedPointId, count: usize) -> usize {
+        let mut hasher = AHasher::default();
+        point_id.hash(&mut hasher);
+        (hasher.finish() % count as u64) as usize
+    }
+
     pub fn remove_indexed_field(&mut self, field: &PayloadKeyType) {
         self.indexed_fields.remove(field);
     }
@@ -227,7 +254,20 @@ impl SegmentBuilder {
     /// * `bool` - if `true` - data successfully added, if `false` - process was interrupted
     ///
     pub fn update(&mut self, segments: &[&Segment], stopped: &AtomicBool) -> OperationResult<bool> {
-        if segments.is_empty() {
+        Self::update_partitioned(std::slice::from_mut(self), segments, stopped)
+    }
+
+    /// Same as [`Self::update`], but splits the points of `segments` between several builders.
+    ///
+    /// Points are assigned to builders by the hash of their ID, see [`Self::point_partition`],
+    /// so building each of them splits the source segments into parts of roughly equal size.
+    /// Source segments are read only once, regardless of the number of builders.
+    pub fn update_partitioned(
+        builders: &mut [SegmentBuilder],
+        segments: &[&Segment],
+        stopped: &AtomicBool,
+    ) -> OperationResult<bool> {
+        if segments.is_empty() || builders.is_empty() {
             return Ok(true);
         }
 
@@ -257,9 +297,31 @@ impl SegmentBuilder {
             }
         }
 
-        let payloads: Vec<_> = segments.iter().map(|i| i.payload_index.borrow()).collect();
+        let num_parts = builders.len();
+        let mut parts: Vec<Vec<_>> = (0..num_parts).map(|_| Vec::new()).collect();
+        if num_parts == 1 {
+            parts[0] = merged_points.into_values().collect();
+        } else {
+            for point_data in merged_points.into_values() {
+                parts[Self::point_partition(point_data.external_id, num_parts)].push(point_data);
+            }
+        }
 
-        let mut points_to_insert: Vec<_> = merged_points.into_values().collect();
+        for (builder, points_to_insert) in builders.iter_mut().zip(parts) {
+            builder.insert_points(segments, points_to_insert, stopped)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Add given points of `segments` to the builder, with their vectors and payload
+    fn insert_points(
+        &mut self,
+        segments: &[&Segment],
+        mut points_to_insert: Vec<PositionedPointMetadata>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<()> {
+        let payloads: Vec<_> = segments.iter().map(|i| i.payload_index.borrow()).collect();
 
         for defragment_key in &self.defragment_keys {
             for point_data in &mut points_to_insert {
@@ -334,6 +396,12 @@ impl SegmentBuilder {
 
                 let other_payload =
                     payloads[point_data.segment_index].get_payload(old_internal_id)?;
+                let other_vector_metadata = segments[point_data.segment_index]
+                    .vector_metadata
+                    .get(old_internal_id)?;
+                let other_revision = segments[point_data.segment_index]
+                    .point_revisions
+                    .get(old_internal_id)?;
 
                 match self.id_tracker.internal_id(point_data.external_id) {
                     Some(existing_internal_id) => {
@@ -355,6 +423,8 @@ impl SegmentBuilder {
                             self.id_tracker
                                 .set_internal_version(new_internal_id, point_data.version)?;
                             self.payload_storage.clear(existing_internal_id)?;
+                            self.vector_metadata.clear(existing_internal_id)?;
+                            self.point_revisions.clear(existing_internal_id)?;
 
                             existing_internal_id
                         } else {
@@ -378,6 +448,17 @@ impl SegmentBuilder {
                 if !other_payload.is_empty() {
                     self.payload_storage.set(new_internal_id, &other_payload)?;
                 }
+
+                // Propagate vector metadata to new segment
+                if !other_vector_metadata.is_empty() {
+                    self.vector_metadata
+                        .set(new_internal_id, &other_vector_metadata)?;
+                }
+
+                // Propagate revision to new segment
+                if other_revision != 0 {
+                    self.point_revisions.set(new_internal_id, other_revision)?;
+                }
             }
         }
 
@@ -387,16 +468,27 @@ impl SegmentBuilder {
             }
         }
 
-        Ok(true)
+        Ok(())
     }
 
     pub fn build(self, permit: CpuPermit, stopped: &AtomicBool) -> Result<Segment, OperationError> {
+        self.build_with_shared_permit(Arc::new(permit), stopped)
+    }
+
+    /// Same as [`Self::build`], but takes a CPU permit shared with other builders
+    pub fn build_with_shared_permit(
+        self,
+        permit: Arc<CpuPermit>,
+        stopped: &AtomicBool,
+    ) -> Result<Segment, OperationError> {
         let (temp_dir, destination_path) = {
             let SegmentBuilder {
                 version,
                 id_tracker,
                 payload_storage,
-                mut vector_storages,
+                vector_storages,
+                vector_metadata,
+                point_revisions,
                 segment_config,
                 destination_path,
                 temp_dir,
@@ -407,6 +499,8 @@ impl SegmentBuilder {
             let appendable_flag = segment_config.is_appendable();
 
             payload_storage.flusher()()?;
+            vector_metadata.flusher()()?;
+            point_revisions.flusher()()?;
             let payload_storage_arc = Arc::new(AtomicRefCell::new(payload_storage));
 
             let id_tracker = match id_tracker {
@@ -426,11 +520,27 @@ impl SegmentBuilder {
             id_tracker.versions_flusher()()?;
             let id_tracker_arc = Arc::new(AtomicRefCell::new(id_tracker));
 
+            let mut quantized_vectors = Self::update_quantization(
+                &segment_config,
+                &vector_storages,
+                temp_dir.path(),
+                &permit,
+                stopped,
+            )?;
+
+            let mut vector_storages_arc = HashMap::new();
+            for (vector_name, vector_storage) in vector_storages {
+                vector_storage.flusher()()?;
+                vector_storages_arc
+                    .insert(vector_name, Arc::new(AtomicRefCell::new(vector_storage)));
+            }
+
             let payload_index_path = get_payload_index_path(temp_dir.path());
 
             let mut payload_index = StructPayloadIndex::open(
                 payload_storage_arc,
                 id_tracker_arc.clone(),
+                vector_storages_arc.clone(),
                 &payload_index_path,
                 appendable_flag,
             )?;
@@ -443,30 +553,15 @@ impl SegmentBuilder {
             payload_index.flusher()()?;
             let payload_index_arc = Arc::new(AtomicRefCell::new(payload_index));
 
-            // Arc permit to share it with each vector store
-            let permit = Arc::new(permit);
-
-            let mut quantized_vectors = Self::update_quantization(
-                &segment_config,
-                &vector_storages,
-                temp_dir.path(),
-                &permit,
-                stopped,
-            )?;
-
             for (vector_name, vector_config) in &segment_config.vector_data {
                 let vector_index_path = get_vector_index_path(temp_dir.path(), vector_name);
 
-                let Some(vector_storage) = vector_storages.remove(vector_name) else {
+                let Some(vector_storage_arc) = vector_storages_arc.remove(vector_name) else {
                     return Err(OperationError::service_error(format!(
                         "Vector storage for vector name {vector_name} not found on segment build"
                     )));
                 };
 
-                vector_storage.flusher()()?;
-
-                let vector_storage_arc = Arc::new(AtomicRefCell::new(vector_storage));
-
                 let quantized_vectors = quantized_vectors.remove(vector_name);
                 let quantized_vectors_arc = Arc::new(AtomicRefCell::new(quantized_vectors));
 
@@ -485,16 +580,12 @@ impl SegmentBuilder {
             for (vector_name, sparse_vector_config) in &segment_config.sparse_vector_data {
                 let vector_index_path = get_vector_index_path(temp_dir.path(), vector_name);
 
-                let Some(vector_storage) = vector_storages.remove(vector_name) else {
+                let Some(vector_storage_arc) = vector_storages_arc.remove(vector_name) else {
                     return Err(OperationError::service_error(format!(
                         "Vector storage for vector name {vector_name} not found on sparse segment build"
                     )));
                 };
 
-                vector_storage.flusher()()?;
-
-                let vector_storage_arc = Arc::new(AtomicRefCell::new(vector_storage));
-
                 create_sparse_vector_index(SparseVectorIndexOpenArgs {
                     config: sparse_vector_config.index,
                     id_tracker: id_tracker_arc.clone(),
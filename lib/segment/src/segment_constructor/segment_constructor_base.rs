@@ -30,33 +30,39 @@ use crate::index::struct_payload_index::StructPayloadIndex;
 use crate::index::VectorIndexEnum;
 use crate::payload_storage::on_disk_payload_storage::OnDiskPayloadStorage;
 use crate::payload_storage::payload_storage_enum::PayloadStorageEnum;
+use crate::payload_storage::point_revision_storage::PointRevisionStorage;
 use crate::payload_storage::simple_payload_storage::SimplePayloadStorage;
+use crate::payload_storage::vector_metadata_storage::VectorMetadataStorage;
 use crate::segment::{Segment, SegmentVersion, VectorData, SEGMENT_STATE_FILE};
 use crate::types::{
     Distance, Indexes, PayloadStorageType, SegmentConfig, SegmentState, SegmentType, SeqNumberType,
-    VectorDataConfig, VectorStorageDatatype, VectorStorageType,
+    SparseVectorDataConfig, VectorDataConfig, VectorStorageDatatype, VectorStorageType,
 };
 use crate::vector_storage::dense::appendable_dense_vector_storage::{
     open_appendable_in_ram_vector_storage, open_appendable_in_ram_vector_storage_byte,
-    open_appendable_in_ram_vector_storage_half, open_appendable_memmap_vector_storage,
-    open_appendable_memmap_vector_storage_byte, open_appendable_memmap_vector_storage_half,
+    open_appendable_in_ram_vector_storage_half, open_appendable_in_ram_vector_storage_int8,
+    open_appendable_memmap_vector_storage, open_appendable_memmap_vector_storage_byte,
+    open_appendable_memmap_vector_storage_half, open_appendable_memmap_vector_storage_int8,
 };
 use crate::vector_storage::dense::memmap_dense_vector_storage::{
     open_memmap_vector_storage, open_memmap_vector_storage_byte, open_memmap_vector_storage_half,
+    open_memmap_vector_storage_int8,
 };
 use crate::vector_storage::dense::simple_dense_vector_storage::{
     open_simple_dense_byte_vector_storage, open_simple_dense_half_vector_storage,
-    open_simple_dense_vector_storage,
+    open_simple_dense_int8_vector_storage, open_simple_dense_vector_storage,
 };
 use crate::vector_storage::multi_dense::appendable_mmap_multi_dense_vector_storage::{
     open_appendable_in_ram_multi_vector_storage, open_appendable_in_ram_multi_vector_storage_byte,
-    open_appendable_in_ram_multi_vector_storage_half, open_appendable_memmap_multi_vector_storage,
+    open_appendable_in_ram_multi_vector_storage_half,
+    open_appendable_in_ram_multi_vector_storage_int8, open_appendable_memmap_multi_vector_storage,
     open_appendable_memmap_multi_vector_storage_byte,
     open_appendable_memmap_multi_vector_storage_half,
+    open_appendable_memmap_multi_vector_storage_int8,
 };
 use crate::vector_storage::multi_dense::simple_multi_dense_vector_storage::{
     open_simple_multi_dense_vector_storage, open_simple_multi_dense_vector_storage_byte,
-    open_simple_multi_dense_vector_storage_half,
+    open_simple_multi_dense_vector_storage_half, open_simple_multi_dense_vector_storage_int8,
 };
 use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
@@ -70,7 +76,7 @@ fn sp<T>(t: T) -> Arc<AtomicRefCell<T>> {
     Arc::new(AtomicRefCell::new(t))
 }
 
-fn get_vector_name_with_prefix(prefix: &str, vector_name: &str) -> String {
+pub(crate) fn get_vector_name_with_prefix(prefix: &str, vector_name: &str) -> String {
     if !vector_name.is_empty() {
         format!("{prefix}-{vector_name}")
     } else {
@@ -121,6 +127,14 @@ pub(crate) fn open_vector_storage(
                         *multi_vec_config,
                         stopped,
                     ),
+                    VectorStorageDatatype::Int8 => open_simple_multi_dense_vector_storage_int8(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        *multi_vec_config,
+                        stopped,
+                    ),
                     VectorStorageDatatype::Float16 => open_simple_multi_dense_vector_storage_half(
                         database.clone(),
                         &db_column_name,
@@ -146,6 +160,13 @@ pub(crate) fn open_vector_storage(
                         vector_config.distance,
                         stopped,
                     ),
+                    VectorStorageDatatype::Int8 => open_simple_dense_int8_vector_storage(
+                        database.clone(),
+                        &db_column_name,
+                        vector_config.size,
+                        vector_config.distance,
+                        stopped,
+                    ),
                     VectorStorageDatatype::Float16 => open_simple_dense_half_vector_storage(
                         database.clone(),
                         &db_column_name,
@@ -175,6 +196,14 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Int8 => {
+                        open_appendable_memmap_multi_vector_storage_int8(
+                            vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                            *multi_vec_config,
+                        )
+                    }
                     VectorStorageDatatype::Float16 => {
                         open_appendable_memmap_multi_vector_storage_half(
                             vector_storage_path,
@@ -196,6 +225,11 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Int8 => open_memmap_vector_storage_int8(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                     VectorStorageDatatype::Float16 => open_memmap_vector_storage_half(
                         vector_storage_path,
                         vector_config.size,
@@ -222,6 +256,14 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Int8 => {
+                        open_appendable_memmap_multi_vector_storage_int8(
+                            vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                            *multi_vec_config,
+                        )
+                    }
                     VectorStorageDatatype::Float16 => {
                         open_appendable_memmap_multi_vector_storage_half(
                             vector_storage_path,
@@ -243,6 +285,11 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Int8 => open_appendable_memmap_vector_storage_int8(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                     VectorStorageDatatype::Float16 => open_appendable_memmap_vector_storage_half(
                         vector_storage_path,
                         vector_config.size,
@@ -268,6 +315,14 @@ pub(crate) fn open_vector_storage(
                             *multi_vec_config,
                         )
                     }
+                    VectorStorageDatatype::Int8 => {
+                        open_appendable_in_ram_multi_vector_storage_int8(
+                            vector_storage_path,
+                            vector_config.size,
+                            vector_config.distance,
+                            *multi_vec_config,
+                        )
+                    }
                     VectorStorageDatatype::Float16 => {
                         open_appendable_in_ram_multi_vector_storage_half(
                             vector_storage_path,
@@ -289,6 +344,11 @@ pub(crate) fn open_vector_storage(
                         vector_config.size,
                         vector_config.distance,
                     ),
+                    VectorStorageDatatype::Int8 => open_appendable_in_ram_vector_storage_int8(
+                        vector_storage_path,
+                        vector_config.size,
+                        vector_config.distance,
+                    ),
                     VectorStorageDatatype::Float16 => open_appendable_in_ram_vector_storage_half(
                         vector_storage_path,
                         vector_config.size,
@@ -324,12 +384,14 @@ pub(crate) fn create_payload_storage(
     config: &SegmentConfig,
 ) -> OperationResult<PayloadStorageEnum> {
     let payload_storage = match config.payload_storage_type {
-        PayloadStorageType::InMemory => {
-            PayloadStorageEnum::from(SimplePayloadStorage::open(database)?)
-        }
-        PayloadStorageType::OnDisk => {
-            PayloadStorageEnum::from(OnDiskPayloadStorage::open(database)?)
-        }
+        PayloadStorageType::InMemory => PayloadStorageEnum::from(SimplePayloadStorage::open(
+            database,
+            config.payload_compression,
+        )?),
+        PayloadStorageType::OnDisk => PayloadStorageEnum::from(OnDiskPayloadStorage::open(
+            database,
+            config.payload_compression,
+        )?),
     };
     Ok(payload_storage)
 }
@@ -377,6 +439,7 @@ pub(crate) fn create_vector_index(
                 hnsw_config: vector_hnsw_config.clone(),
                 permit,
                 stopped,
+                progress: None,
             };
             if vector_hnsw_config.on_disk == Some(true) {
                 VectorIndexEnum::HnswMmap(HNSWIndex::open(args)?)
@@ -404,11 +467,10 @@ pub(crate) fn create_sparse_vector_index(
         args.config.datatype.unwrap_or_default(),
         sparse_vector_index::USE_COMPRESSED,
     ) {
-        (_, a @ (VectorStorageDatatype::Float16 | VectorStorageDatatype::Uint8), false) => {
-            Err(OperationError::ValidationError {
-                description: format!("{a:?} datatype is not supported"),
-            })?
-        }
+        (_, a @ (VectorStorageDatatype::Float16 | VectorStorageDatatype::Uint8), false)
+        | (_, a @ VectorStorageDatatype::Int8, _) => Err(OperationError::ValidationError {
+            description: format!("{a:?} datatype is not supported"),
+        })?,
 
         (SparseIndexType::MutableRam, _, _) => {
             VectorIndexEnum::SparseRam(SparseVectorIndex::open(args)?)
@@ -449,10 +511,16 @@ pub(crate) fn create_sparse_vector_index(
 pub(crate) fn create_sparse_vector_storage(
     database: Arc<RwLock<DB>>,
     vector_name: &str,
+    sparse_vector_config: &SparseVectorDataConfig,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let db_column_name = get_vector_name_with_prefix(DB_VECTOR_CF, vector_name);
-    open_simple_sparse_vector_storage(database, &db_column_name, stopped)
+    open_simple_sparse_vector_storage(
+        database,
+        &db_column_name,
+        sparse_vector_config.distance.unwrap_or_default(),
+        stopped,
+    )
 }
 
 fn create_segment(
@@ -479,10 +547,36 @@ fn create_segment(
         ))
     };
 
+    // Vector storages are opened first, payload index needs them for `has_vector` condition
+    let mut vector_storages = HashMap::new();
+    for (vector_name, vector_config) in &config.vector_data {
+        let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
+
+        // Select suitable vector storage type based on configuration
+        let vector_storage = sp(open_vector_storage(
+            &database,
+            vector_config,
+            stopped,
+            &vector_storage_path,
+            vector_name,
+        )?);
+        vector_storages.insert(vector_name.to_owned(), vector_storage);
+    }
+    for (vector_name, sparse_vector_config) in &config.sparse_vector_data {
+        let vector_storage = sp(create_sparse_vector_storage(
+            database.clone(),
+            vector_name,
+            sparse_vector_config,
+            stopped,
+        )?);
+        vector_storages.insert(vector_name.to_owned(), vector_storage);
+    }
+
     let payload_index_path = get_payload_index_path(segment_path);
     let payload_index: Arc<AtomicRefCell<StructPayloadIndex>> = sp(StructPayloadIndex::open(
         payload_storage,
         id_tracker.clone(),
+        vector_storages.clone(),
         &payload_index_path,
         appendable_flag,
     )?);
@@ -492,14 +586,7 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
-        // Select suitable vector storage type based on configuration
-        let vector_storage = Arc::new(AtomicRefCell::new(open_vector_storage(
-            &database,
-            vector_config,
-            stopped,
-            &vector_storage_path,
-            vector_name,
-        )?));
+        let vector_storage = vector_storages[vector_name].clone();
 
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
@@ -551,11 +638,7 @@ fn create_segment(
         let vector_storage_path = get_vector_storage_path(segment_path, vector_name);
         let vector_index_path = get_vector_index_path(segment_path, vector_name);
 
-        let vector_storage = sp(create_sparse_vector_storage(
-            database.clone(),
-            vector_name,
-            stopped,
-        )?);
+        let vector_storage = vector_storages[vector_name].clone();
 
         // Warn when number of points between ID tracker and storage differs
         let point_count = id_tracker.borrow().total_point_count();
@@ -604,6 +687,8 @@ fn create_segment(
         segment_type,
         appendable_flag,
         payload_index,
+        vector_metadata: VectorMetadataStorage::open(database.clone()),
+        point_revisions: PointRevisionStorage::open(database.clone()),
         segment_config: config.clone(),
         error_status: None,
         database,
@@ -0,0 +1,12 @@
+//! Metrics over signed 8-bit vectors.
+//!
+//! Int8 vectors are expected to be quantized on the client side, scores are computed on the raw
+//! integer values without any rescaling. If the client quantized vectors with a symmetric scale
+//! `s`, dot product scores are scaled by `s^2` and euclid and manhattan distances by `s`
+//! compared to the original float vectors. Cosine similarity is invariant to the scale, as long
+//! as all vectors of the collection share it.
+
+pub mod simple_cosine;
+pub mod simple_dot;
+pub mod simple_euclid;
+pub mod simple_manhattan;
@@ -0,0 +1,63 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeInt8};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::CosineMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeInt8> for CosineMetric {
+    fn distance() -> Distance {
+        Distance::Cosine
+    }
+
+    fn similarity(v1: &[VectorElementTypeInt8], v2: &[VectorElementTypeInt8]) -> ScoreType {
+        cosine_similarity_int8(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn cosine_similarity_int8(
+    v1: &[VectorElementTypeInt8],
+    v2: &[VectorElementTypeInt8],
+) -> ScoreType {
+    let mut dot_product = 0i64;
+    let mut norm1 = 0i64;
+    let mut norm2 = 0i64;
+
+    for (a, b) in v1.iter().zip(v2) {
+        dot_product += i64::from(*a) * i64::from(*b);
+        norm1 += i64::from(*a) * i64::from(*a);
+        norm2 += i64::from(*b) * i64::from(*b);
+    }
+
+    if norm1 == 0 || norm2 == 0 {
+        return 0.0;
+    }
+
+    dot_product as ScoreType / (norm1 as ScoreType * norm2 as ScoreType).sqrt()
+}
+
+#[test]
+fn test_zero() {
+    let v1: Vec<i8> = vec![0, 0, 0, 0, 0, 0, 0, 0];
+    let v2: Vec<i8> = vec![127, -128, 0, 126, -125, 124, -123, 122];
+
+    assert_eq!(cosine_similarity_int8(&v1, &v2), 0.0);
+    assert_eq!(cosine_similarity_int8(&v2, &v1), 0.0);
+    assert_eq!(cosine_similarity_int8(&v1, &v1), 0.0);
+}
+
+#[test]
+fn test_scale_invariance() {
+    let v1: Vec<i8> = vec![10, -20, 30, -40];
+    let v2: Vec<i8> = vec![-5, 15, 25, 35];
+    let v1_scaled: Vec<i8> = v1.iter().map(|x| x * 3).collect();
+    let v2_scaled: Vec<i8> = v2.iter().map(|x| x * 3).collect();
+
+    let score = cosine_similarity_int8(&v1, &v2);
+    let scaled_score = cosine_similarity_int8(&v1_scaled, &v2_scaled);
+    assert!((score - scaled_score).abs() < 1e-6);
+}
@@ -0,0 +1,44 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeInt8};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::DotProductMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeInt8> for DotProductMetric {
+    fn distance() -> Distance {
+        Distance::Dot
+    }
+
+    fn similarity(v1: &[VectorElementTypeInt8], v2: &[VectorElementTypeInt8]) -> ScoreType {
+        dot_similarity_int8(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn dot_similarity_int8(
+    v1: &[VectorElementTypeInt8],
+    v2: &[VectorElementTypeInt8],
+) -> ScoreType {
+    let mut dot_product = 0i64;
+
+    for (a, b) in v1.iter().zip(v2) {
+        dot_product += i64::from(*a) * i64::from(*b);
+    }
+
+    dot_product as ScoreType
+}
+
+#[test]
+fn test_negative_values() {
+    let v1: Vec<i8> = vec![-128, 127, -1, 0];
+    let v2: Vec<i8> = vec![-128, -128, 1, 5];
+
+    assert_eq!(
+        dot_similarity_int8(&v1, &v2),
+        (128 * 128 - 127 * 128 - 1) as ScoreType
+    );
+}
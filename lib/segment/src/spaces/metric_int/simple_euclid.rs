@@ -0,0 +1,41 @@
+use common::types::ScoreType;
+
+use crate::data_types::vectors::{DenseVector, VectorElementTypeInt8};
+use crate::spaces::metric::Metric;
+use crate::spaces::simple::EuclidMetric;
+use crate::types::Distance;
+
+impl Metric<VectorElementTypeInt8> for EuclidMetric {
+    fn distance() -> Distance {
+        Distance::Euclid
+    }
+
+    fn similarity(v1: &[VectorElementTypeInt8], v2: &[VectorElementTypeInt8]) -> ScoreType {
+        euclid_similarity_int8(v1, v2)
+    }
+
+    fn preprocess(vector: DenseVector) -> DenseVector {
+        vector
+    }
+}
+
+pub fn euclid_similarity_int8(
+    v1: &[VectorElementTypeInt8],
+    v2: &[VectorElementTypeInt8],
+) -> ScoreType {
+    -v1.iter()
+        .zip(v2)
+        .map(|(a, b)| {
+            let diff = i64::from(*a) - i64::from(*b);
+            diff * diff
+        })
+        .sum::<i64>() as ScoreType
+}
+
+#[test]
+fn test_large_dimension_does_not_overflow() {
+    let v1: Vec<i8> = vec![-128; 65536];
+    let v2: Vec<i8> = vec![127; 65536];
+
+    assert_eq!(euclid_similarity_int8(&v1, &v2), -(255.0 * 255.0 * 65536.0));
+}
@@ -12,8 +12,20 @@ use crate::types::{
 pub struct SegmentTelemetry {
     pub info: SegmentInfo,
     pub config: SegmentConfig,
+    /// Number of points deleted from the segment, but not yet cleaned up by optimization
+    pub num_deleted_points: usize,
+    /// Share of deleted points among all points stored in the segment.
+    /// Equals `1.0` for a fully deleted segment awaiting cleanup.
+    pub deleted_ratio: f64,
     pub vector_index_searches: Vec<VectorIndexSearchesTelemetry>,
     pub payload_field_indices: Vec<PayloadIndexTelemetry>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompressionTelemetry>,
+}
+
+#[derive(Serialize, Clone, Debug, JsonSchema)]
+pub struct PayloadCompressionTelemetry {
+    pub level: i32,
 }
 
 #[derive(Serialize, Clone, Debug, JsonSchema)]
@@ -71,12 +83,21 @@ impl Anonymize for SegmentTelemetry {
         Self {
             info: self.info.anonymize(),
             config: self.config.anonymize(),
+            num_deleted_points: self.num_deleted_points.anonymize(),
+            deleted_ratio: self.deleted_ratio,
             vector_index_searches: self.vector_index_searches.anonymize(),
             payload_field_indices: self.payload_field_indices.anonymize(),
+            payload_compression: self.payload_compression.anonymize(),
         }
     }
 }
 
+impl Anonymize for PayloadCompressionTelemetry {
+    fn anonymize(&self) -> Self {
+        Self { level: self.level }
+    }
+}
+
 impl Anonymize for SegmentInfo {
     fn anonymize(&self) -> Self {
         SegmentInfo {
@@ -120,6 +141,7 @@ impl Anonymize for SegmentConfig {
             vector_data: self.vector_data.anonymize(),
             sparse_vector_data: self.sparse_vector_data.anonymize(),
             payload_storage_type: self.payload_storage_type,
+            payload_compression: self.payload_compression,
         }
     }
 }
@@ -142,6 +164,7 @@ impl Anonymize for SparseVectorDataConfig {
     fn anonymize(&self) -> Self {
         SparseVectorDataConfig {
             index: self.index.anonymize(),
+            distance: self.distance,
         }
     }
 }
@@ -8,10 +8,11 @@ use std::rc::Rc;
 use std::str::FromStr;
 use std::sync::Arc;
 
+use chrono::SubsecRound;
 use common::types::ScoreType;
 use fnv::FnvBuildHasher;
 use geo::prelude::HaversineDistance;
-use geo::{Contains, Coord, LineString, Point, Polygon};
+use geo::{Contains, Coord, Intersects, Line, LineString, Point, Polygon};
 use indexmap::IndexSet;
 use itertools::Itertools;
 use ordered_float::OrderedFloat;
@@ -19,6 +20,7 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::{Map, Value};
 use smol_str::SmolStr;
+use sparse::common::sparse_vector::SparseVector;
 use strum::EnumIter;
 use uuid::Uuid;
 use validator::{Validate, ValidationError, ValidationErrors};
@@ -33,7 +35,7 @@ use crate::data_types::order_by::OrderValue;
 use crate::data_types::vectors::VectorStructInternal;
 use crate::index::field_index::CardinalityEstimation;
 use crate::index::sparse_index::sparse_index_config::SparseIndexConfig;
-use crate::json_path::JsonPath;
+use crate::json_path::{JsonPath, JsonPathItem};
 use crate::spaces::metric::MetricPostProcessing;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
 
@@ -55,6 +57,9 @@ pub type UuidIntType = u128;
 /// Name of the vector field
 pub type VectorName = String;
 
+/// Metadata attached to individual named vectors of a point
+pub type VectorMetadata = HashMap<VectorName, Payload>;
+
 /// Wraps `DateTime<Utc>` to allow more flexible deserialization
 #[derive(Clone, Copy, Serialize, JsonSchema, Debug, PartialEq, PartialOrd)]
 #[serde(transparent)]
@@ -65,6 +70,18 @@ impl DateTimeWrapper {
     pub fn timestamp(&self) -> i64 {
         self.0.timestamp_micros()
     }
+
+    /// Inverse of [`DateTimeWrapper::timestamp`]
+    pub fn from_timestamp(timestamp: i64) -> Option<Self> {
+        chrono::DateTime::from_timestamp_micros(timestamp).map(DateTimeWrapper)
+    }
+
+    /// Drop precision below microseconds, which can't be represented in the timestamp.
+    ///
+    /// This makes comparisons of parsed values consistent with comparisons of indexed ones.
+    fn normalized(self) -> Self {
+        DateTimeWrapper(self.0.trunc_subsecs(6))
+    }
 }
 
 impl<'de> Deserialize<'de> for DateTimeWrapper {
@@ -217,6 +234,18 @@ impl Distance {
             Order::SmallBetter => score < threshold,
         }
     }
+
+    /// Raw distance between vectors, from their post-processed score.
+    ///
+    /// Euclid and Manhattan scores already are distances, similarities are converted to
+    /// cosine distance and negative inner product respectively.
+    pub fn score_to_distance(&self, score: ScoreType) -> ScoreType {
+        match self {
+            Distance::Cosine => 1.0 - score,
+            Distance::Euclid | Distance::Manhattan => score,
+            Distance::Dot => -score,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -225,6 +254,61 @@ pub enum Order {
     SmallBetter,
 }
 
+/// Similarity function used to compare sparse vectors
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SparseDistance {
+    // <https://en.wikipedia.org/wiki/Dot_product>
+    #[default]
+    Dot,
+    // <https://en.wikipedia.org/wiki/Cosine_similarity>
+    Cosine,
+    // Weighted Jaccard (Tanimoto) similarity, same as Jaccard similarity for binary vectors
+    // <https://en.wikipedia.org/wiki/Jaccard_index>
+    Jaccard,
+}
+
+impl SparseDistance {
+    /// Whether scores depend on the norms of the vectors, and not only on their dot product
+    pub fn requires_norms(self) -> bool {
+        match self {
+            SparseDistance::Dot => false,
+            SparseDistance::Cosine | SparseDistance::Jaccard => true,
+        }
+    }
+
+    /// Score of two vectors, from their dot product and squared norms
+    pub fn score_from_dot(self, dot: ScoreType, norm_a: ScoreType, norm_b: ScoreType) -> ScoreType {
+        let denominator = match self {
+            SparseDistance::Dot => return dot,
+            SparseDistance::Cosine => (norm_a * norm_b).sqrt(),
+            SparseDistance::Jaccard => norm_a + norm_b - dot,
+        };
+        // Vectors without non-zero values are not similar to anything
+        if denominator > 0.0 {
+            dot / denominator
+        } else {
+            0.0
+        }
+    }
+
+    /// Score two sparse vectors, sorted by indices
+    pub fn score(self, a: &SparseVector, b: &SparseVector) -> ScoreType {
+        let dot = a.score(b).unwrap_or(0.0);
+        if !self.requires_norms() {
+            return dot;
+        }
+        self.score_from_dot(dot, a.squared_norm(), b.squared_norm())
+    }
+
+    /// Raw distance between sparse vectors, from their score
+    pub fn score_to_distance(self, score: ScoreType) -> ScoreType {
+        match self {
+            SparseDistance::Dot => -score,
+            SparseDistance::Cosine | SparseDistance::Jaccard => 1.0 - score,
+        }
+    }
+}
+
 /// Search result
 #[derive(Clone, Debug)]
 pub struct ScoredPoint {
@@ -242,6 +326,22 @@ pub struct ScoredPoint {
     pub shard_key: Option<ShardKey>,
     /// Order-by value
     pub order_value: Option<OrderValue>,
+    /// Raw distance to the query vector, if requested. Derived from the score as found by the
+    /// shard, before any score modifier or reranker is applied.
+    pub distance: Option<ScoreType>,
+    /// Shard and segment the point was found in, if requested
+    pub provenance: Option<PointProvenance>,
+}
+
+/// Where a found point is stored, for debugging distribution of points
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct PointProvenance {
+    /// Shard of the point, unknown until the results of the shards are merged
+    pub shard_id: Option<u32>,
+    /// Segment of the point within the shard. If the point is stored in multiple segments, e.g.
+    /// before they are merged, this is the segment with the latest version of the point.
+    pub segment_id: usize,
 }
 
 impl Eq for ScoredPoint {}
@@ -376,10 +476,42 @@ pub struct SearchParams {
     /// Size of the beam in a beam-search. Larger the value - more accurate the result, more time required for search.
     pub hnsw_ef: Option<usize>,
 
+    /// Number of candidates kept while descending the upper layers of the HNSW graph.
+    /// By default the descent is greedy and keeps only the single closest candidate.
+    /// A wider beam starts the bottom layer search from several entry points, which improves
+    /// recall on hard queries at the cost of latency. Capped by `hnsw_ef`, which is still the
+    /// number of candidates considered on the bottom layer.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub hnsw_beam_width: Option<usize>,
+
+    /// Maximum number of candidates to visit on the bottom layer of the HNSW graph.
+    /// Once reached, the search stops and returns the best points found so far, even if it has
+    /// not converged yet. Bounds the work of a search, trading recall for latency.
+    /// The search still continues until `limit` points are found, if there are enough.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub hnsw_max_candidates: Option<usize>,
+
+    /// Only use this many dimensions of a sparse query vector, those with the highest absolute
+    /// weights. The other dimensions are ignored, which speeds up searches with long sparse
+    /// queries, trading recall for latency. Stored vectors are not affected.
+    #[serde(default)]
+    #[validate(range(min = 1))]
+    pub sparse_top_k: Option<usize>,
+
     /// Search without approximation. If set to true, search may run long but with exact results.
     #[serde(default)]
     pub exact: bool,
 
+    /// Score points with this distance instead of the distance configured for the vector.
+    /// The search is then exact, over original vectors: the HNSW index and quantized vectors are
+    /// built for the configured distance and are not used.
+    /// Only supported for nearest search by dense vectors. Stored vectors are only normalized if
+    /// the configured distance is `Cosine`, they are normalized when scored for `Cosine` otherwise.
+    #[serde(default)]
+    pub distance: Option<Distance>,
+
     /// Quantization params
     #[serde(default)]
     #[validate(nested)]
@@ -390,6 +522,11 @@ pub struct SearchParams {
     /// guarantee that all uploaded vectors will be included in search results
     #[serde(default)]
     pub indexed_only: bool,
+
+    /// For debugging: annotate each found point with the shard and segment it was found in.
+    /// Points rescored or fetched after the search, e.g. by a formula, are not annotated.
+    #[serde(default)]
+    pub provenance: bool,
 }
 
 /// Collection default values
@@ -464,11 +601,16 @@ impl Indexes {
 }
 
 /// Config of HNSW index
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq, Eq)]
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
 #[serde(rename_all = "snake_case")]
+#[validate(schema(function = "validate_hnsw_config_m0"))]
 pub struct HnswConfig {
     /// Number of edges per node in the index graph. Larger the value - more accurate the search, more space required.
     pub m: usize,
+    /// Number of edges per node on the base layer (level 0) of the index graph, which contains all
+    /// points. Must not be smaller than `m`. If not set, `2 * m` will be used.
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub m0: Option<usize>,
     /// Number of neighbours to consider during the index building. Larger the value - more accurate the search, more time required to build index.
     #[validate(range(min = 4))]
     pub ef_construct: usize,
@@ -490,6 +632,38 @@ pub struct HnswConfig {
     /// Custom M param for hnsw graph built for payload index. If not set, default M will be used.
     #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
     pub payload_m: Option<usize>,
+    /// Level generation multiplier (mL) used to pick the layer of each point in the graph.
+    /// Larger values produce more layers. If not set, the standard `1 / ln(M)` will be used.
+    /// The number of layers is capped based on the number of indexed vectors.
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    #[validate(range(min = 0.0, max = 10.0))]
+    pub level_multiplier: Option<f64>,
+    /// Build the graph in a single thread, with a fixed seed, so that the same data always produces
+    /// the same index. Building is slower, `max_indexing_threads` is not used. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")] // Better backward compatibility
+    pub deterministic_build: Option<bool>,
+}
+
+impl Eq for HnswConfig {}
+
+fn validate_hnsw_config_m0(hnsw_config: &HnswConfig) -> Result<(), ValidationError> {
+    validate_hnsw_m0(Some(hnsw_config.m), hnsw_config.m0)
+}
+
+/// Validate that M on the base layer is not smaller than M on the other layers
+///
+/// A base layer with fewer links than the upper layers degrades recall.
+pub fn validate_hnsw_m0(m: Option<usize>, m0: Option<usize>) -> Result<(), ValidationError> {
+    match (m, m0) {
+        (Some(m), Some(m0)) if m0 < m => {
+            let mut error = ValidationError::new("m0");
+            error.message = Some(Cow::from(format!(
+                "m0 ({m0}) must not be smaller than m ({m})",
+            )));
+            Err(error)
+        }
+        _ => Ok(()),
+    }
 }
 
 impl HnswConfig {
@@ -503,9 +677,11 @@ impl HnswConfig {
     /// doesn't affect the final index, and thus this would return false.
     pub fn mismatch_requires_rebuild(&self, other: &Self) -> bool {
         self.m != other.m
+            || self.m0 != other.m0
             || self.ef_construct != other.ef_construct
             || self.full_scan_threshold != other.full_scan_threshold
             || self.payload_m != other.payload_m
+            || self.level_multiplier != other.level_multiplier
             // Data on disk is the same, we have a unit test for that. We can eventually optimize
             // this to just reload the collection rather than optimizing it again as a whole just
             // to flip this flag
@@ -667,11 +843,14 @@ impl Default for HnswConfig {
     fn default() -> Self {
         HnswConfig {
             m: 16,
+            m0: None,
             ef_construct: DEFAULT_HNSW_EF_CONSTRUCT,
             full_scan_threshold: DEFAULT_FULL_SCAN_THRESHOLD,
             max_indexing_threads: 0,
             on_disk: Some(false),
             payload_m: None,
+            level_multiplier: None,
+            deterministic_build: None,
         }
     }
 }
@@ -699,6 +878,30 @@ impl PayloadStorageType {
     }
 }
 
+/// Compression of stored payloads
+///
+/// Small payloads, for which compression wouldn't reduce size, are always stored uncompressed.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Copy, Clone, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub struct PayloadCompressionConfig {
+    /// Zstd compression level, from 1 (fastest) to 22 (smallest). Default: 3
+    #[serde(default = "default_payload_compression_level")]
+    #[validate(range(min = 1, max = 22))]
+    pub level: i32,
+}
+
+impl Default for PayloadCompressionConfig {
+    fn default() -> Self {
+        Self {
+            level: default_payload_compression_level(),
+        }
+    }
+}
+
+const fn default_payload_compression_level() -> i32 {
+    3
+}
+
 #[derive(Default, Debug, Deserialize, Serialize, JsonSchema, Clone)]
 #[serde(rename_all = "snake_case")]
 pub struct SegmentConfig {
@@ -709,6 +912,10 @@ pub struct SegmentConfig {
     pub sparse_vector_data: HashMap<String, SparseVectorDataConfig>,
     /// Defines payload storage type
     pub payload_storage_type: PayloadStorageType,
+    /// Compression of stored payloads, disabled if not set
+    #[serde(default)]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub payload_compression: Option<PayloadCompressionConfig>,
 }
 
 impl SegmentConfig {
@@ -805,6 +1012,8 @@ pub enum VectorStorageDatatype {
     Float16,
     // Unsigned 8-bit integer
     Uint8,
+    // Signed 8-bit integer
+    Int8,
 }
 
 #[derive(Debug, Default, Deserialize, Serialize, JsonSchema, Eq, PartialEq, Copy, Clone, Hash)]
@@ -878,6 +1087,9 @@ impl VectorDataConfig {
 pub struct SparseVectorDataConfig {
     /// Sparse inverted index config
     pub index: SparseIndexConfig,
+    /// Similarity function of the vectors, dot product if not set
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub distance: Option<SparseDistance>,
 }
 
 impl SparseVectorDataConfig {
@@ -913,6 +1125,48 @@ pub struct GeoLineString {
     pub points: Vec<GeoPoint>,
 }
 
+impl GeoLineString {
+    /// Whether the line crosses the antimeridian.
+    ///
+    /// Each segment of the line is assumed to take the shorter way around the globe, so a
+    /// segment spanning more than 180 degrees of longitude crosses the antimeridian instead.
+    pub fn crosses_antimeridian(&self) -> bool {
+        self.points
+            .windows(2)
+            .any(|segment| (segment[0].lon - segment[1].lon).abs() > 180.0)
+    }
+
+    /// Whether any two non-adjacent segments of the line intersect
+    ///
+    /// If `unwrap_antimeridian` is set, a line crossing the antimeridian is made continuous first.
+    pub fn is_self_intersecting(&self, unwrap_antimeridian: bool) -> bool {
+        let crosses_antimeridian = unwrap_antimeridian && self.crosses_antimeridian();
+        let mut coords: Vec<Coord> = self
+            .points
+            .iter()
+            .map(|p| p.to_coord(crosses_antimeridian))
+            .collect();
+        // Repeated points would make adjacent segments look non-adjacent
+        coords.dedup();
+
+        let segments: Vec<Line> = coords.windows(2).map(|w| Line::new(w[0], w[1])).collect();
+        let is_closed = coords.len() > 1 && coords.first() == coords.last();
+
+        for i in 0..segments.len() {
+            for j in i + 2..segments.len() {
+                // First and last segments of a closed line share a point
+                if is_closed && i == 0 && j == segments.len() - 1 {
+                    continue;
+                }
+                if segments[i].intersects(&segments[j]) {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+}
+
 #[derive(Deserialize)]
 struct GeoPointShadow {
     pub lon: f64,
@@ -948,6 +1202,22 @@ impl GeoPoint {
         Self::validate(lon, lat)?;
         Ok(GeoPoint { lon, lat })
     }
+
+    /// Convert to planar coordinates.
+    ///
+    /// If `unwrap_antimeridian` is set, western longitudes are shifted by 360 degrees, so that
+    /// shapes crossing the antimeridian become continuous.
+    pub fn to_coord(&self, unwrap_antimeridian: bool) -> Coord {
+        let lon = if unwrap_antimeridian && self.lon < 0.0 {
+            self.lon + 360.0
+        } else {
+            self.lon
+        };
+        Coord {
+            x: lon,
+            y: self.lat,
+        }
+    }
 }
 
 impl TryFrom<GeoPointShadow> for GeoPoint {
@@ -982,10 +1252,25 @@ fn payload_example() -> Option<Payload> {
 pub struct Payload(pub Map<String, Value>);
 
 impl Payload {
+    /// Keep only the fields selected by `include`, as [`PayloadSelectorInclude`] does
+    pub fn project(&self, include: &[JsonPath]) -> Payload {
+        JsonPath::value_filter(&self.0, |key, _| {
+            include
+                .iter()
+                .any(|pattern| pattern.check_include_pattern(key))
+        })
+        .into()
+    }
+
     pub fn merge(&mut self, value: &Payload) {
         utils::merge_map(&mut self.0, &value.0)
     }
 
+    /// Apply `patch` with JSON merge patch (RFC 7386) semantics
+    pub fn merge_patch(&mut self, patch: &Payload) {
+        utils::merge_patch_map(&mut self.0, &patch.0)
+    }
+
     pub fn merge_by_key(&mut self, value: &Payload, key: &JsonPath) {
         JsonPath::value_set(Some(key), &mut self.0, &value.0);
     }
@@ -994,6 +1279,39 @@ impl Payload {
         path.value_remove(&mut self.0).to_vec()
     }
 
+    /// Copy the value at `from` to `to`, replacing the value at `to`. Removes the value at `from`
+    /// if `move_field` is set. Both paths must only consist of keys, see [`JsonPath::is_key_path`].
+    ///
+    /// Returns `false` and leaves the payload unchanged, if there is no value at `from`.
+    pub fn copy_field(&mut self, from: &JsonPath, to: &JsonPath, move_field: bool) -> bool {
+        debug_assert!(from.is_key_path() && to.is_key_path());
+        let Some(value) = from
+            .value_get(&self.0)
+            .first()
+            .map(|value| (*value).clone())
+        else {
+            return false;
+        };
+        if move_field {
+            self.remove(from);
+        }
+
+        // Set the last key of `to` in the object at its parent path
+        let (parent, key) = match to.rest.split_last() {
+            Some((JsonPathItem::Key(key), parent)) => (
+                Some(JsonPath {
+                    first_key: to.first_key.clone(),
+                    rest: parent.to_vec(),
+                }),
+                key,
+            ),
+            _ => (None, &to.first_key),
+        };
+        let src = Map::from_iter([(key.clone(), value)]);
+        JsonPath::value_set(parent.as_ref(), &mut self.0, &src);
+        true
+    }
+
     pub fn len(&self) -> usize {
         self.0.len()
     }
@@ -1170,6 +1488,22 @@ pub enum PayloadSchemaParams {
     Uuid(UuidIndexParams),
 }
 
+// The validator crate does not support deriving for enums.
+impl Validate for PayloadSchemaParams {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            PayloadSchemaParams::Text(text_index_params) => text_index_params.validate(),
+            PayloadSchemaParams::Keyword(_)
+            | PayloadSchemaParams::Integer(_)
+            | PayloadSchemaParams::Float(_)
+            | PayloadSchemaParams::Geo(_)
+            | PayloadSchemaParams::Bool(_)
+            | PayloadSchemaParams::Datetime(_)
+            | PayloadSchemaParams::Uuid(_) => Ok(()),
+        }
+    }
+}
+
 impl PayloadSchemaParams {
     /// Human readable type name
     pub fn name(&self) -> &'static str {
@@ -1211,7 +1545,7 @@ impl PayloadSchemaParams {
             PayloadSchemaParams::Uuid(i) => i.on_disk.unwrap_or_default(),
             PayloadSchemaParams::Text(i) => i.on_disk.unwrap_or_default(),
             PayloadSchemaParams::Geo(_) => false,
-            PayloadSchemaParams::Bool(_) => false,
+            PayloadSchemaParams::Bool(i) => i.on_disk.unwrap_or_default(),
         }
     }
 }
@@ -1223,6 +1557,15 @@ pub enum PayloadFieldSchema {
     FieldParams(PayloadSchemaParams),
 }
 
+impl Validate for PayloadFieldSchema {
+    fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            PayloadFieldSchema::FieldType(_) => Ok(()),
+            PayloadFieldSchema::FieldParams(params) => params.validate(),
+        }
+    }
+}
+
 impl Display for PayloadFieldSchema {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
         match self {
@@ -1364,6 +1707,19 @@ pub enum AnyVariants {
     Integers(IndexSet<IntPayloadType, FnvBuildHasher>),
 }
 
+impl AnyVariants {
+    pub fn len(&self) -> usize {
+        match self {
+            AnyVariants::Strings(index_set) => index_set.len(),
+            AnyVariants::Integers(index_set) => index_set.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
 /// Exact match of the given value
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
@@ -1398,6 +1754,17 @@ pub struct MatchExcept {
     pub except: AnyVariants,
 }
 
+/// Should contain all of the given values
+///
+/// All values of the field are considered together, so an array matches if it contains every
+/// listed value. A missing field never matches, while an empty list matches any present value,
+/// including an empty array.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub struct MatchAll {
+    pub all: AnyVariants,
+}
+
 /// Match filter request
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 #[serde(untagged, rename_all = "snake_case")]
@@ -1406,6 +1773,7 @@ pub enum MatchInterface {
     Text(MatchText),
     Any(MatchAny),
     Except(MatchExcept),
+    All(MatchAll),
 }
 
 /// Match filter request
@@ -1416,6 +1784,7 @@ pub enum Match {
     Text(MatchText),
     Any(MatchAny),
     Except(MatchExcept),
+    All(MatchAll),
 }
 
 impl Match {
@@ -1434,6 +1803,10 @@ impl Match {
     pub fn new_except(except: AnyVariants) -> Self {
         Self::Except(MatchExcept { except })
     }
+
+    pub fn new_all(all: AnyVariants) -> Self {
+        Self::All(MatchAll { all })
+    }
 }
 
 impl From<AnyVariants> for Match {
@@ -1451,6 +1824,7 @@ impl From<MatchInterface> for Match {
             MatchInterface::Except(except) => Self::Except(MatchExcept {
                 except: except.except,
             }),
+            MatchInterface::All(all) => Self::All(MatchAll { all: all.all }),
         }
     }
 }
@@ -1529,6 +1903,24 @@ impl From<Vec<IntPayloadType>> for MatchExcept {
     }
 }
 
+impl From<Vec<String>> for MatchAll {
+    fn from(keywords: Vec<String>) -> Self {
+        let keywords: IndexSet<String, FnvBuildHasher> = keywords.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Strings(keywords),
+        }
+    }
+}
+
+impl From<Vec<IntPayloadType>> for MatchAll {
+    fn from(integers: Vec<IntPayloadType>) -> Self {
+        let integers: IndexSet<_, FnvBuildHasher> = integers.into_iter().collect();
+        MatchAll {
+            all: AnyVariants::Integers(integers),
+        }
+    }
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
 #[serde(untagged)]
 pub enum RangeInterface {
@@ -1562,9 +1954,9 @@ impl FromStr for DateTimePayloadType {
             // - YYYY-MM-DD HH:MM:SS-HHMM (timezone without colon)
             .or_else(|_| chrono::DateTime::parse_from_str(s, "%Y-%m-%dT%H:%M:%S%.f%#z"))
             .or_else(|_| chrono::DateTime::parse_from_str(s, "%Y-%m-%d %H:%M:%S%.f%#z"))
-            .map(|dt| chrono::DateTime::<chrono::Utc>::from(dt).into())
+            .map(|dt| DateTimeWrapper(chrono::DateTime::<chrono::Utc>::from(dt)))
         {
-            return Ok(datetime);
+            return Ok(datetime.normalized());
         }
 
         // Attempt to parse the input string in the specified formats:
@@ -1579,8 +1971,8 @@ impl FromStr for DateTimePayloadType {
             .or_else(|_| chrono::NaiveDate::parse_from_str(s, "%Y-%m-%d").map(Into::into))?;
 
         // Convert the parsed NaiveDateTime to a DateTime<Utc>
-        let datetime_utc = datetime.and_utc().into();
-        Ok(datetime_utc)
+        let datetime_utc = DateTimeWrapper(datetime.and_utc());
+        Ok(datetime_utc.normalized())
     }
 }
 
@@ -1667,36 +2059,70 @@ impl GeoBoundingBox {
 
 /// Geo filter request
 ///
-/// Matches coordinates inside the circle of `radius` and center with coordinates `center`
-#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq)]
+/// Matches coordinates inside the circle of `radius` and center with coordinates `center`.
+/// If `inner_radius` is specified, coordinates closer to the center than `inner_radius` are
+/// excluded, so that the area becomes a ring.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate, Clone, PartialEq)]
+#[validate(schema(function = "validate_geo_radius"))]
 #[serde(rename_all = "snake_case")]
 pub struct GeoRadius {
     /// Coordinates of the top left point of the area rectangle
     pub center: GeoPoint,
     /// Radius of the area in meters
     pub radius: f64,
+    /// Radius of the excluded area around the center in meters
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inner_radius: Option<f64>,
 }
 
 impl GeoRadius {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
         let query_center = Point::new(self.center.lon, self.center.lat);
-        query_center.haversine_distance(&Point::new(point.lon, point.lat)) < self.radius
+        let distance = query_center.haversine_distance(&Point::new(point.lon, point.lat));
+        distance < self.radius && self.inner_radius.map_or(true, |inner| distance >= inner)
+    }
+
+    /// Circle of the excluded area, if any
+    pub fn inner_circle(&self) -> Option<GeoRadius> {
+        self.inner_radius.map(|radius| GeoRadius {
+            center: self.center.clone(),
+            radius,
+            inner_radius: None,
+        })
+    }
+}
+
+fn validate_geo_radius(geo_radius: &GeoRadius) -> Result<(), ValidationError> {
+    let Some(inner_radius) = geo_radius.inner_radius else {
+        return Ok(());
+    };
+    if !(0.0..geo_radius.radius).contains(&inner_radius) {
+        let mut error = ValidationError::new("inner_radius");
+        error.message = Some(Cow::from(
+            "inner_radius must be non-negative and smaller than radius",
+        ));
+        return Err(error);
     }
+    Ok(())
 }
 
 #[derive(Deserialize)]
 pub struct GeoPolygonShadow {
     pub exterior: GeoLineString,
     pub interiors: Option<Vec<GeoLineString>>,
+    pub crosses_antimeridian: Option<bool>,
 }
 
 pub struct PolygonWrapper {
     pub polygon: Polygon,
+    /// Whether longitudes of the polygon are shifted to be continuous across the antimeridian,
+    /// see [`GeoLineString::crosses_antimeridian`]
+    pub crosses_antimeridian: bool,
 }
 
 impl PolygonWrapper {
     pub fn check_point(&self, point: &GeoPoint) -> bool {
-        let point_new = Point::new(point.lon, point.lat);
+        let point_new = Point::from(point.to_coord(self.crosses_antimeridian));
         self.polygon.contains(&point_new)
     }
 }
@@ -1715,10 +2141,17 @@ pub struct GeoPolygon {
     /// each GeoLineString must consist of a minimum of 4 points, and the first
     /// and last points must be the same.
     pub interiors: Option<Vec<GeoLineString>>,
+    /// If true, edges spanning more than 180 degrees of longitude cross the antimeridian,
+    /// instead of going around the globe the other way. Default: false
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub crosses_antimeridian: Option<bool>,
 }
 
 impl GeoPolygon {
-    pub fn validate_line_string(line: &GeoLineString) -> OperationResult<()> {
+    pub fn validate_line_string(
+        line: &GeoLineString,
+        unwrap_antimeridian: bool,
+    ) -> OperationResult<()> {
         if line.points.len() <= 3 {
             return Err(OperationError::ValidationError {
                 description: format!(
@@ -1738,16 +2171,30 @@ impl GeoPolygon {
             }
         }
 
+        if line.is_self_intersecting(unwrap_antimeridian) {
+            return Err(OperationError::ValidationError {
+                description: String::from("polygon invalid, the line should not intersect itself"),
+            });
+        }
+
         Ok(())
     }
 
     // convert GeoPolygon to Geo crate Polygon class for checking point intersection
     pub fn convert(&self) -> PolygonWrapper {
+        let crosses_antimeridian = self.crosses_antimeridian.unwrap_or(false)
+            && (self.exterior.crosses_antimeridian()
+                || self
+                    .interiors
+                    .iter()
+                    .flatten()
+                    .any(GeoLineString::crosses_antimeridian));
+
         let exterior_line: LineString = LineString(
             self.exterior
                 .points
                 .iter()
-                .map(|p| Coord { x: p.lon, y: p.lat })
+                .map(|p| p.to_coord(crosses_antimeridian))
                 .collect(),
         );
 
@@ -1760,7 +2207,7 @@ impl GeoPolygon {
                     interior_points
                         .points
                         .iter()
-                        .map(|p| Coord { x: p.lon, y: p.lat })
+                        .map(|p| p.to_coord(crosses_antimeridian))
                         .collect()
                 })
                 .map(LineString)
@@ -1768,6 +2215,7 @@ impl GeoPolygon {
         };
         PolygonWrapper {
             polygon: Polygon::new(exterior_line, interior_lines),
+            crosses_antimeridian,
         }
     }
 }
@@ -1776,17 +2224,20 @@ impl TryFrom<GeoPolygonShadow> for GeoPolygon {
     type Error = OperationError;
 
     fn try_from(value: GeoPolygonShadow) -> OperationResult<Self> {
-        Self::validate_line_string(&value.exterior)?;
+        let unwrap_antimeridian = value.crosses_antimeridian.unwrap_or(false);
+
+        Self::validate_line_string(&value.exterior, unwrap_antimeridian)?;
 
         if let Some(interiors) = &value.interiors {
             for interior in interiors {
-                Self::validate_line_string(interior)?;
+                Self::validate_line_string(interior, unwrap_antimeridian)?;
             }
         }
 
         Ok(GeoPolygon {
             exterior: value.exterior,
             interiors: value.interiors,
+            crosses_antimeridian: value.crosses_antimeridian,
         })
     }
 }
@@ -1805,6 +2256,7 @@ pub struct FieldCondition {
     /// Check if points geo location lies in a given area
     pub geo_bounding_box: Option<GeoBoundingBox>,
     /// Check if geo point is within a given radius
+    #[validate(nested)]
     pub geo_radius: Option<GeoRadius>,
     /// Check if geo point is within a given polygon
     pub geo_polygon: Option<GeoPolygon>,
@@ -1958,6 +2410,23 @@ impl From<JsonPath> for IsEmptyCondition {
     }
 }
 
+/// Select points which have a value for a specified field.
+///
+/// `null` values and empty arrays are not considered a value, so this is the opposite of
+/// `is_empty`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasFieldCondition {
+    pub has_field: PayloadField,
+}
+
+impl From<JsonPath> for HasFieldCondition {
+    fn from(key: JsonPath) -> Self {
+        HasFieldCondition {
+            has_field: PayloadField { key },
+        }
+    }
+}
+
 /// ID-based filtering condition
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
 pub struct HasIdCondition {
@@ -1970,14 +2439,116 @@ impl From<HashSet<PointIdType>> for HasIdCondition {
     }
 }
 
+/// Range of numeric point ids
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Copy, Clone, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub struct IdRange {
+    /// point.id < id_range.lt
+    pub lt: Option<u64>,
+    /// point.id > id_range.gt
+    pub gt: Option<u64>,
+    /// point.id >= id_range.gte
+    pub gte: Option<u64>,
+    /// point.id <= id_range.lte
+    pub lte: Option<u64>,
+}
+
+impl IdRange {
+    /// Inclusive bounds of the range, `None` if the range is empty
+    pub fn bounds(&self) -> Option<(u64, u64)> {
+        let start = match (self.gte, self.gt) {
+            (_, Some(u64::MAX)) => return None,
+            (gte, gt) => gte.unwrap_or(0).max(gt.map_or(0, |gt| gt + 1)),
+        };
+        let end = match (self.lte, self.lt) {
+            (_, Some(0)) => return None,
+            (lte, lt) => lte
+                .unwrap_or(u64::MAX)
+                .min(lt.map_or(u64::MAX, |lt| lt - 1)),
+        };
+        (start <= end).then_some((start, end))
+    }
+
+    /// Whether the id is in the range, UUIDs never are
+    pub fn check(&self, id: PointIdType) -> bool {
+        match id {
+            ExtendedPointId::NumId(id) => self
+                .bounds()
+                .is_some_and(|(start, end)| start <= id && id <= end),
+            ExtendedPointId::Uuid(_) => false,
+        }
+    }
+}
+
+/// Select points with numeric ids in a given range.
+///
+/// Uses the id mapping of segments to select points, so it is as cheap as `has_id`.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct IdRangeCondition {
+    pub id_range: IdRange,
+}
+
+impl From<IdRange> for IdRangeCondition {
+    fn from(id_range: IdRange) -> Self {
+        IdRangeCondition { id_range }
+    }
+}
+
+/// Select points which have a vector with the given name.
+///
+/// A vector is considered present if it was provided for the point, even if all of its values
+/// are zero. Combine with `must_not` to select points which are missing the vector.
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Eq)]
+pub struct HasVectorCondition {
+    pub has_vector: VectorName,
+}
+
+impl From<VectorName> for HasVectorCondition {
+    fn from(vector_name: VectorName) -> Self {
+        HasVectorCondition {
+            has_vector: vector_name,
+        }
+    }
+}
+
 /// Select points with payload for a specified nested field
+///
+/// Conditions of the `filter` are checked against each element of the array separately, so a
+/// point matches only if a single element satisfies the whole filter.
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
+#[validate(schema(function = "validate_nested"))]
 pub struct Nested {
     pub key: PayloadKeyType,
     #[validate(nested)]
     pub filter: Filter,
 }
 
+/// `has_id` and `id_range` can't be checked against an array element; they would never match within nested filter
+fn validate_nested(nested: &Nested) -> Result<(), ValidationError> {
+    fn has_id_condition(filter: &Filter) -> bool {
+        filter.iter_conditions().any(|condition| match condition {
+            Condition::HasId(_) | Condition::IdRange(_) => true,
+            Condition::Filter(filter) => has_id_condition(filter),
+            Condition::Nested(nested) => has_id_condition(nested.filter()),
+            Condition::Field(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasField(_)
+            | Condition::HasVector(_)
+            | Condition::CustomIdChecker(_) => false,
+        })
+    }
+
+    if has_id_condition(&nested.filter) {
+        let mut error = ValidationError::new("nested_has_id");
+        error.message = Some(Cow::from(
+            "has_id and id_range conditions are not supported inside of nested filter, use them on the top level instead",
+        ));
+        return Err(error);
+    }
+    Ok(())
+}
+
 #[derive(Debug, Deserialize, Serialize, JsonSchema, Clone, PartialEq, Validate)]
 pub struct NestedCondition {
     #[validate(nested)]
@@ -2015,8 +2586,14 @@ pub enum Condition {
     IsEmpty(IsEmptyCondition),
     /// Check if payload field equals `NULL`
     IsNull(IsNullCondition),
+    /// Check if payload field has a value, which is not `NULL` or an empty array
+    HasField(HasFieldCondition),
     /// Check if points id is in a given set
     HasId(HasIdCondition),
+    /// Check if points id is in a given range
+    IdRange(IdRangeCondition),
+    /// Check if point has a vector with the given name
+    HasVector(HasVectorCondition),
     /// Nested filters
     Nested(NestedCondition),
     /// Nested filter
@@ -2032,7 +2609,10 @@ impl PartialEq for Condition {
             (Self::Field(this), Self::Field(other)) => this == other,
             (Self::IsEmpty(this), Self::IsEmpty(other)) => this == other,
             (Self::IsNull(this), Self::IsNull(other)) => this == other,
+            (Self::HasField(this), Self::HasField(other)) => this == other,
             (Self::HasId(this), Self::HasId(other)) => this == other,
+            (Self::IdRange(this), Self::IdRange(other)) => this == other,
+            (Self::HasVector(this), Self::HasVector(other)) => this == other,
             (Self::Nested(this), Self::Nested(other)) => this == other,
             (Self::Filter(this), Self::Filter(other)) => this == other,
             (Self::CustomIdChecker(_), Self::CustomIdChecker(_)) => false,
@@ -2053,7 +2633,12 @@ impl Condition {
 impl Validate for Condition {
     fn validate(&self) -> Result<(), ValidationErrors> {
         match self {
-            Condition::HasId(_) | Condition::IsEmpty(_) | Condition::IsNull(_) => Ok(()),
+            Condition::HasId(_)
+            | Condition::IdRange(_)
+            | Condition::HasVector(_)
+            | Condition::IsEmpty(_)
+            | Condition::IsNull(_)
+            | Condition::HasField(_) => Ok(()),
             Condition::Field(field_condition) => field_condition.validate(),
             Condition::Nested(nested_condition) => nested_condition.validate(),
             Condition::Filter(filter) => filter.validate(),
@@ -2225,13 +2810,7 @@ impl PayloadSelector {
     /// Process payload selector
     pub fn process(&self, x: Payload) -> Payload {
         match self {
-            PayloadSelector::Include(selector) => JsonPath::value_filter(&x.0, |key, _| {
-                selector
-                    .include
-                    .iter()
-                    .any(|pattern| pattern.check_include_pattern(key))
-            })
-            .into(),
+            PayloadSelector::Include(selector) => x.project(&selector.include),
             PayloadSelector::Exclude(selector) => JsonPath::value_filter(&x.0, |key, _| {
                 selector
                     .exclude
@@ -2391,6 +2970,7 @@ pub(crate) mod test_utils {
         GeoPolygon {
             exterior: exterior_line,
             interiors: None,
+            crosses_antimeridian: None,
         }
     }
 
@@ -2420,6 +3000,7 @@ pub(crate) mod test_utils {
         GeoPolygon {
             exterior: exterior_line,
             interiors: interior_lines,
+            crosses_antimeridian: None,
         }
     }
 }
@@ -2431,7 +3012,7 @@ mod tests {
     use serde_json;
     use serde_json::json;
 
-    use super::test_utils::build_polygon_with_interiors;
+    use super::test_utils::{build_polygon, build_polygon_with_interiors};
     use super::*;
 
     #[allow(dead_code)]
@@ -2483,6 +3064,40 @@ mod tests {
         assert_eq!(datetime.timestamp(), datetime_no_z.timestamp());
     }
 
+    #[test]
+    fn test_datetime_normalization() {
+        // Same instant with different offsets and precision
+        let same_instant = [
+            "2020-03-01T01:02:03Z",
+            "2020-03-01T01:02:03.000Z",
+            "2020-03-01T01:02:03.000000000Z",
+            "2020-03-01T10:02:03+09:00",
+            "2020-02-29 16:02:03.0-0900",
+            "2020-03-01T01:02:03",
+        ];
+        let expected = DateTimePayloadType::from_str(same_instant[0]).unwrap();
+        for datetime in same_instant {
+            assert_eq!(DateTimePayloadType::from_str(datetime).unwrap(), expected);
+        }
+
+        // Sub-microsecond precision is dropped
+        let nanos = DateTimePayloadType::from_str("2020-03-01T01:02:03.123456789+01:00").unwrap();
+        let micros = DateTimePayloadType::from_str("2020-03-01T00:02:03.123456Z").unwrap();
+        assert_eq!(nanos, micros);
+        assert_eq!(
+            DateTimePayloadType::from_timestamp(nanos.timestamp()),
+            Some(micros)
+        );
+
+        let range = Range {
+            lt: None,
+            gt: None,
+            gte: Some(micros),
+            lte: Some(micros),
+        };
+        assert!(range.check_range(nanos));
+    }
+
     #[test]
     fn test_timezone_ordering() {
         let datetimes = [
@@ -2521,6 +3136,7 @@ mod tests {
         let radius = GeoRadius {
             center: GeoPoint { lon: 0.0, lat: 0.0 },
             radius: 80000.0,
+            inner_radius: None,
         };
 
         let inside_result = radius.check_point(&GeoPoint { lon: 0.5, lat: 0.5 });
@@ -2528,6 +3144,22 @@ mod tests {
 
         let outside_result = radius.check_point(&GeoPoint { lon: 1.5, lat: 1.5 });
         assert!(!outside_result);
+
+        // haversine distance between (0, 0) and (0.5, 0.5) is 78626.29627999048
+        let ring = GeoRadius {
+            center: GeoPoint { lon: 0.0, lat: 0.0 },
+            radius: 200000.0,
+            inner_radius: Some(80000.0),
+        };
+        assert!(ring.check_point(&GeoPoint { lon: 1.0, lat: 1.0 }));
+        assert!(!ring.check_point(&GeoPoint { lon: 0.5, lat: 0.5 }));
+        assert!(!ring.check_point(&GeoPoint { lon: 1.5, lat: 1.5 }));
+
+        let invalid_ring = GeoRadius {
+            inner_radius: Some(300000.0),
+            ..ring
+        };
+        assert!(invalid_ring.validate().is_err());
     }
 
     #[test]
@@ -2650,6 +3282,87 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_geo_polygon_antimeridian_check_point() {
+        // Polygon over the Pacific, from Japan to California
+        let polygon = build_polygon(vec![
+            (130.0, 50.0),
+            (-110.0, 50.0),
+            (-110.0, 20.0),
+            (130.0, 20.0),
+            (130.0, 50.0),
+        ]);
+
+        // Without opting in, the polygon spans the Atlantic instead
+        let atlantic = polygon.convert();
+        assert!(!atlantic.crosses_antimeridian);
+        assert!(atlantic.check_point(&GeoPoint {
+            lon: -30.0,
+            lat: 35.0,
+        }));
+
+        let polygon = GeoPolygon {
+            crosses_antimeridian: Some(true),
+            ..polygon
+        }
+        .convert();
+        assert!(polygon.crosses_antimeridian);
+
+        // Tokyo
+        assert!(polygon.check_point(&GeoPoint {
+            lon: 139.691706,
+            lat: 35.689487,
+        }));
+        // Los Angeles
+        assert!(polygon.check_point(&GeoPoint {
+            lon: -118.243683,
+            lat: 34.052235,
+        }));
+        // Berlin
+        assert!(!polygon.check_point(&GeoPoint {
+            lon: 13.41053,
+            lat: 52.52437,
+        }));
+        // New York
+        assert!(!polygon.check_point(&GeoPoint {
+            lon: -73.991516,
+            lat: 40.75798,
+        }));
+    }
+
+    #[test]
+    fn test_geo_polygon_self_intersection() {
+        let square = r#"
+        {
+            "exterior": {
+                "points": [
+                    { "lon": -1.0, "lat": -1.0 },
+                    { "lon": 1.0, "lat": -1.0 },
+                    { "lon": 1.0, "lat": 1.0 },
+                    { "lon": -1.0, "lat": 1.0 },
+                    { "lon": -1.0, "lat": -1.0 }
+                ]
+            }
+        }
+        "#;
+        assert!(serde_json::from_str::<GeoPolygon>(square).is_ok());
+
+        let twisted_square = r#"
+        {
+            "exterior": {
+                "points": [
+                    { "lon": -1.0, "lat": -1.0 },
+                    { "lon": 1.0, "lat": 1.0 },
+                    { "lon": 1.0, "lat": -1.0 },
+                    { "lon": -1.0, "lat": 1.0 },
+                    { "lon": -1.0, "lat": -1.0 }
+                ]
+            }
+        }
+        "#;
+        assert!(serde_json::from_str::<GeoPolygon>(twisted_square).is_err());
+    }
+
     #[test]
     fn test_serialize_query() {
         let filter = Filter {
@@ -2988,6 +3701,43 @@ mod tests {
         assert!(c.range.is_some());
     }
 
+    #[test]
+    fn test_validate_nested_filter_has_id() {
+        let valid = r#"
+        {
+          "must": [
+            { "has_id": [1, 2] },
+            {
+              "nested": {
+                "key": "country.cities",
+                "filter": { "must": { "key": "population", "range": { "gte": 8 } } }
+              }
+            }
+          ]
+        }
+        "#;
+        let filter: Filter = serde_json::from_str(valid).unwrap();
+        assert!(filter.validate().is_ok());
+
+        let invalid = r#"
+        {
+          "must": {
+            "nested": {
+              "key": "country.cities",
+              "filter": {
+                "must": [
+                  { "key": "population", "range": { "gte": 8 } },
+                  { "should": [{ "has_id": [1, 2] }] }
+                ]
+              }
+            }
+          }
+        }
+        "#;
+        let filter: Filter = serde_json::from_str(invalid).unwrap();
+        assert!(filter.validate().is_err());
+    }
+
     #[test]
     fn test_payload_query_parse() {
         let query1 = r#"
@@ -3372,6 +4122,42 @@ mod tests {
         assert_eq!(payload, expected.into());
     }
 
+    #[test]
+    fn test_payload_copy_field() {
+        let payload: Payload = json!({
+            "a": 1,
+            "b": {"c": [1, 2], "d": "text"},
+            "e": 2,
+        })
+        .into();
+
+        // Copy into a nested field, creating the objects on the way
+        let mut copied = payload.clone();
+        assert!(copied.copy_field(&JsonPath::new("b.c"), &JsonPath::new("x.y"), false));
+        let expected = json!({
+            "a": 1,
+            "b": {"c": [1, 2], "d": "text"},
+            "e": 2,
+            "x": {"y": [1, 2]},
+        });
+        assert_eq!(copied, expected.into());
+
+        // Move replaces the value at the destination
+        let mut moved = payload.clone();
+        assert!(moved.copy_field(&JsonPath::new("b.d"), &JsonPath::new("e"), true));
+        let expected = json!({
+            "a": 1,
+            "b": {"c": [1, 2]},
+            "e": "text",
+        });
+        assert_eq!(moved, expected.into());
+
+        // Missing source field
+        let mut missing = payload.clone();
+        assert!(!missing.copy_field(&JsonPath::new("b.x"), &JsonPath::new("a"), true));
+        assert_eq!(missing, payload);
+    }
+
     #[test]
     fn test_payload_selector_array_include() {
         let payload = json!({
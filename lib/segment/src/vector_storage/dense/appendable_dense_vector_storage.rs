@@ -184,6 +184,18 @@ pub fn open_appendable_memmap_vector_storage_byte(
     )))
 }
 
+pub fn open_appendable_memmap_vector_storage_int8(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_appendable_memmap_vector_storage_impl(path, dim, distance)?;
+
+    Ok(VectorStorageEnum::DenseAppendableMemmapInt8(Box::new(
+        storage,
+    )))
+}
+
 pub fn open_appendable_memmap_vector_storage_half(
     path: &Path,
     dim: usize,
@@ -249,6 +261,18 @@ pub fn open_appendable_in_ram_vector_storage_byte(
     )))
 }
 
+pub fn open_appendable_in_ram_vector_storage_int8(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_appendable_in_ram_vector_storage_impl(path, dim, distance)?;
+
+    Ok(VectorStorageEnum::DenseAppendableInRamInt8(Box::new(
+        storage,
+    )))
+}
+
 pub fn open_appendable_in_ram_vector_storage_half(
     path: &Path,
     dim: usize,
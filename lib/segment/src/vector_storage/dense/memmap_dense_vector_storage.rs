@@ -1,6 +1,6 @@
 use std::borrow::Cow;
 use std::fs::{create_dir_all, File, OpenOptions};
-use std::io::{self, Write};
+use std::io::{self, BufWriter, Write};
 use std::ops::Range;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::AtomicBool;
@@ -16,11 +16,15 @@ use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{VectorElementType, VectorRef};
 use crate::types::{Distance, VectorStorageDatatype};
 use crate::vector_storage::common::get_async_scorer;
-use crate::vector_storage::dense::mmap_dense_vectors::MmapDenseVectors;
+use crate::vector_storage::dense::mmap_dense_vectors::{
+    MmapDenseVectors, HEADER_SIZE, SLOTS_HEADER,
+};
+use crate::vector_storage::dense::vector_deduplication::{VectorDeduplication, VectorDeduplicator};
 use crate::vector_storage::{DenseVectorStorage, VectorStorage, VectorStorageEnum};
 
 const VECTORS_PATH: &str = "matrix.dat";
 const DELETED_PATH: &str = "deleted.dat";
+const SLOTS_PATH: &str = "slots.dat";
 
 /// Stores all dense vectors in mem-mapped file
 ///
@@ -28,12 +32,19 @@ const DELETED_PATH: &str = "deleted.dat";
 /// but possible to mark some vectors as removed
 ///
 /// Mem-mapped storage can only be constructed from another storage
+///
+/// If deduplication is enabled, duplicate vectors are stored only once, and points reference
+/// them through a slots file. Stored vectors are never removed, they are dropped along with the
+/// storage when the segment is optimized.
 #[derive(Debug)]
 pub struct MemmapDenseVectorStorage<T: PrimitiveVectorElement> {
     vectors_path: PathBuf,
     deleted_path: PathBuf,
+    slots_path: PathBuf,
     mmap_store: Option<MmapDenseVectors<T>>,
     distance: Distance,
+    /// Deduplication of vectors added with [`VectorStorage::update_from`]
+    deduplication: Option<VectorDeduplication>,
 }
 
 pub fn open_memmap_vector_storage(
@@ -60,6 +71,16 @@ pub fn open_memmap_vector_storage_byte(
     Ok(VectorStorageEnum::DenseMemmapByte(storage))
 }
 
+pub fn open_memmap_vector_storage_int8(
+    path: &Path,
+    dim: usize,
+    distance: Distance,
+) -> OperationResult<VectorStorageEnum> {
+    let storage =
+        open_memmap_vector_storage_with_async_io_impl(path, dim, distance, get_async_scorer())?;
+    Ok(VectorStorageEnum::DenseMemmapInt8(storage))
+}
+
 pub fn open_memmap_vector_storage_half(
     path: &Path,
     dim: usize,
@@ -95,13 +116,22 @@ fn open_memmap_vector_storage_with_async_io_impl<T: PrimitiveVectorElement>(
 
     let vectors_path = path.join(VECTORS_PATH);
     let deleted_path = path.join(DELETED_PATH);
-    let mmap_store = MmapDenseVectors::open(&vectors_path, &deleted_path, dim, with_async_io)?;
+    let slots_path = path.join(SLOTS_PATH);
+    let mmap_store = MmapDenseVectors::open(
+        &vectors_path,
+        &deleted_path,
+        &slots_path,
+        dim,
+        with_async_io,
+    )?;
 
     Ok(Box::new(MemmapDenseVectorStorage {
         vectors_path,
         deleted_path,
+        slots_path,
         mmap_store: Some(mmap_store),
         distance,
+        deduplication: None,
     }))
 }
 
@@ -124,6 +154,76 @@ impl<T: PrimitiveVectorElement> MemmapDenseVectorStorage<T> {
             .map(|x| x.has_async_reader())
             .unwrap_or(false)
     }
+
+    pub fn set_deduplication(&mut self, deduplication: Option<VectorDeduplication>) {
+        self.deduplication = deduplication;
+    }
+
+    /// Append vectors, storing each duplicate only once
+    ///
+    /// Returns deleted flags of the appended points.
+    fn append_deduplicated<'a>(
+        &self,
+        deduplication: VectorDeduplication,
+        other_vectors: &'a mut impl Iterator<Item = (CowVector<'a>, bool)>,
+        stopped: &AtomicBool,
+    ) -> OperationResult<Vec<bool>> {
+        let store = self.mmap_store.as_ref().unwrap();
+        let dim = store.dim;
+
+        let mut deduplicator =
+            VectorDeduplicator::new(deduplication, &self.vectors_path, HEADER_SIZE, dim)?;
+        for (index, vector) in store.iter_stored_vectors().enumerate() {
+            check_process_stopped(stopped)?;
+            deduplicator.insert(vector, index as PointOffsetType);
+        }
+
+        // Existing points of a storage without deduplication reference their own vectors
+        let mut slots_file = if store.is_deduplicated() {
+            BufWriter::new(open_append(&self.slots_path)?)
+        } else {
+            let mut slots_file = BufWriter::new(File::create(&self.slots_path)?);
+            slots_file.write_all(SLOTS_HEADER)?;
+            for index in 0..store.num_vectors as PointOffsetType {
+                slots_file.write_all(mmap_ops::transmute_to_u8(&index))?;
+            }
+            slots_file
+        };
+
+        let mut next_index = store.num_stored_vectors as PointOffsetType;
+        // Not buffered, the deduplicator reads back written vectors
+        let mut vectors_file = open_append(&self.vectors_path)?;
+        let mut deleted = vec![];
+        let mut duplicates = 0;
+        for (other_vector, other_deleted) in other_vectors {
+            check_process_stopped(stopped)?;
+            let vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+            let index = match deduplicator.get_or_insert(vector.as_ref(), next_index)? {
+                Some(index) => {
+                    duplicates += 1;
+                    index
+                }
+                None => {
+                    vectors_file.write_all(mmap_ops::transmute_to_u8_slice(vector.as_ref()))?;
+                    let index = next_index;
+                    next_index += 1;
+                    index
+                }
+            };
+            slots_file.write_all(mmap_ops::transmute_to_u8(&index))?;
+            deleted.push(other_deleted);
+        }
+        vectors_file.flush()?;
+        slots_file.flush()?;
+
+        log::debug!(
+            "Deduplicated {duplicates} out of {} vectors in {}",
+            deleted.len(),
+            self.vectors_path.display(),
+        );
+
+        Ok(deleted)
+    }
 }
 
 impl<T: PrimitiveVectorElement> DenseVectorStorage<T> for MemmapDenseVectorStorage<T> {
@@ -180,36 +280,38 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
     ) -> OperationResult<Range<PointOffsetType>> {
         let dim = self.vector_dim();
         let start_index = self.mmap_store.as_ref().unwrap().num_vectors as PointOffsetType;
-        let mut end_index = start_index;
 
         let with_async_io = self
             .mmap_store
-            .take()
+            .as_ref()
             .map(|x| x.has_async_reader())
             .unwrap_or(get_async_scorer());
 
         // Extend vectors file, write other vectors into it
-        let mut vectors_file = open_append(&self.vectors_path)?;
-        let mut deleted_ids = vec![];
-        for (offset, (other_vector, other_deleted)) in other_ids.enumerate() {
-            check_process_stopped(stopped)?;
-            let vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
-            let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
-            vectors_file.write_all(raw_bites)?;
-            end_index += 1;
-
-            // Remember deleted IDs so we can propagate deletions later
-            if other_deleted {
-                deleted_ids.push(start_index as PointOffsetType + offset as PointOffsetType);
+        let deleted_flags = match self.deduplication {
+            Some(deduplication) => self.append_deduplicated(deduplication, other_ids, stopped)?,
+            None => {
+                let mut vectors_file = open_append(&self.vectors_path)?;
+                let mut deleted_flags = vec![];
+                for (other_vector, other_deleted) in other_ids {
+                    check_process_stopped(stopped)?;
+                    let vector = T::slice_from_float_cow(Cow::try_from(other_vector)?);
+                    let raw_bites = mmap_ops::transmute_to_u8_slice(vector.as_ref());
+                    vectors_file.write_all(raw_bites)?;
+                    deleted_flags.push(other_deleted);
+                }
+                vectors_file.flush()?;
+                deleted_flags
             }
-        }
-        vectors_file.flush()?;
-        drop(vectors_file);
+        };
+        let end_index = start_index + deleted_flags.len() as PointOffsetType;
 
         // Load store with updated files
+        self.mmap_store.take();
         self.mmap_store.replace(MmapDenseVectors::open(
             &self.vectors_path,
             &self.deleted_path,
+            &self.slots_path,
             dim,
             with_async_io,
         )?);
@@ -219,9 +321,11 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
         // because the file backing delete storage must be resized, and for that we'd need to know
         // the exact number of vectors beforehand. When opening the store it is done automatically.
         let store = self.mmap_store.as_mut().unwrap();
-        for id in deleted_ids {
+        for (offset, deleted) in deleted_flags.into_iter().enumerate() {
             check_process_stopped(stopped)?;
-            store.delete(id);
+            if deleted {
+                store.delete(start_index + offset as PointOffsetType);
+            }
         }
 
         Ok(start_index..end_index)
@@ -238,7 +342,11 @@ impl<T: PrimitiveVectorElement> VectorStorage for MemmapDenseVectorStorage<T> {
     }
 
     fn files(&self) -> Vec<PathBuf> {
-        vec![self.vectors_path.clone(), self.deleted_path.clone()]
+        let mut files = vec![self.vectors_path.clone(), self.deleted_path.clone()];
+        if self.slots_path.exists() {
+            files.push(self.slots_path.clone());
+        }
+        files
     }
 
     fn delete_vector(&mut self, key: PointOffsetType) -> OperationResult<bool> {
@@ -285,9 +393,76 @@ mod tests {
     use crate::id_tracker::id_tracker_base::IdTracker;
     use crate::types::{PointIdType, QuantizationConfig, ScalarQuantizationConfig};
     use crate::vector_storage::dense::simple_dense_vector_storage::open_simple_dense_vector_storage;
+    use crate::vector_storage::dense::vector_deduplication::VectorDeduplication;
     use crate::vector_storage::new_raw_scorer;
     use crate::vector_storage::quantized::quantized_vectors::QuantizedVectors;
 
+    #[test]
+    fn test_deduplication() {
+        let points = [
+            vec![1.0, 0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 1.0, 0.0],
+            vec![1.0, 0.0, 1.0, 1.0],
+            vec![1.0, 0.0, 1.0, 0.001],
+            vec![1.0, 0.0, 1.0, 1.0],
+        ];
+
+        let build = |deduplication, deleted: &[PointOffsetType]| {
+            let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
+            let mut storage = open_memmap_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+            assert!(storage.set_deduplication(Some(deduplication)));
+            let mut iter = points.iter().enumerate().map(|(i, point)| {
+                let deleted = deleted.contains(&(i as PointOffsetType));
+                (CowVector::from(point.as_slice()), deleted)
+            });
+            storage.update_from(&mut iter, &Default::default()).unwrap();
+            (dir, storage)
+        };
+
+        let stored_vectors = |storage: &VectorStorageEnum| match storage {
+            VectorStorageEnum::DenseMemmap(storage) => {
+                storage.get_mmap_vectors().num_stored_vectors
+            }
+            _ => unreachable!(),
+        };
+
+        // Exact duplicates share a vector, near-duplicates are kept apart
+        let (dir, storage) = build(VectorDeduplication::Exact, &[2]);
+        assert_eq!(storage.total_vector_count(), points.len());
+        assert_eq!(stored_vectors(&storage), 3);
+        assert!(storage.is_deleted_vector(2));
+        assert!(!storage.is_deleted_vector(4));
+        for (i, point) in points.iter().enumerate() {
+            let vector: DenseVector = storage
+                .get_vector(i as PointOffsetType)
+                .to_owned()
+                .try_into()
+                .unwrap();
+            assert_eq!(&vector, point);
+        }
+
+        // Deduplicated vectors are loaded from disk
+        drop(storage);
+        let storage = open_memmap_vector_storage(dir.path(), 4, Distance::Dot).unwrap();
+        assert_eq!(storage.total_vector_count(), points.len());
+        assert_eq!(stored_vectors(&storage), 3);
+        assert!(storage.is_deleted_vector(2));
+        for (i, point) in points.iter().enumerate() {
+            let vector: DenseVector = storage
+                .get_vector(i as PointOffsetType)
+                .to_owned()
+                .try_into()
+                .unwrap();
+            assert_eq!(&vector, point);
+        }
+
+        // Near-duplicates are merged only if requested
+        let (_dir, storage) = build(VectorDeduplication::Epsilon(0.01), &[]);
+        assert_eq!(stored_vectors(&storage), 2);
+        let vector: DenseVector = storage.get_vector(3).to_owned().try_into().unwrap();
+        assert_eq!(vector, points[1]);
+    }
+
     #[test]
     fn test_basic_persistence() {
         let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
@@ -20,19 +20,25 @@ use crate::vector_storage::async_io::UringReader;
 #[cfg(not(target_os = "linux"))]
 use crate::vector_storage::async_io_mock::UringReader;
 
-const HEADER_SIZE: usize = 4;
+pub(super) const HEADER_SIZE: usize = 4;
 const VECTORS_HEADER: &[u8; HEADER_SIZE] = b"data";
 const DELETED_HEADER: &[u8; HEADER_SIZE] = b"drop";
+pub(super) const SLOTS_HEADER: &[u8; HEADER_SIZE] = b"slot";
 
 /// Mem-mapped file for dense vectors
 #[derive(Debug)]
 pub struct MmapDenseVectors<T: PrimitiveVectorElement> {
     pub dim: usize,
     pub num_vectors: usize,
+    /// Number of vectors in the data file, less than `num_vectors` if vectors are deduplicated
+    pub num_stored_vectors: usize,
     /// Memory mapped file for vector data
     ///
-    /// Has an exact size to fit a header and `num_vectors` of vectors.
+    /// Has an exact size to fit a header and `num_stored_vectors` of vectors.
     mmap: Arc<Mmap>,
+    /// Memory mapped index of stored vector for each point, only present if vectors are
+    /// deduplicated
+    slots: Option<Mmap>,
     /// Context for io_uring-base async IO
     #[cfg_attr(not(target_os = "linux"), allow(dead_code))]
     uring_reader: Mutex<Option<UringReader<T>>>,
@@ -46,6 +52,7 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
     pub fn open(
         vectors_path: &Path,
         deleted_path: &Path,
+        slots_path: &Path,
         dim: usize,
         with_async_io: bool,
     ) -> OperationResult<Self> {
@@ -54,7 +61,21 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
             .describe("Create mmap data file")?;
         let mmap = mmap_ops::open_read_mmap(vectors_path, AdviceSetting::Global, false)
             .describe("Open mmap for reading")?;
-        let num_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<T>();
+        let num_stored_vectors = (mmap.len() - HEADER_SIZE) / dim / size_of::<T>();
+
+        // Open slots mmap of deduplicated vectors
+        let slots = if slots_path.exists() {
+            Some(
+                mmap_ops::open_read_mmap(slots_path, AdviceSetting::Global, false)
+                    .describe("Open mmap slots for reading")?,
+            )
+        } else {
+            None
+        };
+        let num_vectors = match &slots {
+            Some(slots) => (slots.len() - HEADER_SIZE) / size_of::<PointOffsetType>(),
+            None => num_stored_vectors,
+        };
 
         // Allocate/open deleted mmap
         let deleted_mmap_size = deleted_mmap_size(num_vectors);
@@ -73,7 +94,9 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
         let deleted = MmapBitSlice::try_from(deleted_mmap, deleted_mmap_data_start())?;
         let deleted_count = deleted.count_ones();
 
-        let uring_reader = if with_async_io {
+        // Async IO reads vectors by point offset, which doesn't match the position of deduplicated
+        // vectors in the data file
+        let uring_reader = if with_async_io && slots.is_none() {
             // Keep file handle open for async IO
             let vectors_file = File::open(vectors_path)?;
             let raw_size = dim * size_of::<T>();
@@ -85,7 +108,9 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
         Ok(MmapDenseVectors {
             dim,
             num_vectors,
+            num_stored_vectors,
             mmap: mmap.into(),
+            slots,
             uring_reader: Mutex::new(uring_reader),
             deleted,
             deleted_count,
@@ -101,12 +126,29 @@ impl<T: PrimitiveVectorElement> MmapDenseVectors<T> {
     }
 
     pub fn data_offset(&self, key: PointOffsetType) -> Option<usize> {
-        let vector_data_length = self.dim * size_of::<T>();
-        let offset = (key as usize) * vector_data_length + HEADER_SIZE;
         if key >= (self.num_vectors as PointOffsetType) {
             return None;
         }
-        Some(offset)
+        let index = match &self.slots {
+            Some(slots) => {
+                mmap_ops::transmute_from_u8_to_slice::<PointOffsetType>(&slots[HEADER_SIZE..])
+                    [key as usize]
+            }
+            None => key,
+        };
+        let vector_data_length = self.dim * size_of::<T>();
+        Some((index as usize) * vector_data_length + HEADER_SIZE)
+    }
+
+    /// Whether points share stored vectors
+    pub fn is_deduplicated(&self) -> bool {
+        self.slots.is_some()
+    }
+
+    /// Iterate over vectors in the data file, in the order they are stored
+    pub fn iter_stored_vectors(&self) -> impl Iterator<Item = &[T]> + '_ {
+        (0..self.num_stored_vectors)
+            .map(|index| self.raw_vector_offset(index * self.raw_size() + HEADER_SIZE))
     }
 
     pub fn raw_size(&self) -> usize {
@@ -0,0 +1,133 @@
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fs::File;
+use std::hash::{BuildHasher, Hash, Hasher};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use common::types::PointOffsetType;
+use memory::mmap_ops;
+
+use crate::common::operation_error::OperationResult;
+use crate::data_types::primitive::PrimitiveVectorElement;
+
+/// Which vectors are considered duplicates and stored only once
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VectorDeduplication {
+    /// Only bitwise equal vectors, does not affect search results
+    Exact,
+    /// Vectors whose components all differ by at most the given value.
+    ///
+    /// Merged vectors are replaced by the first stored one, which changes their scores.
+    /// Vectors are bucketed by a grid of this size, so close vectors in neighboring cells are not
+    /// merged.
+    Epsilon(f32),
+}
+
+/// Finds vectors which were already written into a vector file, while it is being appended to.
+///
+/// Only keeps hashes of stored vectors in memory, candidates are read back from the file to
+/// compare them.
+pub(super) struct VectorDeduplicator<T: PrimitiveVectorElement> {
+    deduplication: VectorDeduplication,
+    hasher: ahash::RandomState,
+    /// Indices of stored vectors by hash of their deduplication key
+    stored: HashMap<u64, Vec<PointOffsetType>>,
+    vectors_file: File,
+    header_size: usize,
+    buffer: Vec<T>,
+}
+
+impl<T: PrimitiveVectorElement> VectorDeduplicator<T> {
+    pub fn new(
+        deduplication: VectorDeduplication,
+        vectors_path: &Path,
+        header_size: usize,
+        dim: usize,
+    ) -> OperationResult<Self> {
+        Ok(Self {
+            deduplication,
+            hasher: ahash::RandomState::with_seeds(0, 0, 0, 0),
+            stored: HashMap::new(),
+            vectors_file: File::open(vectors_path)?,
+            header_size,
+            buffer: vec![T::default(); dim],
+        })
+    }
+
+    /// Register a vector stored at the given index
+    pub fn insert(&mut self, vector: &[T], index: PointOffsetType) {
+        let hash = self.hash(vector);
+        self.stored.entry(hash).or_default().push(index);
+    }
+
+    /// Find a stored duplicate of the vector, or register it under `index` if there is none.
+    ///
+    /// The vector must be written at `index` before the next call.
+    pub fn get_or_insert(
+        &mut self,
+        vector: &[T],
+        index: PointOffsetType,
+    ) -> OperationResult<Option<PointOffsetType>> {
+        let hash = self.hash(vector);
+        if let Some(candidates) = self.stored.get(&hash) {
+            for &candidate in candidates {
+                read_vector(
+                    &mut self.vectors_file,
+                    self.header_size,
+                    candidate,
+                    &mut self.buffer,
+                )?;
+                if self.is_duplicate(vector, &self.buffer) {
+                    return Ok(Some(candidate));
+                }
+            }
+        }
+        self.stored.entry(hash).or_default().push(index);
+        Ok(None)
+    }
+
+    fn hash(&self, vector: &[T]) -> u64 {
+        match self.deduplication {
+            VectorDeduplication::Exact => self
+                .hasher
+                .hash_one(mmap_ops::transmute_to_u8_slice(vector)),
+            VectorDeduplication::Epsilon(epsilon) => {
+                let mut hasher = self.hasher.build_hasher();
+                for value in T::slice_to_float_cow(Cow::Borrowed(vector)).iter() {
+                    ((value / epsilon).floor() as i64).hash(&mut hasher);
+                }
+                hasher.finish()
+            }
+        }
+    }
+
+    fn is_duplicate(&self, vector: &[T], stored: &[T]) -> bool {
+        match self.deduplication {
+            VectorDeduplication::Exact => {
+                mmap_ops::transmute_to_u8_slice(vector) == mmap_ops::transmute_to_u8_slice(stored)
+            }
+            VectorDeduplication::Epsilon(epsilon) => {
+                let vector = T::slice_to_float_cow(Cow::Borrowed(vector));
+                let stored = T::slice_to_float_cow(Cow::Borrowed(stored));
+                vector
+                    .iter()
+                    .zip(stored.iter())
+                    .all(|(a, b)| (a - b).abs() <= epsilon)
+            }
+        }
+    }
+}
+
+fn read_vector<T>(
+    file: &mut File,
+    header_size: usize,
+    index: PointOffsetType,
+    buffer: &mut [T],
+) -> OperationResult<()> {
+    let raw = mmap_ops::transmute_to_u8_slice_mut(buffer);
+    let offset = header_size + index as usize * raw.len();
+    file.seek(SeekFrom::Start(offset as u64))?;
+    file.read_exact(raw)?;
+    Ok(())
+}
@@ -101,6 +101,25 @@ pub fn open_simple_multi_dense_vector_storage_byte(
     Ok(VectorStorageEnum::MultiDenseSimpleByte(storage))
 }
 
+pub fn open_simple_multi_dense_vector_storage_int8(
+    database: Arc<RwLock<DB>>,
+    database_column_name: &str,
+    dim: usize,
+    distance: Distance,
+    multi_vector_config: MultiVectorConfig,
+    stopped: &AtomicBool,
+) -> OperationResult<VectorStorageEnum> {
+    let storage = open_simple_multi_dense_vector_storage_impl(
+        database,
+        database_column_name,
+        dim,
+        distance,
+        multi_vector_config,
+        stopped,
+    )?;
+    Ok(VectorStorageEnum::MultiDenseSimpleInt8(storage))
+}
+
 pub fn open_simple_multi_dense_vector_storage_half(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
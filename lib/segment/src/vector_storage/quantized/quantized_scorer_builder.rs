@@ -10,7 +10,7 @@ use crate::common::operation_error::OperationResult;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    VectorElementTypeHalf, VectorElementTypeInt8,
 };
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
@@ -73,6 +73,16 @@ impl<'a> QuantizedScorerBuilder<'a> {
                     self.build_with_metric::<VectorElementTypeByte, ManhattanMetric>()
                 }
             },
+            VectorStorageDatatype::Int8 => match self.distance {
+                Distance::Cosine => self.build_with_metric::<VectorElementTypeInt8, CosineMetric>(),
+                Distance::Euclid => self.build_with_metric::<VectorElementTypeInt8, EuclidMetric>(),
+                Distance::Dot => {
+                    self.build_with_metric::<VectorElementTypeInt8, DotProductMetric>()
+                }
+                Distance::Manhattan => {
+                    self.build_with_metric::<VectorElementTypeInt8, ManhattanMetric>()
+                }
+            },
             VectorStorageDatatype::Float16 => match self.distance {
                 Distance::Cosine => self.build_with_metric::<VectorElementTypeHalf, CosineMetric>(),
                 Distance::Euclid => self.build_with_metric::<VectorElementTypeHalf, EuclidMetric>(),
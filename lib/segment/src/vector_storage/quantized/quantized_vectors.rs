@@ -1,10 +1,13 @@
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
 use bitvec::slice::BitSlice;
 use common::types::PointOffsetType;
 use io::file_operations::{atomic_save_json, read_json};
+use memory::madvise::AdviceSetting;
+use memory::mmap_ops;
 use quantization::encoded_vectors_binary::{EncodedBinVector, EncodedVectorsBin};
 use quantization::{
     EncodedQueryPQ, EncodedQueryU8, EncodedVectors, EncodedVectorsPQ, EncodedVectorsU8,
@@ -143,6 +146,42 @@ impl QuantizedVectors {
         }
     }
 
+    pub fn is_on_disk(&self) -> bool {
+        match self.storage_impl {
+            QuantizedVectorStorage::ScalarRam(_) => false,
+            QuantizedVectorStorage::ScalarMmap(_) => true,
+            QuantizedVectorStorage::PQRam(_) => false,
+            QuantizedVectorStorage::PQMmap(_) => true,
+            QuantizedVectorStorage::BinaryRam(_) => false,
+            QuantizedVectorStorage::BinaryMmap(_) => true,
+            QuantizedVectorStorage::ScalarRamMulti(_) => false,
+            QuantizedVectorStorage::ScalarMmapMulti(_) => true,
+            QuantizedVectorStorage::PQRamMulti(_) => false,
+            QuantizedVectorStorage::PQMmapMulti(_) => true,
+            QuantizedVectorStorage::BinaryRamMulti(_) => false,
+            QuantizedVectorStorage::BinaryMmapMulti(_) => true,
+        }
+    }
+
+    /// Task to read on-disk quantized data into the page cache.
+    ///
+    /// Quantized storage owns its mmap, so the data file is mapped once more just for reading.
+    /// Page cache is shared between mappings of the same file.
+    /// Returns `None` if quantized vectors are stored in RAM.
+    pub fn prefault_mmap_pages(&self) -> OperationResult<Option<mmap_ops::PrefaultMmapPages>> {
+        if !self.is_on_disk() {
+            return Ok(None);
+        }
+
+        let data_path = self.path.join(QUANTIZED_DATA_PATH);
+        let mmap = mmap_ops::open_read_mmap(&data_path, AdviceSetting::Global, false)?;
+
+        Ok(Some(mmap_ops::PrefaultMmapPages::new(
+            Arc::new(mmap),
+            Some(data_path),
+        )))
+    }
+
     pub fn raw_scorer<'a>(
         &'a self,
         query: QueryVector,
@@ -225,6 +264,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseSimpleByte(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseSimpleInt8(v) => {
+                Self::create_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseSimpleHalf(v) => {
                 Self::create_impl(v, quantization_config, path, max_threads, stopped)
             }
@@ -234,6 +276,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseMemmapByte(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseMemmapInt8(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -243,6 +288,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseAppendableMemmapByte(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -252,6 +300,9 @@ impl QuantizedVectors {
             VectorStorageEnum::DenseAppendableInRamByte(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => {
+                Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::DenseAppendableInRamHalf(v) => {
                 Self::create_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -262,6 +313,9 @@ impl QuantizedVectors {
             VectorStorageEnum::MultiDenseSimpleByte(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => {
+                Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::MultiDenseSimpleHalf(v) => {
                 Self::create_multi_impl(v, quantization_config, path, max_threads, stopped)
             }
@@ -271,6 +325,9 @@ impl QuantizedVectors {
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => {
+                Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -280,6 +337,9 @@ impl QuantizedVectors {
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => {
+                Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => {
                 Self::create_multi_impl(v.as_ref(), quantization_config, path, max_threads, stopped)
             }
@@ -901,7 +961,7 @@ impl QuantizedVectors {
         }
     }
 
-    fn is_ram(always_ram: Option<bool>, on_disk_vector_storage: bool) -> bool {
+    pub(crate) fn is_ram(always_ram: Option<bool>, on_disk_vector_storage: bool) -> bool {
         !on_disk_vector_storage || always_ram == Some(true)
     }
 
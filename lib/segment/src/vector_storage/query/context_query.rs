@@ -8,10 +8,15 @@ use super::{Query, TransformInto};
 use crate::common::operation_error::OperationResult;
 use crate::data_types::vectors::{QueryVector, Vector};
 
+/// Weight of a context pair, if not specified otherwise
+pub const DEFAULT_CONTEXT_WEIGHT: u32 = 1;
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct ContextPair<T> {
     pub positive: T,
     pub negative: T,
+    /// How many times this pair counts in the score, compared to a pair with the default weight
+    pub weight: u32,
 }
 
 impl<T> ContextPair<T> {
@@ -26,6 +31,7 @@ impl<T> ContextPair<T> {
         Ok(ContextPair {
             positive: f(self.positive)?,
             negative: f(self.negative)?,
+            weight: self.weight,
         })
     }
 
@@ -48,6 +54,8 @@ impl<T> ContextPair<T> {
     /// ```
     /// Simple 2D model:
     /// <https://www.desmos.com/calculator/lbxycyh2hs>
+    ///
+    /// The loss is multiplied by the weight of the pair.
     pub fn loss_by(&self, similarity: impl Fn(&T) -> ScoreType) -> ScoreType {
         const MARGIN: ScoreType = ScoreType::EPSILON;
 
@@ -56,7 +64,7 @@ impl<T> ContextPair<T> {
 
         let difference = positive - negative - MARGIN;
 
-        fast_sigmoid(ScoreType::min(difference, 0.0))
+        fast_sigmoid(ScoreType::min(difference, 0.0)) * self.weight as ScoreType
     }
 }
 
@@ -76,6 +84,7 @@ impl<T> From<(T, T)> for ContextPair<T> {
         Self {
             positive: pair.0,
             negative: pair.1,
+            weight: DEFAULT_CONTEXT_WEIGHT,
         }
     }
 }
@@ -158,5 +167,16 @@ mod test {
             assert!(score <= 0.0, "similarity: {score}");
             assert!(score > -1.0, "similarity: {score}");
         }
+
+        /// Checks that a weighted pair counts as many times as its weight
+        #[test]
+        fn loss_is_multiplied_by_weight((p, n) in (sim(), sim()), weight in 1..10u32) {
+            let pair = ContextPair::from((p, n));
+            let weighted_pair = ContextPair { weight, ..pair.clone() };
+
+            let score = ContextQuery::new(vec![pair; weight as usize]).score_by(dummy_similarity);
+            let weighted_score = ContextQuery::new(vec![weighted_pair]).score_by(dummy_similarity);
+            assert!((score - weighted_score).abs() <= 1.0e-5, "{score} != {weighted_score}");
+        }
     }
 }
@@ -12,13 +12,14 @@ use crate::data_types::vectors::{QueryVector, Vector};
 type RankType = i32;
 
 impl<T> ContextPair<T> {
-    /// Calculates on which side of the space the point is, with respect to this pair
+    /// Calculates on which side of the space the point is, with respect to this pair,
+    /// multiplied by the weight of the pair
     fn rank_by(&self, similarity: impl Fn(&T) -> ScoreType) -> RankType {
         let positive_similarity = similarity(&self.positive);
         let negative_similarity = similarity(&self.negative);
 
         // if closer to positive, return 1, else -1
-        positive_similarity.total_cmp(&negative_similarity) as RankType
+        positive_similarity.total_cmp(&negative_similarity) as RankType * self.weight as RankType
     }
 }
 
@@ -121,6 +122,38 @@ mod test {
         );
     }
 
+    /// Pairs which are weighted count as many times as their weight, so that conflicting pairs
+    /// cancel out only if their weights are equal
+    #[rstest]
+    #[case::weighted_positive(vec![(10, 4, 3)], 3)]
+    #[case::weighted_negative(vec![(4, 10, 3)], -3)]
+    #[case::equal_scores(vec![(11, 11, 5)], 0)]
+    #[case::stronger_positive(vec![(10, 4, 3), (4, 10, 1)], 2)]
+    #[case::stronger_negative(vec![(10, 4, 1), (4, 10, 2)], -1)]
+    #[case::balanced_conflict(vec![(10, 4, 2), (4, 10, 1), (2, 8, 1)], 0)]
+    fn weighted_context_ranking(
+        #[case] pairs: Vec<(isize, isize, u32)>,
+        #[case] expected: RankType,
+    ) {
+        let pairs = pairs
+            .into_iter()
+            .map(|(positive, negative, weight)| ContextPair {
+                positive,
+                negative,
+                weight,
+            })
+            .collect();
+
+        let query = DiscoveryQuery::new(42, pairs);
+
+        let rank = query.rank_by(dummy_similarity);
+
+        assert_eq!(
+            rank, expected,
+            "Ranking is incorrect, expected {expected}, but got {rank}"
+        );
+    }
+
     /// Compares the score of a query against a fixed score
     #[rstest]
     #[case::no_pairs(1, vec![], Ordering::Less)]
@@ -7,7 +7,7 @@ mod context_query;
 mod discovery_query;
 mod reco_query;
 
-pub use context_query::{ContextPair, ContextQuery};
+pub use context_query::{ContextPair, ContextQuery, DEFAULT_CONTEXT_WEIGHT};
 pub use discovery_query::DiscoveryQuery;
 pub use reco_query::RecoQuery;
 
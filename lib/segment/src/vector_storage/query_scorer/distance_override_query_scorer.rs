@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+use common::types::{PointOffsetType, ScoreType};
+
+use crate::data_types::named_vectors::CowVector;
+use crate::data_types::vectors::{DenseVector, VectorElementType};
+use crate::spaces::metric::Metric;
+use crate::types::Distance;
+use crate::vector_storage::query_scorer::QueryScorer;
+use crate::vector_storage::{VectorStorage, VectorStorageEnum};
+
+/// Scores dense vectors with another metric than the distance they are stored with.
+///
+/// Stored vectors are read as float vectors, whatever their datatype. Only vectors stored with
+/// cosine distance are normalized, so other vectors are normalized when scored with cosine.
+pub struct DistanceOverrideQueryScorer<'a, TMetric: Metric<VectorElementType>> {
+    vector_storage: &'a VectorStorageEnum,
+    query: DenseVector,
+    normalize_stored: bool,
+    metric: PhantomData<TMetric>,
+}
+
+impl<'a, TMetric: Metric<VectorElementType>> DistanceOverrideQueryScorer<'a, TMetric> {
+    pub fn new(query: DenseVector, vector_storage: &'a VectorStorageEnum) -> Self {
+        let normalize_stored = TMetric::distance() == Distance::Cosine
+            && vector_storage.distance() != Distance::Cosine;
+        Self {
+            vector_storage,
+            query: TMetric::preprocess(query),
+            normalize_stored,
+            metric: PhantomData,
+        }
+    }
+
+    fn stored_vector(&self, idx: PointOffsetType) -> Cow<'a, [VectorElementType]> {
+        let vector = match self.vector_storage.get_vector(idx) {
+            CowVector::Dense(vector) => vector,
+            CowVector::Sparse(_) | CowVector::MultiDense(_) => {
+                debug_assert!(false, "distance override is only used for dense vectors");
+                Cow::Owned(vec![0.0; self.query.len()])
+            }
+        };
+        if self.normalize_stored {
+            Cow::Owned(TMetric::preprocess(vector.into_owned()))
+        } else {
+            vector
+        }
+    }
+}
+
+impl<'a, TMetric: Metric<VectorElementType>> QueryScorer<[VectorElementType]>
+    for DistanceOverrideQueryScorer<'a, TMetric>
+{
+    #[inline]
+    fn score_stored(&self, idx: PointOffsetType) -> ScoreType {
+        TMetric::similarity(&self.query, &self.stored_vector(idx))
+    }
+
+    #[inline]
+    fn score(&self, v2: &[VectorElementType]) -> ScoreType {
+        TMetric::similarity(&self.query, v2)
+    }
+
+    fn score_internal(&self, point_a: PointOffsetType, point_b: PointOffsetType) -> ScoreType {
+        TMetric::similarity(&self.stored_vector(point_a), &self.stored_vector(point_b))
+    }
+}
@@ -1,6 +1,7 @@
 use common::types::{PointOffsetType, ScoreType};
 use sparse::common::sparse_vector::SparseVector;
 
+use crate::types::SparseDistance;
 use crate::vector_storage::query::{Query, TransformInto};
 use crate::vector_storage::query_scorer::QueryScorer;
 use crate::vector_storage::SparseVectorStorage;
@@ -12,6 +13,7 @@ pub struct SparseCustomQueryScorer<
 > {
     vector_storage: &'a TVectorStorage,
     query: TQuery,
+    distance: SparseDistance,
 }
 
 impl<
@@ -30,6 +32,7 @@ impl<
         Self {
             vector_storage,
             query,
+            distance: vector_storage.sparse_distance(),
         }
     }
 }
@@ -44,12 +47,12 @@ impl<'a, TVectorStorage: SparseVectorStorage, TQuery: Query<SparseVector>> Query
             .get_sparse(idx)
             .expect("Failed to get sparse vector");
         self.query
-            .score_by(|example| stored.score(example).unwrap_or(0.0))
+            .score_by(|example| self.distance.score(&stored, example))
     }
 
     fn score(&self, v: &SparseVector) -> ScoreType {
         self.query
-            .score_by(|example| example.score(v).unwrap_or(0.0))
+            .score_by(|example| self.distance.score(example, v))
     }
 
     fn score_internal(&self, _point_a: PointOffsetType, _point_b: PointOffsetType) -> ScoreType {
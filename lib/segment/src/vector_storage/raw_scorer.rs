@@ -6,13 +6,16 @@ use sparse::common::sparse_vector::SparseVector;
 
 use super::query::{ContextQuery, DiscoveryQuery, RecoQuery, TransformInto};
 use super::query_scorer::custom_query_scorer::CustomQueryScorer;
+use super::query_scorer::distance_override_query_scorer::DistanceOverrideQueryScorer;
 use super::query_scorer::multi_custom_query_scorer::MultiCustomQueryScorer;
 use super::query_scorer::sparse_custom_query_scorer::SparseCustomQueryScorer;
-use super::{DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorageEnum};
+use super::{
+    DenseVectorStorage, MultiVectorStorage, SparseVectorStorage, VectorStorage, VectorStorageEnum,
+};
 use crate::common::operation_error::{OperationError, OperationResult};
 use crate::data_types::vectors::{
-    DenseVector, MultiDenseVectorInternal, QueryVector, VectorElementType, VectorElementTypeByte,
-    VectorElementTypeHalf,
+    DenseVector, MultiDenseVectorInternal, QueryVector, Vector, VectorElementType,
+    VectorElementTypeByte, VectorElementTypeHalf, VectorElementTypeInt8,
 };
 use crate::spaces::metric::Metric;
 use crate::spaces::simple::{CosineMetric, DotProductMetric, EuclidMetric, ManhattanMetric};
@@ -114,6 +117,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseSimpleByte(vs) => {
             raw_scorer_byte_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseSimpleInt8(vs) => {
+            raw_scorer_int8_impl(query, vs, point_deleted, is_stopped)
+        }
         VectorStorageEnum::DenseSimpleHalf(vs) => {
             raw_scorer_half_impl(query, vs, point_deleted, is_stopped)
         }
@@ -141,6 +147,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseMemmapByte(vs) => {
             raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseMemmapInt8(vs) => {
+            raw_scorer_int8_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::DenseMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -151,6 +160,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseAppendableMemmapByte(vs) => {
             raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseAppendableMemmapInt8(vs) => {
+            raw_scorer_int8_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::DenseAppendableMemmapHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -160,6 +172,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::DenseAppendableInRamByte(vs) => {
             raw_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::DenseAppendableInRamInt8(vs) => {
+            raw_scorer_int8_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::DenseAppendableInRamHalf(vs) => {
             raw_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -172,6 +187,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseSimpleByte(vs) => {
             raw_multi_scorer_byte_impl(query, vs, point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseSimpleInt8(vs) => {
+            raw_multi_scorer_int8_impl(query, vs, point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseSimpleHalf(vs) => {
             raw_multi_scorer_half_impl(query, vs, point_deleted, is_stopped)
         }
@@ -181,6 +199,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseAppendableMemmapByte(vs) => {
             raw_multi_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseAppendableMemmapInt8(vs) => {
+            raw_multi_scorer_int8_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseAppendableMemmapHalf(vs) => {
             raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -190,6 +211,9 @@ pub fn new_stoppable_raw_scorer<'a>(
         VectorStorageEnum::MultiDenseAppendableInRamByte(vs) => {
             raw_multi_scorer_byte_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
+        VectorStorageEnum::MultiDenseAppendableInRamInt8(vs) => {
+            raw_multi_scorer_int8_impl(query, vs.as_ref(), point_deleted, is_stopped)
+        }
         VectorStorageEnum::MultiDenseAppendableInRamHalf(vs) => {
             raw_multi_scorer_half_impl(query, vs.as_ref(), point_deleted, is_stopped)
         }
@@ -247,6 +271,52 @@ pub fn new_raw_scorer<'a>(
     new_stoppable_raw_scorer(vector, vector_storage, point_deleted, &DEFAULT_STOPPED)
 }
 
+/// Raw scorer, which scores dense vectors with `distance` instead of the distance of the storage.
+///
+/// Scores original vectors one by one, so it is only meant for exact search.
+pub fn new_raw_scorer_with_distance<'a>(
+    query: QueryVector,
+    distance: Distance,
+    vector_storage: &'a VectorStorageEnum,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let QueryVector::Nearest(Vector::Dense(vector)) = query else {
+        return Err(OperationError::ValidationError {
+            description: "Distance can only be overridden for nearest search by dense vectors"
+                .to_string(),
+        });
+    };
+
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match distance {
+        Distance::Cosine => raw_scorer_from_query_scorer(
+            DistanceOverrideQueryScorer::<CosineMetric>::new(vector, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => raw_scorer_from_query_scorer(
+            DistanceOverrideQueryScorer::<EuclidMetric>::new(vector, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => raw_scorer_from_query_scorer(
+            DistanceOverrideQueryScorer::<DotProductMetric>::new(vector, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => raw_scorer_from_query_scorer(
+            DistanceOverrideQueryScorer::<ManhattanMetric>::new(vector, vector_storage),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+    }
+}
+
 pub fn raw_scorer_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementType>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -375,6 +445,40 @@ pub fn raw_scorer_byte_impl<'a, TVectorStorage: DenseVectorStorage<VectorElement
     }
 }
 
+pub fn raw_scorer_int8_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeInt8>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_scorer_int8_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_scorer_int8_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_scorer_int8_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_scorer_int8_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
 fn new_scorer_byte_with_metric<
     'a,
     TMetric: Metric<VectorElementTypeByte> + 'a,
@@ -435,6 +539,66 @@ fn new_scorer_byte_with_metric<
     }
 }
 
+fn new_scorer_int8_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeInt8> + 'a,
+    TVectorStorage: DenseVectorStorage<VectorElementTypeInt8>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MetricQueryScorer::<VectorElementTypeInt8, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<DenseVector> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<DenseVector> = discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<DenseVector> = context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                CustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_scorer_half_impl<'a, TVectorStorage: DenseVectorStorage<VectorElementTypeHalf>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -678,6 +842,40 @@ pub fn raw_multi_scorer_byte_impl<'a, TVectorStorage: MultiVectorStorage<VectorE
     }
 }
 
+pub fn raw_multi_scorer_int8_impl<'a, TVectorStorage: MultiVectorStorage<VectorElementTypeInt8>>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    match vector_storage.distance() {
+        Distance::Cosine => new_multi_scorer_int8_with_metric::<CosineMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Euclid => new_multi_scorer_int8_with_metric::<EuclidMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Dot => new_multi_scorer_int8_with_metric::<DotProductMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+        Distance::Manhattan => new_multi_scorer_int8_with_metric::<ManhattanMetric, _>(
+            query,
+            vector_storage,
+            point_deleted,
+            is_stopped,
+        ),
+    }
+}
+
 fn new_multi_scorer_byte_with_metric<
     'a,
     TMetric: Metric<VectorElementTypeByte> + 'a,
@@ -740,6 +938,68 @@ fn new_multi_scorer_byte_with_metric<
     }
 }
 
+fn new_multi_scorer_int8_with_metric<
+    'a,
+    TMetric: Metric<VectorElementTypeInt8> + 'a,
+    TVectorStorage: MultiVectorStorage<VectorElementTypeInt8>,
+>(
+    query: QueryVector,
+    vector_storage: &'a TVectorStorage,
+    point_deleted: &'a BitSlice,
+    is_stopped: &'a AtomicBool,
+) -> OperationResult<Box<dyn RawScorer + 'a>> {
+    let vec_deleted = vector_storage.deleted_vector_bitslice();
+    match query {
+        QueryVector::Nearest(vector) => raw_scorer_from_query_scorer(
+            MultiMetricQueryScorer::<VectorElementTypeInt8, TMetric, _>::new(
+                vector.try_into()?,
+                vector_storage,
+            ),
+            point_deleted,
+            vec_deleted,
+            is_stopped,
+        ),
+        QueryVector::Recommend(reco_query) => {
+            let reco_query: RecoQuery<MultiDenseVectorInternal> = reco_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    reco_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Discovery(discovery_query) => {
+            let discovery_query: DiscoveryQuery<MultiDenseVectorInternal> =
+                discovery_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    discovery_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+        QueryVector::Context(context_query) => {
+            let context_query: ContextQuery<MultiDenseVectorInternal> =
+                context_query.transform_into()?;
+            raw_scorer_from_query_scorer(
+                MultiCustomQueryScorer::<VectorElementTypeInt8, TMetric, _, _, _>::new(
+                    context_query,
+                    vector_storage,
+                ),
+                point_deleted,
+                vec_deleted,
+                is_stopped,
+            )
+        }
+    }
+}
+
 pub fn raw_multi_scorer_half_impl<'a, TVectorStorage: MultiVectorStorage<VectorElementTypeHalf>>(
     query: QueryVector,
     vector_storage: &'a TVectorStorage,
@@ -3,7 +3,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
 use bitvec::prelude::{BitSlice, BitVec};
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use parking_lot::RwLock;
 use rocksdb::DB;
 use sparse::common::sparse_vector::SparseVector;
@@ -14,7 +14,7 @@ use crate::common::rocksdb_wrapper::DatabaseColumnWrapper;
 use crate::common::Flusher;
 use crate::data_types::named_vectors::CowVector;
 use crate::data_types::vectors::VectorRef;
-use crate::types::{Distance, VectorStorageDatatype};
+use crate::types::{Distance, SparseDistance, VectorStorageDatatype};
 use crate::vector_storage::bitvec::bitvec_set_deleted;
 use crate::vector_storage::common::StoredRecord;
 use crate::vector_storage::{VectorStorage, VectorStorageEnum};
@@ -34,11 +34,15 @@ pub struct SimpleSparseVectorStorage {
     total_vector_count: usize,
     /// Total number of non-zero elements in all vectors. Used to estimate average vector size.
     total_sparse_size: usize,
+    sparse_distance: SparseDistance,
+    /// Squared norms of the vectors, only kept if the distance requires them
+    squared_norms: Vec<ScoreType>,
 }
 
 pub fn open_simple_sparse_vector_storage(
     database: Arc<RwLock<DB>>,
     database_column_name: &str,
+    sparse_distance: SparseDistance,
     stopped: &AtomicBool,
 ) -> OperationResult<VectorStorageEnum> {
     let (mut deleted, mut deleted_count) = (BitVec::new(), 0);
@@ -46,6 +50,7 @@ pub fn open_simple_sparse_vector_storage(
 
     let mut total_vector_count = 0;
     let mut total_sparse_size = 0;
+    let mut squared_norms = Vec::new();
     db_wrapper.lock_db().iter()?;
     for (key, value) in db_wrapper.lock_db().iter()? {
         let point_id: PointOffsetType = bincode::deserialize(&key)
@@ -60,6 +65,9 @@ pub fn open_simple_sparse_vector_storage(
         }
         total_vector_count = std::cmp::max(total_vector_count, point_id as usize + 1);
         total_sparse_size += stored_record.vector.values.len();
+        if sparse_distance.requires_norms() {
+            set_squared_norm(&mut squared_norms, point_id, &stored_record.vector);
+        }
 
         check_process_stopped(stopped)?;
     }
@@ -70,9 +78,23 @@ pub fn open_simple_sparse_vector_storage(
         deleted_count,
         total_vector_count,
         total_sparse_size,
+        sparse_distance,
+        squared_norms,
     }))
 }
 
+fn set_squared_norm(
+    squared_norms: &mut Vec<ScoreType>,
+    key: PointOffsetType,
+    vector: &SparseVector,
+) {
+    let key = key as usize;
+    if key >= squared_norms.len() {
+        squared_norms.resize(key + 1, 0.0);
+    }
+    squared_norms[key] = vector.squared_norm();
+}
+
 impl SimpleSparseVectorStorage {
     /// Set deleted flag for given key. Returns previous deleted state.
     #[inline]
@@ -108,6 +130,9 @@ impl SimpleSparseVectorStorage {
             } else {
                 self.total_sparse_size += vector.values.len();
             }
+            if self.sparse_distance.requires_norms() {
+                set_squared_norm(&mut self.squared_norms, key, vector);
+            }
         }
 
         // Store updated record
@@ -130,6 +155,18 @@ impl SparseVectorStorage for SimpleSparseVectorStorage {
         })?;
         Ok(record.vector)
     }
+
+    fn sparse_distance(&self) -> SparseDistance {
+        self.sparse_distance
+    }
+
+    fn squared_norm(&self, key: PointOffsetType) -> ScoreType {
+        debug_assert!(self.sparse_distance.requires_norms());
+        self.squared_norms
+            .get(key as usize)
+            .copied()
+            .unwrap_or_default()
+    }
 }
 
 impl VectorStorage for SimpleSparseVectorStorage {
@@ -85,7 +85,11 @@ fn random_discovery_query<R: Rng + ?Sized>(
         .map(|_| {
             let positive = sampler.take(DIMS).collect_vec().into();
             let negative = sampler.take(DIMS).collect_vec().into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -102,7 +106,11 @@ fn random_context_query<R: Rng + ?Sized>(
         .map(|_| {
             let positive = sampler.take(DIMS).collect_vec().into();
             let negative = sampler.take(DIMS).collect_vec().into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -10,6 +10,7 @@ use crate::common::rocksdb_wrapper::{open_db, DB_VECTOR_CF};
 use crate::data_types::vectors::QueryVector;
 use crate::fixtures::payload_context_fixture::FixtureIdTracker;
 use crate::id_tracker::IdTrackerSS;
+use crate::types::SparseDistance;
 use crate::vector_storage::query::RecoQuery;
 use crate::vector_storage::simple_sparse_vector_storage::open_simple_sparse_vector_storage;
 use crate::vector_storage::{new_raw_scorer, VectorStorage, VectorStorageEnum};
@@ -123,8 +124,13 @@ fn do_test_update_from_delete_points(storage: &mut VectorStorageEnum) {
     {
         let dir2 = Builder::new().prefix("db_dir").tempdir().unwrap();
         let db = open_db(dir2.path(), &[DB_VECTOR_CF]).unwrap();
-        let mut storage2 =
-            open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
+        let mut storage2 = open_simple_sparse_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            SparseDistance::Dot,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
         {
             points.iter().enumerate().for_each(|(i, vec)| {
                 storage2
@@ -190,14 +196,24 @@ fn test_delete_points_in_simple_sparse_vector_storage() {
 
     {
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
-        let mut storage =
-            open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
+        let mut storage = open_simple_sparse_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            SparseDistance::Dot,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
         do_test_delete_points(&mut storage);
         storage.flusher()().unwrap();
     }
     let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
-    let _storage =
-        open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
+    let _storage = open_simple_sparse_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        SparseDistance::Dot,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
 }
 
 #[test]
@@ -205,13 +221,23 @@ fn test_update_from_delete_points_simple_sparse_vector_storage() {
     let dir = Builder::new().prefix("storage_dir").tempdir().unwrap();
     {
         let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
-        let mut storage =
-            open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
+        let mut storage = open_simple_sparse_vector_storage(
+            db,
+            DB_VECTOR_CF,
+            SparseDistance::Dot,
+            &AtomicBool::new(false),
+        )
+        .unwrap();
         do_test_update_from_delete_points(&mut storage);
         storage.flusher()().unwrap();
     }
 
     let db = open_db(dir.path(), &[DB_VECTOR_CF]).unwrap();
-    let _storage =
-        open_simple_sparse_vector_storage(db, DB_VECTOR_CF, &AtomicBool::new(false)).unwrap();
+    let _storage = open_simple_sparse_vector_storage(
+        db,
+        DB_VECTOR_CF,
+        SparseDistance::Dot,
+        &AtomicBool::new(false),
+    )
+    .unwrap();
 }
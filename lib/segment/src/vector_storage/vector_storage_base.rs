@@ -3,7 +3,7 @@ use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 
 use bitvec::prelude::BitSlice;
-use common::types::PointOffsetType;
+use common::types::{PointOffsetType, ScoreType};
 use sparse::common::sparse_vector::SparseVector;
 
 use super::dense::memmap_dense_vector_storage::MemmapDenseVectorStorage;
@@ -18,11 +18,12 @@ use crate::data_types::named_vectors::CowVector;
 use crate::data_types::primitive::PrimitiveVectorElement;
 use crate::data_types::vectors::{
     MultiDenseVectorInternal, TypedMultiDenseVectorRef, Vector, VectorElementType,
-    VectorElementTypeByte, VectorElementTypeHalf, VectorRef,
+    VectorElementTypeByte, VectorElementTypeHalf, VectorElementTypeInt8, VectorRef,
 };
-use crate::types::{Distance, MultiVectorConfig, VectorStorageDatatype};
+use crate::types::{Distance, MultiVectorConfig, SparseDistance, VectorStorageDatatype};
 use crate::vector_storage::chunked_mmap_vectors::ChunkedMmapVectors;
 use crate::vector_storage::dense::appendable_dense_vector_storage::AppendableMmapDenseVectorStorage;
+use crate::vector_storage::dense::vector_deduplication::VectorDeduplication;
 use crate::vector_storage::in_ram_persisted_vectors::InRamPersistedVectors;
 use crate::vector_storage::simple_sparse_vector_storage::SimpleSparseVectorStorage;
 
@@ -111,6 +112,11 @@ pub trait DenseVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
 
 pub trait SparseVectorStorage: VectorStorage {
     fn get_sparse(&self, key: PointOffsetType) -> OperationResult<SparseVector>;
+
+    fn sparse_distance(&self) -> SparseDistance;
+
+    /// Squared norm of the stored vector, only available if the distance requires norms
+    fn squared_norm(&self, key: PointOffsetType) -> ScoreType;
 }
 
 pub trait MultiVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
@@ -125,9 +131,11 @@ pub trait MultiVectorStorage<T: PrimitiveVectorElement>: VectorStorage {
 pub enum VectorStorageEnum {
     DenseSimple(SimpleDenseVectorStorage<VectorElementType>),
     DenseSimpleByte(SimpleDenseVectorStorage<VectorElementTypeByte>),
+    DenseSimpleInt8(SimpleDenseVectorStorage<VectorElementTypeInt8>),
     DenseSimpleHalf(SimpleDenseVectorStorage<VectorElementTypeHalf>),
     DenseMemmap(Box<MemmapDenseVectorStorage<VectorElementType>>),
     DenseMemmapByte(Box<MemmapDenseVectorStorage<VectorElementTypeByte>>),
+    DenseMemmapInt8(Box<MemmapDenseVectorStorage<VectorElementTypeInt8>>),
     DenseMemmapHalf(Box<MemmapDenseVectorStorage<VectorElementTypeHalf>>),
     DenseAppendableMemmap(
         Box<
@@ -145,6 +153,14 @@ pub enum VectorStorageEnum {
             >,
         >,
     ),
+    DenseAppendableMemmapInt8(
+        Box<
+            AppendableMmapDenseVectorStorage<
+                VectorElementTypeInt8,
+                ChunkedMmapVectors<VectorElementTypeInt8>,
+            >,
+        >,
+    ),
     DenseAppendableMemmapHalf(
         Box<
             AppendableMmapDenseVectorStorage<
@@ -169,6 +185,14 @@ pub enum VectorStorageEnum {
             >,
         >,
     ),
+    DenseAppendableInRamInt8(
+        Box<
+            AppendableMmapDenseVectorStorage<
+                VectorElementTypeInt8,
+                InRamPersistedVectors<VectorElementTypeInt8>,
+            >,
+        >,
+    ),
     DenseAppendableInRamHalf(
         Box<
             AppendableMmapDenseVectorStorage<
@@ -180,6 +204,7 @@ pub enum VectorStorageEnum {
     SparseSimple(SimpleSparseVectorStorage),
     MultiDenseSimple(SimpleMultiDenseVectorStorage<VectorElementType>),
     MultiDenseSimpleByte(SimpleMultiDenseVectorStorage<VectorElementTypeByte>),
+    MultiDenseSimpleInt8(SimpleMultiDenseVectorStorage<VectorElementTypeInt8>),
     MultiDenseSimpleHalf(SimpleMultiDenseVectorStorage<VectorElementTypeHalf>),
     MultiDenseAppendableMemmap(
         Box<
@@ -199,6 +224,15 @@ pub enum VectorStorageEnum {
             >,
         >,
     ),
+    MultiDenseAppendableMemmapInt8(
+        Box<
+            AppendableMmapMultiDenseVectorStorage<
+                VectorElementTypeInt8,
+                ChunkedMmapVectors<VectorElementTypeInt8>,
+                ChunkedMmapVectors<MultivectorMmapOffset>,
+            >,
+        >,
+    ),
     MultiDenseAppendableMemmapHalf(
         Box<
             AppendableMmapMultiDenseVectorStorage<
@@ -226,6 +260,15 @@ pub enum VectorStorageEnum {
             >,
         >,
     ),
+    MultiDenseAppendableInRamInt8(
+        Box<
+            AppendableMmapMultiDenseVectorStorage<
+                VectorElementTypeInt8,
+                InRamPersistedVectors<VectorElementTypeInt8>,
+                InRamPersistedVectors<MultivectorMmapOffset>,
+            >,
+        >,
+    ),
     MultiDenseAppendableInRamHalf(
         Box<
             AppendableMmapMultiDenseVectorStorage<
@@ -242,41 +285,102 @@ impl VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(_) => None,
             VectorStorageEnum::DenseSimpleByte(_) => None,
+            VectorStorageEnum::DenseSimpleInt8(_) => None,
             VectorStorageEnum::DenseSimpleHalf(_) => None,
             VectorStorageEnum::DenseMemmap(_) => None,
             VectorStorageEnum::DenseMemmapByte(_) => None,
+            VectorStorageEnum::DenseMemmapInt8(_) => None,
             VectorStorageEnum::DenseMemmapHalf(_) => None,
             VectorStorageEnum::DenseAppendableMemmap(_) => None,
             VectorStorageEnum::DenseAppendableMemmapByte(_) => None,
+            VectorStorageEnum::DenseAppendableMemmapInt8(_) => None,
             VectorStorageEnum::DenseAppendableMemmapHalf(_) => None,
             VectorStorageEnum::DenseAppendableInRam(_) => None,
             VectorStorageEnum::DenseAppendableInRamByte(_) => None,
+            VectorStorageEnum::DenseAppendableInRamInt8(_) => None,
             VectorStorageEnum::DenseAppendableInRamHalf(_) => None,
             VectorStorageEnum::SparseSimple(_) => None,
             VectorStorageEnum::MultiDenseSimple(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseSimpleByte(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::MultiDenseSimpleInt8(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseSimpleHalf(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmap(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableInRam(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableInRamByte(s) => Some(s.multi_vector_config()),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(s) => Some(s.multi_vector_config()),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(s) => Some(s.multi_vector_config()),
         }
     }
 
+    /// Store duplicate vectors added with [`VectorStorage::update_from`] only once.
+    ///
+    /// Only supported by immutable dense storages, returns `false` if not supported.
+    pub fn set_deduplication(&mut self, deduplication: Option<VectorDeduplication>) -> bool {
+        match self {
+            VectorStorageEnum::DenseSimple(_) => false,
+            VectorStorageEnum::DenseSimpleByte(_) => false,
+            VectorStorageEnum::DenseSimpleInt8(_) => false,
+            VectorStorageEnum::DenseSimpleHalf(_) => false,
+            VectorStorageEnum::DenseMemmap(v) => {
+                v.set_deduplication(deduplication);
+                true
+            }
+            VectorStorageEnum::DenseMemmapByte(v) => {
+                v.set_deduplication(deduplication);
+                true
+            }
+            VectorStorageEnum::DenseMemmapInt8(v) => {
+                v.set_deduplication(deduplication);
+                true
+            }
+            VectorStorageEnum::DenseMemmapHalf(v) => {
+                v.set_deduplication(deduplication);
+                true
+            }
+            VectorStorageEnum::DenseAppendableMemmap(_) => false,
+            VectorStorageEnum::DenseAppendableMemmapByte(_) => false,
+            VectorStorageEnum::DenseAppendableMemmapInt8(_) => false,
+            VectorStorageEnum::DenseAppendableMemmapHalf(_) => false,
+            VectorStorageEnum::DenseAppendableInRam(_) => false,
+            VectorStorageEnum::DenseAppendableInRamByte(_) => false,
+            VectorStorageEnum::DenseAppendableInRamInt8(_) => false,
+            VectorStorageEnum::DenseAppendableInRamHalf(_) => false,
+            VectorStorageEnum::SparseSimple(_) => false,
+            VectorStorageEnum::MultiDenseSimple(_) => false,
+            VectorStorageEnum::MultiDenseSimpleByte(_) => false,
+            VectorStorageEnum::MultiDenseSimpleInt8(_) => false,
+            VectorStorageEnum::MultiDenseSimpleHalf(_) => false,
+            VectorStorageEnum::MultiDenseAppendableMemmap(_) => false,
+            VectorStorageEnum::MultiDenseAppendableMemmapByte(_) => false,
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(_) => false,
+            VectorStorageEnum::MultiDenseAppendableMemmapHalf(_) => false,
+            VectorStorageEnum::MultiDenseAppendableInRam(_) => false,
+            VectorStorageEnum::MultiDenseAppendableInRamByte(_) => false,
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(_) => false,
+            VectorStorageEnum::MultiDenseAppendableInRamHalf(_) => false,
+        }
+    }
+
     pub(crate) fn default_vector(&self) -> Vector {
         match self {
             VectorStorageEnum::DenseSimple(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseSimpleByte(v) => Vector::from(vec![1.0; v.vector_dim()]),
+            VectorStorageEnum::DenseSimpleInt8(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseSimpleHalf(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseMemmap(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseMemmapByte(v) => Vector::from(vec![1.0; v.vector_dim()]),
+            VectorStorageEnum::DenseMemmapInt8(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseMemmapHalf(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseAppendableMemmap(v) => Vector::from(vec![1.0; v.vector_dim()]),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => {
                 Vector::from(vec![1.0; v.vector_dim()])
             }
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => {
+                Vector::from(vec![1.0; v.vector_dim()])
+            }
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => {
                 Vector::from(vec![1.0; v.vector_dim()])
             }
@@ -284,6 +388,9 @@ impl VectorStorageEnum {
             VectorStorageEnum::DenseAppendableInRamByte(v) => {
                 Vector::from(vec![1.0; v.vector_dim()])
             }
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => {
+                Vector::from(vec![1.0; v.vector_dim()])
+            }
             VectorStorageEnum::DenseAppendableInRamHalf(v) => {
                 Vector::from(vec![1.0; v.vector_dim()])
             }
@@ -294,6 +401,9 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseSimpleByte(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => {
+                Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
+            }
             VectorStorageEnum::MultiDenseSimpleHalf(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
@@ -303,6 +413,9 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => {
+                Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
+            }
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
@@ -312,6 +425,9 @@ impl VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => {
+                Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
+            }
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => {
                 Vector::from(MultiDenseVectorInternal::placeholder(v.vector_dim()))
             }
@@ -324,25 +440,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.distance(),
             VectorStorageEnum::DenseSimpleByte(v) => v.distance(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.distance(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.distance(),
             VectorStorageEnum::DenseMemmap(v) => v.distance(),
             VectorStorageEnum::DenseMemmapByte(v) => v.distance(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.distance(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.distance(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.distance(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.distance(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.distance(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.distance(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.distance(),
             VectorStorageEnum::SparseSimple(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimple(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.distance(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.distance(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.distance(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.distance(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.distance(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.distance(),
         }
     }
@@ -351,25 +474,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.datatype(),
             VectorStorageEnum::DenseSimpleByte(v) => v.datatype(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.datatype(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.datatype(),
             VectorStorageEnum::DenseMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapByte(v) => v.datatype(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.datatype(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.datatype(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.datatype(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.datatype(),
             VectorStorageEnum::SparseSimple(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimple(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.datatype(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.datatype(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.datatype(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.datatype(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.datatype(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.datatype(),
         }
     }
@@ -378,25 +508,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::DenseSimpleByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.is_on_disk(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.is_on_disk(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.is_on_disk(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.is_on_disk(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.is_on_disk(),
             VectorStorageEnum::SparseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimple(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.is_on_disk(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.is_on_disk(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.is_on_disk(),
         }
     }
@@ -405,25 +542,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::DenseSimpleByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.total_vector_count(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.total_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.total_vector_count(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.total_vector_count(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.total_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimple(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.total_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.total_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.total_vector_count(),
         }
     }
@@ -432,25 +576,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseSimpleByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmapByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::SparseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimple(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.available_size_in_bytes(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.available_size_in_bytes(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.available_size_in_bytes(),
         }
     }
@@ -459,25 +610,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.get_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.get_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableInRam(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.get_vector(key),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.get_vector(key),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.get_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.get_vector(key),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.get_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.get_vector(key),
         }
     }
@@ -486,25 +644,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableInRam(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::SparseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.get_vector_opt(key),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.get_vector_opt(key),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.get_vector_opt(key),
         }
     }
@@ -513,25 +678,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseSimpleByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseSimpleHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseMemmapHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableInRam(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::SparseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimple(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.insert_vector(key, vector),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.insert_vector(key, vector),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.insert_vector(key, vector),
         }
     }
@@ -544,24 +716,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseSimpleByte(v) => v.update_from(other_ids, stopped),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseSimpleHalf(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseMemmap(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseMemmapByte(v) => v.update_from(other_ids, stopped),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseMemmapHalf(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.update_from(other_ids, stopped),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableInRam(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.update_from(other_ids, stopped),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::SparseSimple(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::MultiDenseSimple(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.update_from(other_ids, stopped),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.update_from(other_ids, stopped),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => {
                 v.update_from(other_ids, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => {
+                v.update_from(other_ids, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => {
                 v.update_from(other_ids, stopped)
             }
@@ -569,6 +749,9 @@ impl VectorStorage for VectorStorageEnum {
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => {
                 v.update_from(other_ids, stopped)
             }
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => {
+                v.update_from(other_ids, stopped)
+            }
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => {
                 v.update_from(other_ids, stopped)
             }
@@ -579,25 +762,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.flusher(),
             VectorStorageEnum::DenseSimpleByte(v) => v.flusher(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.flusher(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.flusher(),
             VectorStorageEnum::DenseMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.flusher(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.flusher(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.flusher(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.flusher(),
             VectorStorageEnum::SparseSimple(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimple(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.flusher(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.flusher(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.flusher(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.flusher(),
         }
     }
@@ -606,25 +796,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.files(),
             VectorStorageEnum::DenseSimpleByte(v) => v.files(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.files(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.files(),
             VectorStorageEnum::DenseMemmap(v) => v.files(),
             VectorStorageEnum::DenseMemmapByte(v) => v.files(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.files(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.files(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.files(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.files(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.files(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.files(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.files(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.files(),
             VectorStorageEnum::SparseSimple(v) => v.files(),
             VectorStorageEnum::MultiDenseSimple(v) => v.files(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.files(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.files(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.files(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.files(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.files(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.files(),
         }
     }
@@ -633,25 +830,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.delete_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.delete_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableInRam(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.delete_vector(key),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.delete_vector(key),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.delete_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.delete_vector(key),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.delete_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.delete_vector(key),
         }
     }
@@ -660,25 +864,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseSimpleByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseSimpleHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseMemmapHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableInRam(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::SparseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimple(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.is_deleted_vector(key),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.is_deleted_vector(key),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.is_deleted_vector(key),
         }
     }
@@ -687,25 +898,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.deleted_vector_count(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.deleted_vector_count(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.deleted_vector_count(),
         }
     }
@@ -714,25 +932,32 @@ impl VectorStorage for VectorStorageEnum {
         match self {
             VectorStorageEnum::DenseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseSimpleByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseSimpleInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseSimpleHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseMemmapInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseMemmapHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableMemmapInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableInRam(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableInRamByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::DenseAppendableInRamInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::DenseAppendableInRamHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::SparseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimple(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimpleByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseSimpleInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseSimpleHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmap(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseAppendableMemmapInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableMemmapHalf(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableInRam(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableInRamByte(v) => v.deleted_vector_bitslice(),
+            VectorStorageEnum::MultiDenseAppendableInRamInt8(v) => v.deleted_vector_bitslice(),
             VectorStorageEnum::MultiDenseAppendableInRamHalf(v) => v.deleted_vector_bitslice(),
         }
     }
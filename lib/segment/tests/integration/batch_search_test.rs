@@ -48,6 +48,7 @@ fn test_batch_and_single_request_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -141,11 +142,14 @@ fn test_batch_and_single_request_equivalency() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -162,6 +166,7 @@ fn test_batch_and_single_request_equivalency() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
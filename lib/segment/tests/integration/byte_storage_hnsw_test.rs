@@ -41,7 +41,11 @@ fn random_discovery_query<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> QueryVect
         .map(|_| {
             let positive = random_dense_byte_vector(rnd, dim).into();
             let negative = random_dense_byte_vector(rnd, dim).into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -134,6 +138,7 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let config_byte = SegmentConfig {
         vector_data: HashMap::from([(
@@ -150,6 +155,7 @@ fn test_byte_storage_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -202,11 +208,14 @@ fn test_byte_storage_hnsw(
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -224,6 +233,7 @@ fn test_byte_storage_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
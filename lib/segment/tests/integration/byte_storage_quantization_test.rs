@@ -13,7 +13,9 @@ use segment::data_types::vectors::{
     only_default_vector, DenseVector, QueryVector, DEFAULT_VECTOR_NAME,
 };
 use segment::entry::entry_point::SegmentEntry;
-use segment::fixtures::payload_fixtures::{random_dense_byte_vector, random_int_payload};
+use segment::fixtures::payload_fixtures::{
+    random_dense_byte_vector, random_dense_int8_vector, random_int_payload,
+};
 use segment::index::hnsw_index::graph_links::GraphLinksRam;
 use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
 use segment::index::hnsw_index::num_rayon_threads;
@@ -57,6 +59,7 @@ where
             vector
         }
         VectorStorageDatatype::Uint8 => random_dense_byte_vector(rnd_gen, dim),
+        VectorStorageDatatype::Int8 => random_dense_int8_vector(rnd_gen, dim),
     }
 }
 
@@ -73,7 +76,11 @@ fn random_discovery_query<R: Rng + ?Sized>(
         .map(|_| {
             let positive = random_vector(rnd, dim, data_type).into();
             let negative = random_vector(rnd, dim, data_type).into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -155,6 +162,15 @@ fn sames_count(a: &[Vec<ScoredPointOffset>], b: &[Vec<ScoredPointOffset>]) -> us
     64, // ef
     1., // min_acc out of 100
 )]
+#[case::nearest_binary_dot(
+    QueryVariant::Nearest,
+    VectorStorageDatatype::Int8,
+    QuantizationVariant::Binary,
+    Distance::Dot,
+    128, // dim
+    32, // ef
+    5., // min_acc out of 100
+)]
 #[case::nearest_binary_cosine(
     QueryVariant::Nearest,
     VectorStorageDatatype::Uint8,
@@ -200,6 +216,15 @@ fn sames_count(a: &[Vec<ScoredPointOffset>], b: &[Vec<ScoredPointOffset>]) -> us
     32, // ef
     80., // min_acc out of 100
 )]
+#[case::nearest_scalar_dot(
+    QueryVariant::Nearest,
+    VectorStorageDatatype::Int8,
+    QuantizationVariant::Scalar,
+    Distance::Dot,
+    32, // dim
+    32, // ef
+    80., // min_acc out of 100
+)]
 #[case::nearest_scalar_cosine(
     QueryVariant::Nearest,
     VectorStorageDatatype::Uint8,
@@ -258,6 +283,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -272,6 +298,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         assert!(
             matches!(raw_storage, &VectorStorageEnum::DenseSimpleByte(_))
                 | matches!(raw_storage, &VectorStorageEnum::DenseSimpleHalf(_))
+                | matches!(raw_storage, &VectorStorageEnum::DenseSimpleInt8(_))
         );
     }
 
@@ -329,11 +356,14 @@ fn test_byte_storage_binary_quantization_hnsw(
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -351,6 +381,7 @@ fn test_byte_storage_binary_quantization_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
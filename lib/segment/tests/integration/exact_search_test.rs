@@ -56,6 +56,7 @@ fn exact_search_test() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -81,11 +82,14 @@ fn exact_search_test() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     payload_index_ptr
@@ -141,6 +145,7 @@ fn exact_search_test() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -42,7 +42,11 @@ fn random_discovery_query<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> QueryVect
         .map(|_| {
             let positive = random_vector(rnd, dim).into();
             let negative = random_vector(rnd, dim).into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -118,6 +122,7 @@ fn _test_filterable_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -142,11 +147,14 @@ fn _test_filterable_hnsw(
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
@@ -201,6 +209,7 @@ fn _test_filterable_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -164,6 +164,7 @@ pub fn build_segment_3(path: &Path) -> Segment {
             ]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
@@ -253,9 +254,11 @@ pub fn build_segment_sparse_1(path: &Path) -> Segment {
                 "sparse".to_owned(),
                 SparseVectorDataConfig {
                     index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
+                    distance: None,
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
@@ -337,9 +340,11 @@ pub fn build_segment_sparse_2(path: &Path) -> Segment {
                 "sparse".to_owned(),
                 SparseVectorDataConfig {
                     index: SparseIndexConfig::new(None, SparseIndexType::MutableRam, None),
+                    distance: None,
                 },
             )]),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         },
         true,
     )
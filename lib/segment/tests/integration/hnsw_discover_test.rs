@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 
@@ -14,10 +14,10 @@ use segment::index::hnsw_index::hnsw::{HNSWIndex, HnswIndexOpenArgs};
 use segment::index::hnsw_index::num_rayon_threads;
 use segment::index::{PayloadIndex, VectorIndex};
 use segment::json_path::JsonPath;
-use segment::segment_constructor::build_segment;
+use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
 use segment::types::{
-    Condition, Distance, FieldCondition, Filter, HnswConfig, Indexes, Payload, PayloadSchemaType,
-    SearchParams, SegmentConfig, SeqNumberType, VectorDataConfig, VectorStorageType,
+    Condition, Distance, FieldCondition, Filter, HnswConfig, Payload, PayloadSchemaType,
+    SearchParams, SeqNumberType,
 };
 use segment::vector_storage::query::{ContextPair, DiscoveryQuery};
 use serde_json::json;
@@ -34,7 +34,11 @@ fn random_discovery_query<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> QueryVect
         .map(|_| {
             let positive = random_vector(rnd, dim).into();
             let negative = random_vector(rnd, dim).into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -66,24 +70,7 @@ fn hnsw_discover_precision() {
     let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
     let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
 
-    let config = SegmentConfig {
-        vector_data: HashMap::from([(
-            DEFAULT_VECTOR_NAME.to_owned(),
-            VectorDataConfig {
-                size: dim,
-                distance,
-                storage_type: VectorStorageType::Memory,
-                index: Indexes::Plain {},
-                quantization_config: None,
-                multivector_config: None,
-                datatype: None,
-            },
-        )]),
-        payload_storage_type: Default::default(),
-        sparse_vector_data: Default::default(),
-    };
-
-    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut segment = build_simple_segment(dir.path(), dim, distance).unwrap();
 
     for n in 0..num_vectors {
         let idx = n.into();
@@ -98,11 +85,14 @@ fn hnsw_discover_precision() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -119,6 +109,7 @@ fn hnsw_discover_precision() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -178,26 +169,9 @@ fn filtered_hnsw_discover_precision() {
     let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
     let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
 
-    let config = SegmentConfig {
-        vector_data: HashMap::from([(
-            DEFAULT_VECTOR_NAME.to_owned(),
-            VectorDataConfig {
-                size: dim,
-                distance,
-                storage_type: VectorStorageType::Memory,
-                index: Indexes::Plain {},
-                quantization_config: None,
-                multivector_config: None,
-                datatype: None,
-            },
-        )]),
-        payload_storage_type: Default::default(),
-        sparse_vector_data: Default::default(),
-    };
-
     let keyword_key = "keyword";
 
-    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+    let mut segment = build_simple_segment(dir.path(), dim, distance).unwrap();
     for n in 0..num_vectors {
         let idx = n.into();
         let vector = random_vector(&mut rnd, dim);
@@ -221,11 +195,14 @@ fn filtered_hnsw_discover_precision() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -242,6 +219,7 @@ fn filtered_hnsw_discover_precision() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -288,3 +266,109 @@ fn filtered_hnsw_discover_precision() {
         "hits: {discovery_hits} of {attempts}"
     ); // Not more than X% failures
 }
+
+/// With a restrictive filter, few points matching it are reachable in the graph. Discovery must
+/// still return as many points as requested, as long as enough points match the filter.
+#[test]
+fn restrictive_filtered_hnsw_discover_fill() {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 8;
+    let num_vectors: u64 = 5_000;
+    let top = 10;
+    let full_scan_threshold = 1; // KB, 32 vectors
+    let num_payload_values = 100;
+
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let hnsw_dir = Builder::new().prefix("hnsw_dir").tempdir().unwrap();
+
+    let keyword_key = "keyword";
+
+    let mut segment = build_simple_segment(dir.path(), dim, Distance::Cosine).unwrap();
+    for n in 0..num_vectors {
+        let idx = n.into();
+        let vector = random_vector(&mut rnd, dim);
+
+        let keyword_payload = get_random_keyword_of(num_payload_values, &mut rnd);
+        let payload: Payload = json!({keyword_key:keyword_payload,}).into();
+
+        segment
+            .upsert_point(n as SeqNumberType, idx, only_default_vector(&vector))
+            .unwrap();
+        segment
+            .set_full_payload(n as SeqNumberType, idx, &payload)
+            .unwrap();
+    }
+
+    let payload_index_ptr = segment.payload_index.clone();
+    payload_index_ptr
+        .borrow_mut()
+        .set_indexed(&JsonPath::new(keyword_key), PayloadSchemaType::Keyword)
+        .unwrap();
+
+    // No payload links, so points matching the filter are poorly connected in the graph
+    let hnsw_config = HnswConfig {
+        m: 8,
+        m0: None,
+        ef_construct: 32,
+        full_scan_threshold,
+        max_indexing_threads: 2,
+        on_disk: Some(false),
+        payload_m: Some(0),
+        level_multiplier: None,
+        deterministic_build: None,
+    };
+
+    let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
+    let permit = Arc::new(CpuPermit::dummy(permit_cpu_count as u32));
+
+    let vector_storage = &segment.vector_data[DEFAULT_VECTOR_NAME].vector_storage;
+    let quantized_vectors = &segment.vector_data[DEFAULT_VECTOR_NAME].quantized_vectors;
+    let hnsw_index = HNSWIndex::<GraphLinksRam>::open(HnswIndexOpenArgs {
+        path: hnsw_dir.path(),
+        id_tracker: segment.id_tracker.clone(),
+        vector_storage: vector_storage.clone(),
+        quantized_vectors: quantized_vectors.clone(),
+        payload_index: payload_index_ptr.clone(),
+        hnsw_config,
+        permit: Some(permit),
+        stopped: &stopped,
+        progress: None,
+    })
+    .unwrap();
+
+    for _ in 0..20 {
+        let filter = Filter::new_must(Condition::Field(FieldCondition::new_match(
+            JsonPath::new(keyword_key),
+            get_random_keyword_of(num_payload_values, &mut rnd).into(),
+        )));
+        let matching: HashSet<_> = payload_index_ptr
+            .borrow()
+            .query_points(&filter)
+            .into_iter()
+            .collect();
+        assert!(matching.len() >= top);
+
+        let query: QueryVector = random_discovery_query(&mut rnd, dim);
+
+        let result = hnsw_index
+            .search(
+                &[&query],
+                Some(&filter),
+                top,
+                Some(&SearchParams {
+                    hnsw_ef: Some(top),
+                    ..Default::default()
+                }),
+                &Default::default(),
+            )
+            .unwrap();
+
+        assert_eq!(result[0].len(), top);
+        assert!(result[0]
+            .iter()
+            .all(|scored| matching.contains(&scored.idx)));
+    }
+}
@@ -77,6 +77,7 @@ fn hnsw_quantized_search_test(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
@@ -119,11 +120,14 @@ fn hnsw_quantized_search_test(
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold: 2 * payloads_count as usize,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -142,6 +146,7 @@ fn hnsw_quantized_search_test(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -415,11 +420,14 @@ fn test_build_hnsw_using_quantization() {
     );
     vector_data_config.index = Indexes::Hnsw(HnswConfig {
         m: 16,
+        m0: None,
         ef_construct: 64,
         full_scan_threshold: 16,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     });
 
     let permit_cpu_count = num_rayon_threads(0);
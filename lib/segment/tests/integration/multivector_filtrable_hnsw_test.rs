@@ -47,7 +47,11 @@ fn random_multi_vec_discovery_query<R: Rng + ?Sized>(
         .map(|_| {
             let positive = random_multi_vector(rnd, dim, num_vector_per_points).into();
             let negative = random_multi_vector(rnd, dim, num_vector_per_points).into();
-            ContextPair { positive, negative }
+            ContextPair {
+                positive,
+                negative,
+                weight: 1,
+            }
         })
         .collect_vec();
 
@@ -134,6 +138,7 @@ fn test_multi_filterable_hnsw(
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -172,11 +177,14 @@ fn test_multi_filterable_hnsw(
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     let permit_cpu_count = num_rayon_threads(hnsw_config.max_indexing_threads);
@@ -193,6 +201,7 @@ fn test_multi_filterable_hnsw(
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -56,6 +56,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let int_key = "int";
@@ -130,11 +131,14 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
 
     let hnsw_config = HnswConfig {
         m,
+        m0: None,
         ef_construct,
         full_scan_threshold,
         max_indexing_threads: 2,
         on_disk: Some(false),
         payload_m: None,
+        level_multiplier: None,
+        deterministic_build: None,
     };
 
     // single threaded mode to guarantee equivalency between single and multi hnsw
@@ -151,6 +155,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         hnsw_config: hnsw_config.clone(),
         permit: Some(permit.clone()),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
@@ -165,6 +170,7 @@ fn test_single_multi_and_dense_hnsw_equivalency() {
         hnsw_config,
         permit: Some(permit),
         stopped: &stopped,
+        progress: None,
     })
     .unwrap();
 
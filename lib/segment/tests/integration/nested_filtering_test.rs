@@ -71,8 +71,14 @@ fn test_filtering_context_consistency() {
     let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(NUM_POINTS)));
 
-    let mut index =
-        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+    let mut index = StructPayloadIndex::open(
+        wrapped_payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        true,
+    )
+    .unwrap();
 
     index
         .set_indexed(&JsonPath::new("f"), PayloadSchemaType::Integer)
@@ -244,4 +250,104 @@ fn test_filtering_context_consistency() {
         assert_eq!(res3, check_res3);
         assert!(!res3.is_empty());
     }
+
+    {
+        // Nested conditions combined with top-level conditions
+        let nested_filter_4 = Filter {
+            must: Some(vec![
+                Condition::new_nested(
+                    JsonPath::new("arr1"),
+                    Filter {
+                        must: Some(vec![
+                            Condition::Field(FieldCondition::new_match(
+                                JsonPath::new("a"),
+                                1.into(),
+                            )),
+                            Condition::Field(FieldCondition::new_match(
+                                JsonPath::new("c"),
+                                1.into(),
+                            )),
+                        ]),
+                        should: None,
+                        min_should: None,
+                        must_not: None,
+                    },
+                ),
+                Condition::Field(FieldCondition::new_match(JsonPath::new("f"), 2.into())),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: Some(vec![Condition::new_nested(
+                JsonPath::new("arr1"),
+                Filter {
+                    must: Some(vec![
+                        Condition::Field(FieldCondition::new_match(JsonPath::new("a"), 2.into())),
+                        Condition::Field(FieldCondition::new_match(JsonPath::new("d"), 0.into())),
+                    ]),
+                    should: None,
+                    min_should: None,
+                    must_not: None,
+                },
+            )]),
+        };
+
+        let res4 = index.query_points(&nested_filter_4);
+
+        let filter_context = index.filter_context(&nested_filter_4);
+
+        let check_res4: Vec<_> = (0..NUM_POINTS as PointOffsetType)
+            .filter(|point_id| filter_context.check(*point_id as PointOffsetType))
+            .collect();
+
+        // i % 2 == 0, i % 10 == 2, i % 3 != 0
+        let expected4: Vec<_> = (0..NUM_POINTS as PointOffsetType)
+            .filter(|i| i % 10 == 2 && i % 3 != 0)
+            .collect();
+
+        assert_eq!(res4, check_res4);
+        assert_eq!(res4, expected4);
+    }
+
+    {
+        // Conditions must be satisfied by the same element, not spread across elements.
+        // Element with "a" = 3 always has "c" >= 2
+        let nested_filter_5 = Filter::new_must(Condition::new_nested(
+            JsonPath::new("arr1"),
+            Filter {
+                must: Some(vec![
+                    Condition::Field(FieldCondition::new_match(JsonPath::new("a"), 3.into())),
+                    Condition::Field(FieldCondition::new_match(JsonPath::new("c"), 1.into())),
+                ]),
+                should: None,
+                min_should: None,
+                must_not: None,
+            },
+        ));
+
+        let res5 = index.query_points(&nested_filter_5);
+        assert!(res5.is_empty());
+
+        let filter_context = index.filter_context(&nested_filter_5);
+        assert!((0..NUM_POINTS as PointOffsetType).all(|point_id| !filter_context.check(point_id)));
+
+        // While flat conditions match any element
+        let flat_filter_5 = Filter {
+            must: Some(vec![
+                Condition::Field(FieldCondition::new_match(
+                    JsonPath::new("arr1[].a"),
+                    3.into(),
+                )),
+                Condition::Field(FieldCondition::new_match(
+                    JsonPath::new("arr1[].c"),
+                    1.into(),
+                )),
+            ]),
+            should: None,
+            min_should: None,
+            must_not: None,
+        };
+
+        let flat_res5 = index.query_points(&flat_filter_5);
+        assert_eq!(flat_res5.len(), NUM_POINTS / 2);
+    }
 }
@@ -226,6 +226,7 @@ impl TestSegments {
             )]),
             sparse_vector_data: Default::default(),
             payload_storage_type: Default::default(),
+            payload_compression: None,
         };
         assert_eq!(conf.is_appendable(), appendable);
         conf
@@ -257,6 +258,7 @@ impl TestSegments {
                         r#type: KeywordIndexType::Keyword,
                         is_tenant: None,
                         on_disk: Some(true),
+                        hnsw_partitions: None,
                     },
                 ))),
             )
@@ -351,6 +353,7 @@ fn build_test_segments_nested_payload(path_struct: &Path, path_plain: &Path) ->
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut plain_segment = build_segment(path_plain, &config, true).unwrap();
@@ -929,6 +932,7 @@ fn test_struct_payload_geo_radius_index() {
             lat: rnd.gen_range(LAT_RANGE),
         },
         radius: r_meters,
+        inner_radius: None,
     };
 
     let condition = Condition::Field(FieldCondition::new_geo_radius(
@@ -970,6 +974,7 @@ fn test_struct_payload_geo_polygon_index() {
     let geo_polygon = GeoPolygon {
         exterior,
         interiors,
+        crosses_antimeridian: None,
     };
 
     let condition = Condition::Field(FieldCondition::new_geo_polygon(
@@ -1082,8 +1087,14 @@ fn test_update_payload_index_type() {
     let wrapped_payload_storage = Arc::new(AtomicRefCell::new(payload_storage.into()));
     let id_tracker = Arc::new(AtomicRefCell::new(FixtureIdTracker::new(point_num)));
 
-    let mut index =
-        StructPayloadIndex::open(wrapped_payload_storage, id_tracker, dir.path(), true).unwrap();
+    let mut index = StructPayloadIndex::open(
+        wrapped_payload_storage,
+        id_tracker,
+        HashMap::new(),
+        dir.path(),
+        true,
+    )
+    .unwrap();
 
     let field = JsonPath::new("field");
 
@@ -48,6 +48,7 @@ fn test_on_disk_segment_snapshot() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
 
     let mut segment = build_segment(segment_builder_dir.path(), &building_config, true).unwrap();
@@ -75,6 +76,7 @@ fn test_on_disk_segment_snapshot() {
                     r#type: segment::data_types::index::KeywordIndexType::Keyword,
                     is_tenant: None,
                     on_disk: Some(true),
+                    hnsw_partitions: None,
                 }),
             )),
         )
@@ -104,11 +106,14 @@ fn test_on_disk_segment_snapshot() {
                 storage_type: VectorStorageType::Mmap, // mmap vectors
                 index: Indexes::Hnsw(HnswConfig {
                     m: 4,
+                    m0: None,
                     ef_construct: 16,
                     full_scan_threshold: 8,
                     max_indexing_threads: 2,
                     on_disk: Some(true), // mmap index
                     payload_m: None,
+                    level_multiplier: None,
+                    deterministic_build: None,
                 }),
                 quantization_config: None,
                 multivector_config: None,
@@ -117,6 +122,7 @@ fn test_on_disk_segment_snapshot() {
         )]),
         sparse_vector_data: Default::default(),
         payload_storage_type: PayloadStorageType::OnDisk, // on-disk payload
+        payload_compression: None,
     };
 
     let segment_base_dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
@@ -132,6 +138,11 @@ fn test_on_disk_segment_snapshot() {
         .build(CpuPermit::dummy(num_rayon_threads(0) as u32), &false.into())
         .unwrap();
 
+    // Both mmap vectors and mmap index can be warmed up
+    let warmup_tasks = segment.warmup_tasks(true).unwrap();
+    assert_eq!(warmup_tasks.len(), 2);
+    assert!(warmup_tasks.iter().all(|task| task.size_bytes() > 0));
+
     let snapshot_dir = Builder::new().prefix("snapshot_dir").tempdir().unwrap();
     let temp_dir = Builder::new().prefix("temp_dir").tempdir().unwrap();
     let snapshot_name = snapshot_dir.path().join("snapshot.tar");
@@ -12,7 +12,7 @@ use segment::entry::entry_point::SegmentEntry;
 use segment::fixtures::index_fixtures::random_vector;
 use segment::segment_constructor::load_segment;
 use segment::segment_constructor::simple_segment_constructor::build_simple_segment;
-use segment::types::{Condition, Distance, Filter, SearchParams, WithPayload};
+use segment::types::{Condition, Distance, Filter, PointIdType, SearchParams, WithPayload};
 use tempfile::Builder;
 
 use crate::fixtures::segment::{build_segment_1, build_segment_3};
@@ -261,6 +261,11 @@ fn test_update_named_vector() {
         exact: true,
         quantization: None,
         indexed_only: false,
+        hnsw_beam_width: None,
+        hnsw_max_candidates: None,
+        sparse_top_k: None,
+        provenance: false,
+        distance: None,
     };
     let nearest_upsert = segment
         .search(
@@ -328,3 +333,68 @@ fn test_update_named_vector() {
     // check that nearests are the same
     assert_eq!(nearest_upsert.id, nearest_update.id);
 }
+
+#[test]
+fn test_search_with_distance_override() {
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let mut segment = build_simple_segment(dir.path(), 2, Distance::Dot).unwrap();
+
+    let vectors = [[1.0, 0.0], [3.0, 4.0], [0.0, -2.0], [-1.0, 1.0]];
+    for (i, vec) in vectors.iter().enumerate() {
+        let i = i as u64;
+        segment
+            .upsert_point(i, i.into(), only_default_vector(vec))
+            .unwrap();
+    }
+
+    let query = [1.0, 1.0];
+    let search = |distance| {
+        let params = SearchParams {
+            distance: Some(distance),
+            ..Default::default()
+        };
+        segment
+            .search(
+                DEFAULT_VECTOR_NAME,
+                &query.into(),
+                &false.into(),
+                &false.into(),
+                None,
+                vectors.len(),
+                Some(&params),
+            )
+            .unwrap()
+            .into_iter()
+            .map(|point| (point.id, point.score))
+            .collect_vec()
+    };
+
+    let assert_scores = |result: Vec<(PointIdType, f32)>, expected: [(u64, f32); 4]| {
+        assert_eq!(result.len(), expected.len());
+        for ((id, score), (expected_id, expected_score)) in result.into_iter().zip(expected) {
+            assert_eq!(id, expected_id.into());
+            assert!((score - expected_score).abs() < 1e-5);
+        }
+    };
+
+    // Euclid scores are negated squared distances before post-processing
+    assert_scores(
+        search(Distance::Euclid),
+        [(0, -1.0), (3, -4.0), (2, -10.0), (1, -13.0)],
+    );
+
+    // Stored vectors are not normalized for dot product, they are normalized for cosine
+    assert_scores(
+        search(Distance::Cosine),
+        [
+            (1, 7.0 / (5.0 * 2f32.sqrt())),
+            (0, 1.0 / 2f32.sqrt()),
+            (3, 0.0),
+            (2, -1.0 / 2f32.sqrt()),
+        ],
+    );
+
+    // Overriding with the configured distance searches as usual
+    let result = search(Distance::Dot);
+    assert_eq!(result[0], (1.into(), 7.0));
+}
@@ -73,6 +73,7 @@ fn random_discovery_query<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> (QueryVec
             .map(|(positive, negative)| ContextPair {
                 positive: positive.into(),
                 negative: negative.into(),
+                weight: 1,
             })
             .collect(),
     )
@@ -84,6 +85,7 @@ fn random_discovery_query<R: Rng + ?Sized>(rnd: &mut R, dim: usize) -> (QueryVec
             .map(|(positive, negative)| ContextPair {
                 positive: positive.into(),
                 negative: negative.into(),
+                weight: 1,
             })
             .collect(),
     )
@@ -121,9 +123,11 @@ fn sparse_index_discover_test() {
                     index_type: SparseIndexType::MutableRam,
                     datatype: Some(VectorStorageDatatype::Float32),
                 },
+                distance: None,
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let dense_config = SegmentConfig {
         vector_data: HashMap::from([(
@@ -140,6 +144,7 @@ fn sparse_index_discover_test() {
         )]),
         payload_storage_type: Default::default(),
         sparse_vector_data: Default::default(),
+        payload_compression: None,
     };
 
     let mut sparse_segment = build_segment(dir.path(), &sparse_config, true).unwrap();
@@ -26,13 +26,16 @@ use segment::segment_constructor::{build_segment, load_segment};
 use segment::types::PayloadFieldSchema::FieldType;
 use segment::types::PayloadSchemaType::Keyword;
 use segment::types::{
-    Condition, FieldCondition, Filter, Payload, ScoredPoint, SegmentConfig, SeqNumberType,
-    SparseVectorDataConfig, VectorStorageDatatype, DEFAULT_SPARSE_FULL_SCAN_THRESHOLD,
+    Condition, FieldCondition, Filter, Payload, ScoredPoint, SearchParams, SegmentConfig,
+    SeqNumberType, SparseDistance, SparseVectorDataConfig, VectorStorageDatatype,
+    DEFAULT_SPARSE_FULL_SCAN_THRESHOLD,
 };
 use segment::vector_storage::VectorStorage;
 use serde_json::json;
 use sparse::common::sparse_vector::SparseVector;
-use sparse::common::sparse_vector_fixture::{random_full_sparse_vector, random_sparse_vector};
+use sparse::common::sparse_vector_fixture::{
+    random_full_sparse_vector, random_positive_sparse_vector, random_sparse_vector,
+};
 use sparse::common::types::DimId;
 use sparse::index::inverted_index::inverted_index_compressed_immutable_ram::InvertedIndexCompressedImmutableRam;
 use sparse::index::inverted_index::inverted_index_compressed_mmap::InvertedIndexCompressedMmap;
@@ -139,6 +142,59 @@ fn sparse_vector_index_ram_filter_search() {
     compare_sparse_vectors_search_with_without_filter(LOW_FULL_SCAN_THRESHOLD);
 }
 
+#[test]
+fn sparse_vector_index_top_k_query_search() {
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let data_dir = Builder::new().prefix("data_dir").tempdir().unwrap();
+
+    let sparse_vector_index = fixture_sparse_index::<InvertedIndexCompressedImmutableRam<f32>, _>(
+        &mut rnd,
+        NUM_VECTORS,
+        MAX_SPARSE_DIM,
+        LOW_FULL_SCAN_THRESHOLD,
+        data_dir.path(),
+    );
+
+    let sparse_top_k = 3;
+    let params = SearchParams {
+        sparse_top_k: Some(sparse_top_k),
+        ..Default::default()
+    };
+    let top = 10;
+
+    for _ in 0..100 {
+        let query = random_sparse_vector(&mut rnd, MAX_SPARSE_DIM);
+
+        // only the dimensions of the query with the highest weights are used
+        let mut pruned_query = query.clone();
+        pruned_query.retain_top_weights(sparse_top_k);
+        assert!(pruned_query.indices.len() <= sparse_top_k);
+
+        let query_vector: QueryVector = query.into();
+        let pruned_query_vector: QueryVector = pruned_query.into();
+        let results = sparse_vector_index
+            .search(
+                &[&query_vector],
+                None,
+                top,
+                Some(&params),
+                &Default::default(),
+            )
+            .unwrap();
+        let expected = sparse_vector_index
+            .search(
+                &[&pruned_query_vector],
+                None,
+                top,
+                None,
+                &Default::default(),
+            )
+            .unwrap();
+        assert_eq!(results, expected);
+    }
+}
+
 #[test]
 fn sparse_vector_index_fallback_plain_search() {
     // very high full scan threshold to force fallback to plain search
@@ -579,9 +635,11 @@ fn sparse_vector_index_persistence_test() {
                     index_type: SparseIndexType::MutableRam,
                     datatype: Some(VectorStorageDatatype::Float32),
                 },
+                distance: None,
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -747,9 +805,11 @@ fn sparse_vector_test_large_index() {
                     index_type: SparseIndexType::MutableRam,
                     datatype: Some(VectorStorageDatatype::Float32),
                 },
+                distance: None,
             },
         )]),
         payload_storage_type: Default::default(),
+        payload_compression: None,
     };
     let mut segment = build_segment(dir.path(), &config, true).unwrap();
 
@@ -779,3 +839,107 @@ fn sparse_vector_test_large_index() {
         _ => panic!("unexpected vector index type"),
     }
 }
+
+fn check_sparse_distance_search(distance: SparseDistance, binary: bool) {
+    let stopped = AtomicBool::new(false);
+
+    let dim = 256;
+    let num_vectors: u64 = 1_000;
+    let top = 10;
+    let mut rnd = StdRng::seed_from_u64(42);
+
+    let random_vector = |rnd: &mut StdRng| {
+        let mut vector = random_positive_sparse_vector(rnd, dim);
+        if binary {
+            vector.values.iter_mut().for_each(|value| *value = 1.0);
+        }
+        vector.sort_by_indices();
+        vector
+    };
+
+    let dir = Builder::new().prefix("segment_dir").tempdir().unwrap();
+    let config = SegmentConfig {
+        vector_data: Default::default(),
+        sparse_vector_data: HashMap::from([(
+            SPARSE_VECTOR_NAME.to_owned(),
+            SparseVectorDataConfig {
+                index: SparseIndexConfig {
+                    full_scan_threshold: Some(LOW_FULL_SCAN_THRESHOLD),
+                    index_type: SparseIndexType::MutableRam,
+                    datatype: Some(VectorStorageDatatype::Float32),
+                },
+                distance: Some(distance),
+            },
+        )]),
+        payload_storage_type: Default::default(),
+        payload_compression: None,
+    };
+    let mut segment = build_segment(dir.path(), &config, true).unwrap();
+
+    let vectors: Vec<_> = (0..num_vectors).map(|_| random_vector(&mut rnd)).collect();
+    for (n, vector) in vectors.iter().enumerate() {
+        let mut named_vector = NamedVectors::default();
+        named_vector.insert(SPARSE_VECTOR_NAME.to_owned(), vector.clone().into());
+        segment
+            .upsert_point(n as SeqNumberType, (n as u64).into(), named_vector)
+            .unwrap();
+    }
+    segment.flush(true, false).unwrap();
+
+    let search = |segment: &Segment, query: &SparseVector| {
+        segment
+            .search(
+                SPARSE_VECTOR_NAME,
+                &query.clone().into(),
+                &Default::default(),
+                &Default::default(),
+                None,
+                top,
+                None,
+            )
+            .unwrap()
+    };
+
+    let queries: Vec<_> = (0..10).map(|_| random_vector(&mut rnd)).collect();
+    let results: Vec<_> = queries
+        .iter()
+        .map(|query| search(&segment, query))
+        .collect();
+
+    for (query, result) in queries.iter().zip(&results) {
+        // Exact scores of the best matches, the inverted index only finds overlapping vectors
+        let mut expected: Vec<_> = vectors
+            .iter()
+            .map(|vector| distance.score(query, vector))
+            .filter(|&score| score != 0.0)
+            .collect();
+        expected.sort_by(|a, b| b.total_cmp(a));
+        expected.truncate(top);
+
+        let scores: Vec<_> = result.iter().map(|point| point.score).collect();
+        assert_eq!(scores.len(), expected.len());
+        for (score, expected) in scores.iter().zip(&expected) {
+            assert!((score - expected).abs() < 1e-4, "{score} != {expected}");
+        }
+        // Both similarities are normalized
+        assert!(scores.iter().all(|&score| score <= 1.0 + 1e-4));
+    }
+
+    // Norms are restored when the segment is loaded
+    let path = segment.current_path.clone();
+    drop(segment);
+    let segment = load_segment(&path, &stopped).unwrap().unwrap();
+    for (query, result) in queries.iter().zip(&results) {
+        assert_eq!(&search(&segment, query), result);
+    }
+}
+
+#[test]
+fn sparse_vector_index_cosine_search() {
+    check_sparse_distance_search(SparseDistance::Cosine, false);
+}
+
+#[test]
+fn sparse_vector_index_jaccard_search() {
+    check_sparse_distance_search(SparseDistance::Jaccard, true);
+}
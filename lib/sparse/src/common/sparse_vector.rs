@@ -142,6 +142,27 @@ impl SparseVector {
         score_vectors(&self.indices, &self.values, &other.indices, &other.values)
     }
 
+    /// Sum of the squared values, which is the number of non-zero elements for binary vectors.
+    pub fn squared_norm(&self) -> ScoreType {
+        self.values.iter().map(|value| value * value).sum()
+    }
+
+    /// Keep only the `top` elements with the highest absolute values, which contribute the most
+    /// to dot products. The kept elements stay in their order.
+    pub fn retain_top_weights(&mut self, top: usize) {
+        if self.values.len() <= top {
+            return;
+        }
+        let mut positions: Vec<usize> = (0..self.values.len()).collect();
+        positions.select_nth_unstable_by(top, |&a, &b| {
+            self.values[b].abs().total_cmp(&self.values[a].abs())
+        });
+        positions.truncate(top);
+        positions.sort_unstable();
+        self.indices = positions.iter().map(|&i| self.indices[i]).collect();
+        self.values = positions.iter().map(|&i| self.values[i]).collect();
+    }
+
     /// Construct a new vector that is the result of performing all indices-wise operations.
     /// Automatically sort input vectors if necessary.
     pub fn combine_aggregate(
@@ -340,6 +361,18 @@ mod tests {
         assert!(not_unique.is_err());
     }
 
+    #[test]
+    fn retain_top_weights_test() {
+        let mut vector = SparseVector::new(vec![5, 1, 3, 2], vec![0.1, -3.0, 2.0, 0.5]).unwrap();
+        vector.retain_top_weights(2);
+        assert_eq!(vector.indices, vec![1, 3]);
+        assert_eq!(vector.values, vec![-3.0, 2.0]);
+
+        // Vectors with fewer elements are not changed
+        vector.retain_top_weights(5);
+        assert_eq!(vector.indices, vec![1, 3]);
+    }
+
     #[test]
     fn sorting_test() {
         let mut not_sorted = SparseVector::new(vec![1, 3, 2], vec![1.0, 2.0, 3.0]).unwrap();
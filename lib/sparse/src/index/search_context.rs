@@ -3,7 +3,7 @@ use std::sync::atomic::AtomicBool;
 use std::sync::atomic::Ordering::Relaxed;
 
 use common::top_k::TopK;
-use common::types::{PointOffsetType, ScoredPointOffset};
+use common::types::{PointOffsetType, ScoreType, ScoredPointOffset};
 
 use super::posting_list_common::PostingListIter;
 use crate::common::scores_memory_pool::PooledScoresHandle;
@@ -22,6 +22,10 @@ pub struct IndexedPostingListIterator<T: PostingListIter> {
 /// Making this larger makes the search faster but uses more (pooled) memory
 const ADVANCE_BATCH_SIZE: usize = 10_000;
 
+/// Turns the dot product of the query and a record into the score of the record,
+/// e.g. to score by similarities which also depend on the norm of the record
+pub type ScoreTransform<'a> = &'a dyn Fn(PointOffsetType, ScoreType) -> ScoreType;
+
 pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
     postings_iterators: Vec<IndexedPostingListIterator<T>>,
     query: RemappedSparseVector,
@@ -32,6 +36,7 @@ pub struct SearchContext<'a, 'b, T: PostingListIter = PostingListIterator<'a>> {
     max_record_id: PointOffsetType,         // max_record_id ids across all posting lists
     pooled: PooledScoresHandle<'b>,         // handle to pooled scores
     use_pruning: bool,
+    score_transform: Option<ScoreTransform<'a>>,
 }
 
 impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
@@ -86,9 +91,19 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             max_record_id,
             pooled,
             use_pruning,
+            score_transform: None,
         }
     }
 
+    /// Score records with the given transform of the dot product.
+    ///
+    /// Pruning relies on the dot product of the remaining postings, so it is disabled.
+    pub fn with_score_transform(mut self, score_transform: ScoreTransform<'a>) -> Self {
+        self.score_transform = Some(score_transform);
+        self.use_pruning = false;
+        self
+    }
+
     /// Plain search against the given ids without any pruning
     pub fn plain_search(&mut self, ids: &[PointOffsetType]) -> Vec<ScoredPointOffset> {
         // sort ids to fully leverage posting list iterator traversal
@@ -117,10 +132,11 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
             }
             // reconstruct sparse vector and score against query
             let sparse_vector = RemappedSparseVector { indices, values };
-            self.top_results.push(ScoredPointOffset {
-                score: sparse_vector.score(&self.query).unwrap_or(0.0),
-                idx: id,
-            });
+            let mut score = sparse_vector.score(&self.query).unwrap_or(0.0);
+            if let Some(score_transform) = self.score_transform {
+                score = score_transform(id, score);
+            }
+            self.top_results.push(ScoredPointOffset { score, idx: id });
         }
         let top = std::mem::take(&mut self.top_results);
         top.into_vec()
@@ -154,9 +170,16 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
         }
 
         for (local_index, &score) in self.pooled.scores.iter().enumerate() {
+            if score == 0.0 {
+                continue;
+            }
+            let real_id = batch_start_id + local_index as PointOffsetType;
+            let score = match self.score_transform {
+                Some(score_transform) => score_transform(real_id, score),
+                None => score,
+            };
             // publish only the non-zero scores above the current min to beat
-            if score != 0.0 && score > self.top_results.threshold() {
-                let real_id = batch_start_id + local_index as PointOffsetType;
+            if score > self.top_results.threshold() {
                 // do not score if filter condition is not satisfied
                 if !filter_condition(real_id) {
                     continue;
@@ -174,6 +197,7 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
     fn process_last_posting_list<F: Fn(PointOffsetType) -> bool>(&mut self, filter_condition: &F) {
         debug_assert_eq!(self.postings_iterators.len(), 1);
         let posting = &mut self.postings_iterators[0];
+        let score_transform = self.score_transform;
         posting.posting_list_iterator.for_each_till_id(
             PointOffsetType::MAX,
             &mut (),
@@ -182,7 +206,10 @@ impl<'a, 'b, T: PostingListIter> SearchContext<'a, 'b, T> {
                 if !filter_condition(id) {
                     return;
                 }
-                let score = weight * posting.query_weight;
+                let mut score = weight * posting.query_weight;
+                if let Some(score_transform) = score_transform {
+                    score = score_transform(id, score);
+                }
                 self.top_results.push(ScoredPointOffset { score, idx: id });
             },
         );
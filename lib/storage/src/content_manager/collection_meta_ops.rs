@@ -1,12 +1,12 @@
 use std::collections::BTreeMap;
 
-use collection::config::{CollectionConfig, ShardingMethod};
+use collection::config::{CollectionConfig, ShardingMethod, TtlConfig};
 use collection::operations::config_diff::{
     CollectionParamsDiff, HnswConfigDiff, OptimizersConfigDiff, QuantizationConfigDiff,
     StrictModeConfig, WalConfigDiff,
 };
 use collection::operations::types::{
-    SparseVectorParams, SparseVectorsConfig, VectorsConfig, VectorsConfigDiff,
+    SparseVectorParams, SparseVectorsConfig, VectorParams, VectorsConfig, VectorsConfigDiff,
 };
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::resharding::ReshardKey;
@@ -14,7 +14,9 @@ use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
 use collection::shards::{replica_set, CollectionId};
 use schemars::JsonSchema;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey};
+use segment::types::{
+    PayloadCompressionConfig, PayloadFieldSchema, PayloadKeyType, QuantizationConfig, ShardKey,
+};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
 
@@ -146,6 +148,11 @@ pub struct CreateCollection {
     /// Note: those payload values that are involved in filtering and are indexed - remain in RAM.
     #[serde(default)]
     pub on_disk_payload: Option<bool>,
+    /// If set - payloads are compressed with zstd before being stored.
+    /// Payloads too small to benefit from compression are stored as is.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[validate(nested)]
+    pub payload_compression: Option<PayloadCompressionConfig>,
     /// Custom params for HNSW index. If none - values from service configuration file are used.
     #[validate(nested)]
     pub hnsw_config: Option<HnswConfigDiff>,
@@ -168,8 +175,11 @@ pub struct CreateCollection {
     pub sparse_vectors: Option<BTreeMap<String, SparseVectorParams>>,
     /// Strict-mode config.
     #[validate(nested)]
-    #[schemars(skip)]
     pub strict_mode_config: Option<StrictModeConfig>,
+    /// Expiry of points after a time-to-live. If none - points never expire.
+    #[serde(default)]
+    #[validate(nested)]
+    pub ttl_config: Option<TtlConfig>,
 }
 
 /// Operation for creating new collection and (optionally) specify index params
@@ -227,6 +237,14 @@ pub struct UpdateCollection {
     /// Map of sparse vector data parameters to update for each sparse vector.
     #[validate(nested)]
     pub sparse_vectors: Option<SparseVectorsConfig>,
+    /// Overrides of optimizer params for individual shards, replacing previous overrides of these
+    /// shards. Empty params remove the override of a shard. If none - it is left unchanged.
+    #[serde(default)]
+    pub shard_optimizers_config: Option<BTreeMap<ShardId, OptimizersConfigDiff>>,
+    /// Named vectors to add to the collection. Existing points have no values for them.
+    /// Not allowed for collections having a single unnamed vector.
+    #[serde(default)]
+    pub new_vectors: Option<BTreeMap<String, VectorParams>>,
 }
 
 /// Operation for updating parameters of the existing collection
@@ -249,6 +267,8 @@ impl UpdateCollectionOperation {
                 optimizers_config: None,
                 quantization_config: None,
                 sparse_vectors: None,
+                shard_optimizers_config: None,
+                new_vectors: None,
             },
             shard_replica_changes: None,
         }
@@ -292,6 +312,8 @@ pub struct DeleteCollectionOperation(pub String);
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Deserialize, Serialize)]
 pub enum ReshardingOperation {
     Start(ReshardKey),
+    /// Start resharding to split an oversized shard, driven by the target peer until it finishes
+    StartAutomatic(ReshardKey),
     CommitRead(ReshardKey),
     CommitWrite(ReshardKey),
     Finish(ReshardKey),
@@ -396,6 +418,7 @@ impl From<CollectionConfig> for CreateCollection {
             replication_factor: Some(value.params.replication_factor.get()),
             write_consistency_factor: Some(value.params.write_consistency_factor.get()),
             on_disk_payload: Some(value.params.on_disk_payload),
+            payload_compression: value.params.payload_compression,
             hnsw_config: Some(value.hnsw_config.into()),
             wal_config: Some(value.wal_config.into()),
             optimizers_config: Some(value.optimizer_config.into()),
@@ -403,6 +426,7 @@ impl From<CollectionConfig> for CreateCollection {
             quantization_config: value.quantization_config,
             sparse_vectors: value.params.sparse_vectors,
             strict_mode_config: value.strict_mode_config,
+            ttl_config: value.ttl_config,
         }
     }
 }
@@ -7,11 +7,15 @@ use super::toc::TableOfContent;
 use crate::dispatcher::Dispatcher;
 use crate::rbac::{Access, AccessRequirements};
 
+/// Verifies the request against the strict mode config of the collection.
+///
+/// If strict mode is configured to clamp limits, the limits of `request` are reduced in place.
 pub async fn check_strict_mode(
-    request: &impl StrictModeVerification,
+    request: &mut impl StrictModeVerification,
     collection_name: &str,
     dispatcher: &Dispatcher,
     access: &Access,
+    bypass: bool,
 ) -> Result<VerificationPass, StorageError> {
     let toc = get_toc_without_verification_pass(dispatcher, access);
 
@@ -21,8 +25,17 @@ pub async fn check_strict_mode(
     let collection_pass =
         access.check_collection_access(collection_name, AccessRequirements::new())?;
     let collection = toc.get_collection(&collection_pass).await?;
+
+    // Skipping strict mode is meant for maintenance tasks only, so it requires global manage access.
+    if bypass {
+        access.check_global_access(AccessRequirements::new().manage())?;
+        return Ok(new_pass());
+    }
+
     if let Some(strict_mode_config) = &collection.strict_mode_config().await {
         if strict_mode_config.enabled.unwrap_or_default() {
+            request.apply_custom(strict_mode_config);
+            request.apply_query_limit(strict_mode_config);
             request.check_strict_mode(&collection, strict_mode_config)?;
         }
     }
@@ -23,8 +23,14 @@ impl From<StorageError> for tonic::Status {
             StorageError::ChecksumMismatch { .. } => tonic::Code::DataLoss,
             StorageError::Forbidden { .. } => tonic::Code::PermissionDenied,
             StorageError::PreconditionFailed { .. } => tonic::Code::FailedPrecondition,
+            StorageError::ResourceExhausted { .. } => tonic::Code::ResourceExhausted,
+            StorageError::Cancelled { .. } => tonic::Code::Cancelled,
+            StorageError::OutOfMemory { .. } => tonic::Code::ResourceExhausted,
         };
-        tonic::Status::new(error_code, format!("{error}"))
+        api::grpc::with_error_code(
+            tonic::Status::new(error_code, format!("{error}")),
+            error.code(),
+        )
     }
 }
 
@@ -49,6 +55,7 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                 optimizers_config: value.optimizers_config.map(|v| v.into()),
                 shard_number: value.shard_number,
                 on_disk_payload: value.on_disk_payload,
+                payload_compression: value.payload_compression.map(From::from),
                 replication_factor: value.replication_factor,
                 write_consistency_factor: value.write_consistency_factor,
                 init_from: value
@@ -63,6 +70,7 @@ impl TryFrom<api::grpc::qdrant::CreateCollection> for CollectionMetaOperations {
                     .map(sharding_method_from_proto)
                     .transpose()?,
                 strict_mode_config: value.strict_mode_config.map(From::from),
+                ttl_config: value.ttl_config.map(TryInto::try_into).transpose()?,
             },
         )))
     }
@@ -91,6 +99,22 @@ impl TryFrom<api::grpc::qdrant::UpdateCollection> for CollectionMetaOperations {
                     .sparse_vectors_config
                     .map(TryInto::try_into)
                     .transpose()?,
+                shard_optimizers_config: (!value.shard_optimizers_config.is_empty()).then(|| {
+                    value
+                        .shard_optimizers_config
+                        .into_iter()
+                        .map(|(shard_id, diff)| (shard_id, diff.into()))
+                        .collect()
+                }),
+                new_vectors: (!value.new_vectors_config.is_empty())
+                    .then(|| {
+                        value
+                            .new_vectors_config
+                            .into_iter()
+                            .map(|(vector_name, params)| Ok((vector_name, params.try_into()?)))
+                            .collect::<Result<_, Status>>()
+                    })
+                    .transpose()?,
             },
         )))
     }
@@ -169,3 +193,41 @@ impl TryFrom<api::grpc::qdrant::ChangeAliases> for CollectionMetaOperations {
         Ok(Self::ChangeAliases(ChangeAliasesOperation { actions }))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use api::grpc::ERROR_CODE_METADATA_KEY;
+    use collection::operations::types::CollectionError;
+
+    use super::*;
+
+    #[test]
+    fn test_error_code_in_status() {
+        let error_code = |status: &Status| {
+            status
+                .metadata()
+                .get(ERROR_CODE_METADATA_KEY)
+                .unwrap()
+                .to_str()
+                .unwrap()
+                .to_string()
+        };
+
+        let status = Status::from(StorageError::from(CollectionError::OutOfMemory {
+            description: "failed to allocate".to_string(),
+            free: 0,
+        }));
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        assert_eq!(error_code(&status), "resource_exhausted");
+
+        let status = Status::from(StorageError::from(CollectionError::Cancelled {
+            description: "shutting down".to_string(),
+        }));
+        assert_eq!(status.code(), tonic::Code::Cancelled);
+        assert_eq!(error_code(&status), "cancelled");
+
+        let status = Status::from(StorageError::not_found("collection"));
+        assert_eq!(status.code(), tonic::Code::NotFound);
+        assert_eq!(error_code(&status), "not_found");
+    }
+}
@@ -20,6 +20,11 @@ const MIGRATION_BATCH_SIZE: usize = 1000;
 const COLLECTION_INITIATION_TIMEOUT: Duration = Duration::from_secs(60);
 
 /// Handlers for transferring data from one collection into another within single cluster
+///
+/// Locks are never nested: the collections lock is not acquired again while it is held, because
+/// a waiting writer blocks new readers, and the updates lock of the source collection is released
+/// before updating the target collection. Otherwise, transfers in opposite directions, or a
+/// collection creation during a transfer, can deadlock.
 
 /// Get a list of local shards, which can be used for migration
 ///
@@ -68,6 +73,19 @@ fn handle_get_collection(collection: Option<&Collection>) -> CollectionResult<&C
     }
 }
 
+/// A collection can't be initialized from itself, the transfer would read the points it writes
+fn check_not_same_collection(
+    source_collection_name: &CollectionId,
+    target_collection_name: &CollectionId,
+) -> CollectionResult<()> {
+    if source_collection_name == target_collection_name {
+        return Err(CollectionError::bad_input(format!(
+            "Collection {target_collection_name} can't be initialized from itself"
+        )));
+    }
+    Ok(())
+}
+
 async fn replicate_shard_data(
     collections: Arc<RwLock<Collections>>,
     source_collection_name: &CollectionId,
@@ -89,17 +107,19 @@ async fn replicate_shard_data(
 
         let collections_read = collections.read().await;
 
-        let source_collection =
-            handle_get_collection(collections_read.get(source_collection_name))?;
-        let _updates_guard = source_collection.lock_updates().await;
-        let scroll_result = source_collection
-            .scroll_by(
-                request,
-                None,
-                &ShardSelectorInternal::ShardId(shard_id),
-                None,
-            )
-            .await?;
+        let scroll_result = {
+            let source_collection =
+                handle_get_collection(collections_read.get(source_collection_name))?;
+            let _updates_guard = source_collection.lock_updates().await;
+            source_collection
+                .scroll_by(
+                    request,
+                    None,
+                    &ShardSelectorInternal::ShardId(shard_id),
+                    None,
+                )
+                .await?
+        };
 
         offset = scroll_result.next_page_offset;
 
@@ -114,6 +134,7 @@ async fn replicate_shard_data(
                 id: point.id,
                 vector: point.vector.unwrap(),
                 payload: point.payload,
+                vector_metadata: point.vector_metadata,
             })
             .collect();
 
@@ -160,9 +181,13 @@ pub async fn populate_collection(
     target_collection: &CollectionId,
     this_peer_id: PeerId,
 ) -> CollectionResult<()> {
-    let collections_read = collections.read().await;
-    let collection = handle_get_collection(collections_read.get(source_collection))?;
-    let local_responsible_shards = get_local_source_shards(collection, this_peer_id).await?;
+    check_not_same_collection(source_collection, target_collection)?;
+
+    let local_responsible_shards = {
+        let collections_read = collections.read().await;
+        let collection = handle_get_collection(collections_read.get(source_collection))?;
+        get_local_source_shards(collection, this_peer_id).await?
+    };
 
     log::debug!(
         "Transferring shards {:?} from collection {} to collection {}",
@@ -193,10 +218,14 @@ pub async fn transfer_indexes(
     target_collection: &CollectionId,
     this_peer_id: PeerId,
 ) -> CollectionResult<()> {
+    check_not_same_collection(source_collection, target_collection)?;
+
     // Do this action on the "main" peer only
-    let collections_read = collections.read().await;
-    let collection = handle_get_collection(collections_read.get(source_collection))?;
-    let state = collection.state().await;
+    let state = {
+        let collections_read = collections.read().await;
+        let collection = handle_get_collection(collections_read.get(source_collection))?;
+        collection.state().await
+    };
     let max_peer = state
         .shards
         .iter()
@@ -216,6 +245,8 @@ pub async fn transfer_indexes(
 
     wait_all_shards_active(collections.clone(), target_collection).await?;
 
+    let collections_read = collections.read().await;
+    let collection = handle_get_collection(collections_read.get(source_collection))?;
     let collection_info = collection.info(&ShardSelectorInternal::All).await?;
 
     let target_collection = handle_get_collection(collections_read.get(target_collection))?;
@@ -3,6 +3,7 @@ use std::io::Error as IoError;
 
 use collection::operations::types::CollectionError;
 use io::file_operations::FileStorageError;
+use segment::common::operation_error::ErrorCode;
 use tempfile::PersistError;
 use thiserror::Error;
 
@@ -34,6 +35,12 @@ pub enum StorageError {
     Forbidden { description: String },
     #[error("Pre-condition failure: {description}")]
     PreconditionFailed { description: String }, // system is not in the state to perform the operation
+    #[error("Resource exhausted: {description}")]
+    ResourceExhausted { description: String },
+    #[error("Operation cancelled: {description}")]
+    Cancelled { description: String },
+    #[error("Out of memory: {description}")]
+    OutOfMemory { description: String },
 }
 
 impl StorageError {
@@ -81,6 +88,26 @@ impl StorageError {
         }
     }
 
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            StorageError::BadInput { .. }
+            | StorageError::BadRequest { .. }
+            | StorageError::ChecksumMismatch { .. } => ErrorCode::InvalidArgument,
+            StorageError::AlreadyExists { .. } => ErrorCode::AlreadyExists,
+            StorageError::NotFound { .. } => ErrorCode::NotFound,
+            StorageError::ServiceError { .. } => ErrorCode::Internal,
+            StorageError::Locked { .. } | StorageError::PreconditionFailed { .. } => {
+                ErrorCode::FailedPrecondition
+            }
+            StorageError::Timeout { .. } => ErrorCode::DeadlineExceeded,
+            StorageError::Forbidden { .. } => ErrorCode::PermissionDenied,
+            StorageError::ResourceExhausted { .. } | StorageError::OutOfMemory { .. } => {
+                ErrorCode::ResourceExhausted
+            }
+            StorageError::Cancelled { .. } => ErrorCode::Cancelled,
+        }
+    }
+
     /// Used to override the `description` field of the resulting `StorageError`
     pub fn from_inconsistent_shard_failure(
         err: CollectionError,
@@ -103,9 +130,8 @@ impl StorageError {
             CollectionError::BadRequest { .. } => StorageError::BadRequest {
                 description: overriding_description,
             },
-            CollectionError::Cancelled { .. } => StorageError::ServiceError {
-                description: format!("Operation cancelled: {overriding_description}"),
-                backtrace: None,
+            CollectionError::Cancelled { .. } => StorageError::Cancelled {
+                description: overriding_description,
             },
             CollectionError::InconsistentShardFailure { ref first_err, .. } => {
                 StorageError::from_inconsistent_shard_failure(
@@ -119,9 +145,8 @@ impl StorageError {
             CollectionError::ForwardProxyError { error, .. } => {
                 Self::from_inconsistent_shard_failure(*error, overriding_description)
             }
-            CollectionError::OutOfMemory { .. } => StorageError::ServiceError {
+            CollectionError::OutOfMemory { .. } => StorageError::OutOfMemory {
                 description: overriding_description,
-                backtrace: None,
             },
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: overriding_description,
@@ -134,6 +159,9 @@ impl StorageError {
                 backtrace: None,
             },
             CollectionError::StrictMode { description } => StorageError::Forbidden { description },
+            CollectionError::ResourceExhausted { .. } => StorageError::ResourceExhausted {
+                description: overriding_description,
+            },
         }
     }
 }
@@ -153,10 +181,7 @@ impl From<CollectionError> for StorageError {
                 backtrace,
             },
             CollectionError::BadRequest { description } => StorageError::BadRequest { description },
-            CollectionError::Cancelled { description } => StorageError::ServiceError {
-                description: format!("Operation cancelled: {description}"),
-                backtrace: None,
-            },
+            CollectionError::Cancelled { description } => StorageError::Cancelled { description },
             CollectionError::InconsistentShardFailure { ref first_err, .. } => {
                 let full_description = format!("{}", &err);
                 StorageError::from_inconsistent_shard_failure(*first_err.clone(), full_description)
@@ -168,9 +193,8 @@ impl From<CollectionError> for StorageError {
                 let full_description = format!("{error}");
                 StorageError::from_inconsistent_shard_failure(*error, full_description)
             }
-            CollectionError::OutOfMemory { .. } => StorageError::ServiceError {
-                description: format!("{err}"),
-                backtrace: None,
+            CollectionError::OutOfMemory { description, free } => StorageError::OutOfMemory {
+                description: format!("{description}, free: {free}"),
             },
             CollectionError::Timeout { .. } => StorageError::Timeout {
                 description: format!("{err}"),
@@ -183,6 +207,9 @@ impl From<CollectionError> for StorageError {
                 backtrace: None,
             },
             CollectionError::StrictMode { description } => StorageError::Forbidden { description },
+            CollectionError::ResourceExhausted { description } => {
+                StorageError::ResourceExhausted { description }
+            }
         }
     }
 }
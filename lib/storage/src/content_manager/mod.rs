@@ -88,6 +88,16 @@ pub mod consensus_ops {
             )))
         }
 
+        pub fn start_automatic_resharding(
+            collection_id: CollectionId,
+            reshard_key: ReshardKey,
+        ) -> Self {
+            ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::Resharding(
+                collection_id,
+                ReshardingOperation::StartAutomatic(reshard_key),
+            )))
+        }
+
         pub fn abort_resharding(collection_id: CollectionId, reshard_key: ReshardKey) -> Self {
             ConsensusOperations::CollectionMeta(Box::new(CollectionMetaOperations::Resharding(
                 collection_id,
@@ -135,6 +145,8 @@ pub mod consensus_ops {
                     hnsw_config: None,
                     quantization_config: None,
                     sparse_vectors: None,
+                    shard_optimizers_config: None,
+                    new_vectors: None,
                 },
             );
             operation
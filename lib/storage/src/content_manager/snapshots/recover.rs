@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use collection::collection::Collection;
 use collection::common::sha_256::{hash_file, hashes_equal};
 use collection::config::CollectionConfig;
@@ -172,6 +174,32 @@ async fn _do_recover_from_snapshot(
         )));
     }
 
+    // Validate local data of all shards in the snapshot before replacing any of them,
+    // so that an incompatible snapshot leaves the existing collection untouched
+    let mut snapshot_shard_paths = HashMap::new();
+    for shard_id in state.shards.keys() {
+        let shards = latest_shard_paths(tmp_collection_dir.path(), *shard_id).await?;
+
+        let snapshot_shard_path =
+            shards
+                .into_iter()
+                .find_map(
+                    |(snapshot_shard_path, _version, shard_type)| match shard_type {
+                        ShardType::Local => Some(snapshot_shard_path),
+                        ShardType::ReplicaSet => Some(snapshot_shard_path),
+                        ShardType::Remote { .. } => None,
+                        ShardType::Temporary => None,
+                    },
+                );
+
+        if let Some(snapshot_shard_path) = snapshot_shard_path {
+            collection
+                .check_local_shard_snapshot(&snapshot_shard_path)
+                .await?;
+            snapshot_shard_paths.insert(*shard_id, snapshot_shard_path);
+        }
+    }
+
     // Deactivate collection local shards during recovery
     for (shard_id, shard_info) in &state.shards {
         let local_shard_state = shard_info.replicas.get(&this_peer_id);
@@ -195,21 +223,7 @@ async fn _do_recover_from_snapshot(
 
     // Recover shards from the snapshot
     for (shard_id, shard_info) in &state.shards {
-        let shards = latest_shard_paths(tmp_collection_dir.path(), *shard_id).await?;
-
-        let snapshot_shard_path =
-            shards
-                .into_iter()
-                .find_map(
-                    |(snapshot_shard_path, _version, shard_type)| match shard_type {
-                        ShardType::Local => Some(snapshot_shard_path),
-                        ShardType::ReplicaSet => Some(snapshot_shard_path),
-                        ShardType::Remote { .. } => None,
-                        ShardType::Temporary => None,
-                    },
-                );
-
-        if let Some(snapshot_shard_path) = snapshot_shard_path {
+        if let Some(snapshot_shard_path) = snapshot_shard_paths.remove(shard_id) {
             log::debug!(
                 "Recovering shard {} from {}",
                 shard_id,
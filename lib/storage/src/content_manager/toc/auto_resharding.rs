@@ -0,0 +1,130 @@
+use std::collections::HashMap;
+use std::sync::Weak;
+use std::time::Duration;
+
+use collection::operations::cluster_ops::ReshardingDirection;
+use collection::shards::resharding::ReshardKey;
+use tokio::time::MissedTickBehavior;
+
+use super::TableOfContent;
+use crate::content_manager::consensus_ops::ConsensusOperations;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::{Access, CollectionPass};
+
+const AUTO_RESHARDING_ACCESS: Access = Access::full("For automatic resharding");
+
+impl TableOfContent {
+    /// Split oversized shards periodically, according to the automatic resharding config of the
+    /// storage. Returns immediately if it is not configured, or in a single node deployment.
+    ///
+    /// Splits are proposed to consensus, each one adds a shard to the collection by resharding up.
+    /// The peer of the new shard drives the resharding until it finishes, while the collection
+    /// keeps serving reads and writes.
+    pub async fn run_auto_resharding(toc: Weak<Self>) {
+        let Some(config) = toc.upgrade().and_then(|toc| {
+            let config = toc.storage_config.auto_resharding?;
+            if toc.consensus_proposal_sender.is_none() {
+                log::warn!("Automatic resharding is configured, but only applies to a cluster");
+                return None;
+            }
+            Some(config)
+        }) else {
+            return;
+        };
+
+        log::info!(
+            "Splitting shards with more than {} points, checking every {} seconds",
+            config.max_shard_points,
+            config.check_interval_sec,
+        );
+
+        let mut interval =
+            tokio::time::interval(Duration::from_secs(config.check_interval_sec.get()));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+        loop {
+            interval.tick().await;
+
+            let Some(toc) = toc.upgrade() else {
+                return;
+            };
+
+            for collection_pass in toc.all_collections(&AUTO_RESHARDING_ACCESS).await {
+                if let Err(err) = toc
+                    .propose_shard_split(&collection_pass, config.max_shard_points.get())
+                    .await
+                {
+                    log::warn!(
+                        "Failed to propose automatic resharding of collection {collection_pass}: {err}"
+                    );
+                }
+            }
+        }
+    }
+
+    /// Propose to reshard the collection up, if a local shard has more than `max_points` points.
+    ///
+    /// Peers propose splits of their own shards only, so a resharding is proposed at most once.
+    /// If proposals race anyway, consensus rejects all but the first one.
+    async fn propose_shard_split(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        max_points: usize,
+    ) -> Result<(), StorageError> {
+        let Some(proposal_sender) = &self.consensus_proposal_sender else {
+            return Ok(());
+        };
+
+        let collection = self.get_collection(collection_pass).await?;
+        let Some((oversized_shard_id, shard_key)) =
+            collection.oversized_local_shard(max_points).await
+        else {
+            return Ok(());
+        };
+
+        let collection_state = collection.state().await;
+
+        // Same shard ID and peer as when resharding up through the API without a peer
+        let shard_id = collection_state
+            .shards
+            .keys()
+            .copied()
+            .max()
+            .expect("collection must contain shards")
+            + 1;
+
+        let mut shards_on_peers: HashMap<_, usize> = self
+            .channel_service
+            .id_to_address
+            .read()
+            .keys()
+            .map(|peer_id| (*peer_id, 0))
+            .collect();
+        for shard_info in collection_state.shards.values() {
+            for peer_id in shard_info.replicas.keys() {
+                *shards_on_peers.entry(*peer_id).or_default() += 1;
+            }
+        }
+        let peer_id = shards_on_peers
+            .into_iter()
+            .min_by_key(|(_, count)| *count)
+            .map_or(self.this_peer_id, |(peer_id, _)| peer_id);
+
+        let reshard_key = ReshardKey {
+            direction: ReshardingDirection::Up,
+            peer_id,
+            shard_id,
+            shard_key,
+        };
+
+        log::info!(
+            "Shard {collection_pass}:{oversized_shard_id} has more than {max_points} points, \
+             proposing to split it by resharding {reshard_key}",
+        );
+
+        proposal_sender.send(ConsensusOperations::start_automatic_resharding(
+            collection.name(),
+            reshard_key,
+        ))
+    }
+}
@@ -126,6 +126,8 @@ impl TableOfContent {
             optimizers_config,
             quantization_config,
             sparse_vectors,
+            shard_optimizers_config,
+            new_vectors,
         } = operation.update_collection;
         let collection = self
             .get_collection_unchecked(&operation.collection_name)
@@ -144,6 +146,10 @@ impl TableOfContent {
             collection.update_hnsw_config_from_diff(diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(new_vectors) = new_vectors {
+            collection.add_vectors(&new_vectors).await?;
+            recreate_optimizers = true;
+        }
         if let Some(diff) = vectors {
             collection.update_vectors_from_diff(&diff).await?;
             recreate_optimizers = true;
@@ -158,6 +164,12 @@ impl TableOfContent {
             collection.update_sparse_vectors_from_other(&diff).await?;
             recreate_optimizers = true;
         }
+        if let Some(diffs) = shard_optimizers_config {
+            collection
+                .update_shard_optimizer_params_from_diff(diffs)
+                .await?;
+            recreate_optimizers = true;
+        }
         if let Some(changes) = replica_changes {
             collection.handle_replica_changes(changes).await?;
         }
@@ -295,8 +307,10 @@ impl TableOfContent {
             ));
         };
 
+        let automatic = matches!(operation, ReshardingOperation::StartAutomatic(_));
+
         match operation {
-            ReshardingOperation::Start(key) => {
+            ReshardingOperation::Start(key) | ReshardingOperation::StartAutomatic(key) => {
                 let consensus = match self.shard_transfer_dispatcher.lock().as_ref() {
                     Some(consensus) => Box::new(consensus.clone()),
                     None => {
@@ -331,7 +345,7 @@ impl TableOfContent {
                 };
 
                 collection
-                    .start_resharding(key, consensus, on_finish, on_failure)
+                    .start_resharding(key, automatic, consensus, on_finish, on_failure)
                     .await?;
             }
 
@@ -570,6 +584,7 @@ impl TableOfContent {
                     to: transfer_restart.to,
                     sync: old_transfer.sync, // Preserve sync flag from the old transfer
                     method: Some(transfer_restart.method),
+                    target_created: false,
                 };
 
                 Box::pin(
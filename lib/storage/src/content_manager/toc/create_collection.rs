@@ -11,6 +11,7 @@ use collection::shards::collection_shard_distribution::CollectionShardDistributi
 use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::{PeerId, ShardId};
 use collection::shards::CollectionId;
+use validator::Validate as _;
 
 use super::TableOfContent;
 use crate::content_manager::collection_meta_ops::*;
@@ -36,6 +37,7 @@ impl TableOfContent {
             shard_number,
             sharding_method,
             on_disk_payload,
+            payload_compression,
             hnsw_config: hnsw_config_diff,
             wal_config: wal_config_diff,
             optimizers_config: optimizers_config_diff,
@@ -45,6 +47,7 @@ impl TableOfContent {
             quantization_config,
             sparse_vectors,
             strict_mode_config,
+            ttl_config,
         } = operation;
 
         self.collections
@@ -151,6 +154,7 @@ impl TableOfContent {
                 },
             )?,
             read_fan_out_factor: None,
+            payload_compression,
         };
         let wal_config = match wal_config_diff {
             None => self.storage_config.wal.clone(),
@@ -166,6 +170,9 @@ impl TableOfContent {
             None => self.storage_config.hnsw_index.clone(),
             Some(diff) => diff.update(&self.storage_config.hnsw_index)?,
         };
+        hnsw_config
+            .validate()
+            .map_err(|err| StorageError::bad_input(format!("Invalid HNSW config: {err}")))?;
 
         let quantization_config = match quantization_config {
             None => self
@@ -188,6 +195,8 @@ impl TableOfContent {
             hnsw_config,
             quantization_config,
             strict_mode_config,
+            ttl_config,
+            shard_optimizer_config: Default::default(),
         };
         let collection = Collection::new(
             collection_name.to_string(),
@@ -0,0 +1,94 @@
+use std::path::{Path, PathBuf};
+
+use common::cpu::CpuBudget;
+use io::file_operations::{atomic_save_json, read_json};
+use serde::{Deserialize, Serialize};
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageResult;
+use crate::rbac::{Access, AccessRequirements};
+
+pub const MAINTENANCE_FILE: &str = "maintenance.json";
+
+/// Maintenance options of this peer, persisted in its storage directory.
+///
+/// The options are peer-local: they are not replicated through consensus and only affect the peer
+/// they are set on.
+#[derive(Debug, Default, Deserialize, Serialize)]
+struct MaintenanceState {
+    pause_optimizations: bool,
+}
+
+impl TableOfContent {
+    fn maintenance_path(storage_path: &str) -> PathBuf {
+        Path::new(storage_path).join(MAINTENANCE_FILE)
+    }
+
+    /// Restore maintenance options persisted by a previous run of this peer.
+    pub(super) fn restore_maintenance_state(
+        storage_path: &str,
+        optimizer_cpu_budget: &CpuBudget,
+    ) -> StorageResult<()> {
+        let path = Self::maintenance_path(storage_path);
+        if !path.exists() {
+            return Ok(());
+        }
+
+        let state: MaintenanceState = read_json(&path)?;
+        if state.pause_optimizations {
+            log::warn!("Optimizations are paused on this peer, resume them with POST /maintenance");
+        }
+        optimizer_cpu_budget.set_paused(state.pause_optimizations);
+        Ok(())
+    }
+
+    pub fn are_optimizations_paused(&self) -> bool {
+        self.optimizer_cpu_budget.is_paused()
+    }
+
+    /// Pause or resume optimizations in all collections of this peer, without changing their
+    /// optimizer configs. Returns whether optimizations were paused before.
+    ///
+    /// While paused, no new optimizations are started. Running optimizations are not interrupted
+    /// and finish normally, so no optimization progress is lost. Once resumed, collections pick up
+    /// the remaining optimizations.
+    ///
+    /// The setting is peer-local, it is not propagated to other peers of the cluster. It is
+    /// persisted in the storage directory of this peer and survives restarts.
+    pub fn set_optimizations_paused(&self, paused: bool) -> StorageResult<bool> {
+        let _guard = self.maintenance_lock.lock();
+
+        atomic_save_json(
+            &Self::maintenance_path(self.storage_path()),
+            &MaintenanceState {
+                pause_optimizations: paused,
+            },
+        )?;
+
+        let was_paused = self.optimizer_cpu_budget.set_paused(paused);
+        if paused != was_paused {
+            if paused {
+                log::info!("Optimizations paused");
+            } else {
+                log::info!("Optimizations resumed");
+            }
+        }
+        Ok(was_paused)
+    }
+
+    /// Persist WAL and segments of all local shards of the collection to disk.
+    ///
+    /// Only shards of this peer are flushed. Meant to be called right before taking an external,
+    /// filesystem-level snapshot of the storage of this peer.
+    pub async fn flush_collection(
+        &self,
+        collection_name: &str,
+        access: Access,
+    ) -> StorageResult<()> {
+        let collection_pass = access
+            .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+
+        self.get_collection(&collection_pass).await?.flush().await?;
+        Ok(())
+    }
+}
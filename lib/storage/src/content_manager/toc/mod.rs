@@ -1,10 +1,13 @@
+mod auto_resharding;
 mod collection_container;
 use common::types::TelemetryDetail;
 mod collection_meta_ops;
 mod create_collection;
 mod locks;
+mod maintenance;
 mod point_ops;
 mod point_ops_internal;
+mod snapshot_schedule;
 mod snapshots;
 mod temp_directories;
 pub mod transfer;
@@ -63,6 +66,8 @@ pub struct TableOfContent {
     consensus_proposal_sender: Option<OperationSender>,
     is_write_locked: AtomicBool,
     lock_error_message: parking_lot::Mutex<Option<String>>,
+    /// Serializes changes of the persisted maintenance options of this peer.
+    maintenance_lock: parking_lot::Mutex<()>,
     /// Prevent DDoS of too many concurrent updates in distributed mode.
     /// One external update usually triggers multiple internal updates, which breaks internal
     /// timings. For example, the health check timing and consensus timing.
@@ -160,6 +165,8 @@ impl TableOfContent {
         let alias_path = Path::new(&storage_config.storage_path).join(ALIASES_PATH);
         let alias_persistence =
             AliasPersistence::open(alias_path).expect("Can't open database by the provided config");
+        Self::restore_maintenance_state(&storage_config.storage_path, &optimizer_cpu_budget)
+            .expect("Can't restore maintenance options of this peer");
 
         let rate_limiter = match storage_config.performance.update_rate_limit {
             Some(limit) => Some(Semaphore::new(limit)),
@@ -191,6 +198,7 @@ impl TableOfContent {
             consensus_proposal_sender,
             is_write_locked: AtomicBool::new(false),
             lock_error_message: parking_lot::Mutex::new(None),
+            maintenance_lock: Default::default(),
             update_rate_limiter: rate_limiter,
             collection_create_lock: Default::default(),
             shard_transfer_dispatcher: Default::default(),
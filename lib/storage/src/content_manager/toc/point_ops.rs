@@ -1,26 +1,31 @@
+use std::sync::Arc;
 use std::time::Duration;
 
+use cancel::CancellationToken;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
+use collection::collection::point_dump::DEFAULT_POINT_DUMP_BATCH_SIZE;
+use collection::collection::point_ops::scroll_pages;
 use collection::collection::Collection;
 use collection::grouping::group_by::GroupRequest;
 use collection::grouping::GroupBy;
 use collection::operations::consistency_params::ReadConsistency;
-use collection::operations::point_ops::WriteOrdering;
+use collection::operations::idempotency::IdempotencyKey;
+use collection::operations::point_ops::{WriteConsistency, WriteOrdering};
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::*;
 use collection::operations::universal_query::collection_query::CollectionQueryRequest;
 use collection::operations::{CollectionUpdateOperations, OperationWithClockTag};
-use collection::{discovery, recommendations};
+use collection::{discovery, neighbors, recommendations};
 use futures::stream::FuturesUnordered;
-use futures::TryStreamExt as _;
+use futures::{Stream, TryStreamExt as _};
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::types::{ScoredPoint, ShardKey};
 
 use super::TableOfContent;
 use crate::content_manager::errors::{StorageError, StorageResult};
-use crate::rbac::Access;
+use crate::rbac::{Access, AccessRequirements};
 
 impl TableOfContent {
     /// Recommend points using positive and negative example from the request
@@ -57,6 +62,39 @@ impl TableOfContent {
         .map_err(|err| err.into())
     }
 
+    /// Search the nearest neighbors of a stored point, by its stored vector
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_name` - in what collection do we search
+    /// * `request` - [`NeighborsRequestInternal`]
+    ///
+    /// # Result
+    ///
+    /// Points with search score
+    pub async fn neighbors(
+        &self,
+        collection_name: &str,
+        mut request: NeighborsRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        shard_selector: ShardSelectorInternal,
+        access: Access,
+        timeout: Option<Duration>,
+    ) -> StorageResult<Vec<ScoredPoint>> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        neighbors::neighbors_by_id(
+            request,
+            &collection,
+            read_consistency,
+            shard_selector,
+            timeout,
+        )
+        .await
+        .map_err(|err| err.into())
+    }
+
     /// Recommend points in a batching fashion using positive and negative example from the request
     ///
     /// # Arguments
@@ -105,10 +143,12 @@ impl TableOfContent {
     /// * `shard_selection` - which local shard to use
     /// * `timeout` - how long to wait for the response
     /// * `read_consistency` - consistency level
+    /// * `cancel` - stops the search early, e.g. once the client disconnected
     ///
     /// # Result
     ///
     /// Points with search score
+    #[allow(clippy::too_many_arguments)]
     pub async fn core_search_batch(
         &self,
         collection_name: &str,
@@ -117,6 +157,7 @@ impl TableOfContent {
         shard_selection: ShardSelectorInternal,
         access: Access,
         timeout: Option<Duration>,
+        cancel: CancellationToken,
     ) -> StorageResult<Vec<Vec<ScoredPoint>>> {
         let mut collection_pass = None;
         for request in &mut request.searches {
@@ -128,7 +169,7 @@ impl TableOfContent {
 
         let collection = self.get_collection(&collection_pass).await?;
         collection
-            .core_search_batch(request, read_consistency, shard_selection, timeout)
+            .core_search_batch(request, read_consistency, shard_selection, timeout, cancel)
             .await
             .map_err(|err| err.into())
     }
@@ -301,6 +342,104 @@ impl TableOfContent {
             .map_err(|err| err.into())
     }
 
+    /// Scroll all points matching the request as a stream, see [`Collection::scroll_stream`]
+    ///
+    /// Access and the existence of the collection are checked before the stream is returned. The
+    /// collection is looked up again for every page, so that no lock on the collections is held
+    /// while the consumer of the stream is slow to take the points of a page.
+    ///
+    /// # Result
+    ///
+    /// Stream of all points matching the request, `request.limit` is the size of a page
+    pub async fn scroll_stream(
+        self: &Arc<Self>,
+        collection_name: &str,
+        mut request: ScrollRequestInternal,
+        read_consistency: Option<ReadConsistency>,
+        timeout: Option<Duration>,
+        shard_selection: ShardSelectorInternal,
+        access: Access,
+    ) -> StorageResult<impl Stream<Item = StorageResult<api::rest::Record>> + 'static> {
+        let collection_pass = access.check_point_op(collection_name, &mut request)?;
+
+        // Resolve aliases once, so that all pages are read from the same collection
+        let collection_name = self.get_collection(&collection_pass).await?.name();
+        let toc = Arc::clone(self);
+
+        let records = scroll_pages(request, move |request| {
+            let toc = Arc::clone(&toc);
+            let collection_name = collection_name.clone();
+            let read_consistency = read_consistency.clone();
+            let shard_selection = shard_selection.clone();
+
+            async move {
+                let collection = toc.get_collection_unchecked(&collection_name).await?;
+                let page = collection
+                    .scroll_by(request, read_consistency, &shard_selection, timeout)
+                    .await?;
+                Ok::<_, StorageError>(page)
+            }
+        });
+
+        Ok(records)
+    }
+
+    /// Export all points of the collection as a binary point dump
+    ///
+    /// Requires read access to the whole collection.
+    ///
+    /// # Result
+    ///
+    /// The dump, see [`collection::collection::point_dump`] for its format, and the number of
+    /// exported points.
+    pub async fn export_points(
+        &self,
+        collection_name: &str,
+        batch_size: Option<usize>,
+        access: Access,
+    ) -> StorageResult<(Vec<u8>, usize)> {
+        let collection_pass =
+            access.check_collection_access(collection_name, AccessRequirements::new().whole())?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        let mut dump = Vec::new();
+        let exported = collection
+            .export_points(
+                &mut dump,
+                batch_size.unwrap_or(DEFAULT_POINT_DUMP_BATCH_SIZE),
+            )
+            .await?;
+        Ok((dump, exported))
+    }
+
+    /// Upsert all points of a binary point dump created by [`TableOfContent::export_points`]
+    ///
+    /// Requires write access to the whole collection.
+    ///
+    /// # Result
+    ///
+    /// Number of imported points.
+    pub async fn import_points(
+        &self,
+        collection_name: &str,
+        dump: &[u8],
+        batch_size: Option<usize>,
+        access: Access,
+    ) -> StorageResult<usize> {
+        let collection_pass = access
+            .check_collection_access(collection_name, AccessRequirements::new().write().whole())?;
+        self.check_write_lock()?;
+
+        let collection = self.get_collection(&collection_pass).await?;
+        let imported = collection
+            .import_points(
+                &mut &*dump,
+                batch_size.unwrap_or(DEFAULT_POINT_DUMP_BATCH_SIZE),
+            )
+            .await?;
+        Ok(imported)
+    }
+
     pub async fn query_batch(
         &self,
         collection_name: &str,
@@ -376,19 +515,31 @@ impl TableOfContent {
     ///
     /// When it is cancelled, the operation may not be applied on some shard keys. But, all nodes
     /// are guaranteed to be consistent.
+    #[allow(clippy::too_many_arguments)]
     async fn _update_shard_keys(
         collection: &Collection,
         shard_keys: Vec<ShardKey>,
         operation: CollectionUpdateOperations,
+        idempotency_key: Option<IdempotencyKey>,
+        skip_invalid_points: bool,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
     ) -> StorageResult<UpdateResult> {
         // `Collection::update_from_client` is cancel safe, so this method is cancel safe.
 
         let updates: FuturesUnordered<_> = shard_keys
             .into_iter()
             .map(|shard_key| {
-                collection.update_from_client(operation.clone(), wait, ordering, Some(shard_key))
+                collection.update_from_client_idempotent(
+                    operation.clone(),
+                    idempotency_key.clone(),
+                    skip_invalid_points,
+                    wait,
+                    ordering,
+                    consistency,
+                    Some(shard_key),
+                )
             })
             .collect();
 
@@ -405,11 +556,48 @@ impl TableOfContent {
     ///
     /// This method is cancel safe.
     pub async fn update(
+        &self,
+        collection_name: &str,
+        operation: OperationWithClockTag,
+        wait: bool,
+        ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
+        shard_selector: ShardSelectorInternal,
+        access: Access,
+    ) -> StorageResult<UpdateResult> {
+        self.update_idempotent(
+            collection_name,
+            operation,
+            None,
+            false,
+            wait,
+            ordering,
+            consistency,
+            shard_selector,
+            access,
+        )
+        .await
+    }
+
+    /// Same as [`TableOfContent::update`], but deduplicates client retries by idempotency key.
+    ///
+    /// The key is ignored for updates forwarded from other peers, which target a shard by id.
+    /// So is `skip_invalid_points`, as invalid points are left out by the peer which received the
+    /// update from the client.
+    ///
+    /// # Cancel safety
+    ///
+    /// This method is cancel safe.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn update_idempotent(
         &self,
         collection_name: &str,
         mut operation: OperationWithClockTag,
+        idempotency_key: Option<IdempotencyKey>,
+        skip_invalid_points: bool,
         wait: bool,
         ordering: WriteOrdering,
+        consistency: Option<WriteConsistency>,
         shard_selector: ShardSelectorInternal,
         access: Access,
     ) -> StorageResult<UpdateResult> {
@@ -469,7 +657,15 @@ impl TableOfContent {
         let res = match shard_selector {
             ShardSelectorInternal::Empty => {
                 collection
-                    .update_from_client(operation.operation, wait, ordering, None)
+                    .update_from_client_idempotent(
+                        operation.operation,
+                        idempotency_key,
+                        skip_invalid_points,
+                        wait,
+                        ordering,
+                        consistency,
+                        None,
+                    )
                     .await?
             }
 
@@ -477,15 +673,26 @@ impl TableOfContent {
                 let shard_keys = collection.get_shard_keys().await;
                 if shard_keys.is_empty() {
                     collection
-                        .update_from_client(operation.operation, wait, ordering, None)
+                        .update_from_client_idempotent(
+                            operation.operation,
+                            idempotency_key,
+                            skip_invalid_points,
+                            wait,
+                            ordering,
+                            consistency,
+                            None,
+                        )
                         .await?
                 } else {
                     Self::_update_shard_keys(
                         &collection,
                         shard_keys,
                         operation.operation,
+                        idempotency_key,
+                        skip_invalid_points,
                         wait,
                         ordering,
+                        consistency,
                     )
                     .await?
                 }
@@ -493,7 +700,15 @@ impl TableOfContent {
 
             ShardSelectorInternal::ShardKey(shard_key) => {
                 collection
-                    .update_from_client(operation.operation, wait, ordering, Some(shard_key))
+                    .update_from_client_idempotent(
+                        operation.operation,
+                        idempotency_key,
+                        skip_invalid_points,
+                        wait,
+                        ordering,
+                        consistency,
+                        Some(shard_key),
+                    )
                     .await?
             }
 
@@ -502,15 +717,18 @@ impl TableOfContent {
                     &collection,
                     shard_keys,
                     operation.operation,
+                    idempotency_key,
+                    skip_invalid_points,
                     wait,
                     ordering,
+                    consistency,
                 )
                 .await?
             }
 
             ShardSelectorInternal::ShardId(shard_selection) => {
                 collection
-                    .update_from_peer(operation, shard_selection, wait, ordering)
+                    .update_from_peer(operation, shard_selection, wait, ordering, consistency)
                     .await?
             }
         };
@@ -0,0 +1,86 @@
+use std::num::NonZeroUsize;
+use std::sync::{Arc, Weak};
+use std::time::Duration;
+
+use collection::operations::snapshot_ops::SnapshotDescription;
+use tokio::time::MissedTickBehavior;
+
+use super::TableOfContent;
+use crate::content_manager::errors::StorageError;
+use crate::rbac::{Access, CollectionPass};
+
+const SCHEDULE_ACCESS: Access = Access::full("For scheduled snapshots");
+
+impl TableOfContent {
+    /// Create snapshots of all collections periodically, according to the snapshot schedule of
+    /// the storage config. Returns immediately if there is no schedule.
+    ///
+    /// Each collection is snapshotted in its own task, so that a slow snapshot doesn't delay the
+    /// snapshots of other collections. The schedule stops once the table of content is dropped.
+    pub async fn run_snapshot_schedule(toc: Weak<Self>) {
+        let Some(schedule) = toc
+            .upgrade()
+            .and_then(|toc| toc.storage_config.snapshots_config.schedule.clone())
+        else {
+            return;
+        };
+
+        log::info!(
+            "Creating snapshots every {} seconds, keeping the last {}",
+            schedule.interval_sec,
+            schedule.retention,
+        );
+
+        let mut interval = tokio::time::interval(Duration::from_secs(schedule.interval_sec.get()));
+        interval.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        // The first tick completes immediately, don't create snapshots right on startup
+        interval.tick().await;
+
+        loop {
+            interval.tick().await;
+
+            let Some(toc) = toc.upgrade() else {
+                return;
+            };
+
+            for collection_pass in toc.all_collections(&SCHEDULE_ACCESS).await {
+                tokio::spawn(Self::create_scheduled_snapshot(
+                    Arc::downgrade(&toc),
+                    collection_pass,
+                    schedule.retention,
+                ));
+            }
+        }
+    }
+
+    async fn create_scheduled_snapshot(
+        toc: Weak<Self>,
+        collection_pass: CollectionPass<'static>,
+        retention: NonZeroUsize,
+    ) {
+        let Some(toc) = toc.upgrade() else {
+            return;
+        };
+
+        // Errors of the snapshot itself are reported by the collection
+        if let Err(err) = toc
+            .create_collection_scheduled_snapshot(&collection_pass, retention)
+            .await
+        {
+            log::debug!("Scheduled snapshot of collection {collection_pass} not created: {err}");
+        }
+    }
+
+    async fn create_collection_scheduled_snapshot(
+        &self,
+        collection_pass: &CollectionPass<'_>,
+        retention: NonZeroUsize,
+    ) -> Result<Option<SnapshotDescription>, StorageError> {
+        let collection = self.get_collection(collection_pass).await?;
+        // Same temp dir as for snapshots created through the API, see `create_snapshot`
+        let temp_dir = self.optional_temp_or_storage_temp_path()?;
+        Ok(collection
+            .create_scheduled_snapshot(&temp_dir, self.this_peer_id, retention)
+            .await?)
+    }
+}
@@ -130,6 +130,7 @@ impl TableOfContent {
                 to: to_peer,
                 sync,
                 method,
+                target_created: false,
             };
             let operation = ConsensusOperations::start_transfer(collection_name, transfer_request);
             proposal_sender.send(operation)?;
@@ -6,11 +6,14 @@ use api::rest::LookupLocation;
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::grouping::group_by::{GroupRequest, SourceRequest};
 use collection::lookup::WithLookup;
-use collection::operations::payload_ops::{DeletePayloadOp, PayloadOps, SetPayloadOp};
+use collection::operations::payload_ops::{
+    CopyPayloadFieldOp, DeletePayloadOp, PayloadOps, SetPayloadOp,
+};
 use collection::operations::point_ops::{PointIdsList, PointOperations};
 use collection::operations::types::{
     ContextExamplePair, CoreSearchRequest, CountRequestInternal, DiscoverRequestInternal,
-    PointRequestInternal, RecommendExample, RecommendRequestInternal, ScrollRequestInternal,
+    NeighborsRequestInternal, PointRequestInternal, RecommendExample, RecommendRequestInternal,
+    ScrollRequestInternal,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionPrefetch, CollectionQueryRequest, Query, VectorInput, VectorQuery,
@@ -187,6 +190,27 @@ impl CheckableCollectionOperation for RecommendRequestInternal {
     }
 }
 
+impl CheckableCollectionOperation for NeighborsRequestInternal {
+    fn access_requirements(&self) -> AccessRequirements {
+        AccessRequirements {
+            write: false,
+            manage: false,
+            whole: false,
+        }
+    }
+
+    fn check_access(
+        &mut self,
+        view: CollectionAccessView<'_>,
+        _access: &CollectionAccessList,
+    ) -> Result<(), StorageError> {
+        // The vector of the point is read regardless of the payload constraint
+        view.check_whole_access()?;
+        view.apply_filter(&mut self.filter);
+        Ok(())
+    }
+}
+
 impl CheckableCollectionOperation for PointRequestInternal {
     fn access_requirements(&self) -> AccessRequirements {
         AccessRequirements {
@@ -286,8 +310,7 @@ impl CheckableCollectionOperation for DiscoverRequestInternal {
         if let Some(target) = &self.target {
             view.check_recommend_example(target)?;
         }
-        for ContextExamplePair { positive, negative } in self.context.iter().flat_map(|c| c.iter())
-        {
+        for (ContextExamplePair { positive, negative }, _weight) in self.weighted_pairs() {
             view.check_recommend_example(positive)?;
             view.check_recommend_example(negative)?;
         }
@@ -430,7 +453,9 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
     ) -> Result<(), StorageError> {
         match self {
             CollectionUpdateOperations::PointOperation(op) => match op {
-                PointOperations::UpsertPoints(_) => {
+                PointOperations::UpsertPoints(_)
+                | PointOperations::UpsertPointsVersioned(_)
+                | PointOperations::UpsertPointsWithTtl(_) => {
                     view.check_whole_access()?;
                 }
                 PointOperations::DeletePoints { ids } => {
@@ -530,6 +555,27 @@ impl CheckableCollectionOperation for CollectionUpdateOperations {
                         points,
                         filter,
                         key: _, // TODO: validate
+                    })
+                    | PayloadOps::MergePatchPayload(SetPayloadOp {
+                        payload: _, // TODO: validate
+                        points,
+                        filter,
+                        key: _, // TODO: validate
+                    }) => {
+                        let filter = filter.get_or_insert_with(Default::default);
+                        if let Some(points) = take(points) {
+                            *filter = take(filter).merge_owned(make_filter_from_ids(points));
+                        }
+
+                        // Reject as not implemented
+                        return incompatible_with_payload_constraint(view.collection);
+                    }
+                    PayloadOps::CopyPayloadField(CopyPayloadFieldOp {
+                        from: _, // TODO: validate
+                        to: _,   // TODO: validate
+                        mode: _,
+                        points,
+                        filter,
                     }) => {
                         let filter = filter.get_or_insert_with(Default::default);
                         if let Some(points) = take(points) {
@@ -657,13 +703,14 @@ mod tests_ops {
         self, BatchVectorStruct, LookupLocation, OrderByInterface, RecommendStrategy,
         SearchRequestInternal, VectorStruct,
     };
-    use collection::operations::payload_ops::PayloadOpsDiscriminants;
+    use collection::operations::payload_ops::{PayloadCopyMode, PayloadOpsDiscriminants};
     use collection::operations::point_ops::{
         Batch, PointInsertOperationsInternal, PointInsertOperationsInternalDiscriminants,
-        PointOperationsDiscriminants, PointStruct, PointSyncOperation,
+        PointOperationsDiscriminants, PointStruct, PointSyncOperation, PointVersion,
+        TtlInsertOperationInternal, TtlStamp, VersionedInsertOperationInternal,
     };
     use collection::operations::query_enum::QueryEnum;
-    use collection::operations::types::UsingVector;
+    use collection::operations::types::{UsingVector, WeightedContext};
     use collection::operations::vector_ops::{
         PointVectors, UpdateVectorsOp, VectorOperationsDiscriminants,
     };
@@ -761,6 +808,7 @@ mod tests_ops {
                 vector: Some("vector".to_string()),
                 shard_key: None,
             }),
+            mmr_lambda: None,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -822,6 +870,7 @@ mod tests_ops {
             ids: vec![PointIdType::NumId(12345)],
             with_payload: None,
             with_vector: WithVector::Bool(true),
+            with_version: false,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -853,6 +902,10 @@ mod tests_ops {
             with_payload: Some(WithPayloadInterface::Bool(true)),
             with_vector: Some(WithVector::Bool(true)),
             score_threshold: Some(42.0),
+            score_modifier: None,
+            rerank: None,
+            max_score_threshold: None,
+            with_distance: false,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -881,6 +934,8 @@ mod tests_ops {
         let op = CountRequestInternal {
             filter: None,
             exact: false,
+            consistent: false,
+            allow_partial: false,
         };
 
         assert_allowed(&op, &Access::Global(GlobalAccessMode::Manage));
@@ -917,6 +972,10 @@ mod tests_ops {
                 with_payload: Some(WithPayloadInterface::Bool(true)),
                 with_vector: Some(WithVector::Bool(true)),
                 score_threshold: Some(42.0),
+                score_modifier: None,
+                rerank: None,
+                max_score_threshold: None,
+                with_distance: None,
             }),
             group_by: "path".parse().unwrap(),
             group_size: 100,
@@ -988,6 +1047,7 @@ mod tests_ops {
                 positive: RecommendExample::Dense(vec![0.0, 1.0, 2.0]),
                 negative: RecommendExample::Dense(vec![0.0, 1.0, 2.0]),
             }]),
+            weighted_context: None,
             filter: None,
             params: Some(SearchParams::default()),
             limit: 100,
@@ -1061,6 +1121,22 @@ mod tests_ops {
                 .add("col2", false, true)
                 .into(),
         );
+        assert_forbidden(
+            &DiscoverRequestInternal {
+                weighted_context: Some(vec![WeightedContext {
+                    pairs: vec![ContextExamplePair {
+                        positive: RecommendExample::PointId(ExtendedPointId::NumId(12345)),
+                        negative: RecommendExample::Dense(vec![0.0, 1.0, 2.0]),
+                    }],
+                    weight: 2,
+                }]),
+                ..op.clone()
+            },
+            &AccessCollectionBuilder::new()
+                .add("col", false, false)
+                .add("col2", false, true)
+                .into(),
+        );
 
         // lookup_from requires read access
         assert_forbidden(
@@ -1148,6 +1224,7 @@ mod tests_ops {
                                 id: ExtendedPointId::NumId(12345),
                                 vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
                                 payload: None,
+                                vector_metadata: None,
                             }])
                         }
                     };
@@ -1159,6 +1236,53 @@ mod tests_ops {
                 }
             }
 
+            PointOperationsDiscriminants::UpsertPointsVersioned => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsVersioned(VersionedInsertOperationInternal {
+                        points_op: PointInsertOperationsInternal::PointsList(vec![PointStruct {
+                            id: ExtendedPointId::NumId(12345),
+                            vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                            payload: None,
+                            vector_metadata: None,
+                        }]),
+                        expected_versions: vec![PointVersion {
+                            id: ExtendedPointId::NumId(12345),
+                            version: 1,
+                        }],
+                        timestamp: None,
+                        versions: Vec::new(),
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
+            PointOperationsDiscriminants::UpsertPointsWithTtl => {
+                let op = CollectionUpdateOperations::PointOperation(
+                    PointOperations::UpsertPointsWithTtl(TtlInsertOperationInternal {
+                        upsert: VersionedInsertOperationInternal {
+                            points_op: PointInsertOperationsInternal::PointsList(vec![
+                                PointStruct {
+                                    id: ExtendedPointId::NumId(12345),
+                                    vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
+                                    payload: None,
+                                    vector_metadata: None,
+                                },
+                            ]),
+                            expected_versions: Vec::new(),
+                            timestamp: None,
+                            versions: Vec::new(),
+                        },
+                        stamp: TtlStamp {
+                            key: "created_at".to_string(),
+                            timestamp: 1_000,
+                            set_on_insert: true,
+                            refresh_on_update: false,
+                        },
+                    }),
+                );
+                assert_requires_whole_write_access(&op);
+            }
+
             PointOperationsDiscriminants::DeletePoints => {
                 let op =
                     CollectionUpdateOperations::PointOperation(PointOperations::DeletePoints {
@@ -1182,6 +1306,7 @@ mod tests_ops {
                         from_id: None,
                         to_id: None,
                         points: Vec::new(),
+                        versions: Vec::new(),
                     },
                 ));
                 assert_requires_whole_write_access(&op);
@@ -1232,6 +1357,7 @@ mod tests_ops {
                             id: ExtendedPointId::NumId(12345),
                             vector: VectorStruct::Single(vec![0.0, 1.0, 2.0]),
                         }],
+                        delete_vectors: Vec::new(),
                     }),
                 );
                 assert_requires_whole_write_access(&op);
@@ -1326,6 +1452,23 @@ mod tests_ops {
                         key: None,
                     })
                 }
+                PayloadOpsDiscriminants::MergePatchPayload => {
+                    PayloadOps::MergePatchPayload(SetPayloadOp {
+                        payload: Payload::default(),
+                        points: Some(vec![ExtendedPointId::NumId(12345)]),
+                        filter: None,
+                        key: None,
+                    })
+                }
+                PayloadOpsDiscriminants::CopyPayloadField => {
+                    PayloadOps::CopyPayloadField(CopyPayloadFieldOp {
+                        from: "a".parse().unwrap(),
+                        to: "b".parse().unwrap(),
+                        mode: PayloadCopyMode::Copy,
+                        points: Some(vec![ExtendedPointId::NumId(12345)]),
+                        filter: None,
+                    })
+                }
             };
 
             let op = CollectionUpdateOperations::PayloadOperation(inner);
@@ -1,8 +1,9 @@
 use std::collections::HashMap;
-use std::num::NonZeroUsize;
+use std::num::{NonZeroU64, NonZeroUsize};
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use collection::collection::query_cache::QueryCacheConfig;
 use collection::common::snapshots_manager::SnapShotsConfig;
 use collection::config::WalConfig;
 use collection::operations::config_diff::OptimizersConfigDiff;
@@ -11,6 +12,7 @@ use collection::operations::shared_storage_config::{
 };
 use collection::operations::types::{NodeType, PeerMetadata};
 use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::local_shard::segment_load::SegmentLoadConfig;
 use collection::shards::shard::PeerId;
 use collection::shards::transfer::ShardTransferMethod;
 use memory::madvise;
@@ -33,6 +35,10 @@ pub struct PerformanceConfig {
     pub update_rate_limit: Option<usize>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub search_timeout_sec: Option<usize>,
+    /// How long a read with session consistency waits for a replica to apply the updates of
+    /// the session, in seconds. Default: 10
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_read_timeout_sec: Option<usize>,
     /// CPU budget, how many CPUs (threads) to allocate for an optimization job.
     /// If 0 - auto selection, keep 1 or more CPUs unallocated depending on CPU size
     /// If negative - subtract this relative number of CPUs from the available CPUs.
@@ -80,6 +86,12 @@ pub struct StorageConfig {
     pub node_type: NodeType,
     #[serde(default)]
     pub update_queue_size: Option<usize>,
+    /// Max number of updates queued or waiting to be queued per shard. New updates are rejected
+    /// with a resource exhausted error while the limit is reached, and may be retried later.
+    /// If not set, updates wait until there is space in the update queue.
+    #[validate(range(min = 1))]
+    #[serde(default)]
+    pub max_pending_updates: Option<usize>,
     #[serde(default)]
     pub handle_collection_load_errors: bool,
     #[serde(default)]
@@ -91,31 +103,81 @@ pub struct StorageConfig {
     pub recovery_mode: Option<String>,
     #[serde(default)]
     pub update_concurrency: Option<NonZeroUsize>,
+    /// Max number of shards requested concurrently by a single search or query request.
+    /// If not set, all shards are requested at once.
+    #[serde(default)]
+    pub search_shard_concurrency: Option<NonZeroUsize>,
+    /// Max number of segments searched concurrently by a single search request on a shard.
+    /// If not set, all segments are searched at once.
+    #[serde(default)]
+    pub search_segment_concurrency: Option<NonZeroUsize>,
     /// Default method used for transferring shards.
     #[serde(default)]
     pub shard_transfer_method: Option<ShardTransferMethod>,
     /// Default values for collections.
     #[serde(default)]
     pub collection: Option<CollectionConfigDefaults>,
+    /// If set, results of search and query requests are cached per collection, until the
+    /// collection is updated. Only applies to a single node deployment.
+    #[validate(nested)]
+    #[serde(default)]
+    pub query_cache: Option<QueryCacheConfig>,
+    /// If set, shards which grow above the configured number of points are split automatically,
+    /// by resharding the collection up. Only applies to a distributed deployment.
+    #[serde(default)]
+    pub auto_resharding: Option<AutoReshardingConfig>,
+    /// If set, upserted points with a payload larger than this size in kilobytes, serialized as
+    /// JSON, are rejected like points with invalid vectors.
+    #[serde(default)]
+    pub max_payload_size_kb: Option<usize>,
+    /// If set, segments which fail to load are retried, and optionally quarantined so that their
+    /// shard is loaded without them, with its replica disabled.
+    #[validate(nested)]
+    #[serde(default)]
+    pub segment_load: Option<SegmentLoadConfig>,
+}
+
+/// Configuration of automatic resharding, to split oversized shards.
+#[derive(Debug, Deserialize, Clone, Copy, PartialEq, Eq)]
+pub struct AutoReshardingConfig {
+    /// Max number of points in a shard. A shard with more points is split by adding a new shard
+    /// to the collection, which takes over a part of the points of every shard.
+    pub max_shard_points: NonZeroUsize,
+    /// Interval between checks of the shard sizes, in seconds.
+    #[serde(default = "default_auto_resharding_check_interval_sec")]
+    pub check_interval_sec: NonZeroU64,
+}
+
+fn default_auto_resharding_check_interval_sec() -> NonZeroU64 {
+    NonZeroU64::new(60).unwrap()
 }
 
 impl StorageConfig {
     pub fn to_shared_storage_config(&self, is_distributed: bool) -> SharedStorageConfig {
         SharedStorageConfig::new(
             self.update_queue_size,
+            self.max_pending_updates,
             self.node_type,
             self.handle_collection_load_errors,
             self.recovery_mode.clone(),
             self.performance
                 .search_timeout_sec
                 .map(|x| Duration::from_secs(x as u64)),
+            self.performance
+                .session_read_timeout_sec
+                .map(|x| Duration::from_secs(x as u64)),
             self.update_concurrency,
+            self.search_shard_concurrency,
+            self.search_segment_concurrency,
             is_distributed,
             self.shard_transfer_method,
             self.performance.incoming_shard_transfers_limit,
             self.performance.outgoing_shard_transfers_limit,
             self.snapshots_path.clone(),
             self.snapshots_config.clone(),
+            self.query_cache,
+            self.max_payload_size_kb.map(|size_kb| size_kb * 1024),
+            self.segment_load,
         )
     }
 }
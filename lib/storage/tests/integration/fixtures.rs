@@ -0,0 +1,97 @@
+use std::num::NonZeroUsize;
+use std::path::Path;
+use std::sync::Arc;
+
+use collection::optimizers_builder::OptimizersConfig;
+use collection::shards::channel_service::ChannelService;
+use common::cpu::CpuBudget;
+use memory::madvise;
+use storage::content_manager::consensus::operation_sender::OperationSender;
+use storage::content_manager::toc::TableOfContent;
+use storage::dispatcher::Dispatcher;
+use storage::types::{PerformanceConfig, StorageConfig};
+use tokio::runtime::{Handle, Runtime};
+
+/// Creates a single node dispatcher storing its data in `storage_path`.
+///
+/// Returns the dispatcher and a handle of its search runtime to drive async operations.
+pub fn test_dispatcher(storage_path: &Path) -> (Dispatcher, Handle) {
+    let config = StorageConfig {
+        storage_path: storage_path.to_str().unwrap().to_string(),
+        snapshots_path: storage_path.join("snapshots").to_str().unwrap().to_string(),
+        snapshots_config: Default::default(),
+        temp_path: None,
+        on_disk_payload: false,
+        optimizers: OptimizersConfig {
+            deleted_threshold: 0.5,
+            payload_index_deleted_threshold: None,
+            vacuum_min_vector_number: 100,
+            default_segment_number: 2,
+            max_segment_size: None,
+            memmap_threshold: Some(100),
+            indexing_threshold: Some(100),
+            flush_interval_sec: 2,
+            min_segment_size: None,
+            max_flush_delay_sec: None,
+            max_optimization_threads: Some(2),
+            deduplicate_vectors: None,
+            deduplication_epsilon: None,
+            defer_indexing: None,
+            split_oversized_segments: None,
+        },
+        optimizers_overwrite: None,
+        wal: Default::default(),
+        performance: PerformanceConfig {
+            max_search_threads: 1,
+            max_optimization_threads: 1,
+            optimizer_cpu_budget: 0,
+            update_rate_limit: None,
+            search_timeout_sec: None,
+            session_read_timeout_sec: None,
+            incoming_shard_transfers_limit: Some(1),
+            outgoing_shard_transfers_limit: Some(1),
+        },
+        hnsw_index: Default::default(),
+        mmap_advice: madvise::Advice::Random,
+        node_type: Default::default(),
+        update_queue_size: Default::default(),
+        max_pending_updates: None,
+        handle_collection_load_errors: false,
+        recovery_mode: None,
+        async_scorer: false,
+        update_concurrency: Some(NonZeroUsize::new(2).unwrap()),
+        // update_concurrency: None,
+        search_shard_concurrency: None,
+        search_segment_concurrency: None,
+        shard_transfer_method: None,
+        collection: None,
+        query_cache: None,
+        auto_resharding: None,
+        max_payload_size_kb: None,
+        segment_load: None,
+    };
+
+    let search_runtime = Runtime::new().unwrap();
+    let handle = search_runtime.handle().clone();
+
+    let update_runtime = Runtime::new().unwrap();
+
+    let general_runtime = Runtime::new().unwrap();
+
+    let (propose_sender, _propose_receiver) = std::sync::mpsc::channel();
+    let propose_operation_sender = OperationSender::new(propose_sender);
+
+    let toc = Arc::new(TableOfContent::new(
+        &config,
+        search_runtime,
+        update_runtime,
+        general_runtime,
+        CpuBudget::default(),
+        ChannelService::new(6333, None),
+        0,
+        Some(propose_operation_sender),
+    ));
+    let dispatcher = Dispatcher::new(toc);
+
+    (dispatcher, handle)
+}
@@ -1 +1,3 @@
 mod alias_tests;
+mod fixtures;
+mod strict_mode_tests;
@@ -0,0 +1,111 @@
+use collection::operations::config_diff::StrictModeConfig;
+use collection::operations::types::ScrollRequestInternal;
+use collection::operations::vector_params_builder::VectorParamsBuilder;
+use segment::types::Distance;
+use storage::content_manager::collection_meta_ops::{
+    CollectionMetaOperations, CreateCollection, CreateCollectionOperation,
+};
+use storage::content_manager::collection_verification::check_strict_mode;
+use storage::content_manager::errors::StorageError;
+use storage::rbac::{Access, CollectionAccess, CollectionAccessList, CollectionAccessMode};
+use tempfile::Builder;
+
+use crate::fixtures::test_dispatcher;
+
+const FULL_ACCESS: Access = Access::full("For test");
+
+#[test]
+fn test_strict_mode_scroll_limit_and_bypass() {
+    let storage_dir = Builder::new().prefix("storage").tempdir().unwrap();
+
+    let (dispatcher, handle) = test_dispatcher(storage_dir.path());
+
+    handle
+        .block_on(
+            dispatcher.submit_collection_meta_op(
+                CollectionMetaOperations::CreateCollection(CreateCollectionOperation::new(
+                    "test".to_string(),
+                    CreateCollection {
+                        vectors: VectorParamsBuilder::new(10, Distance::Cosine)
+                            .build()
+                            .into(),
+                        sparse_vectors: None,
+                        hnsw_config: None,
+                        wal_config: None,
+                        optimizers_config: None,
+                        shard_number: Some(1),
+                        on_disk_payload: None,
+                        payload_compression: None,
+                        replication_factor: None,
+                        write_consistency_factor: None,
+                        init_from: None,
+                        quantization_config: None,
+                        sharding_method: None,
+                        strict_mode_config: Some(StrictModeConfig {
+                            enabled: Some(true),
+                            scroll_max_limit: Some(5),
+                            ..Default::default()
+                        }),
+                        ttl_config: None,
+                    },
+                )),
+                FULL_ACCESS.clone(),
+                None,
+            ),
+        )
+        .unwrap();
+
+    let scroll = ScrollRequestInternal {
+        limit: Some(100),
+        ..Default::default()
+    };
+
+    // Unfiltered scroll limit is capped
+    let mut request = scroll.clone();
+    handle
+        .block_on(check_strict_mode(
+            &mut request,
+            "test",
+            &dispatcher,
+            &FULL_ACCESS,
+            false,
+        ))
+        .unwrap();
+    assert_eq!(request.limit, Some(5));
+
+    // Bypassing leaves the request untouched
+    let mut request = scroll.clone();
+    handle
+        .block_on(check_strict_mode(
+            &mut request,
+            "test",
+            &dispatcher,
+            &FULL_ACCESS,
+            true,
+        ))
+        .unwrap();
+    assert_eq!(request.limit, Some(100));
+
+    // Bypassing requires global manage access
+    let read_only_access = Access::full_ro("For test");
+    let collection_access = Access::Collection(CollectionAccessList(vec![CollectionAccess {
+        collection: "test".to_string(),
+        access: CollectionAccessMode::ReadWrite,
+        payload: None,
+    }]));
+    for access in [read_only_access, collection_access] {
+        let mut request = scroll.clone();
+        let result = handle.block_on(check_strict_mode(
+            &mut request,
+            "test",
+            &dispatcher,
+            &access,
+            true,
+        ));
+        assert!(
+            matches!(result, Err(StorageError::Forbidden { .. })),
+            "bypass must be forbidden for {access:?}",
+        );
+        assert_eq!(request.limit, Some(100));
+    }
+}
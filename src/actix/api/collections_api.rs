@@ -204,6 +204,22 @@ async fn update_collection_cluster(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/flush")]
+async fn flush_collection(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .flush_collection(&collection.name, access)
+            .await?;
+        Ok(true)
+    })
+    .await
+}
+
 // Configure services
 pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
     // Ordering of services is important for correct path pattern matching
@@ -218,7 +234,8 @@ pub fn config_collections_api(cfg: &mut web::ServiceConfig) {
         .service(get_aliases)
         .service(get_collection_aliases)
         .service(get_cluster_info)
-        .service(update_collection_cluster);
+        .service(update_collection_cluster)
+        .service(flush_collection);
 }
 
 #[cfg(test)]
@@ -21,11 +21,18 @@ async fn count_points(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let CountRequest {
-        count_request,
+        mut count_request,
         shard_key,
     } = request.into_inner();
 
-    let pass = match check_strict_mode(&count_request, &collection.name, &dispatcher, &access).await
+    let pass = match check_strict_mode(
+        &mut count_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
     {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
@@ -40,7 +47,7 @@ async fn count_points(
         dispatcher.toc_new(&access, &pass),
         &collection.name,
         count_request,
-        params.consistency,
+        params.consistency.clone(),
         params.timeout(),
         shard_selector,
         access,
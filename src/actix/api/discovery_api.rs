@@ -23,15 +23,22 @@ async fn discover_points(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let DiscoverRequest {
-        discover_request,
+        mut discover_request,
         shard_key,
     } = request.into_inner();
 
-    let pass =
-        match check_strict_mode(&discover_request, &collection.name, &dispatcher, &access).await {
-            Ok(pass) => pass,
-            Err(err) => return process_response_error(err, Instant::now()),
-        };
+    let pass = match check_strict_mode(
+        &mut discover_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
 
     let shard_selection = match shard_key {
         None => ShardSelectorInternal::All,
@@ -44,7 +51,7 @@ async fn discover_points(
             .discover(
                 &collection.name,
                 discover_request,
-                params.consistency,
+                params.consistency.clone(),
                 shard_selection,
                 access,
                 params.timeout(),
@@ -67,9 +74,17 @@ async fn discover_batch_points(
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let request = request.into_inner();
+    let mut request = request.into_inner();
 
-    let pass = match check_strict_mode(&request, &collection.name, &dispatcher, &access).await {
+    let pass = match check_strict_mode(
+        &mut request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
@@ -79,7 +94,7 @@ async fn discover_batch_points(
             dispatcher.toc_new(&access, &pass),
             &collection.name,
             request,
-            params.consistency,
+            params.consistency.clone(),
             access,
             params.timeout(),
         )
@@ -22,11 +22,18 @@ async fn facet(
     let timing = Instant::now();
 
     let FacetRequest {
-        facet_request,
+        mut facet_request,
         shard_key,
     } = request.into_inner();
 
-    let pass = match check_strict_mode(&facet_request, &collection.name, &dispatcher, &access).await
+    let pass = match check_strict_mode(
+        &mut facet_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
     {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, timing),
@@ -45,7 +52,7 @@ async fn facet(
             &collection.name,
             facet_params,
             shard_selection,
-            params.consistency,
+            params.consistency.clone(),
             access,
             params.timeout(),
         )
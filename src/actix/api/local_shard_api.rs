@@ -37,7 +37,7 @@ async fn get_points(
             dispatcher.toc(&access),
             &path.collection,
             request.into_inner(),
-            params.consistency,
+            params.consistency.clone(),
             params.timeout(),
             ShardSelectorInternal::ShardId(path.shard),
             access,
@@ -85,7 +85,7 @@ async fn scroll_points(
             .scroll(
                 &path.collection,
                 request,
-                params.consistency,
+                params.consistency.clone(),
                 params.timeout(),
                 ShardSelectorInternal::ShardId(path.shard),
                 access,
@@ -129,7 +129,7 @@ async fn count_points(
             dispatcher.toc(&access),
             &path.collection,
             request,
-            params.consistency,
+            params.consistency.clone(),
             params.timeout(),
             ShardSelectorInternal::ShardId(path.shard),
             access,
@@ -6,6 +6,7 @@ pub mod discovery_api;
 pub mod facet_api;
 pub mod issues_api;
 pub mod local_shard_api;
+pub mod point_dump_api;
 pub mod query_api;
 pub mod read_params;
 pub mod recommend_api;
@@ -0,0 +1,68 @@
+use std::num::NonZeroUsize;
+
+use actix_web::http::header::ContentType;
+use actix_web::{post, web, HttpResponse, Responder};
+use actix_web_validator::{Path, Query};
+use serde::Deserialize;
+use storage::dispatcher::Dispatcher;
+use tokio::time::Instant;
+use validator::Validate;
+
+use crate::actix::api::CollectionPath;
+use crate::actix::auth::ActixAccess;
+use crate::actix::helpers::{self, process_response_error};
+
+#[derive(Deserialize, Validate)]
+struct PointDumpParams {
+    /// Number of points read or upserted per batch
+    batch_size: Option<NonZeroUsize>,
+}
+
+impl PointDumpParams {
+    fn batch_size(&self) -> Option<usize> {
+        self.batch_size.map(NonZeroUsize::get)
+    }
+}
+
+#[post("/collections/{name}/points/export")]
+async fn export_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<PointDumpParams>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let timing = Instant::now();
+
+    let result = dispatcher
+        .toc(&access)
+        .export_points(&collection.name, params.batch_size(), access)
+        .await;
+
+    match result {
+        Ok((dump, _)) => HttpResponse::Ok()
+            .content_type(ContentType::octet_stream())
+            .body(dump),
+        Err(err) => process_response_error(err, timing),
+    }
+}
+
+#[post("/collections/{name}/points/import")]
+async fn import_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    params: Query<PointDumpParams>,
+    dump: web::Bytes,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    helpers::time(async move {
+        dispatcher
+            .toc(&access)
+            .import_points(&collection.name, &dump, params.batch_size(), access)
+            .await
+    })
+    .await
+}
+
+pub fn config_point_dump_api(cfg: &mut web::ServiceConfig) {
+    cfg.service(export_points).service(import_points);
+}
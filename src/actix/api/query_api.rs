@@ -40,7 +40,7 @@ async fn query_points(
             .query_batch(
                 &collection.name,
                 vec![(query_request.into(), shard_selection)],
-                params.consistency,
+                params.consistency.clone(),
                 access,
                 params.timeout(),
             )
@@ -93,7 +93,7 @@ async fn query_points_batch(
             .query_batch(
                 &collection.name,
                 batch,
-                params.consistency,
+                params.consistency.clone(),
                 access,
                 params.timeout(),
             )
@@ -138,7 +138,7 @@ async fn query_points_groups(
             dispatcher.toc(&access),
             &collection.name,
             query_group_request,
-            params.consistency,
+            params.consistency.clone(),
             shard_selection,
             access,
             params.timeout(),
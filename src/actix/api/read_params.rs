@@ -6,13 +6,17 @@ use schemars::JsonSchema;
 use serde::Deserialize;
 use validator::Validate;
 
-#[derive(Copy, Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
+#[derive(Clone, Debug, Default, Eq, PartialEq, Deserialize, JsonSchema, Validate)]
 pub struct ReadParams {
     #[serde(default, deserialize_with = "deserialize_read_consistency")]
     #[validate(nested)]
     pub consistency: Option<ReadConsistency>,
     /// If set, overrides global timeout for this request. Unit is seconds.
     pub timeout: Option<NonZeroU64>,
+    /// If true, strict mode restrictions of the collection are not applied to this request.
+    /// Requires global manage access.
+    #[serde(default)]
+    pub bypass_strict_mode: bool,
 }
 
 impl ReadParams {
@@ -73,6 +77,31 @@ mod test {
         }
     }
 
+    #[test]
+    fn deserialize_session_token() {
+        let token = "v1:0-1-0-42,1-2-0-7";
+
+        test(
+            token,
+            ReadParams {
+                consistency: Some(ReadConsistency::Session(token.parse().unwrap())),
+                ..Default::default()
+            },
+        );
+    }
+
+    #[test]
+    fn deserialize_bypass_strict_mode() {
+        test_str(
+            "consistency=all&bypass_strict_mode=true",
+            ReadParams {
+                consistency: Some(ReadConsistency::Type(ReadConsistencyType::All)),
+                bypass_strict_mode: true,
+                ..Default::default()
+            },
+        );
+    }
+
     #[test]
     fn try_deserialize_factor_0() {
         assert!(try_deserialize(&str("0")).is_err());
@@ -31,15 +31,22 @@ async fn recommend_points(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let RecommendRequest {
-        recommend_request,
+        mut recommend_request,
         shard_key,
     } = request.into_inner();
 
-    let pass =
-        match check_strict_mode(&recommend_request, &collection.name, &dispatcher, &access).await {
-            Ok(pass) => pass,
-            Err(err) => return process_response_error(err, Instant::now()),
-        };
+    let pass = match check_strict_mode(
+        &mut recommend_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
 
     let shard_selection = match shard_key {
         None => ShardSelectorInternal::All,
@@ -52,7 +59,7 @@ async fn recommend_points(
             .recommend(
                 &collection.name,
                 recommend_request,
-                params.consistency,
+                params.consistency.clone(),
                 shard_selection,
                 access,
                 params.timeout(),
@@ -100,13 +107,16 @@ async fn recommend_batch_points(
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
+    let mut request = request.into_inner();
+
     let mut vpass = None;
-    for operation in request.searches.iter() {
+    for operation in request.searches.iter_mut() {
         let pass = match check_strict_mode(
-            &operation.recommend_request,
+            &mut operation.recommend_request,
             &collection.name,
             &dispatcher,
             &access,
+            params.bypass_strict_mode,
         )
         .await
         {
@@ -124,8 +134,8 @@ async fn recommend_batch_points(
         do_recommend_batch_points(
             dispatcher.toc_new(&access, &pass),
             &collection.name,
-            request.into_inner(),
-            params.consistency,
+            request,
+            params.consistency.clone(),
             access,
             params.timeout(),
         )
@@ -167,7 +177,7 @@ async fn recommend_point_groups(
         dispatcher.toc(&access),
         &collection.name,
         recommend_group_request,
-        params.consistency,
+        params.consistency.clone(),
         shard_selection,
         access,
         params.timeout(),
@@ -1,24 +1,27 @@
 use std::time::Duration;
 
-use actix_web::{get, post, web, Responder};
+use actix_web::web::Bytes;
+use actix_web::{get, post, web, HttpResponse, Responder};
 use actix_web_validator::{Json, Path, Query};
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{PointRequest, PointRequestInternal, Record, ScrollRequest};
-use futures::TryFutureExt;
+use futures::{StreamExt as _, TryFutureExt};
 use itertools::Itertools;
 use segment::types::{PointIdType, WithPayloadInterface};
 use serde::Deserialize;
+use storage::content_manager::collection_verification::check_strict_mode;
 use storage::content_manager::errors::StorageError;
 use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
+use tokio::time::Instant;
 use validator::Validate;
 
 use super::read_params::ReadParams;
 use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
-use crate::actix::helpers;
+use crate::actix::helpers::{self, process_response_error};
 use crate::common::points::do_get_points;
 
 #[derive(Deserialize, Validate)]
@@ -40,6 +43,7 @@ async fn do_get_point(
         ids: vec![point_id],
         with_payload: Some(WithPayloadInterface::Bool(true)),
         with_vector: true.into(),
+        with_version: false,
     };
 
     let shard_selection = ShardSelectorInternal::All;
@@ -74,7 +78,7 @@ async fn get_point(
             dispatcher.toc(&access),
             &collection.name,
             point_id,
-            params.consistency,
+            params.consistency.clone(),
             params.timeout(),
             access,
         )
@@ -114,7 +118,7 @@ async fn get_points(
             dispatcher.toc(&access),
             &collection.name,
             point_request,
-            params.consistency,
+            params.consistency.clone(),
             params.timeout(),
             shard_selection,
             access,
@@ -137,24 +141,111 @@ async fn scroll_points(
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    // TODO: Check strict mode
     let ScrollRequest {
-        scroll_request,
+        mut scroll_request,
         shard_key,
     } = request.into_inner();
 
+    let pass = match check_strict_mode(
+        &mut scroll_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
+
     let shard_selection = match shard_key {
         None => ShardSelectorInternal::All,
         Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
     };
 
-    helpers::time(dispatcher.toc(&access).scroll(
+    helpers::time(dispatcher.toc_new(&access, &pass).scroll(
         &collection.name,
         scroll_request,
-        params.consistency,
+        params.consistency.clone(),
         params.timeout(),
         shard_selection,
         access,
     ))
     .await
 }
+
+/// Scroll all points matching the request as a chunked response, one JSON record per line
+///
+/// `limit` is the size of the pages the points are read in. Errors of the first page are returned
+/// as a regular error response. Later errors abort the response, as its status is already sent.
+#[post("/collections/{name}/points/scroll/stream")]
+async fn scroll_points_stream(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<ScrollRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let timing = Instant::now();
+
+    let ScrollRequest {
+        mut scroll_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &mut scroll_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, timing),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => ShardSelectorInternal::from(shard_keys),
+    };
+
+    let records = dispatcher
+        .toc_new(&access, &pass)
+        .scroll_stream(
+            &collection.name,
+            scroll_request,
+            params.consistency.clone(),
+            params.timeout(),
+            shard_selection,
+            access,
+        )
+        .await;
+
+    let mut records = match records {
+        Ok(records) => Box::pin(records.peekable()),
+        Err(err) => return process_response_error(err, timing),
+    };
+
+    if matches!(records.as_mut().peek().await, Some(Err(_))) {
+        if let Some(Err(err)) = records.next().await {
+            return process_response_error(err, timing);
+        }
+    }
+
+    let body = records.map(|record| {
+        let record = record.inspect_err(|err| log::warn!("Failed to stream points: {err}"))?;
+
+        let mut line = serde_json::to_vec(&record).map_err(|err| {
+            StorageError::service_error(format!("Failed to serialize point: {err}"))
+        })?;
+        line.push(b'\n');
+        Ok::<_, StorageError>(Bytes::from(line))
+    });
+
+    HttpResponse::Ok()
+        .content_type("application/x-ndjson")
+        .streaming(body)
+}
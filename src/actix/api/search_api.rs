@@ -4,7 +4,7 @@ use api::rest::{SearchMatrixOffsetsResponse, SearchMatrixPairsResponse, SearchMa
 use collection::collection::distance_matrix::CollectionSearchMatrixRequest;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
-    CoreSearchRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
+    CoreSearchRequest, NeighborsRequest, SearchGroupsRequest, SearchRequest, SearchRequestBatch,
 };
 use futures::TryFutureExt;
 use itertools::Itertools;
@@ -29,15 +29,22 @@ async fn search_points(
     ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
     let SearchRequest {
-        search_request,
+        mut search_request,
         shard_key,
     } = request.into_inner();
 
-    let pass =
-        match check_strict_mode(&search_request, &collection.name, &dispatcher, &access).await {
-            Ok(pass) => pass,
-            Err(err) => return process_response_error(err, Instant::now()),
-        };
+    let pass = match check_strict_mode(
+        &mut search_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
 
     let shard_selection = match shard_key {
         None => ShardSelectorInternal::All,
@@ -49,7 +56,7 @@ async fn search_points(
             dispatcher.toc_new(&access, &pass),
             &collection.name,
             search_request.into(),
-            params.consistency,
+            params.consistency.clone(),
             shard_selection,
             access,
             params.timeout(),
@@ -72,9 +79,17 @@ async fn batch_search_points(
     params: Query<ReadParams>,
     ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
-    let request = request.into_inner();
+    let mut request = request.into_inner();
 
-    let pass = match check_strict_mode(&request, &collection.name, &dispatcher, &access).await {
+    let pass = match check_strict_mode(
+        &mut request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
@@ -102,7 +117,7 @@ async fn batch_search_points(
             dispatcher.toc_new(&access, &pass),
             &collection.name,
             requests,
-            params.consistency,
+            params.consistency.clone(),
             access,
             params.timeout(),
         )
@@ -130,15 +145,16 @@ async fn search_point_groups(
     ActixAccess(access): ActixAccess,
 ) -> HttpResponse {
     let SearchGroupsRequest {
-        search_group_request,
+        mut search_group_request,
         shard_key,
     } = request.into_inner();
 
     let pass = match check_strict_mode(
-        &search_group_request,
+        &mut search_group_request,
         &collection.name,
         &dispatcher,
         &access,
+        params.bypass_strict_mode,
     )
     .await
     {
@@ -155,7 +171,7 @@ async fn search_point_groups(
         dispatcher.toc_new(&access, &pass),
         &collection.name,
         search_group_request,
-        params.consistency,
+        params.consistency.clone(),
         shard_selection,
         access,
         params.timeout(),
@@ -188,7 +204,7 @@ async fn search_points_matrix_pairs(
         dispatcher.toc(&access),
         &collection.name,
         CollectionSearchMatrixRequest::from(search_request),
-        params.consistency,
+        params.consistency.clone(),
         shard_selection,
         access,
         params.timeout(),
@@ -224,7 +240,7 @@ async fn search_points_matrix_offsets(
         dispatcher.toc(&access),
         &collection.name,
         CollectionSearchMatrixRequest::from(search_request),
-        params.consistency,
+        params.consistency.clone(),
         shard_selection,
         access,
         params.timeout(),
@@ -235,11 +251,64 @@ async fn search_points_matrix_offsets(
     process_response(response, timing)
 }
 
+#[post("/collections/{name}/points/neighbors")]
+async fn neighbors_points(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    request: Json<NeighborsRequest>,
+    params: Query<ReadParams>,
+    ActixAccess(access): ActixAccess,
+) -> HttpResponse {
+    let NeighborsRequest {
+        mut neighbors_request,
+        shard_key,
+    } = request.into_inner();
+
+    let pass = match check_strict_mode(
+        &mut neighbors_request,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
+
+    let shard_selection = match shard_key {
+        None => ShardSelectorInternal::All,
+        Some(shard_keys) => shard_keys.into(),
+    };
+
+    helpers::time(
+        dispatcher
+            .toc_new(&access, &pass)
+            .neighbors(
+                &collection.name,
+                neighbors_request,
+                params.consistency.clone(),
+                shard_selection,
+                access,
+                params.timeout(),
+            )
+            .map_ok(|scored_points| {
+                scored_points
+                    .into_iter()
+                    .map(api::rest::ScoredPoint::from)
+                    .collect_vec()
+            }),
+    )
+    .await
+}
+
 // Configure services
 pub fn config_search_api(cfg: &mut web::ServiceConfig) {
     cfg.service(search_points)
         .service(batch_search_points)
         .service(search_point_groups)
         .service(search_points_matrix_pairs)
-        .service(search_points_matrix_offsets);
+        .service(search_points_matrix_offsets)
+        .service(neighbors_points);
 }
@@ -19,8 +19,7 @@ use tokio::sync::Mutex;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response_error};
 use crate::common::health;
-use crate::common::helpers::LocksOption;
-use crate::common::metrics::MetricsData;
+use crate::common::helpers::{LocksOption, MaintenanceOption};
 use crate::common::stacktrace::get_stack_trace;
 use crate::common::telemetry::TelemetryCollector;
 use crate::tracing;
@@ -78,8 +77,9 @@ async fn metrics(
     let telemetry_data = telemetry_collector
         .prepare_data(
             &access,
+            // Level 2 for collection and shard metrics, labeled with collection names
             TelemetryDetail {
-                level: DetailsLevel::Level1,
+                level: DetailsLevel::Level2,
                 histograms: true,
             },
         )
@@ -92,7 +92,7 @@ async fn metrics(
 
     HttpResponse::Ok()
         .content_type(ContentType::plaintext())
-        .body(MetricsData::from(telemetry_data).format_metrics())
+        .body(telemetry_data.to_prometheus())
 }
 
 #[post("/locks")]
@@ -129,6 +129,36 @@ fn get_locks(
     })
 }
 
+#[post("/maintenance")]
+fn put_maintenance(
+    dispatcher: web::Data<Dispatcher>,
+    maintenance_option: Json<MaintenanceOption>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        let toc = dispatcher.toc(&access);
+        access.check_global_access(AccessRequirements::new().manage())?;
+        let was_paused = toc.set_optimizations_paused(maintenance_option.pause_optimizations)?;
+        Ok(MaintenanceOption {
+            pause_optimizations: was_paused,
+        })
+    })
+}
+
+#[get("/maintenance")]
+fn get_maintenance(
+    dispatcher: web::Data<Dispatcher>,
+    ActixAccess(access): ActixAccess,
+) -> impl Future<Output = HttpResponse> {
+    helpers::time(async move {
+        access.check_global_access(AccessRequirements::new())?;
+        let toc = dispatcher.toc(&access);
+        Ok(MaintenanceOption {
+            pause_optimizations: toc.are_optimizations_paused(),
+        })
+    })
+}
+
 #[get("/stacktrace")]
 fn get_stacktrace(ActixAccess(access): ActixAccess) -> impl Future<Output = HttpResponse> {
     helpers::time(async move {
@@ -201,6 +231,8 @@ pub fn config_service_api(cfg: &mut web::ServiceConfig) {
         .service(metrics)
         .service(put_locks)
         .service(get_locks)
+        .service(put_maintenance)
+        .service(get_maintenance)
         .service(get_stacktrace)
         .service(healthz)
         .service(livez)
@@ -1,8 +1,11 @@
 use actix_web::rt::time::Instant;
-use actix_web::{delete, post, put, web, Responder};
+use actix_web::{delete, patch, post, put, web, Responder};
 use actix_web_validator::{Json, Path, Query};
-use collection::operations::payload_ops::{DeletePayload, SetPayload};
-use collection::operations::point_ops::{PointInsertOperations, PointsSelector, WriteOrdering};
+use collection::operations::idempotency::IdempotencyKey;
+use collection::operations::payload_ops::{CopyPayloadField, DeletePayload, SetPayload};
+use collection::operations::point_ops::{
+    PointInsertOperations, PointsSelector, WriteConsistency, WriteOrdering,
+};
 use collection::operations::types::UpdateResult;
 use collection::operations::vector_ops::{DeleteVectors, UpdateVectors};
 use schemars::JsonSchema;
@@ -16,8 +19,9 @@ use super::CollectionPath;
 use crate::actix::auth::ActixAccess;
 use crate::actix::helpers::{self, process_response, process_response_error};
 use crate::common::points::{
-    do_batch_update_points, do_clear_payload, do_create_index, do_delete_index, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_overwrite_payload, do_set_payload, do_update_vectors,
+    do_batch_update_points, do_clear_payload, do_copy_payload_field, do_create_index,
+    do_delete_index, do_delete_payload, do_delete_points, do_delete_vectors,
+    do_merge_patch_payload, do_overwrite_payload, do_set_payload, do_update_vectors,
     do_upsert_points, CreateFieldIndex, UpdateOperations,
 };
 
@@ -31,6 +35,22 @@ struct FieldPath {
 pub struct UpdateParam {
     pub wait: Option<bool>,
     pub ordering: Option<WriteOrdering>,
+    /// How many replicas must confirm the update, defaults to `write_consistency_factor`
+    /// of the collection
+    pub consistency: Option<WriteConsistency>,
+    /// If true, strict mode restrictions of the collection are not applied to this request.
+    /// Requires global manage access.
+    #[serde(default)]
+    pub bypass_strict_mode: bool,
+    /// If set, retries of the operation with the same key are applied only once.
+    /// Only used by upserts and deletes of points.
+    #[validate(nested)]
+    pub idempotency_key: Option<IdempotencyKey>,
+    /// If true, points with invalid vectors or oversized payloads are left out of the update and
+    /// reported in the result, instead of failing the whole update.
+    /// Only used by upserts of points and updates of vectors.
+    #[serde(default)]
+    pub skip_invalid_points: bool,
 }
 
 #[put("/collections/{name}/points")]
@@ -44,6 +64,9 @@ async fn upsert_points(
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
+    let skip_invalid_points = params.skip_invalid_points;
+    let idempotency_key = params.into_inner().idempotency_key;
 
     helpers::time(do_upsert_points(
         dispatcher.toc(&access).clone(),
@@ -53,6 +76,12 @@ async fn upsert_points(
         None,
         wait,
         ordering,
+        consistency,
+        idempotency_key,
+        skip_invalid_points,
+        None,
+        None,
+        Vec::new(),
         access,
     ))
     .await
@@ -66,14 +95,24 @@ async fn delete_points(
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
 
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
+    let idempotency_key = params.into_inner().idempotency_key;
 
     helpers::time(do_delete_points(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -83,6 +122,8 @@ async fn delete_points(
         None,
         wait,
         ordering,
+        consistency,
+        idempotency_key,
         access,
     ))
     .await
@@ -99,6 +140,7 @@ async fn update_vectors(
     let operation = operation.into_inner();
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     helpers::time(do_update_vectors(
         dispatcher.toc(&access).clone(),
@@ -108,6 +150,9 @@ async fn update_vectors(
         None,
         wait,
         ordering,
+        consistency,
+        params.skip_invalid_points,
+        Vec::new(),
         access,
     ))
     .await
@@ -123,14 +168,23 @@ async fn delete_vectors(
 ) -> impl Responder {
     let timing = Instant::now();
 
-    let operation = operation.into_inner();
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, timing),
     };
 
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     let response = do_delete_vectors(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -140,6 +194,7 @@ async fn delete_vectors(
         None,
         wait,
         ordering,
+        consistency,
         access,
     )
     .await;
@@ -154,15 +209,24 @@ async fn set_payload(
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
-
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
 
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     helpers::time(do_set_payload(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -172,6 +236,7 @@ async fn set_payload(
         None,
         wait,
         ordering,
+        consistency,
         access,
     ))
     .await
@@ -185,13 +250,22 @@ async fn overwrite_payload(
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     helpers::time(do_overwrite_payload(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -201,6 +275,85 @@ async fn overwrite_payload(
         None,
         wait,
         ordering,
+        consistency,
+        access,
+    ))
+    .await
+}
+
+#[patch("/collections/{name}/points/payload")]
+async fn merge_patch_payload(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<SetPayload>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
+
+    helpers::time(do_merge_patch_payload(
+        dispatcher.toc_new(&access, &pass).clone(),
+        collection.into_inner().name,
+        operation,
+        None,
+        None,
+        wait,
+        ordering,
+        consistency,
+        access,
+    ))
+    .await
+}
+
+#[post("/collections/{name}/points/payload/copy")]
+async fn copy_payload_field(
+    dispatcher: web::Data<Dispatcher>,
+    collection: Path<CollectionPath>,
+    operation: Json<CopyPayloadField>,
+    params: Query<UpdateParam>,
+    ActixAccess(access): ActixAccess,
+) -> impl Responder {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
+        Ok(pass) => pass,
+        Err(err) => return process_response_error(err, Instant::now()),
+    };
+    let wait = params.wait.unwrap_or(false);
+    let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
+
+    helpers::time(do_copy_payload_field(
+        dispatcher.toc_new(&access, &pass).clone(),
+        collection.into_inner().name,
+        operation,
+        None,
+        None,
+        wait,
+        ordering,
+        consistency,
         access,
     ))
     .await
@@ -214,13 +367,22 @@ async fn delete_payload(
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     helpers::time(do_delete_payload(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -230,6 +392,7 @@ async fn delete_payload(
         None,
         wait,
         ordering,
+        consistency,
         access,
     ))
     .await
@@ -243,14 +406,23 @@ async fn clear_payload(
     params: Query<UpdateParam>,
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
-    let operation = operation.into_inner();
-    let pass = match check_strict_mode(&operation, &collection.name, &dispatcher, &access).await {
+    let mut operation = operation.into_inner();
+    let pass = match check_strict_mode(
+        &mut operation,
+        &collection.name,
+        &dispatcher,
+        &access,
+        params.bypass_strict_mode,
+    )
+    .await
+    {
         Ok(pass) => pass,
         Err(err) => return process_response_error(err, Instant::now()),
     };
 
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     helpers::time(do_clear_payload(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -260,6 +432,7 @@ async fn clear_payload(
         None,
         wait,
         ordering,
+        consistency,
         access,
     ))
     .await
@@ -274,11 +447,18 @@ async fn update_batch(
     ActixAccess(access): ActixAccess,
 ) -> impl Responder {
     let timing = Instant::now();
-    let operations = operations.into_inner();
+    let mut operations = operations.into_inner();
 
     let mut vpass = None;
-    for operation in operations.operations.iter() {
-        let pass = match check_strict_mode(operation, &collection.name, &dispatcher, &access).await
+    for operation in operations.operations.iter_mut() {
+        let pass = match check_strict_mode(
+            operation,
+            &collection.name,
+            &dispatcher,
+            &access,
+            params.bypass_strict_mode,
+        )
+        .await
         {
             Ok(pass) => pass,
             Err(err) => return process_response_error(err, Instant::now()),
@@ -293,6 +473,7 @@ async fn update_batch(
 
     let wait = params.wait.unwrap_or(false);
     let ordering = params.ordering.unwrap_or_default();
+    let consistency = params.consistency;
 
     let response = do_batch_update_points(
         dispatcher.toc_new(&access, &pass).clone(),
@@ -302,6 +483,7 @@ async fn update_batch(
         None,
         wait,
         ordering,
+        consistency,
         access,
     )
     .await;
@@ -368,6 +550,8 @@ pub fn config_update_api(cfg: &mut web::ServiceConfig) {
         .service(delete_vectors)
         .service(set_payload)
         .service(overwrite_payload)
+        .service(merge_patch_payload)
+        .service(copy_payload_field)
         .service(delete_payload)
         .service(clear_payload)
         .service(create_field_index)
@@ -12,6 +12,7 @@ pub fn accepted_response(timing: Instant) -> HttpResponse {
     HttpResponse::Accepted().json(ApiResponse::<()> {
         result: None,
         status: ApiStatus::Accepted,
+        error_code: None,
         time: timing.elapsed().as_secs_f64(),
     })
 }
@@ -24,6 +25,7 @@ where
         Ok(res) => HttpResponse::Ok().json(ApiResponse {
             result: Some(res),
             status: ApiStatus::Ok,
+            error_code: None,
             time: timing.elapsed().as_secs_f64(),
         }),
 
@@ -39,6 +41,7 @@ pub fn process_response_error(err: StorageError, timing: Instant) -> HttpRespons
     HttpResponse::build(error.status_code()).json(ApiResponse::<()> {
         result: None,
         status: ApiStatus::Error(error.to_string()),
+        error_code: Some(error.0.code()),
         time: timing.elapsed().as_secs_f64(),
     })
 }
@@ -130,6 +133,9 @@ impl ResponseError for HttpError {
             StorageError::ChecksumMismatch { .. } => http::StatusCode::BAD_REQUEST,
             StorageError::Forbidden { .. } => http::StatusCode::FORBIDDEN,
             StorageError::PreconditionFailed { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::ResourceExhausted { .. } => http::StatusCode::TOO_MANY_REQUESTS,
+            StorageError::Cancelled { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
+            StorageError::OutOfMemory { .. } => http::StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 }
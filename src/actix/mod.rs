@@ -19,6 +19,7 @@ use actix_web::{error, get, web, App, HttpRequest, HttpResponse, HttpServer, Res
 use actix_web_extras::middleware::Condition as ConditionEx;
 use api::facet_api::config_facet_api;
 use collection::operations::validation;
+use segment::common::operation_error::ErrorCode;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::Access;
 
@@ -29,9 +30,10 @@ use crate::actix::api::debug_api::config_debugger_api;
 use crate::actix::api::discovery_api::config_discovery_api;
 use crate::actix::api::issues_api::config_issues_api;
 use crate::actix::api::local_shard_api::config_local_shard_api;
+use crate::actix::api::point_dump_api::config_point_dump_api;
 use crate::actix::api::query_api::config_query_api;
 use crate::actix::api::recommend_api::config_recommend_api;
-use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points};
+use crate::actix::api::retrieve_api::{get_point, get_points, scroll_points, scroll_points_stream};
 use crate::actix::api::search_api::config_search_api;
 use crate::actix::api::service_api::config_service_api;
 use crate::actix::api::shards_api::config_shards_api;
@@ -135,6 +137,9 @@ pub fn init(
                 .app_data(validate_path_config)
                 .app_data(validate_query_config)
                 .app_data(validate_json_config)
+                .app_data(web::PayloadConfig::new(
+                    settings.service.max_request_size_mb * 1024 * 1024,
+                ))
                 .app_data(TempFileConfig::default().directory(&upload_dir))
                 .app_data(MultipartFormConfig::default().total_limit(usize::MAX))
                 .service(index)
@@ -152,9 +157,11 @@ pub fn init(
                 .configure(config_issues_api)
                 .configure(config_debugger_api)
                 .configure(config_local_shard_api)
+                .configure(config_point_dump_api)
                 // Ordering of services is important for correct path pattern matching
                 // See: <https://github.com/qdrant/qdrant/issues/3543>
                 .service(scroll_points)
+                .service(scroll_points_stream)
                 .service(count_points)
                 .service(get_point)
                 .service(get_points);
@@ -234,6 +241,7 @@ fn validation_error_handler(
     .json(ApiResponse::<()> {
         result: None,
         status: ApiStatus::Error(msg),
+        error_code: Some(ErrorCode::InvalidArgument),
         time: 0.0,
     });
     error::InternalError::from_response(err, response).into()
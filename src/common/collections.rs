@@ -1,21 +1,25 @@
-use std::collections::HashMap;
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use api::grpc::models::{CollectionDescription, CollectionsResponse};
 use api::grpc::qdrant::CollectionExists;
+use collection::collection_state::ShardInfo;
 use collection::config::ShardingMethod;
 use collection::operations::cluster_ops::{
-    AbortTransferOperation, ClusterOperations, DropReplicaOperation, MoveShardOperation,
-    ReplicateShardOperation, ReshardingDirection, RestartTransfer, RestartTransferOperation,
-    StartResharding,
+    AbortTransferOperation, ChangeReplicationFactor, ChangeReplicationFactorOperation,
+    ClusterOperations, DropReplicaOperation, MakeReplicaReadOnlyOperation, MoveShardOperation,
+    PromoteReplicaOperation, Replica, ReplicateShardOperation, ReshardingDirection,
+    RestartTransfer, RestartTransferOperation, StartResharding,
 };
+use collection::operations::config_diff::CollectionParamsDiff;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::snapshot_ops::SnapshotDescription;
 use collection::operations::types::{
     AliasDescription, CollectionClusterInfo, CollectionInfo, CollectionsAliasesResponse,
 };
-use collection::shards::replica_set;
+use collection::shards::replica_set::{self, ReplicaState};
 use collection::shards::resharding::ReshardKey;
 use collection::shards::shard::{PeerId, ShardId, ShardsPlacement};
 use collection::shards::transfer::{ShardTransfer, ShardTransferKey, ShardTransferRestart};
@@ -32,6 +36,9 @@ use storage::content_manager::toc::TableOfContent;
 use storage::dispatcher::Dispatcher;
 use storage::rbac::{Access, AccessRequirements};
 
+/// How long promotion of a read-only replica waits for it to catch up, if no timeout is given
+const DEFAULT_READ_ONLY_CATCH_UP_TIMEOUT: Duration = Duration::from_secs(30);
+
 pub async fn do_collection_exists(
     toc: &TableOfContent,
     access: Access,
@@ -124,6 +131,94 @@ fn generate_even_placement(
     exact_placement
 }
 
+/// Plan replica changes to bring every shard to `replication_factor` replicas
+///
+/// New replicas are placed on peers holding the fewest replicas of the collection, and are
+/// synchronized from active replicas of the same shard. Excess replicas are dropped starting
+/// with non-active ones, then from the most loaded peers.
+///
+/// Replicas involved in ongoing transfers are left untouched. A source replica can serve only
+/// one transfer of a shard at a time, so if there are not enough active replicas to create all
+/// new ones at once, repeating the operation after transfers finish completes the change.
+fn plan_replication_factor_change(
+    shards: &HashMap<ShardId, ShardInfo>,
+    current_transfers: &HashSet<ShardTransfer>,
+    peers: &[PeerId],
+    replication_factor: usize,
+) -> (Vec<ShardTransferKey>, Vec<replica_set::Change>) {
+    let mut replicas_on_peers: HashMap<PeerId, usize> =
+        peers.iter().map(|peer_id| (*peer_id, 0)).collect();
+    for peer_id in shards
+        .values()
+        .flat_map(|shard_info| shard_info.replicas.keys())
+    {
+        *replicas_on_peers.entry(*peer_id).or_insert(0) += 1;
+    }
+
+    let mut transfers = Vec::new();
+    let mut replica_changes = Vec::new();
+
+    for (&shard_id, shard_info) in shards.iter().sorted_by_key(|(shard_id, _)| **shard_id) {
+        let busy_peers: HashSet<PeerId> = current_transfers
+            .iter()
+            .filter(|transfer| transfer.shard_id == shard_id)
+            .flat_map(|transfer| [transfer.from, transfer.to])
+            .collect();
+        let replica_count = shard_info.replicas.len();
+
+        if replica_count < replication_factor {
+            let mut sources = shard_info
+                .replicas
+                .iter()
+                .filter(|(peer_id, state)| {
+                    **state == ReplicaState::Active && !busy_peers.contains(*peer_id)
+                })
+                .map(|(peer_id, _)| *peer_id)
+                .sorted();
+            let targets = peers
+                .iter()
+                .copied()
+                .filter(|peer_id| !shard_info.replicas.contains_key(peer_id))
+                .sorted_by_key(|peer_id| (replicas_on_peers[peer_id], *peer_id))
+                .take(replication_factor - replica_count)
+                .collect_vec();
+            for to in targets {
+                let Some(from) = sources.next() else {
+                    break;
+                };
+                *replicas_on_peers.get_mut(&to).unwrap() += 1;
+                transfers.push(ShardTransferKey {
+                    shard_id,
+                    to_shard_id: None,
+                    to,
+                    from,
+                });
+            }
+        } else if replica_count > replication_factor {
+            let excess = shard_info
+                .replicas
+                .iter()
+                .filter(|(peer_id, _)| !busy_peers.contains(*peer_id))
+                .sorted_by_key(|(peer_id, state)| {
+                    (
+                        **state == ReplicaState::Active,
+                        Reverse(replicas_on_peers[*peer_id]),
+                        **peer_id,
+                    )
+                })
+                .map(|(peer_id, _)| *peer_id)
+                .take(replica_count - replication_factor)
+                .collect_vec();
+            for peer_id in excess {
+                *replicas_on_peers.get_mut(&peer_id).unwrap() -= 1;
+                replica_changes.push(replica_set::Change::Remove(shard_id, peer_id));
+            }
+        }
+    }
+
+    (transfers, replica_changes)
+}
+
 pub async fn do_list_collection_aliases(
     toc: &TableOfContent,
     access: Access,
@@ -268,6 +363,7 @@ pub async fn do_update_collection_cluster(
                             from: move_shard.from_peer_id,
                             sync: false,
                             method: move_shard.method,
+                            target_created: false,
                         }),
                     ),
                     access,
@@ -304,6 +400,7 @@ pub async fn do_update_collection_cluster(
                             from: replicate_shard.from_peer_id,
                             sync: true,
                             method: replicate_shard.method,
+                            target_created: false,
                         }),
                     ),
                     access,
@@ -524,6 +621,153 @@ pub async fn do_update_collection_cluster(
                 )
                 .await
         }
+        ClusterOperations::ChangeReplicationFactor(ChangeReplicationFactorOperation {
+            change_replication_factor,
+        }) => {
+            let ChangeReplicationFactor {
+                replication_factor,
+                method,
+            } = change_replication_factor;
+
+            let state = collection.state().await;
+
+            // Writes could never reach the required number of replicas
+            let write_consistency_factor = state.config.params.write_consistency_factor;
+            if replication_factor < write_consistency_factor {
+                return Err(StorageError::bad_request(format!(
+                    "replication factor {replication_factor} cannot be lower than \
+                     write consistency factor {write_consistency_factor} \
+                     of collection {collection_name}"
+                )));
+            }
+
+            let peers = get_all_peer_ids();
+            if replication_factor.get() as usize > peers.len() {
+                return Err(StorageError::bad_request(format!(
+                    "replication factor {replication_factor} exceeds number of peers {}",
+                    peers.len(),
+                )));
+            }
+
+            if let Some(resharding) = &state.resharding {
+                return Err(StorageError::bad_request(format!(
+                    "resharding {resharding:?} is in progress for collection {collection_name}"
+                )));
+            }
+
+            let (transfers, replica_changes) = plan_replication_factor_change(
+                &state.shards,
+                &state.transfers,
+                &peers,
+                replication_factor.get() as usize,
+            );
+
+            let mut update_operation =
+                UpdateCollectionOperation::new_empty(collection_name.clone());
+            update_operation.update_collection.params = Some(CollectionParamsDiff {
+                replication_factor: Some(replication_factor),
+                write_consistency_factor: None,
+                read_fan_out_factor: None,
+                on_disk_payload: None,
+            });
+            update_operation.set_shard_replica_changes(replica_changes);
+
+            let mut result = dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::UpdateCollection(update_operation),
+                    access.clone(),
+                    wait_timeout,
+                )
+                .await?;
+
+            // New replicas are synchronized in the background, their progress is reported
+            // in shard transfers of the collection cluster info
+            for transfer in transfers {
+                result &= dispatcher
+                    .submit_collection_meta_op(
+                        CollectionMetaOperations::TransferShard(
+                            collection_name.clone(),
+                            Start(ShardTransfer {
+                                shard_id: transfer.shard_id,
+                                to_shard_id: None,
+                                to: transfer.to,
+                                from: transfer.from,
+                                sync: true,
+                                method,
+                                target_created: false,
+                            }),
+                        ),
+                        access.clone(),
+                        wait_timeout,
+                    )
+                    .await?;
+            }
+
+            Ok(result)
+        }
+        ClusterOperations::MakeReplicaReadOnly(MakeReplicaReadOnlyOperation {
+            make_replica_read_only,
+        }) => {
+            let Replica { shard_id, peer_id } = make_replica_read_only;
+
+            if !collection.contains_shard(shard_id).await {
+                return Err(StorageError::BadRequest {
+                    description: format!("Shard {shard_id} of {collection_name} does not exist"),
+                });
+            };
+
+            validate_peer_exists(peer_id)?;
+
+            // Only active replicas can be made read-only, the last active one is kept by consensus
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::SetShardReplicaState(SetShardReplicaState {
+                        collection_name,
+                        shard_id,
+                        peer_id,
+                        state: ReplicaState::ReadOnly,
+                        from_state: Some(ReplicaState::Active),
+                    }),
+                    access,
+                    wait_timeout,
+                )
+                .await
+        }
+        ClusterOperations::PromoteReplica(PromoteReplicaOperation { promote_replica }) => {
+            let Replica { shard_id, peer_id } = promote_replica;
+
+            if !collection.contains_shard(shard_id).await {
+                return Err(StorageError::BadRequest {
+                    description: format!("Shard {shard_id} of {collection_name} does not exist"),
+                });
+            };
+
+            validate_peer_exists(peer_id)?;
+
+            // Catch-up barrier: the replica starts confirming writes only once it has seen
+            // everything active replicas have, otherwise reads from it could go back in time
+            collection
+                .wait_for_read_only_catch_up(
+                    shard_id,
+                    peer_id,
+                    wait_timeout.unwrap_or(DEFAULT_READ_ONLY_CATCH_UP_TIMEOUT),
+                )
+                .await?;
+
+            dispatcher
+                .submit_collection_meta_op(
+                    CollectionMetaOperations::SetShardReplicaState(SetShardReplicaState {
+                        collection_name,
+                        shard_id,
+                        peer_id,
+                        state: ReplicaState::Active,
+                        from_state: Some(ReplicaState::ReadOnly),
+                    }),
+                    access,
+                    wait_timeout,
+                )
+                .await
+        }
         ClusterOperations::StartResharding(op) => {
             let StartResharding {
                 direction,
@@ -786,8 +1030,6 @@ pub async fn do_update_collection_cluster(
 
 #[cfg(test)]
 mod tests {
-    use std::collections::HashSet;
-
     use super::*;
 
     #[test]
@@ -827,4 +1069,73 @@ mod tests {
             assert_eq!(shard_placement.len(), 5);
         }
     }
+
+    #[test]
+    fn test_plan_replication_factor_change() {
+        use ReplicaState::{Active, Dead, Partial};
+
+        let shard_info = |replicas: &[(PeerId, ReplicaState)]| ShardInfo {
+            replicas: replicas.iter().copied().collect(),
+        };
+        let transfer_key = |shard_id, from, to| ShardTransferKey {
+            shard_id,
+            to_shard_id: None,
+            to,
+            from,
+        };
+        let peers = [1, 2, 3];
+
+        let shards = HashMap::from([
+            (0, shard_info(&[(1, Active), (2, Active)])),
+            (1, shard_info(&[(2, Active), (3, Active)])),
+        ]);
+
+        // Scale up, new replicas go to peers missing the shard
+        let (transfers, changes) =
+            plan_replication_factor_change(&shards, &HashSet::new(), &peers, 3);
+        assert_eq!(
+            transfers,
+            vec![transfer_key(0, 1, 3), transfer_key(1, 2, 1)]
+        );
+        assert!(changes.is_empty());
+
+        // Scale down, replicas are dropped from the most loaded peer first
+        let (transfers, changes) =
+            plan_replication_factor_change(&shards, &HashSet::new(), &peers, 1);
+        assert!(transfers.is_empty());
+        assert_eq!(
+            changes,
+            vec![
+                replica_set::Change::Remove(0, 2),
+                replica_set::Change::Remove(1, 2),
+            ],
+        );
+
+        // Same replication factor, nothing to do
+        let (transfers, changes) =
+            plan_replication_factor_change(&shards, &HashSet::new(), &peers, 2);
+        assert!(transfers.is_empty());
+        assert!(changes.is_empty());
+
+        // Dead replicas are dropped before active ones
+        let shards = HashMap::from([(0, shard_info(&[(1, Dead), (2, Active), (3, Active)]))]);
+        let (_, changes) = plan_replication_factor_change(&shards, &HashSet::new(), &peers, 2);
+        assert_eq!(changes, vec![replica_set::Change::Remove(0, 1)]);
+
+        // Replicas involved in ongoing transfers can't be used as source, nor be dropped
+        let shards = HashMap::from([(0, shard_info(&[(1, Active), (2, Partial)]))]);
+        let current_transfers = HashSet::from([ShardTransfer {
+            shard_id: 0,
+            to_shard_id: None,
+            from: 1,
+            to: 2,
+            sync: true,
+            method: None,
+            target_created: true,
+        }]);
+        let (transfers, _) = plan_replication_factor_change(&shards, &current_transfers, &peers, 3);
+        assert!(transfers.is_empty());
+        let (_, changes) = plan_replication_factor_change(&shards, &current_transfers, &peers, 1);
+        assert!(changes.is_empty());
+    }
 }
@@ -8,6 +8,7 @@ use std::{panic, thread};
 use api::grpc::qdrant::qdrant_internal_client::QdrantInternalClient;
 use api::grpc::qdrant::{GetConsensusCommitRequest, GetConsensusCommitResponse};
 use api::grpc::transport_channel_pool::{self, TransportChannelPool};
+use collection::shards::replica_set::ReplicaState;
 use collection::shards::shard::ShardId;
 use collection::shards::CollectionId;
 use common::defaults;
@@ -312,7 +313,8 @@ impl Task {
                     continue;
                 };
 
-                if state.is_active_or_listener() {
+                // Read-only replicas only become active by explicit promotion
+                if state.is_active_or_listener() || *state == ReplicaState::ReadOnly {
                     continue;
                 }
 
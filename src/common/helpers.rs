@@ -17,6 +17,13 @@ pub struct LocksOption {
     pub write: bool,
 }
 
+#[derive(Debug, Deserialize, Serialize, JsonSchema, Validate)]
+pub struct MaintenanceOption {
+    /// Do not start new optimizations in any collection of this peer.
+    /// Only applies to this peer and persists across restarts.
+    pub pause_optimizations: bool,
+}
+
 pub fn create_search_runtime(max_search_threads: usize) -> io::Result<Runtime> {
     let mut search_threads = max_search_threads;
 
@@ -1,3 +1,7 @@
+use collection::collection_manager::optimizers::TrackerStatus;
+use collection::operations::types::OptimizersStatus;
+use collection::shards::telemetry::ReplicaSetTelemetry;
+use collection::telemetry::CollectionTelemetry;
 use prometheus::proto::{Counter, Gauge, LabelPair, Metric, MetricFamily, MetricType};
 use prometheus::TextEncoder;
 use segment::common::operation_time_statistics::OperationDurationStatistics;
@@ -90,6 +94,16 @@ impl MetricsData {
     }
 }
 
+impl TelemetryData {
+    /// Serialize in the Prometheus text exposition format.
+    ///
+    /// Collection and shard metrics are only included if the telemetry is collected with
+    /// details of level 2 or higher.
+    pub fn to_prometheus(self) -> String {
+        MetricsData::from(self).format_metrics()
+    }
+}
+
 impl From<TelemetryData> for MetricsData {
     fn from(telemetry_data: TelemetryData) -> Self {
         let mut metrics = vec![];
@@ -161,9 +175,207 @@ impl MetricsProvider for CollectionsTelemetry {
             MetricType::GAUGE,
             vec![gauge(vector_count as f64, &[])],
         ));
+
+        let mut builder = MetricFamiliesBuilder::default();
+        for collection in self.collections.iter().flatten() {
+            if let CollectionTelemetryEnum::Full(collection) = collection {
+                add_collection_metrics(collection, &mut builder);
+            }
+        }
+        builder.build(metrics);
+    }
+}
+
+/// Metrics of a single collection and its shards, labeled with the collection name.
+///
+/// Names are used as label values, which are escaped by the text encoder, so collection names
+/// with special characters don't need to be sanitized.
+fn add_collection_metrics(collection: &CollectionTelemetry, builder: &mut MetricFamiliesBuilder) {
+    let collection_labels = [("collection", collection.id.as_str())];
+
+    let num_points: usize = collection
+        .shards
+        .iter()
+        .filter_map(|shard| shard.local.as_ref())
+        .flat_map(|local| &local.segments)
+        .map(|segment| segment.info.num_points)
+        .sum();
+    builder.add(
+        "collection_points",
+        "number of points in local shards of the collection",
+        MetricType::GAUGE,
+        gauge(num_points as f64, &collection_labels),
+    );
+    builder.add(
+        "collection_vectors",
+        "number of vectors in local shards of the collection",
+        MetricType::GAUGE,
+        gauge(collection.count_vectors() as f64, &collection_labels),
+    );
+    builder.add(
+        "collection_running_transfers",
+        "number of running shard transfers of the collection",
+        MetricType::GAUGE,
+        gauge(collection.transfers.len() as f64, &collection_labels),
+    );
+
+    for shard in &collection.shards {
+        add_shard_metrics(&collection.id, shard, builder);
     }
 }
 
+fn add_shard_metrics(
+    collection_name: &str,
+    shard: &ReplicaSetTelemetry,
+    builder: &mut MetricFamiliesBuilder,
+) {
+    let shard_id = shard.id.to_string();
+    let shard_labels = [
+        ("collection", collection_name),
+        ("shard", shard_id.as_str()),
+    ];
+
+    for (peer_id, state) in &shard.replicate_states {
+        builder.add(
+            "collection_shard_replica_state",
+            "state of a replica of the shard",
+            MetricType::GAUGE,
+            gauge(
+                1.0,
+                &[
+                    ("collection", collection_name),
+                    ("shard", &shard_id),
+                    ("peer_id", &peer_id.to_string()),
+                    ("state", &format!("{state:?}")),
+                ],
+            ),
+        );
+    }
+
+    for remote in &shard.remote {
+        let Some(peer_id) = remote.peer_id.map(|peer_id| peer_id.to_string()) else {
+            continue;
+        };
+        for (operation, stats) in [("search", &remote.searches), ("update", &remote.updates)] {
+            let labels = [
+                ("collection", collection_name),
+                ("shard", &shard_id),
+                ("peer_id", &peer_id),
+                ("operation", operation),
+            ];
+            builder.add(
+                "collection_shard_remote_requests_total",
+                "total number of requests to a remote replica of the shard",
+                MetricType::COUNTER,
+                counter(stats.count as f64, &labels),
+            );
+            builder.add(
+                "collection_shard_remote_requests_fail_total",
+                "total number of failed requests to a remote replica of the shard",
+                MetricType::COUNTER,
+                counter(stats.fail_count as f64, &labels),
+            );
+            builder.add(
+                "collection_shard_remote_requests_duration_seconds",
+                "duration histogram of requests to a remote replica of the shard",
+                MetricType::HISTOGRAM,
+                duration_histogram(stats, &labels),
+            );
+        }
+    }
+
+    let Some(local) = &shard.local else {
+        return;
+    };
+
+    let (num_points, num_vectors, num_deleted_points) =
+        local
+            .segments
+            .iter()
+            .fold((0, 0, 0), |(points, vectors, deleted), segment| {
+                (
+                    points + segment.info.num_points,
+                    vectors + segment.info.num_vectors,
+                    deleted + segment.num_deleted_points,
+                )
+            });
+    let running_optimizations = local
+        .optimizations
+        .log
+        .iter()
+        .filter(|tracker| tracker.status == TrackerStatus::Optimizing)
+        .count();
+    let optimizations = &local.optimizations.optimizations;
+
+    builder.add(
+        "collection_shard_points",
+        "number of points in the local shard",
+        MetricType::GAUGE,
+        gauge(num_points as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_vectors",
+        "number of vectors in the local shard",
+        MetricType::GAUGE,
+        gauge(num_vectors as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_deleted_points",
+        "number of deleted points, which are not yet removed from the segments of the local shard",
+        MetricType::GAUGE,
+        gauge(num_deleted_points as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_segments",
+        "number of segments of the local shard",
+        MetricType::GAUGE,
+        gauge(local.segments.len() as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_wal_lag",
+        "number of operations in the WAL of the local shard, which are not yet applied to the segments",
+        MetricType::GAUGE,
+        gauge(local.wal_lag as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_optimizer_error",
+        "whether the last optimization of the local shard failed",
+        MetricType::GAUGE,
+        gauge(
+            if local.optimizations.status == OptimizersStatus::Ok {
+                0.0
+            } else {
+                1.0
+            },
+            &shard_labels,
+        ),
+    );
+    builder.add(
+        "collection_shard_running_optimizations",
+        "number of running optimizations of the local shard",
+        MetricType::GAUGE,
+        gauge(running_optimizations as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_optimizations_total",
+        "total number of optimizations of the local shard",
+        MetricType::COUNTER,
+        counter(optimizations.count as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_optimizations_fail_total",
+        "total number of failed optimizations of the local shard",
+        MetricType::COUNTER,
+        counter(optimizations.fail_count as f64, &shard_labels),
+    );
+    builder.add(
+        "collection_shard_optimizations_duration_seconds",
+        "optimization duration histogram of the local shard",
+        MetricType::HISTOGRAM,
+        duration_histogram(optimizations, &shard_labels),
+    );
+}
+
 impl MetricsProvider for ClusterTelemetry {
     fn add_metrics(&self, metrics: &mut Vec<MetricFamily>) {
         let ClusterTelemetry {
@@ -317,16 +529,8 @@ impl OperationDurationMetricsBuilder {
             f64::from(stat.max_duration_micros.unwrap_or(0.0)) / 1_000_000.0,
             labels,
         ));
-        self.duration_histogram_secs.push(histogram(
-            stat.count as u64,
-            stat.total_duration_micros as f64 / 1_000_000.0,
-            &stat
-                .duration_micros_histogram
-                .iter()
-                .map(|&(b, c)| (f64::from(b) / 1_000_000.0, c as u64))
-                .collect::<Vec<_>>(),
-            labels,
-        ));
+        self.duration_histogram_secs
+            .push(duration_histogram(stat, labels));
     }
 
     /// Build metrics and add them to the provided vector.
@@ -382,6 +586,39 @@ impl OperationDurationMetricsBuilder {
     }
 }
 
+/// A helper struct to build one [`MetricFamily`] per metric name, out of metrics which are
+/// added in arbitrary order, i.e. the same metrics of multiple collections.
+#[derive(Default)]
+struct MetricFamiliesBuilder {
+    families: Vec<(&'static str, &'static str, MetricType, Vec<Metric>)>,
+}
+
+impl MetricFamiliesBuilder {
+    pub fn add(
+        &mut self,
+        name: &'static str,
+        help: &'static str,
+        r#type: MetricType,
+        metric: Metric,
+    ) {
+        match self
+            .families
+            .iter_mut()
+            .find(|(family_name, ..)| *family_name == name)
+        {
+            Some((.., family_metrics)) => family_metrics.push(metric),
+            None => self.families.push((name, help, r#type, vec![metric])),
+        }
+    }
+
+    /// Build metric families and add them to the provided vector.
+    pub fn build(self, metrics: &mut Vec<MetricFamily>) {
+        for (name, help, r#type, family_metrics) in self.families {
+            metrics.push(metric_family(name, help, r#type, family_metrics));
+        }
+    }
+}
+
 fn metric_family(name: &str, help: &str, r#type: MetricType, metrics: Vec<Metric>) -> MetricFamily {
     let mut metric_family = MetricFamily::default();
     metric_family.set_name(name.into());
@@ -441,6 +678,20 @@ fn histogram(
     metric
 }
 
+/// Histogram of operation durations in seconds
+fn duration_histogram(stat: &OperationDurationStatistics, labels: &[(&str, &str)]) -> Metric {
+    histogram(
+        stat.count as u64,
+        stat.total_duration_micros as f64 / 1_000_000.0,
+        &stat
+            .duration_micros_histogram
+            .iter()
+            .map(|&(b, c)| (f64::from(b) / 1_000_000.0, c as u64))
+            .collect::<Vec<_>>(),
+        labels,
+    )
+}
+
 fn label_pair(name: &str, value: &str) -> LabelPair {
     let mut label = LabelPair::default();
     label.set_name(name.into());
@@ -463,4 +714,40 @@ mod tests {
             "GRPC_ENDPOINT_WHITELIST must be sorted in code to allow binary search"
         );
     }
+
+    #[test]
+    fn test_metric_families_grouped_and_escaped() {
+        use prometheus::proto::MetricType;
+
+        use super::{gauge, MetricFamiliesBuilder, MetricsData};
+
+        let mut builder = MetricFamiliesBuilder::default();
+        for (collection, points) in [("first", 1.0), ("quo\"ted\\\nname", 2.0)] {
+            builder.add(
+                "collection_points",
+                "number of points",
+                MetricType::GAUGE,
+                gauge(points, &[("collection", collection)]),
+            );
+            builder.add(
+                "collection_vectors",
+                "number of vectors",
+                MetricType::GAUGE,
+                gauge(points, &[("collection", collection)]),
+            );
+        }
+        let mut metrics = vec![];
+        builder.build(&mut metrics);
+
+        let text = MetricsData { metrics }.format_metrics();
+        assert_eq!(text.matches("# TYPE collection_points gauge").count(), 1);
+        assert_eq!(text.matches("# TYPE collection_vectors gauge").count(), 1);
+        assert!(text.contains(r#"collection_points{collection="first"} 1"#));
+        assert!(text.contains(r#"collection_points{collection="quo\"ted\\\nname"} 2"#));
+        // Every sample is on its own line
+        assert_eq!(
+            text.lines().filter(|line| !line.starts_with('#')).count(),
+            4
+        );
+    }
 }
@@ -2,6 +2,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use api::rest::{SearchGroupsRequestInternal, ShardKeySelector};
+use cancel::CancellationToken;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
@@ -10,18 +11,20 @@ use collection::common::batching::batch_requests;
 use collection::grouping::group_by::GroupRequest;
 use collection::operations::config_diff::StrictModeConfig;
 use collection::operations::consistency_params::ReadConsistency;
+use collection::operations::idempotency::IdempotencyKey;
 use collection::operations::payload_ops::{
-    DeletePayload, DeletePayloadOp, PayloadOps, SetPayload, SetPayloadOp,
+    CopyPayloadField, CopyPayloadFieldOp, DeletePayload, DeletePayloadOp, PayloadOps, SetPayload,
+    SetPayloadOp,
 };
 use collection::operations::point_ops::{
-    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointsSelector,
-    WriteOrdering,
+    FilterSelector, PointIdsList, PointInsertOperations, PointOperations, PointVersion,
+    PointsSelector, TtlStamp, WriteConsistency, WriteOrdering,
 };
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::types::{
     CollectionError, CoreSearchRequest, CoreSearchRequestBatch, CountRequestInternal, CountResult,
     DiscoverRequestBatch, GroupsResult, PointRequestInternal, RecommendGroupsRequestInternal,
-    Record, ScrollRequestInternal, ScrollResult, UpdateResult,
+    Record, ScrollRequestInternal, ScrollResult, UpdateResult, UpdateStatus,
 };
 use collection::operations::universal_query::collection_query::{
     CollectionQueryGroupsRequest, CollectionQueryRequest,
@@ -36,7 +39,10 @@ use collection::operations::{
 use collection::shards::shard::ShardId;
 use schemars::JsonSchema;
 use segment::json_path::JsonPath;
-use segment::types::{PayloadFieldSchema, PayloadKeyType, ScoredPoint};
+use segment::types::{
+    Filter, PayloadFieldSchema, PayloadKeyType, PointIdType, ScoredPoint, WithPayloadInterface,
+    WithVector,
+};
 use serde::{Deserialize, Serialize};
 use storage::content_manager::collection_meta_ops::{
     CollectionMetaOperations, CreatePayloadIndex, DropPayloadIndex,
@@ -51,6 +57,7 @@ use validator::Validate;
 pub struct CreateFieldIndex {
     pub field_name: PayloadKeyType,
     #[serde(alias = "field_type")]
+    #[validate(nested)]
     pub field_schema: Option<PayloadFieldSchema>,
 }
 
@@ -78,6 +85,12 @@ pub struct OverwritePayloadOperation {
     overwrite_payload: SetPayload,
 }
 
+#[derive(Deserialize, Serialize, JsonSchema, Validate)]
+pub struct MergePatchPayloadOperation {
+    #[validate(nested)]
+    merge_patch_payload: SetPayload,
+}
+
 #[derive(Deserialize, Serialize, JsonSchema, Validate)]
 pub struct DeletePayloadOperation {
     #[validate(nested)]
@@ -110,6 +123,7 @@ pub enum UpdateOperation {
     Delete(DeleteOperation),
     SetPayload(SetPayloadOperation),
     OverwritePayload(OverwritePayloadOperation),
+    MergePatchPayload(MergePatchPayloadOperation),
     DeletePayload(DeletePayloadOperation),
     ClearPayload(ClearPayloadOperation),
     UpdateVectors(UpdateVectorsOperation),
@@ -128,6 +142,7 @@ impl Validate for UpdateOperation {
             UpdateOperation::Delete(op) => op.validate(),
             UpdateOperation::SetPayload(op) => op.validate(),
             UpdateOperation::OverwritePayload(op) => op.validate(),
+            UpdateOperation::MergePatchPayload(op) => op.validate(),
             UpdateOperation::DeletePayload(op) => op.validate(),
             UpdateOperation::ClearPayload(op) => op.validate(),
             UpdateOperation::UpdateVectors(op) => op.validate(),
@@ -176,6 +191,9 @@ impl StrictModeVerification for UpdateOperation {
             UpdateOperation::OverwritePayload(overwrite_payload) => overwrite_payload
                 .overwrite_payload
                 .check_strict_mode(collection, strict_mode_config),
+            UpdateOperation::MergePatchPayload(merge_patch_payload) => merge_patch_payload
+                .merge_patch_payload
+                .check_strict_mode(collection, strict_mode_config),
             UpdateOperation::DeletePayload(delete_payload) => delete_payload
                 .delete_payload
                 .check_strict_mode(collection, strict_mode_config),
@@ -230,19 +248,36 @@ pub async fn do_upsert_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
+    idempotency_key: Option<IdempotencyKey>,
+    skip_invalid_points: bool,
+    ttl_stamp: Option<TtlStamp>,
+    version_timestamp: Option<u64>,
+    versions: Vec<PointVersion>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
-    let (shard_key, operation) = operation.decompose();
-    let collection_operation =
-        CollectionUpdateOperations::PointOperation(PointOperations::UpsertPoints(operation));
+    let (shard_key, mut operation) = operation.decompose()?;
+    if let Some(ttl_stamp) = ttl_stamp {
+        operation = operation.with_ttl_stamp(ttl_stamp);
+    }
+    if let Some(version_timestamp) = version_timestamp {
+        operation = operation.with_version_timestamp(version_timestamp);
+    }
+    if !versions.is_empty() {
+        operation = operation.with_versions(versions);
+    }
+    let collection_operation = CollectionUpdateOperations::PointOperation(operation);
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
-    toc.update(
+    toc.update_idempotent(
         &collection_name,
         OperationWithClockTag::new(collection_operation, clock_tag),
+        idempotency_key,
+        skip_invalid_points,
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -258,6 +293,8 @@ pub async fn do_delete_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
+    idempotency_key: Option<IdempotencyKey>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
@@ -271,11 +308,14 @@ pub async fn do_delete_points(
     let collection_operation = CollectionUpdateOperations::PointOperation(point_operation);
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
-    toc.update(
+    toc.update_idempotent(
         &collection_name,
         OperationWithClockTag::new(collection_operation, clock_tag),
+        idempotency_key,
+        false,
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -291,21 +331,30 @@ pub async fn do_update_vectors(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
+    skip_invalid_points: bool,
+    delete_vectors: Vec<(PointIdType, String)>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let UpdateVectors { points, shard_key } = operation;
 
     let collection_operation = CollectionUpdateOperations::VectorOperation(
-        VectorOperations::UpdateVectors(UpdateVectorsOp { points }),
+        VectorOperations::UpdateVectors(UpdateVectorsOp {
+            points,
+            delete_vectors,
+        }),
     );
 
     let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
 
-    toc.update(
+    toc.update_idempotent(
         &collection_name,
         OperationWithClockTag::new(collection_operation, clock_tag),
+        None,
+        skip_invalid_points,
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -321,6 +370,7 @@ pub async fn do_delete_vectors(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     // TODO: Is this cancel safe!?
@@ -349,6 +399,7 @@ pub async fn do_delete_vectors(
                 OperationWithClockTag::new(collection_operation, clock_tag),
                 wait,
                 ordering,
+                consistency,
                 shard_selector.clone(),
                 access.clone(),
             )
@@ -365,6 +416,7 @@ pub async fn do_delete_vectors(
                 OperationWithClockTag::new(collection_operation, clock_tag),
                 wait,
                 ordering,
+                consistency,
                 shard_selector,
                 access,
             )
@@ -384,6 +436,7 @@ pub async fn do_set_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
@@ -409,6 +462,7 @@ pub async fn do_set_payload(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -424,6 +478,7 @@ pub async fn do_overwrite_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let SetPayload {
@@ -450,12 +505,168 @@ pub async fn do_overwrite_payload(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        consistency,
+        shard_selector,
+        access,
+    )
+    .await
+}
+
+#[allow(clippy::too_many_arguments)]
+pub async fn do_merge_patch_payload(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: SetPayload,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let SetPayload {
+        points,
+        payload,
+        filter,
+        shard_key,
+        key,
+    } = operation;
+
+    if key.is_some() {
+        return Err(StorageError::bad_input(
+            "Merge patch is applied to the whole payload, `key` is not supported",
+        ));
+    }
+
+    let collection_operation =
+        CollectionUpdateOperations::PayloadOperation(PayloadOps::MergePatchPayload(SetPayloadOp {
+            payload,
+            points,
+            filter,
+            key: None,
+        }));
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    toc.update(
+        &collection_name,
+        OperationWithClockTag::new(collection_operation, clock_tag),
+        wait,
+        ordering,
+        consistency,
         shard_selector,
         access,
     )
     .await
 }
 
+/// Number of points per update operation, when copying a payload field of many points
+const COPY_PAYLOAD_FIELD_BATCH_SIZE: usize = 1024;
+
+/// Copy or move a payload field of the selected points.
+///
+/// A request of a user is split into update operations of at most
+/// `COPY_PAYLOAD_FIELD_BATCH_SIZE` points, each of them written to the WAL on its own, so that
+/// copying the field of many points doesn't block other updates for long. Points of a filter are
+/// resolved page by page. Internal requests, which are already split, are applied as they are.
+#[allow(clippy::too_many_arguments)]
+pub async fn do_copy_payload_field(
+    toc: Arc<TableOfContent>,
+    collection_name: String,
+    operation: CopyPayloadField,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    wait: bool,
+    ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
+    access: Access,
+) -> Result<UpdateResult, StorageError> {
+    let CopyPayloadField {
+        from,
+        to,
+        mode,
+        points,
+        filter,
+        shard_key,
+    } = operation;
+
+    let shard_selector = get_shard_selector_for_update(shard_selection, shard_key);
+
+    let update = |points: Option<Vec<PointIdType>>, filter: Option<Filter>| {
+        let collection_operation = CollectionUpdateOperations::PayloadOperation(
+            PayloadOps::CopyPayloadField(CopyPayloadFieldOp {
+                from: from.clone(),
+                to: to.clone(),
+                mode,
+                points,
+                filter,
+            }),
+        );
+        toc.update(
+            &collection_name,
+            OperationWithClockTag::new(collection_operation, clock_tag),
+            wait,
+            ordering,
+            consistency,
+            shard_selector.clone(),
+            access.clone(),
+        )
+    };
+
+    if clock_tag.is_some() || shard_selection.is_some() {
+        return update(points, filter).await;
+    }
+
+    let mut result = None;
+
+    if let Some(points) = points {
+        for chunk in points.chunks(COPY_PAYLOAD_FIELD_BATCH_SIZE) {
+            result = Some(update(Some(chunk.to_vec()), None).await?);
+        }
+    } else if let Some(filter) = filter {
+        let mut offset = None;
+        loop {
+            let request = ScrollRequestInternal {
+                offset,
+                limit: Some(COPY_PAYLOAD_FIELD_BATCH_SIZE),
+                filter: Some(filter.clone()),
+                with_payload: Some(WithPayloadInterface::Bool(false)),
+                with_vector: WithVector::Bool(false),
+                order_by: None,
+            };
+            let page = toc
+                .scroll(
+                    &collection_name,
+                    request,
+                    None,
+                    None,
+                    shard_selector.clone(),
+                    access.clone(),
+                )
+                .await?;
+
+            if !page.points.is_empty() {
+                let ids = page.points.into_iter().map(|point| point.id).collect();
+                result = Some(update(Some(ids), None).await?);
+            }
+
+            offset = page.next_page_offset;
+            if offset.is_none() {
+                break;
+            }
+        }
+    }
+
+    // No points selected, nothing to update
+    Ok(result.unwrap_or(UpdateResult {
+        operation_id: None,
+        status: UpdateStatus::Completed,
+        clock_tag: None,
+        session_token: None,
+        invalid_points: Vec::new(),
+    }))
+}
+
 #[allow(clippy::too_many_arguments)]
 pub async fn do_delete_payload(
     toc: Arc<TableOfContent>,
@@ -465,6 +676,7 @@ pub async fn do_delete_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let DeletePayload {
@@ -488,6 +700,7 @@ pub async fn do_delete_payload(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -503,6 +716,7 @@ pub async fn do_clear_payload(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<UpdateResult, StorageError> {
     let (point_operation, shard_key) = match points {
@@ -523,6 +737,7 @@ pub async fn do_clear_payload(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        consistency,
         shard_selector,
         access,
     )
@@ -538,6 +753,7 @@ pub async fn do_batch_update_points(
     shard_selection: Option<ShardId>,
     wait: bool,
     ordering: WriteOrdering,
+    consistency: Option<WriteConsistency>,
     access: Access,
 ) -> Result<Vec<UpdateResult>, StorageError> {
     let mut results = Vec::with_capacity(operations.len());
@@ -552,6 +768,12 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
+                    None,
+                    false,
+                    None,
+                    None,
+                    Vec::new(),
                     access.clone(),
                 )
                 .await
@@ -565,6 +787,8 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
+                    None,
                     access.clone(),
                 )
                 .await
@@ -578,6 +802,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
                     access.clone(),
                 )
                 .await
@@ -591,6 +816,21 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
+                    access.clone(),
+                )
+                .await
+            }
+            UpdateOperation::MergePatchPayload(operation) => {
+                do_merge_patch_payload(
+                    toc.clone(),
+                    collection_name.clone(),
+                    operation.merge_patch_payload,
+                    clock_tag,
+                    shard_selection,
+                    wait,
+                    ordering,
+                    consistency,
                     access.clone(),
                 )
                 .await
@@ -604,6 +844,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
                     access.clone(),
                 )
                 .await
@@ -617,6 +858,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
                     access.clone(),
                 )
                 .await
@@ -630,6 +872,9 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
+                    false,
+                    Vec::new(),
                     access.clone(),
                 )
                 .await
@@ -643,6 +888,7 @@ pub async fn do_batch_update_points(
                     shard_selection,
                     wait,
                     ordering,
+                    consistency,
                     access.clone(),
                 )
                 .await
@@ -682,6 +928,7 @@ pub async fn do_create_index_internal(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        None,
         shard_selector,
         Access::full("Internal API"),
     )
@@ -765,6 +1012,7 @@ pub async fn do_delete_index_internal(
         OperationWithClockTag::new(collection_operation, clock_tag),
         wait,
         ordering,
+        None,
         shard_selector,
         Access::full("Internal API"),
     )
@@ -846,6 +1094,10 @@ pub async fn do_search_batch_points(
     access: Access,
     timeout: Option<Duration>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+    // Stop searching once this request is dropped, e.g. because the client disconnected
+    let cancel = CancellationToken::new();
+    let _cancel_on_drop = cancel.clone().drop_guard();
+
     let requests = batch_requests::<
         (CoreSearchRequest, ShardSelectorInternal),
         ShardSelectorInternal,
@@ -870,10 +1122,11 @@ pub async fn do_search_batch_points(
             let req = toc.core_search_batch(
                 collection_name,
                 core_batch,
-                read_consistency,
+                read_consistency.clone(),
                 shard_selector,
                 access.clone(),
                 timeout,
+                cancel.clone(),
             );
             res.push(req);
             Ok(())
@@ -894,6 +1147,10 @@ pub async fn do_core_search_batch_points(
     access: Access,
     timeout: Option<Duration>,
 ) -> Result<Vec<Vec<ScoredPoint>>, StorageError> {
+    // Stop searching once this request is dropped, e.g. because the client disconnected
+    let cancel = CancellationToken::new();
+    let _cancel_on_drop = cancel.clone().drop_guard();
+
     toc.core_search_batch(
         collection_name,
         request,
@@ -901,6 +1158,7 @@ pub async fn do_core_search_batch_points(
         shard_selection,
         access,
         timeout,
+        cancel,
     )
     .await
 }
@@ -1339,12 +1339,14 @@ mod tests {
                             optimizers_config: None,
                             shard_number: Some(2),
                             on_disk_payload: None,
+                            payload_compression: None,
                             replication_factor: None,
                             write_consistency_factor: None,
                             init_from: None,
                             quantization_config: None,
                             sharding_method: None,
                             strict_mode_config: None,
+                            ttl_config: None,
                         },
                     )),
                     Access::full("For test"),
@@ -271,6 +271,13 @@ fn main() -> anyhow::Result<()> {
     let toc_arc = Arc::new(toc);
     let storage_path = toc_arc.storage_path();
 
+    runtime_handle.spawn(TableOfContent::run_snapshot_schedule(Arc::downgrade(
+        &toc_arc,
+    )));
+    runtime_handle.spawn(TableOfContent::run_auto_resharding(Arc::downgrade(
+        &toc_arc,
+    )));
+
     // Holder for all actively running threads of the service: web, gPRC, consensus, etc.
     let mut handles: Vec<JoinHandle<Result<(), Error>>> = vec![];
 
@@ -348,11 +355,10 @@ fn main() -> anyhow::Result<()> {
             }
         });
 
-        // TODO(resharding): Remove resharding driver?
-        //
-        // runtime_handle.block_on(async {
-        //     toc_arc.resume_resharding_tasks().await;
-        // });
+        // Resume automatic resharding, resharding started through the API is driven externally
+        runtime_handle.block_on(async {
+            toc_arc.resume_resharding_tasks().await;
+        });
 
         let collections_to_recover_in_consensus = if is_new_deployment {
             let existing_collections =
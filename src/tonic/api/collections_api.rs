@@ -6,8 +6,8 @@ use api::grpc::qdrant::{
     ChangeAliases, CollectionClusterInfoRequest, CollectionClusterInfoResponse,
     CollectionExistsRequest, CollectionExistsResponse, CollectionOperationResponse,
     CreateCollection, CreateShardKeyRequest, CreateShardKeyResponse, DeleteCollection,
-    DeleteShardKeyRequest, DeleteShardKeyResponse, GetCollectionInfoRequest,
-    GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
+    DeleteShardKeyRequest, DeleteShardKeyResponse, FlushCollectionRequest,
+    GetCollectionInfoRequest, GetCollectionInfoResponse, ListAliasesRequest, ListAliasesResponse,
     ListCollectionAliasesRequest, ListCollectionsRequest, ListCollectionsResponse,
     UpdateCollection, UpdateCollectionClusterSetupRequest, UpdateCollectionClusterSetupResponse,
 };
@@ -282,6 +282,23 @@ impl Collections for CollectionsService {
 
         Ok(Response::new(DeleteShardKeyResponse { result }))
     }
+
+    async fn flush(
+        &self,
+        mut request: Request<FlushCollectionRequest>,
+    ) -> Result<Response<CollectionOperationResponse>, Status> {
+        validate(request.get_ref())?;
+        let timing = Instant::now();
+        let access = extract_access(&mut request);
+        let FlushCollectionRequest { collection_name } = request.into_inner();
+        self.dispatcher
+            .toc(&access)
+            .flush_collection(&collection_name, access)
+            .await?;
+
+        let response = CollectionOperationResponse::from((timing, true));
+        Ok(Response::new(response))
+    }
 }
 
 trait WithTimeout {
@@ -1,12 +1,15 @@
+use std::str::FromStr as _;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
 use api::grpc::qdrant::collections_internal_server::CollectionsInternal;
 use api::grpc::qdrant::{
     CollectionOperationResponse, GetCollectionInfoRequestInternal, GetCollectionInfoResponse,
-    GetShardRecoveryPointRequest, GetShardRecoveryPointResponse, InitiateShardTransferRequest,
-    UpdateShardCutoffPointRequest, WaitForShardStateRequest,
+    GetShardRecoveryPointRequest, GetShardRecoveryPointResponse, HasAppliedSessionRequest,
+    HasAppliedSessionResponse, InitiateShardTransferRequest, UpdateShardCutoffPointRequest,
+    WaitForShardStateRequest,
 };
+use collection::operations::consistency_params::SessionToken;
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::{Access, AccessRequirements, CollectionPass};
 use tonic::{Request, Response, Status};
@@ -205,4 +208,46 @@ impl CollectionsInternal for CollectionsInternalService {
         };
         Ok(Response::new(response))
     }
+
+    async fn has_applied_session(
+        &self,
+        request: Request<HasAppliedSessionRequest>,
+    ) -> Result<Response<HasAppliedSessionResponse>, Status> {
+        validate_and_log(request.get_ref());
+
+        let timing = Instant::now();
+        let HasAppliedSessionRequest {
+            collection_name,
+            shard_id,
+            session_token,
+        } = request.into_inner();
+
+        let session_token = SessionToken::from_str(&session_token)
+            .map_err(|err| Status::invalid_argument(err.to_string()))?;
+
+        let collection_read = self
+            .toc
+            .get_collection(&full_access_pass(&collection_name)?)
+            .await
+            .map_err(|err| {
+                Status::not_found(format!(
+                    "Collection {collection_name} could not be found: {err}"
+                ))
+            })?;
+
+        let applied = collection_read
+            .shard_has_applied_clocks(shard_id, &session_token.shard_clocks(shard_id))
+            .await
+            .map_err(|err| {
+                Status::internal(format!(
+                    "Failed to check session clocks of shard {shard_id}: {err}"
+                ))
+            })?;
+
+        let response = HasAppliedSessionResponse {
+            applied,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
@@ -7,7 +7,9 @@ pub mod points_internal_api;
 pub mod raft_api;
 pub mod snapshots_api;
 
+use api::grpc::with_error_code;
 use collection::operations::validation;
+use segment::common::operation_error::ErrorCode;
 use tonic::Status;
 use validator::Validate;
 
@@ -16,7 +18,10 @@ use validator::Validate;
 /// Returns validation error on failure.
 fn validate(request: &impl Validate) -> Result<(), Status> {
     request.validate().map_err(|ref err| {
-        Status::invalid_argument(validation::label_errors("Validation error in body", err))
+        with_error_code(
+            Status::invalid_argument(validation::label_errors("Validation error in body", err)),
+            ErrorCode::InvalidArgument,
+        )
     })
 }
 
@@ -59,6 +64,15 @@ mod tests {
         assert_eq!(
             validation.message(),
             "Validation error in body: [things[0].idx: value 0 invalid, must be 1 or larger]"
-        )
+        );
+        assert_eq!(
+            validation
+                .metadata()
+                .get(api::grpc::ERROR_CODE_METADATA_KEY)
+                .unwrap()
+                .to_str()
+                .unwrap(),
+            "invalid_argument",
+        );
     }
 }
@@ -5,8 +5,9 @@ use api::grpc::qdrant::points_server::Points;
 use api::grpc::qdrant::{
     ClearPayloadPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
     DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
-    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts,
-    FacetResponse, GetPoints, GetResponse, PointsOperationResponse, QueryBatchPoints,
+    DiscoverBatchPoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
+    ExportPointsRequest, ExportPointsResponse, FacetCounts, FacetResponse, GetPoints, GetResponse,
+    ImportPointsRequest, ImportPointsResponse, PointsOperationResponse, QueryBatchPoints,
     QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
     RecommendBatchPoints, RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups,
     RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchPoints,
@@ -56,6 +57,9 @@ impl Points for PointsService {
             request.into_inner(),
             None,
             None,
+            None,
+            None,
+            Vec::new(),
             access,
         )
         .await
@@ -108,6 +112,7 @@ impl Points for PointsService {
             request.into_inner(),
             None,
             None,
+            Vec::new(),
             access,
         )
         .await
@@ -543,4 +548,61 @@ impl Points for PointsService {
         };
         Ok(Response::new(offsets_response))
     }
+
+    async fn export_points(
+        &self,
+        mut request: Request<ExportPointsRequest>,
+    ) -> Result<Response<ExportPointsResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        let timing = Instant::now();
+        let ExportPointsRequest {
+            collection_name,
+            batch_size,
+        } = request.into_inner();
+        let (dump, exported) = self
+            .dispatcher
+            .toc(&access)
+            .export_points(
+                &collection_name,
+                batch_size.map(|batch_size| batch_size as usize),
+                access,
+            )
+            .await?;
+        let response = ExportPointsResponse {
+            dump,
+            exported: exported as u64,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
+
+    async fn import_points(
+        &self,
+        mut request: Request<ImportPointsRequest>,
+    ) -> Result<Response<ImportPointsResponse>, Status> {
+        validate(request.get_ref())?;
+        let access = extract_access(&mut request);
+        let timing = Instant::now();
+        let ImportPointsRequest {
+            collection_name,
+            dump,
+            batch_size,
+        } = request.into_inner();
+        let imported = self
+            .dispatcher
+            .toc(&access)
+            .import_points(
+                &collection_name,
+                &dump,
+                batch_size.map(|batch_size| batch_size as usize),
+                access,
+            )
+            .await?;
+        let response = ImportPointsResponse {
+            imported: imported as u64,
+            time: timing.elapsed().as_secs_f64(),
+        };
+        Ok(Response::new(response))
+    }
 }
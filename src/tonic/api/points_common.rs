@@ -5,28 +5,33 @@ use api::grpc::conversions::{json_path_from_proto, proto_to_payloads};
 use api::grpc::qdrant::payload_index_params::IndexParams;
 use api::grpc::qdrant::points_update_operation::{ClearPayload, Operation, PointStructList};
 use api::grpc::qdrant::{
-    points_update_operation, BatchResult, ClearPayloadPoints, CoreSearchPoints, CountPoints,
-    CountResponse, CreateFieldIndexCollection, DeleteFieldIndexCollection, DeletePayloadPoints,
-    DeletePointVectors, DeletePoints, DiscoverBatchResponse, DiscoverPoints, DiscoverResponse,
-    FacetCounts, FacetResponse, FieldType, GetPoints, GetResponse, PayloadIndexParams,
-    PointsOperationResponseInternal, PointsSelector, QueryBatchResponse, QueryGroupsResponse,
-    QueryPointGroups, QueryPoints, QueryResponse, ReadConsistency as ReadConsistencyGrpc,
-    RecommendBatchResponse, RecommendGroupsResponse, RecommendPointGroups, RecommendPoints,
-    RecommendResponse, ScrollPoints, ScrollResponse, SearchBatchResponse, SearchGroupsResponse,
-    SearchMatrixPoints, SearchPointGroups, SearchPoints, SearchResponse, SetPayloadPoints,
-    SyncPoints, UpdateBatchPoints, UpdateBatchResponse, UpdatePointVectors, UpsertPoints,
+    points_update_operation, BatchResult, ClearPayloadPoints, CopyPayloadFieldInternal,
+    CoreSearchPoints, CountPoints, CountResponse, CreateFieldIndexCollection,
+    DeleteFieldIndexCollection, DeletePayloadPoints, DeletePointVectors, DeletePoints,
+    DiscoverBatchResponse, DiscoverPoints, DiscoverResponse, FacetCounts, FacetResponse, FieldType,
+    GetPoints, GetResponse, PayloadIndexParams, PointsOperationResponseInternal, PointsSelector,
+    QueryBatchResponse, QueryGroupsResponse, QueryPointGroups, QueryPoints, QueryResponse,
+    ReadConsistency as ReadConsistencyGrpc, RecommendBatchResponse, RecommendGroupsResponse,
+    RecommendPointGroups, RecommendPoints, RecommendResponse, ScrollPoints, ScrollResponse,
+    SearchBatchResponse, SearchGroupsResponse, SearchMatrixPoints, SearchPointGroups, SearchPoints,
+    SearchResponse, SetPayloadPoints, SyncPoints, UpdateBatchPoints, UpdateBatchResponse,
+    UpdatePointVectors, UpsertPoints,
 };
 use api::rest::{OrderByInterface, ShardKeySelector};
+use cancel::CancellationToken;
 use collection::collection::distance_matrix::{
     CollectionSearchMatrixRequest, CollectionSearchMatrixResponse,
 };
 use collection::operations::consistency_params::ReadConsistency;
 use collection::operations::conversions::{
-    try_discover_request_from_grpc, try_points_selector_from_grpc, write_ordering_from_proto,
+    duplicate_ids_policy_from_proto, try_discover_request_from_grpc, try_points_selector_from_grpc,
+    write_consistency_from_proto, write_ordering_from_proto,
 };
-use collection::operations::payload_ops::DeletePayload;
+use collection::operations::idempotency::IdempotencyKey;
+use collection::operations::payload_ops::{CopyPayloadField, DeletePayload, PayloadCopyMode};
 use collection::operations::point_ops::{
-    self, PointInsertOperations, PointOperations, PointSyncOperation, PointsList,
+    self, PointInsertOperations, PointOperations, PointSyncOperation, PointVersion, PointsList,
+    TtlStamp,
 };
 use collection::operations::query_enum::QueryEnum;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
@@ -53,11 +58,12 @@ use storage::rbac::Access;
 use tonic::{Response, Status};
 
 use crate::common::points::{
-    do_clear_payload, do_core_search_points, do_count_points, do_create_index,
-    do_create_index_internal, do_delete_index, do_delete_index_internal, do_delete_payload,
-    do_delete_points, do_delete_vectors, do_get_points, do_overwrite_payload,
-    do_query_batch_points, do_query_point_groups, do_query_points, do_scroll_points,
-    do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points, CreateFieldIndex,
+    do_clear_payload, do_copy_payload_field, do_core_search_points, do_count_points,
+    do_create_index, do_create_index_internal, do_delete_index, do_delete_index_internal,
+    do_delete_payload, do_delete_points, do_delete_vectors, do_get_points, do_merge_patch_payload,
+    do_overwrite_payload, do_query_batch_points, do_query_point_groups, do_query_points,
+    do_scroll_points, do_search_batch_points, do_set_payload, do_update_vectors, do_upsert_points,
+    CreateFieldIndex,
 };
 
 fn extract_points_selector(
@@ -103,11 +109,15 @@ pub(crate) fn convert_shard_selector_for_read(
     }
 }
 
+#[allow(clippy::too_many_arguments)]
 pub async fn upsert(
     toc: Arc<TableOfContent>,
     upsert_points: UpsertPoints,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    ttl_stamp: Option<TtlStamp>,
+    version_timestamp: Option<u64>,
+    versions: Vec<PointVersion>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let UpsertPoints {
@@ -115,15 +125,32 @@ pub async fn upsert(
         wait,
         points,
         ordering,
+        write_consistency,
         shard_key_selector,
+        expected_versions,
+        duplicate_ids,
+        idempotency_key,
+        skip_invalid_points,
     } = upsert_points;
     let points = points
         .into_iter()
         .map(|point| point.try_into())
         .collect::<Result<_, _>>()?;
+    let expected_versions = if expected_versions.is_empty() {
+        None
+    } else {
+        Some(
+            expected_versions
+                .into_iter()
+                .map(PointVersion::try_from)
+                .collect::<Result<_, _>>()?,
+        )
+    };
     let operation = PointInsertOperations::PointsList(PointsList {
         points,
         shard_key: shard_key_selector.map(ShardKeySelector::from),
+        expected_versions,
+        duplicate_ids: duplicate_ids_policy_from_proto(duplicate_ids)?,
     });
     let timing = Instant::now();
     let result = do_upsert_points(
@@ -134,6 +161,12 @@ pub async fn upsert(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
+        idempotency_key.map(IdempotencyKey::new),
+        skip_invalid_points.unwrap_or(false),
+        ttl_stamp,
+        version_timestamp,
+        versions,
         access,
     )
     .await?;
@@ -156,6 +189,7 @@ pub async fn sync(
         from_id,
         to_id,
         ordering,
+        versions,
     } = sync_points;
 
     let points = points
@@ -169,6 +203,10 @@ pub async fn sync(
         points,
         from_id: from_id.map(|x| x.try_into()).transpose()?,
         to_id: to_id.map(|x| x.try_into()).transpose()?,
+        versions: versions
+            .into_iter()
+            .map(PointVersion::try_from)
+            .collect::<Result<_, _>>()?,
     };
     let collection_operation =
         CollectionUpdateOperations::PointOperation(PointOperations::SyncPoints(operation));
@@ -186,6 +224,7 @@ pub async fn sync(
             OperationWithClockTag::new(collection_operation, clock_tag),
             wait.unwrap_or(false),
             write_ordering_from_proto(ordering)?,
+            None,
             shard_selector,
             access,
         )
@@ -207,7 +246,9 @@ pub async fn delete(
         wait,
         points,
         ordering,
+        write_consistency,
         shard_key_selector,
+        idempotency_key,
     } = delete_points;
 
     let points_selector = match points {
@@ -224,6 +265,8 @@ pub async fn delete(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
+        idempotency_key.map(IdempotencyKey::new),
         access,
     )
     .await?;
@@ -237,6 +280,7 @@ pub async fn update_vectors(
     update_point_vectors: UpdatePointVectors,
     clock_tag: Option<ClockTag>,
     shard_selection: Option<ShardId>,
+    delete_vectors: Vec<(ExtendedPointId, String)>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
     let UpdatePointVectors {
@@ -244,7 +288,9 @@ pub async fn update_vectors(
         wait,
         points,
         ordering,
+        write_consistency,
         shard_key_selector,
+        skip_invalid_points,
     } = update_point_vectors;
 
     // Build list of operation points
@@ -278,6 +324,9 @@ pub async fn update_vectors(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
+        skip_invalid_points.unwrap_or(false),
+        delete_vectors,
         access,
     )
     .await?;
@@ -299,6 +348,7 @@ pub async fn delete_vectors(
         points_selector,
         vectors,
         ordering,
+        write_consistency,
         shard_key_selector,
     } = delete_point_vectors;
 
@@ -324,6 +374,7 @@ pub async fn delete_vectors(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
         access,
     )
     .await?;
@@ -339,14 +390,21 @@ pub async fn set_payload(
     shard_selection: Option<ShardId>,
     access: Access,
 ) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    if set_payload_points.merge_patch.unwrap_or(false) {
+        return merge_patch_payload(toc, set_payload_points, clock_tag, shard_selection, access)
+            .await;
+    }
+
     let SetPayloadPoints {
         collection_name,
         wait,
         payload,
         points_selector,
         ordering,
+        write_consistency,
         shard_key_selector,
         key,
+        merge_patch: _,
     } = set_payload_points;
     let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
 
@@ -368,6 +426,110 @@ pub async fn set_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
+        access,
+    )
+    .await?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn merge_patch_payload(
+    toc: Arc<TableOfContent>,
+    set_payload_points: SetPayloadPoints,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let SetPayloadPoints {
+        collection_name,
+        wait,
+        payload,
+        points_selector,
+        ordering,
+        write_consistency,
+        shard_key_selector,
+        key,
+        merge_patch: _,
+    } = set_payload_points;
+    let key = key.map(|k| json_path_from_proto(&k)).transpose()?;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = collection::operations::payload_ops::SetPayload {
+        payload: proto_to_payloads(payload)?,
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+        key,
+    };
+
+    let timing = Instant::now();
+    let result = do_merge_patch_payload(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
+        access,
+    )
+    .await?;
+
+    let response = points_operation_response_internal(timing, result);
+    Ok(Response::new(response))
+}
+
+pub async fn copy_payload_field(
+    toc: Arc<TableOfContent>,
+    set_payload_points: SetPayloadPoints,
+    copy_field: CopyPayloadFieldInternal,
+    clock_tag: Option<ClockTag>,
+    shard_selection: Option<ShardId>,
+    access: Access,
+) -> Result<Response<PointsOperationResponseInternal>, Status> {
+    let SetPayloadPoints {
+        collection_name,
+        wait,
+        points_selector,
+        ordering,
+        write_consistency,
+        shard_key_selector,
+        ..
+    } = set_payload_points;
+
+    let CopyPayloadFieldInternal {
+        from,
+        to,
+        move_field,
+    } = copy_field;
+
+    let (points, filter) = extract_points_selector(points_selector)?;
+    let operation = CopyPayloadField {
+        from: json_path_from_proto(&from)?,
+        to: json_path_from_proto(&to)?,
+        mode: if move_field {
+            PayloadCopyMode::Move
+        } else {
+            PayloadCopyMode::Copy
+        },
+        points,
+        filter,
+        shard_key: shard_key_selector.map(ShardKeySelector::from),
+    };
+
+    let timing = Instant::now();
+    let result = do_copy_payload_field(
+        toc,
+        collection_name,
+        operation,
+        clock_tag,
+        shard_selection,
+        wait.unwrap_or(false),
+        write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
         access,
     )
     .await?;
@@ -389,6 +551,7 @@ pub async fn overwrite_payload(
         payload,
         points_selector,
         ordering,
+        write_consistency,
         shard_key_selector,
         ..
     } = set_payload_points;
@@ -412,6 +575,7 @@ pub async fn overwrite_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
         access,
     )
     .await?;
@@ -433,6 +597,7 @@ pub async fn delete_payload(
         keys,
         points_selector,
         ordering,
+        write_consistency,
         shard_key_selector,
     } = delete_payload_points;
     let keys = keys.iter().map(|k| json_path_from_proto(k)).try_collect()?;
@@ -454,6 +619,7 @@ pub async fn delete_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
         access,
     )
     .await?;
@@ -474,6 +640,7 @@ pub async fn clear_payload(
         wait,
         points,
         ordering,
+        write_consistency,
         shard_key_selector,
     } = clear_payload_points;
 
@@ -491,6 +658,7 @@ pub async fn clear_payload(
         shard_selection,
         wait.unwrap_or(false),
         write_ordering_from_proto(ordering)?,
+        write_consistency_from_proto(write_consistency)?,
         access,
     )
     .await?;
@@ -511,6 +679,7 @@ pub async fn update_batch(
         wait,
         operations,
         ordering,
+        write_consistency,
     } = update_batch_points;
 
     let timing = Instant::now();
@@ -521,10 +690,13 @@ pub async fn update_batch(
             .ok_or(Status::invalid_argument("Operation is missing"))?;
         let collection_name = collection_name.clone();
         let ordering = ordering.clone();
+        let write_consistency = write_consistency.clone();
         let result = match operation {
             points_update_operation::Operation::Upsert(PointStructList {
                 points,
                 shard_key_selector,
+                expected_versions,
+                duplicate_ids,
             }) => {
                 upsert(
                     toc.clone(),
@@ -533,10 +705,18 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
+                        expected_versions,
+                        duplicate_ids,
+                        idempotency_key: None,
+                        skip_invalid_points: None,
                     },
                     clock_tag,
                     shard_selection,
+                    None,
+                    None,
+                    Vec::new(),
                     access.clone(),
                 )
                 .await
@@ -549,7 +729,9 @@ pub async fn update_batch(
                         wait,
                         points: Some(points),
                         ordering,
+                        write_consistency,
                         shard_key_selector: None,
+                        idempotency_key: None,
                     },
                     clock_tag,
                     shard_selection,
@@ -563,6 +745,7 @@ pub async fn update_batch(
                     points_selector,
                     shard_key_selector,
                     key,
+                    merge_patch,
                 },
             ) => {
                 set_payload(
@@ -573,8 +756,10 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
                         key,
+                        merge_patch,
                     },
                     clock_tag,
                     shard_selection,
@@ -598,9 +783,11 @@ pub async fn update_batch(
                         payload,
                         points_selector,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
                         // overwrite operation don't support it
                         key: None,
+                        merge_patch: None,
                     },
                     clock_tag,
                     shard_selection,
@@ -623,6 +810,7 @@ pub async fn update_batch(
                         keys,
                         points_selector,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
                     },
                     clock_tag,
@@ -642,6 +830,7 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
                     },
                     clock_tag,
@@ -663,10 +852,13 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
+                        skip_invalid_points: None,
                     },
                     clock_tag,
                     shard_selection,
+                    Vec::new(),
                     access.clone(),
                 )
                 .await
@@ -686,6 +878,7 @@ pub async fn update_batch(
                         points_selector,
                         vectors,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
                     },
                     clock_tag,
@@ -702,6 +895,7 @@ pub async fn update_batch(
                         wait,
                         points: Some(selector),
                         ordering,
+                        write_consistency,
                         shard_key_selector: None,
                     },
                     clock_tag,
@@ -721,7 +915,9 @@ pub async fn update_batch(
                         wait,
                         points,
                         ordering,
+                        write_consistency,
                         shard_key_selector,
+                        idempotency_key: None,
                     },
                     clock_tag,
                     shard_selection,
@@ -990,6 +1186,10 @@ pub async fn search(
         timeout,
         shard_key_selector,
         sparse_indices,
+        score_modifier,
+        rerank,
+        max_score_threshold,
+        with_distance,
     } = search_points;
 
     let vector_struct =
@@ -1010,6 +1210,10 @@ pub async fn search(
                 .unwrap_or_default(),
         ),
         score_threshold,
+        score_modifier: score_modifier.map(TryInto::try_into).transpose()?,
+        rerank: rerank.map(Into::into),
+        max_score_threshold,
+        with_distance: with_distance.unwrap_or_default(),
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1102,6 +1306,10 @@ pub async fn core_search_list(
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
 
+    // Stop searching once this request is dropped, e.g. because the calling peer cancelled it
+    let cancel = CancellationToken::new();
+    let _cancel_on_drop = cancel.clone().drop_guard();
+
     let scored_points = toc
         .core_search_batch(
             &collection_name,
@@ -1110,6 +1318,7 @@ pub async fn core_search_list(
             shard_selection,
             access,
             timeout,
+            cancel,
         )
         .await?;
 
@@ -1191,6 +1400,7 @@ pub async fn recommend(
         read_consistency,
         timeout,
         shard_key_selector,
+        mmr_lambda,
     } = recommend_points;
 
     let timeout = timeout.map(Duration::from_secs);
@@ -1232,6 +1442,7 @@ pub async fn recommend(
         score_threshold,
         using: using.map(|u| u.into()),
         lookup_from: lookup_from.map(|l| l.into()),
+        mmr_lambda,
     };
 
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1497,11 +1708,15 @@ pub async fn count(
         read_consistency,
         shard_key_selector,
         timeout,
+        consistent,
+        allow_partial,
     } = count_points;
 
     let count_request = collection::operations::types::CountRequestInternal {
         filter: filter.map(|f| f.try_into()).transpose()?,
         exact: exact.unwrap_or_else(default_exact_count),
+        consistent: consistent.unwrap_or(false),
+        allow_partial: allow_partial.unwrap_or(false),
     };
     let timeout = timeout.map(Duration::from_secs);
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1542,6 +1757,7 @@ pub async fn get(
         read_consistency,
         shard_key_selector,
         timeout,
+        with_version,
     } = get_points;
 
     let point_request = PointRequestInternal {
@@ -1553,6 +1769,7 @@ pub async fn get(
         with_vector: with_vectors
             .map(|selector| selector.into())
             .unwrap_or_default(),
+        with_version: with_version.unwrap_or(false),
     };
     let timeout = timeout.map(Duration::from_secs);
     let read_consistency = ReadConsistency::try_from_optional(read_consistency)?;
@@ -1739,10 +1956,16 @@ pub async fn facet(
         )
         .await?;
 
-    let segment::data_types::facets::FacetResponse { hits } = facet_response;
+    let segment::data_types::facets::FacetResponse {
+        hits,
+        other_count,
+        exact,
+    } = facet_response;
 
     let response = FacetResponse {
         hits: hits.into_iter().map(From::from).collect(),
+        other_count: other_count as u64,
+        exact,
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -1763,6 +1986,7 @@ pub async fn search_points_matrix(
         read_consistency,
         shard_key_selector,
         timeout,
+        seed,
     } = search_matrix_points;
 
     let search_matrix_request = CollectionSearchMatrixRequest {
@@ -1778,6 +2002,7 @@ pub async fn search_points_matrix(
             .map_err(|_| Status::invalid_argument("could not parse 'limit' param into usize"))?
             .unwrap_or(CollectionSearchMatrixRequest::DEFAULT_LIMIT_PER_SAMPLE),
         using: using.unwrap_or(DEFAULT_VECTOR_NAME.to_string()),
+        seed,
     };
 
     let timeout = timeout.map(Duration::from_secs);
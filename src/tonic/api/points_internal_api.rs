@@ -7,19 +7,20 @@ use api::grpc::qdrant::{
     ClearPayloadPointsInternal, CoreSearchBatchPointsInternal, CountPointsInternal, CountResponse,
     CreateFieldIndexCollectionInternal, DeleteFieldIndexCollectionInternal,
     DeletePayloadPointsInternal, DeletePointsInternal, DeleteVectorsInternal, FacetCountsInternal,
-    FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult,
+    FacetResponseInternal, GetPointsInternal, GetResponse, IntermediateResult, PointVectorName,
     PointsOperationResponseInternal, QueryBatchPointsInternal, QueryBatchResponseInternal,
     QueryResultInternal, QueryShardPoints, RecommendPointsInternal, RecommendResponse,
     ScrollPointsInternal, ScrollResponse, SearchBatchResponse, SetPayloadPointsInternal,
     SyncPointsInternal, UpdateVectorsInternal, UpsertPointsInternal,
 };
+use collection::operations::point_ops::PointVersion;
 use collection::operations::shard_selector_internal::ShardSelectorInternal;
 use collection::operations::universal_query::shard_query::ShardQueryRequest;
 use collection::shards::shard::ShardId;
 use itertools::Itertools;
 use segment::data_types::facets::{FacetParams, FacetResponse};
 use segment::json_path::JsonPath;
-use segment::types::Filter;
+use segment::types::{Filter, PointIdType};
 use storage::content_manager::toc::TableOfContent;
 use storage::rbac::Access;
 use tonic::{Request, Response, Status};
@@ -27,9 +28,9 @@ use tonic::{Request, Response, Status};
 use super::points_common::core_search_list;
 use super::validate_and_log;
 use crate::tonic::api::points_common::{
-    clear_payload, count, create_field_index_internal, delete, delete_field_index_internal,
-    delete_payload, delete_vectors, get, overwrite_payload, recommend, scroll, set_payload, sync,
-    update_vectors, upsert,
+    clear_payload, copy_payload_field, count, create_field_index_internal, delete,
+    delete_field_index_internal, delete_payload, delete_vectors, get, merge_patch_payload,
+    overwrite_payload, recommend, scroll, set_payload, sync, update_vectors, upsert,
 };
 
 const FULL_ACCESS: Access = Access::full("Internal API");
@@ -126,10 +127,15 @@ async fn facet_counts_internal(
         )
         .await?;
 
-    let FacetResponse { hits } = response;
+    let FacetResponse {
+        hits,
+        other_count,
+        exact: _,
+    } = response;
 
     let response = FacetResponseInternal {
         hits: hits.into_iter().map(From::from).collect_vec(),
+        other_count: other_count as u64,
         time: timing.elapsed().as_secs_f64(),
     };
 
@@ -148,6 +154,9 @@ impl PointsInternal for PointsInternalService {
             upsert_points,
             shard_id,
             clock_tag,
+            ttl_stamp,
+            version_timestamp,
+            versions,
         } = request.into_inner();
 
         let upsert_points =
@@ -158,6 +167,12 @@ impl PointsInternal for PointsInternalService {
             upsert_points,
             clock_tag.map(Into::into),
             shard_id,
+            ttl_stamp.map(Into::into),
+            version_timestamp,
+            versions
+                .into_iter()
+                .map(PointVersion::try_from)
+                .collect::<Result<_, _>>()?,
             FULL_ACCESS.clone(),
         )
         .await
@@ -203,11 +218,21 @@ impl PointsInternal for PointsInternalService {
             .update_vectors
             .ok_or_else(|| Status::invalid_argument("UpdateVectors is missing"))?;
 
+        let delete_vectors = request
+            .delete_vectors
+            .into_iter()
+            .map(|PointVectorName { id, vector_name }| {
+                let id = id.ok_or_else(|| Status::invalid_argument("id is expected"))?;
+                Ok((PointIdType::try_from(id)?, vector_name))
+            })
+            .collect::<Result<_, Status>>()?;
+
         update_vectors(
             self.toc.clone(),
             update_point_vectors,
             clock_tag.map(Into::into),
             shard_id,
+            delete_vectors,
             FULL_ACCESS.clone(),
         )
         .await
@@ -248,11 +273,36 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             shard_id,
             clock_tag,
+            merge_patch,
+            copy_field,
         } = request.into_inner();
 
         let set_payload_points = set_payload_points
             .ok_or_else(|| Status::invalid_argument("SetPayloadPoints is missing"))?;
 
+        if let Some(copy_field) = copy_field {
+            return copy_payload_field(
+                self.toc.clone(),
+                set_payload_points,
+                copy_field,
+                clock_tag.map(Into::into),
+                shard_id,
+                FULL_ACCESS.clone(),
+            )
+            .await;
+        }
+
+        if merge_patch.unwrap_or(false) {
+            return merge_patch_payload(
+                self.toc.clone(),
+                set_payload_points,
+                clock_tag.map(Into::into),
+                shard_id,
+                FULL_ACCESS.clone(),
+            )
+            .await;
+        }
+
         set_payload(
             self.toc.clone(),
             set_payload_points,
@@ -273,6 +323,8 @@ impl PointsInternal for PointsInternalService {
             set_payload_points,
             shard_id,
             clock_tag,
+            merge_patch: _,
+            copy_field: _,
         } = request.into_inner();
 
         let set_payload_points = set_payload_points
@@ -1,3 +1,4 @@
+import base64
 import json
 import tempfile
 from inspect import isfunction
@@ -56,6 +57,9 @@ PEER_ID = 0
 SHARD_KEY = "existing_shard_key"
 FACET_KEY = "a"
 
+# Point dump header without any points
+EMPTY_POINT_DUMP = b"QPDM\x01\x00\x00\x00"
+
 _cached_grpc_clients = None
 
 SHARD_KEY_SELECTOR = {"shard_key_selector": {"shard_keys": [{"keyword": SHARD_KEY}]}}
@@ -247,6 +251,15 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/shards/delete",
         "qdrant.Collections/DeleteShardKey",
     ),
+    ### Flush ###
+    "flush_collection": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/flush",
+        "qdrant.Collections/Flush",
+        coll_rw_payload=False,
+    ),
     ### Payload Indexes ###
     "create_index": EndpointAccess(
         False,
@@ -461,6 +474,28 @@ ACTION_ACCESS = {
         "POST /collections/{collection_name}/points/scroll",
         "qdrant.Points/Scroll",
     ),
+    "scroll_points_stream": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/scroll/stream",
+    ),
+    "export_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/export",
+        "qdrant.Points/ExportPoints",
+        coll_rw_payload=False,
+    ),
+    "import_points": EndpointAccess(
+        False,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/import",
+        "qdrant.Points/ImportPoints",
+        coll_rw_payload=False,
+    ),
     "search_points": EndpointAccess(
         True,
         True,
@@ -506,6 +541,13 @@ ACTION_ACCESS = {
         "qdrant.Points/RecommendGroups",
         coll_rw_payload=False,
     ),
+    "neighbors_points": EndpointAccess(
+        True,
+        True,
+        True,
+        "POST /collections/{collection_name}/points/neighbors",
+        coll_rw_payload=False,
+    ),
     "discover_points": EndpointAccess(
         True,
         True,
@@ -563,6 +605,8 @@ ACTION_ACCESS = {
     "metrics": EndpointAccess(True, False, True, "GET /metrics", coll_r=False),
     "post_locks": EndpointAccess(False, False, True, "POST /locks"),
     "get_locks": EndpointAccess(True, False, True, "GET /locks", coll_r=False),
+    "post_maintenance": EndpointAccess(False, False, True, "POST /maintenance"),
+    "get_maintenance": EndpointAccess(True, False, True, "GET /maintenance", coll_r=False),
     "get_issues": EndpointAccess(False, False, True, "GET /issues"),
     "clear_issues": EndpointAccess(False, False, True, "DELETE /issues"),
 }
@@ -1192,6 +1236,14 @@ def test_delete_shard_key():
     )
 
 
+def test_flush_collection():
+    check_access(
+        "flush_collection",
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME},
+    )
+
+
 def test_create_index():
     check_access(
         "create_index",
@@ -1622,6 +1674,34 @@ def test_scroll_points():
     )
 
 
+def test_scroll_points_stream():
+    check_access(
+        "scroll_points_stream",
+        rest_request={"limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
+def test_export_points():
+    check_access(
+        "export_points",
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={"collection_name": COLL_NAME},
+    )
+
+
+def test_import_points():
+    check_access(
+        "import_points",
+        rest_req_kwargs={"data": EMPTY_POINT_DUMP},
+        path_params={"collection_name": COLL_NAME},
+        grpc_request={
+            "collection_name": COLL_NAME,
+            "dump": base64.b64encode(EMPTY_POINT_DUMP).decode(),
+        },
+    )
+
+
 def test_search_points():
     check_access(
         "search_points",
@@ -1696,6 +1776,14 @@ def test_recommend_point_groups():
     )
 
 
+def test_neighbors_points():
+    check_access(
+        "neighbors_points",
+        rest_request={"id": 1, "limit": 10},
+        path_params={"collection_name": COLL_NAME},
+    )
+
+
 def test_discover_points():
     check_access(
         "discover_points",
@@ -1866,6 +1954,14 @@ def test_get_locks():
     check_access("get_locks")
 
 
+def test_post_maintenance():
+    check_access("post_maintenance", rest_request={"pause_optimizations": False})
+
+
+def test_get_maintenance():
+    check_access("get_maintenance")
+
+
 def test_get_issues():
     check_access("get_issues")
 
@@ -117,6 +117,76 @@ def test_shard_stream_transfer(tmp_path: pathlib.Path):
     assert counts[0] == counts[1] == counts[2]
 
 
+# Replicate shard from one node to another and abort the transfer right away
+#
+# Test that the replica created for the transfer is removed from the receiver,
+# instead of lingering as a dead replica with partial data.
+def test_shard_stream_transfer_abort(tmp_path: pathlib.Path):
+    assert_project_root()
+
+    # seed port to reuse the same port for the restarted nodes
+    peer_api_uris, peer_dirs, bootstrap_uri = start_cluster(tmp_path, N_PEERS, 20000)
+
+    create_collection(peer_api_uris[0], shard_number=N_SHARDS, replication_factor=N_REPLICA)
+    wait_collection_exists_and_active_on_all_peers(
+        collection_name=COLLECTION_NAME,
+        peer_api_uris=peer_api_uris
+    )
+
+    # Insert some initial number of points
+    upsert_random_points(peer_api_uris[0], 10000)
+
+    transfer_collection_cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    receiver_collection_cluster_info = get_collection_cluster_info(peer_api_uris[2], COLLECTION_NAME)
+
+    from_peer_id = transfer_collection_cluster_info['peer_id']
+    to_peer_id = receiver_collection_cluster_info['peer_id']
+
+    shard_id = transfer_collection_cluster_info['local_shards'][0]['shard_id']
+
+    # Replicate shard `shard_id` to peer `target_peer_id`
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "replicate_shard": {
+                "shard_id": shard_id,
+                "from_peer_id": from_peer_id,
+                "to_peer_id": to_peer_id,
+                "method": "stream_records",
+            }
+        })
+    assert_http_ok(r)
+
+    # Abort shard transfer
+    r = requests.post(
+        f"{peer_api_uris[0]}/collections/{COLLECTION_NAME}/cluster", json={
+            "abort_transfer": {
+                "shard_id": shard_id,
+                "from_peer_id": from_peer_id,
+                "to_peer_id": to_peer_id,
+            }
+        })
+    assert_http_ok(r)
+
+    wait_for_collection_shard_transfers_count(peer_api_uris[2], COLLECTION_NAME, 0)
+
+    # Receiver must not keep the replica created for the transfer
+    receiver_collection_cluster_info = get_collection_cluster_info(peer_api_uris[2], COLLECTION_NAME)
+    number_local_shards = len(receiver_collection_cluster_info['local_shards'])
+    assert number_local_shards == 1
+
+    # Source must not know about a replica on the receiver either
+    transfer_collection_cluster_info = get_collection_cluster_info(peer_api_uris[0], COLLECTION_NAME)
+    for remote_shard in transfer_collection_cluster_info['remote_shards']:
+        assert not (remote_shard['shard_id'] == shard_id and remote_shard['peer_id'] == to_peer_id)
+
+    # Source shard must still be active
+    local_shard = next(
+        shard for shard in transfer_collection_cluster_info['local_shards']
+        if shard['shard_id'] == shard_id
+    )
+    assert local_shard['state'] == "Active"
+
+
 # Transfer shards from one node to another while applying throttled updates in parallel
 #
 # Updates are throttled to prevent sending updates faster than the queue proxy
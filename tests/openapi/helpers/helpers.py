@@ -1,4 +1,4 @@
-from typing import Any, Dict, List
+from typing import Any, Dict, List, Optional
 import jsonschema
 import requests
 from schemathesis.models import APIOperation
@@ -86,7 +86,7 @@ def request_with_validation(
 # from client implementation:
 # https://github.com/qdrant/qdrant-client/blob/d18cb1702f4cf8155766c7b32d1e4a68af11cd6a/qdrant_client/hybrid/fusion.py#L6C1-L31C25
 def reciprocal_rank_fusion(
-    responses: List[List[Any]], limit: int = 10
+    responses: List[List[Any]], limit: int = 10, weights: Optional[List[float]] = None
 ) -> List[Any]:
     def compute_score(pos: int) -> float:
         ranking_constant = (
@@ -96,13 +96,14 @@ def reciprocal_rank_fusion(
 
     scores: Dict[Any, float] = {} # id -> score
     point_pile = {}
-    for response in responses:
+    weights = weights or [1.0] * len(responses)
+    for response, weight in zip(responses, weights):
         for i, scored_point in enumerate(response):
             if scored_point["id"] in scores:
-                scores[scored_point["id"]] += compute_score(i)
+                scores[scored_point["id"]] += compute_score(i) * weight
             else:
                 point_pile[scored_point["id"]] = scored_point
-                scores[scored_point["id"]] = compute_score(i)
+                scores[scored_point["id"]] = compute_score(i) * weight
 
     sorted_scores = sorted(scores.items(), key=lambda item: item[1], reverse=True)
     sorted_points = []
@@ -1,7 +1,11 @@
+import json
+
 import pytest
+import requests
 
 from .helpers.collection_setup import basic_collection_setup, drop_collection
 from .helpers.helpers import request_with_validation
+from .helpers.settings import QDRANT_HOST
 
 collection_name = 'test_collection'
 
@@ -387,3 +391,32 @@ def test_with_vectors_alias_of_with_vector():
 
     scroll_with_vector("with_vector")
     scroll_with_vector("with_vectors")
+
+
+def test_points_scroll_stream():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"limit": 1000, "with_payload": True}
+    )
+    assert response.ok
+    expected = response.json()['result']['points']
+
+    # Stream in pages smaller than the collection
+    response = requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/scroll/stream",
+        json={"limit": 3, "with_payload": True},
+        stream=True,
+    )
+    assert response.ok
+    assert response.headers["Content-Type"] == "application/x-ndjson"
+    points = [json.loads(line) for line in response.iter_lines() if line]
+    assert points == expected
+
+    # Ordering by payload has no stable cursor, it is rejected before streaming
+    response = requests.post(
+        f"{QDRANT_HOST}/collections/{collection_name}/points/scroll/stream",
+        json={"limit": 3, "order_by": "price"},
+    )
+    assert response.status_code == 400
@@ -69,3 +69,29 @@ def test_approx_count_search():
     assert response.ok
     assert response.json()['result']['count'] < 10
     assert response.json()['result']['count'] > 0
+
+
+def test_consistent_count():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {
+                "must": [
+                    {
+                        "key": "city",
+                        "match": {
+                            "value": "London"
+                        }
+                    }
+                ]
+            },
+            "consistent": True,
+            "allow_partial": True
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 2
+    # All shards are available, the count is not marked as partial
+    assert 'partial' not in response.json()['result']
@@ -0,0 +1,80 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_defer_indexing'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_defer_indexing(defer_indexing):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "indexing_threshold": 10,
+                "defer_indexing": defer_indexing,
+            },
+        }
+    )
+    assert response.ok
+
+
+def get_collection_info():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_defer_indexing():
+    set_defer_indexing(True)
+
+    info = get_collection_info()
+    assert info['config']['optimizer_config']['defer_indexing'] is True
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 100 + i, "vector": [0.1 * i, 0.2, 0.3, 0.4]}
+                for i in range(100)
+            ]
+        }
+    )
+    assert response.ok
+
+    # Nothing is indexed, but search is still exact
+    info = get_collection_info()
+    assert info['indexed_vectors_count'] == 0
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [1.0, 0.0, 0.0, 0.0],
+            "limit": 3,
+        }
+    )
+    assert response.ok
+    assert [point['id'] for point in response.json()['result']] == [199, 198, 197]
+
+    set_defer_indexing(False)
+
+    info = get_collection_info()
+    assert info['config']['optimizer_config']['defer_indexing'] is False
@@ -392,3 +392,58 @@ def test_discovery_lookup():
 
     # check if results are the same
     assert discovery_result_by_id == discovery_result_by_vector, f"discovery_result_by_id: {discovery_result_by_id}, discovery_result_by_vector: {discovery_result_by_vector}"
+
+
+# A weighted context counts the same as repeating its pairs in the unweighted context
+@pytest.mark.parametrize("with_target", [True, False])
+def test_weighted_context(with_target):
+    target = random_vector() if with_target else None
+    pair = {"positive": random_vector(), "negative": random_vector()}
+    weighted_pair = {"positive": random_vector(), "negative": random_vector()}
+
+    def discover(body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/discover",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "target": target,
+                "limit": 8,
+                "params": {"exact": True},
+                **body,
+            },
+        )
+        assert response.ok, response.json()
+        return response.json()["result"]
+
+    weighted = discover({
+        "context": [pair],
+        "weighted_context": [{"pairs": [weighted_pair], "weight": 3}],
+    })
+    repeated = discover({
+        "context": [pair, weighted_pair, weighted_pair, weighted_pair],
+    })
+
+    assert len(weighted) == len(repeated) == 8
+    for weighted_point, repeated_point in zip(weighted, repeated):
+        assert math.isclose(weighted_point["score"], repeated_point["score"], rel_tol=1e-5, abs_tol=1e-5)
+
+
+def test_weighted_context_only():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/discover",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "weighted_context": [
+                {"pairs": [{"positive": random_vector(), "negative": random_vector()}], "weight": 2},
+            ],
+            "limit": 8,
+        },
+    )
+    assert response.ok, response.json()
+    assert len(response.json()["result"]) == 8
+
+    # Loss of each pair is multiplied by its weight
+    for point in response.json()["result"]:
+        assert -2.0 < point["score"] <= 0.0
@@ -0,0 +1,111 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_duplicate_ids'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={"vectors": {"size": 2, "distance": "Dot"}},
+    )
+    assert response.ok
+
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def upsert(body):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body=body,
+    )
+
+
+def get_point(point_id):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/{id}',
+        method="GET",
+        path_params={'collection_name': collection_name, 'id': point_id},
+    )
+    return response
+
+
+def count():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    return response.json()['result']['count']
+
+
+def test_last_wins_by_default():
+    response = upsert({
+        "points": [
+            {"id": 1, "vector": [1.0, 0.0], "payload": {"n": 1}},
+            {"id": 2, "vector": [0.0, 1.0], "payload": {"n": 2}},
+            {"id": 1, "vector": [1.0, 1.0], "payload": {"n": 3}},
+        ]
+    })
+    assert response.ok
+    assert count() == 2
+
+    response = get_point(1)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"n": 3}
+
+
+def test_first_wins():
+    response = upsert({
+        "batch": {
+            "ids": [1, 2, 1],
+            "vectors": [[1.0, 0.0], [0.0, 1.0], [1.0, 1.0]],
+            "payloads": [{"n": 1}, {"n": 2}, {"n": 3}],
+        },
+        "duplicate_ids": "first_wins",
+    })
+    assert response.ok
+    assert count() == 2
+
+    response = get_point(1)
+    assert response.ok
+    assert response.json()['result']['payload'] == {"n": 1}
+
+
+def test_error():
+    response = upsert({
+        "points": [
+            {"id": 1, "vector": [1.0, 0.0]},
+            {"id": 2, "vector": [0.0, 1.0]},
+            {"id": 1, "vector": [1.0, 1.0]},
+        ],
+        "duplicate_ids": "error",
+    })
+    assert response.status_code == 400
+    assert "occurs more than once" in response.json()['status']['error']
+
+    # Nothing is upserted
+    assert count() == 0
+
+    response = upsert({
+        "points": [
+            {"id": 1, "vector": [1.0, 0.0]},
+            {"id": 2, "vector": [0.0, 1.0]},
+        ],
+        "duplicate_ids": "error",
+    })
+    assert response.ok
+    assert count() == 2
@@ -45,5 +45,32 @@ def test_basic_facet():
             {"value": "Berlin", "count": 3 },
             {"value": "London", "count": 2 },
             {"value": "Moscow", "count": 2 },
-        ]
+        ],
+        "other_count": 0,
+        "exact": False,
+    }
+
+
+def test_facet_other_count():
+    response = request_with_validation(
+        api="/collections/{collection_name}/facet",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "key": "city",
+            "limit": 1,
+            "exact": True,
+        }
+    )
+
+    assert response.ok, response.json()
+
+    city_facet = response.json()["result"]
+    assert city_facet == {
+        "hits": [
+            {"value": "Berlin", "count": 3 },
+        ],
+        # Counts of London and Moscow
+        "other_count": 4,
+        "exact": True,
     }
@@ -0,0 +1,78 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_has_field'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def scroll_ids(condition):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/scroll',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": condition,
+            "limit": 100,
+        }
+    )
+    assert response.ok
+    return sorted(point['id'] for point in response.json()['result']['points'])
+
+
+def check_has_field():
+    # Missing, `null` and empty array values are not considered present
+    assert scroll_ids({"must": [{"has_field": {"key": "city"}}]}) == [1, 2, 3, 4]
+    assert scroll_ids({"must_not": [{"has_field": {"key": "city"}}]}) == [5, 6, 7, 8]
+    assert scroll_ids({"must_not": [{"is_empty": {"key": "city"}}]}) == [1, 2, 3, 4]
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "filter": {"must": [{"has_field": {"key": "city"}}]},
+            "exact": True,
+        }
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 4
+
+
+def test_has_field_condition():
+    check_has_field()
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/index',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "field_name": "city",
+            "field_schema": "keyword"
+        }
+    )
+    assert response.ok
+
+    check_has_field()
+
+    # Presence is maintained on payload updates of an indexed field
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/payload',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "payload": {"city": None},
+            "points": [1],
+        }
+    )
+    assert response.ok
+    assert scroll_ids({"must": [{"has_field": {"key": "city"}}]}) == [2, 3, 4]
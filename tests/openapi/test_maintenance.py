@@ -0,0 +1,61 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def set_optimizations_paused(paused):
+    response = request_with_validation(
+        api='/maintenance',
+        method="POST",
+        body={"pause_optimizations": paused}
+    )
+    assert response.ok
+    return response.json()['result']['pause_optimizations']
+
+
+def test_pause_optimizations():
+    assert not set_optimizations_paused(True)
+
+    response = request_with_validation(
+        api='/maintenance',
+        method="GET",
+    )
+    assert response.ok
+    assert response.json()['result']['pause_optimizations']
+
+    # Updates are still accepted while optimizations are paused
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 10,
+                    "vector": [0.05, -0.61, -0.76, 0.74],
+                    "payload": {"city": "Gdansk"}
+                }
+            ]
+        }
+    )
+    assert response.ok
+
+    assert set_optimizations_paused(False)
+
+    response = request_with_validation(
+        api='/maintenance',
+        method="GET",
+    )
+    assert response.ok
+    assert not response.json()['result']['pause_optimizations']
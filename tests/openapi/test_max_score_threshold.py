@@ -0,0 +1,58 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_max_threshold'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "params": {"exact": True},
+            **body,
+        }
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_search_with_max_threshold():
+    result = search({"limit": 4})
+    assert len(result) == 4
+    ids = [point['id'] for point in result]
+    second_score = result[1]['score']
+
+    # Excluded points are replaced by the next ones, so the limit is still filled
+    result = search({"limit": 3, "max_score_threshold": second_score - 0.0001})
+    assert len(result) == 3
+    assert [point['id'] for point in result][:2] == ids[2:]
+    assert all(point['score'] < second_score for point in result)
+
+    # Points with a score equal to the threshold are returned
+    result = search({"limit": 3, "max_score_threshold": second_score})
+    assert [point['id'] for point in result] == ids[1:]
+
+
+def test_search_with_score_range():
+    result = search({"limit": 3})
+    first_score = result[0]['score']
+    third_score = result[2]['score']
+
+    result = search({
+        "limit": 3,
+        "max_score_threshold": first_score - 0.0001,
+        "score_threshold": third_score - 0.0001,
+    })
+    assert [point['id'] for point in result] == [point['id'] for point in search({"limit": 3})][1:]
@@ -0,0 +1,66 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_min_segment_size'
+
+
+@pytest.fixture(autouse=True)
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def get_collection_info():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_min_segment_size():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "optimizers_config": {
+                "min_segment_size": 1000,
+                "max_flush_delay_sec": 30,
+            },
+        }
+    )
+    assert response.ok
+
+    info = get_collection_info()
+    assert info['config']['optimizer_config']['min_segment_size'] == 1000
+    assert info['config']['optimizer_config']['max_flush_delay_sec'] == 30
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {"id": 100 + i, "vector": [0.1 * i, 0.2, 0.3, 0.4]}
+                for i in range(10)
+            ]
+        }
+    )
+    assert response.ok
+
+    # Buffered writes are visible before they are flushed
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/count',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"exact": True},
+    )
+    assert response.ok
+    assert response.json()['result']['count'] == 20
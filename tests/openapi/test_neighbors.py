@@ -0,0 +1,77 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = "test_neighbors"
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def neighbors(body):
+    return request_with_validation(
+        api="/collections/{collection_name}/points/neighbors",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body=body,
+    )
+
+
+def test_neighbors_match_search_by_vector():
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/{id}",
+        method="GET",
+        path_params={"collection_name": collection_name, "id": 2},
+    )
+    assert response.ok
+    vector = response.json()["result"]["vector"]
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/search",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={"vector": vector, "limit": 10, "params": {"exact": True}},
+    )
+    assert response.ok
+    search_ids = [point["id"] for point in response.json()["result"]]
+
+    response = neighbors({"id": 2, "limit": 10, "params": {"exact": True}})
+    assert response.ok
+    neighbor_ids = [point["id"] for point in response.json()["result"]]
+
+    assert 2 not in neighbor_ids
+    assert neighbor_ids == [point_id for point_id in search_ids if point_id != 2]
+
+    response = neighbors({"id": 2, "limit": 10, "params": {"exact": True}, "include_self": True})
+    assert response.ok
+    assert [point["id"] for point in response.json()["result"]] == search_ids
+
+
+def test_neighbors_with_filter():
+    response = neighbors({
+        "id": 2,
+        "limit": 10,
+        "filter": {"must": [{"key": "city", "match": {"value": "London"}}]},
+    })
+    assert response.ok
+
+    for point in response.json()["result"]:
+        assert point["id"] != 2
+
+    response = neighbors({"id": 2, "limit": 1})
+    assert response.ok
+    assert len(response.json()["result"]) == 1
+
+
+def test_neighbors_errors():
+    response = neighbors({"id": 1000, "limit": 10})
+    assert response.status_code == 404
+
+    response = neighbors({"id": 2, "limit": 10, "using": "missing"})
+    assert response.status_code == 400
+    assert "missing" in response.json()["status"]["error"]
@@ -343,6 +343,10 @@ def test_payload_schemas():
             "is_tenant": True,
             "on_disk": True,
         },
+        {
+            "type": "keyword",
+            "hnsw_partitions": ["en", "de"],
+        },
     ]
 
     try:
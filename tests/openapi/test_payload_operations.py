@@ -589,3 +589,61 @@ def test_payload_index_overwrite():
     )
     assert response.ok
     assert len(response.json()["result"]["points"]) == 1
+
+
+def test_copy_payload_field():
+    def get_payload(point_id):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/{id}",
+            method="GET",
+            path_params={"collection_name": collection_name, "id": point_id},
+        )
+        assert response.ok
+        return response.json()["result"]["payload"]
+
+    # Rename a field, point 5 has no `city` and is skipped
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/payload/copy",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "from": "city",
+            "to": "location.city",
+            "mode": "move",
+            "filter": {"must": [{"has_id": [1, 5]}]},
+        },
+    )
+    assert response.ok
+
+    assert get_payload(1) == {"price": 10.0, "location": {"city": "Berlin"}}
+    assert get_payload(5) == {"count": 0}
+
+    # Copy keeps the source field
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/payload/copy",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        query_params={"wait": "true"},
+        body={
+            "from": "price",
+            "to": "old_price",
+            "points": [1],
+        },
+    )
+    assert response.ok
+
+    assert get_payload(1) == {"price": 10.0, "old_price": 10.0, "location": {"city": "Berlin"}}
+
+    # Values in arrays can't be copied
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/payload/copy",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "from": "city[0]",
+            "to": "first_city",
+            "points": [2],
+        },
+    )
+    assert response.status_code == 422
@@ -352,6 +352,31 @@ def test_basic_random_query():
     assert len(ids_lists) > 1
 
 
+def test_seeded_random_query():
+    ids_lists = set()
+    for _ in range(4):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/query",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "query": { "sample": "random" },
+                "limit": 5,
+                "seed": 42,
+            },
+        )
+        assert response.ok, response.text
+
+        points = response.json()["result"]["points"]
+        assert len(points) == 5
+
+        ids = str([point["id"] for point in points])
+        ids_lists.add(ids)
+
+    # the same seed gives the same sample, in the same order
+    assert len(ids_lists) == 1
+
+
 def test_basic_rrf():
     response = request_with_validation(
         api="/collections/{collection_name}/points/search",
@@ -402,7 +427,47 @@ def test_basic_rrf():
         assert expected["id"] == result["id"]
         assert expected.get("payload") == result.get("payload")
         assert isclose(expected["score"], result["score"], rel_tol=1e-5)
-        
+
+
+def test_weighted_rrf():
+    search_results = []
+    for vector in [[0.1, 0.2, 0.3, 0.4], [0.5, 0.6, 0.7, 0.8]]:
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/search",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body={
+                "vector": vector,
+                "limit": 10,
+            },
+        )
+        assert response.ok
+        search_results.append(response.json()["result"])
+
+    rrf_expected = reciprocal_rank_fusion(search_results, limit=10, weights=[3.0, 0.5])
+
+    response = request_with_validation(
+        api="/collections/{collection_name}/points/query",
+        method="POST",
+        path_params={"collection_name": collection_name},
+        body={
+            "prefetch": [
+                { "query": [0.1, 0.2, 0.3, 0.4], "weight": 3.0 },
+                { "query": [0.5, 0.6, 0.7, 0.8], "weight": 0.5 },
+            ],
+            "query": {"fusion": "rrf"},
+        },
+    )
+    assert response.ok, response.json()
+    rrf_result = response.json()["result"]["points"]
+
+    def get_id(x):
+        return x["id"]
+
+    for expected, result in zip(sorted(rrf_expected, key=get_id), sorted(rrf_result, key=get_id)):
+        assert expected["id"] == result["id"]
+        assert isclose(expected["score"], result["score"], rel_tol=1e-5)
+
 
 def test_basic_dbsf():
     response = request_with_validation(
@@ -263,3 +263,38 @@ def test_raw_vectors():
     assert len(response_raw.json()["result"]) == 4
 
     assert response_ids.json()["result"] == response_raw.json()["result"]
+
+
+def test_mmr_lambda():
+    params = {
+        "positive": [1, 2],
+        "limit": 4,
+        "params": {"exact": True},
+    }
+
+    def recommend(body):
+        response = request_with_validation(
+            api="/collections/{collection_name}/points/recommend",
+            method="POST",
+            path_params={"collection_name": collection_name},
+            body=body,
+        )
+        assert response.ok, response.text
+        return response.json()["result"]
+
+    default_result = recommend(params)
+
+    # lambda = 1 is pure relevance
+    assert recommend({**params, "mmr_lambda": 1.0}) == default_result
+
+    # The most relevant point comes first, the rest are picked for diversity
+    diverse_result = recommend({**params, "mmr_lambda": 0.0})
+    assert len(diverse_result) == len(default_result)
+    assert diverse_result[0] == default_result[0]
+    assert all(point["id"] not in (1, 2) for point in diverse_result)
+
+    # Vectors used for diversity are not returned unless requested
+    assert all(point.get("vector") is None for point in diverse_result)
+    diverse_result = recommend({**params, "mmr_lambda": 0.5, "with_vector": True, "with_payload": True})
+    assert all(point["vector"] is not None for point in diverse_result)
+    assert all(point["payload"] is not None for point in diverse_result)
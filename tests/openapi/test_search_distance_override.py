@@ -0,0 +1,98 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_search_distance_override'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "dot": {"size": 2, "distance": "Dot"},
+            },
+            "sparse_vectors": {
+                "sparse": {},
+            },
+        }
+    )
+    assert response.ok
+
+    vectors = {1: [3.0, 4.0], 2: [0.0, 1.0], 3: [-1.0, 0.0]}
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": point_id,
+                    "vector": {
+                        "dot": vector,
+                        "sparse": {"indices": [0, 1], "values": vector},
+                    },
+                }
+                for point_id, vector in vectors.items()
+            ]
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(vector, distance):
+    return request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": vector,
+            "limit": 3,
+            "params": {"distance": distance},
+        }
+    )
+
+
+def test_configured_distance():
+    response = search({"name": "dot", "vector": [0.0, 1.0]}, None)
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [1, 2, 3]
+    assert result[0]['score'] == pytest.approx(4.0)
+
+
+def test_euclid_override():
+    response = search({"name": "dot", "vector": [0.0, 1.0]}, "Euclid")
+    assert response.ok
+    result = response.json()['result']
+    # Smaller distances are better
+    assert [point['id'] for point in result] == [2, 3, 1]
+    assert result[1]['score'] == pytest.approx(2 ** 0.5)
+    assert result[2]['score'] == pytest.approx(18 ** 0.5)
+
+
+def test_cosine_override_normalizes_stored_vectors():
+    response = search({"name": "dot", "vector": [0.0, 2.0]}, "Cosine")
+    assert response.ok
+    result = response.json()['result']
+    assert [point['id'] for point in result] == [2, 1, 3]
+    assert result[0]['score'] == pytest.approx(1.0)
+    assert result[1]['score'] == pytest.approx(0.8)
+
+
+def test_sparse_override_is_rejected():
+    response = search(
+        {"name": "sparse", "vector": {"indices": [1], "values": [1.0]}},
+        "Euclid",
+    )
+    assert response.status_code == 400
+    assert "sparse vector" in response.json()['status']['error']
@@ -0,0 +1,63 @@
+import pytest
+
+from .helpers.collection_setup import basic_collection_setup, drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_search_provenance'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup(on_disk_vectors):
+    basic_collection_setup(collection_name=collection_name, on_disk_vectors=on_disk_vectors)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_search_provenance():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+            "params": {"provenance": True},
+        }
+    )
+    assert response.ok
+    result = response.json()['result']
+    assert len(result) == 3
+    for point in result:
+        assert point['provenance']['shard_id'] == 0
+        assert point['provenance']['segment_id'] >= 0
+
+
+def test_query_provenance():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/query',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "query": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+            "params": {"provenance": True},
+        }
+    )
+    assert response.ok
+    for point in response.json()['result']['points']:
+        assert point['provenance']['shard_id'] == 0
+
+
+def test_no_provenance_by_default():
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": [0.2, 0.1, 0.9, 0.7],
+            "limit": 3,
+        }
+    )
+    assert response.ok
+    for point in response.json()['result']:
+        assert 'provenance' not in point
@@ -0,0 +1,92 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_search_with_distance'
+
+
+@pytest.fixture(autouse=True, scope="module")
+def setup():
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "euclid": {"size": 2, "distance": "Euclid"},
+                "cosine": {"size": 2, "distance": "Cosine"},
+                "dot": {"size": 2, "distance": "Dot"},
+            },
+        }
+    )
+    assert response.ok
+
+    vectors = {1: [3.0, 4.0], 2: [0.0, 1.0], 3: [-1.0, 0.0]}
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": point_id,
+                    "vector": {"euclid": vector, "cosine": vector, "dot": vector},
+                }
+                for point_id, vector in vectors.items()
+            ]
+        }
+    )
+    assert response.ok
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def search(vector_name, body):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {"name": vector_name, "vector": [0.0, 1.0]},
+            "limit": 3,
+            **body,
+        }
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+def test_distance_not_returned_by_default():
+    for point in search("euclid", {}):
+        assert 'distance' not in point
+
+    for point in search("euclid", {"with_distance": False}):
+        assert 'distance' not in point
+
+
+def test_euclid_distance():
+    result = search("euclid", {"with_distance": True})
+    assert [point['id'] for point in result] == [2, 3, 1]
+    # Euclid scores already are distances
+    for point in result:
+        assert point['distance'] == pytest.approx(point['score'])
+    assert result[1]['distance'] == pytest.approx(2 ** 0.5)
+
+
+def test_cosine_distance():
+    result = search("cosine", {"with_distance": True})
+    assert [point['id'] for point in result] == [2, 1, 3]
+    for point in result:
+        assert point['distance'] == pytest.approx(1 - point['score'])
+    assert result[1]['distance'] == pytest.approx(0.2)
+
+
+def test_dot_distance():
+    result = search("dot", {"with_distance": True})
+    assert [point['id'] for point in result] == [1, 2, 3]
+    for point in result:
+        assert point['distance'] == pytest.approx(-point['score'])
@@ -27,6 +27,10 @@ def test_metrics():
     assert 'app_info{name="qdrant",version="' in response.text
     assert 'collections_total ' in response.text
 
+    # Collection and shard metrics are labeled with the collection name
+    assert f'collection_points{{collection="{collection_name}"}} ' in response.text
+    assert f'collection_shard_wal_lag{{collection="{collection_name}",shard="0"}} ' in response.text
+
 
 def test_telemetry():
     response = request_with_validation(
@@ -0,0 +1,141 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_sparse_distance'
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    sparse_distance_collection_setup(collection_name=collection_name)
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def binary_vector(indices):
+    return {
+        "indices": indices,
+        "values": [1.] * len(indices)
+    }
+
+
+def sparse_distance_collection_setup(
+        collection_name='test_collection',
+):
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="DELETE",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "cosine": {
+                    "distance": "Cosine"
+                },
+                "jaccard": {
+                    "distance": "Jaccard"
+                },
+            }
+        }
+    )
+    assert response.ok
+
+    response = request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={
+            "points": [
+                {
+                    "id": 1,
+                    "vector": {
+                        "cosine": {"indices": [1, 2], "values": [3., 4.]},
+                        "jaccard": binary_vector([1, 2, 3, 4]),
+                    },
+                },
+                {
+                    "id": 2,
+                    "vector": {
+                        "cosine": {"indices": [1, 3], "values": [1., 1.]},
+                        "jaccard": binary_vector([1, 2]),
+                    },
+                },
+                {
+                    "id": 3,
+                    "vector": {
+                        "cosine": {"indices": [3], "values": [5.]},
+                        "jaccard": binary_vector([5, 6]),
+                    },
+                },
+            ]
+        }
+    )
+    assert response.ok
+
+
+def search(vector_name, vector):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={
+            "vector": {
+                "name": vector_name,
+                "vector": vector,
+            },
+            "limit": 10,
+        }
+    )
+    assert response.ok
+    return [(point['id'], point['score']) for point in response.json()['result']]
+
+
+def test_sparse_cosine():
+    result = search("cosine", {"indices": [1, 2], "values": [6., 8.]})
+
+    # Cosine doesn't depend on the length of the vectors
+    assert [point_id for point_id, _ in result] == [1, 2]
+    assert result[0][1] == pytest.approx(1.0)
+    assert result[1][1] == pytest.approx(0.6 / 2 ** 0.5)
+
+
+def test_sparse_jaccard():
+    result = search("jaccard", binary_vector([1, 2, 3]))
+
+    # Intersection over union of the indices, points without common indices are not found
+    assert [point_id for point_id, _ in result] == [1, 2]
+    assert result[0][1] == pytest.approx(3 / 4)
+    assert result[1][1] == pytest.approx(2 / 3)
+
+
+def test_sparse_distance_is_immutable():
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PATCH",
+        path_params={'collection_name': collection_name},
+        body={
+            "sparse_vectors": {
+                "jaccard": {
+                    "distance": "Dot",
+                },
+            },
+        }
+    )
+    assert response.status_code == 400
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="GET",
+        path_params={'collection_name': collection_name},
+    )
+    assert response.ok
+    sparse_vectors = response.json()['result']['config']['params']['sparse_vectors']
+    assert sparse_vectors['jaccard']['distance'] == "Jaccard"
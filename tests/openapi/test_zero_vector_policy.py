@@ -0,0 +1,111 @@
+import pytest
+
+from .helpers.collection_setup import drop_collection
+from .helpers.helpers import request_with_validation
+
+collection_name = 'test_collection_zero_vector_policy'
+
+
+def zero_vector_collection_setup(collection_name, zero_vector_policy):
+    drop_collection(collection_name=collection_name)
+
+    response = request_with_validation(
+        api='/collections/{collection_name}',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        body={
+            "vectors": {
+                "size": 2,
+                "distance": "Cosine",
+                "zero_vector_policy": zero_vector_policy,
+            }
+        }
+    )
+    assert response.ok
+
+
+def upsert(collection_name, points):
+    return request_with_validation(
+        api='/collections/{collection_name}/points',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": points},
+    )
+
+
+def search(collection_name, vector):
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/search',
+        method="POST",
+        path_params={'collection_name': collection_name},
+        body={"vector": vector, "limit": 10},
+    )
+    assert response.ok
+    return response.json()['result']
+
+
+@pytest.fixture(autouse=True)
+def setup():
+    yield
+    drop_collection(collection_name=collection_name)
+
+
+def test_score_zero():
+    zero_vector_collection_setup(collection_name, "score_zero")
+
+    response = upsert(collection_name, [
+        {"id": 1, "vector": [0.0, 0.0]},
+        {"id": 2, "vector": [1.0, 0.0]},
+    ])
+    assert response.ok
+
+    result = search(collection_name, [1.0, 0.0])
+    assert [point['id'] for point in result] == [2, 1]
+    assert result[1]['score'] == 0.0
+
+    result = search(collection_name, [0.0, 0.0])
+    assert len(result) == 2
+    assert all(point['score'] == 0.0 for point in result)
+
+
+def test_exclude():
+    zero_vector_collection_setup(collection_name, "exclude")
+
+    response = upsert(collection_name, [
+        {"id": 1, "vector": [0.0, 0.0]},
+        {"id": 2, "vector": [1.0, 0.0]},
+        {"id": 3, "vector": [0.0, 1.0]},
+    ])
+    assert response.ok
+
+    # Updating a vector to zero removes it
+    response = request_with_validation(
+        api='/collections/{collection_name}/points/vectors',
+        method="PUT",
+        path_params={'collection_name': collection_name},
+        query_params={'wait': 'true'},
+        body={"points": [{"id": 3, "vector": [0.0, 0.0]}]},
+    )
+    assert response.ok
+
+    result = search(collection_name, [1.0, 0.0])
+    assert [point['id'] for point in result] == [2]
+
+    assert search(collection_name, [0.0, 0.0]) == []
+
+
+def test_reject():
+    zero_vector_collection_setup(collection_name, "reject")
+
+    response = upsert(collection_name, [
+        {"id": 1, "vector": [0.0, 0.0]},
+        {"id": 2, "vector": [1.0, 0.0]},
+    ])
+    assert response.status_code == 400
+    assert "Zero vector error" in response.json()["status"]["error"]
+
+    response = upsert(collection_name, [{"id": 2, "vector": [1.0, 0.0]}])
+    assert response.ok
+
+    assert search(collection_name, [0.0, 0.0]) == []